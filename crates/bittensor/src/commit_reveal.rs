@@ -0,0 +1,220 @@
+//! # Commit-Reveal Weights
+//!
+//! Helpers for subnets that require weights to be committed as a hash first
+//! and revealed in a later epoch. The commit hash matches the one subtensor
+//! computes on reveal, so the uids, values, salt and version key must be kept
+//! exactly as committed until the reveal is submitted.
+
+use crate::utils::NormalizedWeight;
+use crate::AccountId;
+use serde::{Deserialize, Serialize};
+use subxt::ext::codec::Encode;
+use subxt::ext::sp_core::hashing::blake2_256;
+use subxt::utils::H256;
+
+/// Number of u16 words in a generated salt
+const SALT_LENGTH: usize = 8;
+
+/// Commit-reveal settings of a subnet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitRevealParams {
+    /// Whether the subnet accepts committed weights
+    pub enabled: bool,
+    /// Blocks per epoch, excluding the epoch block itself
+    pub tempo: u16,
+    /// Epochs between a commit and its reveal
+    pub reveal_period_epochs: u64,
+}
+
+impl CommitRevealParams {
+    /// Epoch a block belongs to, as subtensor counts them for a subnet
+    pub fn epoch_index(&self, netuid: u16, block: u64) -> u64 {
+        (block + netuid as u64 + 1) / (self.tempo as u64 + 1)
+    }
+
+    /// First block of an epoch
+    pub fn epoch_start_block(&self, netuid: u16, epoch: u64) -> u64 {
+        (epoch * (self.tempo as u64 + 1)).saturating_sub(netuid as u64 + 1)
+    }
+
+    /// Inclusive block range in which a commit made at `commit_block` can be revealed
+    ///
+    /// Reveals are only accepted during the epoch `reveal_period_epochs` after
+    /// the commit; after that the commit expires.
+    pub fn reveal_window(&self, netuid: u16, commit_block: u64) -> (u64, u64) {
+        let reveal_epoch = self.epoch_index(netuid, commit_block) + self.reveal_period_epochs;
+        let start = self.epoch_start_block(netuid, reveal_epoch);
+        let end = self
+            .epoch_start_block(netuid, reveal_epoch + 1)
+            .saturating_sub(1);
+        (start, end)
+    }
+}
+
+/// Weights committed to the chain and everything needed to reveal them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeightCommit {
+    pub netuid: u16,
+    pub uids: Vec<u16>,
+    pub values: Vec<u16>,
+    pub salt: Vec<u16>,
+    pub version_key: u64,
+    /// Hex-encoded commit hash, without `0x` prefix
+    pub commit_hash: String,
+    /// Block that included the commit
+    pub commit_block: u64,
+}
+
+impl WeightCommit {
+    /// Prepare a commit of weights with a fresh salt
+    pub fn new(
+        account_id: &AccountId,
+        netuid: u16,
+        weights: &[NormalizedWeight],
+        version_key: u64,
+        commit_block: u64,
+    ) -> Self {
+        let (uids, values): (Vec<u16>, Vec<u16>) =
+            weights.iter().map(|w| (w.uid, w.weight)).unzip();
+        let salt = generate_salt();
+        let commit_hash =
+            weight_commit_hash(account_id, netuid, &uids, &values, &salt, version_key);
+
+        Self {
+            netuid,
+            uids,
+            values,
+            salt,
+            version_key,
+            commit_hash: hex::encode(commit_hash.as_bytes()),
+            commit_block,
+        }
+    }
+
+    /// Commit hash as submitted to the chain
+    pub fn hash(&self) -> Result<H256, hex::FromHexError> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(&self.commit_hash, &mut bytes)?;
+        Ok(H256::from(bytes))
+    }
+}
+
+/// Generate a random salt for a weight commit
+pub fn generate_salt() -> Vec<u16> {
+    (0..SALT_LENGTH).map(|_| rand::random::<u16>()).collect()
+}
+
+/// Hash that subtensor expects for committed weights
+///
+/// This is the blake2-256 hash of the SCALE-encoded tuple
+/// `(hotkey, netuid, uids, values, salt, version_key)`.
+pub fn weight_commit_hash(
+    account_id: &AccountId,
+    netuid: u16,
+    uids: &[u16],
+    values: &[u16],
+    salt: &[u16],
+    version_key: u64,
+) -> H256 {
+    let encoded = (
+        account_id.0,
+        netuid,
+        uids.to_vec(),
+        values.to_vec(),
+        salt.to_vec(),
+        version_key,
+    )
+        .encode();
+    H256::from(blake2_256(&encoded))
+}
+
+/// Create a commit_weights payload
+pub fn commit_weights_payload(netuid: u16, commit_hash: H256) -> impl subxt::tx::Payload {
+    use crate::api::api;
+
+    api::tx()
+        .subtensor_module()
+        .commit_weights(netuid, commit_hash)
+}
+
+/// Create a reveal_weights payload for a previous commit
+pub fn reveal_weights_payload(commit: &WeightCommit) -> impl subxt::tx::Payload {
+    use crate::api::api;
+
+    api::tx().subtensor_module().reveal_weights(
+        commit.netuid,
+        commit.uids.clone(),
+        commit.values.clone(),
+        commit.salt.clone(),
+        commit.version_key,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> CommitRevealParams {
+        CommitRevealParams {
+            enabled: true,
+            tempo: 360,
+            reveal_period_epochs: 1,
+        }
+    }
+
+    #[test]
+    fn test_reveal_window_is_next_epoch() {
+        let params = params();
+        let netuid = 39;
+
+        let commit_block = 10_000;
+        let commit_epoch = params.epoch_index(netuid, commit_block);
+        let (start, end) = params.reveal_window(netuid, commit_block);
+
+        assert_eq!(params.epoch_index(netuid, start), commit_epoch + 1);
+        assert_eq!(params.epoch_index(netuid, start - 1), commit_epoch);
+        assert_eq!(params.epoch_index(netuid, end), commit_epoch + 1);
+        assert_eq!(params.epoch_index(netuid, end + 1), commit_epoch + 2);
+        assert_eq!(end - start, params.tempo as u64);
+    }
+
+    #[test]
+    fn test_commit_hash_is_deterministic() {
+        let account = AccountId::from([7u8; 32]);
+        let weights = vec![
+            NormalizedWeight {
+                uid: 1,
+                weight: 100,
+            },
+            NormalizedWeight {
+                uid: 2,
+                weight: 200,
+            },
+        ];
+
+        let commit = WeightCommit::new(&account, 1, &weights, 5, 1000);
+        assert_eq!(commit.uids, vec![1, 2]);
+        assert_eq!(commit.values, vec![100, 200]);
+        assert_eq!(commit.salt.len(), SALT_LENGTH);
+
+        let expected = weight_commit_hash(
+            &account,
+            1,
+            &commit.uids,
+            &commit.values,
+            &commit.salt,
+            commit.version_key,
+        );
+        assert_eq!(commit.hash().unwrap(), expected);
+
+        let other_version = weight_commit_hash(
+            &account,
+            1,
+            &commit.uids,
+            &commit.values,
+            &commit.salt,
+            commit.version_key + 1,
+        );
+        assert_ne!(other_version, expected);
+    }
+}
@@ -4,6 +4,7 @@
 //! Provides high-level interface for wallet management, transaction submission,
 //! and chain state queries.
 
+pub mod commit_reveal;
 pub mod discovery;
 pub mod error;
 pub mod registration;
@@ -18,6 +19,10 @@ pub mod api;
 #[cfg(test)]
 mod error_tests;
 
+pub use commit_reveal::{
+    commit_weights_payload, generate_salt, reveal_weights_payload, weight_commit_hash,
+    CommitRevealParams, WeightCommit,
+};
 pub use discovery::NeuronDiscovery;
 pub use error::{BittensorError, ErrorCategory, RetryConfig};
 pub use registration::{
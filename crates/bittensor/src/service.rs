@@ -7,6 +7,9 @@ use crate::retry::{CircuitBreaker, RetryExecutor};
 use anyhow::Result;
 use common::config::BittensorConfig;
 // Import our own utilities
+use crate::commit_reveal::{
+    commit_weights_payload, reveal_weights_payload, CommitRevealParams, WeightCommit,
+};
 use crate::utils::{set_weights_payload, NormalizedWeight};
 use crate::AccountId;
 
@@ -273,6 +276,7 @@ impl Service {
     ///
     /// * `netuid` - The subnet UID to set weights for
     /// * `weights` - Vector of (uid, weight) pairs representing neuron weights
+    /// * `version_key` - Weights version key expected by the subnet
     ///
     /// # Returns
     ///
@@ -287,6 +291,7 @@ impl Service {
         &self,
         netuid: u16,
         weights: Vec<(u16, u16)>,
+        version_key: u64,
     ) -> Result<(), BittensorError> {
         info!(
             "Setting weights for netuid {} with {} weights using retry logic",
@@ -294,82 +299,194 @@ impl Service {
             weights.len()
         );
 
-        // Validate weights before attempting transaction
-        if weights.is_empty() {
-            return Err(BittensorError::InvalidWeights {
-                reason: "Weight vector cannot be empty".to_string(),
-            });
-        }
+        let normalized_weights = validate_weight_pairs(&weights)?;
 
-        // Check for duplicate UIDs
-        let mut seen_uids = std::collections::HashSet::new();
-        for (uid, _) in &weights {
-            if !seen_uids.insert(*uid) {
-                return Err(BittensorError::InvalidWeights {
-                    reason: format!("Duplicate UID found: {uid}"),
-                });
+        let operation = || {
+            let payload = set_weights_payload(netuid, normalized_weights.clone(), version_key);
+            let client = &self.client;
+            let signer = &self.signer;
+
+            async move {
+                client
+                    .tx()
+                    .sign_and_submit_then_watch_default(&payload, signer)
+                    .await
+                    .map_err(|e| weights_tx_error("set_weights", netuid, e))?;
+                Ok(())
             }
-        }
+        };
+
+        self.retry_executor.execute(operation).await?;
+        info!("Weights set successfully for netuid {}", netuid);
+        Ok(())
+    }
+
+    /// Commits a hash of weights for a later reveal, with retry logic.
+    ///
+    /// A fresh salt is generated for the commit. The returned commit holds
+    /// everything [`Service::reveal_weights`] needs and must be kept, across
+    /// restarts if necessary, until it has been revealed.
+    ///
+    /// Returns once the commit is finalized. Its `commit_block` is the block
+    /// that included it, which is the block subtensor derives the reveal
+    /// window from.
+    ///
+    /// # Arguments
+    ///
+    /// * `netuid` - The subnet UID to commit weights for
+    /// * `weights` - Vector of (uid, weight) pairs representing neuron weights
+    /// * `version_key` - Weights version key expected by the subnet
+    ///
+    /// # Errors
+    ///
+    /// * `TxSubmissionError` - If the commit_weights transaction fails to submit
+    /// * `InvalidWeights` - If the weight vector is invalid
+    /// * `MaxRetriesExceeded` - If all retry attempts are exhausted
+    pub async fn commit_weights(
+        &self,
+        netuid: u16,
+        weights: Vec<(u16, u16)>,
+        version_key: u64,
+    ) -> Result<WeightCommit, BittensorError> {
+        info!(
+            "Committing weights for netuid {} with {} weights",
+            netuid,
+            weights.len()
+        );
+
+        let normalized_weights = validate_weight_pairs(&weights)?;
+        // The block is only known once the commit is included
+        let mut commit = WeightCommit::new(
+            self.get_account_id(),
+            netuid,
+            &normalized_weights,
+            version_key,
+            0,
+        );
+        let commit_hash = commit.hash().map_err(|e| BittensorError::InvalidWeights {
+            reason: format!("Invalid commit hash: {e}"),
+        })?;
 
         let operation = || {
-            // Convert to NormalizedWeight format
-            let normalized_weights: Vec<NormalizedWeight> = weights
-                .iter()
-                .map(|(uid, weight)| NormalizedWeight {
-                    uid: *uid,
-                    weight: *weight,
-                })
-                .collect();
-
-            // Create set_weights payload with version_key = 0
-            let payload = set_weights_payload(netuid, normalized_weights, 0);
+            let payload = commit_weights_payload(netuid, commit_hash);
             let client = &self.client;
             let signer = &self.signer;
 
             async move {
-                client
+                let events = client
                     .tx()
                     .sign_and_submit_then_watch_default(&payload, signer)
                     .await
-                    .map_err(|e| {
-                        let err_msg = e.to_string();
-                        let err_lower = err_msg.to_lowercase();
+                    .map_err(|e| weights_tx_error("commit_weights", netuid, e))?
+                    .wait_for_finalized_success()
+                    .await
+                    .map_err(|e| weights_tx_error("commit_weights", netuid, e))?;
 
-                        if err_lower.contains("timeout") {
-                            BittensorError::TxTimeoutError {
-                                message: format!("set_weights transaction timeout: {err_msg}"),
-                                timeout: Duration::from_secs(120),
-                            }
-                        } else if err_lower.contains("weight") || err_lower.contains("invalid") {
-                            BittensorError::WeightSettingFailed {
-                                netuid,
-                                reason: format!("Weight validation failed: {err_msg}"),
-                            }
-                        } else if err_lower.contains("fee") || err_lower.contains("balance") {
-                            BittensorError::InsufficientTxFees {
-                                required: 0,
-                                available: 0,
-                            }
-                        } else if err_lower.contains("nonce") {
-                            BittensorError::InvalidNonce {
-                                expected: 0,
-                                actual: 0,
-                            }
-                        } else {
-                            BittensorError::TxSubmissionError {
-                                message: format!("Failed to submit set_weights: {err_msg}"),
-                            }
-                        }
-                    })?;
+                let block = client.blocks().at(events.block_hash()).await.map_err(|e| {
+                    BittensorError::RpcError {
+                        message: format!("Failed to get block including commit: {e}"),
+                    }
+                })?;
+                Ok(u64::from(block.number()))
+            }
+        };
+
+        commit.commit_block = self.retry_executor.execute(operation).await?;
+        info!(
+            "Weights committed for netuid {} at block {} with hash {}",
+            netuid, commit.commit_block, commit.commit_hash
+        );
+        Ok(commit)
+    }
+
+    /// Reveals previously committed weights, with retry logic.
+    ///
+    /// # Arguments
+    ///
+    /// * `commit` - The commit returned by [`Service::commit_weights`]
+    ///
+    /// # Errors
+    ///
+    /// * `TxSubmissionError` - If the reveal_weights transaction fails to submit,
+    ///   including reveals outside the subnet's reveal window
+    /// * `MaxRetriesExceeded` - If all retry attempts are exhausted
+    pub async fn reveal_weights(&self, commit: &WeightCommit) -> Result<(), BittensorError> {
+        let netuid = commit.netuid;
+        info!(
+            "Revealing weights for netuid {} committed at block {}",
+            netuid, commit.commit_block
+        );
+
+        let operation = || {
+            let payload = reveal_weights_payload(commit);
+            let client = &self.client;
+            let signer = &self.signer;
+
+            async move {
+                client
+                    .tx()
+                    .sign_and_submit_then_watch_default(&payload, signer)
+                    .await
+                    .map_err(|e| weights_tx_error("reveal_weights", netuid, e))?;
                 Ok(())
             }
         };
 
         self.retry_executor.execute(operation).await?;
-        info!("Weights set successfully for netuid {}", netuid);
+        info!(
+            "Weights revealed for netuid {} with hash {}",
+            netuid, commit.commit_hash
+        );
         Ok(())
     }
 
+    /// Gets the commit-reveal settings of a subnet.
+    ///
+    /// # Arguments
+    ///
+    /// * `netuid` - The subnet UID
+    ///
+    /// # Errors
+    ///
+    /// * `RpcError` - If a storage query fails
+    pub async fn get_commit_reveal_params(
+        &self,
+        netuid: u16,
+    ) -> Result<CommitRevealParams, BittensorError> {
+        let storage =
+            self.client
+                .storage()
+                .at_latest()
+                .await
+                .map_err(|e| BittensorError::RpcError {
+                    message: format!("Failed to get latest storage: {e}"),
+                })?;
+
+        let subtensor = api::storage().subtensor_module();
+        let rpc_error = |e: subxt::Error| BittensorError::RpcError {
+            message: format!("Failed to query commit-reveal settings: {e}"),
+        };
+
+        let enabled = storage
+            .fetch_or_default(&subtensor.commit_reveal_weights_enabled(netuid))
+            .await
+            .map_err(rpc_error)?;
+        let tempo = storage
+            .fetch_or_default(&subtensor.tempo(netuid))
+            .await
+            .map_err(rpc_error)?;
+        let reveal_period_epochs = storage
+            .fetch_or_default(&subtensor.reveal_period_epochs(netuid))
+            .await
+            .map_err(rpc_error)?;
+
+        Ok(CommitRevealParams {
+            enabled,
+            tempo,
+            reveal_period_epochs,
+        })
+    }
+
     /// Gets neuron information for a specific UID in the subnet.
     ///
     /// # Arguments
@@ -772,6 +889,64 @@ impl Service {
     }
 }
 
+/// Check (uid, weight) pairs before they are submitted
+fn validate_weight_pairs(weights: &[(u16, u16)]) -> Result<Vec<NormalizedWeight>, BittensorError> {
+    if weights.is_empty() {
+        return Err(BittensorError::InvalidWeights {
+            reason: "Weight vector cannot be empty".to_string(),
+        });
+    }
+
+    let mut seen_uids = std::collections::HashSet::new();
+    for (uid, _) in weights {
+        if !seen_uids.insert(*uid) {
+            return Err(BittensorError::InvalidWeights {
+                reason: format!("Duplicate UID found: {uid}"),
+            });
+        }
+    }
+
+    Ok(weights
+        .iter()
+        .map(|(uid, weight)| NormalizedWeight {
+            uid: *uid,
+            weight: *weight,
+        })
+        .collect())
+}
+
+/// Classify a failed weights extrinsic so the retry logic can act on it
+fn weights_tx_error(call: &str, netuid: u16, error: subxt::Error) -> BittensorError {
+    let err_msg = error.to_string();
+    let err_lower = err_msg.to_lowercase();
+
+    if err_lower.contains("timeout") {
+        BittensorError::TxTimeoutError {
+            message: format!("{call} transaction timeout: {err_msg}"),
+            timeout: Duration::from_secs(120),
+        }
+    } else if err_lower.contains("weight") || err_lower.contains("invalid") {
+        BittensorError::WeightSettingFailed {
+            netuid,
+            reason: format!("Weight validation failed: {err_msg}"),
+        }
+    } else if err_lower.contains("fee") || err_lower.contains("balance") {
+        BittensorError::InsufficientTxFees {
+            required: 0,
+            available: 0,
+        }
+    } else if err_lower.contains("nonce") {
+        BittensorError::InvalidNonce {
+            expected: 0,
+            actual: 0,
+        }
+    } else {
+        BittensorError::TxSubmissionError {
+            message: format!("Failed to submit {call}: {err_msg}"),
+        }
+    }
+}
+
 /// Statistics for retry mechanisms
 #[derive(Debug, Clone)]
 pub struct RetryStats {
@@ -0,0 +1,216 @@
+//! Collateral contract client bound to a configured deployment
+
+use alloy::rpc::types::TransactionReceipt;
+use alloy::signers::Signer;
+use alloy::signers::local::PrivateKeySigner;
+use alloy_primitives::{Address, FixedBytes, TxHash, U256};
+use alloy_provider::ProviderBuilder;
+
+use crate::config::CollateralConfig;
+use crate::{Collateral, Reclaim};
+
+/// Client for a single Collateral contract deployment
+#[derive(Debug, Clone)]
+pub struct CollateralClient {
+    config: CollateralConfig,
+}
+
+impl CollateralClient {
+    pub fn new(config: CollateralConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &CollateralConfig {
+        &self.config
+    }
+
+    pub fn contract_address(&self) -> Address {
+        self.config.contract_address
+    }
+
+    /// Build the transaction signer from the configured signer source
+    fn signer(&self) -> Result<PrivateKeySigner, anyhow::Error> {
+        let private_key =
+            self.config.signer.resolve()?.ok_or_else(|| {
+                anyhow::anyhow!("No signer configured for collateral transactions")
+            })?;
+        let mut signer: PrivateKeySigner = private_key.parse()?;
+        signer.set_chain_id(Some(self.config.chain_id));
+        Ok(signer)
+    }
+
+    // transactions
+
+    pub async fn deposit(&self, executor_id: u128, amount: U256) -> Result<TxHash, anyhow::Error> {
+        let provider = ProviderBuilder::new()
+            .wallet(self.signer()?)
+            .connect(&self.config.rpc_url)
+            .await?;
+
+        let contract = Collateral::new(self.config.contract_address, &provider);
+
+        let executor_bytes = executor_id.to_be_bytes();
+        let tx = contract
+            .deposit(FixedBytes::from_slice(&executor_bytes))
+            .value(amount);
+        let receipt = tx.send().await?.get_receipt().await?;
+        confirmed(receipt)
+    }
+
+    pub async fn reclaim_collateral(
+        &self,
+        executor_id: u128,
+        url: &str,
+        url_content_md5_checksum: u128,
+    ) -> Result<TxHash, anyhow::Error> {
+        let provider = ProviderBuilder::new()
+            .wallet(self.signer()?)
+            .connect(&self.config.rpc_url)
+            .await?;
+
+        let contract = Collateral::new(self.config.contract_address, &provider);
+
+        let tx = contract.reclaimCollateral(
+            FixedBytes::from_slice(&executor_id.to_be_bytes()),
+            url.to_string(),
+            FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
+        );
+        let receipt = tx.send().await?.get_receipt().await?;
+        confirmed(receipt)
+    }
+
+    pub async fn finalize_reclaim(
+        &self,
+        reclaim_request_id: U256,
+    ) -> Result<TxHash, anyhow::Error> {
+        let provider = ProviderBuilder::new()
+            .wallet(self.signer()?)
+            .connect(&self.config.rpc_url)
+            .await?;
+
+        let contract = Collateral::new(self.config.contract_address, &provider);
+
+        let tx = contract.finalizeReclaim(reclaim_request_id);
+        let receipt = tx.send().await?.get_receipt().await?;
+        confirmed(receipt)
+    }
+
+    pub async fn deny_reclaim(
+        &self,
+        reclaim_request_id: U256,
+        url: &str,
+        url_content_md5_checksum: u128,
+    ) -> Result<TxHash, anyhow::Error> {
+        let provider = ProviderBuilder::new()
+            .wallet(self.signer()?)
+            .connect(&self.config.rpc_url)
+            .await?;
+
+        let contract = Collateral::new(self.config.contract_address, &provider);
+
+        let tx = contract.denyReclaimRequest(
+            reclaim_request_id,
+            url.to_string(),
+            FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
+        );
+        let receipt = tx.send().await?.get_receipt().await?;
+        confirmed(receipt)
+    }
+
+    pub async fn slash_collateral(
+        &self,
+        executor_id: u128,
+        url: &str,
+        url_content_md5_checksum: u128,
+    ) -> Result<TxHash, anyhow::Error> {
+        let provider = ProviderBuilder::new()
+            .wallet(self.signer()?)
+            .connect(&self.config.rpc_url)
+            .await?;
+
+        let contract = Collateral::new(self.config.contract_address, &provider);
+
+        let tx = contract.slashCollateral(
+            FixedBytes::from_slice(&executor_id.to_be_bytes()),
+            url.to_string(),
+            FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
+        );
+        let receipt = tx.send().await?.get_receipt().await?;
+        confirmed(receipt)
+    }
+
+    // Get methods
+
+    pub async fn netuid(&self) -> Result<u16, anyhow::Error> {
+        let provider = ProviderBuilder::new().connect(&self.config.rpc_url).await?;
+        let contract = Collateral::new(self.config.contract_address, &provider);
+        let netuid = contract.NETUID().call().await?;
+        Ok(netuid)
+    }
+
+    pub async fn trustee(&self) -> Result<Address, anyhow::Error> {
+        let provider = ProviderBuilder::new().connect(&self.config.rpc_url).await?;
+        let contract = Collateral::new(self.config.contract_address, &provider);
+        let trustee = contract.TRUSTEE().call().await?;
+        Ok(trustee)
+    }
+
+    pub async fn decision_timeout(&self) -> Result<u64, anyhow::Error> {
+        let provider = ProviderBuilder::new().connect(&self.config.rpc_url).await?;
+        let contract = Collateral::new(self.config.contract_address, &provider);
+        let decision_timeout = contract.DECISION_TIMEOUT().call().await?;
+        Ok(decision_timeout)
+    }
+
+    pub async fn min_collateral_increase(&self) -> Result<U256, anyhow::Error> {
+        let provider = ProviderBuilder::new().connect(&self.config.rpc_url).await?;
+        let contract = Collateral::new(self.config.contract_address, &provider);
+        let min_collateral_increase = contract.MIN_COLLATERAL_INCREASE().call().await?;
+        Ok(min_collateral_increase)
+    }
+
+    pub async fn executor_to_miner(&self, executor_id: u128) -> Result<Address, anyhow::Error> {
+        let provider = ProviderBuilder::new().connect(&self.config.rpc_url).await?;
+        let contract = Collateral::new(self.config.contract_address, &provider);
+        let executor_to_miner = contract
+            .executorToMiner(FixedBytes::from_slice(&executor_id.to_be_bytes()))
+            .call()
+            .await?;
+        Ok(executor_to_miner)
+    }
+
+    pub async fn collaterals(&self, executor_id: u128) -> Result<U256, anyhow::Error> {
+        let provider = ProviderBuilder::new().connect(&self.config.rpc_url).await?;
+        let contract = Collateral::new(self.config.contract_address, &provider);
+        let collaterals = contract
+            .collaterals(FixedBytes::from_slice(&executor_id.to_be_bytes()))
+            .call()
+            .await?;
+        Ok(collaterals)
+    }
+
+    pub async fn reclaims(&self, reclaim_request_id: U256) -> Result<Reclaim, anyhow::Error> {
+        let provider = ProviderBuilder::new().connect(&self.config.rpc_url).await?;
+        let contract = Collateral::new(self.config.contract_address, &provider);
+        let result = contract.reclaims(reclaim_request_id).call().await?;
+        let reclaim = Reclaim::from((
+            result.executorId,
+            result.miner,
+            result.amount,
+            result.denyTimeout,
+        ));
+        Ok(reclaim)
+    }
+}
+
+/// Hash of a mined transaction, or an error if it reverted
+fn confirmed(receipt: TransactionReceipt) -> Result<TxHash, anyhow::Error> {
+    if !receipt.status() {
+        return Err(anyhow::anyhow!(
+            "Transaction {} reverted",
+            receipt.transaction_hash
+        ));
+    }
+    tracing::debug!("Transaction {} confirmed", receipt.transaction_hash);
+    Ok(receipt.transaction_hash)
+}
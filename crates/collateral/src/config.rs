@@ -0,0 +1,199 @@
+//! Network configuration for the collateral contract client
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Where the signing key for collateral transactions comes from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerSource {
+    /// No signer, only view calls are available
+    #[default]
+    None,
+
+    /// Hex-encoded private key given inline
+    PrivateKey(String),
+
+    /// Name of an environment variable holding the hex-encoded private key
+    Env(String),
+
+    /// Path to a file containing the hex-encoded private key
+    File(PathBuf),
+}
+
+impl SignerSource {
+    /// Resolve the hex-encoded private key, if any
+    pub fn resolve(&self) -> Result<Option<String>, anyhow::Error> {
+        match self {
+            SignerSource::None => Ok(None),
+            SignerSource::PrivateKey(key) => Ok(Some(key.trim().to_string())),
+            SignerSource::Env(var) => std::env::var(var)
+                .map(|key| Some(key.trim().to_string()))
+                .map_err(|e| anyhow::anyhow!("Failed to read private key from ${var}: {e}")),
+            SignerSource::File(path) => std::fs::read_to_string(path)
+                .map(|key| Some(key.trim().to_string()))
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to read private key from {}: {e}", path.display())
+                }),
+        }
+    }
+}
+
+/// Connection settings for a Collateral contract deployment
+///
+/// There is no default contract address, every deployment has to name its own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollateralConfig {
+    /// Deployed Collateral contract address
+    pub contract_address: Address,
+
+    /// EVM chain id used when signing transactions
+    pub chain_id: u64,
+
+    /// EVM JSON-RPC endpoint
+    pub rpc_url: String,
+
+    /// Signing key source for transactions
+    #[serde(default)]
+    pub signer: SignerSource,
+}
+
+impl CollateralConfig {
+    /// Local subtensor node with EVM enabled
+    pub fn local(contract_address: Address) -> Self {
+        Self {
+            contract_address,
+            chain_id: 42,
+            rpc_url: "http://localhost:9944".to_string(),
+            signer: SignerSource::None,
+        }
+    }
+
+    /// Bittensor testnet EVM
+    pub fn testnet(contract_address: Address) -> Self {
+        Self {
+            contract_address,
+            chain_id: 945,
+            rpc_url: "https://test.chain.opentensor.ai".to_string(),
+            signer: SignerSource::None,
+        }
+    }
+
+    /// Bittensor mainnet (finney) EVM
+    pub fn mainnet(contract_address: Address) -> Self {
+        Self {
+            contract_address,
+            chain_id: 964,
+            rpc_url: "https://lite.chain.opentensor.ai".to_string(),
+            signer: SignerSource::None,
+        }
+    }
+
+    /// Preset matching a bittensor network name ("local", "test" or "finney")
+    pub fn for_network(network: &str, contract_address: Address) -> Result<Self, anyhow::Error> {
+        match network {
+            "local" => Ok(Self::local(contract_address)),
+            "test" | "testnet" => Ok(Self::testnet(contract_address)),
+            "finney" | "mainnet" => Ok(Self::mainnet(contract_address)),
+            _ => Err(anyhow::anyhow!(
+                "Unknown network: {network}. Valid networks are: finney, test, local"
+            )),
+        }
+    }
+
+    /// Override the contract address
+    pub fn with_contract_address(mut self, contract_address: Address) -> Self {
+        self.contract_address = contract_address;
+        self
+    }
+
+    /// Override the RPC endpoint, e.g. to target an anvil node in tests
+    pub fn with_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = rpc_url.into();
+        self
+    }
+
+    /// Override the chain id
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Set the signer source
+    pub fn with_signer(mut self, signer: SignerSource) -> Self {
+        self.signer = signer;
+        self
+    }
+
+    /// Shorthand for an inline private key signer
+    pub fn with_private_key(self, private_key: impl Into<String>) -> Self {
+        self.with_signer(SignerSource::PrivateKey(private_key.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: Address = Address::repeat_byte(0x11);
+
+    #[test]
+    fn test_presets_for_network() {
+        let local = CollateralConfig::for_network("local", CONTRACT).unwrap();
+        assert_eq!(local.chain_id, 42);
+        assert_eq!(local.contract_address, CONTRACT);
+        assert_eq!(
+            CollateralConfig::for_network("test", CONTRACT)
+                .unwrap()
+                .chain_id,
+            945
+        );
+        assert_eq!(
+            CollateralConfig::for_network("finney", CONTRACT)
+                .unwrap()
+                .chain_id,
+            964
+        );
+        assert!(CollateralConfig::for_network("devnet", CONTRACT).is_err());
+    }
+
+    #[test]
+    fn test_contract_address_is_required() {
+        let without_address = r#"
+            chain_id = 945
+            rpc_url = "https://test.chain.opentensor.ai"
+        "#;
+        assert!(toml::from_str::<CollateralConfig>(without_address).is_err());
+
+        let config: CollateralConfig = toml::from_str(&format!(
+            "contract_address = \"{CONTRACT}\"\n{without_address}"
+        ))
+        .unwrap();
+        assert_eq!(config.contract_address, CONTRACT);
+        assert_eq!(config.signer, SignerSource::None);
+    }
+
+    #[test]
+    fn test_builder_overrides() {
+        let config = CollateralConfig::local(CONTRACT)
+            .with_rpc_url("http://127.0.0.1:8545")
+            .with_chain_id(31337)
+            .with_private_key("0xabc");
+
+        assert_eq!(config.rpc_url, "http://127.0.0.1:8545");
+        assert_eq!(config.chain_id, 31337);
+        assert_eq!(config.signer.resolve().unwrap(), Some("0xabc".to_string()));
+    }
+
+    #[test]
+    fn test_signer_source_env() {
+        let var = "COLLATERAL_TEST_SIGNER_SOURCE_ENV";
+        unsafe { std::env::set_var(var, " 0xdef\n") };
+        let source = SignerSource::Env(var.to_string());
+        assert_eq!(source.resolve().unwrap(), Some("0xdef".to_string()));
+        unsafe { std::env::remove_var(var) };
+        assert!(source.resolve().is_err());
+        assert_eq!(SignerSource::None.resolve().unwrap(), None);
+    }
+}
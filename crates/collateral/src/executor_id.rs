@@ -0,0 +1,73 @@
+//! Mapping between off-chain executor identifiers and the contract's bytes16 ids
+
+use alloy_primitives::{FixedBytes, U256, keccak256};
+
+/// Number of wei in one TAO on the subtensor EVM
+pub const WEI_PER_TAO: u128 = 1_000_000_000_000_000_000;
+
+/// Convert the u128 executor id used by the client API to the contract's bytes16
+pub fn executor_id_bytes(executor_id: u128) -> FixedBytes<16> {
+    FixedBytes::from_slice(&executor_id.to_be_bytes())
+}
+
+/// Derive the on-chain executor id from an executor identifier string
+///
+/// UUIDs (with or without hyphens) map directly to their 128-bit value so ids
+/// issued by the executor identity system are stable and reversible. Any other
+/// identifier is hashed and the first 16 bytes of its keccak256 digest are used.
+pub fn executor_id_from_str(executor_id: &str) -> u128 {
+    let hex: String = executor_id.chars().filter(|c| *c != '-').collect();
+    if hex.len() == 32
+        && let Ok(value) = u128::from_str_radix(&hex, 16)
+    {
+        return value;
+    }
+
+    let digest = keccak256(executor_id.as_bytes());
+    u128::from_be_bytes(digest[..16].try_into().expect("digest is 32 bytes"))
+}
+
+/// Convert a wei amount to TAO
+pub fn wei_to_tao(amount: U256) -> f64 {
+    f64::from(amount) / WEI_PER_TAO as f64
+}
+
+/// Convert a TAO amount to wei, truncating below 1 wei
+pub fn tao_to_wei(amount: f64) -> U256 {
+    if amount <= 0.0 {
+        return U256::ZERO;
+    }
+    let whole = amount.trunc() as u128;
+    let fraction = ((amount - amount.trunc()) * WEI_PER_TAO as f64) as u128;
+    U256::from(whole) * U256::from(WEI_PER_TAO) + U256::from(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_maps_to_its_value() {
+        let id = executor_id_from_str("550e8400-e29b-41d4-a716-446655440000");
+        assert_eq!(id, 0x550e8400e29b41d4a716446655440000);
+        assert_eq!(executor_id_from_str("550e8400e29b41d4a716446655440000"), id);
+        assert_eq!(
+            executor_id_bytes(id).to_string(),
+            "0x550e8400e29b41d4a716446655440000"
+        );
+    }
+
+    #[test]
+    fn test_other_ids_are_hashed_deterministically() {
+        let id = executor_id_from_str("executor-1");
+        assert_eq!(id, executor_id_from_str("executor-1"));
+        assert_ne!(id, executor_id_from_str("executor-2"));
+    }
+
+    #[test]
+    fn test_tao_wei_conversion() {
+        assert_eq!(tao_to_wei(1.5), U256::from(1_500_000_000_000_000_000u128));
+        assert_eq!(tao_to_wei(-1.0), U256::ZERO);
+        assert!((wei_to_tao(tao_to_wei(2.25)) - 2.25).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,174 @@
+//! Typed Collateral contract events
+
+use alloy::rpc::types::Log;
+use alloy_primitives::{Address, B256, FixedBytes, U256};
+use alloy_sol_types::SolEventInterface;
+
+use crate::Collateral::CollateralEvents;
+
+/// A decoded Collateral contract event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollateralEvent {
+    Deposit {
+        executor_id: FixedBytes<16>,
+        miner: Address,
+        amount: U256,
+    },
+    ReclaimProcessStarted {
+        reclaim_request_id: U256,
+        executor_id: FixedBytes<16>,
+        miner: Address,
+        amount: U256,
+        expiration_time: u64,
+        url: String,
+        url_content_md5_checksum: FixedBytes<16>,
+    },
+    Reclaimed {
+        reclaim_request_id: U256,
+        executor_id: FixedBytes<16>,
+        miner: Address,
+        amount: U256,
+    },
+    Denied {
+        reclaim_request_id: U256,
+        url: String,
+        url_content_md5_checksum: FixedBytes<16>,
+    },
+    Slashed {
+        executor_id: FixedBytes<16>,
+        miner: Address,
+        amount: U256,
+        url: String,
+        url_content_md5_checksum: FixedBytes<16>,
+    },
+}
+
+impl CollateralEvent {
+    /// Event name as emitted by the contract
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            CollateralEvent::Deposit { .. } => "Deposit",
+            CollateralEvent::ReclaimProcessStarted { .. } => "ReclaimProcessStarted",
+            CollateralEvent::Reclaimed { .. } => "Reclaimed",
+            CollateralEvent::Denied { .. } => "Denied",
+            CollateralEvent::Slashed { .. } => "Slashed",
+        }
+    }
+
+    pub fn executor_id(&self) -> Option<FixedBytes<16>> {
+        match self {
+            CollateralEvent::Deposit { executor_id, .. }
+            | CollateralEvent::ReclaimProcessStarted { executor_id, .. }
+            | CollateralEvent::Reclaimed { executor_id, .. }
+            | CollateralEvent::Slashed { executor_id, .. } => Some(*executor_id),
+            CollateralEvent::Denied { .. } => None,
+        }
+    }
+
+    pub fn miner(&self) -> Option<Address> {
+        match self {
+            CollateralEvent::Deposit { miner, .. }
+            | CollateralEvent::ReclaimProcessStarted { miner, .. }
+            | CollateralEvent::Reclaimed { miner, .. }
+            | CollateralEvent::Slashed { miner, .. } => Some(*miner),
+            CollateralEvent::Denied { .. } => None,
+        }
+    }
+
+    pub fn amount(&self) -> Option<U256> {
+        match self {
+            CollateralEvent::Deposit { amount, .. }
+            | CollateralEvent::ReclaimProcessStarted { amount, .. }
+            | CollateralEvent::Reclaimed { amount, .. }
+            | CollateralEvent::Slashed { amount, .. } => Some(*amount),
+            CollateralEvent::Denied { .. } => None,
+        }
+    }
+
+    pub fn reclaim_request_id(&self) -> Option<U256> {
+        match self {
+            CollateralEvent::ReclaimProcessStarted {
+                reclaim_request_id, ..
+            }
+            | CollateralEvent::Reclaimed {
+                reclaim_request_id, ..
+            }
+            | CollateralEvent::Denied {
+                reclaim_request_id, ..
+            } => Some(*reclaim_request_id),
+            _ => None,
+        }
+    }
+}
+
+impl From<CollateralEvents> for CollateralEvent {
+    fn from(event: CollateralEvents) -> Self {
+        match event {
+            CollateralEvents::Deposit(e) => CollateralEvent::Deposit {
+                executor_id: e.executorId,
+                miner: e.miner,
+                amount: e.amount,
+            },
+            CollateralEvents::ReclaimProcessStarted(e) => CollateralEvent::ReclaimProcessStarted {
+                reclaim_request_id: e.reclaimRequestId,
+                executor_id: e.executorId,
+                miner: e.miner,
+                amount: e.amount,
+                expiration_time: e.expirationTime,
+                url: e.url,
+                url_content_md5_checksum: e.urlContentMd5Checksum,
+            },
+            CollateralEvents::Reclaimed(e) => CollateralEvent::Reclaimed {
+                reclaim_request_id: e.reclaimRequestId,
+                executor_id: e.executorId,
+                miner: e.miner,
+                amount: e.amount,
+            },
+            CollateralEvents::Denied(e) => CollateralEvent::Denied {
+                reclaim_request_id: e.reclaimRequestId,
+                url: e.url,
+                url_content_md5_checksum: e.urlContentMd5Checksum,
+            },
+            CollateralEvents::Slashed(e) => CollateralEvent::Slashed {
+                executor_id: e.executorId,
+                miner: e.miner,
+                amount: e.amount,
+                url: e.url,
+                url_content_md5_checksum: e.urlContentMd5Checksum,
+            },
+        }
+    }
+}
+
+/// A Collateral event together with its position on chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEvent {
+    pub block_number: u64,
+    pub block_hash: B256,
+    pub transaction_hash: B256,
+    pub log_index: u64,
+    pub event: CollateralEvent,
+}
+
+impl IndexedEvent {
+    /// Decode an RPC log into an indexed event
+    pub fn from_log(log: &Log) -> Result<Self, anyhow::Error> {
+        let decoded = CollateralEvents::decode_log(&log.inner)?;
+
+        Ok(Self {
+            block_number: log
+                .block_number
+                .ok_or_else(|| anyhow::anyhow!("Log is missing block number"))?,
+            block_hash: log
+                .block_hash
+                .ok_or_else(|| anyhow::anyhow!("Log is missing block hash"))?,
+            transaction_hash: log
+                .transaction_hash
+                .ok_or_else(|| anyhow::anyhow!("Log is missing transaction hash"))?,
+            log_index: log
+                .log_index
+                .ok_or_else(|| anyhow::anyhow!("Log is missing log index"))?,
+            event: decoded.data.into(),
+        })
+    }
+}
@@ -0,0 +1,203 @@
+//! Background indexer for Collateral contract events
+//!
+//! Follows the contract's logs from a start block, keeps them in SQLite and
+//! rewinds when the chain reorganizes below the indexed head.
+
+pub mod events;
+pub mod store;
+
+use alloy::eips::BlockNumberOrTag;
+use alloy::rpc::types::Filter;
+use alloy_primitives::B256;
+use alloy_provider::{Provider, ProviderBuilder};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+pub use events::{CollateralEvent, IndexedEvent};
+pub use store::CollateralEventStore;
+
+use crate::config::CollateralConfig;
+
+/// Configuration for the event indexer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexerConfig {
+    /// Block to start indexing from when nothing has been indexed yet
+    pub start_block: u64,
+
+    /// Number of blocks to stay behind the chain head
+    pub confirmations: u64,
+
+    /// How far back to look for a common ancestor after a reorg
+    pub max_reorg_depth: u64,
+
+    /// Maximum block range per log query
+    pub batch_size: u64,
+
+    /// Delay between polls once caught up, in seconds
+    pub poll_interval_secs: u64,
+}
+
+impl Default for IndexerConfig {
+    fn default() -> Self {
+        Self {
+            start_block: 0,
+            confirmations: 3,
+            max_reorg_depth: 64,
+            batch_size: 1000,
+            poll_interval_secs: 12,
+        }
+    }
+}
+
+/// Follows Collateral contract events into a [`CollateralEventStore`]
+pub struct CollateralIndexer {
+    collateral: CollateralConfig,
+    config: IndexerConfig,
+    store: CollateralEventStore,
+}
+
+impl CollateralIndexer {
+    pub fn new(
+        collateral: CollateralConfig,
+        config: IndexerConfig,
+        store: CollateralEventStore,
+    ) -> Self {
+        Self {
+            collateral,
+            config,
+            store,
+        }
+    }
+
+    pub fn store(&self) -> &CollateralEventStore {
+        &self.store
+    }
+
+    /// Start the indexing loop
+    pub async fn start(&self) -> Result<(), anyhow::Error> {
+        info!(
+            "Starting collateral event indexer for {} from block {}",
+            self.collateral.contract_address, self.config.start_block
+        );
+
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.poll_interval_secs.max(1)));
+
+        loop {
+            interval.tick().await;
+
+            match self.sync_once().await {
+                Ok(0) => debug!("Collateral indexer is up to date"),
+                Ok(count) => info!("Indexed {} collateral events", count),
+                Err(e) => error!("Collateral indexing failed: {}", e),
+            }
+        }
+    }
+
+    /// Index all confirmed blocks not yet in the store, returning the number of new events
+    pub async fn sync_once(&self) -> Result<u64, anyhow::Error> {
+        let provider = ProviderBuilder::new()
+            .connect(&self.collateral.rpc_url)
+            .await?;
+
+        self.handle_reorg(&provider).await?;
+
+        let head = provider.get_block_number().await?;
+        let safe_head = head.saturating_sub(self.config.confirmations);
+
+        let mut from_block = match self.store.last_indexed_block().await? {
+            Some((block_number, _)) => block_number + 1,
+            None => self.config.start_block,
+        };
+
+        let mut total = 0;
+        while from_block <= safe_head {
+            let to_block = (from_block + self.config.batch_size.max(1) - 1).min(safe_head);
+
+            let filter = Filter::new()
+                .address(self.collateral.contract_address)
+                .from_block(from_block)
+                .to_block(to_block);
+            let logs = provider.get_logs(&filter).await?;
+
+            let mut events = Vec::with_capacity(logs.len());
+            let mut blocks = Vec::new();
+            for log in &logs {
+                match IndexedEvent::from_log(log) {
+                    Ok(event) => {
+                        blocks.push((event.block_number, event.block_hash));
+                        events.push(event);
+                    }
+                    Err(e) => warn!("Skipping undecodable collateral log: {}", e),
+                }
+            }
+
+            // Remember the hash of the batch tip so reorgs are detected on the next pass
+            let tip_hash = self
+                .block_hash(&provider, to_block)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Block {to_block} not found"))?;
+            blocks.push((to_block, tip_hash));
+
+            total += self.store.insert_batch(&events, &blocks).await?;
+            debug!(
+                "Indexed collateral blocks {}..={} ({} events)",
+                from_block,
+                to_block,
+                events.len()
+            );
+
+            from_block = to_block + 1;
+        }
+
+        Ok(total)
+    }
+
+    /// Rewind the store to the last block still on the canonical chain
+    async fn handle_reorg<P: Provider>(&self, provider: &P) -> Result<(), anyhow::Error> {
+        let Some((last_block, last_hash)) = self.store.last_indexed_block().await? else {
+            return Ok(());
+        };
+
+        if self.block_hash(provider, last_block).await? == Some(last_hash) {
+            return Ok(());
+        }
+
+        let candidates = self
+            .store
+            .indexed_blocks_since(last_block.saturating_sub(self.config.max_reorg_depth))
+            .await?;
+
+        let mut ancestor = candidates
+            .last()
+            .map(|(block_number, _)| block_number.saturating_sub(1))
+            .unwrap_or(0);
+        for (block_number, block_hash) in &candidates {
+            if self.block_hash(provider, *block_number).await? == Some(*block_hash) {
+                ancestor = *block_number;
+                break;
+            }
+        }
+
+        let removed = self.store.rewind_to(ancestor).await?;
+        warn!(
+            "Chain reorg detected at block {}, rewound collateral index to block {} ({} events removed)",
+            last_block, ancestor, removed
+        );
+
+        Ok(())
+    }
+
+    async fn block_hash<P: Provider>(
+        &self,
+        provider: &P,
+        block_number: u64,
+    ) -> Result<Option<B256>, anyhow::Error> {
+        let block = provider
+            .get_block_by_number(BlockNumberOrTag::Number(block_number))
+            .await?;
+        Ok(block.map(|block| block.header.hash))
+    }
+}
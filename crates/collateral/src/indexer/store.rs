@@ -0,0 +1,458 @@
+//! SQLite storage for indexed Collateral events
+
+use alloy_primitives::{Address, B256, FixedBytes, U256};
+use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+
+use super::events::{CollateralEvent, IndexedEvent};
+
+const EVENT_COLUMNS: &str = "block_number, block_hash, transaction_hash, log_index, event_type, \
+     executor_id, miner, amount, reclaim_request_id, expiration_time, url, url_content_md5_checksum";
+
+/// Persistent history of Collateral contract events
+#[derive(Debug, Clone)]
+pub struct CollateralEventStore {
+    pool: SqlitePool,
+}
+
+impl CollateralEventStore {
+    /// Create a store on an existing pool, creating the tables if needed
+    pub async fn new(pool: SqlitePool) -> Result<Self, anyhow::Error> {
+        let store = Self { pool };
+        store.run_migrations().await?;
+        Ok(store)
+    }
+
+    /// Connect to a SQLite database URL and create the tables if needed
+    pub async fn connect(database_url: &str) -> Result<Self, anyhow::Error> {
+        let pool = SqlitePool::connect(database_url).await?;
+        Self::new(pool).await
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    async fn run_migrations(&self) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS collateral_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                block_number INTEGER NOT NULL,
+                block_hash TEXT NOT NULL,
+                transaction_hash TEXT NOT NULL,
+                log_index INTEGER NOT NULL,
+                event_type TEXT NOT NULL,
+                executor_id TEXT,
+                miner TEXT,
+                amount TEXT,
+                reclaim_request_id TEXT,
+                expiration_time INTEGER,
+                url TEXT,
+                url_content_md5_checksum TEXT,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+
+                UNIQUE (transaction_hash, log_index)
+            );
+
+            CREATE TABLE IF NOT EXISTS collateral_indexed_blocks (
+                block_number INTEGER PRIMARY KEY,
+                block_hash TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_collateral_events_block ON collateral_events(block_number);
+            CREATE INDEX IF NOT EXISTS idx_collateral_events_executor ON collateral_events(executor_id);
+            CREATE INDEX IF NOT EXISTS idx_collateral_events_miner ON collateral_events(miner);
+            CREATE INDEX IF NOT EXISTS idx_collateral_events_reclaim ON collateral_events(reclaim_request_id);
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Store a batch of events and the hashes of the blocks they were read at
+    ///
+    /// Events already present (same transaction hash and log index) are ignored.
+    pub async fn insert_batch(
+        &self,
+        events: &[IndexedEvent],
+        blocks: &[(u64, B256)],
+    ) -> Result<u64, anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = 0;
+
+        for indexed in events {
+            let event = &indexed.event;
+            let (expiration_time, url, checksum) = match event {
+                CollateralEvent::ReclaimProcessStarted {
+                    expiration_time,
+                    url,
+                    url_content_md5_checksum,
+                    ..
+                } => (
+                    Some(*expiration_time as i64),
+                    Some(url.clone()),
+                    Some(url_content_md5_checksum.to_string()),
+                ),
+                CollateralEvent::Denied {
+                    url,
+                    url_content_md5_checksum,
+                    ..
+                }
+                | CollateralEvent::Slashed {
+                    url,
+                    url_content_md5_checksum,
+                    ..
+                } => (
+                    None,
+                    Some(url.clone()),
+                    Some(url_content_md5_checksum.to_string()),
+                ),
+                _ => (None, None, None),
+            };
+
+            let result = sqlx::query(&format!(
+                "INSERT OR IGNORE INTO collateral_events ({EVENT_COLUMNS})
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            ))
+            .bind(indexed.block_number as i64)
+            .bind(indexed.block_hash.to_string())
+            .bind(indexed.transaction_hash.to_string())
+            .bind(indexed.log_index as i64)
+            .bind(event.event_type())
+            .bind(event.executor_id().map(|id| id.to_string()))
+            .bind(event.miner().map(|miner| miner.to_string()))
+            .bind(event.amount().map(|amount| amount.to_string()))
+            .bind(event.reclaim_request_id().map(|id| id.to_string()))
+            .bind(expiration_time)
+            .bind(url)
+            .bind(checksum)
+            .execute(&mut *tx)
+            .await?;
+
+            inserted += result.rows_affected();
+        }
+
+        for (block_number, block_hash) in blocks {
+            sqlx::query(
+                "INSERT OR REPLACE INTO collateral_indexed_blocks (block_number, block_hash)
+                 VALUES (?, ?)",
+            )
+            .bind(*block_number as i64)
+            .bind(block_hash.to_string())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    /// Highest indexed block and its hash
+    pub async fn last_indexed_block(&self) -> Result<Option<(u64, B256)>, anyhow::Error> {
+        let row = sqlx::query(
+            "SELECT block_number, block_hash FROM collateral_indexed_blocks
+             ORDER BY block_number DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| row_to_block(&row)).transpose()
+    }
+
+    /// Indexed blocks at or above `from_block`, newest first
+    pub async fn indexed_blocks_since(
+        &self,
+        from_block: u64,
+    ) -> Result<Vec<(u64, B256)>, anyhow::Error> {
+        let rows = sqlx::query(
+            "SELECT block_number, block_hash FROM collateral_indexed_blocks
+             WHERE block_number >= ?
+             ORDER BY block_number DESC",
+        )
+        .bind(from_block as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(row_to_block).collect()
+    }
+
+    /// Drop everything indexed above `block_number`, returning the number of removed events
+    pub async fn rewind_to(&self, block_number: u64) -> Result<u64, anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM collateral_events WHERE block_number > ?")
+            .bind(block_number as i64)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM collateral_indexed_blocks WHERE block_number > ?")
+            .bind(block_number as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Full event history for an executor, oldest first
+    pub async fn executor_history(
+        &self,
+        executor_id: FixedBytes<16>,
+    ) -> Result<Vec<IndexedEvent>, anyhow::Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {EVENT_COLUMNS} FROM collateral_events
+             WHERE executor_id = ?
+                OR (event_type = 'Denied' AND reclaim_request_id IN (
+                    SELECT reclaim_request_id FROM collateral_events
+                    WHERE event_type = 'ReclaimProcessStarted' AND executor_id = ?))
+             ORDER BY block_number, log_index"
+        ))
+        .bind(executor_id.to_string())
+        .bind(executor_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(row_to_event).collect()
+    }
+
+    /// Full event history for a miner address, oldest first
+    pub async fn miner_history(&self, miner: Address) -> Result<Vec<IndexedEvent>, anyhow::Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {EVENT_COLUMNS} FROM collateral_events
+             WHERE miner = ?
+             ORDER BY block_number, log_index"
+        ))
+        .bind(miner.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(row_to_event).collect()
+    }
+
+    /// Reclaim requests that were neither finalized nor denied
+    pub async fn open_reclaims(&self) -> Result<Vec<IndexedEvent>, anyhow::Error> {
+        self.query_open_reclaims(None).await
+    }
+
+    /// Open reclaim requests whose deny timeout is before `now` (unix seconds)
+    ///
+    /// These can be finalized by the miner and can no longer be denied by the trustee.
+    pub async fn open_reclaims_past_deny_timeout(
+        &self,
+        now: u64,
+    ) -> Result<Vec<IndexedEvent>, anyhow::Error> {
+        self.query_open_reclaims(Some(now)).await
+    }
+
+    async fn query_open_reclaims(
+        &self,
+        expired_before: Option<u64>,
+    ) -> Result<Vec<IndexedEvent>, anyhow::Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {EVENT_COLUMNS} FROM collateral_events started
+             WHERE started.event_type = 'ReclaimProcessStarted'
+               AND (? IS NULL OR started.expiration_time < ?)
+               AND NOT EXISTS (
+                   SELECT 1 FROM collateral_events closed
+                   WHERE closed.reclaim_request_id = started.reclaim_request_id
+                     AND closed.event_type IN ('Reclaimed', 'Denied'))
+             ORDER BY started.block_number, started.log_index"
+        ))
+        .bind(expired_before.map(|t| t as i64))
+        .bind(expired_before.map(|t| t as i64))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(row_to_event).collect()
+    }
+}
+
+fn row_to_block(row: &SqliteRow) -> Result<(u64, B256), anyhow::Error> {
+    let block_number: i64 = row.get("block_number");
+    let block_hash: String = row.get("block_hash");
+    Ok((block_number as u64, block_hash.parse()?))
+}
+
+fn row_to_event(row: &SqliteRow) -> Result<IndexedEvent, anyhow::Error> {
+    fn required<T: std::str::FromStr>(row: &SqliteRow, column: &str) -> Result<T, anyhow::Error>
+    where
+        T::Err: std::fmt::Display,
+    {
+        let value: Option<String> = row.get(column);
+        let value = value.ok_or_else(|| anyhow::anyhow!("Missing column {column}"))?;
+        value
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid {column} {value}: {e}"))
+    }
+
+    let event_type: String = row.get("event_type");
+    let event = match event_type.as_str() {
+        "Deposit" => CollateralEvent::Deposit {
+            executor_id: required(row, "executor_id")?,
+            miner: required(row, "miner")?,
+            amount: required::<U256>(row, "amount")?,
+        },
+        "ReclaimProcessStarted" => CollateralEvent::ReclaimProcessStarted {
+            reclaim_request_id: required(row, "reclaim_request_id")?,
+            executor_id: required(row, "executor_id")?,
+            miner: required(row, "miner")?,
+            amount: required(row, "amount")?,
+            expiration_time: row.get::<Option<i64>, _>("expiration_time").unwrap_or(0) as u64,
+            url: required(row, "url")?,
+            url_content_md5_checksum: required(row, "url_content_md5_checksum")?,
+        },
+        "Reclaimed" => CollateralEvent::Reclaimed {
+            reclaim_request_id: required(row, "reclaim_request_id")?,
+            executor_id: required(row, "executor_id")?,
+            miner: required(row, "miner")?,
+            amount: required(row, "amount")?,
+        },
+        "Denied" => CollateralEvent::Denied {
+            reclaim_request_id: required(row, "reclaim_request_id")?,
+            url: required(row, "url")?,
+            url_content_md5_checksum: required(row, "url_content_md5_checksum")?,
+        },
+        "Slashed" => CollateralEvent::Slashed {
+            executor_id: required(row, "executor_id")?,
+            miner: required(row, "miner")?,
+            amount: required(row, "amount")?,
+            url: required(row, "url")?,
+            url_content_md5_checksum: required(row, "url_content_md5_checksum")?,
+        },
+        other => return Err(anyhow::anyhow!("Unknown collateral event type: {other}")),
+    };
+
+    let block_hash: String = row.get("block_hash");
+    let transaction_hash: String = row.get("transaction_hash");
+
+    Ok(IndexedEvent {
+        block_number: row.get::<i64, _>("block_number") as u64,
+        block_hash: block_hash.parse()?,
+        transaction_hash: transaction_hash.parse()?,
+        log_index: row.get::<i64, _>("log_index") as u64,
+        event,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor_id::executor_id_bytes;
+
+    async fn store() -> CollateralEventStore {
+        CollateralEventStore::connect("sqlite::memory:")
+            .await
+            .expect("Failed to create store")
+    }
+
+    fn indexed(block_number: u64, log_index: u64, event: CollateralEvent) -> IndexedEvent {
+        IndexedEvent {
+            block_number,
+            block_hash: B256::with_last_byte(block_number as u8),
+            transaction_hash: B256::with_last_byte((block_number * 10 + log_index) as u8),
+            log_index,
+            event,
+        }
+    }
+
+    fn reclaim_started(id: u64, executor: u128, expiration_time: u64) -> CollateralEvent {
+        CollateralEvent::ReclaimProcessStarted {
+            reclaim_request_id: U256::from(id),
+            executor_id: executor_id_bytes(executor),
+            miner: Address::with_last_byte(1),
+            amount: U256::from(100),
+            expiration_time,
+            url: "https://example.com/reclaim".to_string(),
+            url_content_md5_checksum: FixedBytes::with_last_byte(7),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_executor_history_round_trip() {
+        let store = store().await;
+        let deposit = CollateralEvent::Deposit {
+            executor_id: executor_id_bytes(42),
+            miner: Address::with_last_byte(1),
+            amount: U256::from(1_000_000_000_000_000_000u128),
+        };
+        let other = CollateralEvent::Deposit {
+            executor_id: executor_id_bytes(43),
+            miner: Address::with_last_byte(2),
+            amount: U256::from(5),
+        };
+        let denied = CollateralEvent::Denied {
+            reclaim_request_id: U256::from(1),
+            url: "https://example.com/deny".to_string(),
+            url_content_md5_checksum: FixedBytes::with_last_byte(9),
+        };
+
+        let events = vec![
+            indexed(1, 0, deposit.clone()),
+            indexed(1, 1, other),
+            indexed(2, 0, reclaim_started(1, 42, 100)),
+            indexed(3, 0, denied.clone()),
+        ];
+        assert_eq!(store.insert_batch(&events, &[]).await.unwrap(), 4);
+        // Re-inserting the same logs is a no-op
+        assert_eq!(store.insert_batch(&events, &[]).await.unwrap(), 0);
+
+        let history = store.executor_history(executor_id_bytes(42)).await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0], events[0]);
+        assert_eq!(history[1], events[2]);
+        assert_eq!(history[2].event, denied);
+    }
+
+    #[tokio::test]
+    async fn test_open_reclaims_past_deny_timeout() {
+        let store = store().await;
+        let events = vec![
+            indexed(1, 0, reclaim_started(1, 42, 100)),
+            indexed(1, 1, reclaim_started(2, 42, 200)),
+            indexed(2, 0, reclaim_started(3, 43, 50)),
+            indexed(
+                3,
+                0,
+                CollateralEvent::Reclaimed {
+                    reclaim_request_id: U256::from(3),
+                    executor_id: executor_id_bytes(43),
+                    miner: Address::with_last_byte(1),
+                    amount: U256::from(100),
+                },
+            ),
+        ];
+        store.insert_batch(&events, &[]).await.unwrap();
+
+        assert_eq!(store.open_reclaims().await.unwrap().len(), 2);
+
+        let expired = store.open_reclaims_past_deny_timeout(150).await.unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].event.reclaim_request_id(), Some(U256::from(1)));
+    }
+
+    #[tokio::test]
+    async fn test_rewind_drops_orphaned_blocks() {
+        let store = store().await;
+        let events = vec![
+            indexed(10, 0, reclaim_started(1, 42, 100)),
+            indexed(12, 0, reclaim_started(2, 42, 100)),
+        ];
+        let blocks = vec![
+            (10, B256::with_last_byte(10)),
+            (12, B256::with_last_byte(12)),
+        ];
+        store.insert_batch(&events, &blocks).await.unwrap();
+        assert_eq!(
+            store.last_indexed_block().await.unwrap(),
+            Some((12, B256::with_last_byte(12)))
+        );
+
+        assert_eq!(store.rewind_to(11).await.unwrap(), 1);
+        assert_eq!(
+            store.last_indexed_block().await.unwrap(),
+            Some((10, B256::with_last_byte(10)))
+        );
+        assert_eq!(store.open_reclaims().await.unwrap().len(), 1);
+    }
+}
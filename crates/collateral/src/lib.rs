@@ -1,10 +1,17 @@
-use alloy_primitives::address;
 use alloy_primitives::{Address, FixedBytes, U256};
-use alloy_provider::ProviderBuilder;
 use alloy_sol_types::sol;
 
-use alloy::signers::Signer;
-use alloy::signers::local::PrivateKeySigner;
+pub mod client;
+pub mod config;
+pub mod executor_id;
+pub mod indexer;
+
+pub use client::CollateralClient;
+pub use config::{CollateralConfig, SignerSource};
+pub use executor_id::{
+    WEI_PER_TAO, executor_id_bytes, executor_id_from_str, tao_to_wei, wei_to_tao,
+};
+pub use indexer::{CollateralEventStore, CollateralIndexer, IndexerConfig};
 
 sol!(
     #[allow(missing_docs)]
@@ -16,14 +23,9 @@ sol!(
     "./src/collateral.json"
 );
 
-// Deployed Collateral contract address
-const COLLATERAL_ADDRESS: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
-const CHAIN_ID: u64 = 42;
-const RPC_URL: &str = "http://localhost:9944";
-
 #[derive(Debug, Clone)]
 pub struct Reclaim {
-    pub executor_id: u16,
+    pub executor_id: u128,
     pub miner: Address,
     pub amount: U256,
     pub deny_timeout: u64,
@@ -32,7 +34,7 @@ pub struct Reclaim {
 impl From<(FixedBytes<16>, Address, U256, u64)> for Reclaim {
     fn from(tuple: (FixedBytes<16>, Address, U256, u64)) -> Self {
         Self {
-            executor_id: u16::from_be_bytes(tuple.0[0..2].try_into().unwrap()),
+            executor_id: u128::from_be_bytes(tuple.0.0),
             miner: tuple.1,
             amount: tuple.2,
             deny_timeout: tuple.3,
@@ -40,197 +42,12 @@ impl From<(FixedBytes<16>, Address, U256, u64)> for Reclaim {
     }
 }
 
-// transactions
-pub async fn deposit(
-    private_key: &str,
-    executor_id: u128,
-    amount: U256,
-) -> Result<(), anyhow::Error> {
-    let mut signer: PrivateKeySigner = private_key.parse()?;
-
-    signer.set_chain_id(Some(CHAIN_ID));
-
-    let provider = ProviderBuilder::new()
-        .wallet(signer)
-        .connect(RPC_URL)
-        .await?;
-
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-
-    let executor_bytes = executor_id.to_be_bytes();
-    let tx = contract
-        .deposit(FixedBytes::from_slice(&executor_bytes))
-        .value(amount);
-    let tx = tx.send().await?;
-    let receipt = tx.get_receipt().await?;
-    tracing::info!("{receipt:?}");
-    Ok(())
-}
-
-pub async fn reclaim_collateral(
-    private_key: &str,
-    executor_id: u128,
-    url: &str,
-    url_content_md5_checksum: u128,
-) -> Result<(), anyhow::Error> {
-    let mut signer: PrivateKeySigner = private_key.parse()?;
-    signer.set_chain_id(Some(CHAIN_ID));
-
-    let provider = ProviderBuilder::new()
-        .wallet(signer)
-        .connect(RPC_URL)
-        .await?;
-
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-
-    let tx = contract.reclaimCollateral(
-        FixedBytes::from_slice(&executor_id.to_be_bytes()),
-        url.to_string(),
-        FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
-    );
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
-    Ok(())
-}
-
-pub async fn finalize_reclaim(
-    private_key: &str,
-    reclaim_request_id: U256,
-) -> Result<(), anyhow::Error> {
-    let mut signer: PrivateKeySigner = private_key.parse()?;
-    signer.set_chain_id(Some(CHAIN_ID));
-
-    let provider = ProviderBuilder::new()
-        .wallet(signer)
-        .connect(RPC_URL)
-        .await?;
-
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-
-    let tx = contract.finalizeReclaim(reclaim_request_id);
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
-    Ok(())
-}
-
-pub async fn deny_reclaim(
-    private_key: &str,
-    reclaim_request_id: U256,
-    url: &str,
-    url_content_md5_checksum: u128,
-) -> Result<(), anyhow::Error> {
-    let mut signer: PrivateKeySigner = private_key.parse()?;
-    signer.set_chain_id(Some(CHAIN_ID));
-
-    let provider = ProviderBuilder::new()
-        .wallet(signer)
-        .connect(RPC_URL)
-        .await?;
-
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-
-    let tx = contract.denyReclaimRequest(
-        reclaim_request_id,
-        url.to_string(),
-        FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
-    );
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
-    Ok(())
-}
-
-pub async fn slash_collateral(
-    private_key: &str,
-    executor_id: u128,
-    url: &str,
-    url_content_md5_checksum: u128,
-) -> Result<(), anyhow::Error> {
-    let mut signer: PrivateKeySigner = private_key.parse()?;
-    signer.set_chain_id(Some(CHAIN_ID));
-
-    let provider = ProviderBuilder::new()
-        .wallet(signer)
-        .connect(RPC_URL)
-        .await?;
-
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-
-    let tx = contract.slashCollateral(
-        FixedBytes::from_slice(&executor_id.to_be_bytes()),
-        url.to_string(),
-        FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
-    );
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
-    Ok(())
-}
-
-// Get methods
-
-pub async fn netuid() -> Result<u16, anyhow::Error> {
-    let provider = ProviderBuilder::new().connect(RPC_URL).await?;
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-    let netuid = contract.NETUID().call().await?;
-    Ok(netuid)
-}
-
-pub async fn trustee() -> Result<Address, anyhow::Error> {
-    let provider = ProviderBuilder::new().connect(RPC_URL).await?;
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-    let trustee = contract.TRUSTEE().call().await?;
-    Ok(trustee)
-}
-
-pub async fn decision_timeout() -> Result<u64, anyhow::Error> {
-    let provider = ProviderBuilder::new().connect(RPC_URL).await?;
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-    let decision_timeout = contract.DECISION_TIMEOUT().call().await?;
-    Ok(decision_timeout)
-}
-
-pub async fn min_collateral_increase() -> Result<U256, anyhow::Error> {
-    let provider = ProviderBuilder::new().connect(RPC_URL).await?;
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-    let min_collateral_increase = contract.MIN_COLLATERAL_INCREASE().call().await?;
-    Ok(min_collateral_increase)
-}
-
-pub async fn executor_to_miner(executor_id: u128) -> Result<Address, anyhow::Error> {
-    let provider = ProviderBuilder::new().connect(RPC_URL).await?;
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-    let executor_to_miner = contract
-        .executorToMiner(FixedBytes::from_slice(&executor_id.to_be_bytes()))
-        .call()
-        .await?;
-    Ok(executor_to_miner)
-}
-
-pub async fn collaterals(executor_id: u128) -> Result<U256, anyhow::Error> {
-    let provider = ProviderBuilder::new().connect(RPC_URL).await?;
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-    let collaterals = contract
-        .collaterals(FixedBytes::from_slice(&executor_id.to_be_bytes()))
-        .call()
-        .await?;
-    Ok(collaterals)
-}
-
-pub async fn reclaims(reclaim_request_id: U256) -> Result<Reclaim, anyhow::Error> {
-    let provider = ProviderBuilder::new().connect(RPC_URL).await?;
-    let contract = Collateral::new(COLLATERAL_ADDRESS, &provider);
-    let result = contract.reclaims(reclaim_request_id).call().await?;
-    let reclaim = Reclaim::from((
-        result.executorId,
-        result.miner,
-        result.amount,
-        result.denyTimeout,
-    ));
-    Ok(reclaim)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use alloy::signers::Signer;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy_provider::ProviderBuilder;
     use bittensor::api::api::{self as bittensorapi};
     use subxt::{OnlineClient, PolkadotConfig};
     use subxt_signer::sr25519::dev;
@@ -281,12 +98,13 @@ mod test {
 
         // get sudo alice signer
         let alithe_private_key = "5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133";
+        let config = CollateralConfig::local(Address::ZERO);
         let mut signer: PrivateKeySigner = alithe_private_key.parse().unwrap();
-        signer.set_chain_id(Some(CHAIN_ID));
+        signer.set_chain_id(Some(config.chain_id));
 
         let provider = ProviderBuilder::new()
             .wallet(signer.clone())
-            .connect("http://localhost:9944")
+            .connect(&config.rpc_url)
             .await
             .unwrap();
 
@@ -312,5 +130,14 @@ mod test {
         contract.NETUID().call().await.unwrap();
         contract.DECISION_TIMEOUT().call().await.unwrap();
         contract.MIN_COLLATERAL_INCREASE().call().await.unwrap();
+
+        // the same deployment through the configured client
+        let client = CollateralClient::new(
+            config
+                .with_contract_address(*contract.address())
+                .with_private_key(alithe_private_key),
+        );
+        assert_eq!(client.trustee().await.unwrap(), trustee);
+        assert_eq!(client.netuid().await.unwrap(), netuid);
     }
 }
@@ -0,0 +1,167 @@
+//! Signed executor evidence
+//!
+//! Executors sign system profile and benchmark responses with their
+//! persistent Ed25519 key over a nonce chosen by the requesting validator.
+//! Intermediaries such as the miner forwarding the response cannot alter it
+//! without invalidating the signature, and a nonce ties each response to one
+//! request so old responses cannot be replayed.
+//!
+//! The signed message is the BLAKE3 hash of a domain tag followed by each
+//! field, length-prefixed, so that field boundaries cannot be shifted.
+
+use super::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use crate::error::CryptoError;
+use ed25519_dalek::PUBLIC_KEY_LENGTH;
+use std::collections::{BTreeMap, HashMap};
+
+/// Domain tag of signed system profiles
+const SYSTEM_PROFILE_DOMAIN: &[u8] = b"basilica/executor/system-profile/v1";
+
+/// Domain tag of signed benchmark results
+const BENCHMARK_DOMAIN: &[u8] = b"basilica/executor/benchmark/v1";
+
+/// Number of random bytes in an evidence nonce
+const NONCE_LENGTH: usize = 32;
+
+/// Generate a hex-encoded nonce for an evidence request
+pub fn generate_evidence_nonce() -> String {
+    hex::encode(rand::random::<[u8; NONCE_LENGTH]>())
+}
+
+/// Message an executor signs for a system profile response
+pub fn system_profile_message(
+    nonce: &str,
+    validator_hotkey: &str,
+    profile_hash: &str,
+    encrypted_profile: &str,
+    encryption_nonce: &str,
+) -> Vec<u8> {
+    let mut hasher = MessageHasher::new(SYSTEM_PROFILE_DOMAIN);
+    hasher.field(nonce.as_bytes());
+    hasher.field(validator_hotkey.as_bytes());
+    hasher.field(profile_hash.as_bytes());
+    hasher.field(encrypted_profile.as_bytes());
+    hasher.field(encryption_nonce.as_bytes());
+    hasher.finish()
+}
+
+/// Message an executor signs for a benchmark response
+///
+/// Results are hashed in key order, so the map's iteration order does not matter.
+pub fn benchmark_message(
+    nonce: &str,
+    validator_hotkey: &str,
+    benchmark_type: &str,
+    score: f64,
+    results: &HashMap<String, String>,
+) -> Vec<u8> {
+    let mut hasher = MessageHasher::new(BENCHMARK_DOMAIN);
+    hasher.field(nonce.as_bytes());
+    hasher.field(validator_hotkey.as_bytes());
+    hasher.field(benchmark_type.as_bytes());
+    hasher.field(&score.to_bits().to_be_bytes());
+
+    let sorted: BTreeMap<&String, &String> = results.iter().collect();
+    hasher.field(&(sorted.len() as u64).to_be_bytes());
+    for (key, value) in sorted {
+        hasher.field(key.as_bytes());
+        hasher.field(value.as_bytes());
+    }
+    hasher.finish()
+}
+
+/// Sign an evidence message, returning the hex-encoded signature
+pub fn sign_evidence(private_key: &Ed25519PrivateKey, message: &[u8]) -> String {
+    hex::encode(private_key.sign(message))
+}
+
+/// Verify a hex-encoded signature from a hex-encoded executor public key
+pub fn verify_evidence(
+    public_key_hex: &str,
+    message: &[u8],
+    signature_hex: &str,
+) -> Result<(), CryptoError> {
+    let key_bytes: [u8; PUBLIC_KEY_LENGTH] = hex::decode(public_key_hex)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| CryptoError::InvalidPublicKey {
+            details: "Executor public key must be 32 hex-encoded bytes".to_string(),
+        })?;
+    let signature = hex::decode(signature_hex).map_err(|e| CryptoError::InvalidSignature {
+        details: format!("Signature is not valid hex: {e}"),
+    })?;
+
+    Ed25519PublicKey::from_bytes(&key_bytes)?.verify(message, &signature)
+}
+
+/// Length-prefixed field hasher
+struct MessageHasher(blake3::Hasher);
+
+impl MessageHasher {
+    fn new(domain: &[u8]) -> Self {
+        let mut hasher = Self(blake3::Hasher::new());
+        hasher.field(domain);
+        hasher
+    }
+
+    fn field(&mut self, bytes: &[u8]) {
+        self.0.update(&(bytes.len() as u64).to_be_bytes());
+        self.0.update(bytes);
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.0.finalize().as_bytes().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> HashMap<String, String> {
+        HashMap::from([
+            ("cpu_threads".to_string(), "8".to_string()),
+            (
+                "cpu_hash_throughput_mb_s".to_string(),
+                "4096.00".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_signed_benchmark_verifies() {
+        let key = Ed25519PrivateKey::generate();
+        let public_key = key.public_key().to_string();
+        let nonce = generate_evidence_nonce();
+
+        let message = benchmark_message(&nonce, "5Validator", "cpu", 4096.0, &results());
+        let signature = sign_evidence(&key, &message);
+        assert!(verify_evidence(&public_key, &message, &signature).is_ok());
+
+        let mut tampered = results();
+        tampered.insert("cpu_threads".to_string(), "64".to_string());
+        let tampered = benchmark_message(&nonce, "5Validator", "cpu", 4096.0, &tampered);
+        assert!(verify_evidence(&public_key, &tampered, &signature).is_err());
+
+        let replayed = benchmark_message(
+            &generate_evidence_nonce(),
+            "5Validator",
+            "cpu",
+            4096.0,
+            &results(),
+        );
+        assert!(verify_evidence(&public_key, &replayed, &signature).is_err());
+
+        let other_key = Ed25519PrivateKey::generate().public_key().to_string();
+        assert!(verify_evidence(&other_key, &message, &signature).is_err());
+    }
+
+    #[test]
+    fn test_profile_fields_are_not_ambiguous() {
+        let a = system_profile_message("n", "v", "ab", "c", "");
+        let b = system_profile_message("n", "v", "a", "bc", "");
+        assert_ne!(a, b);
+
+        assert!(verify_evidence("zz", &a, "00").is_err());
+    }
+}
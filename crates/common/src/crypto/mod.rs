@@ -5,15 +5,19 @@
 //! - P256 (secp256r1) ECDSA operations
 //! - Key derivation functions (PBKDF2, Argon2)
 //! - Hashing and signature verification
+//! - Signed executor evidence
+//! - Signed requests between services
 
 // Core cryptographic functions
 mod core;
 
 // Public submodules
 pub mod ed25519;
+pub mod evidence;
 pub mod kdf;
 pub mod keys;
 pub mod p256;
+pub mod request_signing;
 
 // Re-export core hashing and symmetric encryption from core module
 pub use core::{
@@ -25,6 +29,13 @@ pub use core::{
 
 // Re-export commonly used types and functions
 pub use ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey};
+pub use evidence::{
+    benchmark_message, generate_evidence_nonce, sign_evidence, system_profile_message,
+    verify_evidence,
+};
 pub use kdf::{argon2_derive_key, pbkdf2_derive_key, KdfParams};
 pub use keys::{generate_ed25519_keypair, generate_p256_keypair, generate_p256_keypair_formatted};
 pub use p256::{verify_p256_signature, P256KeyPair, P256PrivateKey, P256PublicKey, P256Signature};
+pub use request_signing::{
+    canonical_request, generate_request_nonce, verify_request_signature, SignatureScheme,
+};
@@ -0,0 +1,185 @@
+//! Signed HTTP requests between services
+//!
+//! The public API gateway signs each request it forwards to a validator with
+//! either its Bittensor hotkey (sr25519) or a standalone Ed25519 service key.
+//! The signature covers the method, path and query, a Unix timestamp, a
+//! single-use nonce and the BLAKE3 hash of the body, so a captured request can
+//! neither be altered nor replayed once the receiver has seen its nonce.
+
+use super::core::verify_bittensor_signature;
+use super::ed25519::Ed25519PublicKey;
+use crate::error::CryptoError;
+use crate::identity::Hotkey;
+use ed25519_dalek::PUBLIC_KEY_LENGTH;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Header carrying the signer's hotkey or hex Ed25519 public key
+pub const SIGNER_HEADER: &str = "x-request-signer";
+/// Header carrying the signature scheme
+pub const SIGNATURE_SCHEME_HEADER: &str = "x-request-signature-scheme";
+/// Header carrying the hex-encoded signature
+pub const SIGNATURE_HEADER: &str = "x-request-signature";
+/// Header carrying the Unix timestamp the request was signed at
+pub const TIMESTAMP_HEADER: &str = "x-request-timestamp";
+/// Header carrying the request nonce
+pub const NONCE_HEADER: &str = "x-request-nonce";
+
+/// Number of random bytes in a request nonce
+const NONCE_LENGTH: usize = 16;
+
+/// Key type a request is signed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    /// Bittensor hotkey, identified by its SS58 address
+    Sr25519,
+    /// Ed25519 service key, identified by its hex-encoded public key
+    Ed25519,
+}
+
+impl SignatureScheme {
+    /// Name used in the scheme header
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignatureScheme::Sr25519 => "sr25519",
+            SignatureScheme::Ed25519 => "ed25519",
+        }
+    }
+}
+
+impl fmt::Display for SignatureScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SignatureScheme {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sr25519" => Ok(SignatureScheme::Sr25519),
+            "ed25519" => Ok(SignatureScheme::Ed25519),
+            other => Err(CryptoError::InvalidSignature {
+                details: format!("Unknown signature scheme: {other}"),
+            }),
+        }
+    }
+}
+
+/// Generate a hex-encoded nonce for a signed request
+pub fn generate_request_nonce() -> String {
+    hex::encode(rand::random::<[u8; NONCE_LENGTH]>())
+}
+
+/// Canonical string signed for a request
+///
+/// `path_and_query` is the request target as the receiver sees it, for
+/// example `/rentals?status=active`. Requests without a body hash an empty one.
+pub fn canonical_request(
+    method: &str,
+    path_and_query: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &[u8],
+) -> String {
+    format!(
+        "{}\n{}\n{}\n{}\n{}",
+        method.to_ascii_uppercase(),
+        path_and_query,
+        timestamp,
+        nonce,
+        blake3::hash(body).to_hex()
+    )
+}
+
+/// Verify a hex-encoded signature over a canonical request
+pub fn verify_request_signature(
+    scheme: SignatureScheme,
+    signer: &str,
+    canonical: &str,
+    signature_hex: &str,
+) -> Result<(), CryptoError> {
+    match scheme {
+        SignatureScheme::Sr25519 => {
+            let hotkey = Hotkey::new(signer.to_string())
+                .map_err(|details| CryptoError::InvalidPublicKey { details })?;
+            verify_bittensor_signature(&hotkey, signature_hex, canonical.as_bytes())
+        }
+        SignatureScheme::Ed25519 => {
+            let signature =
+                hex::decode(signature_hex).map_err(|e| CryptoError::InvalidSignature {
+                    details: format!("Signature is not valid hex: {e}"),
+                })?;
+            parse_ed25519_signer(signer)?.verify(canonical.as_bytes(), &signature)
+        }
+    }
+}
+
+/// Parse the hex-encoded public key identifying an Ed25519 signer
+pub fn parse_ed25519_signer(signer: &str) -> Result<Ed25519PublicKey, CryptoError> {
+    let key_bytes: [u8; PUBLIC_KEY_LENGTH] = hex::decode(signer)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| CryptoError::InvalidPublicKey {
+            details: "Ed25519 signer must be a 32-byte hex-encoded public key".to_string(),
+        })?;
+    Ed25519PublicKey::from_bytes(&key_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ed25519::Ed25519PrivateKey;
+
+    #[test]
+    fn test_ed25519_request_signature() {
+        let key = Ed25519PrivateKey::generate();
+        let signer = key.public_key().to_string();
+        let nonce = generate_request_nonce();
+
+        let canonical = canonical_request("post", "/rentals", 1_700_000_000, &nonce, b"{}");
+        assert!(canonical.starts_with("POST\n/rentals\n1700000000\n"));
+        let signature = hex::encode(key.sign(canonical.as_bytes()));
+        assert!(verify_request_signature(
+            SignatureScheme::Ed25519,
+            &signer,
+            &canonical,
+            &signature
+        )
+        .is_ok());
+
+        // Any change to the signed request invalidates the signature
+        for tampered in [
+            canonical_request("POST", "/rentals", 1_700_000_000, &nonce, b"{\"a\":1}"),
+            canonical_request("POST", "/rentals?all=1", 1_700_000_000, &nonce, b"{}"),
+            canonical_request("POST", "/rentals", 1_700_000_001, &nonce, b"{}"),
+            canonical_request("DELETE", "/rentals", 1_700_000_000, &nonce, b"{}"),
+        ] {
+            assert!(verify_request_signature(
+                SignatureScheme::Ed25519,
+                &signer,
+                &tampered,
+                &signature
+            )
+            .is_err());
+        }
+
+        let other = Ed25519PrivateKey::generate().public_key().to_string();
+        assert!(
+            verify_request_signature(SignatureScheme::Ed25519, &other, &canonical, &signature)
+                .is_err()
+        );
+        assert!(parse_ed25519_signer("not-hex").is_err());
+    }
+
+    #[test]
+    fn test_signature_scheme_names() {
+        for scheme in [SignatureScheme::Sr25519, SignatureScheme::Ed25519] {
+            assert_eq!(scheme.as_str().parse::<SignatureScheme>().unwrap(), scheme);
+        }
+        assert!("rsa".parse::<SignatureScheme>().is_err());
+    }
+}
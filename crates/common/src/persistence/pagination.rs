@@ -3,7 +3,9 @@
 //! Pagination structures and utilities for database queries.
 //! Provides standard pagination types and enhanced utilities.
 
-use serde::{Deserialize, Serialize};
+use crate::error::ValidationError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// Page request structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -191,6 +193,155 @@ impl<T> PaginatedResponse<T> {
     }
 }
 
+/// Keyset cursor pointing just past the last item of a page
+///
+/// `key` is the sort value of that item and `id` breaks ties between equal
+/// keys. Unlike an offset, the cursor stays valid when items are added or
+/// removed between page requests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor<K> {
+    pub key: K,
+    pub id: String,
+    /// Ordering the cursor was issued for, empty for listings with a single ordering
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sort: String,
+}
+
+impl<K: Serialize + DeserializeOwned> Cursor<K> {
+    /// Create a new cursor
+    pub fn new(key: K, id: impl Into<String>) -> Self {
+        Self {
+            key,
+            id: id.into(),
+            sort: String::new(),
+        }
+    }
+
+    /// Tie the cursor to the ordering of the listing that issued it
+    pub fn with_sort(mut self, sort: impl Into<String>) -> Self {
+        self.sort = sort.into();
+        self
+    }
+
+    /// Encode as an opaque, URL-safe token
+    pub fn encode(&self) -> String {
+        // Serializing a plain struct of a key and a string cannot fail
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decode a token produced by [`Cursor::encode`]
+    pub fn decode(token: &str) -> Result<Self, ValidationError> {
+        let invalid = || ValidationError::InvalidFormat {
+            field: "cursor".to_string(),
+            value: token.to_string(),
+        };
+
+        let json = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+        serde_json::from_slice(&json).map_err(|_| invalid())
+    }
+
+    /// Decode a token, rejecting cursors issued for another ordering
+    ///
+    /// Keys of different orderings are not comparable, so resuming with
+    /// another one would skip or repeat items.
+    pub fn decode_for(token: &str, sort: &str) -> Result<Self, ValidationError> {
+        let cursor = Self::decode(token)?;
+        if cursor.sort != sort {
+            return Err(ValidationError::ConstraintViolation {
+                field: "cursor".to_string(),
+                constraint: format!("issued for ordering {}, not {}", cursor.sort, sort),
+            });
+        }
+        Ok(cursor)
+    }
+}
+
+/// Cursor-paginated response wrapper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    /// Token for the following page, absent on the last page
+    pub next_cursor: Option<String>,
+    /// Number of items across all pages
+    pub total_count: u64,
+}
+
+impl<T> CursorPage<T> {
+    /// Take the page of up to `limit` items following `after` from a sorted list
+    ///
+    /// `position` returns the cursor of an item and `cmp` orders two cursors
+    /// the same way `items` is sorted.
+    pub fn from_sorted<K, P, C>(
+        items: Vec<T>,
+        after: Option<&Cursor<K>>,
+        limit: u32,
+        position: P,
+        cmp: C,
+    ) -> Self
+    where
+        K: Serialize + DeserializeOwned,
+        P: Fn(&T) -> Cursor<K>,
+        C: Fn(&Cursor<K>, &Cursor<K>) -> std::cmp::Ordering,
+    {
+        let total_count = items.len() as u64;
+        let limit = limit.max(1) as usize;
+
+        let mut remaining = items
+            .into_iter()
+            .filter(|item| {
+                after.map_or(true, |after| {
+                    cmp(&position(item), after) == std::cmp::Ordering::Greater
+                })
+            })
+            .peekable();
+
+        let page: Vec<T> = remaining.by_ref().take(limit).collect();
+        let next_cursor = if remaining.peek().is_some() {
+            page.last().map(|last| position(last).encode())
+        } else {
+            None
+        };
+
+        Self {
+            items: page,
+            next_cursor,
+            total_count,
+        }
+    }
+
+    /// Build a page from items fetched in order after the cursor
+    ///
+    /// Fetching `limit + 1` items tells whether another page follows without
+    /// a second query. `total_count` counts the items across all pages.
+    pub fn from_fetched<K, P>(mut items: Vec<T>, limit: u32, total_count: u64, position: P) -> Self
+    where
+        K: Serialize + DeserializeOwned,
+        P: Fn(&T) -> Cursor<K>,
+    {
+        let limit = limit.max(1) as usize;
+        let has_more = items.len() > limit;
+        items.truncate(limit);
+
+        let next_cursor = if has_more {
+            items.last().map(|last| position(last).encode())
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            next_cursor,
+            total_count,
+        }
+    }
+
+    /// Check if there's a next page
+    pub fn has_next_page(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,4 +452,70 @@ mod tests {
         assert_eq!(default_request.page_number, 1);
         assert_eq!(default_request.page_size, 50);
     }
+
+    fn by_key(a: &Cursor<u32>, b: &Cursor<u32>) -> std::cmp::Ordering {
+        a.key.cmp(&b.key).then_with(|| a.id.cmp(&b.id))
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::new(4.5f64, "miner1__exec1");
+        let token = cursor.encode();
+        assert!(!token.contains('/') && !token.contains('+') && !token.contains('='));
+        assert_eq!(Cursor::<f64>::decode(&token).unwrap(), cursor);
+
+        assert!(Cursor::<f64>::decode("not a cursor").is_err());
+        assert!(Cursor::<f64>::decode(&URL_SAFE_NO_PAD.encode("{}")).is_err());
+    }
+
+    #[test]
+    fn test_cursor_sort_must_match() {
+        let token = Cursor::new(4.5f64, "miner1__exec1")
+            .with_sort("price:asc")
+            .encode();
+
+        let cursor = Cursor::<f64>::decode_for(&token, "price:asc").unwrap();
+        assert_eq!(cursor.sort, "price:asc");
+        assert!(Cursor::<f64>::decode_for(&token, "price:desc").is_err());
+        assert!(Cursor::<f64>::decode_for(&token, "score:asc").is_err());
+
+        let unsorted = Cursor::new(4.5f64, "miner1__exec1").encode();
+        assert!(Cursor::<f64>::decode_for(&unsorted, "price:asc").is_err());
+    }
+
+    #[test]
+    fn test_cursor_page_from_fetched() {
+        let position = |item: &(u32, &str)| Cursor::new(item.0, item.1);
+
+        let page = CursorPage::from_fetched(vec![(1, "a"), (2, "b"), (3, "c")], 2, 7, position);
+        assert_eq!(page.items, vec![(1, "a"), (2, "b")]);
+        assert_eq!(page.total_count, 7);
+        let next = Cursor::<u32>::decode(page.next_cursor.as_ref().unwrap()).unwrap();
+        assert_eq!(next, Cursor::new(2, "b"));
+
+        let last = CursorPage::from_fetched(vec![(3, "c")], 2, 7, position);
+        assert!(!last.has_next_page());
+    }
+
+    #[test]
+    fn test_cursor_page_walks_all_items() {
+        let items = vec![(1, "a"), (2, "a"), (2, "b"), (3, "a"), (5, "c")];
+        let position = |item: &(u32, &str)| Cursor::new(item.0, item.1);
+
+        let first = CursorPage::from_sorted(items.clone(), None, 2, position, by_key);
+        assert_eq!(first.items, vec![(1, "a"), (2, "a")]);
+        assert_eq!(first.total_count, 5);
+        assert!(first.has_next_page());
+
+        let after = Cursor::decode(first.next_cursor.as_ref().unwrap()).unwrap();
+        let second = CursorPage::from_sorted(items.clone(), Some(&after), 2, position, by_key);
+        assert_eq!(second.items, vec![(2, "b"), (3, "a")]);
+
+        // Removing an already listed item does not shift the next page
+        let shrunk: Vec<_> = items.iter().copied().filter(|i| *i != (1, "a")).collect();
+        let after = Cursor::decode(second.next_cursor.as_ref().unwrap()).unwrap();
+        let last = CursorPage::from_sorted(shrunk, Some(&after), 2, position, by_key);
+        assert_eq!(last.items, vec![(5, "c")]);
+        assert!(!last.has_next_page());
+    }
 }
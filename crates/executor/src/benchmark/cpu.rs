@@ -0,0 +1,99 @@
+//! CPU benchmark
+//!
+//! Measures multi-threaded BLAKE3 hashing throughput. Every thread hashes its
+//! own block repeatedly and feeds each digest back into the block, so the work
+//! cannot be skipped or shared between iterations.
+
+use super::types::{mb_per_sec, BenchmarkParams, BenchmarkResult};
+use anyhow::{anyhow, Result};
+use std::time::{Duration, Instant};
+
+/// Default size of the block each thread hashes, in KB
+const DEFAULT_BLOCK_SIZE_KB: u64 = 1024;
+
+/// Largest block size a request may ask for, in KB
+const MAX_BLOCK_SIZE_KB: u64 = 64 * 1024;
+
+/// Multi-threaded hashing benchmark
+#[derive(Debug, Clone)]
+pub struct CpuBenchmark {
+    duration: Duration,
+    threads: usize,
+    block_size: usize,
+}
+
+impl CpuBenchmark {
+    /// Read `threads` and `block_size_kb` from the request
+    pub fn from_params(params: &BenchmarkParams) -> Result<Self> {
+        let available = num_cpus::get() as u64;
+        let threads = params.get_u64("threads", available, 1, available.max(1) * 4)?;
+        let block_size_kb =
+            params.get_u64("block_size_kb", DEFAULT_BLOCK_SIZE_KB, 1, MAX_BLOCK_SIZE_KB)?;
+
+        Ok(Self {
+            duration: params.duration,
+            threads: threads as usize,
+            block_size: (block_size_kb * 1024) as usize,
+        })
+    }
+
+    /// Hash on every thread until the duration elapses
+    pub fn run(&self) -> Result<BenchmarkResult> {
+        let start = Instant::now();
+        let deadline = start + self.duration;
+
+        let per_thread: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.threads)
+                .map(|index| scope.spawn(move || hash_until(deadline, self.block_size, index)))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .map_err(|_| anyhow!("CPU benchmark thread panicked"))
+                })
+                .collect::<Result<Vec<u64>>>()
+        })?;
+        let elapsed = start.elapsed();
+
+        let total_bytes: u64 = per_thread.iter().sum();
+        let throughput = mb_per_sec(total_bytes, elapsed);
+        let slowest = per_thread.iter().copied().min().unwrap_or(0);
+
+        let mut result = BenchmarkResult::new(throughput);
+        result.insert("cpu_hash_algorithm", "blake3");
+        result.insert("cpu_threads", self.threads);
+        result.insert("cpu_block_size_kb", self.block_size / 1024);
+        result.insert("cpu_hashed_bytes", total_bytes);
+        result.insert_f64("cpu_hash_throughput_mb_s", throughput);
+        result.insert_f64(
+            "cpu_hash_throughput_per_thread_mb_s",
+            throughput / self.threads as f64,
+        );
+        result.insert_f64(
+            "cpu_hash_throughput_slowest_thread_mb_s",
+            mb_per_sec(slowest, elapsed),
+        );
+        result.insert("duration_ms", elapsed.as_millis());
+        Ok(result)
+    }
+}
+
+/// Hash a block repeatedly until `deadline`, returning the bytes hashed
+fn hash_until(deadline: Instant, block_size: usize, seed: usize) -> u64 {
+    let mut block = vec![seed as u8; block_size];
+    let mut hashed = 0u64;
+
+    loop {
+        let digest = blake3::hash(&block);
+        let len = block.len().min(32);
+        block[..len].copy_from_slice(&digest.as_bytes()[..len]);
+        hashed += block_size as u64;
+
+        if Instant::now() >= deadline {
+            return hashed;
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Disk benchmark
+//!
+//! Runs against a scratch file in the configured data directory: a sequential
+//! write, a sequential read, then random reads and random writes. The duration
+//! is split evenly between the four phases. Before each read phase the file is
+//! synced and, on Linux, its pages are dropped from the page cache so reads hit
+//! the device rather than memory.
+
+use super::types::{mb_per_sec, per_sec, BenchmarkParams, BenchmarkResult, MB};
+use crate::config::BenchmarkConfig;
+use anyhow::{anyhow, Context, Result};
+use rand::Rng;
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Default size of sequential I/O requests, in KB
+const DEFAULT_BLOCK_SIZE_KB: u64 = 1024;
+
+/// Default size of random I/O requests, in KB
+const DEFAULT_IO_SIZE_KB: u64 = 4;
+
+/// Sequential throughput and random IOPS benchmark
+#[derive(Debug, Clone)]
+pub struct DiskBenchmark {
+    duration: Duration,
+    data_dir: PathBuf,
+    file_size: u64,
+    block_size: usize,
+    io_size: usize,
+}
+
+impl DiskBenchmark {
+    /// Read `file_size_mb`, `block_size_kb` and `io_size_kb` from the request
+    pub fn from_params(config: &BenchmarkConfig, params: &BenchmarkParams) -> Result<Self> {
+        let file_size_mb = params.get_u64(
+            "file_size_mb",
+            config.disk_file_size_mb,
+            1,
+            config.max_size_mb,
+        )?;
+        let block_size_kb = params.get_u64("block_size_kb", DEFAULT_BLOCK_SIZE_KB, 4, 64 * 1024)?;
+        let io_size_kb = params.get_u64("io_size_kb", DEFAULT_IO_SIZE_KB, 1, 1024)?;
+
+        if block_size_kb > file_size_mb * 1024 || io_size_kb > file_size_mb * 1024 {
+            return Err(anyhow!(
+                "Disk benchmark block and I/O sizes must not exceed the file size"
+            ));
+        }
+
+        Ok(Self {
+            duration: params.duration,
+            data_dir: config.data_dir.clone(),
+            file_size: file_size_mb * MB,
+            block_size: (block_size_kb * 1024) as usize,
+            io_size: (io_size_kb * 1024) as usize,
+        })
+    }
+
+    /// Run all phases, removing the scratch file afterwards
+    pub fn run(&self) -> Result<BenchmarkResult> {
+        std::fs::create_dir_all(&self.data_dir).with_context(|| {
+            format!(
+                "Failed to create benchmark directory {}",
+                self.data_dir.display()
+            )
+        })?;
+
+        let path = self
+            .data_dir
+            .join(format!("disk-benchmark-{}.dat", uuid::Uuid::new_v4()));
+        let result = self.run_phases(&path);
+
+        if let Err(e) = std::fs::remove_file(&path) {
+            tracing::warn!(
+                "Failed to remove disk benchmark file {}: {}",
+                path.display(),
+                e
+            );
+        }
+
+        result
+    }
+
+    fn run_phases(&self, path: &Path) -> Result<BenchmarkResult> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .with_context(|| format!("Failed to create benchmark file {}", path.display()))?;
+        let phase = self.duration / 4;
+
+        let (written, write_elapsed) = self.sequential_write(&file, phase)?;
+        drop_cache(&file);
+        // Random I/O only touches data that was actually written
+        if written < self.io_size as u64 {
+            return Err(anyhow!(
+                "Disk benchmark wrote {} bytes, less than one random I/O request",
+                written
+            ));
+        }
+        let span = written;
+
+        let (read, read_elapsed) = self.sequential_read(&file, written, phase)?;
+        drop_cache(&file);
+        let (random_reads, random_read_elapsed) = self.random_io(&file, span, phase, false)?;
+        let (random_writes, random_write_elapsed) = self.random_io(&file, span, phase, true)?;
+
+        let write_throughput = mb_per_sec(written, write_elapsed);
+        let read_throughput = mb_per_sec(read, read_elapsed);
+        let random_read_iops = per_sec(random_reads, random_read_elapsed);
+        let random_write_iops = per_sec(random_writes, random_write_elapsed);
+
+        let mut result = BenchmarkResult::new(random_read_iops + random_write_iops);
+        result.insert("disk_path", self.data_dir.display());
+        result.insert("disk_file_size_mb", self.file_size / MB);
+        result.insert("disk_block_size_kb", self.block_size / 1024);
+        result.insert("disk_io_size_kb", self.io_size / 1024);
+        result.insert_f64("disk_seq_write_mb_s", write_throughput);
+        result.insert_f64("disk_seq_read_mb_s", read_throughput);
+        result.insert_f64("disk_random_read_iops", random_read_iops);
+        result.insert_f64("disk_random_write_iops", random_write_iops);
+        result.insert("disk_seq_written_bytes", written);
+        result.insert("disk_seq_read_bytes", read);
+        result.insert(
+            "duration_ms",
+            (write_elapsed + read_elapsed + random_read_elapsed + random_write_elapsed).as_millis(),
+        );
+        Ok(result)
+    }
+
+    /// Fill the file front to back until it is full or the phase ends
+    fn sequential_write(&self, file: &File, phase: Duration) -> Result<(u64, Duration)> {
+        let block: Vec<u8> = (0..self.block_size).map(|i| (i % 251) as u8).collect();
+        let start = Instant::now();
+        let deadline = start + phase;
+        let mut offset = 0u64;
+
+        while offset < self.file_size {
+            let len = (self.file_size - offset).min(self.block_size as u64) as usize;
+            file.write_all_at(&block[..len], offset)
+                .context("Sequential write failed")?;
+            offset += len as u64;
+
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+        file.sync_all().context("Failed to sync benchmark file")?;
+
+        Ok((offset, start.elapsed()))
+    }
+
+    /// Read the written part of the file front to back, wrapping around until the phase ends
+    fn sequential_read(
+        &self,
+        file: &File,
+        written: u64,
+        phase: Duration,
+    ) -> Result<(u64, Duration)> {
+        let mut block = vec![0u8; self.block_size];
+        let start = Instant::now();
+        let deadline = start + phase;
+        let mut offset = 0u64;
+        let mut read = 0u64;
+
+        if written == 0 {
+            return Ok((0, start.elapsed()));
+        }
+
+        loop {
+            let len = (written - offset).min(self.block_size as u64) as usize;
+            file.read_exact_at(&mut block[..len], offset)
+                .context("Sequential read failed")?;
+            read += len as u64;
+            offset += len as u64;
+            if offset >= written {
+                offset = 0;
+            }
+
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+
+        Ok((read, start.elapsed()))
+    }
+
+    /// Aligned random reads or writes within `span` bytes until the phase ends
+    ///
+    /// Writes are synced before the clock stops so buffered writes are not
+    /// counted as completed I/O.
+    fn random_io(
+        &self,
+        file: &File,
+        span: u64,
+        phase: Duration,
+        write: bool,
+    ) -> Result<(u64, Duration)> {
+        let mut buffer = vec![0xa5u8; self.io_size];
+        let slots = span / self.io_size as u64;
+        let mut rng = rand::thread_rng();
+        let start = Instant::now();
+        let deadline = start + phase;
+        let mut ops = 0u64;
+
+        loop {
+            let offset = rng.gen_range(0..slots) * self.io_size as u64;
+            if write {
+                file.write_all_at(&buffer, offset)
+                    .context("Random write failed")?;
+            } else {
+                file.read_exact_at(&mut buffer, offset)
+                    .context("Random read failed")?;
+            }
+            ops += 1;
+
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+
+        if write {
+            file.sync_data().context("Failed to sync benchmark file")?;
+        }
+
+        Ok((ops, start.elapsed()))
+    }
+}
+
+/// Ask the kernel to evict the file's cached pages
+#[cfg(target_os = "linux")]
+fn drop_cache(file: &File) {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: the descriptor is owned by `file` and stays open for the call
+    let ret = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    if ret != 0 {
+        tracing::debug!("posix_fadvise on disk benchmark file failed: {}", ret);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn drop_cache(_file: &File) {}
@@ -0,0 +1,130 @@
+//! Memory bandwidth benchmark
+//!
+//! Copies a buffer into a second buffer for the first half of the duration
+//! and sums it for the second half, with the buffers split between threads so
+//! that every memory channel is kept busy. Buffers should be much larger than
+//! the CPU caches for the numbers to reflect main memory.
+
+use super::types::{mb_per_sec, BenchmarkParams, BenchmarkResult, MB};
+use crate::config::BenchmarkConfig;
+use anyhow::{anyhow, Result};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Memory copy and read bandwidth benchmark
+#[derive(Debug, Clone)]
+pub struct MemoryBenchmark {
+    duration: Duration,
+    buffer_size: usize,
+    threads: usize,
+}
+
+impl MemoryBenchmark {
+    /// Read `buffer_mb` and `threads` from the request
+    pub fn from_params(config: &BenchmarkConfig, params: &BenchmarkParams) -> Result<Self> {
+        let buffer_mb =
+            params.get_u64("buffer_mb", config.memory_buffer_mb, 1, config.max_size_mb)?;
+        let available = num_cpus::get() as u64;
+        let threads = params.get_u64("threads", available, 1, available.max(1) * 4)?;
+
+        Ok(Self {
+            duration: params.duration,
+            buffer_size: (buffer_mb * MB) as usize,
+            threads: threads as usize,
+        })
+    }
+
+    /// Measure copy and read bandwidth
+    pub fn run(&self) -> Result<BenchmarkResult> {
+        let src: Vec<u64> = (0..self.buffer_size / 8).map(|i| i as u64).collect();
+        let mut dst = vec![0u64; src.len()];
+        let chunk_len = src.len().div_ceil(self.threads).max(1);
+        let phase = self.duration / 2;
+
+        let (copied, copy_elapsed) = self.run_phase(phase, |deadline| {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = src
+                    .chunks(chunk_len)
+                    .zip(dst.chunks_mut(chunk_len))
+                    .map(|(src, dst)| scope.spawn(move || copy_until(deadline, src, dst)))
+                    .collect();
+                join_all(handles)
+            })
+        })?;
+
+        let (read, read_elapsed) = self.run_phase(phase, |deadline| {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = dst
+                    .chunks(chunk_len)
+                    .map(|chunk| scope.spawn(move || read_until(deadline, chunk)))
+                    .collect();
+                join_all(handles)
+            })
+        })?;
+
+        let copy_bandwidth = mb_per_sec(copied, copy_elapsed);
+        let read_bandwidth = mb_per_sec(read, read_elapsed);
+
+        let mut result = BenchmarkResult::new(copy_bandwidth);
+        result.insert("memory_buffer_mb", self.buffer_size as u64 / MB);
+        result.insert("memory_threads", self.threads);
+        result.insert_f64("memory_copy_bandwidth_mb_s", copy_bandwidth);
+        result.insert_f64("memory_read_bandwidth_mb_s", read_bandwidth);
+        result.insert("memory_copied_bytes", copied);
+        result.insert("memory_read_bytes", read);
+        result.insert("duration_ms", (copy_elapsed + read_elapsed).as_millis());
+        Ok(result)
+    }
+
+    fn run_phase<F>(&self, duration: Duration, phase: F) -> Result<(u64, Duration)>
+    where
+        F: FnOnce(Instant) -> Result<u64>,
+    {
+        let start = Instant::now();
+        let bytes = phase(start + duration)?;
+        Ok((bytes, start.elapsed()))
+    }
+}
+
+fn join_all(handles: Vec<std::thread::ScopedJoinHandle<'_, u64>>) -> Result<u64> {
+    handles.into_iter().try_fold(0u64, |total, handle| {
+        handle
+            .join()
+            .map(|bytes| total + bytes)
+            .map_err(|_| anyhow!("Memory benchmark thread panicked"))
+    })
+}
+
+/// Copy `src` into `dst` until `deadline`, returning the bytes read and written
+fn copy_until(deadline: Instant, src: &[u64], dst: &mut [u64]) -> u64 {
+    let pass_bytes = (src.len() * 8 * 2) as u64;
+    let mut bytes = 0u64;
+
+    loop {
+        dst.copy_from_slice(black_box(src));
+        black_box(&mut *dst);
+        bytes += pass_bytes;
+
+        if Instant::now() >= deadline {
+            return bytes;
+        }
+    }
+}
+
+/// Sum `buffer` until `deadline`, returning the bytes read
+fn read_until(deadline: Instant, buffer: &[u64]) -> u64 {
+    let pass_bytes = (buffer.len() * 8) as u64;
+    let mut bytes = 0u64;
+
+    loop {
+        let sum = black_box(buffer)
+            .iter()
+            .fold(0u64, |sum, value| sum.wrapping_add(*value));
+        black_box(sum);
+        bytes += pass_bytes;
+
+        if Instant::now() >= deadline {
+            return bytes;
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Hardware benchmarks for the Basilca Executor
+//!
+//! Measures CPU, memory, disk and network performance on behalf of
+//! validators. Benchmarks are serialized so concurrent requests do not skew
+//! each other's numbers.
+
+pub mod cpu;
+pub mod disk;
+pub mod memory;
+pub mod network;
+pub mod types;
+
+use crate::config::BenchmarkConfig;
+use anyhow::{anyhow, Result};
+use cpu::CpuBenchmark;
+use disk::DiskBenchmark;
+use memory::MemoryBenchmark;
+use network::NetworkBenchmark;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tracing::info;
+
+pub use types::*;
+
+/// A benchmark with validated parameters, ready to run
+#[derive(Debug, Clone)]
+pub enum Benchmark {
+    Cpu(CpuBenchmark),
+    Memory(MemoryBenchmark),
+    Disk(DiskBenchmark),
+    Network(NetworkBenchmark),
+}
+
+impl Benchmark {
+    /// Name of the benchmark type as used in requests
+    pub fn name(&self) -> &'static str {
+        match self {
+            Benchmark::Cpu(_) => "cpu",
+            Benchmark::Memory(_) => "memory",
+            Benchmark::Disk(_) => "disk",
+            Benchmark::Network(_) => "network",
+        }
+    }
+}
+
+/// Runs benchmarks one at a time
+pub struct BenchmarkRunner {
+    config: BenchmarkConfig,
+    running: Mutex<()>,
+}
+
+impl BenchmarkRunner {
+    /// Create new benchmark runner
+    pub fn new(config: BenchmarkConfig) -> Self {
+        Self {
+            config,
+            running: Mutex::new(()),
+        }
+    }
+
+    /// Validate a request and prepare the benchmark it asks for
+    pub fn prepare(
+        &self,
+        benchmark_type: &str,
+        duration_seconds: u64,
+        parameters: HashMap<String, String>,
+    ) -> Result<Benchmark> {
+        let params = BenchmarkParams::from_request(&self.config, duration_seconds, parameters)?;
+
+        match benchmark_type {
+            "cpu" => Ok(Benchmark::Cpu(CpuBenchmark::from_params(&params)?)),
+            "memory" => Ok(Benchmark::Memory(MemoryBenchmark::from_params(
+                &self.config,
+                &params,
+            )?)),
+            "disk" => Ok(Benchmark::Disk(DiskBenchmark::from_params(
+                &self.config,
+                &params,
+            )?)),
+            "network" => Ok(Benchmark::Network(NetworkBenchmark::from_params(
+                &self.config,
+                &params,
+            )?)),
+            other => Err(anyhow!("Unknown benchmark type: {}", other)),
+        }
+    }
+
+    /// Run a prepared benchmark once no other benchmark is running
+    ///
+    /// CPU, memory and disk benchmarks block, so they run on the blocking pool.
+    pub async fn run(&self, benchmark: Benchmark) -> Result<BenchmarkResult> {
+        let _guard = self.running.lock().await;
+        info!("Running {} benchmark", benchmark.name());
+
+        let result = match benchmark {
+            Benchmark::Cpu(benchmark) => {
+                tokio::task::spawn_blocking(move || benchmark.run()).await??
+            }
+            Benchmark::Memory(benchmark) => {
+                tokio::task::spawn_blocking(move || benchmark.run()).await??
+            }
+            Benchmark::Disk(benchmark) => {
+                tokio::task::spawn_blocking(move || benchmark.run()).await??
+            }
+            Benchmark::Network(benchmark) => benchmark.run().await?,
+        };
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::IpAddr;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn runner(data_dir: &std::path::Path) -> BenchmarkRunner {
+        BenchmarkRunner::new(BenchmarkConfig {
+            data_dir: data_dir.to_path_buf(),
+            allow_private_network_targets: true,
+            ..Default::default()
+        })
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_request_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = runner(dir.path());
+
+        assert!(runner.prepare("cpu", 0, HashMap::new()).is_ok());
+        assert!(runner.prepare("cpu", 10_000, HashMap::new()).is_err());
+        assert!(runner
+            .prepare("cpu", 1, params(&[("threads", "0")]))
+            .is_err());
+        assert!(runner
+            .prepare("memory", 1, params(&[("buffer_mb", "lots")]))
+            .is_err());
+        assert!(runner
+            .prepare(
+                "disk",
+                1,
+                params(&[("file_size_mb", "1"), ("block_size_kb", "4096")])
+            )
+            .is_err());
+        assert!(runner.prepare("network", 1, HashMap::new()).is_err());
+        assert!(runner.prepare("quantum", 1, HashMap::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cpu_memory_and_disk_benchmarks_measure() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = runner(dir.path());
+
+        let cpu = runner
+            .prepare(
+                "cpu",
+                1,
+                params(&[("threads", "2"), ("block_size_kb", "64")]),
+            )
+            .unwrap();
+        let result = runner.run(cpu).await.unwrap();
+        assert!(result.score > 0.0);
+        assert_eq!(result.metrics["cpu_threads"], "2");
+
+        let memory = runner
+            .prepare("memory", 1, params(&[("buffer_mb", "8"), ("threads", "2")]))
+            .unwrap();
+        let result = runner.run(memory).await.unwrap();
+        assert!(result.score > 0.0);
+        assert!(result.metrics.contains_key("memory_read_bandwidth_mb_s"));
+
+        let disk = runner
+            .prepare("disk", 1, params(&[("file_size_mb", "4")]))
+            .unwrap();
+        let result = runner.run(disk).await.unwrap();
+        assert!(result.score > 0.0);
+        assert!(result.metrics.contains_key("disk_seq_write_mb_s"));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_network_benchmark_against_sink() {
+        let dir = tempfile::tempdir().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 64 * 1024];
+                    while matches!(socket.read(&mut buf).await, Ok(n) if n > 0) {}
+                });
+            }
+        });
+
+        let runner = runner(dir.path());
+        let network = runner
+            .prepare(
+                "network",
+                1,
+                params(&[("target", &target), ("latency_samples", "3")]),
+            )
+            .unwrap();
+        let result = runner.run(network).await.unwrap();
+        assert!(result.score > 0.0);
+        assert_eq!(result.metrics["network_latency_samples"], "3");
+
+        let strict = BenchmarkRunner::new(BenchmarkConfig::default());
+        let network = strict
+            .prepare("network", 1, params(&[("target", &target)]))
+            .unwrap();
+        assert!(strict.run(network).await.is_err());
+
+        assert!(network::is_private_address(
+            &"169.254.169.254".parse::<IpAddr>().unwrap()
+        ));
+        assert!(!network::is_private_address(
+            &"8.8.8.8".parse::<IpAddr>().unwrap()
+        ));
+    }
+}
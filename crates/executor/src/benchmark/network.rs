@@ -0,0 +1,162 @@
+//! Network benchmark
+//!
+//! Measures TCP connect latency and upload throughput to a target supplied by
+//! the validator in the `target` parameter (`host:port`). The target is
+//! expected to accept connections and discard whatever it receives.
+
+use super::types::{BenchmarkParams, BenchmarkResult};
+use crate::config::BenchmarkConfig;
+use anyhow::{anyhow, Context, Result};
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+/// Default number of connections timed for latency
+const DEFAULT_LATENCY_SAMPLES: u64 = 10;
+
+/// Default size of each upload write, in KB
+const DEFAULT_CHUNK_SIZE_KB: u64 = 64;
+
+/// Latency and upload throughput benchmark
+#[derive(Debug, Clone)]
+pub struct NetworkBenchmark {
+    duration: Duration,
+    target: String,
+    latency_samples: u64,
+    chunk_size: usize,
+    connect_timeout: Duration,
+    allow_private_targets: bool,
+}
+
+impl NetworkBenchmark {
+    /// Read `target`, `latency_samples` and `chunk_size_kb` from the request
+    pub fn from_params(config: &BenchmarkConfig, params: &BenchmarkParams) -> Result<Self> {
+        let target = params
+            .get("target")
+            .ok_or_else(|| anyhow!("Network benchmark requires a target parameter (host:port)"))?
+            .to_string();
+        let latency_samples = params.get_u64("latency_samples", DEFAULT_LATENCY_SAMPLES, 1, 100)?;
+        let chunk_size_kb = params.get_u64("chunk_size_kb", DEFAULT_CHUNK_SIZE_KB, 1, 1024)?;
+
+        Ok(Self {
+            duration: params.duration,
+            target,
+            latency_samples,
+            chunk_size: (chunk_size_kb * 1024) as usize,
+            connect_timeout: Duration::from_secs(config.network_connect_timeout_secs),
+            allow_private_targets: config.allow_private_network_targets,
+        })
+    }
+
+    /// Time connections to the target, then upload to it until the duration elapses
+    pub async fn run(&self) -> Result<BenchmarkResult> {
+        let addr = self.resolve_target().await?;
+
+        let mut latencies = Vec::with_capacity(self.latency_samples as usize);
+        for _ in 0..self.latency_samples {
+            let start = Instant::now();
+            let stream = self.connect(addr).await?;
+            latencies.push(start.elapsed().as_secs_f64() * 1000.0);
+            drop(stream);
+        }
+        latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let (sent, upload_elapsed) = self.upload(addr).await?;
+        let upload_mbit_s = if upload_elapsed.as_secs_f64() > 0.0 {
+            sent as f64 * 8.0 / 1_000_000.0 / upload_elapsed.as_secs_f64()
+        } else {
+            0.0
+        };
+
+        let mut result = BenchmarkResult::new(upload_mbit_s);
+        result.insert("network_target", &self.target);
+        result.insert("network_target_addr", addr);
+        result.insert("network_latency_samples", latencies.len());
+        result.insert_f64("network_latency_min_ms", latencies[0]);
+        result.insert_f64("network_latency_p50_ms", latencies[latencies.len() / 2]);
+        result.insert_f64("network_latency_max_ms", latencies[latencies.len() - 1]);
+        result.insert_f64(
+            "network_latency_avg_ms",
+            latencies.iter().sum::<f64>() / latencies.len() as f64,
+        );
+        result.insert_f64("network_upload_mbit_s", upload_mbit_s);
+        result.insert("network_upload_bytes", sent);
+        result.insert("duration_ms", upload_elapsed.as_millis());
+        Ok(result)
+    }
+
+    async fn resolve_target(&self) -> Result<SocketAddr> {
+        let addr = tokio::net::lookup_host(&self.target)
+            .await
+            .with_context(|| format!("Failed to resolve benchmark target {}", self.target))?
+            .next()
+            .ok_or_else(|| anyhow!("Benchmark target {} did not resolve", self.target))?;
+
+        if !self.allow_private_targets && is_private_address(&addr.ip()) {
+            return Err(anyhow!(
+                "Benchmark target {} resolves to non-public address {}",
+                self.target,
+                addr.ip()
+            ));
+        }
+
+        Ok(addr)
+    }
+
+    async fn connect(&self, addr: SocketAddr) -> Result<TcpStream> {
+        tokio::time::timeout(self.connect_timeout, TcpStream::connect(addr))
+            .await
+            .map_err(|_| anyhow!("Timed out connecting to benchmark target {}", addr))?
+            .with_context(|| format!("Failed to connect to benchmark target {addr}"))
+    }
+
+    /// Write to the target until the duration elapses, returning the bytes written
+    async fn upload(&self, addr: SocketAddr) -> Result<(u64, Duration)> {
+        let mut stream = self.connect(addr).await?;
+        stream.set_nodelay(true)?;
+
+        let chunk = vec![0u8; self.chunk_size];
+        let start = Instant::now();
+        let deadline = tokio::time::Instant::from_std(start + self.duration);
+        let mut sent = 0u64;
+
+        loop {
+            match tokio::time::timeout_at(deadline, stream.write_all(&chunk)).await {
+                Ok(Ok(())) => sent += chunk.len() as u64,
+                Ok(Err(e)) => {
+                    return Err(anyhow!("Upload to benchmark target {} failed: {}", addr, e))
+                }
+                Err(_) => break,
+            }
+        }
+        let elapsed = start.elapsed();
+        let _ = stream.shutdown().await;
+
+        Ok((sent, elapsed))
+    }
+}
+
+/// Whether an address is loopback, private, link-local or otherwise not publicly routable
+pub fn is_private_address(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+        }
+        IpAddr::V6(ip) => {
+            let first = ip.segments()[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || ip
+                    .to_ipv4_mapped()
+                    .map(|v4| is_private_address(&IpAddr::V4(v4)))
+                    .unwrap_or(false)
+        }
+    }
+}
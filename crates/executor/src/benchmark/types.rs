@@ -0,0 +1,124 @@
+//! Benchmark parameters and results
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::BenchmarkConfig;
+
+/// Bytes in a megabyte as used by benchmark sizes and throughputs
+pub const MB: u64 = 1024 * 1024;
+
+/// Validated duration and raw parameters of a benchmark request
+#[derive(Debug, Clone)]
+pub struct BenchmarkParams {
+    pub duration: Duration,
+    parameters: HashMap<String, String>,
+}
+
+impl BenchmarkParams {
+    /// Build parameters from a request, applying the configured duration limits
+    ///
+    /// A zero duration selects the configured default.
+    pub fn from_request(
+        config: &BenchmarkConfig,
+        duration_seconds: u64,
+        parameters: HashMap<String, String>,
+    ) -> Result<Self> {
+        let duration_seconds = if duration_seconds == 0 {
+            config.default_duration_secs
+        } else {
+            duration_seconds
+        };
+
+        if duration_seconds > config.max_duration_secs {
+            return Err(anyhow!(
+                "Benchmark duration {}s exceeds the maximum of {}s",
+                duration_seconds,
+                config.max_duration_secs
+            ));
+        }
+
+        Ok(Self {
+            duration: Duration::from_secs(duration_seconds),
+            parameters,
+        })
+    }
+
+    /// Raw string parameter
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Integer parameter within `min..=max`, or `default` when absent
+    pub fn get_u64(&self, name: &str, default: u64, min: u64, max: u64) -> Result<u64> {
+        let value = match self.get(name) {
+            Some(raw) => raw
+                .parse::<u64>()
+                .map_err(|_| anyhow!("Parameter {} must be an integer, got: {}", name, raw))?,
+            None => default,
+        };
+
+        if value < min || value > max {
+            return Err(anyhow!(
+                "Parameter {} must be between {} and {}, got: {}",
+                name,
+                min,
+                max,
+                value
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
+/// Measured outcome of a benchmark
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkResult {
+    /// Headline number of the benchmark, higher is better
+    pub score: f64,
+    /// Individual measurements keyed by metric name, units in the name
+    pub metrics: HashMap<String, String>,
+}
+
+impl BenchmarkResult {
+    /// Create a result with the given score
+    pub fn new(score: f64) -> Self {
+        Self {
+            score,
+            metrics: HashMap::new(),
+        }
+    }
+
+    /// Record a metric
+    pub fn insert(&mut self, name: &str, value: impl ToString) {
+        self.metrics.insert(name.to_string(), value.to_string());
+    }
+
+    /// Record a floating point metric rounded to two decimals
+    pub fn insert_f64(&mut self, name: &str, value: f64) {
+        self.insert(name, format!("{value:.2}"));
+    }
+}
+
+/// Throughput in MB/s for `bytes` moved in `elapsed`
+pub fn mb_per_sec(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0.0;
+    }
+    bytes as f64 / MB as f64 / secs
+}
+
+/// Operations per second for `ops` completed in `elapsed`
+pub fn per_sec(ops: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0.0;
+    }
+    ops as f64 / secs
+}
@@ -0,0 +1,88 @@
+//! Benchmark configuration
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Benchmark configuration
+///
+/// Bounds what a validator can request through `ExecuteBenchmark` and where
+/// the disk benchmark writes its scratch file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BenchmarkConfig {
+    /// Directory on the data volume used for disk benchmarks
+    pub data_dir: PathBuf,
+
+    /// Duration used when a request does not specify one, in seconds
+    pub default_duration_secs: u64,
+
+    /// Longest duration a request may ask for, in seconds
+    pub max_duration_secs: u64,
+
+    /// Default size of the disk benchmark file in MB
+    pub disk_file_size_mb: u64,
+
+    /// Default size of the memory benchmark buffer in MB
+    pub memory_buffer_mb: u64,
+
+    /// Largest file or buffer size a request may ask for, in MB
+    pub max_size_mb: u64,
+
+    /// Timeout for connecting to a network benchmark target, in seconds
+    pub network_connect_timeout_secs: u64,
+
+    /// Allow network benchmarks against loopback, private and link-local addresses
+    pub allow_private_network_targets: bool,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: PathBuf::from("/var/lib/basilica/executor/benchmark"),
+            default_duration_secs: 10,
+            max_duration_secs: 120,
+            disk_file_size_mb: 256,
+            memory_buffer_mb: 256,
+            max_size_mb: 4096,
+            network_connect_timeout_secs: 5,
+            allow_private_network_targets: false,
+        }
+    }
+}
+
+/// Benchmark configuration validation trait
+pub trait BenchmarkConfigValidation {
+    fn validate_benchmark_limits(&self) -> Result<(), String>;
+}
+
+impl BenchmarkConfigValidation for BenchmarkConfig {
+    fn validate_benchmark_limits(&self) -> Result<(), String> {
+        if self.default_duration_secs == 0 {
+            return Err("Default benchmark duration must be greater than 0".to_string());
+        }
+
+        if self.default_duration_secs > self.max_duration_secs {
+            return Err(format!(
+                "Default benchmark duration ({}s) exceeds maximum duration ({}s)",
+                self.default_duration_secs, self.max_duration_secs
+            ));
+        }
+
+        if self.disk_file_size_mb == 0 || self.memory_buffer_mb == 0 {
+            return Err("Benchmark file and buffer sizes must be greater than 0".to_string());
+        }
+
+        if self.disk_file_size_mb > self.max_size_mb || self.memory_buffer_mb > self.max_size_mb {
+            return Err(format!(
+                "Benchmark file and buffer sizes must not exceed {} MB",
+                self.max_size_mb
+            ));
+        }
+
+        if self.network_connect_timeout_secs == 0 {
+            return Err("Network benchmark connect timeout must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 //! Docker configuration types and validation
 
+use crate::container_manager::network_policy::Ipv4Cidr;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Docker configuration
@@ -31,6 +33,18 @@ pub struct DockerConfig {
 
     /// Container registry configuration
     pub registry: ContainerRegistryConfig,
+
+    /// Rules an image must satisfy before it is pulled and run
+    #[serde(default)]
+    pub image_policy: ImagePolicyConfig,
+
+    /// Named volumes validators can create and attach to containers
+    #[serde(default)]
+    pub volumes: VolumeConfig,
+
+    /// Limits on files copied into and out of containers over gRPC
+    #[serde(default)]
+    pub file_transfer: FileTransferConfig,
 }
 
 /// Container resource limits
@@ -66,6 +80,156 @@ pub struct ContainerNetworkConfig {
 
     /// Port mapping rules
     pub port_mappings: Vec<PortMapping>,
+
+    /// Network modes a container request may choose; `host` and
+    /// `container:<id>` are refused even if listed
+    #[serde(default = "default_allowed_network_modes")]
+    pub allowed_network_modes: Vec<String>,
+
+    /// Outbound traffic filtering for containers with network access
+    #[serde(default)]
+    pub egress: EgressPolicyConfig,
+
+    /// Host ports container requests may publish on; without a range only
+    /// ports Docker assigns are allowed
+    #[serde(default)]
+    pub host_port_range: Option<HostPortRange>,
+}
+
+/// Inclusive range of host ports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostPortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+fn default_allowed_network_modes() -> Vec<String> {
+    vec!["none".to_string(), "bridge".to_string()]
+}
+
+impl ContainerNetworkConfig {
+    /// Network mode of a container that does not request one
+    ///
+    /// Containers publishing ports are given `bridge` so they can be reached,
+    /// all others follow `enable_isolation`.
+    pub fn default_network_mode(&self, publishes_ports: bool) -> String {
+        if publishes_ports {
+            "bridge".to_string()
+        } else if self.enable_isolation {
+            "none".to_string()
+        } else {
+            "default".to_string()
+        }
+    }
+
+    /// Network mode for a container, checking a requested mode against
+    /// `allowed_network_modes`
+    pub fn resolve_network_mode(
+        &self,
+        requested: Option<&str>,
+        publishes_ports: bool,
+    ) -> Result<String, String> {
+        let Some(mode) = requested.map(str::trim).filter(|mode| !mode.is_empty()) else {
+            return Ok(self.default_network_mode(publishes_ports));
+        };
+
+        if shares_host_namespace(mode) {
+            return Err(format!("Network mode {mode} is never allowed"));
+        }
+        if !self
+            .allowed_network_modes
+            .iter()
+            .any(|allowed| allowed == mode)
+        {
+            return Err(format!(
+                "Network mode {mode} is not allowed on this executor"
+            ));
+        }
+        if mode == "none" && publishes_ports {
+            return Err("Ports cannot be published with network mode none".to_string());
+        }
+        Ok(mode.to_string())
+    }
+
+    /// Check a host port requested for a container, where 0 lets Docker
+    /// assign a free port
+    pub fn check_host_port(&self, port: u16) -> Result<(), String> {
+        if port == 0 {
+            return Ok(());
+        }
+        match self.host_port_range {
+            Some(range) if (range.start..=range.end).contains(&port) => Ok(()),
+            Some(range) => Err(format!(
+                "Host port {port} is outside the allowed range {}-{}",
+                range.start, range.end
+            )),
+            None => Err(format!(
+                "Host port {port} cannot be requested, this executor only assigns ports itself"
+            )),
+        }
+    }
+}
+
+/// Whether a network mode shares the network stack of the host or another container
+fn shares_host_namespace(mode: &str) -> bool {
+    mode == "host" || mode.starts_with("container:")
+}
+
+/// Egress filtering for containers
+///
+/// When enabled, every container with network access gets a Docker network
+/// of its own whose outbound traffic passes an iptables chain built from
+/// these rules and is shaped to `resource_limits.network_bps`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EgressPolicyConfig {
+    /// Filter and shape container egress
+    pub enabled: bool,
+
+    /// Destinations containers may reach, any public address when empty;
+    /// rules requested for a container must fall within these
+    pub allowed: Vec<EgressRule>,
+
+    /// Drop traffic to private, loopback, link-local and shared address
+    /// ranges, including the host's own networks
+    pub block_private_ranges: bool,
+
+    /// Drop traffic to cloud instance metadata endpoints
+    pub block_metadata_endpoints: bool,
+
+    /// Path to the `iptables` binary
+    pub iptables_path: PathBuf,
+
+    /// Path to the `tc` binary used for bandwidth caps
+    pub tc_path: PathBuf,
+}
+
+impl Default for EgressPolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed: vec![],
+            block_private_ranges: true,
+            block_metadata_endpoints: true,
+            iptables_path: PathBuf::from("iptables"),
+            tc_path: PathBuf::from("tc"),
+        }
+    }
+}
+
+/// Destination a container may send traffic to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EgressRule {
+    /// IPv4 address or CIDR block, e.g. `203.0.113.0/24`
+    pub cidr: String,
+
+    /// Destination ports, every port when empty
+    #[serde(default)]
+    pub ports: Vec<u16>,
+
+    /// `tcp` or `udp`, both when unset
+    #[serde(default)]
+    pub protocol: Option<String>,
 }
 
 /// Port mapping configuration
@@ -111,6 +275,9 @@ impl Default for DockerConfig {
             max_concurrent_containers: 10,
             enable_gpu_passthrough: true,
             registry: ContainerRegistryConfig::default(),
+            image_policy: ImagePolicyConfig::default(),
+            volumes: VolumeConfig::default(),
+            file_transfer: FileTransferConfig::default(),
         }
     }
 }
@@ -134,6 +301,9 @@ impl Default for ContainerNetworkConfig {
             allow_internet: false,
             dns_servers: vec!["8.8.8.8".to_string(), "8.8.4.4".to_string()],
             port_mappings: vec![],
+            allowed_network_modes: default_allowed_network_modes(),
+            egress: EgressPolicyConfig::default(),
+            host_port_range: None,
         }
     }
 }
@@ -154,6 +324,119 @@ impl Default for ContainerRegistryConfig {
     }
 }
 
+/// Container image policy
+///
+/// Registries are allowed through `registry.allowed_registries`; the rules
+/// here narrow that further.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImagePolicyConfig {
+    /// Registries or repository prefixes that are always rejected, e.g.
+    /// `docker.io` or `ghcr.io/some-org`
+    pub denied_registries: Vec<String>,
+
+    /// Only accept images pinned by digest (`image@sha256:...`)
+    pub require_digest: bool,
+
+    /// Largest image accepted, in bytes
+    pub max_image_size_bytes: Option<u64>,
+
+    /// Cosign public keys an image signature must verify against when
+    /// `registry.verify_signatures` is set
+    pub signature_public_keys: Vec<PathBuf>,
+
+    /// Path to the `cosign` binary used to verify signatures
+    pub cosign_path: PathBuf,
+}
+
+impl Default for ImagePolicyConfig {
+    fn default() -> Self {
+        Self {
+            denied_registries: vec![],
+            require_digest: false,
+            max_image_size_bytes: None,
+            signature_public_keys: vec![],
+            cosign_path: PathBuf::from("cosign"),
+        }
+    }
+}
+
+/// Named volumes that outlive a single container
+///
+/// Volumes belong to the validator that created them. Usage is measured
+/// periodically and containers writing past a volume's quota are stopped;
+/// drivers that can enforce a size themselves receive the quota through
+/// `size_option` as well.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VolumeConfig {
+    /// Allow validators to create and attach volumes
+    pub enabled: bool,
+
+    /// Docker volume driver
+    pub driver: String,
+
+    /// Driver option that receives the quota in bytes, e.g. `size`
+    pub size_option: Option<String>,
+
+    /// Quota of volumes created without one, in bytes
+    pub default_quota_bytes: u64,
+
+    /// Largest quota a single volume may have, in bytes
+    pub max_quota_bytes: u64,
+
+    /// Most volumes a single validator may own
+    pub max_volumes_per_owner: u32,
+
+    /// Largest sum of the quotas of one validator's volumes, in bytes
+    pub max_total_bytes_per_owner: u64,
+
+    /// Seconds a volume is kept once its owner has no active access
+    pub orphan_grace_period_secs: u64,
+
+    /// Image used to copy volume contents when taking snapshots
+    pub helper_image: String,
+}
+
+impl Default for VolumeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            driver: "local".to_string(),
+            size_option: None,
+            default_quota_bytes: 10 * 1024 * 1024 * 1024, // 10GB
+            max_quota_bytes: 100 * 1024 * 1024 * 1024,    // 100GB
+            max_volumes_per_owner: 16,
+            max_total_bytes_per_owner: 500 * 1024 * 1024 * 1024, // 500GB
+            orphan_grace_period_secs: 24 * 3600,
+            helper_image: "busybox:1.36".to_string(),
+        }
+    }
+}
+
+/// File transfer limits
+///
+/// Uploads are held in memory until their checksum is verified, so
+/// `max_file_bytes` bounds the memory a single upload can take.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileTransferConfig {
+    /// Largest file accepted for upload or served for download, in bytes
+    pub max_file_bytes: u64,
+
+    /// Size of the data chunks sent on download, in bytes
+    pub chunk_size_bytes: usize,
+}
+
+impl Default for FileTransferConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 256 * 1024 * 1024, // 256MB
+            chunk_size_bytes: 64 * 1024,       // 64KB
+        }
+    }
+}
+
 /// Docker configuration validation trait
 pub trait DockerConfigValidation {
     fn validate_resource_limits(&self) -> Result<(), String>;
@@ -176,6 +459,36 @@ impl DockerConfigValidation for DockerConfig {
             return Err("CPU cores must be greater than 0".to_string());
         }
 
+        let volumes = &self.volumes;
+        if volumes.enabled {
+            if volumes.default_quota_bytes == 0 || volumes.max_quota_bytes == 0 {
+                return Err("Volume quotas must be greater than 0".to_string());
+            }
+
+            if volumes.default_quota_bytes > volumes.max_quota_bytes {
+                return Err("Default volume quota exceeds the maximum volume quota".to_string());
+            }
+
+            if volumes.max_volumes_per_owner == 0 {
+                return Err("Must allow at least 1 volume per validator".to_string());
+            }
+
+            if volumes.helper_image.is_empty() {
+                return Err("Volume helper image must not be empty".to_string());
+            }
+        }
+
+        if self.file_transfer.max_file_bytes == 0 {
+            return Err("Maximum file transfer size must be greater than 0".to_string());
+        }
+
+        // Chunks must fit in a gRPC message, which tonic caps at 4MB by default
+        if self.file_transfer.chunk_size_bytes == 0
+            || self.file_transfer.chunk_size_bytes > 1024 * 1024
+        {
+            return Err("File transfer chunk size must be between 1 byte and 1MB".to_string());
+        }
+
         Ok(())
     }
 
@@ -190,6 +503,22 @@ impl DockerConfigValidation for DockerConfig {
             }
         }
 
+        if let Some(range) = self.network_config.host_port_range {
+            if range.start == 0 || range.start > range.end {
+                return Err("Host port range must be a non-empty range of valid ports".to_string());
+            }
+        }
+
+        for mode in &self.network_config.allowed_network_modes {
+            if shares_host_namespace(mode) {
+                return Err(format!("Network mode {mode} cannot be allowed"));
+            }
+        }
+
+        for rule in &self.network_config.egress.allowed {
+            validate_egress_rule(rule)?;
+        }
+
         Ok(())
     }
 
@@ -198,6 +527,19 @@ impl DockerConfigValidation for DockerConfig {
             return Err("At least one registry must be allowed".to_string());
         }
 
+        if self.image_policy.max_image_size_bytes == Some(0) {
+            return Err("Maximum image size must be greater than 0".to_string());
+        }
+
+        if self
+            .image_policy
+            .signature_public_keys
+            .iter()
+            .any(|key| key.as_os_str().is_empty())
+        {
+            return Err("Signature public key paths must not be empty".to_string());
+        }
+
         Ok(())
     }
 
@@ -210,6 +552,24 @@ impl DockerConfigValidation for DockerConfig {
 
         if !self.registry.verify_signatures {
             warnings.push("Image signature verification is disabled - may be insecure".to_string());
+        } else if self.image_policy.signature_public_keys.is_empty() {
+            warnings.push(
+                "No signature public keys configured - image signatures are not verified"
+                    .to_string(),
+            );
+        }
+
+        let egress = &self.network_config.egress;
+        if egress.enabled && !egress.block_metadata_endpoints {
+            warnings.push(
+                "Containers can reach cloud metadata endpoints - may leak host credentials"
+                    .to_string(),
+            );
+        }
+
+        if self.volumes.enabled && self.volumes.orphan_grace_period_secs == 0 {
+            warnings
+                .push("Volumes are deleted as soon as their owner's access expires".to_string());
         }
 
         if self.max_concurrent_containers > 50 {
@@ -220,3 +580,28 @@ impl DockerConfigValidation for DockerConfig {
         warnings
     }
 }
+
+/// Check that an egress rule names a valid destination, ports and protocol
+pub fn validate_egress_rule(rule: &EgressRule) -> Result<(), String> {
+    Ipv4Cidr::parse(&rule.cidr).map_err(|e| format!("Invalid egress rule: {e}"))?;
+
+    if rule.ports.contains(&0) {
+        return Err(format!("Egress rule for {} has port 0", rule.cidr));
+    }
+
+    // iptables multiport matches at most 15 ports
+    if rule.ports.len() > 15 {
+        return Err(format!(
+            "Egress rule for {} lists more than 15 ports",
+            rule.cidr
+        ));
+    }
+
+    if let Some(protocol) = &rule.protocol {
+        if !["tcp", "udp"].contains(&protocol.as_str()) {
+            return Err("Egress rule protocol must be 'tcp' or 'udp'".to_string());
+        }
+    }
+
+    Ok(())
+}
@@ -6,12 +6,14 @@
 //! - Interface Segregation: Specific traits for different concerns
 //! - Dependency Inversion: Abstractions over concrete implementations
 
+pub mod benchmark;
 pub mod docker;
 pub mod system;
 pub mod types;
 pub mod validation;
 
 // Re-exports for convenience
+pub use benchmark::*;
 pub use docker::*;
 pub use system::*;
 pub use types::*;
@@ -1,6 +1,7 @@
 //! System monitoring configuration
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// System monitoring configuration
@@ -38,6 +39,10 @@ pub struct SystemConfig {
 
     /// Enable metrics recording
     pub enable_metrics_recording: bool,
+
+    /// GPU backend selection
+    #[serde(default)]
+    pub gpu: GpuBackendConfig,
 }
 
 impl Default for SystemConfig {
@@ -53,6 +58,46 @@ impl Default for SystemConfig {
             max_gpu_memory_usage: 90.0,
             min_disk_space_gb: 10,
             enable_metrics_recording: true,
+            gpu: GpuBackendConfig::default(),
+        }
+    }
+}
+
+/// Source of GPU information
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuBackendKind {
+    /// NVML if it initializes, otherwise ROCm-SMI if it runs, otherwise NVML
+    #[default]
+    Auto,
+    /// NVIDIA Management Library
+    Nvml,
+    /// AMD `rocm-smi --json`
+    Rocm,
+    /// GPUs read from a JSON fixture file
+    Mock,
+}
+
+/// GPU backend configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GpuBackendConfig {
+    /// Backend to query
+    pub backend: GpuBackendKind,
+
+    /// Path to the `rocm-smi` binary
+    pub rocm_smi_path: PathBuf,
+
+    /// JSON list of GPUs reported by the mock backend
+    pub fixture_path: Option<PathBuf>,
+}
+
+impl Default for GpuBackendConfig {
+    fn default() -> Self {
+        Self {
+            backend: GpuBackendKind::Auto,
+            rocm_smi_path: PathBuf::from("rocm-smi"),
+            fixture_path: None,
         }
     }
 }
@@ -99,6 +144,10 @@ impl SystemConfigValidation for SystemConfig {
             return Err("Minimum disk space must be greater than 0".to_string());
         }
 
+        if self.gpu.backend == GpuBackendKind::Mock && self.gpu.fixture_path.is_none() {
+            return Err("Mock GPU backend requires gpu.fixture_path".to_string());
+        }
+
         Ok(())
     }
 
@@ -3,13 +3,13 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use common::config::{loader, LoggingConfig, MetricsConfig, ServerConfig};
+use common::config::{loader, DatabaseConfig, LoggingConfig, MetricsConfig, ServerConfig};
 use common::identity::Hotkey;
 use std::str::FromStr;
 
-use super::{DockerConfig, SystemConfig};
+use super::{BenchmarkConfig, DockerConfig, SystemConfig};
 use crate::validation_session::ValidatorConfig;
 
 /// Advertised endpoint configuration for executor
@@ -59,6 +59,29 @@ pub struct ExecutorConfig {
     /// Advertised endpoint configuration
     #[serde(default)]
     pub advertised_endpoint: ExecutorAdvertisedEndpoint,
+
+    /// Benchmark configuration
+    #[serde(default)]
+    pub benchmark: BenchmarkConfig,
+
+    /// Ed25519 key used to sign profile and benchmark responses, generated on first start
+    #[serde(default = "default_signing_key_path")]
+    pub signing_key_path: PathBuf,
+
+    /// SQLite database recording containers, validator access and SSH keys across restarts
+    #[serde(default = "default_database_config")]
+    pub database: DatabaseConfig,
+}
+
+fn default_signing_key_path() -> PathBuf {
+    PathBuf::from("./data/executor_signing_key.pem")
+}
+
+fn default_database_config() -> DatabaseConfig {
+    DatabaseConfig {
+        url: "sqlite:./data/executor.db".to_string(),
+        ..Default::default()
+    }
 }
 
 impl Default for ExecutorConfig {
@@ -80,6 +103,9 @@ impl Default for ExecutorConfig {
             )
             .unwrap(), // Default Alice hotkey
             advertised_endpoint: ExecutorAdvertisedEndpoint::default(),
+            benchmark: BenchmarkConfig::default(),
+            signing_key_path: default_signing_key_path(),
+            database: default_database_config(),
         }
     }
 }
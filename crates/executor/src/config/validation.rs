@@ -3,7 +3,9 @@
 use common::config::ConfigValidation;
 use common::error::ConfigurationError;
 
-use super::{DockerConfigValidation, ExecutorConfig, SystemConfigValidation};
+use super::{
+    BenchmarkConfigValidation, DockerConfigValidation, ExecutorConfig, SystemConfigValidation,
+};
 
 impl ConfigValidation for ExecutorConfig {
     type Error = ConfigurationError;
@@ -54,6 +56,15 @@ impl ConfigValidation for ExecutorConfig {
             }
         })?;
 
+        // Validate benchmark limits
+        self.benchmark.validate_benchmark_limits().map_err(|msg| {
+            ConfigurationError::InvalidValue {
+                key: "benchmark".to_string(),
+                value: "limits".to_string(),
+                reason: msg,
+            }
+        })?;
+
         // Validate validator configuration if enabled
         if self.validator.enabled {
             // Basic validation for validator config
@@ -1,5 +1,6 @@
 //! Container configuration builder
 
+use super::types::ContainerCreateOptions;
 use crate::config::{ContainerResourceLimits, DockerConfig};
 use anyhow::Result;
 use bollard::{
@@ -26,12 +27,39 @@ impl ContainerConfigBuilder {
         command: &[String],
         limits: &ContainerResourceLimits,
     ) -> Result<Config<String>> {
-        let host_config = self.build_host_config(limits)?;
-        let config = self.build_container_config(image, command, host_config)?;
+        self.build_with_options(
+            image,
+            command,
+            limits,
+            &ContainerCreateOptions::default(),
+            None,
+        )
+    }
+
+    /// Build a container config with requester supplied options
+    ///
+    /// `gpu_devices` restricts the container to the given GPU UUIDs or
+    /// indices, `None` exposes every GPU when passthrough is enabled.
+    pub fn build_with_options(
+        &self,
+        image: &str,
+        command: &[String],
+        limits: &ContainerResourceLimits,
+        options: &ContainerCreateOptions,
+        gpu_devices: Option<&[String]>,
+    ) -> Result<Config<String>> {
+        let host_config = self.build_host_config(limits, options, gpu_devices)?;
+        let config =
+            self.build_container_config(image, command, host_config, options, gpu_devices)?;
         Ok(config)
     }
 
-    fn build_host_config(&self, limits: &ContainerResourceLimits) -> Result<HostConfig> {
+    fn build_host_config(
+        &self,
+        limits: &ContainerResourceLimits,
+        options: &ContainerCreateOptions,
+        gpu_devices: Option<&[String]>,
+    ) -> Result<HostConfig> {
         Ok(HostConfig {
             memory: Some(limits.memory_bytes as i64),
             nano_cpus: Some((limits.cpu_cores * 1_000_000_000.0) as i64),
@@ -40,11 +68,16 @@ impl ContainerConfigBuilder {
                 maximum_retry_count: None,
             }),
             security_opt: Some(self.build_security_options()),
-            network_mode: Some(self.build_network_mode()),
-            mounts: Some(self.build_mounts()),
-            device_requests: self.build_device_requests(),
+            network_mode: Some(
+                options
+                    .network_mode
+                    .clone()
+                    .unwrap_or_else(|| self.build_network_mode()),
+            ),
+            mounts: Some(self.build_mounts(options)),
+            device_requests: self.build_device_requests(gpu_devices),
             devices: self.build_device_mappings(),
-            port_bindings: self.build_port_bindings(),
+            port_bindings: self.build_port_bindings(options),
             // ulimits: Some(self.build_ulimits()), // Commented out due to API differences
             dns: Some(self.config.network_config.dns_servers.clone()),
             cap_drop: Some(self.build_capability_drops()),
@@ -70,15 +103,12 @@ impl ContainerConfigBuilder {
         security_opts
     }
 
-    fn build_network_mode(&self) -> String {
-        if self.config.network_config.enable_isolation {
-            "none".to_string()
-        } else {
-            "default".to_string()
-        }
+    /// Network mode used when the requester does not choose one
+    pub fn build_network_mode(&self) -> String {
+        self.config.network_config.default_network_mode(false)
     }
 
-    fn build_mounts(&self) -> Vec<Mount> {
+    fn build_mounts(&self, options: &ContainerCreateOptions) -> Vec<Mount> {
         let mut mounts = vec![
             // Temporary filesystem for /tmp
             Mount {
@@ -124,15 +154,32 @@ impl ContainerConfigBuilder {
             },
         ]);
 
+        mounts.extend(options.volumes.iter().map(|volume| Mount {
+            target: Some(volume.container_path.clone()),
+            source: Some(volume.volume_id.clone()),
+            typ: Some(MountTypeEnum::VOLUME),
+            read_only: Some(volume.read_only),
+            ..Default::default()
+        }));
+
         mounts
     }
 
-    fn build_device_requests(&self) -> Option<Vec<bollard::models::DeviceRequest>> {
+    fn build_device_requests(
+        &self,
+        gpu_devices: Option<&[String]>,
+    ) -> Option<Vec<bollard::models::DeviceRequest>> {
         if self.config.enable_gpu_passthrough {
+            // Docker rejects a request that sets both a count and device IDs
+            let (count, device_ids) = match gpu_devices {
+                Some([]) => return None,
+                Some(devices) => (None, Some(devices.to_vec())),
+                None => (Some(-1), None), // All available GPUs
+            };
             Some(vec![bollard::models::DeviceRequest {
                 driver: Some("nvidia".to_string()),
-                count: Some(-1),  // All available GPUs
-                device_ids: None, // Use all available
+                count,
+                device_ids,
                 capabilities: Some(vec![vec![
                     "gpu".to_string(),
                     "compute".to_string(),
@@ -153,19 +200,50 @@ impl ContainerConfigBuilder {
         image: &str,
         command: &[String],
         host_config: HostConfig,
+        options: &ContainerCreateOptions,
+        gpu_devices: Option<&[String]>,
     ) -> Result<Config<String>> {
+        // An empty command keeps the image's default CMD
+        let cmd = if command.is_empty() {
+            None
+        } else {
+            Some(command.to_vec())
+        };
+
+        let exposed_ports = if options.port_mappings.is_empty() {
+            None
+        } else {
+            Some(
+                options
+                    .port_mappings
+                    .iter()
+                    .map(|m| {
+                        (
+                            format!("{}/{}", m.container_port, m.protocol),
+                            std::collections::HashMap::new(),
+                        )
+                    })
+                    .collect(),
+            )
+        };
+
         Ok(Config {
             image: Some(image.to_string()),
-            cmd: Some(command.to_vec()),
+            cmd,
             working_dir: Some("/workspace".to_string()),
-            env: Some(self.build_environment_variables()),
+            env: Some(self.build_environment_variables(options, gpu_devices)),
+            exposed_ports,
             host_config: Some(host_config),
             user: self.build_user_config(),
             ..Default::default()
         })
     }
 
-    fn build_environment_variables(&self) -> Vec<String> {
+    fn build_environment_variables(
+        &self,
+        options: &ContainerCreateOptions,
+        gpu_devices: Option<&[String]>,
+    ) -> Vec<String> {
         let mut env_vars = vec![
             "DEBIAN_FRONTEND=noninteractive".to_string(),
             "PYTHONUNBUFFERED=1".to_string(),
@@ -175,8 +253,11 @@ impl ContainerConfigBuilder {
 
         // Add GPU-specific environment variables if GPU is enabled
         if self.config.enable_gpu_passthrough {
+            let visible_devices = gpu_devices
+                .map(|devices| devices.join(","))
+                .unwrap_or_else(|| "all".to_string());
             env_vars.extend(vec![
-                "NVIDIA_VISIBLE_DEVICES=all".to_string(),
+                format!("NVIDIA_VISIBLE_DEVICES={visible_devices}"),
                 "NVIDIA_DRIVER_CAPABILITIES=compute,utility".to_string(),
                 "NVIDIA_REQUIRE_CUDA=cuda>=11.0".to_string(),
             ]);
@@ -187,6 +268,15 @@ impl ContainerConfigBuilder {
             env_vars.push("NO_INTERNET=1".to_string());
         }
 
+        // Requested variables come last so they take precedence
+        let mut requested: Vec<_> = options.environment.iter().collect();
+        requested.sort();
+        env_vars.extend(requested.into_iter().map(|(k, v)| format!("{k}={v}")));
+
+        if let Some(key) = &options.ssh_public_key {
+            env_vars.push(format!("SSH_PUBLIC_KEY={key}"));
+        }
+
         env_vars
     }
 
@@ -218,8 +308,9 @@ impl ContainerConfigBuilder {
 
     fn build_port_bindings(
         &self,
+        options: &ContainerCreateOptions,
     ) -> Option<std::collections::HashMap<String, Option<Vec<PortBinding>>>> {
-        if self.config.network_config.port_mappings.is_empty() {
+        if self.config.network_config.port_mappings.is_empty() && options.port_mappings.is_empty() {
             return None;
         }
 
@@ -234,6 +325,26 @@ impl ContainerConfigBuilder {
             port_bindings.insert(container_port, Some(vec![host_binding]));
         }
 
+        // Requested ports are published on all interfaces so renters can reach them
+        for mapping in &options.port_mappings {
+            let container_port = format!("{}/{}", mapping.container_port, mapping.protocol);
+            let host_port = if mapping.host_port == 0 {
+                String::new()
+            } else {
+                mapping.host_port.to_string()
+            };
+            let host_binding = PortBinding {
+                host_ip: Some(
+                    mapping
+                        .host_ip
+                        .clone()
+                        .unwrap_or_else(|| "0.0.0.0".to_string()),
+                ),
+                host_port: Some(host_port),
+            };
+            port_bindings.insert(container_port, Some(vec![host_binding]));
+        }
+
         Some(port_bindings)
     }
 
@@ -0,0 +1,126 @@
+//! Interactive command execution in containers
+
+use anyhow::{anyhow, Result};
+use bollard::{
+    container::LogOutput,
+    exec::{CreateExecOptions, ResizeExecOptions, StartExecOptions, StartExecResults},
+    Docker,
+};
+use futures_util::Stream;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+use tracing::{debug, info};
+
+/// How long to wait for Docker to record the exit code once output ends
+const EXIT_CODE_ATTEMPTS: u32 = 20;
+const EXIT_CODE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Command to run interactively
+#[derive(Debug, Clone, Default)]
+pub struct ExecOptions {
+    pub command: Vec<String>,
+    /// Allocate a pseudo-terminal, merging stderr into stdout
+    pub tty: bool,
+    pub environment: HashMap<String, String>,
+    pub working_dir: Option<String>,
+}
+
+/// A running command with its stdin and output attached
+pub struct AttachedExec {
+    pub exec_id: String,
+    pub output: Pin<Box<dyn Stream<Item = Result<LogOutput, bollard::errors::Error>> + Send>>,
+    pub input: Pin<Box<dyn AsyncWrite + Send>>,
+}
+
+impl std::fmt::Debug for AttachedExec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttachedExec")
+            .field("exec_id", &self.exec_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Starts commands in containers with stdin, stdout and stderr attached
+#[derive(Debug, Clone)]
+pub struct InteractiveExec {
+    docker: Docker,
+}
+
+impl InteractiveExec {
+    pub fn new(docker: Docker) -> Self {
+        Self { docker }
+    }
+
+    pub async fn start(&self, container_id: &str, options: &ExecOptions) -> Result<AttachedExec> {
+        if options.command.is_empty() {
+            return Err(anyhow!("No command to run"));
+        }
+        info!(
+            "Starting interactive command in container {}: {:?}",
+            container_id, options.command
+        );
+
+        let mut environment: Vec<String> = options
+            .environment
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        environment.sort();
+
+        let exec_config = CreateExecOptions {
+            cmd: Some(options.command.clone()),
+            env: Some(environment),
+            working_dir: options.working_dir.clone(),
+            tty: Some(options.tty),
+            attach_stdin: Some(true),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+        let exec = self.docker.create_exec(container_id, exec_config).await?;
+
+        let start_options = StartExecOptions {
+            detach: false,
+            tty: options.tty,
+            ..Default::default()
+        };
+        match self
+            .docker
+            .start_exec(&exec.id, Some(start_options))
+            .await?
+        {
+            StartExecResults::Attached { output, input } => Ok(AttachedExec {
+                exec_id: exec.id,
+                output,
+                input,
+            }),
+            StartExecResults::Detached => Err(anyhow!("Command started detached")),
+        }
+    }
+
+    /// Resize the terminal of a command started with a TTY
+    pub async fn resize(&self, exec_id: &str, rows: u16, cols: u16) -> Result<()> {
+        let options = ResizeExecOptions {
+            height: rows,
+            width: cols,
+        };
+        self.docker.resize_exec(exec_id, options).await?;
+        Ok(())
+    }
+
+    /// Exit code of a finished command, `None` if Docker has not recorded one
+    pub async fn exit_code(&self, exec_id: &str) -> Result<Option<i64>> {
+        for _ in 0..EXIT_CODE_ATTEMPTS {
+            let inspect = self.docker.inspect_exec(exec_id).await?;
+            if inspect.running != Some(true) {
+                return Ok(inspect.exit_code);
+            }
+            tokio::time::sleep(EXIT_CODE_INTERVAL).await;
+        }
+
+        debug!("Command {} still running after its output ended", exec_id);
+        Ok(None)
+    }
+}
@@ -0,0 +1,559 @@
+//! Copying single files into and out of containers
+//!
+//! Docker's archive API moves tar streams, so uploads are wrapped in a
+//! one-entry tar archive and downloads are unwrapped from one. Both sides
+//! track a SHA-256 of the contents so callers can verify the transfer.
+
+use crate::config::FileTransferConfig;
+use anyhow::{anyhow, Context, Result};
+use bollard::{
+    container::{DownloadFromContainerOptions, UploadToContainerOptions},
+    Docker,
+};
+use futures_util::stream::{Stream, StreamExt};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::pin::Pin;
+use tar::{Archive, Builder, Entry, EntryType, Header};
+use tokio::runtime::Handle;
+use tokio::sync::{mpsc, oneshot};
+use tracing::info;
+
+/// Size of the reads from the archive being downloaded
+const READ_SIZE: usize = 64 * 1024;
+
+/// Chunks read ahead of the receiver during a download
+const READ_AHEAD_CHUNKS: usize = 4;
+
+/// Permission bits of uploaded files that do not specify any
+pub const DEFAULT_FILE_MODE: u32 = 0o644;
+
+/// Copies files into and out of containers through the Docker archive API
+#[derive(Debug, Clone)]
+pub struct FileTransfer {
+    docker: Docker,
+    config: FileTransferConfig,
+}
+
+impl FileTransfer {
+    pub fn new(docker: Docker, config: FileTransferConfig) -> Self {
+        Self { docker, config }
+    }
+
+    pub fn config(&self) -> &FileTransferConfig {
+        &self.config
+    }
+
+    /// Write `data` to `path` in a container, replacing any existing file
+    ///
+    /// The parent directory must exist. The file is owned by the container's
+    /// configured user when that is numeric, and by root otherwise.
+    pub async fn upload(
+        &self,
+        container_id: &str,
+        path: &str,
+        mode: u32,
+        data: &[u8],
+    ) -> Result<()> {
+        let (directory, name) = split_container_path(path)?;
+        if data.len() as u64 > self.config.max_file_bytes {
+            return Err(anyhow!(
+                "File exceeds the {} byte transfer limit",
+                self.config.max_file_bytes
+            ));
+        }
+
+        let (uid, gid) = self.container_user(container_id).await?;
+        let archive = build_archive(name, mode, uid, gid, data)?;
+
+        let options = UploadToContainerOptions {
+            path: directory.to_string(),
+            no_overwrite_dir_non_dir: "true".to_string(),
+        };
+        self.docker
+            .upload_to_container(container_id, Some(options), archive.into())
+            .await
+            .with_context(|| format!("Failed to write {path}"))?;
+
+        info!(
+            "Uploaded {} bytes to {} in container {}",
+            data.len(),
+            path,
+            container_id
+        );
+        Ok(())
+    }
+
+    /// Start reading a regular file from a container
+    pub async fn download(&self, container_id: &str, path: &str) -> Result<FileDownload> {
+        split_container_path(path)?;
+
+        let options = DownloadFromContainerOptions {
+            path: path.to_string(),
+        };
+        let source = self
+            .docker
+            .download_from_container(container_id, Some(options))
+            .map(|chunk| {
+                chunk
+                    .map(|bytes| bytes.to_vec())
+                    .map_err(anyhow::Error::from)
+            });
+
+        let download = FileDownload::open(Box::pin(source))
+            .await
+            .with_context(|| format!("Failed to read {path}"))?;
+        if download.size > self.config.max_file_bytes {
+            return Err(anyhow!(
+                "File of {} bytes exceeds the {} byte transfer limit",
+                download.size,
+                self.config.max_file_bytes
+            ));
+        }
+
+        info!(
+            "Downloading {} ({} bytes) from container {}",
+            path, download.size, container_id
+        );
+        Ok(download)
+    }
+
+    /// Numeric user and group of a container's configured user
+    async fn container_user(&self, container_id: &str) -> Result<(u32, u32)> {
+        let inspect = self.docker.inspect_container(container_id, None).await?;
+        let user = inspect
+            .config
+            .and_then(|config| config.user)
+            .unwrap_or_default();
+
+        let (uid, gid) = user.split_once(':').unwrap_or((&user, ""));
+        let uid = uid.parse().unwrap_or(0);
+        let gid = gid.parse().unwrap_or(uid);
+        Ok((uid, gid))
+    }
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = Result<Vec<u8>>> + Send>>;
+
+/// Contents of a file being read out of a tar stream
+pub struct FileDownload {
+    pub size: u64,
+    pub mode: u32,
+    chunks: mpsc::Receiver<Result<Vec<u8>>>,
+    buffer: Vec<u8>,
+    remaining: u64,
+    hasher: Sha256,
+}
+
+impl std::fmt::Debug for FileDownload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileDownload")
+            .field("size", &self.size)
+            .field("mode", &self.mode)
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileDownload {
+    /// Read the archive up to its first entry, which must be a regular file
+    ///
+    /// The archive is read on a blocking task that sends the file contents
+    /// back in chunks as they are requested.
+    async fn open(source: ByteStream) -> Result<Self> {
+        let reader = BlockingStreamReader::new(source);
+        let (opened_tx, opened_rx) = oneshot::channel();
+        let (chunks_tx, chunks) = mpsc::channel(READ_AHEAD_CHUNKS);
+        tokio::task::spawn_blocking(move || read_first_file(reader, opened_tx, chunks_tx));
+
+        let (size, mode) = opened_rx
+            .await
+            .map_err(|_| anyhow!("Archive reader stopped"))??;
+        Ok(Self {
+            size,
+            mode,
+            chunks,
+            buffer: Vec::new(),
+            remaining: size,
+            hasher: Sha256::new(),
+        })
+    }
+
+    /// Next chunk of at most `max_len` bytes, `None` once the file is read
+    pub async fn next_chunk(&mut self, max_len: usize) -> Result<Option<Vec<u8>>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        if self.buffer.is_empty() {
+            self.buffer = self
+                .chunks
+                .recv()
+                .await
+                .ok_or_else(|| anyhow!("Archive ended before the end of the file"))??;
+        }
+
+        let len = self
+            .buffer
+            .len()
+            .min(max_len.max(1))
+            .min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        let chunk: Vec<u8> = self.buffer.drain(..len).collect();
+        self.remaining -= len as u64;
+        self.hasher.update(&chunk);
+        Ok(Some(chunk))
+    }
+
+    /// Hex SHA-256 of the chunks read so far
+    pub fn sha256(&self) -> String {
+        hex::encode(self.hasher.clone().finalize())
+    }
+}
+
+/// Send the size and mode of the archive's first file, then its contents
+///
+/// Stops early once the download is dropped.
+fn read_first_file(
+    reader: impl Read,
+    opened: oneshot::Sender<Result<(u64, u32)>>,
+    chunks: mpsc::Sender<Result<Vec<u8>>>,
+) {
+    let mut archive = Archive::new(reader);
+    let mut entry = match first_file(&mut archive) {
+        Ok(entry) => entry,
+        Err(e) => {
+            let _ = opened.send(Err(e));
+            return;
+        }
+    };
+    let header = entry.header();
+    let metadata = header
+        .size()
+        .and_then(|size| Ok((size, header.mode()?)))
+        .context("Invalid tar header");
+    let failed = metadata.is_err();
+    if opened.send(metadata).is_err() || failed {
+        return;
+    }
+
+    let mut buffer = vec![0u8; READ_SIZE];
+    loop {
+        let chunk = match entry.read(&mut buffer) {
+            Ok(0) => return,
+            Ok(len) => Ok(buffer[..len].to_vec()),
+            Err(e) => Err(anyhow::Error::from(e).context("Failed to read archive")),
+        };
+        let failed = chunk.is_err();
+        if chunks.blocking_send(chunk).is_err() || failed {
+            return;
+        }
+    }
+}
+
+/// First entry of an archive, which must be a regular file
+///
+/// Extended (PAX) and GNU long name headers before it are applied rather
+/// than returned.
+fn first_file<R: Read>(archive: &mut Archive<R>) -> Result<Entry<'_, R>> {
+    let entry = archive
+        .entries()
+        .context("Failed to read archive")?
+        .next()
+        .ok_or_else(|| anyhow!("Archive contains no file"))?
+        .context("Corrupt tar header")?;
+
+    match entry.header().entry_type() {
+        EntryType::Regular => Ok(entry),
+        EntryType::Directory => Err(anyhow!("Path is a directory")),
+        EntryType::Link | EntryType::Symlink => Err(anyhow!("Path is a link")),
+        other => Err(anyhow!("Unsupported file type {:?}", other)),
+    }
+}
+
+/// Blocking reader over an async byte stream, for use off the runtime threads
+struct BlockingStreamReader {
+    source: ByteStream,
+    runtime: Handle,
+    chunk: Vec<u8>,
+    position: usize,
+}
+
+impl BlockingStreamReader {
+    /// Must be created within the runtime that polls `source`
+    fn new(source: ByteStream) -> Self {
+        Self {
+            source,
+            runtime: Handle::current(),
+            chunk: Vec::new(),
+            position: 0,
+        }
+    }
+}
+
+impl Read for BlockingStreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.chunk.len() {
+            match self.runtime.block_on(self.source.next()) {
+                Some(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.position = 0;
+                }
+                Some(Err(e)) => return Err(std::io::Error::other(e)),
+                None => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.chunk.len() - self.position);
+        buf[..len].copy_from_slice(&self.chunk[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Accumulates an upload and checks it against the announced size and checksums
+#[derive(Debug)]
+pub struct FileUpload {
+    expected_size: u64,
+    data: Vec<u8>,
+    hasher: Sha256,
+    finished: bool,
+}
+
+impl FileUpload {
+    pub fn new(expected_size: u64, max_file_bytes: u64) -> Result<Self> {
+        if expected_size > max_file_bytes {
+            return Err(anyhow!(
+                "File of {} bytes exceeds the {} byte transfer limit",
+                expected_size,
+                max_file_bytes
+            ));
+        }
+
+        Ok(Self {
+            expected_size,
+            data: Vec::with_capacity(expected_size.min(1024 * 1024) as usize),
+            hasher: Sha256::new(),
+            finished: false,
+        })
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) -> Result<()> {
+        if self.finished {
+            return Err(anyhow!("Data received after the trailer"));
+        }
+        if self.data.len() as u64 + chunk.len() as u64 > self.expected_size {
+            return Err(anyhow!(
+                "Received more than the announced {} bytes",
+                self.expected_size
+            ));
+        }
+
+        self.hasher.update(chunk);
+        self.data.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    /// Check the trailer the sender ended the upload with
+    pub fn verify_trailer(&mut self, sha256: &str, size: u64) -> Result<()> {
+        if self.finished {
+            return Err(anyhow!("Duplicate trailer"));
+        }
+        self.finished = true;
+
+        if size != self.data.len() as u64 {
+            return Err(anyhow!(
+                "Trailer announces {} bytes but {} were received",
+                size,
+                self.data.len()
+            ));
+        }
+        self.verify_checksum(sha256)
+    }
+
+    /// Return the contents and their hex SHA-256 once every byte arrived
+    ///
+    /// `expected_sha256` is checked when not empty.
+    pub fn finish(self, expected_sha256: &str) -> Result<(Vec<u8>, String)> {
+        if self.data.len() as u64 != self.expected_size {
+            return Err(anyhow!(
+                "Received {} of the announced {} bytes",
+                self.data.len(),
+                self.expected_size
+            ));
+        }
+        self.verify_checksum(expected_sha256)?;
+
+        let sha256 = hex::encode(self.hasher.finalize());
+        Ok((self.data, sha256))
+    }
+
+    fn verify_checksum(&self, expected: &str) -> Result<()> {
+        let expected = expected.trim();
+        if expected.is_empty() {
+            return Ok(());
+        }
+
+        let actual = hex::encode(self.hasher.clone().finalize());
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(anyhow!(
+                "Checksum mismatch: expected {}, received data has {}",
+                expected,
+                actual
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Split an absolute file path into its directory and file name
+pub fn split_container_path(path: &str) -> Result<(&str, &str)> {
+    if !path.starts_with('/') || path.ends_with('/') {
+        return Err(anyhow!("Path must be an absolute file path: {}", path));
+    }
+    if path.split('/').any(|part| part == ".." || part == ".") {
+        return Err(anyhow!("Path must not contain '.' or '..': {}", path));
+    }
+
+    let (directory, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let directory = if directory.is_empty() { "/" } else { directory };
+    Ok((directory, name))
+}
+
+/// One-entry tar archive holding `data` as `name`
+fn build_archive(name: &str, mode: u32, uid: u32, gid: u32, data: &[u8]) -> Result<Vec<u8>> {
+    let mode = if mode == 0 {
+        DEFAULT_FILE_MODE
+    } else {
+        mode & 0o7777
+    };
+    let mtime = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Regular);
+    header.set_size(data.len() as u64);
+    header.set_mode(mode);
+    header.set_uid(uid as u64);
+    header.set_gid(gid as u64);
+    header.set_mtime(mtime);
+
+    let mut builder = Builder::new(Vec::new());
+    builder
+        .append_data(&mut header, name, data)
+        .context("Failed to build archive")?;
+    builder.into_inner().context("Failed to build archive")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunked(bytes: Vec<u8>, chunk_size: usize) -> ByteStream {
+        let chunks: Vec<Result<Vec<u8>>> = bytes
+            .chunks(chunk_size)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect();
+        Box::pin(futures_util::stream::iter(chunks))
+    }
+
+    fn sha256(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
+    #[tokio::test]
+    async fn test_archive_round_trip() {
+        let data: Vec<u8> = (0..1300u32).map(|i| (i % 251) as u8).collect();
+        // Names past the 100 bytes of a tar header take an extra GNU header
+        let name = "m".repeat(150);
+        let archive = build_archive(&name, 0o600, 1000, 1000, &data).unwrap();
+        assert_eq!(archive.len() % 512, 0);
+
+        let mut download = FileDownload::open(chunked(archive, 100)).await.unwrap();
+        assert_eq!(download.size, data.len() as u64);
+        assert_eq!(download.mode, 0o600);
+
+        let mut received = Vec::new();
+        while let Some(chunk) = download.next_chunk(256).await.unwrap() {
+            assert!(chunk.len() <= 256);
+            received.extend(chunk);
+        }
+        assert_eq!(received, data);
+        assert_eq!(download.sha256(), sha256(&data));
+    }
+
+    #[tokio::test]
+    async fn test_download_rejects_directories_and_corrupt_archives() {
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Directory);
+        header.set_size(0);
+        header.set_mode(0o755);
+        let mut builder = Builder::new(Vec::new());
+        builder
+            .append_data(&mut header, "data", std::io::empty())
+            .unwrap();
+        let directory = builder.into_inner().unwrap();
+        assert!(FileDownload::open(chunked(directory, 512)).await.is_err());
+
+        let mut corrupt = build_archive("file", 0o644, 0, 0, b"hello").unwrap();
+        corrupt[0] = b'X';
+        assert!(FileDownload::open(chunked(corrupt, 512)).await.is_err());
+        assert!(FileDownload::open(chunked(vec![0; 1024], 512))
+            .await
+            .is_err());
+
+        let archive = build_archive("file", 0o644, 0, 0, &[7; 2048]).unwrap();
+        let mut truncated = FileDownload::open(chunked(archive[..1024].to_vec(), 512))
+            .await
+            .unwrap();
+        let error = loop {
+            match truncated.next_chunk(4096).await {
+                Ok(Some(_)) => continue,
+                Ok(None) => panic!("Truncated file read to the end"),
+                Err(e) => break e,
+            }
+        };
+        assert!(error.to_string().contains("ended before the end"));
+    }
+
+    #[test]
+    fn test_file_upload_checks() {
+        let data = b"checkpoint contents";
+
+        let mut upload = FileUpload::new(data.len() as u64, 1024).unwrap();
+        upload.push(&data[..5]).unwrap();
+        upload.push(&data[5..]).unwrap();
+        upload
+            .verify_trailer(&sha256(data).to_uppercase(), data.len() as u64)
+            .unwrap();
+        assert!(upload.push(b"more").is_err());
+        let (received, checksum) = upload.finish("").unwrap();
+        assert_eq!(received, data);
+        assert_eq!(checksum, sha256(data));
+
+        let mut short = FileUpload::new(100, 1024).unwrap();
+        short.push(data).unwrap();
+        assert!(short.finish("").is_err());
+
+        let mut mismatched = FileUpload::new(data.len() as u64, 1024).unwrap();
+        mismatched.push(data).unwrap();
+        assert!(mismatched.finish(&sha256(b"other")).is_err());
+
+        let mut oversized = FileUpload::new(4, 1024).unwrap();
+        assert!(oversized.push(data).is_err());
+        assert!(FileUpload::new(2048, 1024).is_err());
+    }
+
+    #[test]
+    fn test_split_container_path() {
+        assert_eq!(
+            split_container_path("/workspace/model.bin").unwrap(),
+            ("/workspace", "model.bin")
+        );
+        assert_eq!(split_container_path("/file").unwrap(), ("/", "file"));
+
+        for path in ["relative", "/dir/", "/a/../etc/passwd", "/a/./b", ""] {
+            assert!(split_container_path(path).is_err(), "{path}");
+        }
+    }
+}
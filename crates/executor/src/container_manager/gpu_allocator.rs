@@ -0,0 +1,370 @@
+//! GPU assignment tracking
+//!
+//! Assigns specific GPUs to each container so two containers never share a
+//! device. Assignments are saved in the executor database on every change
+//! and reloaded on start, so a restarted executor does not hand out GPUs that
+//! are still in use by containers it created before the restart.
+
+use crate::persistence::ExecutorDb;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// A GPU that can be assigned to a container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpuDevice {
+    pub index: u32,
+    pub uuid: String,
+}
+
+impl GpuDevice {
+    /// Identifier passed to Docker, the UUID when known and the index otherwise
+    pub fn device_id(&self) -> String {
+        if self.uuid.is_empty() {
+            self.index.to_string()
+        } else {
+            self.uuid.clone()
+        }
+    }
+}
+
+/// GPUs requested for a container
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpuRequest {
+    /// Every GPU of the executor, for whole-executor rentals
+    #[serde(default)]
+    pub all: bool,
+    /// Number of GPUs, ignored when specific devices are requested
+    pub count: u32,
+    /// Specific devices by UUID or index
+    pub device_ids: Vec<String>,
+}
+
+impl GpuRequest {
+    /// Whether the request asks for no GPUs at all
+    pub fn is_empty(&self) -> bool {
+        !self.all && self.count == 0 && self.device_ids.is_empty()
+    }
+}
+
+/// GPUs held by one container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpuAssignment {
+    pub container_id: String,
+    pub devices: Vec<GpuDevice>,
+    /// Unix timestamp of the assignment
+    pub assigned_at: i64,
+}
+
+/// Tracks which GPUs are assigned to which container
+#[derive(Debug)]
+pub struct GpuAllocator {
+    devices: Vec<GpuDevice>,
+    db: ExecutorDb,
+    assignments: Mutex<BTreeMap<String, GpuAssignment>>,
+}
+
+impl GpuAllocator {
+    /// Create an allocator over `devices`, restoring the assignments saved in `db`
+    pub async fn load(devices: Vec<GpuDevice>, db: ExecutorDb) -> Result<Self> {
+        let assignments = db
+            .list_gpu_assignments()
+            .await?
+            .into_iter()
+            .map(|assignment| (assignment.container_id.clone(), assignment))
+            .collect::<BTreeMap<_, _>>();
+        if !assignments.is_empty() {
+            info!(
+                "Restored GPU assignments for {} container(s)",
+                assignments.len()
+            );
+        }
+
+        Ok(Self {
+            devices,
+            db,
+            assignments: Mutex::new(assignments),
+        })
+    }
+
+    /// All GPUs the allocator manages
+    pub fn devices(&self) -> &[GpuDevice] {
+        &self.devices
+    }
+
+    /// Assign GPUs to `owner`, failing if the request cannot be met from free GPUs
+    ///
+    /// An empty request assigns nothing. A request for all GPUs needs every
+    /// GPU of the executor to be free.
+    pub async fn allocate(&self, owner: &str, request: &GpuRequest) -> Result<Vec<GpuDevice>> {
+        if request.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let assignment = self.reserve(owner, request)?;
+        if let Err(e) = self.db.save_gpu_assignment(&assignment).await {
+            self.lock().remove(owner);
+            return Err(e);
+        }
+
+        Ok(assignment.devices)
+    }
+
+    /// Pick GPUs for `owner` and hold them in memory
+    fn reserve(&self, owner: &str, request: &GpuRequest) -> Result<GpuAssignment> {
+        let mut assignments = self.lock();
+        if assignments.contains_key(owner) {
+            return Err(anyhow!("GPUs are already assigned to {}", owner));
+        }
+
+        let in_use = devices_in_use(&assignments);
+        let free: Vec<&GpuDevice> = self
+            .devices
+            .iter()
+            .filter(|d| !in_use.contains(&d.device_id()))
+            .collect();
+
+        let selected: Vec<GpuDevice> = if request.all {
+            if free.len() < self.devices.len() {
+                return Err(anyhow!(
+                    "All GPUs were requested but {} of {} are assigned",
+                    self.devices.len() - free.len(),
+                    self.devices.len()
+                ));
+            }
+            free.into_iter().cloned().collect()
+        } else if !request.device_ids.is_empty() {
+            let mut seen = HashSet::new();
+            request
+                .device_ids
+                .iter()
+                .map(|id| {
+                    let device = self
+                        .devices
+                        .iter()
+                        .find(|d| d.uuid == *id || d.index.to_string() == *id)
+                        .ok_or_else(|| anyhow!("Unknown GPU {}", id))?;
+                    if !seen.insert(device.device_id()) {
+                        return Err(anyhow!("GPU {} is requested more than once", id));
+                    }
+                    if in_use.contains(&device.device_id()) {
+                        return Err(anyhow!("GPU {} is assigned to another container", id));
+                    }
+                    Ok(device.clone())
+                })
+                .collect::<Result<_>>()?
+        } else {
+            if (request.count as usize) > free.len() {
+                return Err(anyhow!(
+                    "Requested {} GPUs but only {} of {} are free",
+                    request.count,
+                    free.len(),
+                    self.devices.len()
+                ));
+            }
+            free.into_iter()
+                .take(request.count as usize)
+                .cloned()
+                .collect()
+        };
+
+        let assignment = GpuAssignment {
+            container_id: owner.to_string(),
+            devices: selected,
+            assigned_at: chrono::Utc::now().timestamp(),
+        };
+        assignments.insert(owner.to_string(), assignment.clone());
+
+        Ok(assignment)
+    }
+
+    /// Move an assignment to a new owner, e.g. from container name to container ID
+    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let renamed = {
+            let mut assignments = self.lock();
+            match assignments.remove(from) {
+                Some(mut assignment) => {
+                    assignment.container_id = to.to_string();
+                    assignments.insert(to.to_string(), assignment);
+                    true
+                }
+                None => false,
+            }
+        };
+
+        if renamed {
+            self.db.rename_gpu_assignment(from, to).await?;
+        }
+        Ok(())
+    }
+
+    /// Release the GPUs held by `owner`, returning whether it held any
+    pub async fn release(&self, owner: &str) -> bool {
+        let Some(assignment) = self.lock().remove(owner) else {
+            return false;
+        };
+
+        info!(
+            "Released {} GPU(s) from {}",
+            assignment.devices.len(),
+            owner
+        );
+        if let Err(e) = self.db.delete_gpu_assignment(owner).await {
+            warn!("Failed to delete GPU assignment of {}: {}", owner, e);
+        }
+        true
+    }
+
+    /// Current assignments, ordered by owner
+    pub fn assignments(&self) -> Vec<GpuAssignment> {
+        self.lock().values().cloned().collect()
+    }
+
+    /// GPUs not assigned to any container
+    pub fn free_devices(&self) -> Vec<GpuDevice> {
+        let assignments = self.lock();
+        let in_use = devices_in_use(&assignments);
+        self.devices
+            .iter()
+            .filter(|d| !in_use.contains(&d.device_id()))
+            .cloned()
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, GpuAssignment>> {
+        self.assignments
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn devices_in_use(assignments: &BTreeMap<String, GpuAssignment>) -> HashSet<String> {
+    assignments
+        .values()
+        .flat_map(|a| a.devices.iter().map(GpuDevice::device_id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::test_db;
+
+    fn devices(count: u32) -> Vec<GpuDevice> {
+        (0..count)
+            .map(|index| GpuDevice {
+                index,
+                uuid: format!("GPU-{index}"),
+            })
+            .collect()
+    }
+
+    fn count(count: u32) -> GpuRequest {
+        GpuRequest {
+            count,
+            ..Default::default()
+        }
+    }
+
+    fn all() -> GpuRequest {
+        GpuRequest {
+            all: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_allocation_refuses_over_allocation() {
+        let dir = tempfile::tempdir().unwrap();
+        let allocator = GpuAllocator::load(devices(4), test_db(dir.path()).await)
+            .await
+            .unwrap();
+
+        let first = allocator.allocate("a", &count(3)).await.unwrap();
+        assert_eq!(first.len(), 3);
+        assert!(allocator.allocate("b", &count(2)).await.is_err());
+        assert!(allocator.allocate("a", &count(1)).await.is_err());
+
+        let specific = GpuRequest {
+            device_ids: vec!["GPU-0".to_string()],
+            ..Default::default()
+        };
+        assert!(allocator.allocate("b", &specific).await.is_err());
+        assert!(allocator.allocate("b", &all()).await.is_err());
+
+        // An empty request gets no GPUs rather than the free ones
+        assert!(allocator
+            .allocate("b", &GpuRequest::default())
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(allocator.free_devices(), vec![devices(4)[3].clone()]);
+
+        let last = allocator.allocate("b", &count(1)).await.unwrap();
+        assert_eq!(last, vec![devices(4)[3].clone()]);
+        assert!(allocator.allocate("c", &count(1)).await.is_err());
+
+        assert!(allocator.release("a").await);
+        assert!(!allocator.release("a").await);
+        assert_eq!(allocator.free_devices().len(), 3);
+        let by_index = GpuRequest {
+            device_ids: vec!["1".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            allocator.allocate("c", &by_index).await.unwrap()[0].uuid,
+            "GPU-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allocation_of_specific_and_all_gpus() {
+        let dir = tempfile::tempdir().unwrap();
+        let allocator = GpuAllocator::load(devices(2), test_db(dir.path()).await)
+            .await
+            .unwrap();
+
+        let duplicate = GpuRequest {
+            device_ids: vec!["GPU-0".to_string(), "0".to_string()],
+            ..Default::default()
+        };
+        assert!(allocator.allocate("a", &duplicate).await.is_err());
+        let unknown = GpuRequest {
+            device_ids: vec!["GPU-7".to_string()],
+            ..Default::default()
+        };
+        assert!(allocator.allocate("a", &unknown).await.is_err());
+        assert!(allocator.assignments().is_empty());
+
+        assert_eq!(allocator.allocate("a", &all()).await.unwrap(), devices(2));
+        assert!(allocator.allocate("b", &count(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assignments_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let allocator = GpuAllocator::load(devices(3), test_db(dir.path()).await)
+            .await
+            .unwrap();
+        allocator.allocate("basilca-1234", &count(1)).await.unwrap();
+        allocator
+            .rename("basilca-1234", "container-id")
+            .await
+            .unwrap();
+        allocator.allocate("released", &count(1)).await.unwrap();
+        assert!(allocator.release("released").await);
+        drop(allocator);
+
+        let reloaded = GpuAllocator::load(devices(3), test_db(dir.path()).await)
+            .await
+            .unwrap();
+        let assignments = reloaded.assignments();
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].container_id, "container-id");
+        assert_eq!(assignments[0].devices[0].uuid, "GPU-0");
+        assert_eq!(reloaded.free_devices(), devices(3)[1..].to_vec());
+    }
+}
@@ -0,0 +1,513 @@
+//! Container image policy
+//!
+//! Decides whether an image may be pulled and run: the registry must be
+//! allowed and not denied, the reference may have to be pinned by digest,
+//! the image must fit the size limit, and its cosign signature must verify
+//! against one of the configured public keys.
+//!
+//! A signature is verified for a manifest digest, not for a tag that can be
+//! moved, so a signed image is pulled and run by the digest cosign verified.
+
+use crate::config::{DockerConfig, ImagePolicyConfig};
+use std::path::Path;
+use tokio::process::Command;
+use tracing::debug;
+
+/// Registry used for references without an explicit registry
+const DEFAULT_REGISTRY: &str = "docker.io";
+
+/// Policy rule an image failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImagePolicyRule {
+    InvalidReference,
+    RegistryNotAllowed,
+    RegistryDenied,
+    DigestRequired,
+    ImageTooLarge,
+    SignatureInvalid,
+}
+
+impl ImagePolicyRule {
+    /// Stable code reported to callers
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidReference => "IMAGE_INVALID_REFERENCE",
+            Self::RegistryNotAllowed => "IMAGE_REGISTRY_NOT_ALLOWED",
+            Self::RegistryDenied => "IMAGE_REGISTRY_DENIED",
+            Self::DigestRequired => "IMAGE_DIGEST_REQUIRED",
+            Self::ImageTooLarge => "IMAGE_TOO_LARGE",
+            Self::SignatureInvalid => "IMAGE_SIGNATURE_INVALID",
+        }
+    }
+}
+
+/// An image rejected by the policy
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Image {image} rejected by policy: {message}")]
+pub struct ImagePolicyViolation {
+    pub rule: ImagePolicyRule,
+    pub image: String,
+    pub message: String,
+}
+
+impl ImagePolicyViolation {
+    fn new(rule: ImagePolicyRule, image: &str, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            image: image.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// A parsed image reference such as `ghcr.io/org/app:1.0@sha256:...`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageReference {
+    pub registry: String,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+impl ImageReference {
+    /// Parse a reference the way Docker resolves it
+    ///
+    /// The first path component is a registry only if it looks like a host,
+    /// so `library/ubuntu` resolves to `docker.io/library/ubuntu`.
+    pub fn parse(image: &str) -> Result<Self, String> {
+        let image = image.trim();
+        if image.is_empty() || image.chars().any(char::is_whitespace) {
+            return Err("image reference is empty or contains whitespace".to_string());
+        }
+
+        let (name, digest) = match image.split_once('@') {
+            Some((name, digest)) => {
+                let hex = digest
+                    .strip_prefix("sha256:")
+                    .ok_or_else(|| format!("unsupported digest '{digest}'"))?;
+                if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!("malformed digest '{digest}'"));
+                }
+                (name, Some(digest.to_ascii_lowercase()))
+            }
+            None => (image, None),
+        };
+
+        let (registry, remainder) = match name.split_once('/') {
+            Some((first, rest))
+                if first.contains('.') || first.contains(':') || first == "localhost" =>
+            {
+                (first.to_ascii_lowercase(), rest)
+            }
+            _ => (DEFAULT_REGISTRY.to_string(), name),
+        };
+        let registry = match registry.as_str() {
+            "index.docker.io" | "registry-1.docker.io" => DEFAULT_REGISTRY.to_string(),
+            _ => registry,
+        };
+
+        // A tag follows the last ':' of the final path component
+        let last_slash = remainder.rfind('/').map(|i| i + 1).unwrap_or(0);
+        let (repository, tag) = match remainder[last_slash..].rfind(':') {
+            Some(i) => (
+                &remainder[..last_slash + i],
+                Some(remainder[last_slash + i + 1..].to_string()),
+            ),
+            None => (remainder, None),
+        };
+
+        if repository.is_empty()
+            || repository.split('/').any(str::is_empty)
+            || tag.as_deref() == Some("")
+        {
+            return Err(format!("malformed image name '{name}'"));
+        }
+
+        let repository = if registry == DEFAULT_REGISTRY && !repository.contains('/') {
+            format!("library/{repository}")
+        } else {
+            repository.to_string()
+        };
+
+        Ok(Self {
+            registry,
+            repository,
+            tag,
+            digest,
+        })
+    }
+
+    /// Whether a registry or repository prefix entry covers this image
+    pub fn matches(&self, entry: &str) -> bool {
+        let entry = entry.trim().trim_end_matches('/').to_ascii_lowercase();
+        let entry = match entry.as_str() {
+            "index.docker.io" | "registry-1.docker.io" => DEFAULT_REGISTRY.to_string(),
+            _ => entry,
+        };
+        let full_name = format!("{}/{}", self.registry, self.repository);
+
+        entry == self.registry || full_name == entry || full_name.starts_with(&format!("{entry}/"))
+    }
+
+    /// Reference to this image's repository pinned to `digest`
+    pub fn pinned(&self, digest: &str) -> String {
+        format!("{}/{}@{}", self.registry, self.repository, digest)
+    }
+}
+
+/// Image policy built from the Docker configuration
+#[derive(Debug, Clone)]
+pub struct ImagePolicy {
+    allowed_registries: Vec<String>,
+    verify_signatures: bool,
+    config: ImagePolicyConfig,
+}
+
+impl ImagePolicy {
+    pub fn new(config: &DockerConfig) -> Self {
+        Self {
+            allowed_registries: config.registry.allowed_registries.clone(),
+            verify_signatures: config.registry.verify_signatures,
+            config: config.image_policy.clone(),
+        }
+    }
+
+    /// Check the rules that only need the reference, before anything is pulled
+    pub fn check_reference(&self, image: &str) -> Result<ImageReference, ImagePolicyViolation> {
+        let reference = ImageReference::parse(image)
+            .map_err(|e| ImagePolicyViolation::new(ImagePolicyRule::InvalidReference, image, e))?;
+
+        if let Some(denied) = self
+            .config
+            .denied_registries
+            .iter()
+            .find(|entry| reference.matches(entry))
+        {
+            return Err(ImagePolicyViolation::new(
+                ImagePolicyRule::RegistryDenied,
+                image,
+                format!("'{denied}' is denied"),
+            ));
+        }
+
+        if !self.allowed_registries.is_empty()
+            && !self
+                .allowed_registries
+                .iter()
+                .any(|entry| reference.matches(entry))
+        {
+            return Err(ImagePolicyViolation::new(
+                ImagePolicyRule::RegistryNotAllowed,
+                image,
+                format!(
+                    "registry '{}' is not in the allowed list: {:?}",
+                    reference.registry, self.allowed_registries
+                ),
+            ));
+        }
+
+        if self.config.require_digest && reference.digest.is_none() {
+            return Err(ImagePolicyViolation::new(
+                ImagePolicyRule::DigestRequired,
+                image,
+                "image must be pinned by digest (image@sha256:...)",
+            ));
+        }
+
+        Ok(reference)
+    }
+
+    /// Check the size Docker reports for the pulled image
+    pub fn check_size(&self, image: &str, size_bytes: u64) -> Result<(), ImagePolicyViolation> {
+        match self.config.max_image_size_bytes {
+            Some(max) if size_bytes > max => Err(ImagePolicyViolation::new(
+                ImagePolicyRule::ImageTooLarge,
+                image,
+                format!("image is {size_bytes} bytes, the limit is {max} bytes"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether images must carry a signature from one of the configured keys
+    pub fn requires_signature(&self) -> bool {
+        self.verify_signatures && !self.config.signature_public_keys.is_empty()
+    }
+
+    /// Verify the image's cosign signature against the configured keys
+    ///
+    /// Passes when any key verifies, returning the manifest digest the
+    /// signature covers. Returns `None` when signatures are not required.
+    pub async fn verify_signature(
+        &self,
+        image: &str,
+    ) -> Result<Option<String>, ImagePolicyViolation> {
+        if !self.requires_signature() {
+            return Ok(None);
+        }
+
+        let reference = ImageReference::parse(image)
+            .map_err(|e| ImagePolicyViolation::new(ImagePolicyRule::InvalidReference, image, e))?;
+
+        let mut failures = Vec::new();
+        for key in &self.config.signature_public_keys {
+            match self.run_cosign(image, key).await {
+                Ok(digest) if reference.digest.as_ref().is_some_and(|d| *d != digest) => {
+                    failures.push(format!(
+                        "{}: signature covers {digest}, not the requested digest",
+                        key.display()
+                    ));
+                }
+                Ok(digest) => {
+                    debug!(
+                        "Image {} signature verified with {} for {}",
+                        image,
+                        key.display(),
+                        digest
+                    );
+                    return Ok(Some(digest));
+                }
+                Err(e) => failures.push(format!("{}: {e}", key.display())),
+            }
+        }
+
+        Err(ImagePolicyViolation::new(
+            ImagePolicyRule::SignatureInvalid,
+            image,
+            format!(
+                "no configured key verified the signature ({})",
+                failures.join("; ")
+            ),
+        ))
+    }
+
+    /// Check that a local image carries the digest its signature covers
+    ///
+    /// `repo_digests` are the `RepoDigests` Docker reports for the image.
+    pub fn check_repo_digest(
+        &self,
+        image: &str,
+        digest: &str,
+        repo_digests: &[String],
+    ) -> Result<(), ImagePolicyViolation> {
+        let reference = ImageReference::parse(image)
+            .map_err(|e| ImagePolicyViolation::new(ImagePolicyRule::InvalidReference, image, e))?;
+
+        let matches = repo_digests.iter().any(|repo_digest| {
+            ImageReference::parse(repo_digest).is_ok_and(|local| {
+                local.registry == reference.registry
+                    && local.repository == reference.repository
+                    && local.digest.as_deref() == Some(digest)
+            })
+        });
+        if matches {
+            Ok(())
+        } else {
+            Err(ImagePolicyViolation::new(
+                ImagePolicyRule::SignatureInvalid,
+                image,
+                format!("local image does not have the verified digest {digest}"),
+            ))
+        }
+    }
+
+    async fn run_cosign(&self, image: &str, key: &Path) -> Result<String, String> {
+        let output = Command::new(&self.config.cosign_path)
+            .arg("verify")
+            .arg("--key")
+            .arg(key)
+            .arg(image)
+            .output()
+            .await
+            .map_err(|e| format!("failed to run {}: {e}", self.config.cosign_path.display()))?;
+
+        if output.status.success() {
+            verified_digest(&String::from_utf8_lossy(&output.stdout))
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    }
+}
+
+/// The manifest digest covered by the signatures `cosign verify` printed
+///
+/// cosign prints a JSON array with one payload per verified signature, each
+/// naming the digest under `critical.image.docker-manifest-digest`.
+fn verified_digest(stdout: &str) -> Result<String, String> {
+    let payloads: Vec<serde_json::Value> = serde_json::from_str(stdout.trim())
+        .map_err(|e| format!("unreadable cosign output: {e}"))?;
+
+    let mut digests = payloads.iter().map(|payload| {
+        payload
+            .pointer("/critical/image/docker-manifest-digest")
+            .and_then(|digest| digest.as_str())
+            .filter(|digest| {
+                digest.strip_prefix("sha256:").is_some_and(|hex| {
+                    hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())
+                })
+            })
+            .map(str::to_ascii_lowercase)
+            .ok_or_else(|| "cosign output names no manifest digest".to_string())
+    });
+
+    let digest = digests
+        .next()
+        .ok_or_else(|| "cosign verified no signatures".to_string())??;
+    for other in digests {
+        if other? != digest {
+            return Err("signatures cover different manifest digests".to_string());
+        }
+    }
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const DIGEST: &str = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn policy(image_policy: ImagePolicyConfig) -> ImagePolicy {
+        ImagePolicy::new(&DockerConfig {
+            image_policy,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_parse_image_reference() {
+        let ubuntu = ImageReference::parse("ubuntu:22.04").unwrap();
+        assert_eq!(ubuntu.registry, "docker.io");
+        assert_eq!(ubuntu.repository, "library/ubuntu");
+        assert_eq!(ubuntu.tag.as_deref(), Some("22.04"));
+
+        let pinned =
+            ImageReference::parse(&format!("localhost:5000/team/app:v1@{DIGEST}")).unwrap();
+        assert_eq!(pinned.registry, "localhost:5000");
+        assert_eq!(pinned.repository, "team/app");
+        assert_eq!(pinned.tag.as_deref(), Some("v1"));
+        assert_eq!(pinned.digest.as_deref(), Some(DIGEST));
+
+        let nested = ImageReference::parse("pytorch/pytorch").unwrap();
+        assert_eq!(nested.registry, "docker.io");
+        assert_eq!(nested.repository, "pytorch/pytorch");
+        assert!(nested.matches("docker.io/pytorch"));
+        assert!(!nested.matches("docker.io/pyt"));
+
+        assert!(ImageReference::parse("").is_err());
+        assert!(ImageReference::parse("ubuntu@sha256:1234").is_err());
+        assert!(ImageReference::parse("ghcr.io//app").is_err());
+    }
+
+    #[test]
+    fn test_reference_rules() {
+        let default = policy(ImagePolicyConfig::default());
+        assert!(default.check_reference("nvidia/cuda:12.4.0-base").is_ok());
+        assert_eq!(
+            default
+                .check_reference("evil.example.com/miner")
+                .unwrap_err()
+                .rule,
+            ImagePolicyRule::RegistryNotAllowed
+        );
+        // A registry must match exactly, not as a substring
+        assert!(default
+            .check_reference("docker.io.example.com/app")
+            .is_err());
+
+        let strict = policy(ImagePolicyConfig {
+            denied_registries: vec!["ghcr.io/untrusted".to_string()],
+            require_digest: true,
+            max_image_size_bytes: Some(1024),
+            ..Default::default()
+        });
+        assert_eq!(
+            strict
+                .check_reference(&format!("ghcr.io/untrusted/app@{DIGEST}"))
+                .unwrap_err()
+                .rule,
+            ImagePolicyRule::RegistryDenied
+        );
+        assert_eq!(
+            strict
+                .check_reference("ghcr.io/trusted/app:v1")
+                .unwrap_err()
+                .rule,
+            ImagePolicyRule::DigestRequired
+        );
+        assert!(strict
+            .check_reference(&format!("ghcr.io/trusted/app@{DIGEST}"))
+            .is_ok());
+
+        assert!(strict.check_size("app", 1024).is_ok());
+        assert_eq!(
+            strict.check_size("app", 1025).unwrap_err().rule,
+            ImagePolicyRule::ImageTooLarge
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signature_verification_fails_closed() {
+        let unsigned = policy(ImagePolicyConfig::default());
+        assert!(!unsigned.requires_signature());
+        assert!(unsigned.verify_signature("ubuntu:22.04").await.is_ok());
+
+        let signed = policy(ImagePolicyConfig {
+            signature_public_keys: vec![PathBuf::from("/etc/basilica/cosign.pub")],
+            cosign_path: PathBuf::from("/nonexistent/cosign"),
+            ..Default::default()
+        });
+        assert!(signed.requires_signature());
+        let violation = signed.verify_signature("ubuntu:22.04").await.unwrap_err();
+        assert_eq!(violation.rule, ImagePolicyRule::SignatureInvalid);
+        assert_eq!(violation.rule.code(), "IMAGE_SIGNATURE_INVALID");
+    }
+
+    #[test]
+    fn test_verified_digest_from_cosign_output() {
+        let payload = |digest: &str| {
+            serde_json::json!({
+                "critical": {
+                    "identity": { "docker-reference": "ghcr.io/org/app" },
+                    "image": { "docker-manifest-digest": digest },
+                    "type": "cosign container image signature"
+                },
+                "optional": null
+            })
+        };
+
+        let output = serde_json::json!([payload(DIGEST), payload(DIGEST)]).to_string();
+        assert_eq!(verified_digest(&output).unwrap(), DIGEST);
+
+        let other = format!("sha256:{}", "f".repeat(64));
+        let mixed = serde_json::json!([payload(DIGEST), payload(&other)]).to_string();
+        assert!(verified_digest(&mixed).is_err());
+        assert!(verified_digest("[]").is_err());
+        assert!(verified_digest(&serde_json::json!([payload("latest")]).to_string()).is_err());
+        assert!(verified_digest("Verification for ghcr.io/org/app succeeded").is_err());
+    }
+
+    #[test]
+    fn test_repo_digest_must_match_verified_digest() {
+        let policy = policy(ImagePolicyConfig::default());
+        let pinned = ImageReference::parse("ubuntu:22.04")
+            .unwrap()
+            .pinned(DIGEST);
+        assert_eq!(pinned, format!("docker.io/library/ubuntu@{DIGEST}"));
+
+        // Docker reports Docker Hub images by their short name
+        assert!(policy
+            .check_repo_digest(&pinned, DIGEST, &[format!("ubuntu@{DIGEST}")])
+            .is_ok());
+
+        let other = format!("sha256:{}", "f".repeat(64));
+        assert!(policy
+            .check_repo_digest(&pinned, DIGEST, &[format!("ubuntu@{other}")])
+            .is_err());
+        assert!(policy
+            .check_repo_digest(&pinned, DIGEST, &[format!("evil/ubuntu@{DIGEST}")])
+            .is_err());
+        assert!(policy.check_repo_digest(&pinned, DIGEST, &[]).is_err());
+    }
+}
@@ -3,23 +3,35 @@
 //! Provides modular container management functionality with separation of concerns.
 
 pub mod config_builder;
+pub mod exec;
+pub mod file_transfer;
+pub mod gpu_allocator;
 pub mod health;
+pub mod image_policy;
 pub mod logs;
+pub mod network_policy;
 pub mod operations;
 pub mod types;
+pub mod volumes;
 
+use exec::{AttachedExec, ExecOptions, InteractiveExec};
+use file_transfer::{FileDownload, FileTransfer};
+use gpu_allocator::{GpuAllocator, GpuAssignment, GpuDevice};
 use health::HealthChecker;
 use logs::LogStreamer;
 use operations::ContainerOperations;
 pub use types::*;
+use volumes::VolumeManager;
 
 use crate::config::{ContainerResourceLimits, DockerConfig};
-use anyhow::Result;
+use crate::persistence::ExecutorDb;
+use anyhow::{anyhow, Result};
 use bollard::Docker;
-use std::collections::HashMap;
+use common::journal::{log_security_violation, SecuritySeverity};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug, Clone)]
 pub struct ContainerManager {
@@ -27,10 +39,36 @@ pub struct ContainerManager {
     operations: ContainerOperations,
     log_streamer: LogStreamer,
     health_checker: HealthChecker,
+    interactive_exec: InteractiveExec,
+    file_transfer: FileTransfer,
+    gpu_allocator: Option<Arc<GpuAllocator>>,
+    volumes: Option<Arc<VolumeManager>>,
 }
 
 impl ContainerManager {
     pub async fn new(config: DockerConfig) -> Result<Self> {
+        Self::build(config, Vec::new(), None).await
+    }
+
+    /// Create a container manager that assigns the given GPUs to containers and
+    /// records them in the executor database
+    ///
+    /// Containers left by a previous run are re-adopted when still running and
+    /// removed otherwise. With no GPUs, containers with passthrough enabled see
+    /// every GPU. Named volumes are only available to managers created this way.
+    pub async fn restore(
+        config: DockerConfig,
+        gpus: Vec<GpuDevice>,
+        db: ExecutorDb,
+    ) -> Result<Self> {
+        Self::build(config, gpus, Some(db)).await
+    }
+
+    async fn build(
+        config: DockerConfig,
+        gpus: Vec<GpuDevice>,
+        db: Option<ExecutorDb>,
+    ) -> Result<Self> {
         info!(
             "Initializing container manager with Docker socket: {}",
             config.socket_path
@@ -48,17 +86,51 @@ impl ContainerManager {
             version.version.unwrap_or_default()
         );
 
+        let gpu_allocator = match &db {
+            Some(db) if config.enable_gpu_passthrough && !gpus.is_empty() => {
+                info!("Tracking GPU assignments for {} GPU(s)", gpus.len());
+                Some(Arc::new(GpuAllocator::load(gpus, db.clone()).await?))
+            }
+            _ => None,
+        };
+
+        let volumes = match &db {
+            Some(db) if config.volumes.enabled => Some(Arc::new(VolumeManager::new(
+                docker.clone(),
+                config.volumes.clone(),
+                db.clone(),
+            ))),
+            _ => None,
+        };
+
         let active_containers = Arc::new(RwLock::new(HashMap::new()));
-        let operations =
-            ContainerOperations::new(docker.clone(), config.clone(), active_containers.clone());
+        let operations = ContainerOperations::new(
+            docker.clone(),
+            config.clone(),
+            active_containers.clone(),
+            gpu_allocator.clone(),
+            db,
+        );
         let log_streamer = LogStreamer::new(docker.clone());
         let health_checker = HealthChecker::new(docker.clone());
+        let interactive_exec = InteractiveExec::new(docker.clone());
+        let file_transfer = FileTransfer::new(docker.clone(), config.file_transfer.clone());
+
+        operations.reconcile_containers().await?;
+        operations.reconcile_gpu_assignments().await?;
+        if let Some(volumes) = &volumes {
+            volumes.reconcile().await?;
+        }
 
         Ok(Self {
             active_containers,
             operations,
             log_streamer,
             health_checker,
+            interactive_exec,
+            file_transfer,
+            gpu_allocator,
+            volumes,
         })
     }
 
@@ -73,6 +145,18 @@ impl ContainerManager {
             .await
     }
 
+    pub async fn create_container_with_options(
+        &self,
+        image: &str,
+        command: &[String],
+        resource_limits: Option<ContainerResourceLimits>,
+        options: &ContainerCreateOptions,
+    ) -> Result<CreatedContainer> {
+        self.operations
+            .create_container_with_options(image, command, resource_limits, options)
+            .await
+    }
+
     pub async fn execute_command(
         &self,
         container_id: &str,
@@ -84,6 +168,58 @@ impl ContainerManager {
             .await
     }
 
+    /// Start a command with stdin, stdout and stderr attached
+    pub async fn start_exec(
+        &self,
+        container_id: &str,
+        options: &ExecOptions,
+    ) -> Result<AttachedExec> {
+        self.interactive_exec.start(container_id, options).await
+    }
+
+    pub async fn resize_exec(&self, exec_id: &str, rows: u16, cols: u16) -> Result<()> {
+        self.interactive_exec.resize(exec_id, rows, cols).await
+    }
+
+    pub async fn exec_exit_code(&self, exec_id: &str) -> Result<Option<i64>> {
+        self.interactive_exec.exit_code(exec_id).await
+    }
+
+    /// Write a file into a container
+    pub async fn upload_file(
+        &self,
+        container_id: &str,
+        path: &str,
+        mode: u32,
+        data: &[u8],
+    ) -> Result<()> {
+        self.file_transfer
+            .upload(container_id, path, mode, data)
+            .await
+    }
+
+    /// Start reading a file out of a container
+    pub async fn download_file(&self, container_id: &str, path: &str) -> Result<FileDownload> {
+        self.file_transfer.download(container_id, path).await
+    }
+
+    pub fn file_transfer_config(&self) -> &crate::config::FileTransferConfig {
+        self.file_transfer.config()
+    }
+
+    /// Hotkey of the validator a container was created for, if recorded
+    pub async fn container_owner(&self, container_id: &str) -> Result<Option<String>> {
+        self.operations.container_owner(container_id).await
+    }
+
+    /// Whether a container was created by this manager and is still tracked
+    pub async fn is_managed(&self, container_id: &str) -> bool {
+        self.active_containers
+            .read()
+            .await
+            .contains_key(container_id)
+    }
+
     pub async fn destroy_container(&self, container_id: &str, force: bool) -> Result<()> {
         self.operations.destroy_container(container_id, force).await
     }
@@ -133,6 +269,81 @@ impl ContainerManager {
         self.operations.get_container_stats(container_id).await
     }
 
+    /// GPUs currently assigned to containers
+    pub fn gpu_assignments(&self) -> Vec<GpuAssignment> {
+        self.gpu_allocator
+            .as_ref()
+            .map(|allocator| allocator.assignments())
+            .unwrap_or_default()
+    }
+
+    /// GPUs not assigned to any container
+    pub fn free_gpus(&self) -> Vec<GpuDevice> {
+        self.gpu_allocator
+            .as_ref()
+            .map(|allocator| allocator.free_devices())
+            .unwrap_or_default()
+    }
+
+    /// Every GPU the manager assigns to containers
+    pub fn gpu_devices(&self) -> Vec<GpuDevice> {
+        self.gpu_allocator
+            .as_ref()
+            .map(|allocator| allocator.devices().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Named volume management, if enabled
+    pub fn volumes(&self) -> Result<&VolumeManager> {
+        self.volumes
+            .as_deref()
+            .ok_or_else(|| anyhow!("Volume management is not enabled on this executor"))
+    }
+
+    /// Measure volume usage, stop containers writing past a volume's quota
+    /// and remove volumes whose owner is not in `active_owners` once their
+    /// grace period has passed
+    ///
+    /// Returns the number of volumes removed.
+    pub async fn maintain_volumes(&self, active_owners: &HashSet<String>) -> Result<u32> {
+        let Some(volumes) = &self.volumes else {
+            return Ok(0);
+        };
+
+        for violation in volumes.check_usage().await? {
+            let volume = &violation.volume;
+            warn!(
+                "Volume {} of {} uses {} bytes of its {} byte quota",
+                volume.volume_id, volume.owner_hotkey, volume.usage_bytes, volume.quota_bytes
+            );
+            log_security_violation(
+                Some(&volume.owner_hotkey),
+                "volume_quota_exceeded",
+                &format!(
+                    "Volume {} exceeded its quota of {} bytes",
+                    volume.name, volume.quota_bytes
+                ),
+                None,
+                SecuritySeverity::Medium,
+                HashMap::from([
+                    ("volume_id".to_string(), volume.volume_id.clone()),
+                    ("usage_bytes".to_string(), volume.usage_bytes.to_string()),
+                ]),
+            );
+
+            for container_id in &violation.container_ids {
+                if let Err(e) = self.operations.stop_container(container_id, None).await {
+                    warn!(
+                        "Failed to stop container {} over volume quota: {}",
+                        container_id, e
+                    );
+                }
+            }
+        }
+
+        volumes.collect_garbage(active_owners).await
+    }
+
     pub async fn health_check(&self) -> Result<()> {
         self.health_checker.health_check().await
     }
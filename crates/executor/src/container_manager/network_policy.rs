@@ -0,0 +1,658 @@
+//! Container network egress policy
+//!
+//! Each container with network access is attached to a bridge network of
+//! its own. Traffic leaving that bridge, towards other hosts or the host
+//! itself, is sent through an iptables chain that drops private ranges and
+//! metadata endpoints and enforces the destination allow-list, and the bridge
+//! is shaped with tc to the container's bandwidth limit. The network and chain
+//! are removed together with the container.
+
+use crate::config::{validate_egress_rule, DockerConfig, EgressPolicyConfig, EgressRule};
+use anyhow::{anyhow, Context, Result};
+use bollard::network::{CreateNetworkOptions, ListNetworksOptions};
+use bollard::Docker;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::path::Path;
+use std::sync::Mutex;
+use tokio::process::Command;
+use tracing::{debug, info, warn};
+
+/// Label naming the container an egress network belongs to
+const OWNER_LABEL: &str = "basilica.egress.container";
+
+/// Ranges dropped when `block_private_ranges` is set
+const PRIVATE_RANGES: &[&str] = &[
+    "0.0.0.0/8",
+    "10.0.0.0/8",
+    "100.64.0.0/10",
+    "127.0.0.0/8",
+    "169.254.0.0/16",
+    "172.16.0.0/12",
+    "192.168.0.0/16",
+    "224.0.0.0/4",
+    "240.0.0.0/4",
+];
+
+/// Instance metadata services of the common cloud providers
+const METADATA_ENDPOINTS: &[&str] = &["169.254.169.254/32", "100.100.100.200/32"];
+
+/// Built-in chains the per-container chain is hooked into: forwarded traffic
+/// goes through DOCKER-USER, traffic to the host itself through INPUT
+const HOOK_CHAINS: &[&str] = &["DOCKER-USER", "INPUT"];
+
+/// An IPv4 network in CIDR notation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4Cidr {
+    addr: Ipv4Addr,
+    prefix_len: u8,
+}
+
+impl Ipv4Cidr {
+    /// Parse `a.b.c.d/len`, or a bare address as a /32
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (addr, prefix_len) = match s.trim().split_once('/') {
+            Some((addr, len)) => {
+                let len = len
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|len| *len <= 32)
+                    .ok_or_else(|| format!("invalid prefix length in {s}"))?;
+                (addr, len)
+            }
+            None => (s.trim(), 32),
+        };
+        let addr = addr
+            .parse::<Ipv4Addr>()
+            .map_err(|_| format!("{s} is not an IPv4 address or CIDR block"))?;
+
+        // Normalize to the network address so `10.1.2.3/8` means `10.0.0.0/8`
+        Ok(Self {
+            addr: Ipv4Addr::from(u32::from(addr) & mask(prefix_len)),
+            prefix_len,
+        })
+    }
+
+    /// Whether `other` lies entirely within this network
+    pub fn contains(&self, other: &Ipv4Cidr) -> bool {
+        other.prefix_len >= self.prefix_len
+            && u32::from(other.addr) & mask(self.prefix_len) == u32::from(self.addr)
+    }
+}
+
+impl fmt::Display for Ipv4Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+fn mask(prefix_len: u8) -> u32 {
+    u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0)
+}
+
+/// Egress rules and limits in force for one container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedEgress {
+    /// Allowed destinations, any public address when empty
+    pub rules: Vec<EgressRule>,
+    pub dns_servers: Vec<Ipv4Addr>,
+    pub block_private_ranges: bool,
+    pub block_metadata_endpoints: bool,
+    /// Bandwidth cap in bytes per second, in each direction
+    pub bandwidth_bps: Option<u64>,
+}
+
+/// Combines the operator's egress configuration with per-container requests
+#[derive(Debug, Clone)]
+pub struct EgressPolicy {
+    config: EgressPolicyConfig,
+    dns_servers: Vec<Ipv4Addr>,
+}
+
+impl EgressPolicy {
+    pub fn new(config: &DockerConfig) -> Self {
+        Self {
+            config: config.network_config.egress.clone(),
+            dns_servers: config
+                .network_config
+                .dns_servers
+                .iter()
+                .filter_map(|server| server.parse().ok())
+                .collect(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Work out the rules for a container from the rules it requested
+    ///
+    /// Requested rules may only narrow the operator's allow-list: each must
+    /// fall inside an allowed CIDR and use only ports that rule allows. With
+    /// no requested rules the operator's allow-list applies as is.
+    pub fn resolve(
+        &self,
+        requested: &[EgressRule],
+        bandwidth_bps: Option<u64>,
+    ) -> Result<ResolvedEgress> {
+        for rule in requested {
+            validate_egress_rule(rule).map_err(|e| anyhow!(e))?;
+            if !self.config.allowed.is_empty() && !self.is_within_allowed(rule)? {
+                return Err(anyhow!(
+                    "Egress rule for {} is not within the executor's allowed destinations",
+                    rule.cidr
+                ));
+            }
+        }
+
+        let rules = if requested.is_empty() {
+            self.config.allowed.clone()
+        } else {
+            requested.to_vec()
+        };
+
+        Ok(ResolvedEgress {
+            rules,
+            dns_servers: self.dns_servers.clone(),
+            block_private_ranges: self.config.block_private_ranges,
+            block_metadata_endpoints: self.config.block_metadata_endpoints,
+            bandwidth_bps: bandwidth_bps.filter(|bps| *bps > 0),
+        })
+    }
+
+    fn is_within_allowed(&self, rule: &EgressRule) -> Result<bool> {
+        let cidr = Ipv4Cidr::parse(&rule.cidr).map_err(|e| anyhow!(e))?;
+        for allowed in &self.config.allowed {
+            let allowed_cidr = Ipv4Cidr::parse(&allowed.cidr).map_err(|e| anyhow!(e))?;
+            let protocol_ok = match (&allowed.protocol, &rule.protocol) {
+                (None, _) => true,
+                (Some(a), Some(r)) => a == r,
+                (Some(_), None) => false,
+            };
+            let ports_ok = allowed.ports.is_empty()
+                || (!rule.ports.is_empty() && rule.ports.iter().all(|p| allowed.ports.contains(p)));
+            if allowed_cidr.contains(&cidr) && protocol_ok && ports_ok {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Names of the Docker network, bridge and iptables chain of one container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EgressNetwork {
+    pub container_name: String,
+    pub network_name: String,
+    pub bridge: String,
+    pub chain: String,
+}
+
+impl EgressNetwork {
+    /// Derive the names from the container name, keeping within the 15
+    /// character interface and 28 character chain name limits
+    pub fn for_container(container_name: &str) -> Self {
+        let suffix: String = container_name
+            .rsplit('-')
+            .next()
+            .unwrap_or(container_name)
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .take(10)
+            .collect();
+
+        Self {
+            container_name: container_name.to_string(),
+            network_name: format!("{container_name}-net"),
+            bridge: format!("bsl-{suffix}"),
+            chain: format!("BASILICA-{}", suffix.to_uppercase()),
+        }
+    }
+}
+
+/// iptables arguments building the container's chain, in order
+pub fn chain_rules(network: &EgressNetwork, egress: &ResolvedEgress) -> Vec<Vec<String>> {
+    let append = |args: &[&str]| -> Vec<String> {
+        ["-A", network.chain.as_str()]
+            .iter()
+            .chain(args)
+            .map(|arg| arg.to_string())
+            .collect()
+    };
+
+    let mut rules = vec![append(&[
+        "-m",
+        "conntrack",
+        "--ctstate",
+        "ESTABLISHED,RELATED",
+        "-j",
+        "RETURN",
+    ])];
+
+    for server in &egress.dns_servers {
+        let server = server.to_string();
+        for protocol in ["udp", "tcp"] {
+            rules.push(append(&[
+                "-d", &server, "-p", protocol, "--dport", "53", "-j", "RETURN",
+            ]));
+        }
+    }
+
+    if egress.block_metadata_endpoints {
+        for cidr in METADATA_ENDPOINTS {
+            rules.push(append(&["-d", cidr, "-j", "DROP"]));
+        }
+    }
+    if egress.block_private_ranges {
+        for cidr in PRIVATE_RANGES {
+            rules.push(append(&["-d", cidr, "-j", "DROP"]));
+        }
+    }
+
+    for rule in &egress.rules {
+        if rule.ports.is_empty() && rule.protocol.is_none() {
+            rules.push(append(&["-d", &rule.cidr, "-j", "RETURN"]));
+            continue;
+        }
+
+        let protocols = match &rule.protocol {
+            Some(protocol) => vec![protocol.as_str()],
+            None => vec!["tcp", "udp"],
+        };
+        let ports = rule
+            .ports
+            .iter()
+            .map(u16::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        for protocol in protocols {
+            let mut args = vec!["-d", rule.cidr.as_str(), "-p", protocol];
+            if !ports.is_empty() {
+                args.extend(["-m", "multiport", "--dports", ports.as_str()]);
+            }
+            args.extend(["-j", "RETURN"]);
+            rules.push(append(&args));
+        }
+    }
+
+    let last = if egress.rules.is_empty() {
+        "RETURN"
+    } else {
+        "DROP"
+    };
+    rules.push(append(&["-j", last]));
+
+    rules
+}
+
+/// tc arguments capping the bridge to `bytes_per_sec` in each direction
+///
+/// The root qdisc shapes traffic towards the container, the ingress policer
+/// drops traffic from the container above the rate.
+pub fn bandwidth_commands(bridge: &str, bytes_per_sec: u64) -> Vec<Vec<String>> {
+    let rate = format!("{}bit", bytes_per_sec.saturating_mul(8));
+    let burst = (bytes_per_sec / 10).max(64 * 1024).to_string();
+
+    [
+        vec![
+            "qdisc", "replace", "dev", bridge, "root", "tbf", "rate", &rate, "burst", &burst,
+            "latency", "50ms",
+        ],
+        vec![
+            "qdisc", "replace", "dev", bridge, "handle", "ffff:", "ingress",
+        ],
+        vec![
+            "filter", "replace", "dev", bridge, "parent", "ffff:", "protocol", "ip", "prio", "1",
+            "u32", "match", "u32", "0", "0", "police", "rate", &rate, "burst", &burst, "drop",
+            "flowid", ":1",
+        ],
+    ]
+    .into_iter()
+    .map(|args| args.into_iter().map(String::from).collect())
+    .collect()
+}
+
+/// Creates, tracks and removes per-container egress networks
+#[derive(Debug)]
+pub struct NetworkPolicyManager {
+    docker: Docker,
+    policy: EgressPolicy,
+    networks: Mutex<HashMap<String, EgressNetwork>>,
+}
+
+impl NetworkPolicyManager {
+    pub fn new(docker: Docker, config: &DockerConfig) -> Self {
+        Self {
+            docker,
+            policy: EgressPolicy::new(config),
+            networks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn policy(&self) -> &EgressPolicy {
+        &self.policy
+    }
+
+    /// Create the network for a new container and apply its egress rules
+    ///
+    /// The network is tracked under `owner` until [`Self::rename`] moves it
+    /// to the container ID.
+    pub async fn prepare(
+        &self,
+        owner: &str,
+        requested: &[EgressRule],
+        bandwidth_bps: Option<u64>,
+    ) -> Result<EgressNetwork> {
+        let egress = self.policy.resolve(requested, bandwidth_bps)?;
+        let network = EgressNetwork::for_container(owner);
+
+        let create_options = CreateNetworkOptions {
+            name: network.network_name.clone(),
+            check_duplicate: true,
+            driver: "bridge".to_string(),
+            options: HashMap::from([
+                (
+                    "com.docker.network.bridge.name".to_string(),
+                    network.bridge.clone(),
+                ),
+                (
+                    "com.docker.network.bridge.enable_icc".to_string(),
+                    "false".to_string(),
+                ),
+            ]),
+            labels: HashMap::from([(OWNER_LABEL.to_string(), owner.to_string())]),
+            ..Default::default()
+        };
+        self.docker
+            .create_network(create_options)
+            .await
+            .with_context(|| format!("Failed to create network {}", network.network_name))?;
+
+        if let Err(e) = self.apply(&network, &egress).await {
+            self.teardown(&network).await;
+            return Err(e);
+        }
+
+        info!(
+            "Prepared egress network {} with {} allow rule(s)",
+            network.network_name,
+            egress.rules.len()
+        );
+        self.lock().insert(owner.to_string(), network.clone());
+        Ok(network)
+    }
+
+    async fn apply(&self, network: &EgressNetwork, egress: &ResolvedEgress) -> Result<()> {
+        let iptables = &self.policy.config.iptables_path;
+
+        // A chain left by an earlier run with the same name is reused
+        let _ = run(iptables, &["-w", "-N", &network.chain]).await;
+        run(iptables, &["-w", "-F", &network.chain]).await?;
+        for rule in chain_rules(network, egress) {
+            let args: Vec<&str> = std::iter::once("-w")
+                .chain(rule.iter().map(String::as_str))
+                .collect();
+            run(iptables, &args).await?;
+        }
+        for hook in HOOK_CHAINS {
+            run(
+                iptables,
+                &[
+                    "-w",
+                    "-I",
+                    hook,
+                    "-i",
+                    &network.bridge,
+                    "-j",
+                    &network.chain,
+                ],
+            )
+            .await?;
+        }
+
+        if let Some(bps) = egress.bandwidth_bps {
+            for command in bandwidth_commands(&network.bridge, bps) {
+                let args: Vec<&str> = command.iter().map(String::as_str).collect();
+                run(&self.policy.config.tc_path, &args).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move a network to a new owner, e.g. from container name to container ID
+    pub fn rename(&self, from: &str, to: &str) {
+        let mut networks = self.lock();
+        if let Some(network) = networks.remove(from) {
+            networks.insert(to.to_string(), network);
+        }
+    }
+
+    /// Track the network of a container re-adopted after a restart
+    pub fn adopt(&self, container_id: &str, container_name: &str) {
+        self.lock().insert(
+            container_id.to_string(),
+            EgressNetwork::for_container(container_name),
+        );
+    }
+
+    /// Remove the network and rules of a container that has been removed
+    pub async fn release(&self, owner: &str) {
+        let network = self.lock().remove(owner);
+        if let Some(network) = network {
+            self.teardown(&network).await;
+        }
+    }
+
+    /// Remove egress networks whose container is not in `live_containers`
+    pub async fn remove_orphans(&self, live_containers: &HashSet<String>) -> Result<()> {
+        let list_options = ListNetworksOptions {
+            filters: HashMap::from([("label".to_string(), vec![OWNER_LABEL.to_string()])]),
+        };
+
+        for network in self.docker.list_networks(Some(list_options)).await? {
+            let Some(owner) = network
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(OWNER_LABEL))
+            else {
+                continue;
+            };
+            if !live_containers.contains(owner) {
+                info!("Removing egress network left by container {}", owner);
+                self.teardown(&EgressNetwork::for_container(owner)).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Delete the chain, its hooks and the Docker network, logging failures
+    async fn teardown(&self, network: &EgressNetwork) {
+        let iptables = &self.policy.config.iptables_path;
+        for hook in HOOK_CHAINS {
+            // Repeated hooks are possible if an earlier teardown was interrupted
+            while run(
+                iptables,
+                &[
+                    "-w",
+                    "-D",
+                    hook,
+                    "-i",
+                    &network.bridge,
+                    "-j",
+                    &network.chain,
+                ],
+            )
+            .await
+            .is_ok()
+            {}
+        }
+        if run(iptables, &["-w", "-F", &network.chain]).await.is_ok() {
+            if let Err(e) = run(iptables, &["-w", "-X", &network.chain]).await {
+                warn!("Failed to delete chain {}: {}", network.chain, e);
+            }
+        }
+
+        match self.docker.remove_network(&network.network_name).await {
+            Ok(()) => debug!("Removed network {}", network.network_name),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {}
+            Err(e) => warn!("Failed to remove network {}: {}", network.network_name, e),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, EgressNetwork>> {
+        self.networks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+async fn run(program: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .with_context(|| format!("Failed to run {}", program.display()))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{} {} failed: {}",
+            program.display(),
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(cidr: &str, ports: &[u16]) -> EgressRule {
+        EgressRule {
+            cidr: cidr.to_string(),
+            ports: ports.to_vec(),
+            protocol: None,
+        }
+    }
+
+    fn policy(allowed: Vec<EgressRule>) -> EgressPolicy {
+        let mut config = DockerConfig::default();
+        config.network_config.egress = EgressPolicyConfig {
+            enabled: true,
+            allowed,
+            ..Default::default()
+        };
+        EgressPolicy::new(&config)
+    }
+
+    #[test]
+    fn test_cidr_containment() {
+        let net = Ipv4Cidr::parse("10.1.2.3/8").unwrap();
+        assert_eq!(net.to_string(), "10.0.0.0/8");
+        assert!(net.contains(&Ipv4Cidr::parse("10.20.0.0/16").unwrap()));
+        assert!(net.contains(&Ipv4Cidr::parse("10.20.0.1").unwrap()));
+        assert!(!net.contains(&Ipv4Cidr::parse("11.0.0.0/16").unwrap()));
+        assert!(!net.contains(&Ipv4Cidr::parse("0.0.0.0/0").unwrap()));
+        assert!(Ipv4Cidr::parse("0.0.0.0/0")
+            .unwrap()
+            .contains(&Ipv4Cidr::parse("203.0.113.7").unwrap()));
+        assert!(Ipv4Cidr::parse("10.0.0.0/33").is_err());
+        assert!(Ipv4Cidr::parse("2001:db8::/32").is_err());
+    }
+
+    #[test]
+    fn test_requested_rules_must_narrow_allow_list() {
+        let restricted = policy(vec![rule("203.0.113.0/24", &[443, 80])]);
+
+        let resolved = restricted.resolve(&[], Some(1_000_000)).unwrap();
+        assert_eq!(resolved.rules, vec![rule("203.0.113.0/24", &[443, 80])]);
+        assert_eq!(resolved.bandwidth_bps, Some(1_000_000));
+
+        let narrowed = restricted
+            .resolve(&[rule("203.0.113.10", &[443])], None)
+            .unwrap();
+        assert_eq!(narrowed.rules, vec![rule("203.0.113.10", &[443])]);
+
+        assert!(restricted
+            .resolve(&[rule("198.51.100.0/24", &[443])], None)
+            .is_err());
+        assert!(restricted
+            .resolve(&[rule("203.0.113.10", &[22])], None)
+            .is_err());
+        assert!(restricted
+            .resolve(&[rule("203.0.113.10", &[])], None)
+            .is_err());
+
+        let open = policy(vec![]);
+        assert!(open.resolve(&[rule("198.51.100.0/24", &[])], None).is_ok());
+        assert!(open.resolve(&[rule("not-a-cidr", &[])], None).is_err());
+    }
+
+    #[test]
+    fn test_chain_rules() {
+        let network = EgressNetwork::for_container("basilca-1a2b3c4d");
+        assert_eq!(network.bridge, "bsl-1a2b3c4d");
+        assert_eq!(network.chain, "BASILICA-1A2B3C4D");
+
+        let egress = ResolvedEgress {
+            rules: vec![rule("203.0.113.0/24", &[443])],
+            dns_servers: vec![Ipv4Addr::new(8, 8, 8, 8)],
+            block_private_ranges: true,
+            block_metadata_endpoints: true,
+            bandwidth_bps: None,
+        };
+        let rules: Vec<String> = chain_rules(&network, &egress)
+            .into_iter()
+            .map(|rule| rule.join(" "))
+            .collect();
+
+        assert_eq!(
+            rules[0],
+            "-A BASILICA-1A2B3C4D -m conntrack --ctstate ESTABLISHED,RELATED -j RETURN"
+        );
+        assert!(rules
+            .contains(&"-A BASILICA-1A2B3C4D -d 8.8.8.8 -p udp --dport 53 -j RETURN".to_string()));
+        let metadata = rules
+            .iter()
+            .position(|r| r == "-A BASILICA-1A2B3C4D -d 169.254.169.254/32 -j DROP")
+            .unwrap();
+        let private = rules
+            .iter()
+            .position(|r| r == "-A BASILICA-1A2B3C4D -d 10.0.0.0/8 -j DROP")
+            .unwrap();
+        let allow = rules
+            .iter()
+            .position(|r| {
+                r == "-A BASILICA-1A2B3C4D -d 203.0.113.0/24 -p tcp -m multiport --dports 443 -j RETURN"
+            })
+            .unwrap();
+        assert!(metadata < allow && private < allow);
+        assert_eq!(rules.last().unwrap(), "-A BASILICA-1A2B3C4D -j DROP");
+
+        let open = ResolvedEgress {
+            rules: vec![],
+            block_private_ranges: false,
+            ..egress
+        };
+        let rules = chain_rules(&network, &open);
+        assert!(!rules.iter().any(|r| r.contains(&"10.0.0.0/8".to_string())));
+        assert_eq!(
+            rules.last().unwrap().join(" "),
+            "-A BASILICA-1A2B3C4D -j RETURN"
+        );
+
+        let tc = bandwidth_commands("bsl-1a2b3c4d", 12_500_000);
+        assert!(tc[0]
+            .join(" ")
+            .contains("tbf rate 100000000bit burst 1250000"));
+        assert!(tc[2].join(" ").contains("police rate 100000000bit"));
+    }
+}
@@ -1,21 +1,30 @@
 //! Container operations and lifecycle management
 
 use super::config_builder::ContainerConfigBuilder;
-use super::types::{ContainerExecutionResult, ContainerResourceUsage, ContainerStatus};
+use super::gpu_allocator::{GpuAllocator, GpuDevice, GpuRequest};
+use super::image_policy::{ImagePolicy, ImagePolicyViolation};
+use super::network_policy::{EgressNetwork, NetworkPolicyManager};
+use super::types::{
+    ContainerCreateOptions, ContainerExecutionResult, ContainerResourceUsage, ContainerStatus,
+    CreatedContainer,
+};
 use crate::config::{ContainerResourceLimits, DockerConfig};
-use anyhow::Result;
+use crate::persistence::{ContainerRecord, ExecutorDb};
+use anyhow::{anyhow, Result};
 use bollard::{
     container::{
-        CreateContainerOptions, KillContainerOptions, RemoveContainerOptions,
-        RestartContainerOptions, StartContainerOptions, StatsOptions, StopContainerOptions,
-        WaitContainerOptions,
+        CreateContainerOptions, KillContainerOptions, ListContainersOptions,
+        RemoveContainerOptions, RestartContainerOptions, StartContainerOptions, StatsOptions,
+        StopContainerOptions, WaitContainerOptions,
     },
     exec::{CreateExecOptions, StartExecResults},
     image::CreateImageOptions,
+    models::ContainerSummary,
     Docker,
 };
+use common::journal::{log_security_violation, SecuritySeverity};
 use futures_util::stream::StreamExt;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
@@ -27,16 +36,27 @@ pub struct ContainerOperations {
     active_containers: Arc<RwLock<HashMap<String, ContainerStatus>>>,
     config_builder: ContainerConfigBuilder,
     lifecycle: ContainerLifecycle,
+    gpu_allocator: Option<Arc<GpuAllocator>>,
+    image_policy: ImagePolicy,
+    network_policy: Arc<NetworkPolicyManager>,
+    db: Option<ExecutorDb>,
 }
 
+/// Prefix of the names given to containers the executor creates
+const CONTAINER_NAME_PREFIX: &str = "basilca-";
+
 impl ContainerOperations {
     pub fn new(
         docker: Docker,
         config: DockerConfig,
         active_containers: Arc<RwLock<HashMap<String, ContainerStatus>>>,
+        gpu_allocator: Option<Arc<GpuAllocator>>,
+        db: Option<ExecutorDb>,
     ) -> Self {
         let config_builder = ContainerConfigBuilder::new(config.clone());
         let lifecycle = ContainerLifecycle::new(docker.clone(), active_containers.clone());
+        let image_policy = ImagePolicy::new(&config);
+        let network_policy = Arc::new(NetworkPolicyManager::new(docker.clone(), &config));
 
         Self {
             docker,
@@ -44,6 +64,10 @@ impl ContainerOperations {
             active_containers,
             config_builder,
             lifecycle,
+            gpu_allocator,
+            image_policy,
+            network_policy,
+            db,
         }
     }
 
@@ -53,21 +77,220 @@ impl ContainerOperations {
         command: &[String],
         resource_limits: Option<ContainerResourceLimits>,
     ) -> Result<String> {
+        let created = self
+            .create_container_with_options(
+                image,
+                command,
+                resource_limits,
+                &ContainerCreateOptions::default(),
+            )
+            .await?;
+        Ok(created.container_id)
+    }
+
+    pub async fn create_container_with_options(
+        &self,
+        image: &str,
+        command: &[String],
+        resource_limits: Option<ContainerResourceLimits>,
+        options: &ContainerCreateOptions,
+    ) -> Result<CreatedContainer> {
         info!(
             "Creating container with image: {} and command: {:?}",
             image, command
         );
 
-        self.ensure_image_available(image).await?;
+        // Requested modes such as `host` would bypass isolation and egress filtering
+        let network_mode = self
+            .config
+            .network_config
+            .resolve_network_mode(
+                options.network_mode.as_deref(),
+                !options.port_mappings.is_empty(),
+            )
+            .map_err(|e| anyhow!(e))?;
+        let options = &ContainerCreateOptions {
+            network_mode: Some(network_mode),
+            ..options.clone()
+        };
+
+        // Signed images run by the digest their signature covers
+        let image = &self.ensure_image_available(image).await?;
 
         let uuid_str = uuid::Uuid::new_v4().to_string();
-        let container_name = format!("basilca-{}", &uuid_str[..8]);
+        let container_name = format!("{CONTAINER_NAME_PREFIX}{}", &uuid_str[..8]);
 
         let limits = resource_limits.unwrap_or_else(|| self.config.resource_limits.clone());
-        let container_config = self.config_builder.build(image, command, &limits)?;
+
+        // GPUs are reserved under the container name until Docker assigns an ID
+        let gpu_devices = self
+            .allocate_gpus(&container_name, &options.gpu_request)
+            .await?;
+        let device_ids: Option<Vec<String>> = gpu_devices
+            .as_ref()
+            .map(|devices| devices.iter().map(GpuDevice::device_id).collect());
+
+        // The egress network is likewise tracked under the name until then
+        let egress_network = match self.prepare_egress(&container_name, options, &limits).await {
+            Ok(network) => network,
+            Err(e) => {
+                self.release_gpus(&container_name).await;
+                return Err(e);
+            }
+        };
+        let create_options = match &egress_network {
+            Some(network) => ContainerCreateOptions {
+                network_mode: Some(network.network_name.clone()),
+                ..options.clone()
+            },
+            None => options.clone(),
+        };
+
+        let container_id = match self
+            .create_and_start(
+                &container_name,
+                image,
+                command,
+                &limits,
+                &create_options,
+                device_ids.as_deref(),
+            )
+            .await
+        {
+            Ok(container_id) => container_id,
+            Err(e) => {
+                self.release_gpus(&container_name).await;
+                self.network_policy.release(&container_name).await;
+                return Err(e);
+            }
+        };
+
+        if let Some(allocator) = &self.gpu_allocator {
+            allocator.rename(&container_name, &container_id).await?;
+        }
+        self.network_policy.rename(&container_name, &container_id);
+
+        self.lifecycle
+            .track_container(&container_id, &container_name, image)
+            .await;
+
+        if let Some(db) = &self.db {
+            let record = ContainerRecord {
+                container_id: container_id.clone(),
+                name: container_name.clone(),
+                image: image.to_string(),
+                ssh_public_key: options.ssh_public_key.clone(),
+                owner_hotkey: options.owner_hotkey.clone(),
+                created_at: chrono::Utc::now(),
+            };
+            if let Err(e) = db.record_container(&record).await {
+                warn!("Failed to record container {}: {}", container_id, e);
+            }
+        }
+
+        let ssh_user = match options.ssh_public_key {
+            Some(_) => self.install_ssh_key(&container_id).await,
+            None => None,
+        };
+
+        let ports = self.published_ports(&container_id).await?;
+
+        Ok(CreatedContainer {
+            container_id,
+            ports,
+            ssh_user,
+        })
+    }
+
+    /// Reserve GPUs for a new container
+    ///
+    /// Returns `None` when GPUs are not tracked, in which case the container
+    /// sees every GPU.
+    async fn allocate_gpus(
+        &self,
+        owner: &str,
+        request: &GpuRequest,
+    ) -> Result<Option<Vec<GpuDevice>>> {
+        if !self.config.enable_gpu_passthrough {
+            if !request.is_empty() {
+                return Err(anyhow!(
+                    "GPUs were requested but GPU passthrough is disabled"
+                ));
+            }
+            return Ok(None);
+        }
+
+        // A container only sees the GPUs it asked for
+        if request.is_empty() {
+            return Ok(Some(Vec::new()));
+        }
+
+        let Some(allocator) = &self.gpu_allocator else {
+            return Ok(None);
+        };
+
+        let devices = allocator.allocate(owner, request).await?;
+        info!(
+            "Assigned GPU(s) {:?} to {}",
+            devices.iter().map(GpuDevice::device_id).collect::<Vec<_>>(),
+            owner
+        );
+        Ok(Some(devices))
+    }
+
+    /// Create the egress network of a new container when filtering applies
+    ///
+    /// Returns `None` when egress filtering is disabled or the container has
+    /// no network access. Network modes that would bypass the filter, such as
+    /// `host`, are refused while it is enabled.
+    async fn prepare_egress(
+        &self,
+        owner: &str,
+        options: &ContainerCreateOptions,
+        limits: &ContainerResourceLimits,
+    ) -> Result<Option<EgressNetwork>> {
+        if !self.network_policy.policy().enabled() {
+            if !options.egress_rules.is_empty() {
+                return Err(anyhow!(
+                    "Egress rules were requested but egress filtering is disabled"
+                ));
+            }
+            return Ok(None);
+        }
+
+        let network_mode = options
+            .network_mode
+            .clone()
+            .unwrap_or_else(|| self.config_builder.build_network_mode());
+        match network_mode.as_str() {
+            "none" => Ok(None),
+            "bridge" | "default" => self
+                .network_policy
+                .prepare(owner, &options.egress_rules, limits.network_bps)
+                .await
+                .map(Some),
+            other => Err(anyhow!(
+                "Network mode {} is not allowed while egress filtering is enabled",
+                other
+            )),
+        }
+    }
+
+    async fn create_and_start(
+        &self,
+        container_name: &str,
+        image: &str,
+        command: &[String],
+        limits: &ContainerResourceLimits,
+        options: &ContainerCreateOptions,
+        gpu_devices: Option<&[String]>,
+    ) -> Result<String> {
+        let container_config =
+            self.config_builder
+                .build_with_options(image, command, limits, options, gpu_devices)?;
 
         let create_options = CreateContainerOptions {
-            name: container_name.clone(),
+            name: container_name.to_string(),
             platform: None,
         };
 
@@ -84,12 +307,210 @@ impl ContainerOperations {
             .await?;
 
         info!("Started container: {}", container_id);
+        Ok(container_id)
+    }
 
-        self.lifecycle
-            .track_container(&container_id, &container_name, image)
-            .await;
+    /// Drop GPU assignments whose containers no longer exist
+    pub async fn reconcile_gpu_assignments(&self) -> Result<()> {
+        let Some(allocator) = &self.gpu_allocator else {
+            return Ok(());
+        };
 
-        Ok(container_id)
+        for assignment in allocator.assignments() {
+            match self
+                .docker
+                .inspect_container(&assignment.container_id, None)
+                .await
+            {
+                Ok(_) => {}
+                Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => {
+                    info!(
+                        "Container {} is gone, releasing its GPUs",
+                        assignment.container_id
+                    );
+                    allocator.release(&assignment.container_id).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-adopt containers left running by a previous run and remove the rest
+    ///
+    /// Running executor containers are tracked again so they can be listed
+    /// and destroyed; stopped ones are removed and their GPUs released.
+    /// Records of containers Docker no longer knows about are dropped.
+    pub async fn reconcile_containers(&self) -> Result<()> {
+        let Some(db) = &self.db else {
+            return Ok(());
+        };
+
+        let list_options = ListContainersOptions {
+            all: true,
+            filters: HashMap::from([("name", vec![CONTAINER_NAME_PREFIX])]),
+            ..Default::default()
+        };
+        let containers = self.docker.list_containers(Some(list_options)).await?;
+        let reconciliation = Reconciliation::plan(db, containers).await?;
+
+        let mut removed = 0;
+        for container_id in &reconciliation.remove {
+            info!(
+                "Removing container {} left stopped by a previous run",
+                container_id
+            );
+            let remove_options = RemoveContainerOptions {
+                force: true,
+                v: true,
+                link: false,
+            };
+            match self
+                .docker
+                .remove_container(container_id, Some(remove_options))
+                .await
+            {
+                Ok(()) => {
+                    self.forget_container(container_id).await;
+                    removed += 1;
+                }
+                Err(e) => warn!("Failed to remove container {}: {}", container_id, e),
+            }
+        }
+
+        reconciliation
+            .record(db, self.gpu_allocator.as_deref())
+            .await?;
+
+        let mut live_names = HashSet::new();
+        let mut adopted = 0;
+        for record in &reconciliation.adopt {
+            let container_id = &record.container_id;
+            let inspect = match self.docker.inspect_container(container_id, None).await {
+                Ok(inspect) => inspect,
+                Err(e) => {
+                    warn!("Failed to inspect container {}: {}", container_id, e);
+                    continue;
+                }
+            };
+            let networks: HashSet<String> = inspect
+                .network_settings
+                .as_ref()
+                .and_then(|settings| settings.networks.as_ref())
+                .map(|networks| networks.keys().cloned().collect())
+                .unwrap_or_default();
+            let mut status = self
+                .lifecycle
+                .build_status_from_inspect(container_id, inspect);
+            status.name = record.name.clone();
+            status.image = record.image.clone();
+
+            if networks.contains(&EgressNetwork::for_container(&record.name).network_name) {
+                self.network_policy.adopt(container_id, &record.name);
+            }
+            live_names.insert(record.name.clone());
+
+            self.active_containers
+                .write()
+                .await
+                .insert(container_id.clone(), status);
+            adopted += 1;
+        }
+
+        if self.network_policy.policy().enabled() {
+            self.network_policy.remove_orphans(&live_names).await?;
+        }
+
+        if adopted > 0 || removed > 0 {
+            info!(
+                "Re-adopted {} running container(s) and removed {} stopped container(s)",
+                adopted, removed
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Release the GPUs assigned to a container that no longer exists
+    async fn release_gpus(&self, container_id: &str) {
+        if let Some(allocator) = &self.gpu_allocator {
+            allocator.release(container_id).await;
+        }
+    }
+
+    /// Stop tracking a container that no longer exists
+    async fn forget_container(&self, container_id: &str) {
+        self.active_containers.write().await.remove(container_id);
+        self.release_gpus(container_id).await;
+        self.network_policy.release(container_id).await;
+        if let Some(db) = &self.db {
+            if let Err(e) = db.remove_container(container_id).await {
+                warn!(
+                    "Failed to delete record of container {}: {}",
+                    container_id, e
+                );
+            }
+        }
+    }
+
+    /// Authorize the key passed in SSH_PUBLIC_KEY for the container's default user
+    ///
+    /// sshd reads keys from the home directory in /etc/passwd rather than $HOME.
+    /// Returns the user the key was installed for. Images that manage their own
+    /// SSH setup may lack a shell, so failures are only logged.
+    async fn install_ssh_key(&self, container_id: &str) -> Option<String> {
+        let command = concat!(
+            r#"dir=$(awk -F: -v uid="$(id -u)" '$3 == uid { print $6 }' /etc/passwd); "#,
+            r#"dir=${dir:-$HOME}; "#,
+            r#"mkdir -p "$dir/.ssh" && chmod 700 "$dir/.ssh" && "#,
+            r#"printf '%s\n' "$SSH_PUBLIC_KEY" >> "$dir/.ssh/authorized_keys" && "#,
+            r#"chmod 600 "$dir/.ssh/authorized_keys" && "#,
+            r#"id -un"#,
+        );
+
+        match self.execute_command(container_id, command, None).await {
+            Ok(result) if result.exit_code == 0 => {
+                debug!("Installed SSH key in container {}", container_id);
+                Some(result.stdout.trim().to_string()).filter(|user| !user.is_empty())
+            }
+            Ok(result) => {
+                warn!(
+                    "Failed to install SSH key in container {} (exit code {}): {}",
+                    container_id, result.exit_code, result.stderr
+                );
+                None
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to install SSH key in container {}: {}",
+                    container_id, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Host ports Docker published for a container, keyed by container port
+    pub async fn published_ports(&self, container_id: &str) -> Result<HashMap<String, u16>> {
+        let container = self.docker.inspect_container(container_id, None).await?;
+
+        let ports = container
+            .network_settings
+            .and_then(|settings| settings.ports)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(container_port, bindings)| {
+                bindings?
+                    .iter()
+                    .find_map(|b| b.host_port.as_deref()?.parse::<u16>().ok())
+                    .map(|host_port| (container_port, host_port))
+            })
+            .collect();
+
+        Ok(ports)
     }
 
     pub async fn execute_command(
@@ -319,21 +740,33 @@ impl ContainerOperations {
             .remove_container(container_id, Some(remove_options))
             .await?;
 
-        self.active_containers.write().await.remove(container_id);
+        self.forget_container(container_id).await;
         info!("Container destroyed: {}", container_id);
         Ok(())
     }
 
+    /// Hotkey of the validator a container was created for
+    ///
+    /// Containers are only attributed to an owner when the executor database
+    /// is in use.
+    pub async fn container_owner(&self, container_id: &str) -> Result<Option<String>> {
+        let Some(db) = &self.db else {
+            return Ok(None);
+        };
+        Ok(db
+            .get_container(container_id)
+            .await?
+            .and_then(|record| record.owner_hotkey))
+    }
+
     pub async fn get_container_status(
         &self,
         container_id: &str,
     ) -> Result<Option<ContainerStatus>> {
         debug!("Getting status for container: {}", container_id);
 
-        if let Some(status) = self.active_containers.read().await.get(container_id) {
-            return Ok(Some(status.clone()));
-        }
-
+        // Inspect the live container so exits and removals are reported, the
+        // tracked status only reflects what the executor last did to it
         match self.docker.inspect_container(container_id, None).await {
             Ok(container) => {
                 let status = self
@@ -341,7 +774,13 @@ impl ContainerOperations {
                     .build_status_from_inspect(container_id, container);
                 Ok(Some(status))
             }
-            Err(_) => Ok(None),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                self.forget_container(container_id).await;
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -604,47 +1043,78 @@ impl ContainerOperations {
         }
     }
 
-    /// Validate image against allowed registries
-    fn validate_image_registry(&self, image: &str) -> Result<()> {
-        let allowed_registries = &self.config.registry.allowed_registries;
-
-        // Extract registry from image name
-        let registry = if image.contains('/') {
-            image.split('/').next().unwrap_or("")
-        } else {
-            "docker.io" // Default registry
-        };
-
-        if !allowed_registries
-            .iter()
-            .any(|allowed| registry.contains(allowed))
-        {
-            return Err(anyhow::anyhow!(
-                "Image registry '{}' not in allowed list: {:?}",
-                registry,
-                allowed_registries
-            ));
-        }
-
-        Ok(())
+    /// Log an image policy rejection to the journal and turn it into an error
+    fn reject_image(&self, violation: ImagePolicyViolation) -> anyhow::Error {
+        log_security_violation(
+            None,
+            violation.rule.code(),
+            &violation.message,
+            None,
+            SecuritySeverity::Medium,
+            HashMap::from([("image".to_string(), violation.image.clone())]),
+        );
+        violation.into()
     }
 
-    async fn ensure_image_available(&self, image: &str) -> Result<()> {
+    /// Apply the image policy and pull the image if it is not present
+    ///
+    /// Returns the reference to run, which is pinned to the verified digest
+    /// when signatures are required.
+    async fn ensure_image_available(&self, image: &str) -> Result<String> {
         debug!("Ensuring image is available: {}", image);
 
-        // Validate image registry
-        self.validate_image_registry(image)?;
+        let reference = self
+            .image_policy
+            .check_reference(image)
+            .map_err(|v| self.reject_image(v))?;
+        let verified_digest = self
+            .image_policy
+            .verify_signature(image)
+            .await
+            .map_err(|v| self.reject_image(v))?;
+        let image = match &verified_digest {
+            Some(digest) => reference.pinned(digest),
+            None => image.to_string(),
+        };
+        let image = image.as_str();
 
-        match self.docker.inspect_image(image).await {
+        let pulled = match self.docker.inspect_image(image).await {
             Ok(_) => {
                 debug!("Image {} already available locally", image);
-                return Ok(());
+                false
             }
             Err(_) => {
                 info!("Image {} not found locally, pulling...", image);
+                self.pull_image(image).await?;
+                true
+            }
+        };
+
+        let inspected = self.docker.inspect_image(image).await?;
+        let size = inspected.size.unwrap_or(0).max(0) as u64;
+        let checked = match &verified_digest {
+            Some(digest) => self.image_policy.check_repo_digest(
+                image,
+                digest,
+                inspected.repo_digests.as_deref().unwrap_or_default(),
+            ),
+            None => Ok(()),
+        }
+        .and_then(|()| self.image_policy.check_size(image, size));
+
+        if let Err(violation) = checked {
+            if pulled {
+                if let Err(e) = self.docker.remove_image(image, None, None).await {
+                    warn!("Failed to remove rejected image {}: {}", image, e);
+                }
             }
+            return Err(self.reject_image(violation));
         }
 
+        Ok(image.to_string())
+    }
+
+    async fn pull_image(&self, image: &str) -> Result<()> {
         let create_image_options = CreateImageOptions {
             from_image: image,
             ..Default::default()
@@ -673,6 +1143,83 @@ impl ContainerOperations {
     }
 }
 
+/// What startup reconciliation does with the executor's containers
+#[derive(Debug, Default)]
+struct Reconciliation {
+    /// Running containers to track again, with their records
+    adopt: Vec<ContainerRecord>,
+    /// Stopped containers to remove
+    remove: Vec<String>,
+    /// Recorded containers Docker no longer knows about
+    stale: Vec<String>,
+}
+
+impl Reconciliation {
+    /// Compare the containers recorded in `db` with the ones Docker lists
+    async fn plan(db: &ExecutorDb, containers: Vec<ContainerSummary>) -> Result<Self> {
+        let mut records: HashMap<String, ContainerRecord> = db
+            .list_containers()
+            .await?
+            .into_iter()
+            .map(|record| (record.container_id.clone(), record))
+            .collect();
+
+        let mut reconciliation = Self::default();
+        for container in containers {
+            let Some(container_id) = container.id else {
+                continue;
+            };
+            let record = records.remove(&container_id);
+
+            if container.state.as_deref() != Some("running") {
+                reconciliation.remove.push(container_id);
+                continue;
+            }
+
+            // Containers started before the database existed get a new record
+            let record = record.unwrap_or_else(|| ContainerRecord {
+                name: container
+                    .names
+                    .iter()
+                    .flatten()
+                    .next()
+                    .map(|name| name.trim_start_matches('/').to_string())
+                    .unwrap_or_default(),
+                image: container.image.unwrap_or_default(),
+                ssh_public_key: None,
+                owner_hotkey: None,
+                created_at: chrono::Utc::now(),
+                container_id,
+            });
+            reconciliation.adopt.push(record);
+        }
+
+        reconciliation.stale = records.into_keys().collect();
+        reconciliation.stale.sort();
+        Ok(reconciliation)
+    }
+
+    /// Record the adopted containers and forget the stale ones
+    ///
+    /// Stale containers also give back their GPUs. Stopped containers keep
+    /// their records until they are actually removed.
+    async fn record(&self, db: &ExecutorDb, gpus: Option<&GpuAllocator>) -> Result<()> {
+        for record in &self.adopt {
+            db.record_container(record).await?;
+        }
+
+        for container_id in &self.stale {
+            debug!("Dropping record of missing container {}", container_id);
+            db.remove_container(container_id).await?;
+            if let Some(gpus) = gpus {
+                gpus.release(container_id).await;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ContainerLifecycle {
     active_containers: Arc<RwLock<HashMap<String, ContainerStatus>>>,
@@ -746,3 +1293,103 @@ impl ContainerLifecycle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::test_db;
+
+    fn record(container_id: &str) -> ContainerRecord {
+        ContainerRecord {
+            container_id: container_id.to_string(),
+            name: format!("{CONTAINER_NAME_PREFIX}{container_id}"),
+            image: "ubuntu:22.04".to_string(),
+            ssh_public_key: Some("ssh-ed25519 AAAA renter".to_string()),
+            owner_hotkey: Some("5Validator".to_string()),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn listed(container_id: &str, state: &str) -> ContainerSummary {
+        ContainerSummary {
+            id: Some(container_id.to_string()),
+            names: Some(vec![format!("/{CONTAINER_NAME_PREFIX}{container_id}")]),
+            image: Some("pytorch/pytorch:latest".to_string()),
+            state: Some(state.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_against_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+        let running = record("running");
+        db.record_container(&running).await.unwrap();
+        for container_id in ["stopped", "missing"] {
+            db.record_container(&record(container_id)).await.unwrap();
+        }
+
+        let devices = (0..2)
+            .map(|index| GpuDevice {
+                index,
+                uuid: format!("GPU-{index}"),
+            })
+            .collect::<Vec<_>>();
+        let gpus = GpuAllocator::load(devices.clone(), db.clone())
+            .await
+            .unwrap();
+        let one = GpuRequest {
+            count: 1,
+            ..Default::default()
+        };
+        gpus.allocate("running", &one).await.unwrap();
+        gpus.allocate("missing", &one).await.unwrap();
+
+        let containers = vec![
+            listed("running", "running"),
+            listed("stopped", "exited"),
+            listed("unrecorded", "running"),
+        ];
+        let reconciliation = Reconciliation::plan(&db, containers).await.unwrap();
+
+        // Recorded containers keep their record, others get one from Docker
+        assert_eq!(reconciliation.adopt.len(), 2);
+        assert_eq!(reconciliation.adopt[0], running);
+        let unrecorded = &reconciliation.adopt[1];
+        assert_eq!(unrecorded.container_id, "unrecorded");
+        assert_eq!(
+            unrecorded.name,
+            format!("{CONTAINER_NAME_PREFIX}unrecorded")
+        );
+        assert_eq!(unrecorded.image, "pytorch/pytorch:latest");
+        assert_eq!(unrecorded.ssh_public_key, None);
+        assert_eq!(reconciliation.remove, vec!["stopped".to_string()]);
+        assert_eq!(reconciliation.stale, vec!["missing".to_string()]);
+
+        reconciliation.record(&db, Some(&gpus)).await.unwrap();
+
+        let stored: Vec<String> = db
+            .list_containers()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record.container_id)
+            .collect();
+        assert_eq!(stored.len(), 3);
+        assert!(stored.contains(&"running".to_string()));
+        assert!(stored.contains(&"unrecorded".to_string()));
+        // Only forgotten once Docker has removed it
+        assert!(stored.contains(&"stopped".to_string()));
+
+        // The missing container's GPU is free again, after a restart too
+        let reloaded = GpuAllocator::load(devices, db.clone()).await.unwrap();
+        let owners: Vec<String> = reloaded
+            .assignments()
+            .into_iter()
+            .map(|assignment| assignment.container_id)
+            .collect();
+        assert_eq!(owners, vec!["running".to_string()]);
+        assert_eq!(reloaded.free_devices().len(), 1);
+    }
+}
@@ -145,6 +145,39 @@ pub struct ContainerPortMapping {
     pub host_ip: Option<String>,
 }
 
+/// Per-container options supplied by the requester at creation time
+///
+/// Applied on top of the executor's Docker configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerCreateOptions {
+    /// Additional environment variables
+    pub environment: std::collections::HashMap<String, String>,
+    /// Ports to publish, a host port of 0 lets Docker pick a free port
+    pub port_mappings: Vec<ContainerPortMapping>,
+    /// Network mode, one of the executor's `allowed_network_modes`
+    pub network_mode: Option<String>,
+    /// Public key to authorize for SSH access inside the container
+    pub ssh_public_key: Option<String>,
+    /// GPUs to assign, every free GPU when empty
+    pub gpu_request: super::gpu_allocator::GpuRequest,
+    /// Destinations the container may reach, narrowing the executor's egress policy
+    pub egress_rules: Vec<crate::config::EgressRule>,
+    /// Managed volumes to mount
+    pub volumes: Vec<super::volumes::VolumeMount>,
+    /// Hotkey of the validator creating the container
+    pub owner_hotkey: Option<String>,
+}
+
+/// A container that has been created and started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedContainer {
+    pub container_id: String,
+    /// Published ports, keyed by container port and protocol (e.g. "22/tcp")
+    pub ports: std::collections::HashMap<String, u16>,
+    /// User the SSH key was authorized for, if it could be installed
+    pub ssh_user: Option<String>,
+}
+
 /// Container volume configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerVolumeConfig {
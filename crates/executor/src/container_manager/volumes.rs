@@ -0,0 +1,689 @@
+//! Named volumes owned by validators
+//!
+//! Volumes are Docker volumes that outlive the containers they are mounted
+//! in, so a rental can keep checkpoints or datasets across container
+//! restarts. Each volume belongs to the validator that created it and is
+//! referred to by a name unique to that validator. Quotas are checked
+//! against measured usage, and volumes whose owner lost access are removed
+//! once a grace period has passed.
+
+use crate::config::VolumeConfig;
+use crate::persistence::{ExecutorDb, VolumeRecord};
+use anyhow::{anyhow, Context, Result};
+use bollard::{
+    container::{
+        Config, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions,
+        WaitContainerOptions,
+    },
+    image::CreateImageOptions,
+    models::{HostConfig, Mount, MountTypeEnum},
+    volume::{CreateVolumeOptions, RemoveVolumeOptions},
+    Docker,
+};
+use chrono::Utc;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, info, warn};
+
+/// Label naming the validator that owns a volume
+pub const OWNER_LABEL: &str = "basilica.volume.owner";
+
+/// Label holding the name the owner refers to a volume by
+pub const NAME_LABEL: &str = "basilica.volume.name";
+
+/// Prefix of the Docker names of managed volumes
+const VOLUME_ID_PREFIX: &str = "basilica-vol-";
+
+/// Directories volumes may not be mounted on or below
+const RESERVED_PATHS: &[&str] = &["/proc", "/sys", "/dev", "/etc"];
+
+/// Mount points already taken by the executor's tmpfs mounts
+const TMPFS_PATHS: &[&str] = &["/tmp", "/workspace"];
+
+/// Suffix of a mount target that mounts the volume read-only
+const READ_ONLY_SUFFIX: &str = ":ro";
+
+/// A managed volume mounted into a new container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeMount {
+    /// Docker volume name
+    pub volume_id: String,
+    pub container_path: String,
+    pub read_only: bool,
+}
+
+/// Owner of a volume
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeOwner {
+    pub validator_hotkey: String,
+    /// Rental the volume is created for, if any
+    pub rental_id: Option<String>,
+}
+
+/// Volume whose measured usage exceeds its quota
+#[derive(Debug, Clone)]
+pub struct QuotaViolation {
+    pub volume: VolumeRecord,
+    /// Running containers the volume is mounted in
+    pub container_ids: Vec<String>,
+}
+
+/// Creates, snapshots and removes the volumes validators own
+#[derive(Debug, Clone)]
+pub struct VolumeManager {
+    docker: Docker,
+    config: VolumeConfig,
+    db: ExecutorDb,
+}
+
+impl VolumeManager {
+    pub fn new(docker: Docker, config: VolumeConfig, db: ExecutorDb) -> Self {
+        Self { docker, config, db }
+    }
+
+    /// Create an empty volume
+    ///
+    /// `quota_bytes` defaults to the configured quota and may not exceed
+    /// the per-volume or per-owner limits.
+    pub async fn create(
+        &self,
+        owner: &VolumeOwner,
+        name: &str,
+        quota_bytes: Option<u64>,
+    ) -> Result<VolumeRecord> {
+        let quota = self.check_quota(owner, name, quota_bytes).await?;
+        self.create_volume(owner, name, quota, None).await
+    }
+
+    /// Volumes of a validator, optionally only those of one rental
+    pub async fn list(
+        &self,
+        owner_hotkey: &str,
+        rental_id: Option<&str>,
+    ) -> Result<Vec<VolumeRecord>> {
+        let volumes = self.db.list_volumes(Some(owner_hotkey)).await?;
+        Ok(volumes
+            .into_iter()
+            .filter(|volume| rental_id.is_none() || volume.rental_id.as_deref() == rental_id)
+            .collect())
+    }
+
+    /// Copy a volume into a new volume with the same quota
+    ///
+    /// The copy is taken while containers may still write to the source, so
+    /// it is only as consistent as the files were at the time.
+    pub async fn snapshot(
+        &self,
+        owner: &VolumeOwner,
+        name: &str,
+        snapshot_name: &str,
+    ) -> Result<VolumeRecord> {
+        let source = self.get(&owner.validator_hotkey, name).await?;
+        if source.usage_bytes > source.quota_bytes {
+            return Err(anyhow!("Volume {} exceeds its quota", name));
+        }
+
+        let quota = self
+            .check_quota(owner, snapshot_name, Some(source.quota_bytes as u64))
+            .await?;
+        let snapshot = self
+            .create_volume(owner, snapshot_name, quota, Some(&source.volume_id))
+            .await?;
+
+        if let Err(e) = self
+            .copy_volume(&source.volume_id, &snapshot.volume_id)
+            .await
+        {
+            warn!(
+                "Failed to snapshot volume {} into {}: {}",
+                source.volume_id, snapshot.volume_id, e
+            );
+            if let Err(e) = self.remove_volume(&snapshot.volume_id).await {
+                warn!("Failed to remove volume {}: {}", snapshot.volume_id, e);
+            }
+            return Err(e);
+        }
+
+        self.db
+            .update_volume_usage(&snapshot.volume_id, source.usage_bytes)
+            .await?;
+        info!(
+            "Snapshotted volume {} into {} for {}",
+            source.volume_id, snapshot.volume_id, owner.validator_hotkey
+        );
+        Ok(VolumeRecord {
+            usage_bytes: source.usage_bytes,
+            ..snapshot
+        })
+    }
+
+    /// Delete a volume that is not mounted in any container
+    pub async fn delete(&self, owner_hotkey: &str, name: &str) -> Result<()> {
+        let volume = self.get(owner_hotkey, name).await?;
+
+        let containers = self.containers_using(&volume.volume_id, true).await?;
+        if !containers.is_empty() {
+            return Err(anyhow!(
+                "Volume {} is mounted in container(s) {}",
+                name,
+                containers.join(", ")
+            ));
+        }
+
+        self.remove_volume(&volume.volume_id).await?;
+        info!("Deleted volume {} of {}", volume.volume_id, owner_hotkey);
+        Ok(())
+    }
+
+    /// Resolve requested mounts of an owner's volumes for a new container
+    ///
+    /// `requested` maps volume names to container paths; a path ending in
+    /// `:ro` mounts the volume read-only. Volumes over their quota can only
+    /// be mounted read-only.
+    pub async fn resolve_mounts(
+        &self,
+        owner_hotkey: &str,
+        requested: &HashMap<String, String>,
+    ) -> Result<Vec<VolumeMount>> {
+        let mut targets = HashSet::new();
+        let mut mounts = Vec::with_capacity(requested.len());
+
+        for (name, target) in requested {
+            let (container_path, read_only) = parse_mount_target(target)?;
+            if !targets.insert(container_path.clone()) {
+                return Err(anyhow!("Mount point {} is used twice", container_path));
+            }
+
+            let volume = self.get(owner_hotkey, name).await?;
+            if !read_only && volume.usage_bytes > volume.quota_bytes {
+                return Err(anyhow!(
+                    "Volume {} exceeds its quota and can only be mounted read-only",
+                    name
+                ));
+            }
+            self.db.touch_volume(&volume.volume_id).await?;
+
+            mounts.push(VolumeMount {
+                volume_id: volume.volume_id,
+                container_path,
+                read_only,
+            });
+        }
+
+        mounts.sort_by(|a, b| a.container_path.cmp(&b.container_path));
+        Ok(mounts)
+    }
+
+    /// Drop records of volumes removed outside the executor
+    pub async fn reconcile(&self) -> Result<()> {
+        for volume in self.db.list_volumes(None).await? {
+            match self.docker.inspect_volume(&volume.volume_id).await {
+                Ok(_) => {}
+                Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => {
+                    info!("Volume {} is gone, dropping its record", volume.volume_id);
+                    self.db.remove_volume(&volume.volume_id).await?;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Measure volume usage and report the volumes over their quota
+    ///
+    /// Volumes that are mounted somewhere count as active, postponing their
+    /// garbage collection.
+    pub async fn check_usage(&self) -> Result<Vec<QuotaViolation>> {
+        let usage = self.measure_usage().await?;
+        let mut violations = Vec::new();
+
+        for mut volume in self.db.list_volumes(None).await? {
+            let Some((size, ref_count)) = usage.get(&volume.volume_id).copied() else {
+                continue;
+            };
+
+            if size >= 0 && size != volume.usage_bytes {
+                self.db.update_volume_usage(&volume.volume_id, size).await?;
+                volume.usage_bytes = size;
+            }
+            if ref_count > 0 {
+                self.db.touch_volume(&volume.volume_id).await?;
+            }
+
+            if volume.usage_bytes > volume.quota_bytes {
+                let container_ids = self.containers_using(&volume.volume_id, false).await?;
+                violations.push(QuotaViolation {
+                    volume,
+                    container_ids,
+                });
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Remove volumes whose owner has had no active access for the grace period
+    ///
+    /// Volumes of owners in `active_owners` are marked active instead, and
+    /// volumes still mounted in a container are kept. Returns the number of
+    /// volumes removed.
+    pub async fn collect_garbage(&self, active_owners: &HashSet<String>) -> Result<u32> {
+        let grace_secs = self
+            .config
+            .orphan_grace_period_secs
+            .min(i64::MAX as u64 / 1000);
+        let grace = chrono::Duration::seconds(grace_secs as i64);
+        let now = Utc::now();
+        let mut removed = 0;
+
+        for volume in self.db.list_volumes(None).await? {
+            if active_owners.contains(&volume.owner_hotkey) {
+                self.db.touch_volume(&volume.volume_id).await?;
+                continue;
+            }
+            if now.signed_duration_since(volume.last_active_at) < grace {
+                continue;
+            }
+            if !self
+                .containers_using(&volume.volume_id, true)
+                .await?
+                .is_empty()
+            {
+                debug!(
+                    "Keeping orphaned volume {} while it is mounted",
+                    volume.volume_id
+                );
+                continue;
+            }
+
+            match self.remove_volume(&volume.volume_id).await {
+                Ok(()) => {
+                    info!(
+                        "Removed volume {} of {} after its access expired",
+                        volume.volume_id, volume.owner_hotkey
+                    );
+                    removed += 1;
+                }
+                Err(e) => warn!("Failed to remove volume {}: {}", volume.volume_id, e),
+            }
+        }
+
+        Ok(removed)
+    }
+
+    async fn get(&self, owner_hotkey: &str, name: &str) -> Result<VolumeRecord> {
+        self.db
+            .get_volume(owner_hotkey, name)
+            .await?
+            .ok_or_else(|| anyhow!("Volume {} not found", name))
+    }
+
+    /// Check a new volume against the owner's limits and return its quota
+    async fn check_quota(
+        &self,
+        owner: &VolumeOwner,
+        name: &str,
+        quota_bytes: Option<u64>,
+    ) -> Result<u64> {
+        validate_volume_name(name)?;
+        let existing = self.db.list_volumes(Some(&owner.validator_hotkey)).await?;
+        check_owner_limits(&self.config, &existing, name, quota_bytes)
+    }
+
+    async fn create_volume(
+        &self,
+        owner: &VolumeOwner,
+        name: &str,
+        quota_bytes: u64,
+        source_volume: Option<&str>,
+    ) -> Result<VolumeRecord> {
+        let uuid = uuid::Uuid::new_v4().simple().to_string();
+        let volume_id = format!("{VOLUME_ID_PREFIX}{}", &uuid[..12]);
+
+        let mut driver_opts = HashMap::new();
+        if let Some(option) = &self.config.size_option {
+            driver_opts.insert(option.clone(), quota_bytes.to_string());
+        }
+        let options = CreateVolumeOptions {
+            name: volume_id.clone(),
+            driver: self.config.driver.clone(),
+            driver_opts,
+            labels: HashMap::from([
+                (OWNER_LABEL.to_string(), owner.validator_hotkey.clone()),
+                (NAME_LABEL.to_string(), name.to_string()),
+            ]),
+        };
+        self.docker
+            .create_volume(options)
+            .await
+            .with_context(|| format!("Failed to create volume {name}"))?;
+
+        let now = Utc::now();
+        let record = VolumeRecord {
+            volume_id: volume_id.clone(),
+            name: name.to_string(),
+            owner_hotkey: owner.validator_hotkey.clone(),
+            rental_id: owner.rental_id.clone(),
+            quota_bytes: i64::try_from(quota_bytes).unwrap_or(i64::MAX),
+            usage_bytes: 0,
+            source_volume: source_volume.map(String::from),
+            created_at: now,
+            last_active_at: now,
+        };
+        if let Err(e) = self.db.record_volume(&record).await {
+            if let Err(e) = self.docker.remove_volume(&volume_id, None).await {
+                warn!("Failed to remove volume {}: {}", volume_id, e);
+            }
+            return Err(e.context(format!("Failed to record volume {name}")));
+        }
+
+        info!(
+            "Created volume {} ({}) for {} with a quota of {} bytes",
+            volume_id, name, owner.validator_hotkey, quota_bytes
+        );
+        Ok(record)
+    }
+
+    /// Remove a Docker volume and its record
+    async fn remove_volume(&self, volume_id: &str) -> Result<()> {
+        match self
+            .docker
+            .remove_volume(volume_id, Some(RemoveVolumeOptions { force: false }))
+            .await
+        {
+            Ok(())
+            | Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {}
+            Err(e) => return Err(e.into()),
+        }
+        self.db.remove_volume(volume_id).await
+    }
+
+    /// Containers that mount a volume, including stopped ones when `all` is set
+    async fn containers_using(&self, volume_id: &str, all: bool) -> Result<Vec<String>> {
+        let options = ListContainersOptions {
+            all,
+            filters: HashMap::from([("volume", vec![volume_id])]),
+            ..Default::default()
+        };
+        let containers = self.docker.list_containers(Some(options)).await?;
+        Ok(containers.into_iter().filter_map(|c| c.id).collect())
+    }
+
+    /// Size and reference count of every volume, -1 where Docker cannot tell
+    async fn measure_usage(&self) -> Result<HashMap<String, (i64, i64)>> {
+        let usage = self.docker.df().await?;
+        Ok(usage
+            .volumes
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|volume| volume.name.starts_with(VOLUME_ID_PREFIX))
+            .map(|volume| {
+                let data = volume.usage_data.map(|d| (d.size, d.ref_count));
+                (volume.name, data.unwrap_or((-1, -1)))
+            })
+            .collect())
+    }
+
+    /// Copy the contents of one volume into another with a helper container
+    async fn copy_volume(&self, source: &str, target: &str) -> Result<()> {
+        self.ensure_helper_image().await?;
+
+        let mount = |volume: &str, path: &str, read_only: bool| Mount {
+            target: Some(path.to_string()),
+            source: Some(volume.to_string()),
+            typ: Some(MountTypeEnum::VOLUME),
+            read_only: Some(read_only),
+            ..Default::default()
+        };
+        let config = Config {
+            image: Some(self.config.helper_image.clone()),
+            cmd: Some(vec![
+                "cp".to_string(),
+                "-a".to_string(),
+                "/source/.".to_string(),
+                "/target/".to_string(),
+            ]),
+            network_disabled: Some(true),
+            host_config: Some(HostConfig {
+                mounts: Some(vec![
+                    mount(source, "/source", true),
+                    mount(target, "/target", false),
+                ]),
+                network_mode: Some("none".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let uuid = uuid::Uuid::new_v4().to_string();
+        let options = CreateContainerOptions {
+            name: format!("basilica-snapshot-{}", &uuid[..8]),
+            platform: None,
+        };
+        let container_id = self
+            .docker
+            .create_container(Some(options), config)
+            .await?
+            .id;
+
+        let result = self.run_to_completion(&container_id).await;
+
+        let remove_options = RemoveContainerOptions {
+            force: true,
+            ..Default::default()
+        };
+        if let Err(e) = self
+            .docker
+            .remove_container(&container_id, Some(remove_options))
+            .await
+        {
+            warn!(
+                "Failed to remove snapshot container {}: {}",
+                container_id, e
+            );
+        }
+
+        result
+    }
+
+    async fn run_to_completion(&self, container_id: &str) -> Result<()> {
+        self.docker
+            .start_container::<String>(container_id, None)
+            .await?;
+
+        let wait_options = WaitContainerOptions {
+            condition: "not-running",
+        };
+        let mut wait_stream = self.docker.wait_container(container_id, Some(wait_options));
+        match wait_stream.next().await {
+            Some(Ok(result)) if result.status_code == 0 => Ok(()),
+            Some(Ok(result)) => Err(anyhow!(
+                "Volume copy exited with status {}",
+                result.status_code
+            )),
+            Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => {
+                Err(anyhow!("Volume copy exited with status {}", code))
+            }
+            Some(Err(e)) => Err(e.into()),
+            None => Err(anyhow!("Container wait stream ended unexpectedly")),
+        }
+    }
+
+    async fn ensure_helper_image(&self) -> Result<()> {
+        let image = &self.config.helper_image;
+        if self.docker.inspect_image(image).await.is_ok() {
+            return Ok(());
+        }
+
+        info!("Pulling volume helper image: {}", image);
+        let options = CreateImageOptions {
+            from_image: image.as_str(),
+            ..Default::default()
+        };
+        let mut pull_stream = self.docker.create_image(Some(options), None, None);
+        while let Some(result) = pull_stream.next().await {
+            result.with_context(|| format!("Failed to pull image {image}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check that a volume name is short and safe to show and log
+pub fn validate_volume_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(anyhow!(
+            "Invalid volume name {:?}: use up to 64 letters, digits, '-', '_' or '.'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Split a requested mount target into its path and whether it is read-only
+fn parse_mount_target(target: &str) -> Result<(String, bool)> {
+    let target = target.trim();
+    let (path, read_only) = match target.strip_suffix(READ_ONLY_SUFFIX) {
+        Some(path) => (path, true),
+        None => (target, false),
+    };
+    let path = path.trim_end_matches('/');
+
+    if !path.starts_with('/') || path.split('/').any(|part| part == "..") {
+        return Err(anyhow!("Mount point must be an absolute path: {}", target));
+    }
+    let reserved = RESERVED_PATHS
+        .iter()
+        .any(|reserved| path == *reserved || path.starts_with(&format!("{reserved}/")));
+    if path.is_empty() || reserved || TMPFS_PATHS.contains(&path) {
+        return Err(anyhow!("Mount point {} is reserved", target));
+    }
+
+    Ok((path.to_string(), read_only))
+}
+
+/// Quota for a new volume given the owner's existing volumes
+fn check_owner_limits(
+    config: &VolumeConfig,
+    existing: &[VolumeRecord],
+    name: &str,
+    quota_bytes: Option<u64>,
+) -> Result<u64> {
+    if existing.iter().any(|volume| volume.name == name) {
+        return Err(anyhow!("Volume {} already exists", name));
+    }
+    if existing.len() >= config.max_volumes_per_owner as usize {
+        return Err(anyhow!(
+            "Volume limit of {} reached",
+            config.max_volumes_per_owner
+        ));
+    }
+
+    let quota = quota_bytes.unwrap_or(config.default_quota_bytes);
+    if quota == 0 || quota > config.max_quota_bytes {
+        return Err(anyhow!(
+            "Volume quota must be between 1 and {} bytes",
+            config.max_quota_bytes
+        ));
+    }
+
+    let allocated: u64 = existing
+        .iter()
+        .map(|volume| volume.quota_bytes.max(0) as u64)
+        .sum();
+    if allocated.saturating_add(quota) > config.max_total_bytes_per_owner {
+        return Err(anyhow!(
+            "Volume quota of {} bytes exceeds the remaining {} bytes",
+            quota,
+            config.max_total_bytes_per_owner.saturating_sub(allocated)
+        ));
+    }
+
+    Ok(quota)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume(name: &str, quota_bytes: i64) -> VolumeRecord {
+        VolumeRecord {
+            volume_id: format!("{VOLUME_ID_PREFIX}{name}"),
+            name: name.to_string(),
+            owner_hotkey: "5Validator".to_string(),
+            rental_id: None,
+            quota_bytes,
+            usage_bytes: 0,
+            source_volume: None,
+            created_at: Utc::now(),
+            last_active_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validate_volume_name() {
+        for name in ["checkpoints", "model.v2", "data_01-a"] {
+            assert!(validate_volume_name(name).is_ok(), "{name}");
+        }
+        for name in ["", "-leading", "../escape", "with space", &"x".repeat(65)] {
+            assert!(validate_volume_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_parse_mount_target() {
+        assert_eq!(
+            parse_mount_target("/data").unwrap(),
+            ("/data".to_string(), false)
+        );
+        assert_eq!(
+            parse_mount_target("/workspace/checkpoints/:ro").unwrap(),
+            ("/workspace/checkpoints".to_string(), true)
+        );
+
+        for target in [
+            "data",
+            "/",
+            "/proc/self",
+            "/etc",
+            "/workspace",
+            "/data/../etc",
+        ] {
+            assert!(parse_mount_target(target).is_err(), "{target}");
+        }
+    }
+
+    #[test]
+    fn test_check_owner_limits() {
+        let config = VolumeConfig {
+            default_quota_bytes: 10,
+            max_quota_bytes: 50,
+            max_volumes_per_owner: 3,
+            max_total_bytes_per_owner: 100,
+            ..Default::default()
+        };
+        let existing = vec![volume("a", 50), volume("b", 40)];
+
+        assert_eq!(
+            check_owner_limits(&config, &existing, "c", None).unwrap(),
+            10
+        );
+        assert!(check_owner_limits(&config, &existing, "a", None).is_err());
+        assert!(check_owner_limits(&config, &existing, "c", Some(11)).is_err());
+        assert!(check_owner_limits(&config, &[], "c", Some(51)).is_err());
+        assert!(check_owner_limits(&config, &[], "c", Some(0)).is_err());
+
+        let full = vec![volume("a", 1), volume("b", 1), volume("c", 1)];
+        assert!(check_owner_limits(&config, &full, "d", Some(1)).is_err());
+    }
+}
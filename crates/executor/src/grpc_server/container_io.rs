@@ -0,0 +1,578 @@
+//! Interactive exec and file transfer service
+
+use super::types::{GrpcResult, SharedExecutorState};
+use crate::container_manager::exec::{AttachedExec, ExecOptions};
+use crate::container_manager::file_transfer::{split_container_path, FileUpload};
+use crate::validation_session::{ValidatorAccessControl, ValidatorId};
+use anyhow::anyhow;
+use bollard::container::LogOutput;
+use common::crypto::request_signing::canonical_request;
+use futures_util::StreamExt;
+use protocol::executor_control::{
+    exec_request, exec_response, file_chunk, ExecExit, ExecRequest, ExecResponse, FileChunk,
+    FileDownloadRequest, FileTransferHeader, FileTransferResponse, FileTransferTrailer,
+    RequestSignature,
+};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, info, warn};
+
+pub type ExecResponseStream = ReceiverStream<Result<ExecResponse, tonic::Status>>;
+pub type FileChunkStream = ReceiverStream<Result<FileChunk, tonic::Status>>;
+
+/// Operations a container I/O signature is bound to
+pub const EXEC_OPERATION: &str = "EXEC";
+pub const UPLOAD_OPERATION: &str = "UPLOAD";
+pub const DOWNLOAD_OPERATION: &str = "DOWNLOAD";
+
+/// Canonical request a validator signs for container I/O
+///
+/// `message` is the request with its `signature` field left empty.
+pub fn container_io_canonical(
+    operation: &str,
+    container_id: &str,
+    message: &impl prost::Message,
+    timestamp: i64,
+    nonce: &str,
+) -> String {
+    canonical_request(
+        operation,
+        &format!("/containers/{container_id}"),
+        timestamp,
+        nonce,
+        &message.encode_to_vec(),
+    )
+}
+
+/// Streams commands and files between clients and containers
+pub struct ContainerIoService {
+    state: SharedExecutorState,
+}
+
+impl ContainerIoService {
+    /// Create new container I/O service
+    pub fn new(state: SharedExecutorState) -> Self {
+        Self { state }
+    }
+
+    /// Run a command and relay its stdin, output and terminal size
+    ///
+    /// The first request must start the command. Output is streamed back as it
+    /// is produced and the stream always ends with the command's exit code.
+    pub async fn exec(
+        &self,
+        mut requests: tonic::Streaming<ExecRequest>,
+    ) -> GrpcResult<ExecResponseStream> {
+        let mut start = match requests.message().await? {
+            Some(ExecRequest {
+                payload: Some(exec_request::Payload::Start(start)),
+            }) => start,
+            _ => return Err(anyhow!("The first exec request must start a command")),
+        };
+        let signature = start.signature.take();
+        self.authorize(
+            EXEC_OPERATION,
+            &start.validator_hotkey,
+            &start.container_id,
+            &start,
+            signature.as_ref(),
+        )
+        .await?;
+        info!(
+            "Interactive exec in container {} requested by {}",
+            start.container_id, start.validator_hotkey
+        );
+
+        let manager = self.state.container_manager.clone();
+        let options = ExecOptions {
+            command: start.command,
+            tty: start.tty,
+            environment: start.environment,
+            working_dir: Some(start.working_dir).filter(|dir| !dir.is_empty()),
+        };
+        let AttachedExec {
+            exec_id,
+            mut output,
+            input,
+        } = manager.start_exec(&start.container_id, &options).await?;
+
+        if let Some(size) = start.terminal_size.filter(|_| start.tty) {
+            if let Err(e) = manager
+                .resize_exec(
+                    &exec_id,
+                    clamp_dimension(size.rows),
+                    clamp_dimension(size.cols),
+                )
+                .await
+            {
+                warn!("Failed to size terminal of command {}: {}", exec_id, e);
+            }
+        }
+
+        let input_manager = manager.clone();
+        let input_exec_id = exec_id.clone();
+        let input_task = tokio::spawn(async move {
+            let mut input = Some(input);
+            loop {
+                let request = match requests.message().await {
+                    Ok(Some(request)) => request,
+                    Ok(None) => break,
+                    Err(e) => {
+                        debug!("Exec request stream for {} failed: {}", input_exec_id, e);
+                        break;
+                    }
+                };
+
+                match request.payload {
+                    Some(exec_request::Payload::Stdin(data)) => {
+                        let Some(stdin) = input.as_mut() else {
+                            continue;
+                        };
+                        if stdin.write_all(&data).await.is_err() || stdin.flush().await.is_err() {
+                            input = None;
+                        }
+                    }
+                    Some(exec_request::Payload::Resize(size)) => {
+                        if let Err(e) = input_manager
+                            .resize_exec(
+                                &input_exec_id,
+                                clamp_dimension(size.rows),
+                                clamp_dimension(size.cols),
+                            )
+                            .await
+                        {
+                            debug!("Failed to resize terminal of {}: {}", input_exec_id, e);
+                        }
+                    }
+                    Some(exec_request::Payload::CloseStdin(true)) => {
+                        if let Some(mut stdin) = input.take() {
+                            let _ = stdin.shutdown().await;
+                        }
+                    }
+                    Some(exec_request::Payload::Start(_)) => {
+                        debug!("Ignoring repeated start for command {}", input_exec_id);
+                    }
+                    Some(exec_request::Payload::CloseStdin(false)) | None => {}
+                }
+            }
+
+            // The client finished sending, so the command sees end of input
+            if let Some(mut stdin) = input.take() {
+                let _ = stdin.shutdown().await;
+            }
+        });
+
+        let (tx, rx) = mpsc::channel(64);
+        tokio::spawn(async move {
+            while let Some(item) = output.next().await {
+                let payload = match item {
+                    Ok(LogOutput::StdErr { message }) => {
+                        exec_response::Payload::Stderr(message.to_vec())
+                    }
+                    Ok(LogOutput::StdOut { message }) | Ok(LogOutput::Console { message }) => {
+                        exec_response::Payload::Stdout(message.to_vec())
+                    }
+                    Ok(LogOutput::StdIn { .. }) => continue,
+                    Err(e) => {
+                        warn!("Output of command {} failed: {}", exec_id, e);
+                        break;
+                    }
+                };
+
+                if tx
+                    .send(Ok(ExecResponse {
+                        payload: Some(payload),
+                    }))
+                    .await
+                    .is_err()
+                {
+                    // Client disconnected
+                    input_task.abort();
+                    return;
+                }
+            }
+            input_task.abort();
+
+            let exit_code = match manager.exec_exit_code(&exec_id).await {
+                Ok(Some(code)) => i32::try_from(code).unwrap_or(-1),
+                Ok(None) => -1,
+                Err(e) => {
+                    warn!("Failed to read exit code of command {}: {}", exec_id, e);
+                    -1
+                }
+            };
+            let _ = tx
+                .send(Ok(ExecResponse {
+                    payload: Some(exec_response::Payload::Exit(ExecExit { exit_code })),
+                }))
+                .await;
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+
+    /// Receive a file and write it into a container
+    ///
+    /// Nothing is written unless the received size and checksum match the
+    /// header and the optional trailer.
+    pub async fn upload_file(
+        &self,
+        mut chunks: tonic::Streaming<FileChunk>,
+    ) -> GrpcResult<FileTransferResponse> {
+        let mut header = match chunks.message().await? {
+            Some(FileChunk {
+                payload: Some(file_chunk::Payload::Header(header)),
+            }) => header,
+            _ => return Err(anyhow!("The first chunk must be the file header")),
+        };
+        let signature = header.signature.take();
+        self.authorize(
+            UPLOAD_OPERATION,
+            &header.validator_hotkey,
+            &header.container_id,
+            &header,
+            signature.as_ref(),
+        )
+        .await?;
+        split_container_path(&header.path)?;
+        info!(
+            "Upload of {} ({} bytes) into container {} requested by {}",
+            header.path, header.size, header.container_id, header.validator_hotkey
+        );
+
+        let manager = &self.state.container_manager;
+        let mut upload =
+            FileUpload::new(header.size, manager.file_transfer_config().max_file_bytes)?;
+        while let Some(chunk) = chunks.message().await? {
+            match chunk.payload {
+                Some(file_chunk::Payload::Data(data)) => upload.push(&data)?,
+                Some(file_chunk::Payload::Trailer(trailer)) => {
+                    upload.verify_trailer(&trailer.sha256, trailer.size)?
+                }
+                Some(file_chunk::Payload::Header(_)) => {
+                    return Err(anyhow!("Duplicate file header"));
+                }
+                None => {}
+            }
+        }
+        let (data, sha256) = upload.finish(&header.sha256)?;
+
+        manager
+            .upload_file(&header.container_id, &header.path, header.mode, &data)
+            .await?;
+
+        Ok(FileTransferResponse {
+            bytes_written: data.len() as u64,
+            sha256,
+        })
+    }
+
+    /// Stream a file out of a container
+    ///
+    /// Sends a header with the file's size and mode, the contents and a
+    /// trailer carrying their SHA-256.
+    pub async fn download_file(
+        &self,
+        mut request: FileDownloadRequest,
+    ) -> GrpcResult<FileChunkStream> {
+        let signature = request.signature.take();
+        self.authorize(
+            DOWNLOAD_OPERATION,
+            &request.validator_hotkey,
+            &request.container_id,
+            &request,
+            signature.as_ref(),
+        )
+        .await?;
+        info!(
+            "Download of {} from container {} requested by {}",
+            request.path, request.container_id, request.validator_hotkey
+        );
+
+        let manager = &self.state.container_manager;
+        let mut download = manager
+            .download_file(&request.container_id, &request.path)
+            .await?;
+        let chunk_size = manager.file_transfer_config().chunk_size_bytes;
+
+        let header = FileTransferHeader {
+            container_id: request.container_id,
+            path: request.path,
+            size: download.size,
+            mode: download.mode,
+            sha256: String::new(),
+            validator_hotkey: String::new(),
+            signature: None,
+        };
+
+        let (tx, rx) = mpsc::channel(8);
+        tokio::spawn(async move {
+            let header = file_chunk::Payload::Header(header);
+            if tx
+                .send(Ok(FileChunk {
+                    payload: Some(header),
+                }))
+                .await
+                .is_err()
+            {
+                return;
+            }
+
+            loop {
+                let payload = match download.next_chunk(chunk_size).await {
+                    Ok(Some(data)) => file_chunk::Payload::Data(data),
+                    Ok(None) => break,
+                    Err(e) => {
+                        let _ = tx
+                            .send(Err(tonic::Status::internal(format!(
+                                "Failed to read file: {e}"
+                            ))))
+                            .await;
+                        return;
+                    }
+                };
+                if tx
+                    .send(Ok(FileChunk {
+                        payload: Some(payload),
+                    }))
+                    .await
+                    .is_err()
+                {
+                    return; // Client disconnected
+                }
+            }
+
+            let trailer = file_chunk::Payload::Trailer(FileTransferTrailer {
+                sha256: download.sha256(),
+                size: download.size,
+            });
+            let _ = tx
+                .send(Ok(FileChunk {
+                    payload: Some(trailer),
+                }))
+                .await;
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+
+    /// Only the validator a container of this executor was created for can
+    /// reach into it, while holding an active access grant and signing
+    /// `unsigned`, the request without its signature
+    async fn authorize(
+        &self,
+        operation: &str,
+        validator_hotkey: &str,
+        container_id: &str,
+        unsigned: &impl prost::Message,
+        signature: Option<&RequestSignature>,
+    ) -> GrpcResult<()> {
+        let Some(validation_service) = &self.state.validation_service else {
+            return Err(anyhow!("Validation service not enabled"));
+        };
+        if container_id.is_empty() {
+            return Err(anyhow!("Container ID is required"));
+        }
+        let manager = &self.state.container_manager;
+        if !manager.is_managed(container_id).await {
+            return Err(anyhow!(
+                "Container {} is not managed by this executor",
+                container_id
+            ));
+        }
+        let owner = manager.container_owner(container_id).await?;
+
+        check_container_access(
+            validation_service.access_control(),
+            owner.as_deref(),
+            operation,
+            validator_hotkey,
+            container_id,
+            unsigned,
+            signature,
+        )
+        .await
+    }
+}
+
+/// Check that `validator_hotkey` owns the container, has an active access
+/// grant and signed the request
+async fn check_container_access(
+    access_control: &ValidatorAccessControl,
+    owner: Option<&str>,
+    operation: &str,
+    validator_hotkey: &str,
+    container_id: &str,
+    unsigned: &impl prost::Message,
+    signature: Option<&RequestSignature>,
+) -> GrpcResult<()> {
+    let hotkey = validator_hotkey.trim();
+    if hotkey.is_empty() {
+        return Err(anyhow!("Validator hotkey is required"));
+    }
+    let validator_id = ValidatorId::new(hotkey.to_string());
+    if !access_control.has_access(&validator_id).await {
+        return Err(anyhow!("Validator {} has no active access grant", hotkey));
+    }
+
+    // Containers without a recorded owner are out of reach of every validator
+    if owner != Some(hotkey) {
+        warn!(
+            "Validator {} refused access to container {} it does not own",
+            hotkey, container_id
+        );
+        return Err(anyhow!(
+            "Container {} does not belong to validator {}",
+            container_id,
+            hotkey
+        ));
+    }
+
+    let Some(signature) = signature else {
+        return Err(anyhow!("Request signature is required"));
+    };
+    let canonical = container_io_canonical(
+        operation,
+        container_id,
+        unsigned,
+        signature.timestamp,
+        &signature.nonce,
+    );
+    if !access_control
+        .verify_request_signature(
+            &validator_id,
+            &canonical,
+            &signature.signature,
+            signature.timestamp,
+            &signature.nonce,
+        )
+        .await
+    {
+        return Err(anyhow!("Invalid request signature from {}", hotkey));
+    }
+    Ok(())
+}
+
+/// Terminal dimensions arrive as u32 but Docker takes u16
+fn clamp_dimension(value: u32) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation_session::{AccessControlConfig, ValidatorAccess};
+    use common::crypto::request_signing::generate_request_nonce;
+    use sp_core::crypto::Ss58Codec;
+    use sp_core::{sr25519, Pair};
+
+    /// Validator holding an access grant, signing with its hotkey
+    struct TestValidator {
+        pair: sr25519::Pair,
+        hotkey: String,
+    }
+
+    impl TestValidator {
+        async fn granted(access_control: &ValidatorAccessControl, seed: &str) -> Self {
+            let pair = sr25519::Pair::from_string(seed, None).unwrap();
+            let hotkey = pair.public().to_ss58check();
+            access_control
+                .grant_access(&ValidatorAccess::new(
+                    ValidatorId::new(hotkey.clone()),
+                    "ssh-ed25519 AAAA validator",
+                ))
+                .await
+                .unwrap();
+            Self { pair, hotkey }
+        }
+
+        /// Signed request downloading a file of `container_id`
+        fn download(&self, container_id: &str) -> (FileDownloadRequest, RequestSignature) {
+            let request = FileDownloadRequest {
+                container_id: container_id.to_string(),
+                path: "/root/results.txt".to_string(),
+                validator_hotkey: self.hotkey.clone(),
+                signature: None,
+            };
+            let timestamp = chrono::Utc::now().timestamp();
+            let nonce = generate_request_nonce();
+            let canonical = container_io_canonical(
+                DOWNLOAD_OPERATION,
+                container_id,
+                &request,
+                timestamp,
+                &nonce,
+            );
+            let signature = RequestSignature {
+                signature: hex::encode(self.pair.sign(canonical.as_bytes()).0),
+                timestamp,
+                nonce,
+            };
+            (request, signature)
+        }
+    }
+
+    async fn check(
+        access_control: &ValidatorAccessControl,
+        owner: Option<&str>,
+        request: &FileDownloadRequest,
+        signature: Option<&RequestSignature>,
+    ) -> GrpcResult<()> {
+        check_container_access(
+            access_control,
+            owner,
+            DOWNLOAD_OPERATION,
+            &request.validator_hotkey,
+            &request.container_id,
+            request,
+            signature,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_only_the_owner_reaches_its_container() {
+        let access_control = ValidatorAccessControl::new(AccessControlConfig::default());
+        let a = TestValidator::granted(&access_control, "//Alice").await;
+        let b = TestValidator::granted(&access_control, "//Bob").await;
+        let owner = Some(a.hotkey.as_str());
+
+        // B holds a grant and signs correctly, but the container is A's
+        let (request, signature) = b.download("container-a");
+        let err = check(&access_control, owner, &request, Some(&signature))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not belong"));
+
+        // Claiming A's hotkey does not help without A's key
+        let (mut request, signature) = b.download("container-a");
+        request.validator_hotkey = a.hotkey.clone();
+        assert!(check(&access_control, owner, &request, Some(&signature))
+            .await
+            .is_err());
+
+        let (request, signature) = a.download("container-a");
+        assert!(check(&access_control, owner, &request, None).await.is_err());
+        check(&access_control, owner, &request, Some(&signature))
+            .await
+            .unwrap();
+        // A signed request is accepted once
+        assert!(check(&access_control, owner, &request, Some(&signature))
+            .await
+            .is_err());
+
+        // The signature covers the container
+        let (mut request, signature) = a.download("container-a");
+        request.container_id = "container-b".to_string();
+        assert!(check(&access_control, owner, &request, Some(&signature))
+            .await
+            .is_err());
+
+        // Containers without a recorded owner are out of reach
+        let (request, signature) = a.download("container-a");
+        assert!(check(&access_control, None, &request, Some(&signature))
+            .await
+            .is_err());
+    }
+}
@@ -1,8 +1,54 @@
 //! Container operations service
 
 use super::types::{GrpcResult, SharedExecutorState};
+use crate::config::{ContainerNetworkConfig, ContainerResourceLimits, EgressRule};
+use crate::container_manager::gpu_allocator::GpuRequest;
+use crate::container_manager::image_policy::ImagePolicyViolation;
+use crate::container_manager::volumes::VolumeOwner;
+use crate::container_manager::{ContainerCreateOptions, ContainerPortMapping, CreatedContainer};
+use crate::persistence::VolumeRecord;
+use anyhow::{anyhow, Context};
+use protocol::common::{self as proto, ContainerSpec, ErrorInfo, Timestamp};
+use std::collections::HashMap;
 use tracing::info;
 
+/// Status reported for containers that no longer exist on the executor
+pub const CONTAINER_NOT_FOUND: &str = "not_found";
+
+/// GPU requirement renting every GPU of the executor
+pub const ALL_GPUS: &str = "all";
+
+/// Outcome of creating a container from a spec
+#[derive(Debug)]
+pub enum ContainerCreation {
+    Created(CreatedContainer),
+    Rejected(ImagePolicyViolation),
+}
+
+/// Structured error reported when the image policy refuses an image
+pub fn image_policy_error(violation: &ImagePolicyViolation) -> ErrorInfo {
+    ErrorInfo {
+        code: violation.rule.code().to_string(),
+        message: violation.to_string(),
+        details: HashMap::from([
+            ("image".to_string(), violation.image.clone()),
+            ("reason".to_string(), violation.message.clone()),
+        ]),
+        timestamp: Some(Timestamp {
+            value: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+        }),
+        stack_trace: String::new(),
+    }
+}
+
+/// Volume operations accepted by `manage_container`
+pub const VOLUME_OPERATIONS: &[&str] = &[
+    "create_volume",
+    "list_volumes",
+    "snapshot_volume",
+    "delete_volume",
+];
+
 /// Container operations handler
 pub struct ContainerOperationsService {
     state: SharedExecutorState,
@@ -28,6 +74,186 @@ impl ContainerOperationsService {
         Ok(container_id)
     }
 
+    /// Create container from a full container specification
+    ///
+    /// Honors the requested environment, published ports, network mode and
+    /// resource limits, and authorizes `ssh_public_key` inside the container.
+    /// Host ports outside the executor's range and resources above its limits
+    /// are refused.
+    /// GPUs come from `max_gpus`, or from `gpu_requirements` naming GPUs of
+    /// this host by UUID. `gpu_requirements` of just [`ALL_GPUS`] rents every
+    /// GPU of the executor; without any of these the container gets no GPU.
+    /// The container belongs to `validator_hotkey`, and `volume_mounts` name
+    /// volumes that validator owns. Images
+    /// refused by the image policy are reported as
+    /// [`ContainerCreation::Rejected`] rather than an error.
+    pub async fn create_container_from_spec(
+        &self,
+        spec: &ContainerSpec,
+        ssh_public_key: Option<&str>,
+        validator_hotkey: &str,
+    ) -> GrpcResult<ContainerCreation> {
+        info!("Creating container from spec with image: {}", spec.image);
+
+        let volumes = if spec.volume_mounts.is_empty() {
+            Vec::new()
+        } else {
+            self.state
+                .container_manager
+                .volumes()?
+                .resolve_mounts(require_owner(validator_hotkey)?, &spec.volume_mounts)
+                .await?
+        };
+
+        let options = ContainerCreateOptions {
+            environment: spec.environment.clone(),
+            port_mappings: parse_port_mappings(
+                &spec.port_mappings,
+                &self.state.config.docker.network_config,
+            )?,
+            network_mode: Some(spec.network_mode.clone()).filter(|mode| !mode.is_empty()),
+            ssh_public_key: ssh_public_key
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(String::from),
+            gpu_request: self.gpu_request(spec)?,
+            egress_rules: parse_egress_rules(&spec.egress_rules)?,
+            volumes,
+            owner_hotkey: Some(validator_hotkey.trim())
+                .filter(|hotkey| !hotkey.is_empty())
+                .map(String::from),
+        };
+
+        let resource_limits = spec
+            .resource_limits
+            .as_ref()
+            .map(|requested| {
+                resolve_resource_limits(&self.state.config.docker.resource_limits, requested)
+            })
+            .transpose()?;
+
+        let result = self
+            .state
+            .container_manager
+            .create_container_with_options(&spec.image, &spec.command, resource_limits, &options)
+            .await;
+
+        match result {
+            Ok(created) => Ok(ContainerCreation::Created(created)),
+            Err(e) => match e.downcast::<ImagePolicyViolation>() {
+                Ok(violation) => Ok(ContainerCreation::Rejected(violation)),
+                Err(e) => Err(e.context("Failed to create container")),
+            },
+        }
+    }
+
+    /// Create, list, snapshot or delete volumes owned by `validator_hotkey`
+    ///
+    /// Volumes are named by the `name` parameter. `create_volume` takes an
+    /// optional `quota_bytes` and `rental_id`, `list_volumes` an optional
+    /// `rental_id` filter and `snapshot_volume` the `snapshot_name` to copy
+    /// the volume into. Returns the affected volumes as JSON.
+    pub async fn manage_volume(
+        &self,
+        operation: &str,
+        validator_hotkey: &str,
+        parameters: &HashMap<String, String>,
+    ) -> GrpcResult<String> {
+        info!("Volume operation {} for {}", operation, validator_hotkey);
+
+        let volumes = self.state.container_manager.volumes()?;
+        let owner = VolumeOwner {
+            validator_hotkey: require_owner(validator_hotkey)?.to_string(),
+            rental_id: parameters
+                .get("rental_id")
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty()),
+        };
+        let parameter = |key: &str| {
+            parameters
+                .get(key)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| anyhow!("Missing parameter: {key}"))
+        };
+
+        let details = match operation {
+            "create_volume" => {
+                let quota_bytes = parameters
+                    .get("quota_bytes")
+                    .map(|quota| {
+                        quota
+                            .trim()
+                            .parse::<u64>()
+                            .with_context(|| format!("Invalid volume quota: {quota}"))
+                    })
+                    .transpose()?;
+                let volume = volumes
+                    .create(&owner, parameter("name")?, quota_bytes)
+                    .await?;
+                volume_json(&volume)
+            }
+            "list_volumes" => {
+                let listed = volumes
+                    .list(&owner.validator_hotkey, owner.rental_id.as_deref())
+                    .await?;
+                serde_json::Value::Array(listed.iter().map(volume_json).collect())
+            }
+            "snapshot_volume" => {
+                let snapshot = volumes
+                    .snapshot(&owner, parameter("name")?, parameter("snapshot_name")?)
+                    .await?;
+                volume_json(&snapshot)
+            }
+            "delete_volume" => {
+                let name = parameter("name")?;
+                volumes.delete(&owner.validator_hotkey, name).await?;
+                serde_json::json!({ "name": name, "deleted": true })
+            }
+            other => return Err(anyhow!("Unknown volume operation: {other}")),
+        };
+
+        Ok(details.to_string())
+    }
+
+    fn gpu_request(&self, spec: &ContainerSpec) -> GrpcResult<GpuRequest> {
+        let count = spec
+            .resource_limits
+            .as_ref()
+            .map(|limits| limits.max_gpus)
+            .unwrap_or(0);
+
+        if spec.gpu_requirements.iter().any(|id| id == ALL_GPUS) {
+            if spec.gpu_requirements.len() > 1 || count > 0 {
+                return Err(anyhow!(
+                    "GPU requirement \"{ALL_GPUS}\" cannot be combined with other GPU requests"
+                ));
+            }
+            return Ok(GpuRequest {
+                all: true,
+                ..Default::default()
+            });
+        }
+
+        let known = self.state.container_manager.gpu_devices();
+        let mut device_ids: Vec<String> = Vec::new();
+        for id in &spec.gpu_requirements {
+            if !known.iter().any(|gpu| gpu.uuid == *id) {
+                return Err(anyhow!("Unknown GPU {id}"));
+            }
+            if device_ids.contains(id) {
+                return Err(anyhow!("GPU {id} is requested more than once"));
+            }
+            device_ids.push(id.clone());
+        }
+
+        Ok(GpuRequest {
+            all: false,
+            count,
+            device_ids,
+        })
+    }
+
     /// Execute command in container
     pub async fn execute_container_command(
         &self,
@@ -89,10 +315,10 @@ impl ContainerOperationsService {
             .await
             .map_err(|e| tonic::Status::internal(format!("Failed to get container status: {e}")))?;
 
-        match status {
-            Some(s) => Ok(s.state),
-            None => Err(anyhow::anyhow!("Container {} not found", container_id)),
-        }
+        // A removed container is a status callers act on, not a failed lookup
+        Ok(status
+            .map(|s| s.state)
+            .unwrap_or_else(|| CONTAINER_NOT_FOUND.to_string()))
     }
 
     /// Stream container logs with real-time streaming capability
@@ -363,3 +589,275 @@ fn get_scaling_advice(
         "Current resource allocation appears sufficient"
     }
 }
+
+/// Resource limits for a container, the executor's limits lowered to the
+/// requested ones
+///
+/// CPU and memory requests above the executor's limits are refused.
+fn resolve_resource_limits(
+    configured: &ContainerResourceLimits,
+    requested: &proto::ResourceLimits,
+) -> GrpcResult<ContainerResourceLimits> {
+    let mut limits = configured.clone();
+    if requested.max_cpu_cores > 0 {
+        let cpu_cores = requested.max_cpu_cores as f64;
+        if cpu_cores > configured.cpu_cores {
+            return Err(anyhow!(
+                "Requested {} CPU cores exceed the executor limit of {}",
+                requested.max_cpu_cores,
+                configured.cpu_cores
+            ));
+        }
+        limits.cpu_cores = cpu_cores;
+    }
+    if requested.max_memory_mb > 0 {
+        let memory_bytes = requested.max_memory_mb.saturating_mul(1024 * 1024);
+        if memory_bytes > configured.memory_bytes {
+            return Err(anyhow!(
+                "Requested {} MB of memory exceed the executor limit of {} MB",
+                requested.max_memory_mb,
+                configured.memory_bytes / (1024 * 1024)
+            ));
+        }
+        limits.memory_bytes = memory_bytes;
+    }
+    // A requested bandwidth may lower the executor's cap but not raise it
+    if requested.max_bandwidth_mbps > 0.0 {
+        let requested_bps = (requested.max_bandwidth_mbps * 1_000_000.0 / 8.0) as u64;
+        limits.network_bps = Some(
+            limits
+                .network_bps
+                .map_or(requested_bps, |cap| cap.min(requested_bps)),
+        );
+    }
+    Ok(limits)
+}
+
+/// Parse requested port mappings of the form `container_port[/protocol] -> host_port`
+///
+/// An empty or zero host port lets Docker assign a free port, other host
+/// ports must fall within the executor's `host_port_range`.
+fn parse_port_mappings(
+    mappings: &HashMap<String, String>,
+    network: &ContainerNetworkConfig,
+) -> GrpcResult<Vec<ContainerPortMapping>> {
+    let mut parsed: Vec<ContainerPortMapping> = mappings
+        .iter()
+        .map(|(container, host)| {
+            let (port, protocol) = container
+                .split_once('/')
+                .unwrap_or((container.as_str(), "tcp"));
+            let container_port = port
+                .parse::<u16>()
+                .ok()
+                .filter(|p| *p != 0)
+                .ok_or_else(|| anyhow!("Invalid container port: {container}"))?;
+            if !["tcp", "udp"].contains(&protocol) {
+                return Err(anyhow!("Unsupported port protocol: {protocol}"));
+            }
+            let host = host.trim();
+            let host_port = if host.is_empty() {
+                0
+            } else {
+                host.parse::<u16>()
+                    .with_context(|| format!("Invalid host port: {host}"))?
+            };
+            network.check_host_port(host_port).map_err(|e| anyhow!(e))?;
+
+            Ok(ContainerPortMapping {
+                host_port,
+                container_port,
+                protocol: protocol.to_string(),
+                host_ip: None,
+            })
+        })
+        .collect::<GrpcResult<_>>()?;
+
+    parsed.sort_by_key(|m| (m.container_port, m.protocol.clone()));
+    Ok(parsed)
+}
+
+/// Volumes belong to a validator, so volume requests must name one
+fn require_owner(validator_hotkey: &str) -> GrpcResult<&str> {
+    let hotkey = validator_hotkey.trim();
+    if hotkey.is_empty() {
+        return Err(anyhow!("Volume operations require a validator hotkey"));
+    }
+    Ok(hotkey)
+}
+
+fn volume_json(volume: &VolumeRecord) -> serde_json::Value {
+    serde_json::json!({
+        "name": volume.name,
+        "volume_id": volume.volume_id,
+        "rental_id": volume.rental_id,
+        "quota_bytes": volume.quota_bytes,
+        "usage_bytes": volume.usage_bytes,
+        "source_volume": volume.source_volume,
+        "created_at": volume.created_at.to_rfc3339(),
+    })
+}
+
+/// Convert requested egress rules, leaving CIDR checks to the egress policy
+fn parse_egress_rules(rules: &[proto::EgressRule]) -> GrpcResult<Vec<EgressRule>> {
+    rules
+        .iter()
+        .map(|rule| {
+            let ports = rule
+                .ports
+                .iter()
+                .map(|port| {
+                    u16::try_from(*port)
+                        .ok()
+                        .filter(|p| *p != 0)
+                        .ok_or_else(|| anyhow!("Invalid egress port: {port}"))
+                })
+                .collect::<GrpcResult<_>>()?;
+
+            Ok(EgressRule {
+                cidr: rule.cidr.trim().to_string(),
+                ports,
+                protocol: Some(rule.protocol.trim().to_lowercase()).filter(|p| !p.is_empty()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HostPortRange;
+
+    fn network(host_port_range: Option<HostPortRange>) -> ContainerNetworkConfig {
+        ContainerNetworkConfig {
+            host_port_range,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_port_mappings() {
+        let mappings = HashMap::from([
+            ("22".to_string(), String::new()),
+            ("8888/tcp".to_string(), "0".to_string()),
+            ("9000/udp".to_string(), "19000".to_string()),
+        ]);
+        let range = HostPortRange {
+            start: 19000,
+            end: 19999,
+        };
+
+        let parsed = parse_port_mappings(&mappings, &network(Some(range))).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].container_port, 22);
+        assert_eq!(parsed[0].protocol, "tcp");
+        assert_eq!(parsed[0].host_port, 0);
+        assert_eq!(parsed[1].host_port, 0);
+        assert_eq!(parsed[2].protocol, "udp");
+        assert_eq!(parsed[2].host_port, 19000);
+    }
+
+    #[test]
+    fn test_parse_port_mappings_rejects_invalid() {
+        for (container, host) in [("ssh", ""), ("0", ""), ("22/sctp", ""), ("22", "abc")] {
+            let mappings = HashMap::from([(container.to_string(), host.to_string())]);
+            assert!(parse_port_mappings(&mappings, &network(None)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_port_mappings_enforces_host_port_range() {
+        let range = HostPortRange {
+            start: 30000,
+            end: 30999,
+        };
+        let ssh = |host: &str| HashMap::from([("22".to_string(), host.to_string())]);
+
+        assert!(parse_port_mappings(&ssh("30000"), &network(Some(range))).is_ok());
+        assert!(parse_port_mappings(&ssh("30999"), &network(Some(range))).is_ok());
+        for host in ["22", "2375", "29999", "31000"] {
+            assert!(parse_port_mappings(&ssh(host), &network(Some(range))).is_err());
+        }
+
+        // Without a range only Docker-assigned ports are allowed
+        assert!(parse_port_mappings(&ssh("0"), &network(None)).is_ok());
+        assert!(parse_port_mappings(&ssh("30000"), &network(None)).is_err());
+    }
+
+    #[test]
+    fn test_resource_requests_cannot_exceed_limits() {
+        let configured = ContainerResourceLimits {
+            memory_bytes: 8 * 1024 * 1024 * 1024,
+            cpu_cores: 4.0,
+            gpu_memory_bytes: None,
+            disk_io_bps: None,
+            network_bps: Some(100_000_000),
+        };
+
+        let limits = resolve_resource_limits(
+            &configured,
+            &proto::ResourceLimits {
+                max_cpu_cores: 2,
+                max_memory_mb: 4096,
+                max_bandwidth_mbps: 8000.0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(limits.cpu_cores, 2.0);
+        assert_eq!(limits.memory_bytes, 4 * 1024 * 1024 * 1024);
+        assert_eq!(limits.network_bps, Some(100_000_000));
+
+        // Unset requests keep the executor's limits
+        let limits =
+            resolve_resource_limits(&configured, &proto::ResourceLimits::default()).unwrap();
+        assert_eq!(limits.cpu_cores, 4.0);
+        assert_eq!(limits.memory_bytes, configured.memory_bytes);
+
+        for requested in [
+            proto::ResourceLimits {
+                max_cpu_cores: 5,
+                ..Default::default()
+            },
+            proto::ResourceLimits {
+                max_memory_mb: 8193,
+                ..Default::default()
+            },
+            proto::ResourceLimits {
+                max_memory_mb: u64::MAX,
+                ..Default::default()
+            },
+        ] {
+            assert!(resolve_resource_limits(&configured, &requested).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_egress_rules() {
+        let rules = vec![
+            proto::EgressRule {
+                cidr: " 203.0.113.0/24 ".to_string(),
+                ports: vec![443],
+                protocol: "TCP".to_string(),
+            },
+            proto::EgressRule {
+                cidr: "198.51.100.7".to_string(),
+                ports: vec![],
+                protocol: String::new(),
+            },
+        ];
+
+        let parsed = parse_egress_rules(&rules).unwrap();
+        assert_eq!(parsed[0].cidr, "203.0.113.0/24");
+        assert_eq!(parsed[0].ports, vec![443]);
+        assert_eq!(parsed[0].protocol.as_deref(), Some("tcp"));
+        assert_eq!(parsed[1].protocol, None);
+
+        let invalid = proto::EgressRule {
+            cidr: "203.0.113.0/24".to_string(),
+            ports: vec![70000],
+            protocol: String::new(),
+        };
+        assert!(parse_egress_rules(&[invalid]).is_err());
+    }
+}
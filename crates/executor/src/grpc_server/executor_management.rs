@@ -3,8 +3,8 @@
 use super::types::SharedExecutorState;
 use chrono::{DateTime, Utc};
 use protocol::executor_management::{
-    executor_management_server::ExecutorManagement, HealthCheckRequest, HealthCheckResponse,
-    SshKeyUpdate, SshKeyUpdateResponse, StatusRequest, StatusResponse,
+    executor_management_server::ExecutorManagement, GpuAssignment, HealthCheckRequest,
+    HealthCheckResponse, SshKeyUpdate, SshKeyUpdateResponse, StatusRequest, StatusResponse,
 };
 use std::collections::HashMap;
 use tonic::{Request, Response, Status};
@@ -150,7 +150,7 @@ impl ExecutorManagement for ExecutorManagementService {
                 .map(|g| protocol::common::GpuSpec {
                     model: g.name.clone(),
                     memory_mb: g.memory_total_bytes / (1024 * 1024),
-                    uuid: String::new(), // Not tracked in system monitor
+                    uuid: g.uuid.clone(),
                     driver_version: g.driver_version.clone(),
                     cuda_version: g.cuda_version.clone().unwrap_or_default(),
                     utilization_percent: g.utilization_percent as f64,
@@ -159,7 +159,7 @@ impl ExecutorManagement for ExecutorManagementService {
                     power_watts: g.power_usage_watts as f64,
                     core_clock_mhz: 0,
                     memory_clock_mhz: 0,
-                    compute_capability: String::new(),
+                    compute_capability: g.compute_capability.clone().unwrap_or_default(),
                 })
                 .collect(),
             cpu: Some(protocol::common::CpuSpec {
@@ -213,6 +213,26 @@ impl ExecutorManagement for ExecutorManagementService {
             .unwrap_or_default()
             .len() as u32;
 
+        let gpu_assignments = self
+            .state
+            .container_manager
+            .gpu_assignments()
+            .into_iter()
+            .map(|assignment| GpuAssignment {
+                container_id: assignment.container_id,
+                gpu_uuids: assignment.devices.iter().map(|d| d.uuid.clone()).collect(),
+                gpu_indices: assignment.devices.iter().map(|d| d.index).collect(),
+                assigned_at: assignment.assigned_at,
+            })
+            .collect();
+        let free_gpu_uuids = self
+            .state
+            .container_manager
+            .free_gpus()
+            .into_iter()
+            .map(|gpu| gpu.uuid)
+            .collect();
+
         // Build OS info
         let _os_info = protocol::common::OsInfo {
             name: system_info.system.os_name.clone(),
@@ -233,6 +253,8 @@ impl ExecutorManagement for ExecutorManagementService {
             active_containers,
             active_validators,
             uptime_seconds: system_info.system.uptime_seconds,
+            gpu_assignments,
+            free_gpu_uuids,
         }))
     }
 }
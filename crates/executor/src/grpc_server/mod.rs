@@ -3,6 +3,7 @@
 //! Provides the ExecutorControl service interface for miner communication.
 //! Modularized following SOLID principles for maintainability and testability.
 
+pub mod container_io;
 pub mod container_operations;
 pub mod executor_management;
 pub mod health_check;
@@ -17,9 +18,12 @@ pub use types::{
     ValidatorListResponse, ValidatorRevokeRequest, ValidatorRevokeResponse, ValidatorServiceTrait,
 };
 
+use crate::benchmark::{BenchmarkRunner, MB};
 use crate::ExecutorState;
 use anyhow::Result;
-use container_operations::ContainerOperationsService;
+use common::crypto::evidence;
+use container_io::{ContainerIoService, ExecResponseStream, FileChunkStream};
+use container_operations::{ContainerCreation, ContainerOperationsService};
 use health_check::{HealthCheckService, HealthStatus};
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -30,9 +34,10 @@ use validator_access::ValidatorAccessService;
 use protocol::common::LogEntry;
 use protocol::executor_control::{
     executor_control_server::{ExecutorControl, ExecutorControlServer},
-    BenchmarkRequest, BenchmarkResponse, ContainerOpRequest, ContainerOpResponse,
-    HealthCheckRequest, HealthCheckResponse, LogSubscriptionRequest, ProvisionAccessRequest,
-    ProvisionAccessResponse, SystemProfileRequest, SystemProfileResponse,
+    BenchmarkRequest, BenchmarkResponse, ContainerOpRequest, ContainerOpResponse, ExecRequest,
+    FileChunk, FileDownloadRequest, FileTransferResponse, HealthCheckRequest, HealthCheckResponse,
+    LogSubscriptionRequest, ProvisionAccessRequest, ProvisionAccessResponse, SystemProfileRequest,
+    SystemProfileResponse,
 };
 use tokio_stream::wrappers::ReceiverStream;
 
@@ -80,6 +85,7 @@ pub struct ExecutorControlService {
     system_profile: SystemProfileService,
     container_operations: ContainerOperationsService,
     health_check: HealthCheckService,
+    benchmarks: BenchmarkRunner,
 }
 
 impl ExecutorControlService {
@@ -90,6 +96,7 @@ impl ExecutorControlService {
             validator_access: ValidatorAccessService::new(state.clone()),
             system_profile: SystemProfileService::new(state.clone()),
             container_operations: ContainerOperationsService::new(state.clone()),
+            benchmarks: BenchmarkRunner::new(state.config.benchmark.clone()),
             health_check: HealthCheckService::new(state),
         }
     }
@@ -274,7 +281,10 @@ impl ExecutorControl for ExecutorControlService {
                     "utilization_percent": gpu.utilization_percent,
                     "temperature_celsius": gpu.temperature_celsius,
                     "driver_version": gpu.driver_version,
-                    "cuda_version": gpu.cuda_version
+                    "cuda_version": gpu.cuda_version,
+                    "uuid": gpu.uuid,
+                    "vendor": gpu.vendor,
+                    "compute_capability": gpu.compute_capability
                 })
             }).collect::<Vec<_>>(),
             "disk": system_info.disk.iter().map(|disk| {
@@ -332,6 +342,18 @@ impl ExecutorControl for ExecutorControlService {
 
         let collected_at = std::time::SystemTime::now();
 
+        // Bind the response to the validator's nonce with the executor key
+        let signature = evidence::sign_evidence(
+            &self.state.signing_key,
+            &evidence::system_profile_message(
+                &req.nonce,
+                &req.validator_hotkey,
+                &profile_hash,
+                &encrypted_profile,
+                &encryption_nonce,
+            ),
+        );
+
         Ok(tonic::Response::new(SystemProfileResponse {
             encrypted_profile,
             encryption_nonce,
@@ -340,6 +362,9 @@ impl ExecutorControl for ExecutorControlService {
             }),
             profile_hash,
             error: None,
+            nonce: req.nonce,
+            executor_public_key: self.state.signing_key.public_key().to_string(),
+            signature,
         }))
     }
 
@@ -381,79 +406,49 @@ impl ExecutorControl for ExecutorControlService {
 
                 (score, metrics)
             }
-            "cpu" => {
-                // CPU benchmark
-                let mut metrics = std::collections::HashMap::new();
-                metrics.insert("cpu_cores".to_string(), system_info.cpu.cores.to_string());
-                metrics.insert("cpu_model".to_string(), system_info.cpu.model.clone());
-                metrics.insert(
-                    "cpu_usage_percent".to_string(),
-                    system_info.cpu.usage_percent.to_string(),
-                );
-
-                // Simple CPU score based on cores
-                let score = (system_info.cpu.cores as f64) * 100.0;
-
-                (score, metrics)
-            }
-            "memory" => {
-                // Memory benchmark
-                let mut metrics = std::collections::HashMap::new();
-                let total_mb = system_info.memory.total_bytes / (1024 * 1024);
-                let available_mb = system_info.memory.available_bytes / (1024 * 1024);
-                metrics.insert("total_memory_mb".to_string(), total_mb.to_string());
-                metrics.insert("available_memory_mb".to_string(), available_mb.to_string());
-
-                // Score based on total memory
-                let score = total_mb as f64 / 1024.0; // GB
-
-                (score, metrics)
-            }
-            "network" => {
-                // Network benchmark
-                let mut metrics = std::collections::HashMap::new();
-                metrics.insert(
-                    "network_interfaces".to_string(),
-                    system_info.network.interfaces.len().to_string(),
-                );
-
-                // Simple network score
-                let score = 100.0; // Placeholder
-
-                (score, metrics)
-            }
-            "disk" => {
-                // Disk benchmark
-                let mut metrics = std::collections::HashMap::new();
-                metrics.insert(
-                    "total_disks".to_string(),
-                    system_info.disk.len().to_string(),
-                );
-                if !system_info.disk.is_empty() {
-                    let total_gb = system_info.disk[0].total_bytes / (1024 * 1024 * 1024);
-                    metrics.insert("primary_disk_total_gb".to_string(), total_gb.to_string());
+            benchmark_type => {
+                let benchmark = self
+                    .benchmarks
+                    .prepare(benchmark_type, req.duration_seconds, req.parameters.clone())
+                    .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+
+                let mut result = self.benchmarks.run(benchmark).await.map_err(|e| {
+                    tonic::Status::internal(format!("{benchmark_type} benchmark failed: {e}"))
+                })?;
+
+                // Static facts alongside the measurements
+                match benchmark_type {
+                    "cpu" => {
+                        result.insert("cpu_cores", system_info.cpu.cores);
+                        result.insert("cpu_model", &system_info.cpu.model);
+                    }
+                    "memory" => {
+                        result.insert("total_memory_mb", system_info.memory.total_bytes / MB);
+                        result.insert(
+                            "available_memory_mb",
+                            system_info.memory.available_bytes / MB,
+                        );
+                    }
+                    _ => {}
                 }
 
-                // Score based on total disk space
-                let score = if !system_info.disk.is_empty() {
-                    system_info.disk[0].total_bytes as f64 / (1024.0 * 1024.0 * 1024.0 * 1024.0)
-                // TB
-                } else {
-                    0.0
-                };
-
-                (score, metrics)
-            }
-            _ => {
-                return Err(tonic::Status::invalid_argument(format!(
-                    "Unknown benchmark type: {}",
-                    req.benchmark_type
-                )));
+                (result.score, result.metrics)
             }
         };
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
+        let signature = evidence::sign_evidence(
+            &self.state.signing_key,
+            &evidence::benchmark_message(
+                &req.nonce,
+                &req.validator_hotkey,
+                &req.benchmark_type,
+                score,
+                &metrics,
+            ),
+        );
+
         Ok(tonic::Response::new(BenchmarkResponse {
             results: metrics,
             score,
@@ -462,6 +457,9 @@ impl ExecutorControl for ExecutorControlService {
                 req.benchmark_type, duration_ms, req.validator_hotkey
             ),
             error: None,
+            nonce: req.nonce,
+            executor_public_key: self.state.signing_key.public_key().to_string(),
+            signature,
         }))
     }
 
@@ -477,19 +475,39 @@ impl ExecutorControl for ExecutorControlService {
         match req.operation.as_str() {
             "create" => {
                 if let Some(spec) = req.container_spec {
-                    let container_id = container_ops
-                        .create_container(&spec.image, &spec.command)
+                    let created = match container_ops
+                        .create_container_from_spec(
+                            &spec,
+                            Some(&req.ssh_public_key),
+                            &req.validator_hotkey,
+                        )
                         .await
-                        .map_err(|e| {
-                            tonic::Status::internal(format!("Failed to create container: {e}"))
-                        })?;
+                        .map_err(|e| tonic::Status::internal(format!("{e:#}")))?
+                    {
+                        ContainerCreation::Created(created) => created,
+                        ContainerCreation::Rejected(violation) => {
+                            return Ok(tonic::Response::new(ContainerOpResponse {
+                                success: false,
+                                container_id: String::new(),
+                                status: None,
+                                details: violation.to_string(),
+                                error: Some(container_operations::image_policy_error(&violation)),
+                            }));
+                        }
+                    };
+                    let container_id = created.container_id;
+                    let details = serde_json::json!({
+                        "ports": created.ports,
+                        "ssh_user": created.ssh_user,
+                    })
+                    .to_string();
 
                     Ok(tonic::Response::new(ContainerOpResponse {
                         success: true,
                         container_id: container_id.clone(),
                         status: Some(protocol::ContainerStatus {
                             container_id: container_id.clone(),
-                            status: "created".to_string(),
+                            status: "running".to_string(),
                             status_message: format!(
                                 "Container {container_id} created successfully"
                             ),
@@ -498,12 +516,16 @@ impl ExecutorControl for ExecutorControlService {
                                     std::time::SystemTime::now(),
                                 )),
                             }),
-                            started_at: None,
+                            started_at: Some(protocol::common::Timestamp {
+                                value: Some(prost_types::Timestamp::from(
+                                    std::time::SystemTime::now(),
+                                )),
+                            }),
                             finished_at: None,
                             exit_code: 0,
                             resource_usage: None,
                         }),
-                        details: "Container created successfully".to_string(),
+                        details,
                         error: None,
                     }))
                 } else {
@@ -607,6 +629,22 @@ impl ExecutorControl for ExecutorControlService {
                     error: None,
                 }))
             }
+            operation if container_operations::VOLUME_OPERATIONS.contains(&operation) => {
+                let details = container_ops
+                    .manage_volume(operation, &req.validator_hotkey, &req.parameters)
+                    .await
+                    .map_err(|e| {
+                        tonic::Status::internal(format!("Volume operation failed: {e}"))
+                    })?;
+
+                Ok(tonic::Response::new(ContainerOpResponse {
+                    success: true,
+                    container_id: String::new(),
+                    status: None,
+                    details,
+                    error: None,
+                }))
+            }
             _ => Err(tonic::Status::invalid_argument(format!(
                 "Unknown operation: {}",
                 req.operation
@@ -691,6 +729,46 @@ impl ExecutorControl for ExecutorControlService {
         Ok(tonic::Response::new(ReceiverStream::new(rx)))
     }
 
+    type ExecStream = ExecResponseStream;
+
+    async fn exec(
+        &self,
+        request: tonic::Request<tonic::Streaming<ExecRequest>>,
+    ) -> Result<tonic::Response<Self::ExecStream>, tonic::Status> {
+        let stream = ContainerIoService::new(self.state.clone())
+            .exec(request.into_inner())
+            .await
+            .map_err(|e| tonic::Status::failed_precondition(format!("Exec failed: {e:#}")))?;
+
+        Ok(tonic::Response::new(stream))
+    }
+
+    async fn upload_file(
+        &self,
+        request: tonic::Request<tonic::Streaming<FileChunk>>,
+    ) -> Result<tonic::Response<FileTransferResponse>, tonic::Status> {
+        let response = ContainerIoService::new(self.state.clone())
+            .upload_file(request.into_inner())
+            .await
+            .map_err(|e| tonic::Status::failed_precondition(format!("Upload failed: {e:#}")))?;
+
+        Ok(tonic::Response::new(response))
+    }
+
+    type DownloadFileStream = FileChunkStream;
+
+    async fn download_file(
+        &self,
+        request: tonic::Request<FileDownloadRequest>,
+    ) -> Result<tonic::Response<Self::DownloadFileStream>, tonic::Status> {
+        let stream = ContainerIoService::new(self.state.clone())
+            .download_file(request.into_inner())
+            .await
+            .map_err(|e| tonic::Status::failed_precondition(format!("Download failed: {e:#}")))?;
+
+        Ok(tonic::Response::new(stream))
+    }
+
     async fn health_check(
         &self,
         request: tonic::Request<HealthCheckRequest>,
@@ -2,21 +2,25 @@
 //!
 //! Core executor functionality and CLI interface.
 
+pub mod benchmark;
 pub mod cli;
 pub mod config;
 pub mod container_manager;
 pub mod grpc_server;
 pub mod journal;
+pub mod persistence;
+pub mod signing_key;
 pub mod system_monitor;
 pub mod validation_session;
 
 pub use config::ExecutorConfig;
 
 use anyhow::Result;
+use common::crypto::Ed25519PrivateKey;
 use common::identity::ExecutorId;
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 use validation_session::ValidationSessionService;
 
 pub struct ExecutorState {
@@ -27,6 +31,8 @@ pub struct ExecutorState {
     pub validation_service: Option<Arc<ValidationSessionService>>,
     pub validation_session: Arc<validation_session::ValidationSessionService>,
     pub active_challenges: Arc<AtomicU32>,
+    /// Key signing profile and benchmark responses
+    pub signing_key: Arc<Ed25519PrivateKey>,
 }
 
 impl ExecutorState {
@@ -36,10 +42,32 @@ impl ExecutorState {
 
         let system_monitor = Arc::new(system_monitor::SystemMonitor::new(config.system.clone())?);
 
+        let db = persistence::ExecutorDb::new(&config.database).await?;
+
+        let gpus = system_monitor
+            .collect_gpu_info()
+            .await
+            .into_iter()
+            .map(|gpu| container_manager::gpu_allocator::GpuDevice {
+                index: gpu.index,
+                uuid: gpu.uuid,
+            })
+            .collect();
         let container_manager =
-            container_manager::ContainerManager::new(config.docker.clone()).await?;
+            container_manager::ContainerManager::restore(config.docker.clone(), gpus, db.clone())
+                .await?;
+
+        let validation_session =
+            Arc::new(ValidationSessionService::restore(config.validator.clone(), db).await?);
+        let removed = validation_session.reconcile_ssh_access().await?;
+        if removed > 0 {
+            info!(
+                "Removed SSH access left from a previous run for {} users",
+                removed
+            );
+        }
 
-        let validation_session = Arc::new(ValidationSessionService::new(config.validator.clone())?);
+        let signing_key = Arc::new(signing_key::load_or_generate(&config.signing_key_path)?);
 
         let validation_service = if config.validator.enabled {
             Some(validation_session.clone())
@@ -55,6 +83,7 @@ impl ExecutorState {
             validation_service,
             validation_session,
             active_challenges: Arc::new(AtomicU32::new(0)),
+            signing_key,
         })
     }
 
@@ -75,6 +104,25 @@ impl ExecutorState {
             }
         }
 
+        let active_owners = self
+            .validation_session
+            .list_active_access()
+            .await?
+            .into_iter()
+            .map(|access| access.validator_id.hotkey)
+            .collect();
+        match self
+            .container_manager
+            .maintain_volumes(&active_owners)
+            .await
+        {
+            Ok(removed) if removed > 0 => {
+                info!("Removed {} volumes whose owner's access expired", removed)
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Volume maintenance failed: {}", e),
+        }
+
         info!("All executor components healthy");
         Ok(())
     }
@@ -0,0 +1,670 @@
+//! Executor state persisted in SQLite
+//!
+//! Records the containers the executor started, the GPUs assigned to them,
+//! validator access grants, the SSH keys written for those grants,
+//! outstanding hotkey challenges and the named volumes validators own, so a
+//! restarted executor can re-adopt or clean up what the previous run left
+//! behind instead of forgetting it.
+
+use crate::container_manager::gpu_allocator::GpuAssignment;
+use crate::validation_session::{SignatureChallenge, ValidatorAccess};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use common::config::DatabaseConfig;
+use common::persistence::{MigrationManager, SqliteConnection};
+use sqlx::{FromRow, SqlitePool};
+use std::path::Path;
+use std::time::SystemTime;
+use tracing::info;
+
+/// Container started by the executor
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct ContainerRecord {
+    pub container_id: String,
+    pub name: String,
+    pub image: String,
+    /// Key authorized for SSH inside the container
+    pub ssh_public_key: Option<String>,
+    /// Hotkey of the validator that created the container, the only one
+    /// allowed to run commands in it or transfer its files
+    pub owner_hotkey: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// SSH key written to a validator user's authorized_keys
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct SshKeyRecord {
+    pub username: String,
+    pub public_key: String,
+    pub validator_hotkey: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Named volume owned by a validator
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct VolumeRecord {
+    /// Docker volume name
+    pub volume_id: String,
+    /// Name the owner refers to the volume by
+    pub name: String,
+    pub owner_hotkey: String,
+    /// Rental the volume was created for
+    pub rental_id: Option<String>,
+    pub quota_bytes: i64,
+    /// Usage at the last measurement
+    pub usage_bytes: i64,
+    /// Volume this one is a snapshot of
+    pub source_volume: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Last time the owner had access or a container used the volume
+    pub last_active_at: DateTime<Utc>,
+}
+
+/// Executor database client
+#[derive(Debug, Clone)]
+pub struct ExecutorDb {
+    pool: SqlitePool,
+}
+
+impl ExecutorDb {
+    /// Open the database, creating it and its tables if needed
+    pub async fn new(config: &DatabaseConfig) -> Result<Self> {
+        ensure_database_directory(&config.url)?;
+
+        let connection = SqliteConnection::new(config)
+            .await
+            .context("Failed to open executor database")?;
+        let db = Self {
+            pool: connection.pool().clone(),
+        };
+
+        if config.run_migrations {
+            connection.run_migrations().await?;
+            db.run_migrations().await?;
+        }
+
+        Ok(db)
+    }
+
+    /// Get the connection pool
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    async fn run_migrations(&self) -> Result<()> {
+        info!("Running executor database migrations");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS containers (
+                container_id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                image TEXT NOT NULL,
+                ssh_public_key TEXT,
+                owner_hotkey TEXT,
+                created_at TIMESTAMP NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create containers table")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS gpu_assignments (
+                container_id TEXT PRIMARY KEY,
+                devices TEXT NOT NULL,
+                assigned_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create gpu_assignments table")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS validator_access (
+                hotkey TEXT PRIMARY KEY,
+                access TEXT NOT NULL,
+                expires_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create validator_access table")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS ssh_keys (
+                username TEXT NOT NULL,
+                public_key TEXT NOT NULL,
+                validator_hotkey TEXT NOT NULL,
+                added_at TIMESTAMP NOT NULL,
+                PRIMARY KEY (username, public_key)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create ssh_keys table")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS signature_challenges (
+                challenge_id TEXT PRIMARY KEY,
+                challenge TEXT NOT NULL,
+                expires_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create signature_challenges table")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS volumes (
+                volume_id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                owner_hotkey TEXT NOT NULL,
+                rental_id TEXT,
+                quota_bytes INTEGER NOT NULL,
+                usage_bytes INTEGER NOT NULL DEFAULT 0,
+                source_volume TEXT,
+                created_at TIMESTAMP NOT NULL,
+                last_active_at TIMESTAMP NOT NULL,
+                UNIQUE (owner_hotkey, name)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create volumes table")?;
+
+        Ok(())
+    }
+
+    /// Record a container the executor started
+    pub async fn record_container(&self, record: &ContainerRecord) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO containers
+                (container_id, name, image, ssh_public_key, owner_hotkey, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&record.container_id)
+        .bind(&record.name)
+        .bind(&record.image)
+        .bind(&record.ssh_public_key)
+        .bind(&record.owner_hotkey)
+        .bind(record.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget a container that has been removed
+    pub async fn remove_container(&self, container_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM containers WHERE container_id = ?")
+            .bind(container_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record of a container that has not been removed yet
+    pub async fn get_container(&self, container_id: &str) -> Result<Option<ContainerRecord>> {
+        let record = sqlx::query_as::<_, ContainerRecord>(
+            "SELECT container_id, name, image, ssh_public_key, owner_hotkey, created_at FROM containers WHERE container_id = ?",
+        )
+        .bind(container_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    /// Containers recorded as started and not yet removed
+    pub async fn list_containers(&self) -> Result<Vec<ContainerRecord>> {
+        let records = sqlx::query_as::<_, ContainerRecord>(
+            "SELECT container_id, name, image, ssh_public_key, owner_hotkey, created_at FROM containers ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(records)
+    }
+
+    /// Save the GPUs assigned to a container
+    pub async fn save_gpu_assignment(&self, assignment: &GpuAssignment) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO gpu_assignments (container_id, devices, assigned_at) VALUES (?, ?, ?)",
+        )
+        .bind(&assignment.container_id)
+        .bind(serde_json::to_string(&assignment.devices)?)
+        .bind(assignment.assigned_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Move a GPU assignment to a new owner
+    pub async fn rename_gpu_assignment(&self, from: &str, to: &str) -> Result<()> {
+        sqlx::query("UPDATE gpu_assignments SET container_id = ? WHERE container_id = ?")
+            .bind(to)
+            .bind(from)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete the GPU assignment of a container
+    pub async fn delete_gpu_assignment(&self, container_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM gpu_assignments WHERE container_id = ?")
+            .bind(container_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// All GPU assignments, ordered by owner
+    pub async fn list_gpu_assignments(&self) -> Result<Vec<GpuAssignment>> {
+        let rows: Vec<(String, String, i64)> = sqlx::query_as(
+            "SELECT container_id, devices, assigned_at FROM gpu_assignments ORDER BY container_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(container_id, devices, assigned_at)| {
+                Ok(GpuAssignment {
+                    container_id,
+                    devices: serde_json::from_str(&devices)
+                        .context("Invalid stored GPU assignment")?,
+                    assigned_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Save a validator access grant, replacing any previous grant
+    pub async fn save_access(&self, access: &ValidatorAccess) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO validator_access (hotkey, access, expires_at) VALUES (?, ?, ?)",
+        )
+        .bind(&access.validator_id.hotkey)
+        .bind(serde_json::to_string(access)?)
+        .bind(unix_seconds(access.expires_at))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete the access grant of a validator
+    pub async fn delete_access(&self, hotkey: &str) -> Result<()> {
+        sqlx::query("DELETE FROM validator_access WHERE hotkey = ?")
+            .bind(hotkey)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Access grants that have not expired
+    pub async fn load_access(&self) -> Result<Vec<ValidatorAccess>> {
+        let rows: Vec<(String,)> =
+            sqlx::query_as("SELECT access FROM validator_access WHERE expires_at > ?")
+                .bind(unix_seconds(SystemTime::now()))
+                .fetch_all(&self.pool)
+                .await?;
+
+        rows.into_iter()
+            .map(|(json,)| serde_json::from_str(&json).context("Invalid stored validator access"))
+            .collect()
+    }
+
+    /// Record an SSH key written for a validator
+    pub async fn record_ssh_key(
+        &self,
+        username: &str,
+        public_key: &str,
+        validator_hotkey: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO ssh_keys (username, public_key, validator_hotkey, added_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(username)
+        .bind(public_key.trim())
+        .bind(validator_hotkey)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget every SSH key written for a user
+    pub async fn delete_ssh_keys(&self, username: &str) -> Result<()> {
+        sqlx::query("DELETE FROM ssh_keys WHERE username = ?")
+            .bind(username)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// All recorded SSH keys
+    pub async fn list_ssh_keys(&self) -> Result<Vec<SshKeyRecord>> {
+        let records = sqlx::query_as::<_, SshKeyRecord>(
+            "SELECT username, public_key, validator_hotkey, added_at FROM ssh_keys ORDER BY added_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(records)
+    }
+
+    /// Save an outstanding hotkey signature challenge
+    pub async fn save_challenge(&self, challenge: &SignatureChallenge) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO signature_challenges (challenge_id, challenge, expires_at) VALUES (?, ?, ?)",
+        )
+        .bind(&challenge.challenge_id)
+        .bind(serde_json::to_string(challenge)?)
+        .bind(unix_seconds(challenge.expires_at))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete an answered or abandoned challenge
+    pub async fn delete_challenge(&self, challenge_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM signature_challenges WHERE challenge_id = ?")
+            .bind(challenge_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete expired challenges and return the ones still outstanding
+    pub async fn load_challenges(&self) -> Result<Vec<SignatureChallenge>> {
+        let now = unix_seconds(SystemTime::now());
+        sqlx::query("DELETE FROM signature_challenges WHERE expires_at <= ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT challenge FROM signature_challenges")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|(json,)| serde_json::from_str(&json).context("Invalid stored challenge"))
+            .collect()
+    }
+
+    /// Record a new volume
+    ///
+    /// Fails if the owner already has a volume with the same name.
+    pub async fn record_volume(&self, record: &VolumeRecord) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO volumes
+                (volume_id, name, owner_hotkey, rental_id, quota_bytes, usage_bytes,
+                 source_volume, created_at, last_active_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&record.volume_id)
+        .bind(&record.name)
+        .bind(&record.owner_hotkey)
+        .bind(&record.rental_id)
+        .bind(record.quota_bytes)
+        .bind(record.usage_bytes)
+        .bind(&record.source_volume)
+        .bind(record.created_at)
+        .bind(record.last_active_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget a volume that has been removed
+    pub async fn remove_volume(&self, volume_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM volumes WHERE volume_id = ?")
+            .bind(volume_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Volume an owner refers to by `name`
+    pub async fn get_volume(&self, owner_hotkey: &str, name: &str) -> Result<Option<VolumeRecord>> {
+        let record = sqlx::query_as::<_, VolumeRecord>(
+            "SELECT * FROM volumes WHERE owner_hotkey = ? AND name = ?",
+        )
+        .bind(owner_hotkey)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    /// Volumes of one owner, or of every owner when `None`
+    pub async fn list_volumes(&self, owner_hotkey: Option<&str>) -> Result<Vec<VolumeRecord>> {
+        let records = match owner_hotkey {
+            Some(owner) => {
+                sqlx::query_as::<_, VolumeRecord>(
+                    "SELECT * FROM volumes WHERE owner_hotkey = ? ORDER BY created_at",
+                )
+                .bind(owner)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_as::<_, VolumeRecord>("SELECT * FROM volumes ORDER BY created_at")
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+        };
+        Ok(records)
+    }
+
+    /// Store the measured usage of a volume
+    pub async fn update_volume_usage(&self, volume_id: &str, usage_bytes: i64) -> Result<()> {
+        sqlx::query("UPDATE volumes SET usage_bytes = ? WHERE volume_id = ?")
+            .bind(usage_bytes)
+            .bind(volume_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Mark a volume as in use now, postponing its garbage collection
+    pub async fn touch_volume(&self, volume_id: &str) -> Result<()> {
+        sqlx::query("UPDATE volumes SET last_active_at = ? WHERE volume_id = ?")
+            .bind(Utc::now())
+            .bind(volume_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Create the directory holding a `sqlite:` database file
+fn ensure_database_directory(url: &str) -> Result<()> {
+    let Some(path) = url.strip_prefix("sqlite:") else {
+        return Ok(());
+    };
+    let path = path.trim_start_matches("//");
+    let path = path.split('?').next().unwrap_or(path);
+    if path.is_empty() || path.starts_with(":memory:") {
+        return Ok(());
+    }
+
+    if let Some(parent) = Path::new(path)
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+/// Open a database under `dir` for tests
+#[cfg(test)]
+pub(crate) async fn test_db(dir: &Path) -> ExecutorDb {
+    ExecutorDb::new(&DatabaseConfig {
+        url: format!("sqlite:{}", dir.join("state").join("executor.db").display()),
+        ..Default::default()
+    })
+    .await
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation_session::ValidatorId;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_state_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+
+        db.record_container(&ContainerRecord {
+            container_id: "abc123".to_string(),
+            name: "basilca-1234abcd".to_string(),
+            image: "ubuntu:22.04".to_string(),
+            ssh_public_key: Some("ssh-ed25519 AAAA renter".to_string()),
+            owner_hotkey: Some("5Validator".to_string()),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+        let validator = ValidatorId::new("5Validator".to_string());
+        db.save_access(&ValidatorAccess::new(
+            validator.clone(),
+            "ssh-ed25519 AAAA validator",
+        ))
+        .await
+        .unwrap();
+        let mut expired = ValidatorAccess::new(
+            ValidatorId::new("5Expired".to_string()),
+            "ssh-ed25519 AAAA old",
+        );
+        expired.expires_at = SystemTime::now() - Duration::from_secs(1);
+        db.save_access(&expired).await.unwrap();
+
+        db.record_ssh_key(
+            "validator_abc",
+            "ssh-ed25519 AAAA validator\n",
+            "5Validator",
+        )
+        .await
+        .unwrap();
+        db.record_ssh_key("validator_abc", "ssh-ed25519 AAAA validator", "5Validator")
+            .await
+            .unwrap();
+
+        let challenge = SignatureChallenge::new("5Validator".to_string(), Duration::from_secs(60));
+        db.save_challenge(&challenge).await.unwrap();
+        db.save_challenge(&SignatureChallenge::new(
+            "5Validator".to_string(),
+            Duration::ZERO,
+        ))
+        .await
+        .unwrap();
+
+        drop(db);
+        let db = test_db(dir.path()).await;
+
+        let containers = db.list_containers().await.unwrap();
+        assert_eq!(containers.len(), 1);
+        assert_eq!(containers[0].container_id, "abc123");
+        let container = db.get_container("abc123").await.unwrap().unwrap();
+        assert_eq!(container.owner_hotkey.as_deref(), Some("5Validator"));
+
+        let access = db.load_access().await.unwrap();
+        assert_eq!(access.len(), 1);
+        assert_eq!(access[0].validator_id, validator);
+
+        let keys = db.list_ssh_keys().await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].public_key, "ssh-ed25519 AAAA validator");
+
+        let challenges = db.load_challenges().await.unwrap();
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0].challenge_id, challenge.challenge_id);
+        assert_eq!(challenges[0].challenge_data, challenge.challenge_data);
+
+        db.remove_container("abc123").await.unwrap();
+        db.delete_access("5Validator").await.unwrap();
+        db.delete_ssh_keys("validator_abc").await.unwrap();
+        db.delete_challenge(&challenge.challenge_id).await.unwrap();
+        assert!(db.list_containers().await.unwrap().is_empty());
+        assert!(db.get_container("abc123").await.unwrap().is_none());
+        assert!(db.load_access().await.unwrap().is_empty());
+        assert!(db.list_ssh_keys().await.unwrap().is_empty());
+        assert!(db.load_challenges().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_volume_names_are_unique_per_owner() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+
+        let volume = |volume_id: &str, owner: &str| VolumeRecord {
+            volume_id: volume_id.to_string(),
+            name: "checkpoints".to_string(),
+            owner_hotkey: owner.to_string(),
+            rental_id: Some("rental-1".to_string()),
+            quota_bytes: 1024,
+            usage_bytes: 0,
+            source_volume: None,
+            created_at: Utc::now(),
+            last_active_at: Utc::now(),
+        };
+
+        db.record_volume(&volume("basilica-vol-1", "5Alice"))
+            .await
+            .unwrap();
+        db.record_volume(&volume("basilica-vol-2", "5Bob"))
+            .await
+            .unwrap();
+        assert!(db
+            .record_volume(&volume("basilica-vol-3", "5Alice"))
+            .await
+            .is_err());
+
+        db.update_volume_usage("basilica-vol-1", 512).await.unwrap();
+        let stored = db
+            .get_volume("5Alice", "checkpoints")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.volume_id, "basilica-vol-1");
+        assert_eq!(stored.usage_bytes, 512);
+        assert_eq!(stored.rental_id.as_deref(), Some("rental-1"));
+
+        assert_eq!(db.list_volumes(Some("5Bob")).await.unwrap().len(), 1);
+        assert_eq!(db.list_volumes(None).await.unwrap().len(), 2);
+
+        db.remove_volume("basilica-vol-1").await.unwrap();
+        assert!(db
+            .get_volume("5Alice", "checkpoints")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,63 @@
+//! Persistent executor signing key
+//!
+//! The executor signs system profile and benchmark responses with an Ed25519
+//! key that survives restarts, so validators can recognise the same machine
+//! across verification rounds.
+
+use anyhow::{Context, Result};
+use common::crypto::Ed25519PrivateKey;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tracing::info;
+
+/// Load the signing key at `path`, generating and saving a new one if it does not exist
+pub fn load_or_generate(path: &Path) -> Result<Ed25519PrivateKey> {
+    if path.exists() {
+        let pem = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read signing key {}", path.display()))?;
+        let key = Ed25519PrivateKey::from_pem(&pem)
+            .with_context(|| format!("Invalid signing key {}", path.display()))?;
+        info!(
+            "Loaded executor signing key {} from {}",
+            key.public_key(),
+            path.display()
+        );
+        return Ok(key);
+    }
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    let key = Ed25519PrivateKey::generate();
+    std::fs::write(path, key.to_pem())
+        .with_context(|| format!("Failed to write signing key {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("Failed to restrict permissions of {}", path.display()))?;
+
+    info!(
+        "Generated executor signing key {} at {}",
+        key.public_key(),
+        path.display()
+    );
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_key_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys").join("executor_signing_key.pem");
+
+        let generated = load_or_generate(&path).unwrap();
+        let loaded = load_or_generate(&path).unwrap();
+        assert_eq!(generated.public_key(), loaded.public_key());
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
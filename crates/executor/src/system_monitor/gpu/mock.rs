@@ -0,0 +1,39 @@
+//! Fixture-driven GPU backend for hosts without GPUs
+
+use super::GpuBackend;
+use crate::system_monitor::types::GpuInfo;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Reports a fixed list of GPUs
+#[derive(Debug, Clone, Default)]
+pub struct MockGpuBackend {
+    gpus: Vec<GpuInfo>,
+}
+
+impl MockGpuBackend {
+    /// Create new mock backend reporting the given GPUs
+    pub fn new(gpus: Vec<GpuInfo>) -> Self {
+        Self { gpus }
+    }
+
+    /// Load the GPUs to report from a JSON array of `GpuInfo`
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read GPU fixture {}", path.display()))?;
+        let gpus = serde_json::from_str(&json)
+            .with_context(|| format!("Invalid GPU fixture {}", path.display()))?;
+        Ok(Self::new(gpus))
+    }
+}
+
+#[async_trait::async_trait]
+impl GpuBackend for MockGpuBackend {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn get_gpu_info(&self) -> Result<Vec<GpuInfo>> {
+        Ok(self.gpus.clone())
+    }
+}
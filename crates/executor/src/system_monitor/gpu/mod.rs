@@ -0,0 +1,152 @@
+//! GPU monitoring functionality
+//!
+//! GPU information comes from a [`GpuBackend`] chosen by configuration:
+//! NVML for NVIDIA hosts, `rocm-smi` for AMD hosts, or a fixture-driven mock
+//! so GPU code paths can be exercised without GPUs.
+
+pub mod mock;
+pub mod nvml;
+pub mod rocm;
+
+use super::types::GpuInfo;
+use crate::config::{GpuBackendConfig, GpuBackendKind};
+use anyhow::{anyhow, Result};
+use mock::MockGpuBackend;
+use nvml::NvmlBackend;
+use rocm::RocmSmiBackend;
+use std::sync::Arc;
+use tracing::info;
+
+/// Source of GPU information
+#[async_trait::async_trait]
+pub trait GpuBackend: Send + Sync + std::fmt::Debug {
+    /// Short backend name for logs
+    fn name(&self) -> &'static str;
+
+    /// Current state of every GPU the backend can see
+    async fn get_gpu_info(&self) -> Result<Vec<GpuInfo>>;
+}
+
+/// GPU monitoring handler
+#[derive(Debug, Clone)]
+pub struct GpuMonitor {
+    backend: Arc<dyn GpuBackend>,
+}
+
+impl GpuMonitor {
+    /// Create new GPU monitor using NVML
+    pub fn new() -> Self {
+        Self::with_backend(Arc::new(NvmlBackend::new()))
+    }
+
+    /// Create GPU monitor using the given backend
+    pub fn with_backend(backend: Arc<dyn GpuBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// Create GPU monitor using the configured backend
+    pub fn from_config(config: &GpuBackendConfig) -> Result<Self> {
+        let backend: Arc<dyn GpuBackend> = match config.backend {
+            GpuBackendKind::Nvml => Arc::new(NvmlBackend::new()),
+            GpuBackendKind::Rocm => Arc::new(RocmSmiBackend::new(&config.rocm_smi_path)),
+            GpuBackendKind::Mock => {
+                let path = config
+                    .fixture_path
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("Mock GPU backend requires a fixture path"))?;
+                Arc::new(MockGpuBackend::from_file(path)?)
+            }
+            GpuBackendKind::Auto => {
+                if !NvmlBackend::is_available()
+                    && RocmSmiBackend::is_available(&config.rocm_smi_path)
+                {
+                    Arc::new(RocmSmiBackend::new(&config.rocm_smi_path))
+                } else {
+                    Arc::new(NvmlBackend::new())
+                }
+            }
+        };
+
+        info!("Using {} GPU backend", backend.name());
+        Ok(Self::with_backend(backend))
+    }
+
+    /// Name of the backend in use
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Get GPU information from the backend
+    pub async fn get_gpu_info(&self) -> Result<Vec<GpuInfo>> {
+        self.backend.get_gpu_info().await
+    }
+}
+
+impl Default for GpuMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn gpu(index: u32) -> GpuInfo {
+        GpuInfo {
+            index,
+            name: "NVIDIA H100 80GB HBM3".to_string(),
+            memory_total_bytes: 80 * 1024 * 1024 * 1024,
+            memory_used_bytes: 0,
+            memory_usage_percent: 0.0,
+            utilization_percent: 0.0,
+            temperature_celsius: 30.0,
+            power_usage_watts: 70.0,
+            driver_version: "550.54.15".to_string(),
+            cuda_version: Some("12.4".to_string()),
+            uuid: format!("GPU-{index}"),
+            vendor: "NVIDIA".to_string(),
+            compute_capability: Some("9.0".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_backend_from_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = dir.path().join("gpus.json");
+        std::fs::write(&fixture, serde_json::to_string(&[gpu(0), gpu(1)]).unwrap()).unwrap();
+
+        let monitor = GpuMonitor::from_config(&GpuBackendConfig {
+            backend: GpuBackendKind::Mock,
+            fixture_path: Some(fixture),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(monitor.backend_name(), "mock");
+
+        let gpus = monitor.get_gpu_info().await.unwrap();
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[1].uuid, "GPU-1");
+
+        assert!(GpuMonitor::from_config(&GpuBackendConfig {
+            backend: GpuBackendKind::Mock,
+            fixture_path: Some(PathBuf::from("/nonexistent/gpus.json")),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rocm_backend_reports_missing_binary() {
+        let monitor = GpuMonitor::from_config(&GpuBackendConfig {
+            backend: GpuBackendKind::Rocm,
+            rocm_smi_path: PathBuf::from("/nonexistent/rocm-smi"),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(monitor.backend_name(), "rocm");
+        assert!(monitor.get_gpu_info().await.is_err());
+    }
+}
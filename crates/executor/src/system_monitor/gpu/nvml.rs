@@ -1,64 +1,34 @@
-//! GPU monitoring functionality
+//! NVIDIA GPU backend using NVML
 
-use super::types::GpuInfo;
+use super::GpuBackend;
+use crate::system_monitor::types::GpuInfo;
 use anyhow::{Context, Result};
+use nvml_wrapper::Nvml;
 use tracing::{debug, info, warn};
 
-/// GPU monitoring handler
-#[derive(Debug)]
-pub struct GpuMonitor;
+/// Reads NVIDIA GPUs through the NVIDIA Management Library
+#[derive(Debug, Default)]
+pub struct NvmlBackend;
 
-impl GpuMonitor {
-    /// Create new GPU monitor
+impl NvmlBackend {
+    /// Create new NVML backend
     pub fn new() -> Self {
         Self
     }
 
-    /// Get GPU information using NVIDIA ML
-    pub async fn get_gpu_info(&self) -> Result<Vec<GpuInfo>> {
-        debug!("Starting GPU detection with NVML...");
-        let mut gpus = Vec::new();
-
-        match self.get_nvidia_device_count() {
-            Ok(device_count) => {
-                info!("NVML detected {} NVIDIA GPU(s)", device_count);
-                for i in 0..device_count {
-                    match self.get_nvidia_gpu_info(i).await {
-                        Ok(gpu_info) => {
-                            debug!("Successfully got NVML info for GPU {}", i);
-                            gpus.push(gpu_info);
-                        }
-                        Err(e) => warn!("Failed to get NVML info for GPU {}: {}", i, e),
-                    }
-                }
-            }
-            Err(e) => {
-                info!("NVML unavailable: {}", e);
-                debug!("This is normal in environments without NVIDIA driver access (like some containers or WSL setups)");
-            }
-        }
-
-        debug!("GPU detection completed, found {} GPUs", gpus.len());
-        Ok(gpus)
+    /// Whether NVML can be initialized on this host
+    pub fn is_available() -> bool {
+        Nvml::init().is_ok()
     }
 
     /// Get NVIDIA device count using NVML
-    fn get_nvidia_device_count(&self) -> Result<u32> {
-        use nvml_wrapper::Nvml;
-
-        debug!("Attempting to initialize NVML...");
-        let nvml = Nvml::init().context("Failed to initialize NVML")?;
-        debug!("NVML initialized successfully");
-
+    fn get_nvidia_device_count(&self, nvml: &Nvml) -> Result<u32> {
         let device_count = nvml.device_count().context("Failed to get device count")?;
         debug!("NVML reported {} devices", device_count);
         Ok(device_count)
     }
 
-    async fn get_nvidia_gpu_info(&self, index: u32) -> Result<GpuInfo> {
-        use nvml_wrapper::Nvml;
-
-        let nvml = Nvml::init().context("Failed to initialize NVML")?;
+    fn get_nvidia_gpu_info(&self, nvml: &Nvml, index: u32) -> Result<GpuInfo> {
         let device = nvml
             .device_by_index(index)
             .context("Failed to get device by index")?;
@@ -91,6 +61,11 @@ impl GpuMonitor {
             .ok()
             .map(|v| format!("{}.{}", v / 1000, (v % 1000) / 10));
 
+        let compute_capability = device
+            .cuda_compute_capability()
+            .ok()
+            .map(|cc| format!("{}.{}", cc.major, cc.minor));
+
         let memory_usage_percent = if memory_info.total > 0 {
             (memory_info.used as f32 / memory_info.total as f32) * 100.0
         } else {
@@ -108,12 +83,49 @@ impl GpuMonitor {
             power_usage_watts: power_usage,
             driver_version,
             cuda_version,
+            uuid: device.uuid().unwrap_or_default(),
+            vendor: "NVIDIA".to_string(),
+            compute_capability,
         })
     }
 }
 
-impl Default for GpuMonitor {
-    fn default() -> Self {
-        Self::new()
+#[async_trait::async_trait]
+impl GpuBackend for NvmlBackend {
+    fn name(&self) -> &'static str {
+        "nvml"
+    }
+
+    async fn get_gpu_info(&self) -> Result<Vec<GpuInfo>> {
+        debug!("Starting GPU detection with NVML...");
+        let mut gpus = Vec::new();
+
+        let nvml = match Nvml::init() {
+            Ok(nvml) => nvml,
+            Err(e) => {
+                info!("NVML unavailable: {}", e);
+                debug!("This is normal in environments without NVIDIA driver access (like some containers or WSL setups)");
+                return Ok(gpus);
+            }
+        };
+
+        match self.get_nvidia_device_count(&nvml) {
+            Ok(device_count) => {
+                info!("NVML detected {} NVIDIA GPU(s)", device_count);
+                for i in 0..device_count {
+                    match self.get_nvidia_gpu_info(&nvml, i) {
+                        Ok(gpu_info) => {
+                            debug!("Successfully got NVML info for GPU {}", i);
+                            gpus.push(gpu_info);
+                        }
+                        Err(e) => warn!("Failed to get NVML info for GPU {}: {}", i, e),
+                    }
+                }
+            }
+            Err(e) => warn!("NVML device enumeration failed: {}", e),
+        }
+
+        debug!("GPU detection completed, found {} GPUs", gpus.len());
+        Ok(gpus)
     }
 }
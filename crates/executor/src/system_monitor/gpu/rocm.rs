@@ -0,0 +1,225 @@
+//! AMD GPU backend using `rocm-smi --json`
+//!
+//! `rocm-smi` reports one object per card (`card0`, `card1`, ...) plus a
+//! `system` object, with every value as a string. Key names differ between
+//! ROCm releases, so power and temperature are matched by prefix.
+
+use super::GpuBackend;
+use crate::system_monitor::types::GpuInfo;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tracing::debug;
+
+/// Arguments selecting every field the backend reads
+const ROCM_SMI_ARGS: &[&str] = &[
+    "--showproductname",
+    "--showmeminfo",
+    "vram",
+    "--showuse",
+    "--showtemp",
+    "--showpower",
+    "--showdriverversion",
+    "--showuniqueid",
+    "--json",
+];
+
+/// Reads AMD GPUs by running `rocm-smi`
+#[derive(Debug, Clone)]
+pub struct RocmSmiBackend {
+    rocm_smi_path: PathBuf,
+}
+
+impl RocmSmiBackend {
+    /// Create new ROCm-SMI backend running the given binary
+    pub fn new(rocm_smi_path: impl Into<PathBuf>) -> Self {
+        Self {
+            rocm_smi_path: rocm_smi_path.into(),
+        }
+    }
+
+    /// Whether the `rocm-smi` binary runs on this host
+    pub fn is_available(rocm_smi_path: &Path) -> bool {
+        std::process::Command::new(rocm_smi_path)
+            .arg("--version")
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+}
+
+#[async_trait::async_trait]
+impl GpuBackend for RocmSmiBackend {
+    fn name(&self) -> &'static str {
+        "rocm"
+    }
+
+    async fn get_gpu_info(&self) -> Result<Vec<GpuInfo>> {
+        debug!(
+            "Starting GPU detection with {}",
+            self.rocm_smi_path.display()
+        );
+
+        let output = Command::new(&self.rocm_smi_path)
+            .args(ROCM_SMI_ARGS)
+            .output()
+            .await
+            .with_context(|| format!("Failed to run {}", self.rocm_smi_path.display()))?;
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "rocm-smi exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let gpus = parse_rocm_smi_json(&String::from_utf8_lossy(&output.stdout))?;
+        debug!("rocm-smi reported {} GPUs", gpus.len());
+        Ok(gpus)
+    }
+}
+
+/// Parse the output of `rocm-smi --json`, ordered by card index
+pub fn parse_rocm_smi_json(json: &str) -> Result<Vec<GpuInfo>> {
+    let root: Map<String, Value> =
+        serde_json::from_str(json).context("rocm-smi output is not a JSON object")?;
+
+    let driver_version = root
+        .get("system")
+        .and_then(Value::as_object)
+        .and_then(|system| text(system, "Driver version"))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let mut gpus: Vec<GpuInfo> = root
+        .iter()
+        .filter_map(|(key, card)| {
+            let index = key.strip_prefix("card")?.parse::<u32>().ok()?;
+            Some((index, card.as_object()?))
+        })
+        .map(|(index, card)| parse_card(index, card, &driver_version))
+        .collect();
+    gpus.sort_by_key(|gpu| gpu.index);
+
+    Ok(gpus)
+}
+
+fn parse_card(index: u32, card: &Map<String, Value>, driver_version: &str) -> GpuInfo {
+    let name = text(card, "Card series")
+        .or_else(|| text(card, "Card model"))
+        .unwrap_or_else(|| format!("Unknown GPU {index}"));
+
+    let memory_total_bytes = number(card, "VRAM Total Memory (B)").unwrap_or(0.0) as u64;
+    let memory_used_bytes = number(card, "VRAM Total Used Memory (B)").unwrap_or(0.0) as u64;
+    let memory_usage_percent = if memory_total_bytes > 0 {
+        (memory_used_bytes as f32 / memory_total_bytes as f32) * 100.0
+    } else {
+        0.0
+    };
+
+    GpuInfo {
+        index,
+        name,
+        memory_total_bytes,
+        memory_used_bytes,
+        memory_usage_percent,
+        utilization_percent: number(card, "GPU use (%)").unwrap_or(0.0) as f32,
+        temperature_celsius: number_with_prefix(card, "Temperature (Sensor edge)")
+            .or_else(|| number_with_prefix(card, "Temperature (Sensor junction)"))
+            .unwrap_or(0.0) as f32,
+        power_usage_watts: number_with_prefix(card, "Average Graphics Package Power")
+            .or_else(|| number_with_prefix(card, "Current Socket Graphics Package Power"))
+            .unwrap_or(0.0) as f32,
+        driver_version: driver_version.to_string(),
+        cuda_version: None,
+        uuid: text(card, "Unique ID").unwrap_or_default(),
+        vendor: "AMD".to_string(),
+        compute_capability: text(card, "GFX Version"),
+    }
+}
+
+fn text(object: &Map<String, Value>, key: &str) -> Option<String> {
+    match object.get(key)? {
+        Value::String(s) if !s.trim().is_empty() && s != "N/A" => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn number(object: &Map<String, Value>, key: &str) -> Option<f64> {
+    to_number(object.get(key)?)
+}
+
+fn number_with_prefix(object: &Map<String, Value>, prefix: &str) -> Option<f64> {
+    object
+        .iter()
+        .find(|(key, _)| key.starts_with(prefix))
+        .and_then(|(_, value)| to_number(value))
+}
+
+fn to_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MI210_OUTPUT: &str = r#"{
+        "card1": {
+            "Unique ID": "0x2a3b4c5d6e7f8091",
+            "Temperature (Sensor edge) (C)": "41.0",
+            "Temperature (Sensor junction) (C)": "44.0",
+            "Current Socket Graphics Package Power (W)": "88.0",
+            "GPU use (%)": "97",
+            "VRAM Total Memory (B)": "68702699520",
+            "VRAM Total Used Memory (B)": "34351349760",
+            "Card series": "AMD Instinct MI210",
+            "Card model": "0x740f",
+            "GFX Version": "gfx90a"
+        },
+        "card0": {
+            "Unique ID": "0x1a2b3c4d5e6f7081",
+            "Temperature (Sensor edge) (C)": "35.0",
+            "Average Graphics Package Power (W)": "42.0",
+            "GPU use (%)": "0",
+            "VRAM Total Memory (B)": "68702699520",
+            "VRAM Total Used Memory (B)": "10960896",
+            "Card series": "AMD Instinct MI210",
+            "GFX Version": "gfx90a"
+        },
+        "system": {
+            "Driver version": "6.2.4"
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_rocm_smi_json() {
+        let gpus = parse_rocm_smi_json(MI210_OUTPUT).unwrap();
+        assert_eq!(gpus.len(), 2);
+
+        assert_eq!(gpus[0].index, 0);
+        assert_eq!(gpus[0].name, "AMD Instinct MI210");
+        assert_eq!(gpus[0].power_usage_watts, 42.0);
+        assert_eq!(gpus[0].driver_version, "6.2.4");
+        assert_eq!(gpus[0].vendor, "AMD");
+        assert_eq!(gpus[0].compute_capability.as_deref(), Some("gfx90a"));
+
+        assert_eq!(gpus[1].index, 1);
+        assert_eq!(gpus[1].uuid, "0x2a3b4c5d6e7f8091");
+        assert_eq!(gpus[1].memory_total_bytes, 68_702_699_520);
+        assert_eq!(gpus[1].memory_usage_percent, 50.0);
+        assert_eq!(gpus[1].utilization_percent, 97.0);
+        assert_eq!(gpus[1].temperature_celsius, 41.0);
+        assert_eq!(gpus[1].power_usage_watts, 88.0);
+        assert_eq!(gpus[1].cuda_version, None);
+
+        assert!(parse_rocm_smi_json("{}").unwrap().is_empty());
+        assert!(parse_rocm_smi_json("not json").is_err());
+    }
+}
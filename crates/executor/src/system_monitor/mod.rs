@@ -1,6 +1,7 @@
 //! System monitoring for the Basilca Executor
 //!
 //! Monitors system resources including CPU, memory, GPU, disk, and network.
+//! GPU information comes from the backend selected in `SystemConfig::gpu`.
 
 pub mod cpu;
 pub mod disk;
@@ -50,7 +51,7 @@ impl SystemMonitor {
             system,
             cpu_monitor: CpuMonitor::new(),
             memory_monitor: MemoryMonitor::new(),
-            gpu_monitor: GpuMonitor::new(),
+            gpu_monitor: GpuMonitor::from_config(&config.gpu)?,
             disk_monitor: DiskMonitor::new(),
             network_monitor: NetworkMonitor::new(),
             metrics_recorder: None,
@@ -238,7 +239,7 @@ impl SystemMonitor {
         let cpu = self.cpu_monitor.get_cpu_info(&self.system)?;
         let memory = self.memory_monitor.get_memory_info(&self.system)?;
         let gpu = if self.config.enable_gpu_monitoring {
-            self.gpu_monitor.get_gpu_info().await?
+            self.collect_gpu_info().await
         } else {
             vec![]
         };
@@ -265,6 +266,21 @@ impl SystemMonitor {
         })
     }
 
+    /// Get GPU information, reporting no GPUs if the backend fails
+    pub async fn collect_gpu_info(&self) -> Vec<GpuInfo> {
+        match self.gpu_monitor.get_gpu_info().await {
+            Ok(gpus) => gpus,
+            Err(e) => {
+                warn!(
+                    "{} GPU backend failed: {}",
+                    self.gpu_monitor.backend_name(),
+                    e
+                );
+                vec![]
+            }
+        }
+    }
+
     /// Get basic system information
     fn get_basic_system_info(&self) -> Result<BasicSystemInfo> {
         Ok(BasicSystemInfo {
@@ -477,7 +493,7 @@ impl SystemMetricsProvider for SystemMonitor {
             return Ok(None);
         }
 
-        let gpu_info = self.gpu_monitor.get_gpu_info().await?;
+        let gpu_info = self.collect_gpu_info().await;
         if gpu_info.is_empty() {
             return Ok(None);
         }
@@ -49,6 +49,15 @@ pub struct GpuInfo {
     pub power_usage_watts: f32,
     pub driver_version: String,
     pub cuda_version: Option<String>,
+    /// Vendor-assigned unique identifier
+    #[serde(default)]
+    pub uuid: String,
+    /// GPU vendor, e.g. `NVIDIA` or `AMD`
+    #[serde(default)]
+    pub vendor: String,
+    /// CUDA compute capability or AMD GFX target
+    #[serde(default)]
+    pub compute_capability: Option<String>,
 }
 
 /// Disk information
@@ -3,11 +3,11 @@ use super::rate_limiter::{RateLimitStatus, RequestType, ValidatorRateLimiter};
 use super::types::{
     AccessControlConfig, SessionStats, ValidatorAccess, ValidatorId, ValidatorRole,
 };
+use crate::persistence::ExecutorDb;
 use anyhow::Result;
 use common::journal::{log_security_violation, SecuritySeverity};
 use std::collections::HashMap;
 use std::net::IpAddr;
-use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::RwLock;
@@ -16,80 +16,69 @@ use tracing::{debug, info, warn};
 pub struct ValidatorAccessControl {
     pub config: AccessControlConfig,
     active_sessions: Arc<RwLock<HashMap<String, ValidatorAccess>>>,
-    storage_path: PathBuf,
+    db: Option<ExecutorDb>,
     hotkey_verifier: HotkeySignatureVerifier,
     rate_limiter: ValidatorRateLimiter,
 }
 
 impl ValidatorAccessControl {
+    /// Create access control that keeps grants in memory only
     pub fn new(config: AccessControlConfig) -> Self {
-        let storage_path = PathBuf::from("/var/lib/basilica/validator_access.json");
-
-        // Initialize hotkey verifier and rate limiter
         let hotkey_verifier = HotkeySignatureVerifier::new(config.hotkey_verification.clone());
         let rate_limiter = ValidatorRateLimiter::new(config.rate_limits.clone());
 
-        let instance = Self {
+        Self {
             config,
             active_sessions: Arc::new(RwLock::new(HashMap::new())),
-            storage_path,
+            db: None,
             hotkey_verifier,
             rate_limiter,
-        };
-
-        // Load existing data on startup (synchronously)
-        if let Err(e) = instance.load_from_storage_sync() {
-            warn!("Failed to load validator access from storage: {}", e);
         }
-
-        instance
     }
 
-    fn load_from_storage_sync(&self) -> Result<()> {
-        if !self.storage_path.exists() {
-            return Ok(());
-        }
-
-        let content = std::fs::read_to_string(&self.storage_path)?;
-        let sessions: HashMap<String, ValidatorAccess> = serde_json::from_str(&content)?;
+    /// Create access control backed by the executor database, restoring unexpired grants
+    pub async fn with_db(config: AccessControlConfig, db: ExecutorDb) -> Result<Self> {
+        let hotkey_verifier =
+            HotkeySignatureVerifier::with_db(config.hotkey_verification.clone(), db.clone())
+                .await?;
+        let rate_limiter = ValidatorRateLimiter::new(config.rate_limits.clone());
 
-        // Filter out expired sessions during load
-        let now = SystemTime::now();
-        let active_sessions: HashMap<String, ValidatorAccess> = sessions
+        let sessions: HashMap<String, ValidatorAccess> = db
+            .load_access()
+            .await?
             .into_iter()
-            .filter(|(_, access)| !access.is_expired(now))
+            .map(|access| (access.validator_id.hotkey.clone(), access))
             .collect();
-
-        if !active_sessions.is_empty() {
+        if !sessions.is_empty() {
             info!(
                 "Loaded {} active validator sessions from storage",
-                active_sessions.len()
+                sessions.len()
             );
-            // Use try_write in sync context during initialization
-            match self.active_sessions.try_write() {
-                Ok(mut sessions_guard) => {
-                    *sessions_guard = active_sessions;
-                }
-                Err(_) => {
-                    warn!("Failed to acquire write lock during storage load");
-                }
-            }
         }
 
-        Ok(())
+        Ok(Self {
+            config,
+            active_sessions: Arc::new(RwLock::new(sessions)),
+            db: Some(db),
+            hotkey_verifier,
+            rate_limiter,
+        })
     }
 
-    async fn save_to_storage(&self) -> Result<()> {
-        let sessions = self.active_sessions.read().await;
-        let content = serde_json::to_string_pretty(&*sessions)?;
-
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = self.storage_path.parent() {
-            std::fs::create_dir_all(parent)?;
+    async fn save_access(&self, access: &ValidatorAccess) {
+        if let Some(db) = &self.db {
+            if let Err(e) = db.save_access(access).await {
+                warn!("Failed to save validator access to storage: {}", e);
+            }
         }
+    }
 
-        std::fs::write(&self.storage_path, content)?;
-        Ok(())
+    async fn delete_access(&self, validator_id: &ValidatorId) {
+        if let Some(db) = &self.db {
+            if let Err(e) = db.delete_access(&validator_id.hotkey).await {
+                warn!("Failed to delete validator access from storage: {}", e);
+            }
+        }
     }
 
     pub async fn grant_access(&self, access: &ValidatorAccess) -> Result<()> {
@@ -101,9 +90,7 @@ impl ValidatorAccessControl {
         }
 
         // Save to persistent storage
-        if let Err(e) = self.save_to_storage().await {
-            warn!("Failed to save validator access to storage: {}", e);
-        }
+        self.save_access(access).await;
 
         info!(
             "Access granted successfully to validator: {}",
@@ -121,10 +108,8 @@ impl ValidatorAccessControl {
         };
 
         if removed.is_some() {
-            // Save to persistent storage
-            if let Err(e) = self.save_to_storage().await {
-                warn!("Failed to save validator access to storage: {}", e);
-            }
+            // Remove from persistent storage
+            self.delete_access(validator_id).await;
 
             info!(
                 "Access revoked successfully for validator: {}",
@@ -390,6 +375,26 @@ impl ValidatorAccessControl {
         Ok(challenge)
     }
 
+    /// Verify a request signed with a validator's hotkey
+    pub async fn verify_request_signature(
+        &self,
+        validator_id: &ValidatorId,
+        canonical: &str,
+        signature_hex: &str,
+        timestamp: i64,
+        nonce: &str,
+    ) -> bool {
+        self.hotkey_verifier
+            .verify_request(
+                &validator_id.hotkey,
+                canonical,
+                signature_hex,
+                timestamp,
+                nonce,
+            )
+            .await
+    }
+
     /// Verify hotkey signature challenge response
     pub async fn verify_hotkey_signature(
         &self,
@@ -437,13 +442,11 @@ impl ValidatorAccessControl {
 
         {
             let mut sessions = self.active_sessions.write().await;
-            sessions.insert(validator_id.hotkey.clone(), access);
+            sessions.insert(validator_id.hotkey.clone(), access.clone());
         }
 
         // Save to persistent storage
-        if let Err(e) = self.save_to_storage().await {
-            warn!("Failed to save validator access to storage: {}", e);
-        }
+        self.save_access(&access).await;
 
         info!("Access granted successfully to validator: {}", validator_id);
         Ok(())
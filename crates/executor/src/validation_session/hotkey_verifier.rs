@@ -4,7 +4,9 @@
 //! providing a secure challenge-response authentication mechanism that replaces simple SSH-based
 //! access control.
 
+use crate::persistence::ExecutorDb;
 use anyhow::{Context, Result};
+use common::crypto::request_signing::{verify_request_signature, SignatureScheme};
 use common::crypto::P256PublicKey;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -91,6 +93,9 @@ pub struct HotkeySignatureVerifier {
     config: HotkeyVerificationConfig,
     active_challenges: RwLock<HashMap<String, SignatureChallenge>>,
     verification_attempts: RwLock<HashMap<String, VerificationAttempt>>,
+    /// Nonces of accepted signed requests per hotkey, with their timestamp
+    seen_nonces: RwLock<HashMap<(String, String), i64>>,
+    db: Option<ExecutorDb>,
 }
 
 impl HotkeySignatureVerifier {
@@ -100,9 +105,35 @@ impl HotkeySignatureVerifier {
             config,
             active_challenges: RwLock::new(HashMap::new()),
             verification_attempts: RwLock::new(HashMap::new()),
+            seen_nonces: RwLock::new(HashMap::new()),
+            db: None,
         }
     }
 
+    /// Create a verifier that persists challenges, restoring those still outstanding
+    pub async fn with_db(config: HotkeyVerificationConfig, db: ExecutorDb) -> Result<Self> {
+        let challenges: HashMap<String, SignatureChallenge> = db
+            .load_challenges()
+            .await?
+            .into_iter()
+            .map(|challenge| (challenge.challenge_id.clone(), challenge))
+            .collect();
+        if !challenges.is_empty() {
+            info!(
+                "Restored {} outstanding signature challenges",
+                challenges.len()
+            );
+        }
+
+        Ok(Self {
+            config,
+            active_challenges: RwLock::new(challenges),
+            verification_attempts: RwLock::new(HashMap::new()),
+            seen_nonces: RwLock::new(HashMap::new()),
+            db: Some(db),
+        })
+    }
+
     /// Generate a new signature challenge for a validator hotkey
     pub async fn generate_challenge(&self, validator_hotkey: &str) -> Result<SignatureChallenge> {
         if !self.config.enabled {
@@ -121,6 +152,9 @@ impl HotkeySignatureVerifier {
         let challenge = SignatureChallenge::new(validator_hotkey.to_string(), timeout);
 
         // Store the challenge
+        if let Some(db) = &self.db {
+            db.save_challenge(&challenge).await?;
+        }
         let mut challenges = self.active_challenges.write().await;
         challenges.insert(challenge.challenge_id.clone(), challenge.clone());
 
@@ -202,6 +236,61 @@ impl HotkeySignatureVerifier {
         }
     }
 
+    /// Verify a request signed with a validator's sr25519 hotkey
+    ///
+    /// `canonical` must include `timestamp` and `nonce`. The timestamp may be
+    /// off by at most the challenge timeout and each nonce is accepted once.
+    pub async fn verify_request(
+        &self,
+        validator_hotkey: &str,
+        canonical: &str,
+        signature_hex: &str,
+        timestamp: i64,
+        nonce: &str,
+    ) -> bool {
+        if !self.config.enabled {
+            return true; // Allow if verification is disabled
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let window = self.config.challenge_timeout_seconds as i64;
+        if (timestamp - now).abs() > window {
+            warn!(
+                "Rejected request from {} signed at {} (now {})",
+                validator_hotkey, timestamp, now
+            );
+            return false;
+        }
+
+        if let Err(e) = verify_request_signature(
+            SignatureScheme::Sr25519,
+            validator_hotkey,
+            canonical,
+            signature_hex,
+        ) {
+            warn!(
+                "Rejected request with invalid signature from {}: {}",
+                validator_hotkey, e
+            );
+            return false;
+        }
+
+        // Only record the nonce once the signature is known to be genuine
+        let mut seen = self.seen_nonces.write().await;
+        seen.retain(|_, signed_at| *signed_at >= now - window);
+        if seen
+            .insert((validator_hotkey.to_string(), nonce.to_string()), timestamp)
+            .is_some()
+        {
+            warn!(
+                "Rejected replayed request from {} (nonce {})",
+                validator_hotkey, nonce
+            );
+            return false;
+        }
+        true
+    }
+
     /// Verify P256 ECDSA signature
     async fn verify_p256_signature(
         &self,
@@ -269,8 +358,17 @@ impl HotkeySignatureVerifier {
 
     /// Remove a challenge from active challenges
     async fn remove_challenge(&self, challenge_id: &str) {
-        let mut challenges = self.active_challenges.write().await;
-        challenges.remove(challenge_id);
+        self.active_challenges.write().await.remove(challenge_id);
+        self.forget_challenge(challenge_id).await;
+    }
+
+    /// Delete a challenge from the database, if persisting
+    async fn forget_challenge(&self, challenge_id: &str) {
+        if let Some(db) = &self.db {
+            if let Err(e) = db.delete_challenge(challenge_id).await {
+                warn!("Failed to delete challenge {}: {}", challenge_id, e);
+            }
+        }
     }
 
     /// Clean up expired challenges and attempt records
@@ -279,7 +377,7 @@ impl HotkeySignatureVerifier {
         let now = SystemTime::now();
 
         // Clean up expired challenges
-        {
+        let expired_ids: Vec<String> = {
             let mut challenges = self.active_challenges.write().await;
             let expired_ids: Vec<String> = challenges
                 .iter()
@@ -287,10 +385,14 @@ impl HotkeySignatureVerifier {
                 .map(|(id, _)| id.clone())
                 .collect();
 
-            for id in expired_ids {
-                challenges.remove(&id);
+            for id in &expired_ids {
+                challenges.remove(id);
                 cleaned += 1;
             }
+            expired_ids
+        };
+        for id in &expired_ids {
+            self.forget_challenge(id).await;
         }
 
         // Clean up old verification attempts (older than 1 hour)
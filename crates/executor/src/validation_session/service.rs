@@ -1,9 +1,11 @@
 use super::access_control::ValidatorAccessControl;
 use super::types::{ValidatorAccess, ValidatorConfig, ValidatorId};
+use crate::persistence::ExecutorDb;
 use anyhow::Result;
 use common::journal::{log_validator_access_granted, log_validator_access_revoked};
 use common::ssh::{SimpleSshKeys, SimpleSshUsers};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
@@ -12,6 +14,7 @@ pub struct ValidationSessionService {
     access_control: Arc<ValidatorAccessControl>,
     #[allow(dead_code)]
     config: ValidatorConfig,
+    db: Option<ExecutorDb>,
 }
 
 impl ValidationSessionService {
@@ -21,6 +24,20 @@ impl ValidationSessionService {
         Ok(Self {
             access_control,
             config,
+            db: None,
+        })
+    }
+
+    /// Create a service backed by the executor database, restoring grants from the last run
+    pub async fn restore(config: ValidatorConfig, db: ExecutorDb) -> Result<Self> {
+        let access_control = Arc::new(
+            ValidatorAccessControl::with_db(config.access_config.clone(), db.clone()).await?,
+        );
+
+        Ok(Self {
+            access_control,
+            config,
+            db: Some(db),
         })
     }
 
@@ -57,6 +74,11 @@ impl ValidationSessionService {
             info!("SSH key already exists for validator: {}", validator_id);
         }
 
+        if let Some(db) = &self.db {
+            db.record_ssh_key(&username, public_key, &validator_id.hotkey)
+                .await?;
+        }
+
         let access = ValidatorAccess::new(validator_id.clone(), public_key);
 
         self.access_control.grant_access(&access).await?;
@@ -76,6 +98,9 @@ impl ValidationSessionService {
         let username = SimpleSshUsers::validator_username(&validator_id.hotkey);
 
         SimpleSshKeys::remove_key(&username).await?;
+        if let Some(db) = &self.db {
+            db.delete_ssh_keys(&username).await?;
+        }
 
         self.access_control.revoke_access(validator_id).await?;
 
@@ -128,4 +153,125 @@ impl ValidationSessionService {
 
         Ok(cleaned)
     }
+
+    /// Remove SSH keys left from a previous run whose grant is gone
+    ///
+    /// Keys recorded in the database for validators without an active grant
+    /// are removed from authorized_keys, then grants whose key file has
+    /// disappeared are revoked. Returns the number of users whose keys were
+    /// removed.
+    pub async fn reconcile_ssh_access(&self) -> Result<u32> {
+        let Some(db) = &self.db else {
+            return Ok(0);
+        };
+
+        let active_hotkeys: HashSet<String> = self
+            .access_control
+            .list_active_access()
+            .await?
+            .into_iter()
+            .map(|access| access.validator_id.hotkey)
+            .collect();
+
+        let removed = remove_stale_ssh_keys(db, &active_hotkeys, |username| async move {
+            SimpleSshKeys::remove_key(&username).await
+        })
+        .await?;
+
+        self.cleanup_access().await?;
+
+        if removed > 0 {
+            info!(
+                "Removed SSH keys for {} users without active access",
+                removed
+            );
+        }
+
+        Ok(removed)
+    }
+}
+
+/// Remove the keys recorded for validators without an active grant
+///
+/// `remove_key` deletes the authorized_keys of a user. Returns the number of
+/// users whose keys were removed.
+async fn remove_stale_ssh_keys<F, Fut>(
+    db: &ExecutorDb,
+    active_hotkeys: &HashSet<String>,
+    remove_key: F,
+) -> Result<u32>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let stale_users: HashSet<String> = db
+        .list_ssh_keys()
+        .await?
+        .into_iter()
+        .filter(|key| !active_hotkeys.contains(&key.validator_hotkey))
+        .map(|key| key.username)
+        .collect();
+
+    let mut removed = 0;
+    for username in stale_users {
+        remove_key(username.clone()).await?;
+        db.delete_ssh_keys(&username).await?;
+        removed += 1;
+        warn!("Removed stale SSH keys for user: {}", username);
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::test_db;
+    use anyhow::Context;
+    use std::path::Path;
+
+    fn authorized_keys(home: &Path, username: &str) -> std::path::PathBuf {
+        home.join(username).join(".ssh").join("authorized_keys")
+    }
+
+    #[tokio::test]
+    async fn test_stale_ssh_keys_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+        let home = dir.path().join("home");
+
+        let active = SimpleSshUsers::validator_username("5Active");
+        let stale = SimpleSshUsers::validator_username("5Stale");
+        for (username, hotkey) in [(&active, "5Active"), (&stale, "5Stale")] {
+            let path = authorized_keys(&home, username);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, format!("ssh-ed25519 AAAA {hotkey}\n")).unwrap();
+            db.record_ssh_key(username, &format!("ssh-ed25519 AAAA {hotkey}"), hotkey)
+                .await
+                .unwrap();
+        }
+
+        let active_hotkeys = HashSet::from(["5Active".to_string()]);
+        let remove_key = |username: String| {
+            let path = authorized_keys(&home, &username);
+            async move { std::fs::remove_file(path).context("Failed to remove authorized_keys") }
+        };
+
+        let removed = remove_stale_ssh_keys(&db, &active_hotkeys, remove_key)
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert!(authorized_keys(&home, &active).exists());
+        assert!(!authorized_keys(&home, &stale).exists());
+
+        let keys = db.list_ssh_keys().await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].validator_hotkey, "5Active");
+
+        // Nothing is left to remove on the next start
+        let removed = remove_stale_ssh_keys(&db, &active_hotkeys, remove_key)
+            .await
+            .unwrap();
+        assert_eq!(removed, 0);
+    }
 }
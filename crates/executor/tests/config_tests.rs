@@ -317,6 +317,42 @@ fn test_network_config_validation() {
     assert!(config.validate_network_settings().is_ok());
 }
 
+#[test]
+fn test_network_mode_resolution() {
+    let mut config = ContainerNetworkConfig::default();
+
+    // Isolated unless the container publishes ports
+    assert_eq!(config.resolve_network_mode(None, false).unwrap(), "none");
+    assert_eq!(
+        config.resolve_network_mode(Some(""), false).unwrap(),
+        "none"
+    );
+    assert_eq!(config.resolve_network_mode(None, true).unwrap(), "bridge");
+    config.enable_isolation = false;
+    assert_eq!(config.resolve_network_mode(None, false).unwrap(), "default");
+
+    assert_eq!(
+        config.resolve_network_mode(Some("bridge"), false).unwrap(),
+        "bridge"
+    );
+    assert!(config.resolve_network_mode(Some("none"), true).is_err());
+    assert!(config.resolve_network_mode(Some("macvlan"), false).is_err());
+
+    // Sharing a network namespace is refused even when allowed
+    config.allowed_network_modes = vec!["host".to_string(), "container:abc".to_string()];
+    assert!(config.resolve_network_mode(Some("host"), false).is_err());
+    assert!(config
+        .resolve_network_mode(Some("container:abc"), false)
+        .is_err());
+
+    let mut docker = DockerConfig::default();
+    docker
+        .network_config
+        .allowed_network_modes
+        .push("host".to_string());
+    assert!(docker.validate_network_settings().is_err());
+}
+
 #[test]
 fn test_registry_validation() {
     let mut config = DockerConfig::default();
@@ -1,5 +1,6 @@
 //! Unit tests for gRPC server
 
+use common::crypto::evidence;
 use common::identity::Hotkey;
 use executor::grpc_server::executor_management::ExecutorManagementService;
 use executor::grpc_server::{ExecutorControlService, ExecutorServer};
@@ -75,6 +76,7 @@ async fn test_system_profile_endpoint() {
         key_mapping: HashMap::new(),
         profile_depth: "basic".to_string(),
         include_benchmarks: false,
+        nonce: "test_nonce".to_string(),
     });
 
     let response = service.execute_system_profile(request).await.unwrap();
@@ -83,6 +85,21 @@ async fn test_system_profile_endpoint() {
     assert!(!profile_response.encrypted_profile.is_empty());
     assert!(!profile_response.profile_hash.is_empty());
     assert!(profile_response.collected_at.is_some());
+    assert_eq!(profile_response.nonce, "test_nonce");
+
+    let message = evidence::system_profile_message(
+        "test_nonce",
+        "test_validator",
+        &profile_response.profile_hash,
+        &profile_response.encrypted_profile,
+        &profile_response.encryption_nonce,
+    );
+    assert!(evidence::verify_evidence(
+        &profile_response.executor_public_key,
+        &message,
+        &profile_response.signature
+    )
+    .is_ok());
 }
 
 #[tokio::test]
@@ -1,6 +1,6 @@
 //! Unit tests for system monitor
 
-use executor::config::SystemConfig;
+use executor::config::{GpuBackendConfig, GpuBackendKind, SystemConfig};
 use executor::system_monitor::{
     BasicSystemInfo, CpuInfo, DiskInfo, GpuInfo, MemoryInfo, NetworkInfo, NetworkInterface,
     SystemInfo, SystemMonitor,
@@ -92,6 +92,9 @@ fn test_gpu_info_struct() {
         power_usage_watts: 250.0,
         driver_version: "525.60.13".to_string(),
         cuda_version: Some("12.0".to_string()),
+        uuid: "GPU-8f3c2a1e".to_string(),
+        vendor: "NVIDIA".to_string(),
+        compute_capability: Some("8.6".to_string()),
     };
 
     assert_eq!(gpu_info.index, 0);
@@ -278,6 +281,49 @@ async fn test_monitor_with_disabled_features() {
     assert!(info.memory.total_bytes > 0);
 }
 
+#[tokio::test]
+async fn test_monitor_with_mock_gpu_backend() {
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = dir.path().join("gpus.json");
+    std::fs::write(
+        &fixture,
+        r#"[{
+            "index": 0,
+            "name": "AMD Instinct MI300X",
+            "memory_total_bytes": 206158430208,
+            "memory_used_bytes": 103079215104,
+            "memory_usage_percent": 50.0,
+            "utilization_percent": 80.0,
+            "temperature_celsius": 55.0,
+            "power_usage_watts": 600.0,
+            "driver_version": "6.2.4",
+            "cuda_version": null,
+            "vendor": "AMD",
+            "compute_capability": "gfx942"
+        }]"#,
+    )
+    .unwrap();
+
+    let config = SystemConfig {
+        gpu: GpuBackendConfig {
+            backend: GpuBackendKind::Mock,
+            fixture_path: Some(fixture),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let monitor = SystemMonitor::new(config).unwrap();
+
+    let info = monitor.get_system_info().await.unwrap();
+    assert_eq!(info.gpu.len(), 1);
+    assert_eq!(info.gpu[0].vendor, "AMD");
+    assert!(info.gpu[0].uuid.is_empty());
+
+    let resources = monitor.get_current_resources().await.unwrap();
+    assert_eq!(resources.gpu_count, 1);
+    assert_eq!(resources.gpu_memory_mb, 96 * 1024);
+}
+
 #[test]
 fn test_memory_calculations() {
     let memory_info = MemoryInfo {
@@ -24,6 +24,7 @@ impl WeightSetterTester {
             },
             weight_set_interval_blocks: 100,
             weight_version_key: 0,
+            collateral: Default::default(),
         };
 
         let weight_allocation_engine = WeightAllocationEngine::new(emission_config, 0.5);
@@ -301,6 +301,72 @@ EXAMPLES:
     },
 }
 
+/// Collateral management subcommands
+#[derive(Subcommand, Debug)]
+#[command(about = "Manage executor collateral on the Collateral contract")]
+#[command(long_about = r#"Collateral Management
+
+Executors are identified on the Collateral contract by a 16-byte ID. The
+miner derives it from the executor identity: UUIDs map to their 128-bit
+value, any other executor ID maps to the first 16 bytes of its keccak256
+hash. Validators use the same mapping when scoring and slashing.
+
+Requires a [collateral] section in the miner configuration with a signer
+for transactions. Submitted transaction hashes are stored in the miner
+database.
+
+EXAMPLES:
+    Deposit 1.5 TAO for an executor:
+        $ basilica collateral deposit swift-falcon-a3f2 1.5
+
+    Show collateral and pending reclaims:
+        $ basilica collateral status swift-falcon-a3f2
+
+    Start reclaiming all collateral:
+        $ basilica collateral reclaim swift-falcon-a3f2
+
+    Finalize a reclaim after its deny deadline:
+        $ basilica collateral finalize 7"#)]
+pub enum CollateralCommand {
+    /// Deposit collateral for an executor
+    Deposit {
+        /// Executor ID (UUID or HUID, supports prefix matching with min 3 chars)
+        #[arg(value_name = "EXECUTOR_ID")]
+        executor_id: String,
+        /// Amount to deposit in TAO
+        amount: f64,
+    },
+
+    /// Show collateral, pending reclaims and local transactions for an executor
+    Status {
+        /// Executor ID (UUID or HUID, supports prefix matching with min 3 chars)
+        #[arg(value_name = "EXECUTOR_ID")]
+        executor_id: String,
+    },
+
+    /// Start reclaiming an executor's available collateral
+    Reclaim {
+        /// Executor ID (UUID or HUID, supports prefix matching with min 3 chars)
+        #[arg(value_name = "EXECUTOR_ID")]
+        executor_id: String,
+        /// URL with the reason for the reclaim
+        #[arg(long, default_value = "")]
+        url: String,
+        /// Hex-encoded MD5 checksum of the URL content
+        #[arg(long)]
+        checksum: Option<String>,
+    },
+
+    /// Finalize a reclaim request once its deny deadline has passed
+    Finalize {
+        /// Reclaim request ID
+        reclaim_id: u64,
+    },
+
+    /// List collateral and pending reclaims for all configured executors
+    List,
+}
+
 /// Configuration management subcommands
 #[derive(Subcommand, Debug)]
 pub enum ConfigCommand {
@@ -443,6 +509,14 @@ pub async fn handle_assignment_command(
     handlers::handle_assignment_command(command, config).await
 }
 
+/// Handle collateral management commands
+pub async fn handle_collateral_command(
+    command: &CollateralCommand,
+    config: &MinerConfig,
+) -> Result<()> {
+    handlers::handle_collateral_command(command, config).await
+}
+
 /// Handle configuration management commands
 pub async fn handle_config_command(command: ConfigCommand, config: &MinerConfig) -> Result<()> {
     let operation = match command {
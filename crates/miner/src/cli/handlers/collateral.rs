@@ -0,0 +1,657 @@
+//! # Collateral CLI Handlers
+//!
+//! Command handlers for depositing and reclaiming executor collateral
+
+use crate::cli::CollateralCommand;
+use crate::config::{ExecutorConfig, MinerCollateralConfig, MinerConfig};
+use crate::persistence::CollateralDb;
+use alloy_primitives::{TxHash, U256};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use collateral::indexer::{CollateralEvent, IndexedEvent};
+use collateral::{CollateralClient, CollateralEventStore, CollateralIndexer, Reclaim};
+use common::executor_identity::ExecutorIdentity;
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+
+use super::identity_integration::find_executor_with_identity;
+
+/// Collateral contract calls made by the CLI
+#[async_trait]
+trait CollateralContract: Sync {
+    async fn min_collateral_increase(&self) -> Result<U256>;
+    async fn deposit(&self, executor_id: u128, amount: U256) -> Result<TxHash>;
+    async fn collaterals(&self, executor_id: u128) -> Result<U256>;
+    async fn reclaim_collateral(
+        &self,
+        executor_id: u128,
+        url: &str,
+        checksum: u128,
+    ) -> Result<TxHash>;
+    async fn decision_timeout(&self) -> Result<u64>;
+    async fn reclaims(&self, reclaim_request_id: U256) -> Result<Reclaim>;
+    async fn finalize_reclaim(&self, reclaim_request_id: U256) -> Result<TxHash>;
+}
+
+#[async_trait]
+impl CollateralContract for CollateralClient {
+    async fn min_collateral_increase(&self) -> Result<U256> {
+        CollateralClient::min_collateral_increase(self).await
+    }
+
+    async fn deposit(&self, executor_id: u128, amount: U256) -> Result<TxHash> {
+        CollateralClient::deposit(self, executor_id, amount).await
+    }
+
+    async fn collaterals(&self, executor_id: u128) -> Result<U256> {
+        CollateralClient::collaterals(self, executor_id).await
+    }
+
+    async fn reclaim_collateral(
+        &self,
+        executor_id: u128,
+        url: &str,
+        checksum: u128,
+    ) -> Result<TxHash> {
+        CollateralClient::reclaim_collateral(self, executor_id, url, checksum).await
+    }
+
+    async fn decision_timeout(&self) -> Result<u64> {
+        CollateralClient::decision_timeout(self).await
+    }
+
+    async fn reclaims(&self, reclaim_request_id: U256) -> Result<Reclaim> {
+        CollateralClient::reclaims(self, reclaim_request_id).await
+    }
+
+    async fn finalize_reclaim(&self, reclaim_request_id: U256) -> Result<TxHash> {
+        CollateralClient::finalize_reclaim(self, reclaim_request_id).await
+    }
+}
+
+/// Handle collateral commands
+pub async fn handle_collateral_command(
+    command: &CollateralCommand,
+    config: &MinerConfig,
+) -> Result<()> {
+    let collateral_config = config.collateral.as_ref().ok_or_else(|| {
+        anyhow!("Collateral is not configured, add a [collateral] section to the miner config")
+    })?;
+    let client = CollateralClient::new(collateral_config.contract.clone());
+
+    let pool = SqlitePool::connect(&config.database.url).await?;
+    let collateral_db = CollateralDb::new(pool.clone());
+    collateral_db.run_migrations().await?;
+
+    match command {
+        CollateralCommand::Deposit {
+            executor_id,
+            amount,
+        } => {
+            let executor = find_executor_with_identity(executor_id, config, None).await?;
+            let tx_hash = deposit(&client, &collateral_db, &executor.config, *amount).await?;
+
+            println!(
+                "Deposited {} TAO for executor {}",
+                amount, executor.config.id
+            );
+            println!("  Transaction: {tx_hash}");
+        }
+
+        CollateralCommand::Status { executor_id } => {
+            let executor = find_executor_with_identity(executor_id, config, None).await?;
+            let onchain_id = collateral::executor_id_from_str(&executor.config.id);
+            let onchain_bytes = collateral::executor_id_bytes(onchain_id);
+
+            let collateral = client.collaterals(onchain_id).await?;
+            let owner = client.executor_to_miner(onchain_id).await?;
+
+            println!("Executor: {}", executor.config.id);
+            if let Some(identity) = &executor.identity {
+                println!("  HUID: {}", identity.huid());
+            }
+            println!("  On-chain ID: {onchain_bytes}");
+            if owner.is_zero() {
+                println!("  Owner: - (no deposit yet)");
+            } else {
+                println!("  Owner: {owner}");
+            }
+            println!("  Collateral: {} TAO", collateral::wei_to_tao(collateral));
+
+            let reclaims = pending_reclaims(&pool, collateral_config).await?;
+            let reclaims: Vec<_> = reclaims
+                .iter()
+                .filter(|event| event.event.executor_id() == Some(onchain_bytes))
+                .collect();
+            println!();
+            print_reclaims(&reclaims);
+
+            let transactions = collateral_db
+                .get_transactions(Some(&executor.config.id))
+                .await?;
+            println!();
+            if transactions.is_empty() {
+                println!("No collateral transactions recorded for this executor");
+            } else {
+                println!("Recorded transactions:");
+                println!(
+                    "{:<10} {:<12} {:<68} Submitted At",
+                    "Action", "Amount", "Transaction"
+                );
+                println!("{}", "-".repeat(112));
+                for tx in transactions {
+                    println!(
+                        "{:<10} {:<12} {:<68} {}",
+                        tx.action,
+                        tx.amount_tao
+                            .map(|amount| format!("{amount} TAO"))
+                            .unwrap_or_else(|| "-".to_string()),
+                        tx.tx_hash,
+                        tx.submitted_at.format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+            }
+        }
+
+        CollateralCommand::Reclaim {
+            executor_id,
+            url,
+            checksum,
+        } => {
+            let executor = find_executor_with_identity(executor_id, config, None).await?;
+            let tx_hash = reclaim(
+                &client,
+                &collateral_db,
+                &executor.config,
+                url,
+                checksum.as_deref(),
+            )
+            .await?;
+
+            let decision_timeout = client.decision_timeout().await?;
+            println!("Reclaim started for executor {}", executor.config.id);
+            println!("  Transaction: {tx_hash}");
+            println!(
+                "  Validators can deny it for {decision_timeout} seconds, run 'collateral finalize' afterwards"
+            );
+        }
+
+        CollateralCommand::Finalize { reclaim_id } => {
+            let (reclaim, tx_hash) = finalize(
+                &client,
+                &collateral_db,
+                &config.executor_management.executors,
+                *reclaim_id,
+                Utc::now().timestamp() as u64,
+            )
+            .await?;
+
+            println!(
+                "Finalized reclaim {} for {} TAO",
+                reclaim_id,
+                collateral::wei_to_tao(reclaim.amount)
+            );
+            println!("  Transaction: {tx_hash}");
+        }
+
+        CollateralCommand::List => {
+            let executors = &config.executor_management.executors;
+            if executors.is_empty() {
+                println!("No executors configured");
+                return Ok(());
+            }
+
+            println!("{:<38} {:<36} Collateral", "Executor ID", "On-chain ID");
+            println!("{}", "-".repeat(90));
+
+            let mut onchain_ids = Vec::with_capacity(executors.len());
+            for executor in executors {
+                let onchain_id = collateral::executor_id_from_str(&executor.id);
+                let onchain_bytes = collateral::executor_id_bytes(onchain_id);
+                onchain_ids.push(onchain_bytes);
+
+                let collateral = match client.collaterals(onchain_id).await {
+                    Ok(amount) => format!("{} TAO", collateral::wei_to_tao(amount)),
+                    Err(e) => {
+                        warn!("Failed to query collateral for {}: {}", executor.id, e);
+                        "unavailable".to_string()
+                    }
+                };
+
+                println!("{:<38} {:<36} {}", executor.id, onchain_bytes, collateral);
+            }
+
+            let reclaims = pending_reclaims(&pool, collateral_config).await?;
+            let reclaims: Vec<_> = reclaims
+                .iter()
+                .filter(|event| {
+                    event
+                        .event
+                        .executor_id()
+                        .is_some_and(|id| onchain_ids.contains(&id))
+                })
+                .collect();
+            println!();
+            print_reclaims(&reclaims);
+        }
+    }
+
+    Ok(())
+}
+
+/// Deposit collateral for an executor and record the transaction
+///
+/// The deposit is made under the ID validators derive from the configured
+/// executor ID, so they find it when scoring and slashing the executor.
+async fn deposit(
+    contract: &impl CollateralContract,
+    collateral_db: &CollateralDb,
+    executor: &ExecutorConfig,
+    amount: f64,
+) -> Result<TxHash> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(anyhow!("Deposit amount must be a positive number of TAO"));
+    }
+
+    let onchain_id = collateral::executor_id_from_str(&executor.id);
+    let amount_wei = collateral::tao_to_wei(amount);
+    let min_increase = contract.min_collateral_increase().await?;
+    if amount_wei < min_increase {
+        return Err(anyhow!(
+            "Deposit of {} TAO is below the contract minimum of {} TAO",
+            amount,
+            collateral::wei_to_tao(min_increase)
+        ));
+    }
+
+    info!(
+        "Depositing {} TAO for executor {} ({})",
+        amount,
+        executor.id,
+        collateral::executor_id_bytes(onchain_id)
+    );
+    let tx_hash = contract.deposit(onchain_id, amount_wei).await?;
+
+    collateral_db
+        .record_transaction(
+            Some(&executor.id),
+            Some(&collateral::executor_id_bytes(onchain_id).to_string()),
+            "deposit",
+            Some(amount),
+            None,
+            &tx_hash.to_string(),
+        )
+        .await?;
+
+    Ok(tx_hash)
+}
+
+/// Start reclaiming an executor's collateral and record the transaction
+async fn reclaim(
+    contract: &impl CollateralContract,
+    collateral_db: &CollateralDb,
+    executor: &ExecutorConfig,
+    url: &str,
+    checksum: Option<&str>,
+) -> Result<TxHash> {
+    let onchain_id = collateral::executor_id_from_str(&executor.id);
+
+    let checksum = match checksum {
+        Some(checksum) => u128::from_str_radix(checksum.trim_start_matches("0x"), 16)
+            .map_err(|e| anyhow!("Invalid MD5 checksum '{}': {}", checksum, e))?,
+        None => 0,
+    };
+
+    let available = contract.collaterals(onchain_id).await?;
+    if available.is_zero() {
+        return Err(anyhow!(
+            "Executor {} has no collateral to reclaim",
+            executor.id
+        ));
+    }
+
+    info!(
+        "Starting reclaim for executor {} ({})",
+        executor.id,
+        collateral::executor_id_bytes(onchain_id)
+    );
+    let tx_hash = contract
+        .reclaim_collateral(onchain_id, url, checksum)
+        .await?;
+
+    collateral_db
+        .record_transaction(
+            Some(&executor.id),
+            Some(&collateral::executor_id_bytes(onchain_id).to_string()),
+            "reclaim",
+            None,
+            None,
+            &tx_hash.to_string(),
+        )
+        .await?;
+
+    Ok(tx_hash)
+}
+
+/// Finalize a reclaim whose deny window has passed and record the transaction
+async fn finalize(
+    contract: &impl CollateralContract,
+    collateral_db: &CollateralDb,
+    executors: &[ExecutorConfig],
+    reclaim_id: u64,
+    now: u64,
+) -> Result<(Reclaim, TxHash)> {
+    let reclaim_request_id = U256::from(reclaim_id);
+    let reclaim = contract.reclaims(reclaim_request_id).await?;
+    if reclaim.amount.is_zero() {
+        return Err(anyhow!(
+            "Reclaim request {} is not pending, it was finalized, denied or never existed",
+            reclaim_id
+        ));
+    }
+
+    if reclaim.deny_timeout >= now {
+        return Err(anyhow!(
+            "Reclaim request {} can be denied until {}, try again afterwards",
+            reclaim_id,
+            format_deadline(reclaim.deny_timeout)
+        ));
+    }
+
+    let onchain_bytes = collateral::executor_id_bytes(reclaim.executor_id);
+    let executor_id = executors
+        .iter()
+        .find(|e| collateral::executor_id_from_str(&e.id) == reclaim.executor_id)
+        .map(|e| e.id.clone());
+
+    let tx_hash = contract.finalize_reclaim(reclaim_request_id).await?;
+
+    collateral_db
+        .record_transaction(
+            executor_id.as_deref(),
+            Some(&onchain_bytes.to_string()),
+            "finalize",
+            Some(collateral::wei_to_tao(reclaim.amount)),
+            Some(&reclaim_id.to_string()),
+            &tx_hash.to_string(),
+        )
+        .await?;
+
+    Ok((reclaim, tx_hash))
+}
+
+/// Bring the local event index up to date and return open reclaim requests
+///
+/// Falls back to the previously indexed events if the RPC endpoint is unreachable.
+async fn pending_reclaims(
+    pool: &SqlitePool,
+    collateral_config: &MinerCollateralConfig,
+) -> Result<Vec<IndexedEvent>> {
+    let store = CollateralEventStore::new(pool.clone()).await?;
+    let indexer = CollateralIndexer::new(
+        collateral_config.contract.clone(),
+        collateral_config.indexer.clone(),
+        store,
+    );
+
+    if let Err(e) = indexer.sync_once().await {
+        warn!(
+            "Failed to sync collateral events, showing last indexed state: {}",
+            e
+        );
+    }
+
+    indexer.store().open_reclaims().await
+}
+
+fn print_reclaims(reclaims: &[&IndexedEvent]) {
+    if reclaims.is_empty() {
+        println!("No pending reclaims");
+        return;
+    }
+
+    let now = Utc::now().timestamp() as u64;
+
+    println!("Pending reclaims:");
+    println!(
+        "{:<8} {:<36} {:<14} {:<22} Status",
+        "ID", "On-chain ID", "Amount", "Deny Deadline"
+    );
+    println!("{}", "-".repeat(100));
+    for indexed in reclaims {
+        if let CollateralEvent::ReclaimProcessStarted {
+            reclaim_request_id,
+            executor_id,
+            amount,
+            expiration_time,
+            ..
+        } = &indexed.event
+        {
+            let status = if *expiration_time < now {
+                "ready to finalize"
+            } else {
+                "deniable"
+            };
+            println!(
+                "{:<8} {:<36} {:<14} {:<22} {}",
+                reclaim_request_id,
+                executor_id,
+                format!("{} TAO", collateral::wei_to_tao(*amount)),
+                format_deadline(*expiration_time),
+                status
+            );
+        }
+    }
+}
+
+fn format_deadline(timestamp: u64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp as i64, 0)
+        .map(|deadline| deadline.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use std::sync::Mutex;
+
+    const TAO: u128 = collateral::WEI_PER_TAO;
+    const NOW: u64 = 1_700_000_000;
+
+    /// Contract answering from fixed state and recording submitted transactions
+    struct FakeContract {
+        min_increase: U256,
+        collateral: U256,
+        reclaim: Reclaim,
+        submitted: Mutex<Vec<String>>,
+    }
+
+    impl FakeContract {
+        fn new() -> Self {
+            Self {
+                min_increase: U256::from(TAO),
+                collateral: U256::ZERO,
+                reclaim: Reclaim {
+                    executor_id: 0,
+                    miner: Address::ZERO,
+                    amount: U256::ZERO,
+                    deny_timeout: 0,
+                },
+                submitted: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn submit(&self, call: String) -> TxHash {
+            let mut submitted = self.submitted.lock().unwrap();
+            submitted.push(call);
+            TxHash::repeat_byte(submitted.len() as u8)
+        }
+
+        fn submitted(&self) -> Vec<String> {
+            self.submitted.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl CollateralContract for FakeContract {
+        async fn min_collateral_increase(&self) -> Result<U256> {
+            Ok(self.min_increase)
+        }
+
+        async fn deposit(&self, executor_id: u128, amount: U256) -> Result<TxHash> {
+            Ok(self.submit(format!("deposit {executor_id:x} {amount}")))
+        }
+
+        async fn collaterals(&self, _executor_id: u128) -> Result<U256> {
+            Ok(self.collateral)
+        }
+
+        async fn reclaim_collateral(
+            &self,
+            executor_id: u128,
+            url: &str,
+            checksum: u128,
+        ) -> Result<TxHash> {
+            Ok(self.submit(format!("reclaim {executor_id:x} {url} {checksum:x}")))
+        }
+
+        async fn decision_timeout(&self) -> Result<u64> {
+            Ok(3600)
+        }
+
+        async fn reclaims(&self, _reclaim_request_id: U256) -> Result<Reclaim> {
+            Ok(self.reclaim.clone())
+        }
+
+        async fn finalize_reclaim(&self, reclaim_request_id: U256) -> Result<TxHash> {
+            Ok(self.submit(format!("finalize {reclaim_request_id}")))
+        }
+    }
+
+    async fn setup_test_db() -> Result<CollateralDb> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+        let db = CollateralDb::new(pool);
+        db.run_migrations().await?;
+        Ok(db)
+    }
+
+    fn executor(id: &str) -> ExecutorConfig {
+        ExecutorConfig {
+            id: id.to_string(),
+            grpc_address: "127.0.0.1:50051".to_string(),
+            host: "127.0.0.1".to_string(),
+            port: 50051,
+            ssh_port: 22,
+            ssh_username: "testuser".to_string(),
+            enabled: true,
+            name: None,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deposit() -> Result<()> {
+        let db = setup_test_db().await?;
+        let contract = FakeContract::new();
+        let executor = executor("test-exec-1");
+        let onchain_id = collateral::executor_id_from_str("test-exec-1");
+
+        let tx_hash = deposit(&contract, &db, &executor, 1.5).await?;
+
+        // Deposited under the ID validators derive from the configured ID
+        assert_eq!(
+            contract.submitted(),
+            [format!("deposit {onchain_id:x} {}", TAO * 3 / 2)]
+        );
+        let transactions = db.get_transactions(Some("test-exec-1")).await?;
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].action, "deposit");
+        assert_eq!(transactions[0].amount_tao, Some(1.5));
+        assert_eq!(transactions[0].tx_hash, tx_hash.to_string());
+        assert_eq!(
+            transactions[0].onchain_executor_id,
+            Some(collateral::executor_id_bytes(onchain_id).to_string())
+        );
+
+        // Invalid or too small amounts are refused before anything is submitted
+        assert!(deposit(&contract, &db, &executor, 0.0).await.is_err());
+        assert!(deposit(&contract, &db, &executor, f64::NAN).await.is_err());
+        assert!(deposit(&contract, &db, &executor, 0.5).await.is_err());
+        assert_eq!(contract.submitted().len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reclaim() -> Result<()> {
+        let db = setup_test_db().await?;
+        let mut contract = FakeContract::new();
+        let executor = executor("test-exec-1");
+        let onchain_id = collateral::executor_id_from_str("test-exec-1");
+
+        // Nothing deposited
+        assert!(reclaim(&contract, &db, &executor, "", None).await.is_err());
+
+        contract.collateral = U256::from(TAO);
+        assert!(reclaim(&contract, &db, &executor, "", Some("not-hex"))
+            .await
+            .is_err());
+        assert!(contract.submitted().is_empty());
+
+        reclaim(
+            &contract,
+            &db,
+            &executor,
+            "https://example.com/reason",
+            Some("0xff"),
+        )
+        .await?;
+        assert_eq!(
+            contract.submitted(),
+            [format!(
+                "reclaim {onchain_id:x} https://example.com/reason ff"
+            )]
+        );
+
+        let transactions = db.get_transactions(Some("test-exec-1")).await?;
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].action, "reclaim");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_finalize() -> Result<()> {
+        let db = setup_test_db().await?;
+        let mut contract = FakeContract::new();
+        let executors = [executor("test-exec-1"), executor("test-exec-2")];
+
+        // No pending reclaim
+        assert!(finalize(&contract, &db, &executors, 7, NOW).await.is_err());
+
+        // Still deniable
+        contract.reclaim = Reclaim {
+            executor_id: collateral::executor_id_from_str("test-exec-2"),
+            miner: Address::ZERO,
+            amount: U256::from(2 * TAO),
+            deny_timeout: NOW,
+        };
+        assert!(finalize(&contract, &db, &executors, 7, NOW).await.is_err());
+        assert!(contract.submitted().is_empty());
+
+        let (reclaim, _) = finalize(&contract, &db, &executors, 7, NOW + 1).await?;
+        assert_eq!(reclaim.amount, U256::from(2 * TAO));
+        assert_eq!(contract.submitted(), ["finalize 7"]);
+
+        // Recorded against the configured executor the reclaim belongs to
+        let transactions = db.get_transactions(Some("test-exec-2")).await?;
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].action, "finalize");
+        assert_eq!(transactions[0].amount_tao, Some(2.0));
+        assert_eq!(transactions[0].reclaim_request_id.as_deref(), Some("7"));
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@
 //! Each handler module provides implementation for specific command categories.
 
 pub mod assignment;
+pub mod collateral;
 pub mod config;
 pub mod database;
 pub mod disambiguation;
@@ -13,6 +14,7 @@ pub mod identity_integration;
 pub mod service;
 
 pub use assignment::*;
+pub use collateral::*;
 pub use config::*;
 pub use database::*;
 pub use executor::*;
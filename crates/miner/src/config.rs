@@ -88,6 +88,10 @@ pub struct MinerConfig {
     /// Validator assignment configuration
     #[serde(default)]
     pub validator_assignment: ValidatorAssignmentConfig,
+
+    /// Collateral contract configuration (optional)
+    #[serde(default)]
+    pub collateral: Option<MinerCollateralConfig>,
 }
 
 /// Miner-specific Bittensor configuration
@@ -365,6 +369,18 @@ impl Default for ValidatorAssignmentConfig {
     }
 }
 
+/// Collateral contract configuration for the miner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinerCollateralConfig {
+    /// Contract deployment, RPC endpoint and signer
+    #[serde(flatten)]
+    pub contract: collateral::CollateralConfig,
+
+    /// Event indexer used to track pending reclaims
+    #[serde(default)]
+    pub indexer: collateral::IndexerConfig,
+}
+
 /// Advertised address configuration for miner services
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct MinerAdvertisedAddresses {
@@ -400,6 +416,7 @@ impl Default for MinerConfig {
             ssh_session: ExecutorSshConfig::default(),
             advertised_addresses: MinerAdvertisedAddresses::default(),
             validator_assignment: ValidatorAssignmentConfig::default(),
+            collateral: None,
         }
     }
 }
@@ -5,8 +5,9 @@
 
 use anyhow::{Context, Result};
 use protocol::executor_control::{
-    executor_control_client::ExecutorControlClient, HealthCheckRequest, HealthCheckResponse,
-    ProvisionAccessRequest, ProvisionAccessResponse,
+    executor_control_client::ExecutorControlClient, BenchmarkRequest, BenchmarkResponse,
+    ContainerOpRequest, ContainerOpResponse, HealthCheckRequest, HealthCheckResponse,
+    ProvisionAccessRequest, ProvisionAccessResponse, SystemProfileRequest, SystemProfileResponse,
 };
 use std::time::Duration;
 use tonic::transport::Channel;
@@ -155,6 +156,104 @@ impl ExecutorGrpcClient {
         Ok(response.into_inner())
     }
 
+    /// Forward a container operation to the executor
+    ///
+    /// Not retried, since operations such as create are not idempotent.
+    pub async fn manage_container(
+        &self,
+        executor_endpoint: &str,
+        request: ContainerOpRequest,
+    ) -> Result<ContainerOpResponse> {
+        let grpc_endpoint = self.build_grpc_endpoint(executor_endpoint)?;
+        info!(
+            "Forwarding container operation '{}' to executor at {}",
+            request.operation, grpc_endpoint
+        );
+
+        let channel = Channel::from_shared(grpc_endpoint.clone())
+            .with_context(|| format!("Invalid gRPC endpoint: {grpc_endpoint}"))?
+            .connect_timeout(self.config.timeout)
+            .connect()
+            .await
+            .with_context(|| format!("Failed to connect to executor at {grpc_endpoint}"))?;
+
+        let mut client = ExecutorControlClient::new(channel);
+        let response = client
+            .manage_container(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("Container operation failed: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Forward a system profile request to the executor
+    pub async fn execute_system_profile(
+        &self,
+        executor_endpoint: &str,
+        request: SystemProfileRequest,
+    ) -> Result<SystemProfileResponse> {
+        let grpc_endpoint = self.build_grpc_endpoint(executor_endpoint)?;
+        info!(
+            "Forwarding system profile request to executor at {}",
+            grpc_endpoint
+        );
+
+        let channel = Channel::from_shared(grpc_endpoint.clone())
+            .with_context(|| format!("Invalid gRPC endpoint: {grpc_endpoint}"))?
+            .connect_timeout(self.config.timeout)
+            .timeout(self.config.timeout)
+            .connect()
+            .await
+            .with_context(|| format!("Failed to connect to executor at {grpc_endpoint}"))?;
+
+        let response = self
+            .retry_grpc_call(|| {
+                let channel = channel.clone();
+                let request = request.clone();
+                async move {
+                    let mut client = ExecutorControlClient::new(channel);
+                    client
+                        .execute_system_profile(request)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("System profile failed: {}", e))
+                }
+            })
+            .await?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Forward a benchmark request to the executor
+    ///
+    /// Only the connection is bounded by the configured timeout, since
+    /// benchmarks run for as long as the request asks.
+    pub async fn execute_benchmark(
+        &self,
+        executor_endpoint: &str,
+        request: BenchmarkRequest,
+    ) -> Result<BenchmarkResponse> {
+        let grpc_endpoint = self.build_grpc_endpoint(executor_endpoint)?;
+        info!(
+            "Forwarding '{}' benchmark request to executor at {}",
+            request.benchmark_type, grpc_endpoint
+        );
+
+        let channel = Channel::from_shared(grpc_endpoint.clone())
+            .with_context(|| format!("Invalid gRPC endpoint: {grpc_endpoint}"))?
+            .connect_timeout(self.config.timeout)
+            .connect()
+            .await
+            .with_context(|| format!("Failed to connect to executor at {grpc_endpoint}"))?;
+
+        let mut client = ExecutorControlClient::new(channel);
+        let response = client
+            .execute_benchmark(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("Benchmark failed: {}", e))?;
+
+        Ok(response.into_inner())
+    }
+
     /// Build gRPC endpoint from executor address
     fn build_grpc_endpoint(&self, executor_endpoint: &str) -> Result<String> {
         // executor_endpoint is like "185.26.8.109:50051"
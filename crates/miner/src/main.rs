@@ -84,6 +84,11 @@ enum Commands {
         #[command(subcommand)]
         assignment_cmd: cli::AssignmentCommand,
     },
+    /// Collateral management commands
+    Collateral {
+        #[command(subcommand)]
+        collateral_cmd: cli::CollateralCommand,
+    },
     /// Service management commands
     Service {
         #[command(subcommand)]
@@ -372,6 +377,14 @@ impl MinerState {
             })
         };
 
+        // Start collateral event indexer if a collateral contract is configured
+        let collateral_indexer_handle = {
+            let pool = sqlx::SqlitePool::connect(&self.config.database.url)
+                .await
+                .context("Failed to create pool for collateral indexer")?;
+            services::spawn_collateral_indexer(self.config.collateral.as_ref(), pool).await?
+        };
+
         // Start validator discovery service if enabled
         let discovery_handle = if let Some(ref discovery) = self.validator_discovery {
             let discovery = discovery.clone();
@@ -393,51 +406,52 @@ impl MinerState {
         info!("All miner services started successfully");
 
         // Wait for shutdown signal
-        if let Some(discovery_handle) = discovery_handle {
-            tokio::select! {
-                _ = signal::ctrl_c() => {
-                    info!("Received shutdown signal, stopping miner...");
-                }
-                _ = validator_handle => {
-                    warn!("Validator comms server stopped unexpectedly");
-                }
-                _ = executor_handle => {
-                    warn!("Executor monitoring stopped unexpectedly");
-                }
-                _ = cleanup_handle => {
-                    warn!("Session cleanup service stopped unexpectedly");
-                }
-                _ = stake_monitor_handle => {
-                    warn!("Stake monitor service stopped unexpectedly");
-                }
-                _ = discovery_handle => {
-                    warn!("Validator discovery service stopped unexpectedly");
-                }
+        let collateral_indexer_abort = collateral_indexer_handle
+            .as_ref()
+            .map(|handle| handle.abort_handle());
+        tokio::select! {
+            _ = signal::ctrl_c() => {
+                info!("Received shutdown signal, stopping miner...");
             }
-        } else {
-            tokio::select! {
-                _ = signal::ctrl_c() => {
-                    info!("Received shutdown signal, stopping miner...");
-                }
-                _ = validator_handle => {
-                    warn!("Validator comms server stopped unexpectedly");
-                }
-                _ = executor_handle => {
-                    warn!("Executor monitoring stopped unexpectedly");
-                }
-                _ = cleanup_handle => {
-                    warn!("Session cleanup service stopped unexpectedly");
-                }
-                _ = stake_monitor_handle => {
-                    warn!("Stake monitor service stopped unexpectedly");
-                }
+            _ = validator_handle => {
+                warn!("Validator comms server stopped unexpectedly");
+            }
+            _ = executor_handle => {
+                warn!("Executor monitoring stopped unexpectedly");
+            }
+            _ = cleanup_handle => {
+                warn!("Session cleanup service stopped unexpectedly");
+            }
+            _ = stake_monitor_handle => {
+                warn!("Stake monitor service stopped unexpectedly");
+            }
+            _ = wait_optional(discovery_handle) => {
+                warn!("Validator discovery service stopped unexpectedly");
+            }
+            _ = wait_optional(collateral_indexer_handle) => {
+                warn!("Collateral indexer stopped unexpectedly");
             }
         }
 
+        // Stop the indexer before exiting so it does not write to the database mid-shutdown
+        if let Some(handle) = collateral_indexer_abort {
+            handle.abort();
+        }
+
         Ok(())
     }
 }
 
+/// Wait for an optional background task, never completing if it was not started
+async fn wait_optional(handle: Option<tokio::task::JoinHandle<()>>) {
+    match handle {
+        Some(handle) => {
+            let _ = handle.await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
@@ -495,6 +509,9 @@ async fn handle_cli_command(command: Commands, config: &MinerConfig) -> Result<(
         Commands::Assignment { assignment_cmd } => {
             cli::handle_assignment_command(&assignment_cmd, config).await
         }
+        Commands::Collateral { collateral_cmd } => {
+            cli::handle_collateral_command(&collateral_cmd, config).await
+        }
         Commands::Service { service_cmd } => cli::handle_service_command(service_cmd, config).await,
         Commands::Database { database_cmd } => {
             cli::handle_database_command(database_cmd, config).await
@@ -509,6 +526,8 @@ async fn handle_cli_command(command: Commands, config: &MinerConfig) -> Result<(
             let assignment_pool = sqlx::SqlitePool::connect(&config.database.url).await?;
             let assignment_db = persistence::AssignmentDb::new(assignment_pool);
             assignment_db.run_migrations().await?;
+            let collateral_db = persistence::CollateralDb::new(assignment_db.pool().clone());
+            collateral_db.run_migrations().await?;
             println!("Database migrations completed successfully");
             Ok(())
         }
@@ -0,0 +1,196 @@
+//! # Collateral Database
+//!
+//! Local record of collateral transactions submitted from the miner CLI
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono;
+use sqlx::{Row, SqlitePool};
+use tracing::{debug, info};
+
+/// A collateral transaction submitted by this miner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollateralTransaction {
+    pub id: i64,
+    /// Executor ID from the miner configuration
+    pub executor_id: Option<String>,
+    /// 16-byte executor ID used by the contract, hex encoded
+    pub onchain_executor_id: Option<String>,
+    /// deposit, reclaim or finalize
+    pub action: String,
+    /// Amount in TAO, when known at submission time
+    pub amount_tao: Option<f64>,
+    /// Reclaim request the transaction refers to
+    pub reclaim_request_id: Option<String>,
+    pub tx_hash: String,
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Collateral transaction database operations
+pub struct CollateralDb {
+    pool: SqlitePool,
+}
+
+impl CollateralDb {
+    /// Create a new collateral database
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Run database migrations
+    pub async fn run_migrations(&self) -> Result<()> {
+        info!("Running collateral database migrations");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS collateral_transactions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                executor_id TEXT,
+                onchain_executor_id TEXT,
+                action TEXT NOT NULL,
+                amount_tao REAL,
+                reclaim_request_id TEXT,
+                tx_hash TEXT NOT NULL UNIQUE,
+                submitted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_collateral_transactions_executor ON collateral_transactions(executor_id)",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        info!("Collateral database migrations completed");
+        Ok(())
+    }
+
+    /// Record a submitted transaction
+    pub async fn record_transaction(
+        &self,
+        executor_id: Option<&str>,
+        onchain_executor_id: Option<&str>,
+        action: &str,
+        amount_tao: Option<f64>,
+        reclaim_request_id: Option<&str>,
+        tx_hash: &str,
+    ) -> Result<CollateralTransaction> {
+        debug!("Recording collateral {} transaction {}", action, tx_hash);
+
+        let now = Utc::now();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO collateral_transactions
+                (executor_id, onchain_executor_id, action, amount_tao, reclaim_request_id, tx_hash, submitted_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(executor_id)
+        .bind(onchain_executor_id)
+        .bind(action)
+        .bind(amount_tao)
+        .bind(reclaim_request_id)
+        .bind(tx_hash)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(CollateralTransaction {
+            id: result.last_insert_rowid(),
+            executor_id: executor_id.map(String::from),
+            onchain_executor_id: onchain_executor_id.map(String::from),
+            action: action.to_string(),
+            amount_tao,
+            reclaim_request_id: reclaim_request_id.map(String::from),
+            tx_hash: tx_hash.to_string(),
+            submitted_at: now,
+        })
+    }
+
+    /// Get transactions, newest first, optionally for a single executor
+    pub async fn get_transactions(
+        &self,
+        executor_id: Option<&str>,
+    ) -> Result<Vec<CollateralTransaction>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, executor_id, onchain_executor_id, action, amount_tao,
+                   reclaim_request_id, tx_hash, submitted_at
+            FROM collateral_transactions
+            WHERE ? IS NULL OR executor_id = ?
+            ORDER BY submitted_at DESC, id DESC
+            "#,
+        )
+        .bind(executor_id)
+        .bind(executor_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CollateralTransaction {
+                id: row.get("id"),
+                executor_id: row.get("executor_id"),
+                onchain_executor_id: row.get("onchain_executor_id"),
+                action: row.get("action"),
+                amount_tao: row.get("amount_tao"),
+                reclaim_request_id: row.get("reclaim_request_id"),
+                tx_hash: row.get("tx_hash"),
+                submitted_at: row.get("submitted_at"),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> Result<CollateralDb> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+        let db = CollateralDb::new(pool);
+        db.run_migrations().await?;
+        Ok(db)
+    }
+
+    #[tokio::test]
+    async fn test_record_and_list_transactions() -> Result<()> {
+        let db = setup_test_db().await?;
+
+        db.record_transaction(
+            Some("exec-1"),
+            Some("0x0000000000000000000000000000002a"),
+            "deposit",
+            Some(1.5),
+            None,
+            "0xaa",
+        )
+        .await?;
+        db.record_transaction(Some("exec-2"), None, "deposit", Some(2.0), None, "0xbb")
+            .await?;
+        db.record_transaction(None, None, "finalize", None, Some("7"), "0xcc")
+            .await?;
+
+        let all = db.get_transactions(None).await?;
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].tx_hash, "0xcc");
+        assert_eq!(all[0].reclaim_request_id.as_deref(), Some("7"));
+
+        let exec1 = db.get_transactions(Some("exec-1")).await?;
+        assert_eq!(exec1.len(), 1);
+        assert_eq!(exec1[0].action, "deposit");
+        assert_eq!(exec1[0].amount_tao, Some(1.5));
+
+        // The same transaction cannot be recorded twice
+        assert!(db
+            .record_transaction(Some("exec-1"), None, "deposit", None, None, "0xaa")
+            .await
+            .is_err());
+
+        Ok(())
+    }
+}
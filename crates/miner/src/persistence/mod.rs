@@ -3,7 +3,9 @@
 //! Database operations and data persistence for the Basilca Miner.
 
 pub mod assignment_db;
+pub mod collateral_db;
 pub mod registration_db;
 
 pub use assignment_db::{AssignmentDb, CoverageStats, ExecutorAssignment, ValidatorStake};
+pub use collateral_db::{CollateralDb, CollateralTransaction};
 pub use registration_db::RegistrationDb;
@@ -0,0 +1,75 @@
+//! # Collateral Indexer Service
+//!
+//! Keeps the local index of Collateral contract events up to date so
+//! `miner collateral` commands can answer from SQLite.
+
+use anyhow::Result;
+use collateral::{CollateralEventStore, CollateralIndexer};
+use sqlx::SqlitePool;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::config::MinerCollateralConfig;
+
+/// Spawn the collateral event indexer if a collateral contract is configured
+///
+/// The returned task runs until it is aborted on shutdown.
+pub async fn spawn_collateral_indexer(
+    config: Option<&MinerCollateralConfig>,
+    pool: SqlitePool,
+) -> Result<Option<JoinHandle<()>>> {
+    let Some(config) = config else {
+        info!("Collateral contract not configured, event indexer disabled");
+        return Ok(None);
+    };
+
+    let store = CollateralEventStore::new(pool).await?;
+    let indexer = CollateralIndexer::new(config.contract.clone(), config.indexer.clone(), store);
+
+    Ok(Some(tokio::spawn(async move {
+        if let Err(e) = indexer.start().await {
+            error!("Collateral indexer error: {}", e);
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collateral::{CollateralConfig, IndexerConfig};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_indexer_disabled_without_collateral_config() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        assert!(spawn_collateral_indexer(None, pool)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_indexer_keeps_running_until_aborted() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let config = MinerCollateralConfig {
+            contract: CollateralConfig::local(Default::default())
+                .with_rpc_url("http://127.0.0.1:1"),
+            indexer: IndexerConfig {
+                poll_interval_secs: 1,
+                ..Default::default()
+            },
+        };
+
+        let handle = spawn_collateral_indexer(Some(&config), pool)
+            .await
+            .unwrap()
+            .expect("indexer should be spawned");
+
+        // RPC failures are logged and retried rather than ending the task
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!handle.is_finished());
+
+        handle.abort();
+        assert!(handle.await.unwrap_err().is_cancelled());
+    }
+}
@@ -3,6 +3,8 @@
 //! Background services for the miner
 
 pub mod assignment_manager;
+pub mod collateral_indexer;
 pub mod stake_monitor;
 
+pub use collateral_indexer::spawn_collateral_indexer;
 pub use stake_monitor::StakeMonitor;
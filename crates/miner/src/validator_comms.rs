@@ -5,6 +5,8 @@
 //! - Authenticate validators
 //! - List available executors
 //! - Coordinate SSH access to executors
+//! - Forward container operations for rentals to executors
+//! - Forward signed system profile and benchmark requests to executors
 
 use anyhow::{anyhow, Context, Result};
 use std::collections::HashMap;
@@ -23,7 +25,8 @@ use tracing::{debug, error, info, warn};
 use common::identity::Hotkey;
 use protocol::miner_discovery::{
     miner_discovery_server::{MinerDiscovery, MinerDiscoveryServer},
-    CloseSshSessionRequest, CloseSshSessionResponse, ExecutorConnectionDetails,
+    CloseSshSessionRequest, CloseSshSessionResponse, ExecutorBenchmarkRequest,
+    ExecutorConnectionDetails, ExecutorContainerRequest, ExecutorSystemProfileRequest,
     InitiateSshSessionRequest, InitiateSshSessionResponse, LeaseOfferResponse, LeaseRequest,
     ListSshSessionsRequest, ListSshSessionsResponse, MinerAuthResponse, SessionInitRequest,
     SessionInitResponse, ValidatorAuthRequest,
@@ -32,6 +35,7 @@ use protocol::miner_discovery::{
 use crate::auth::JwtAuthService;
 use crate::config::{MinerConfig, SecurityConfig, ValidatorCommsConfig};
 use crate::executor_manager::ExecutorManager;
+use crate::executors::{ExecutorGrpcClient, ExecutorGrpcConfig};
 use crate::persistence::RegistrationDb;
 use crate::ssh::{SshSessionOrchestrator, ValidatorAccessService};
 use crate::validator_discovery::ValidatorDiscovery;
@@ -317,6 +321,69 @@ struct MinerDiscoveryService {
     ssh_session_orchestrator: Option<Arc<SshSessionOrchestrator>>,
 }
 
+impl MinerDiscoveryService {
+    /// Check a validator's session token and look up the executor it wants to reach
+    async fn authorize_executor_request(
+        &self,
+        validator_hotkey: &str,
+        session_token: &str,
+        executor_id: &str,
+    ) -> Result<AvailableExecutor, Status> {
+        let claims = self
+            .jwt_service
+            .validate_token(session_token)
+            .await
+            .map_err(|e| {
+                debug!("Token validation failed: {}", e);
+                Status::unauthenticated("Invalid or expired session token")
+            })?;
+
+        if !claims.permissions.contains(&"executor.access".to_string()) {
+            return Err(Status::permission_denied("Insufficient permissions"));
+        }
+
+        if claims.sub != validator_hotkey {
+            return Err(Status::permission_denied("Token validator mismatch"));
+        }
+
+        self.executor_manager
+            .list_available()
+            .await
+            .map_err(|e| Status::internal(format!("Failed to list executors: {e}")))?
+            .into_iter()
+            .find(|e| e.id == executor_id)
+            .ok_or_else(|| Status::not_found(format!("Executor {executor_id} is not available")))
+    }
+
+    /// Record a forwarded evidence request in the interaction log
+    async fn record_evidence_request(
+        &self,
+        validator_hotkey: &str,
+        executor_id: &str,
+        evidence_type: &str,
+        success: bool,
+    ) {
+        if let Err(e) = self
+            .db
+            .record_validator_interaction(
+                validator_hotkey,
+                "executor_evidence",
+                success,
+                Some(
+                    serde_json::json!({
+                        "executor_id": executor_id,
+                        "evidence_type": evidence_type,
+                    })
+                    .to_string(),
+                ),
+            )
+            .await
+        {
+            error!("Failed to record evidence request: {}", e);
+        }
+    }
+}
+
 #[tonic::async_trait]
 impl MinerDiscovery for MinerDiscoveryService {
     /// Authenticate a validator using Bittensor signature
@@ -711,6 +778,141 @@ impl MinerDiscovery for MinerDiscoveryService {
             }
         }
     }
+
+    /// Forward a container operation to an executor
+    async fn manage_executor_container(
+        &self,
+        request: Request<ExecutorContainerRequest>,
+    ) -> Result<Response<protocol::executor_control::ContainerOpResponse>, Status> {
+        let req = request.into_inner();
+
+        debug!(
+            "Received container request from validator {} for executor {}",
+            req.validator_hotkey, req.executor_id
+        );
+
+        let executor = self
+            .authorize_executor_request(&req.validator_hotkey, &req.session_token, &req.executor_id)
+            .await?;
+
+        let operation = req
+            .operation
+            .ok_or_else(|| Status::invalid_argument("Missing container operation"))?;
+
+        let operation_name = operation.operation.clone();
+        let container_id = operation.container_id.clone();
+        let client = ExecutorGrpcClient::new(ExecutorGrpcConfig::default());
+        let result = client
+            .manage_container(&executor.grpc_address, operation)
+            .await;
+
+        if let Err(e) = self
+            .db
+            .record_validator_interaction(
+                &req.validator_hotkey,
+                "container_operation",
+                result.as_ref().is_ok_and(|r| r.success),
+                Some(
+                    serde_json::json!({
+                        "executor_id": req.executor_id,
+                        "operation": operation_name,
+                        "container_id": container_id,
+                    })
+                    .to_string(),
+                ),
+            )
+            .await
+        {
+            error!("Failed to record container operation: {}", e);
+        }
+
+        match result {
+            Ok(response) => {
+                info!(
+                    "Container operation '{}' on executor {} completed (success: {})",
+                    operation_name, req.executor_id, response.success
+                );
+                Ok(Response::new(response))
+            }
+            Err(e) => {
+                error!("Failed to forward container operation: {}", e);
+                Err(Status::unavailable(format!(
+                    "Failed to reach executor {}: {e}",
+                    req.executor_id
+                )))
+            }
+        }
+    }
+
+    /// Forward a signed system profile request to an executor
+    ///
+    /// The response is passed through untouched so the validator can check
+    /// the executor's signature.
+    async fn execute_executor_system_profile(
+        &self,
+        request: Request<ExecutorSystemProfileRequest>,
+    ) -> Result<Response<protocol::executor_control::SystemProfileResponse>, Status> {
+        let req = request.into_inner();
+        let executor = self
+            .authorize_executor_request(&req.validator_hotkey, &req.session_token, &req.executor_id)
+            .await?;
+
+        let mut profile_request = req
+            .request
+            .ok_or_else(|| Status::invalid_argument("Missing system profile request"))?;
+        profile_request.validator_hotkey = req.validator_hotkey.clone();
+
+        let client = ExecutorGrpcClient::new(ExecutorGrpcConfig::default());
+        let result = client
+            .execute_system_profile(&executor.grpc_address, profile_request)
+            .await;
+        self.record_evidence_request(
+            &req.validator_hotkey,
+            &req.executor_id,
+            "system_profile",
+            result.is_ok(),
+        )
+        .await;
+
+        result.map(Response::new).map_err(|e| {
+            error!("Failed to forward system profile request: {}", e);
+            Status::unavailable(format!("Failed to reach executor {}: {e}", req.executor_id))
+        })
+    }
+
+    /// Forward a signed benchmark request to an executor
+    async fn execute_executor_benchmark(
+        &self,
+        request: Request<ExecutorBenchmarkRequest>,
+    ) -> Result<Response<protocol::executor_control::BenchmarkResponse>, Status> {
+        let req = request.into_inner();
+        let executor = self
+            .authorize_executor_request(&req.validator_hotkey, &req.session_token, &req.executor_id)
+            .await?;
+
+        let mut benchmark_request = req
+            .request
+            .ok_or_else(|| Status::invalid_argument("Missing benchmark request"))?;
+        benchmark_request.validator_hotkey = req.validator_hotkey.clone();
+        let evidence_type = format!("benchmark:{}", benchmark_request.benchmark_type);
+
+        let client = ExecutorGrpcClient::new(ExecutorGrpcConfig::default());
+        let result = client
+            .execute_benchmark(&executor.grpc_address, benchmark_request)
+            .await;
+        self.record_evidence_request(
+            &req.validator_hotkey,
+            &req.executor_id,
+            &evidence_type,
+            result.is_ok(),
+        )
+        .await;
+
+        result.map(Response::new).map_err(|e| {
+            error!("Failed to forward benchmark request: {}", e);
+            Status::unavailable(format!("Failed to reach executor {}: {e}", req.executor_id))
+        })
+    }
 }
 
 /// Create GPU spec from available executor information
@@ -378,7 +378,7 @@ pub struct ContainerSpec {
         ::prost::alloc::string::String,
         ::prost::alloc::string::String,
     >,
-    /// Volume mounts (host_path -> container_path)
+    /// Named volumes to mount (volume name -> container_path, suffix ":ro" for read-only)
     #[prost(map = "string, string", tag = "4")]
     pub volume_mounts: ::std::collections::HashMap<
         ::prost::alloc::string::String,
@@ -396,12 +396,30 @@ pub struct ContainerSpec {
     /// User to run as
     #[prost(string, tag = "8")]
     pub user: ::prost::alloc::string::String,
-    /// GPU requirements
+    /// UUIDs of the GPUs to assign, or just "all" for every GPU of the executor
     #[prost(string, repeated, tag = "9")]
     pub gpu_requirements: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     /// Network mode
     #[prost(string, tag = "10")]
     pub network_mode: ::prost::alloc::string::String,
+    /// Destinations the container may reach, narrowing the executor's egress
+    /// policy; the executor's own allow-list applies when empty
+    #[prost(message, repeated, tag = "11")]
+    pub egress_rules: ::prost::alloc::vec::Vec<EgressRule>,
+}
+/// Destination a container may send traffic to
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EgressRule {
+    /// IPv4 address or CIDR block
+    #[prost(string, tag = "1")]
+    pub cidr: ::prost::alloc::string::String,
+    /// Destination ports, every port when empty
+    #[prost(uint32, repeated, tag = "2")]
+    pub ports: ::prost::alloc::vec::Vec<u32>,
+    /// "tcp" or "udp", both when empty
+    #[prost(string, tag = "3")]
+    pub protocol: ::prost::alloc::string::String,
 }
 /// Container status information
 #[allow(clippy::derive_partial_eq_without_eq)]
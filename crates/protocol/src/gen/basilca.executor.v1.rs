@@ -66,6 +66,9 @@ pub struct SystemProfileRequest {
     /// Requesting validator hotkey
     #[prost(string, tag = "5")]
     pub validator_hotkey: ::prost::alloc::string::String,
+    /// Validator-chosen nonce the executor signs the response over
+    #[prost(string, tag = "6")]
+    pub nonce: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -85,6 +88,15 @@ pub struct SystemProfileResponse {
     /// Error information if failed
     #[prost(message, optional, tag = "5")]
     pub error: ::core::option::Option<super::super::common::v1::ErrorInfo>,
+    /// Nonce from the request
+    #[prost(string, tag = "6")]
+    pub nonce: ::prost::alloc::string::String,
+    /// Hex-encoded Ed25519 public key of the executor
+    #[prost(string, tag = "7")]
+    pub executor_public_key: ::prost::alloc::string::String,
+    /// Hex-encoded signature over the nonce, validator hotkey and profile
+    #[prost(string, tag = "8")]
+    pub signature: ::prost::alloc::string::String,
 }
 /// Benchmark execution request
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -105,6 +117,9 @@ pub struct BenchmarkRequest {
     /// Requesting validator hotkey
     #[prost(string, tag = "4")]
     pub validator_hotkey: ::prost::alloc::string::String,
+    /// Validator-chosen nonce the executor signs the response over
+    #[prost(string, tag = "5")]
+    pub nonce: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -124,12 +139,22 @@ pub struct BenchmarkResponse {
     /// Error information if failed
     #[prost(message, optional, tag = "4")]
     pub error: ::core::option::Option<super::super::common::v1::ErrorInfo>,
+    /// Nonce from the request
+    #[prost(string, tag = "5")]
+    pub nonce: ::prost::alloc::string::String,
+    /// Hex-encoded Ed25519 public key of the executor
+    #[prost(string, tag = "6")]
+    pub executor_public_key: ::prost::alloc::string::String,
+    /// Hex-encoded signature over the nonce, validator hotkey, type, score and results
+    #[prost(string, tag = "7")]
+    pub signature: ::prost::alloc::string::String,
 }
 /// Container operation request
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ContainerOpRequest {
-    /// Operation type (create, delete, start, stop, add_key, get_status)
+    /// Operation type (create, delete, start, stop, add_key, get_status,
+    /// create_volume, list_volumes, snapshot_volume, delete_volume)
     #[prost(string, tag = "1")]
     pub operation: ::prost::alloc::string::String,
     /// Container specification (for create)
@@ -193,6 +218,204 @@ pub struct LogSubscriptionRequest {
     #[prost(string, tag = "6")]
     pub validator_hotkey: ::prost::alloc::string::String,
 }
+/// Command to start in a container
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecStart {
+    /// Container to run the command in
+    #[prost(string, tag = "1")]
+    pub container_id: ::prost::alloc::string::String,
+    /// Command and arguments
+    #[prost(string, repeated, tag = "2")]
+    pub command: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Allocate a pseudo-terminal, merging stderr into stdout
+    #[prost(bool, tag = "3")]
+    pub tty: bool,
+    /// Additional environment variables
+    #[prost(map = "string, string", tag = "4")]
+    pub environment: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+    /// Working directory, the container's default when empty
+    #[prost(string, tag = "5")]
+    pub working_dir: ::prost::alloc::string::String,
+    /// Initial terminal size (with tty)
+    #[prost(message, optional, tag = "6")]
+    pub terminal_size: ::core::option::Option<TerminalSize>,
+    /// Requesting validator hotkey, which must own the container and hold an
+    /// active access grant
+    #[prost(string, tag = "7")]
+    pub validator_hotkey: ::prost::alloc::string::String,
+    /// Signature of this message by validator_hotkey
+    #[prost(message, optional, tag = "8")]
+    pub signature: ::core::option::Option<RequestSignature>,
+}
+/// Signature of a container I/O request by the requesting validator's hotkey
+///
+/// The hex sr25519 signature covers the canonical request built from the
+/// operation, the container, the request message encoded with its signature
+/// left empty, the timestamp and the nonce.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestSignature {
+    /// Hex-encoded signature
+    #[prost(string, tag = "1")]
+    pub signature: ::prost::alloc::string::String,
+    /// Unix timestamp the request was signed at
+    #[prost(int64, tag = "2")]
+    pub timestamp: i64,
+    /// Random nonce, accepted once
+    #[prost(string, tag = "3")]
+    pub nonce: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TerminalSize {
+    #[prost(uint32, tag = "1")]
+    pub rows: u32,
+    #[prost(uint32, tag = "2")]
+    pub cols: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecRequest {
+    #[prost(oneof = "exec_request::Payload", tags = "1, 2, 3, 4")]
+    pub payload: ::core::option::Option<exec_request::Payload>,
+}
+/// Nested message and enum types in `ExecRequest`.
+pub mod exec_request {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        /// Command to run, must be the first request
+        #[prost(message, tag = "1")]
+        Start(super::ExecStart),
+        /// Bytes to write to the command's stdin
+        #[prost(bytes, tag = "2")]
+        Stdin(::prost::alloc::vec::Vec<u8>),
+        /// New terminal size (with tty)
+        #[prost(message, tag = "3")]
+        Resize(super::TerminalSize),
+        /// Close the command's stdin
+        #[prost(bool, tag = "4")]
+        CloseStdin(bool),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecExit {
+    /// Exit code of the command, -1 if unknown
+    #[prost(int32, tag = "1")]
+    pub exit_code: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecResponse {
+    #[prost(oneof = "exec_response::Payload", tags = "1, 2, 3")]
+    pub payload: ::core::option::Option<exec_response::Payload>,
+}
+/// Nested message and enum types in `ExecResponse`.
+pub mod exec_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        #[prost(bytes, tag = "1")]
+        Stdout(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "2")]
+        Stderr(::prost::alloc::vec::Vec<u8>),
+        /// The command finished, always the last response
+        #[prost(message, tag = "3")]
+        Exit(super::ExecExit),
+    }
+}
+/// Describes the file being transferred
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FileTransferHeader {
+    /// Container holding the file
+    #[prost(string, tag = "1")]
+    pub container_id: ::prost::alloc::string::String,
+    /// Absolute path of the file in the container
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    /// File size in bytes
+    #[prost(uint64, tag = "3")]
+    pub size: u64,
+    /// Unix permission bits, 0644 when zero on upload
+    #[prost(uint32, tag = "4")]
+    pub mode: u32,
+    /// Expected hex SHA-256 of the contents (optional, upload only)
+    #[prost(string, tag = "5")]
+    pub sha256: ::prost::alloc::string::String,
+    /// Requesting validator hotkey, which must own the container and hold an
+    /// active access grant (upload only)
+    #[prost(string, tag = "6")]
+    pub validator_hotkey: ::prost::alloc::string::String,
+    /// Signature of this header by validator_hotkey (upload only)
+    #[prost(message, optional, tag = "7")]
+    pub signature: ::core::option::Option<RequestSignature>,
+}
+/// Ends a transfer with the checksum of the data sent
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FileTransferTrailer {
+    /// Hex SHA-256 of the contents
+    #[prost(string, tag = "1")]
+    pub sha256: ::prost::alloc::string::String,
+    /// Bytes sent
+    #[prost(uint64, tag = "2")]
+    pub size: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FileChunk {
+    #[prost(oneof = "file_chunk::Payload", tags = "1, 2, 3")]
+    pub payload: ::core::option::Option<file_chunk::Payload>,
+}
+/// Nested message and enum types in `FileChunk`.
+pub mod file_chunk {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        /// Must be the first chunk
+        #[prost(message, tag = "1")]
+        Header(super::FileTransferHeader),
+        /// File contents
+        #[prost(bytes, tag = "2")]
+        Data(::prost::alloc::vec::Vec<u8>),
+        /// Optional on upload, always the last chunk on download
+        #[prost(message, tag = "3")]
+        Trailer(super::FileTransferTrailer),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FileTransferResponse {
+    /// Bytes written into the container
+    #[prost(uint64, tag = "1")]
+    pub bytes_written: u64,
+    /// Hex SHA-256 of the bytes written
+    #[prost(string, tag = "2")]
+    pub sha256: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FileDownloadRequest {
+    /// Container holding the file
+    #[prost(string, tag = "1")]
+    pub container_id: ::prost::alloc::string::String,
+    /// Absolute path of the file in the container
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    /// Requesting validator hotkey, which must own the container and hold an
+    /// active access grant
+    #[prost(string, tag = "3")]
+    pub validator_hotkey: ::prost::alloc::string::String,
+    /// Signature of this request by validator_hotkey
+    #[prost(message, optional, tag = "4")]
+    pub signature: ::core::option::Option<RequestSignature>,
+}
 /// Health check request
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -476,6 +699,92 @@ pub mod executor_control_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Run a command in a container interactively. The first request must
+        /// carry `start`; later ones carry stdin, terminal resizes or close_stdin.
+        pub async fn exec(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::ExecRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ExecResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.executor.v1.ExecutorControl/Exec",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("basilca.executor.v1.ExecutorControl", "Exec"));
+            self.inner.streaming(req, path, codec).await
+        }
+        /// Upload a file into a container. The first chunk must carry `header`.
+        pub async fn upload_file(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::FileChunk>,
+        ) -> std::result::Result<
+            tonic::Response<super::FileTransferResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.executor.v1.ExecutorControl/UploadFile",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("basilca.executor.v1.ExecutorControl", "UploadFile"),
+                );
+            self.inner.client_streaming(req, path, codec).await
+        }
+        /// Download a file from a container as a header, data chunks and a trailer
+        pub async fn download_file(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FileDownloadRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::FileChunk>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.executor.v1.ExecutorControl/DownloadFile",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "basilca.executor.v1.ExecutorControl",
+                        "DownloadFile",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
         /// Health check and heartbeat
         pub async fn health_check(
             &mut self,
@@ -561,6 +870,40 @@ pub mod executor_control_server {
             &self,
             request: tonic::Request<super::LogSubscriptionRequest>,
         ) -> std::result::Result<tonic::Response<Self::StreamLogsStream>, tonic::Status>;
+        /// Server streaming response type for the Exec method.
+        type ExecStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::ExecResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Run a command in a container interactively. The first request must
+        /// carry `start`; later ones carry stdin, terminal resizes or close_stdin.
+        async fn exec(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::ExecRequest>>,
+        ) -> std::result::Result<tonic::Response<Self::ExecStream>, tonic::Status>;
+        /// Upload a file into a container. The first chunk must carry `header`.
+        async fn upload_file(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::FileChunk>>,
+        ) -> std::result::Result<
+            tonic::Response<super::FileTransferResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the DownloadFile method.
+        type DownloadFileStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::FileChunk, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Download a file from a container as a header, data chunks and a trailer
+        async fn download_file(
+            &self,
+            request: tonic::Request<super::FileDownloadRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::DownloadFileStream>,
+            tonic::Status,
+        >;
         /// Health check and heartbeat
         async fn health_check(
             &self,
@@ -894,6 +1237,149 @@ pub mod executor_control_server {
                     };
                     Box::pin(fut)
                 }
+                "/basilca.executor.v1.ExecutorControl/Exec" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExecSvc<T: ExecutorControl>(pub Arc<T>);
+                    impl<
+                        T: ExecutorControl,
+                    > tonic::server::StreamingService<super::ExecRequest>
+                    for ExecSvc<T> {
+                        type Response = super::ExecResponse;
+                        type ResponseStream = T::ExecStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::ExecRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ExecutorControl>::exec(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExecSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/basilca.executor.v1.ExecutorControl/UploadFile" => {
+                    #[allow(non_camel_case_types)]
+                    struct UploadFileSvc<T: ExecutorControl>(pub Arc<T>);
+                    impl<
+                        T: ExecutorControl,
+                    > tonic::server::ClientStreamingService<super::FileChunk>
+                    for UploadFileSvc<T> {
+                        type Response = super::FileTransferResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::FileChunk>>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ExecutorControl>::upload_file(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UploadFileSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/basilca.executor.v1.ExecutorControl/DownloadFile" => {
+                    #[allow(non_camel_case_types)]
+                    struct DownloadFileSvc<T: ExecutorControl>(pub Arc<T>);
+                    impl<
+                        T: ExecutorControl,
+                    > tonic::server::ServerStreamingService<super::FileDownloadRequest>
+                    for DownloadFileSvc<T> {
+                        type Response = super::FileChunk;
+                        type ResponseStream = T::DownloadFileStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FileDownloadRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ExecutorControl>::download_file(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DownloadFileSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/basilca.executor.v1.ExecutorControl/HealthCheck" => {
                     #[allow(non_camel_case_types)]
                     struct HealthCheckSvc<T: ExecutorControl>(pub Arc<T>);
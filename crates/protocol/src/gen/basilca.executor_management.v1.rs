@@ -106,6 +106,29 @@ pub struct StatusResponse {
     /// Uptime
     #[prost(uint64, tag = "8")]
     pub uptime_seconds: u64,
+    /// GPUs assigned to containers
+    #[prost(message, repeated, tag = "9")]
+    pub gpu_assignments: ::prost::alloc::vec::Vec<GpuAssignment>,
+    /// UUIDs of GPUs not assigned to any container
+    #[prost(string, repeated, tag = "10")]
+    pub free_gpu_uuids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// GPUs held by one container
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GpuAssignment {
+    /// Container ID
+    #[prost(string, tag = "1")]
+    pub container_id: ::prost::alloc::string::String,
+    /// Assigned GPU UUIDs
+    #[prost(string, repeated, tag = "2")]
+    pub gpu_uuids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Assigned GPU indices, in the same order as gpu_uuids
+    #[prost(uint32, repeated, tag = "3")]
+    pub gpu_indices: ::prost::alloc::vec::Vec<u32>,
+    /// Unix timestamp of the assignment
+    #[prost(int64, tag = "4")]
+    pub assigned_at: i64,
 }
 /// Generated client implementations.
 pub mod executor_management_client {
@@ -171,6 +171,48 @@ pub struct SshSession {
     #[prost(enumeration = "SshSessionStatus", tag = "6")]
     pub status: i32,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecutorContainerRequest {
+    #[prost(string, tag = "1")]
+    pub validator_hotkey: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub session_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub executor_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub operation: ::core::option::Option<
+        super::super::executor::v1::ContainerOpRequest,
+    >,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecutorSystemProfileRequest {
+    #[prost(string, tag = "1")]
+    pub validator_hotkey: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub session_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub executor_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub request: ::core::option::Option<
+        super::super::executor::v1::SystemProfileRequest,
+    >,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecutorBenchmarkRequest {
+    #[prost(string, tag = "1")]
+    pub validator_hotkey: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub session_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub executor_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub request: ::core::option::Option<
+        super::super::executor::v1::BenchmarkRequest,
+    >,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum SshSessionStatus {
@@ -467,6 +509,93 @@ pub mod miner_discovery_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Container management on an executor, forwarded to ExecutorControl.ManageContainer
+        pub async fn manage_executor_container(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExecutorContainerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::super::executor::v1::ContainerOpResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.miner.v1.MinerDiscovery/ManageExecutorContainer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "basilca.miner.v1.MinerDiscovery",
+                        "ManageExecutorContainer",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Signed system profile of an executor, forwarded to ExecutorControl.ExecuteSystemProfile
+        pub async fn execute_executor_system_profile(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExecutorSystemProfileRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::super::executor::v1::SystemProfileResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.miner.v1.MinerDiscovery/ExecuteExecutorSystemProfile",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("basilca.miner.v1.MinerDiscovery", "ExecuteExecutorSystemProfile"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Signed benchmark on an executor, forwarded to ExecutorControl.ExecuteBenchmark
+        pub async fn execute_executor_benchmark(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExecutorBenchmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::super::executor::v1::BenchmarkResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.miner.v1.MinerDiscovery/ExecuteExecutorBenchmark",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("basilca.miner.v1.MinerDiscovery", "ExecuteExecutorBenchmark"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -522,6 +651,30 @@ pub mod miner_discovery_server {
             tonic::Response<super::ListSshSessionsResponse>,
             tonic::Status,
         >;
+        /// Container management on an executor, forwarded to ExecutorControl.ManageContainer
+        async fn manage_executor_container(
+            &self,
+            request: tonic::Request<super::ExecutorContainerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::super::executor::v1::ContainerOpResponse>,
+            tonic::Status,
+        >;
+        /// Signed system profile of an executor, forwarded to ExecutorControl.ExecuteSystemProfile
+        async fn execute_executor_system_profile(
+            &self,
+            request: tonic::Request<super::ExecutorSystemProfileRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::super::executor::v1::SystemProfileResponse>,
+            tonic::Status,
+        >;
+        /// Signed benchmark on an executor, forwarded to ExecutorControl.ExecuteBenchmark
+        async fn execute_executor_benchmark(
+            &self,
+            request: tonic::Request<super::ExecutorBenchmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::super::executor::v1::BenchmarkResponse>,
+            tonic::Status,
+        >;
     }
     /// MinerDiscovery service for validator-miner interaction
     #[derive(Debug)]
@@ -894,6 +1047,156 @@ pub mod miner_discovery_server {
                     };
                     Box::pin(fut)
                 }
+                "/basilca.miner.v1.MinerDiscovery/ManageExecutorContainer" => {
+                    #[allow(non_camel_case_types)]
+                    struct ManageExecutorContainerSvc<T: MinerDiscovery>(pub Arc<T>);
+                    impl<
+                        T: MinerDiscovery,
+                    > tonic::server::UnaryService<super::ExecutorContainerRequest>
+                    for ManageExecutorContainerSvc<T> {
+                        type Response = super::super::super::executor::v1::ContainerOpResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExecutorContainerRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MinerDiscovery>::manage_executor_container(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ManageExecutorContainerSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+"/basilca.miner.v1.MinerDiscovery/ExecuteExecutorSystemProfile" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExecuteExecutorSystemProfileSvc<T: MinerDiscovery>(pub Arc<T>);
+                    impl<
+                        T: MinerDiscovery,
+                    > tonic::server::UnaryService<super::ExecutorSystemProfileRequest>
+                    for ExecuteExecutorSystemProfileSvc<T> {
+                        type Response = super::super::super::executor::v1::SystemProfileResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExecutorSystemProfileRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MinerDiscovery>::execute_executor_system_profile(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExecuteExecutorSystemProfileSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+"/basilca.miner.v1.MinerDiscovery/ExecuteExecutorBenchmark" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExecuteExecutorBenchmarkSvc<T: MinerDiscovery>(pub Arc<T>);
+                    impl<
+                        T: MinerDiscovery,
+                    > tonic::server::UnaryService<super::ExecutorBenchmarkRequest>
+                    for ExecuteExecutorBenchmarkSvc<T> {
+                        type Response = super::super::super::executor::v1::BenchmarkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExecutorBenchmarkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MinerDiscovery>::execute_executor_benchmark(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExecuteExecutorBenchmarkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -12,6 +12,8 @@
 //! - Run computational challenges
 //! - Manage containers
 //! - Stream logs
+//! - Run interactive commands in containers
+//! - Upload and download container files
 //!
 //! ### MinerDiscovery  
 //! Service for Validator ↔ Miner coordination. Allows validators to:
@@ -396,6 +398,7 @@ mod tests {
             user: "root".to_string(),
             gpu_requirements: vec!["nvidia".to_string()],
             network_mode: "bridge".to_string(),
+            egress_rules: vec![],
         };
 
         assert!(utils::validate_container_spec(&spec).is_ok());
@@ -1,7 +1,12 @@
 //! Request aggregation module
 
+mod policy;
 mod request_distributor;
 mod response_aggregator;
 
+pub use policy::{
+    merge_listings, AggregationPolicy, ListingSchema, MergedListing, ValidatorListing,
+    EXECUTOR_LISTING, MINER_LISTING,
+};
 pub use request_distributor::RequestDistributor;
 pub use response_aggregator::ResponseAggregator;
@@ -0,0 +1,532 @@
+//! Aggregation policies for listings queried from several validators
+//!
+//! Items are matched across validators by ID. The policy decides which items
+//! make it into the merged listing and which value wins for each of their
+//! fields. Only the fields of a listing's [`ListingSchema`] that validators
+//! are expected to agree on count towards a quorum and are reported when they
+//! diverge; the others, such as when a validator last saw a miner, are each
+//! validator's own observation.
+
+use crate::{
+    api::types::{AggregationReport, FieldDisagreement, ItemDisagreement, ReportedValue},
+    config::{AggregationConfig, AggregationMode},
+    discovery::ValidatorInfo,
+    error::{Error, Result},
+};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use tracing::warn;
+
+/// Aggregation policy applied to one request
+#[derive(Debug, Clone, Copy)]
+pub struct AggregationPolicy {
+    /// How listings are merged
+    pub mode: AggregationMode,
+    /// Agreeing validators required in `quorum` mode
+    pub quorum: usize,
+}
+
+impl AggregationPolicy {
+    /// Configured policy with a request's overrides applied
+    pub fn resolve(
+        config: &AggregationConfig,
+        mode: Option<AggregationMode>,
+        quorum: Option<usize>,
+    ) -> Result<Self> {
+        let policy = Self {
+            mode: mode.unwrap_or(config.mode),
+            quorum: quorum.unwrap_or(config.quorum),
+        };
+
+        if policy.quorum == 0 {
+            return Err(Error::InvalidRequest {
+                message: "quorum must be at least 1".to_string(),
+            });
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Shape of a listing merged across validators
+#[derive(Debug, Clone, Copy)]
+pub struct ListingSchema {
+    /// Field of the validator response holding the items
+    pub list_field: &'static str,
+    /// Field identifying an item
+    pub id_field: &'static str,
+    /// Fields validators must report identically to agree on an item
+    pub consensus_fields: &'static [&'static str],
+}
+
+/// Executors listed by `GET /executors`
+pub const EXECUTOR_LISTING: ListingSchema = ListingSchema {
+    list_field: "executors",
+    id_field: "id",
+    consensus_fields: &["gpu_specs", "cpu_specs", "location"],
+};
+
+/// Miners listed by `GET /miners`
+///
+/// Scores, uptime and timestamps are measured by each validator and differ
+/// between them even when they agree on the miner.
+pub const MINER_LISTING: ListingSchema = ListingSchema {
+    list_field: "miners",
+    id_field: "miner_id",
+    consensus_fields: &["hotkey", "endpoint", "executor_count", "total_gpu_count"],
+};
+
+/// Items listed by one validator
+#[derive(Debug)]
+pub struct ValidatorListing {
+    /// Validator that answered
+    pub validator: ValidatorInfo,
+    /// Listed items
+    pub items: Vec<Value>,
+}
+
+/// Listing merged from several validators
+#[derive(Debug)]
+pub struct MergedListing {
+    /// Merged items ordered by ID
+    pub items: Vec<Value>,
+    /// How the items were merged
+    pub report: AggregationReport,
+}
+
+/// One validator's copy of an item
+struct ItemReport<'a> {
+    uid: u16,
+    weight: f64,
+    stake: f64,
+    fields: &'a Map<String, Value>,
+}
+
+/// Validators reporting one value of a field
+struct Vote<'a> {
+    value: &'a Value,
+    weight: f64,
+    stake: f64,
+    validators: Vec<u16>,
+}
+
+/// Merge the listings of the validators that answered
+///
+/// `validators_queried` counts all validators asked, including those that did
+/// not answer. Items are matched by the schema's `id_field`.
+pub fn merge_listings(
+    policy: AggregationPolicy,
+    listings: &[ValidatorListing],
+    validators_queried: usize,
+    schema: &ListingSchema,
+) -> Result<MergedListing> {
+    let id_field = schema.id_field;
+    let responded: Vec<u16> = listings.iter().map(|l| l.validator.uid).collect();
+    if policy.mode == AggregationMode::Quorum && responded.len() < policy.quorum {
+        return Err(Error::QuorumNotMet {
+            message: format!(
+                "{} of {} validators responded, {} required",
+                responded.len(),
+                validators_queried,
+                policy.quorum
+            ),
+        });
+    }
+
+    // Votes are weighted by stake in stake-weighted mode, unless no stake is known
+    let by_stake = policy.mode == AggregationMode::StakeWeighted
+        && listings.iter().any(|l| l.validator.stake > 0.0);
+    let weight = |validator: &ValidatorInfo| {
+        if by_stake {
+            validator.stake
+        } else {
+            1.0
+        }
+    };
+    let total_weight: f64 = listings.iter().map(|l| weight(&l.validator)).sum();
+
+    let mut reports: BTreeMap<String, Vec<ItemReport>> = BTreeMap::new();
+    for listing in listings {
+        let mut seen = HashSet::new();
+        for item in &listing.items {
+            let Some((id, fields)) = item_with_id(item, id_field) else {
+                warn!(
+                    "Ignoring item without {} from validator {}",
+                    id_field, listing.validator.uid
+                );
+                continue;
+            };
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            reports.entry(id).or_default().push(ItemReport {
+                uid: listing.validator.uid,
+                weight: weight(&listing.validator),
+                stake: listing.validator.stake,
+                fields,
+            });
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut disagreements = Vec::new();
+    let mut excluded_items = 0;
+
+    for (id, reports) in reports {
+        let support: f64 = reports.iter().map(|r| r.weight).sum();
+        let mut included = match policy.mode {
+            AggregationMode::Union => true,
+            AggregationMode::Majority | AggregationMode::StakeWeighted => {
+                support * 2.0 > total_weight
+            }
+            AggregationMode::Quorum => reports.len() >= policy.quorum,
+        };
+
+        let field_names: BTreeSet<&String> = reports.iter().flat_map(|r| r.fields.keys()).collect();
+        let mut merged = Map::new();
+        let mut fields = Vec::new();
+
+        for field in field_names {
+            let votes = tally(&reports, field);
+            merged.insert(field.clone(), votes[0].value.clone());
+
+            // Observations of single validators are merged but not compared
+            if !schema.consensus_fields.contains(&field.as_str()) {
+                continue;
+            }
+            if policy.mode == AggregationMode::Quorum && votes[0].validators.len() < policy.quorum {
+                included = false;
+            }
+
+            if votes.len() > 1 {
+                fields.push(FieldDisagreement {
+                    field: field.clone(),
+                    values: votes
+                        .into_iter()
+                        .map(|vote| ReportedValue {
+                            value: vote.value.clone(),
+                            validators: vote.validators,
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        let reported_by: Vec<u16> = reports.iter().map(|r| r.uid).collect();
+        let missing_from: Vec<u16> = responded
+            .iter()
+            .filter(|uid| !reported_by.contains(uid))
+            .copied()
+            .collect();
+
+        if !fields.is_empty() || !missing_from.is_empty() {
+            disagreements.push(ItemDisagreement {
+                id,
+                included,
+                reported_by,
+                missing_from,
+                fields,
+            });
+        }
+
+        if included {
+            items.push(Value::Object(merged));
+        } else {
+            excluded_items += 1;
+        }
+    }
+
+    Ok(MergedListing {
+        items,
+        report: AggregationReport {
+            mode: policy.mode,
+            quorum: (policy.mode == AggregationMode::Quorum).then_some(policy.quorum),
+            validators_queried,
+            validators_responded: responded.len(),
+            excluded_items,
+            disagreements,
+        },
+    })
+}
+
+/// The item's ID and fields, `None` if it is not an object with an ID
+fn item_with_id<'a>(item: &'a Value, id_field: &str) -> Option<(String, &'a Map<String, Value>)> {
+    let fields = item.as_object()?;
+    let id = match fields.get(id_field)? {
+        Value::String(id) => id.clone(),
+        Value::Number(id) => id.to_string(),
+        _ => return None,
+    };
+    Some((id, fields))
+}
+
+/// Votes for the values of a field, the winning value first
+///
+/// Ties go to the value backed by more stake, then to the value reported
+/// first.
+fn tally<'a>(reports: &[ItemReport<'a>], field: &str) -> Vec<Vote<'a>> {
+    let mut votes: Vec<Vote<'a>> = Vec::new();
+    for report in reports {
+        let Some(value) = report.fields.get(field) else {
+            continue;
+        };
+        match votes.iter_mut().find(|vote| vote.value == value) {
+            Some(vote) => {
+                vote.weight += report.weight;
+                vote.stake += report.stake;
+                vote.validators.push(report.uid);
+            }
+            None => votes.push(Vote {
+                value,
+                weight: report.weight,
+                stake: report.stake,
+                validators: vec![report.uid],
+            }),
+        }
+    }
+
+    votes.sort_by(|a, b| {
+        b.weight
+            .total_cmp(&a.weight)
+            .then_with(|| b.stake.total_cmp(&a.stake))
+    });
+    votes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn validator(uid: u16, stake: f64) -> ValidatorInfo {
+        ValidatorInfo {
+            uid,
+            hotkey: format!("validator-{uid}"),
+            endpoint: format!("http://validator-{uid}:8080"),
+            score: 0.75,
+            stake,
+            is_active: true,
+            last_health_check: None,
+            is_healthy: true,
+            failure_count: 0,
+        }
+    }
+
+    fn listing(uid: u16, stake: f64, items: Value) -> ValidatorListing {
+        ValidatorListing {
+            validator: validator(uid, stake),
+            items: items.as_array().unwrap().clone(),
+        }
+    }
+
+    fn policy(mode: AggregationMode, quorum: usize) -> AggregationPolicy {
+        AggregationPolicy { mode, quorum }
+    }
+
+    const LOCATIONS: ListingSchema = ListingSchema {
+        list_field: "items",
+        id_field: "id",
+        consensus_fields: &["location"],
+    };
+
+    /// Three validators: 1 and 2 agree, 3 reports a different location
+    /// for `a` and an extra executor `b`
+    fn listings() -> Vec<ValidatorListing> {
+        vec![
+            listing(1, 10.0, json!([{"id": "a", "location": "us"}])),
+            listing(2, 10.0, json!([{"id": "a", "location": "us"}])),
+            listing(
+                3,
+                100.0,
+                json!([{"id": "a", "location": "eu"}, {"id": "b", "location": "eu"}]),
+            ),
+        ]
+    }
+
+    fn ids(merged: &MergedListing) -> Vec<&str> {
+        merged
+            .items
+            .iter()
+            .map(|item| item["id"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_union_majority_and_disagreements() {
+        let merged = merge_listings(
+            policy(AggregationMode::Union, 2),
+            &listings(),
+            4,
+            &LOCATIONS,
+        )
+        .unwrap();
+        assert_eq!(ids(&merged), ["a", "b"]);
+        assert_eq!(merged.items[0]["location"], "us");
+
+        let report = &merged.report;
+        assert_eq!(report.validators_queried, 4);
+        assert_eq!(report.validators_responded, 3);
+        assert_eq!(report.quorum, None);
+        assert_eq!(report.disagreements.len(), 2);
+
+        let a = &report.disagreements[0];
+        assert_eq!(a.id, "a");
+        assert!(a.included);
+        assert_eq!(a.fields[0].field, "location");
+        assert_eq!(a.fields[0].values[0].value, "us");
+        assert_eq!(a.fields[0].values[0].validators, [1, 2]);
+        assert_eq!(a.fields[0].values[1].validators, [3]);
+
+        let b = &report.disagreements[1];
+        assert_eq!(b.missing_from, [1, 2]);
+
+        let merged = merge_listings(
+            policy(AggregationMode::Majority, 2),
+            &listings(),
+            3,
+            &LOCATIONS,
+        )
+        .unwrap();
+        assert_eq!(ids(&merged), ["a"]);
+        assert_eq!(merged.report.excluded_items, 1);
+        assert!(!merged.report.disagreements[1].included);
+    }
+
+    #[test]
+    fn test_stake_weighted() {
+        let merged = merge_listings(
+            policy(AggregationMode::StakeWeighted, 2),
+            &listings(),
+            3,
+            &LOCATIONS,
+        )
+        .unwrap();
+        assert_eq!(ids(&merged), ["a", "b"]);
+        assert_eq!(merged.items[0]["location"], "eu");
+
+        // Without known stake every validator counts once
+        let unstaked: Vec<_> = listings()
+            .into_iter()
+            .map(|mut l| {
+                l.validator.stake = 0.0;
+                l
+            })
+            .collect();
+        let merged = merge_listings(
+            policy(AggregationMode::StakeWeighted, 2),
+            &unstaked,
+            3,
+            &LOCATIONS,
+        )
+        .unwrap();
+        assert_eq!(ids(&merged), ["a"]);
+        assert_eq!(merged.items[0]["location"], "us");
+    }
+
+    #[test]
+    fn test_quorum() {
+        let merged = merge_listings(
+            policy(AggregationMode::Quorum, 2),
+            &listings(),
+            3,
+            &LOCATIONS,
+        )
+        .unwrap();
+        assert_eq!(ids(&merged), ["a"]);
+        assert_eq!(merged.report.quorum, Some(2));
+
+        // Three validators never agree on the location of `a`
+        let merged = merge_listings(
+            policy(AggregationMode::Quorum, 3),
+            &listings(),
+            3,
+            &LOCATIONS,
+        )
+        .unwrap();
+        assert!(merged.items.is_empty());
+        assert_eq!(merged.report.excluded_items, 2);
+
+        assert!(matches!(
+            merge_listings(
+                policy(AggregationMode::Quorum, 4),
+                &listings(),
+                4,
+                &LOCATIONS
+            ),
+            Err(Error::QuorumNotMet { .. })
+        ));
+    }
+
+    /// A miner as three validators report it, each with its own observations
+    fn miner(uid: u16, endpoint: &str) -> ValidatorListing {
+        let uid_f = f64::from(uid);
+        listing(
+            uid,
+            10.0,
+            json!([{
+                "miner_id": "miner_7",
+                "hotkey": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+                "endpoint": endpoint,
+                "status": "Active",
+                "executor_count": 2,
+                "total_gpu_count": 16,
+                "verification_score": 0.9 + uid_f / 100.0,
+                "uptime_percentage": 99.0 - uid_f / 10.0,
+                "last_seen": format!("2026-10-18T12:0{uid}:00Z"),
+                "registered_at": format!("2026-09-0{uid}T08:00:00Z"),
+            }]),
+        )
+    }
+
+    #[test]
+    fn test_quorum_ignores_validator_observations() {
+        let listings = vec![
+            miner(1, "http://203.0.113.7:8091"),
+            miner(2, "http://203.0.113.7:8091"),
+            miner(3, "http://203.0.113.7:8091"),
+        ];
+        let merged = merge_listings(
+            policy(AggregationMode::Quorum, 3),
+            &listings,
+            3,
+            &MINER_LISTING,
+        )
+        .unwrap();
+        assert_eq!(merged.items.len(), 1);
+        assert_eq!(merged.items[0]["total_gpu_count"], 16);
+        assert!(merged.items[0]["last_seen"].is_string());
+        assert!(merged.report.disagreements.is_empty());
+
+        // A miner reported at another endpoint is a real disagreement
+        let listings = vec![
+            miner(1, "http://203.0.113.7:8091"),
+            miner(2, "http://203.0.113.7:8091"),
+            miner(3, "http://198.51.100.4:8091"),
+        ];
+        let merged = merge_listings(
+            policy(AggregationMode::Quorum, 3),
+            &listings,
+            3,
+            &MINER_LISTING,
+        )
+        .unwrap();
+        assert!(merged.items.is_empty());
+        let disagreement = &merged.report.disagreements[0];
+        assert!(!disagreement.included);
+        let fields: Vec<&str> = disagreement
+            .fields
+            .iter()
+            .map(|f| f.field.as_str())
+            .collect();
+        assert_eq!(fields, ["endpoint"]);
+
+        let merged = merge_listings(
+            policy(AggregationMode::Quorum, 2),
+            &listings,
+            3,
+            &MINER_LISTING,
+        )
+        .unwrap();
+        assert_eq!(merged.items[0]["endpoint"], "http://203.0.113.7:8091");
+    }
+}
@@ -1,6 +1,9 @@
 //! Request distribution across validators
 
-use crate::{discovery::ValidatorInfo, error::Result, load_balancer::LoadBalancer, Error};
+use crate::{
+    discovery::ValidatorInfo, error::Result, load_balancer::LoadBalancer, signing::RequestSigner,
+    Error,
+};
 use futures::future::join_all;
 use reqwest::{Client, Request, Response};
 use std::sync::Arc;
@@ -12,26 +15,101 @@ pub struct RequestDistributor {
     /// HTTP client
     client: Client,
 
+    /// Signs requests to validators
+    signer: Arc<RequestSigner>,
+
     /// Load balancer
     load_balancer: Arc<RwLock<LoadBalancer>>,
 }
 
 impl RequestDistributor {
     /// Create a new request distributor
-    pub fn new(client: Client, load_balancer: Arc<RwLock<LoadBalancer>>) -> Self {
+    pub fn new(
+        client: Client,
+        signer: Arc<RequestSigner>,
+        load_balancer: Arc<RwLock<LoadBalancer>>,
+    ) -> Self {
         Self {
             client,
+            signer,
             load_balancer,
         }
     }
 
     /// Send a request to a single validator
-    pub async fn send_to_single(&self, request: Request) -> Result<Response> {
+    ///
+    /// `build` creates the request for the selected validator.
+    pub async fn send_to_single<F>(&self, build: F) -> Result<(ValidatorInfo, Response)>
+    where
+        F: FnOnce(&ValidatorInfo) -> Result<Request>,
+    {
         let validator = self.load_balancer.read().await.select_validator().await?;
+        let request = match build(&validator) {
+            Ok(request) => request,
+            Err(e) => {
+                // Nothing was sent, so the validator is not at fault
+                self.load_balancer
+                    .read()
+                    .await
+                    .report_success(validator.uid);
+                return Err(e);
+            }
+        };
+        let response = self.send_and_report(request, &validator).await?;
+        Ok((validator, response))
+    }
+
+    /// Send a request to the validator the load balancer maps `key` to
+    ///
+    /// `build` creates the request for the selected validator.
+    pub async fn send_to_single_with_key<F>(
+        &self,
+        key: &str,
+        build: F,
+    ) -> Result<(ValidatorInfo, Response)>
+    where
+        F: FnOnce(&ValidatorInfo) -> Result<Request>,
+    {
+        let validator = self
+            .load_balancer
+            .read()
+            .await
+            .select_validator_with_key(key)
+            .await?;
+        let request = match build(&validator) {
+            Ok(request) => request,
+            Err(e) => {
+                // Nothing was sent, so the validator is not at fault
+                self.load_balancer
+                    .read()
+                    .await
+                    .report_success(validator.uid);
+                return Err(e);
+            }
+        };
+        let response = self.send_and_report(request, &validator).await?;
+        Ok((validator, response))
+    }
+
+    /// Send a request to a given validator
+    pub async fn send_to_validator(
+        &self,
+        request: Request,
+        validator: &ValidatorInfo,
+    ) -> Result<Response> {
+        self.load_balancer.read().await.track_request(validator.uid);
+        self.send_and_report(request, validator).await
+    }
 
+    /// Send a request and report its outcome to the load balancer
+    async fn send_and_report(
+        &self,
+        request: Request,
+        validator: &ValidatorInfo,
+    ) -> Result<Response> {
         debug!("Sending request to validator {}", validator.uid);
 
-        match self.send_request_to_validator(request, &validator).await {
+        match self.send_request_to_validator(request, validator).await {
             Ok(response) => {
                 self.load_balancer
                     .read()
@@ -75,10 +153,12 @@ impl RequestDistributor {
         for validator in validators {
             let req = self.clone_request(&request)?;
             let client = self.client.clone();
+            let signer = self.signer.clone();
             let load_balancer = self.load_balancer.clone();
 
             tasks.push(tokio::spawn(async move {
-                let result = Self::send_request_to_validator_static(&client, req, &validator).await;
+                let result =
+                    Self::send_request_to_validator_static(&client, &signer, req, &validator).await;
 
                 match &result {
                     Ok(_) => load_balancer.read().await.report_success(validator.uid),
@@ -116,21 +196,27 @@ impl RequestDistributor {
         request: Request,
         validator: &ValidatorInfo,
     ) -> Result<Response> {
-        Self::send_request_to_validator_static(&self.client, request, validator).await
+        Self::send_request_to_validator_static(&self.client, &self.signer, request, validator).await
     }
 
     /// Static version for use in spawned tasks
     async fn send_request_to_validator_static(
         client: &Client,
+        signer: &RequestSigner,
         mut request: Request,
         validator: &ValidatorInfo,
     ) -> Result<Response> {
-        // Update the request URL to point to the validator
-        let original_path = request.url().path();
-        let new_url = format!("{}{}", validator.endpoint, original_path);
+        // Update the request URL to point to the validator, keeping the query
+        // the signature covers
+        let url = request.url();
+        let new_url = match url.query() {
+            Some(query) => format!("{}{}?{}", validator.endpoint, url.path(), query),
+            None => format!("{}{}", validator.endpoint, url.path()),
+        };
         *request.url_mut() = new_url.parse().map_err(|e| Error::InvalidRequest {
             message: format!("Invalid URL: {e}"),
         })?;
+        signer.sign(&mut request)?;
 
         client
             .execute(request)
@@ -1,10 +1,14 @@
 //! Response aggregation from multiple validators
 
-use crate::{discovery::ValidatorInfo, error::Result, Error};
+use super::policy::{
+    merge_listings, AggregationPolicy, ListingSchema, MergedListing, ValidatorListing,
+};
+use crate::{discovery::ValidatorInfo, error::Result, signing::RequestSigner, Error};
 use reqwest::{Client, Response};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, warn};
 
@@ -21,6 +25,8 @@ pub struct AggregatedResponse {
 pub struct ResponseAggregator {
     /// HTTP client
     client: Client,
+    /// Signs requests to validators
+    signer: Arc<RequestSigner>,
     /// Validators to query
     validators: Vec<ValidatorInfo>,
     /// Request timeout
@@ -29,9 +35,15 @@ pub struct ResponseAggregator {
 
 impl ResponseAggregator {
     /// Create a new response aggregator
-    pub fn new(client: Client, validators: Vec<ValidatorInfo>, timeout: Duration) -> Self {
+    pub fn new(
+        client: Client,
+        signer: Arc<RequestSigner>,
+        validators: Vec<ValidatorInfo>,
+        timeout: Duration,
+    ) -> Self {
         Self {
             client,
+            signer,
             validators,
             timeout,
         }
@@ -48,6 +60,7 @@ impl ResponseAggregator {
         for validator in &self.validators {
             let url = format!("{}{}", validator.endpoint, path);
             let client = self.client.clone();
+            let signer = self.signer.clone();
             let timeout = self.timeout;
             let validator_info = validator.clone();
             let headers_clone = headers.clone();
@@ -61,7 +74,7 @@ impl ResponseAggregator {
                     }
                 }
 
-                match request.send().await {
+                match signer.send(&client, request).await {
                     Ok(response) => {
                         if response.status().is_success() {
                             match response.json::<Value>().await {
@@ -105,6 +118,39 @@ impl ResponseAggregator {
         }
     }
 
+    /// Query a listing from all validators and merge it under `policy`
+    ///
+    /// Each validator answers with an object holding the items under the
+    /// schema's `list_field`.
+    pub async fn aggregate_listings(
+        &self,
+        path: &str,
+        schema: &ListingSchema,
+        policy: AggregationPolicy,
+    ) -> Result<MergedListing> {
+        let list_field = schema.list_field;
+        let responses = self.aggregate_get_requests(path, None).await?;
+
+        let listings: Vec<ValidatorListing> = responses
+            .into_iter()
+            .filter_map(|response| match response.data.get(list_field) {
+                Some(Value::Array(items)) => Some(ValidatorListing {
+                    items: items.clone(),
+                    validator: response.validator,
+                }),
+                _ => {
+                    warn!(
+                        "Validator {} returned no {} list",
+                        response.validator.endpoint, list_field
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        merge_listings(policy, &listings, self.validators.len(), schema)
+    }
+
     /// Aggregate responses by taking the majority consensus
     pub async fn aggregate_by_consensus<T: DeserializeOwned + PartialEq>(
         responses: Vec<Response>,
@@ -2,6 +2,9 @@
 
 use crate::{
     api::types::{ApiKeyInfo, ApiKeyTier},
+    api_keys::{
+        ANONYMOUS_KEY_ID, DEFAULT_SCOPES, MASTER_KEY_ID, SCOPE_ADMIN, SCOPE_READ, SCOPE_WRITE,
+    },
     error::Error,
     server::AppState,
 };
@@ -37,60 +40,57 @@ impl AuthMiddleware {
             .get(&state.config.auth.api_key_header)
             .and_then(|v| v.to_str().ok());
 
-        // Validate API key
-        match api_key {
+        let key_info = match api_key {
             Some(key) => {
                 // Check if it's a master key
                 if state.config.auth.master_api_keys.contains(&key.to_string()) {
-                    let key_info = ApiKeyInfo {
-                        key_id: "master".to_string(),
+                    ApiKeyInfo {
+                        key_id: MASTER_KEY_ID.to_string(),
+                        owner: MASTER_KEY_ID.to_string(),
                         tier: ApiKeyTier::Enterprise,
+                        scopes: vec![
+                            SCOPE_READ.to_string(),
+                            SCOPE_WRITE.to_string(),
+                            SCOPE_ADMIN.to_string(),
+                        ],
                         rate_limit_override: None,
-                    };
-                    req.extensions_mut().insert(Arc::new(key_info));
+                    }
                 } else {
-                    // Validate API key format and determine tier
-                    let (tier, rate_limit_override) =
-                        if key.starts_with("sk_enterprise_") && key.len() == 32 {
-                            (ApiKeyTier::Enterprise, Some(6000))
-                        } else if (key.starts_with("sk_premium_") || key.starts_with("sk_live_"))
-                            && key.len() >= 24
-                        {
-                            (ApiKeyTier::Premium, None)
-                        } else if key.starts_with("sk_test_") && key.len() >= 20 {
-                            (ApiKeyTier::Free, Some(300))
-                        } else {
-                            // Invalid key format
-                            return Err(Error::Authentication {
-                                message: "Invalid API key format".to_string(),
-                            });
-                        };
-
-                    let key_info = ApiKeyInfo {
-                        key_id: key.to_string(),
-                        tier,
-                        rate_limit_override,
-                    };
-                    req.extensions_mut().insert(Arc::new(key_info));
+                    state.api_keys.authenticate(key).await?
                 }
             }
             None => {
                 // Check if anonymous access is allowed
                 if state.config.auth.allow_anonymous {
-                    let key_info = ApiKeyInfo {
-                        key_id: "anonymous".to_string(),
+                    ApiKeyInfo {
+                        key_id: ANONYMOUS_KEY_ID.to_string(),
+                        owner: ANONYMOUS_KEY_ID.to_string(),
                         tier: ApiKeyTier::Free,
+                        scopes: DEFAULT_SCOPES.iter().map(|s| s.to_string()).collect(),
                         rate_limit_override: None,
-                    };
-                    req.extensions_mut().insert(Arc::new(key_info));
+                    }
                 } else {
                     return Err(Error::Authentication {
                         message: "API key required".to_string(),
                     });
                 }
             }
+        };
+
+        // Admin routes check the admin scope themselves
+        let required_scope = if req.method().is_safe() {
+            SCOPE_READ
+        } else {
+            SCOPE_WRITE
+        };
+        if !key_info.has_scope(required_scope) && !key_info.has_scope(SCOPE_ADMIN) {
+            return Err(Error::Authorization {
+                message: format!("API key lacks the '{required_scope}' scope"),
+            });
         }
 
+        req.extensions_mut().insert(Arc::new(key_info));
+
         Ok(next.run(req).await)
     }
 }
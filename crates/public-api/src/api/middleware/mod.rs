@@ -3,10 +3,11 @@
 mod auth;
 mod cache;
 mod rate_limit;
+mod usage;
 
 pub use auth::AuthMiddleware;
 pub use cache::CacheMiddleware;
-pub use rate_limit::RateLimitMiddleware;
+pub use rate_limit::{RateLimitMiddleware, RateLimitStorage};
 
 use crate::server::AppState;
 use axum::{
@@ -37,16 +38,22 @@ pub fn apply_middleware(router: Router<AppState>, state: AppState) -> Router<App
         .layer(cors)
         // Add tracing
         .layer(TraceLayer::new_for_http().make_span_with(DefaultMakeSpan::default()))
-        // Add custom middleware layers
+        // Add custom middleware layers. The last layer runs first, so keys are
+        // resolved before quotas and rate limits are applied, cached responses
+        // count towards quotas and responses are cached per key.
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
             rate_limit_handler,
         ))
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
-            auth_handler,
+            cache_handler,
         ))
-        .layer(axum::middleware::from_fn_with_state(state, cache_handler))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            usage_handler,
+        ))
+        .layer(axum::middleware::from_fn_with_state(state, auth_handler))
 }
 
 /// Rate limit handler function
@@ -55,13 +62,8 @@ async fn rate_limit_handler(
     req: Request<Body>,
     next: Next,
 ) -> Result<Response<Body>, crate::error::Error> {
-    // Create rate limit storage
-    let storage = std::sync::Arc::new(rate_limit::RateLimitStorage::new(std::sync::Arc::new(
-        state.config.rate_limit.clone(),
-    )));
-
-    // Check rate limit
-    match rate_limit::rate_limit_middleware(storage, req, next).await {
+    // Check rate limit against the limiters shared by all requests
+    match rate_limit::rate_limit_middleware(state.rate_limits.clone(), req, next).await {
         Ok(response) => Ok(response),
         Err(StatusCode::TOO_MANY_REQUESTS) => Err(crate::error::Error::RateLimitExceeded),
         Err(_) => Err(crate::error::Error::Internal {
@@ -80,6 +82,16 @@ async fn auth_handler(
     auth::AuthMiddleware::handle(State(state), req, next).await
 }
 
+/// Usage handler function
+async fn usage_handler(
+    State(state): axum::extract::State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response<Body>, crate::error::Error> {
+    // Enforce quotas and meter the request
+    usage::usage_middleware(state.usage.clone(), req, next).await
+}
+
 /// Cache handler function
 async fn cache_handler(
     State(state): axum::extract::State<AppState>,
@@ -1,6 +1,11 @@
 //! Rate limiting middleware
 
-use crate::{error::Error, server::AppState};
+use crate::{
+    api::types::{ApiKeyInfo, ApiKeyTier},
+    api_keys::ANONYMOUS_KEY_ID,
+    error::Error,
+    server::AppState,
+};
 use axum::{
     extract::{ConnectInfo, Request},
     http::StatusCode,
@@ -21,7 +26,38 @@ pub(super) enum RateLimitKey {
     /// IP-based rate limiting
     Ip(String),
     /// API key-based rate limiting
-    ApiKey(String),
+    ApiKey {
+        key_id: String,
+        requests_per_minute: u32,
+    },
+}
+
+impl RateLimitKey {
+    /// Key for a request, by API key when authenticated and by IP otherwise
+    fn for_request(req: &Request, config: &crate::config::RateLimitConfig) -> Self {
+        if let Some(info) = req.extensions().get::<Arc<ApiKeyInfo>>() {
+            if info.key_id != ANONYMOUS_KEY_ID {
+                return RateLimitKey::ApiKey {
+                    key_id: info.key_id.clone(),
+                    requests_per_minute: requests_per_minute(info, config),
+                };
+            }
+        }
+
+        match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(addr)) => RateLimitKey::Ip(addr.ip().to_string()),
+            None => RateLimitKey::Ip("anonymous".to_string()),
+        }
+    }
+}
+
+/// Requests per minute allowed for a key, from its override or its tier
+fn requests_per_minute(info: &ApiKeyInfo, config: &crate::config::RateLimitConfig) -> u32 {
+    info.rate_limit_override.unwrap_or(match info.tier {
+        ApiKeyTier::Enterprise => 6000, // Enterprise tier: 100 requests per second
+        ApiKeyTier::Premium => config.premium_requests_per_minute,
+        ApiKeyTier::Free => config.default_requests_per_minute,
+    })
 }
 
 /// Type alias for rate limiter
@@ -72,22 +108,12 @@ impl RateLimitStorage {
     /// Get or create limiter for API key
     fn get_api_key_limiter(
         &self,
-        api_key: &str,
+        key_id: &str,
+        requests_per_minute: u32,
     ) -> Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>> {
         self.api_key_limiters
-            .entry(api_key.to_string())
+            .entry(key_id.to_string())
             .or_insert_with(|| {
-                // Determine rate limit based on API key pattern
-                let requests_per_minute = if api_key.starts_with("sk_enterprise_") {
-                    6000 // Enterprise tier: 100 requests per second
-                } else if api_key.starts_with("sk_premium_") || api_key.starts_with("sk_live_") {
-                    self.config.premium_requests_per_minute
-                } else if api_key.starts_with("sk_test_") {
-                    300 // Test tier: 5 requests per second
-                } else {
-                    self.config.default_requests_per_minute
-                };
-
                 let quota = Quota::per_minute(
                     std::num::NonZeroU32::new(requests_per_minute)
                         .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
@@ -98,10 +124,13 @@ impl RateLimitStorage {
     }
 
     /// Check rate limit
-    pub async fn check_limit(&self, key: RateLimitKey) -> Result<(), Error> {
+    pub(super) async fn check_limit(&self, key: RateLimitKey) -> Result<(), Error> {
         let limiter = match &key {
             RateLimitKey::Ip(ip) if self.config.per_ip_limiting => self.get_ip_limiter(ip),
-            RateLimitKey::ApiKey(api_key) => self.get_api_key_limiter(api_key),
+            RateLimitKey::ApiKey {
+                key_id,
+                requests_per_minute,
+            } => self.get_api_key_limiter(key_id, *requests_per_minute),
             _ => self.default_limiter.clone(),
         };
 
@@ -111,6 +140,15 @@ impl RateLimitStorage {
         }
     }
 
+    /// Clean up old entries every five minutes, forever
+    pub async fn start_cleanup_loop(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(300));
+        loop {
+            interval.tick().await;
+            self.cleanup().await;
+        }
+    }
+
     /// Clean up old entries periodically
     pub async fn cleanup(&self) {
         // For now, we'll keep all entries
@@ -144,43 +182,18 @@ pub struct RateLimitMiddleware {
 }
 
 impl RateLimitMiddleware {
-    /// Create new rate limit middleware
+    /// Create new rate limit middleware over the state's shared limiters
     pub fn new(state: AppState) -> Self {
-        let storage = Arc::new(RateLimitStorage::new(Arc::new(
-            state.config.rate_limit.clone(),
-        )));
-
-        // Start cleanup task
-        let storage_clone = storage.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(300)); // 5 minutes
-            loop {
-                interval.tick().await;
-                storage_clone.cleanup().await;
-            }
-        });
-
         Self {
-            storage,
+            storage: state.rate_limits.clone(),
             config: state.config.clone(),
         }
     }
 
     /// Extract rate limit key from request
     #[allow(dead_code)]
-    fn extract_key(req: &Request) -> RateLimitKey {
-        // First check for API key
-        if let Some(api_key) = req.headers().get("X-API-Key").and_then(|h| h.to_str().ok()) {
-            return RateLimitKey::ApiKey(api_key.to_string());
-        }
-
-        // Fall back to IP address
-        if let Some(ConnectInfo(addr)) = req.extensions().get::<ConnectInfo<SocketAddr>>() {
-            return RateLimitKey::Ip(addr.ip().to_string());
-        }
-
-        // Default to anonymous
-        RateLimitKey::Ip("anonymous".to_string())
+    fn extract_key(&self, req: &Request) -> RateLimitKey {
+        RateLimitKey::for_request(req, &self.config.rate_limit)
     }
 }
 
@@ -190,18 +203,8 @@ pub async fn rate_limit_middleware(
     req: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Extract rate limit key
-    let key = match req.headers().get("X-API-Key").and_then(|h| h.to_str().ok()) {
-        Some(api_key) => RateLimitKey::ApiKey(api_key.to_string()),
-        None => {
-            // Try to get IP from connection info
-            if let Some(ConnectInfo(addr)) = req.extensions().get::<ConnectInfo<SocketAddr>>() {
-                RateLimitKey::Ip(addr.ip().to_string())
-            } else {
-                RateLimitKey::Ip("anonymous".to_string())
-            }
-        }
-    };
+    // Extract rate limit key, relying on the API key resolved by the auth middleware
+    let key = RateLimitKey::for_request(&req, &storage.config);
 
     // Check rate limit
     match storage.check_limit(key).await {
@@ -209,3 +212,57 @@ pub async fn rate_limit_middleware(
         Err(_) => Err(StatusCode::TOO_MANY_REQUESTS),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RateLimitConfig;
+    use axum::{body::Body, routing::get, Router};
+    use tower::Service;
+
+    fn key_info(rate_limit_override: Option<u32>) -> Arc<ApiKeyInfo> {
+        Arc::new(ApiKeyInfo {
+            key_id: "key-1".to_string(),
+            owner: "owner-1".to_string(),
+            tier: ApiKeyTier::Free,
+            scopes: vec![],
+            rate_limit_override,
+        })
+    }
+
+    /// Router limited by `storage`, as if the auth middleware resolved `info`
+    fn limited_router(storage: Arc<RateLimitStorage>, info: Arc<ApiKeyInfo>) -> Router {
+        Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn(
+                move |req: Request, next: Next| rate_limit_middleware(storage.clone(), req, next),
+            ))
+            .layer(axum::middleware::from_fn(
+                move |mut req: Request, next: Next| {
+                    req.extensions_mut().insert(info.clone());
+                    next.run(req)
+                },
+            ))
+    }
+
+    async fn status(router: &Router) -> StatusCode {
+        let request = axum::http::Request::get("/").body(Body::empty()).unwrap();
+        // A router is always ready to take a request
+        router.clone().call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_override_rejects_request_past_limit() {
+        let storage = Arc::new(RateLimitStorage::new(Arc::new(RateLimitConfig::default())));
+        let router = limited_router(storage.clone(), key_info(Some(3)));
+
+        for _ in 0..3 {
+            assert_eq!(status(&router).await, StatusCode::OK);
+        }
+        assert_eq!(status(&router).await, StatusCode::TOO_MANY_REQUESTS);
+
+        // The limit lives in the storage, not in the router handling a request
+        let router = limited_router(storage, key_info(Some(3)));
+        assert_eq!(status(&router).await, StatusCode::TOO_MANY_REQUESTS);
+    }
+}
@@ -0,0 +1,43 @@
+//! Usage metering middleware
+
+use crate::{api::types::ApiKeyInfo, error::Error, usage::UsageMeter};
+use axum::{
+    extract::{MatchedPath, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+/// Route label of requests that matched no route
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Enforce the caller's request quotas and count the request by route
+///
+/// The usage endpoint stays reachable with an exhausted quota so callers can
+/// see why they are being rejected. Requests turned away by the rate limiter
+/// are not counted.
+pub async fn usage_middleware(
+    meter: Arc<UsageMeter>,
+    req: Request,
+    next: Next,
+) -> Result<Response, Error> {
+    let Some(info) = req.extensions().get::<Arc<ApiKeyInfo>>().cloned() else {
+        return Ok(next.run(req).await);
+    };
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_ROUTE, |path| path.as_str())
+        .to_string();
+
+    if !route.ends_with("/usage") {
+        meter.check_requests(&info).await?;
+    }
+
+    let response = next.run(req).await;
+    if response.status() != StatusCode::TOO_MANY_REQUESTS {
+        meter.record_request(&info, &route).await;
+    }
+    Ok(response)
+}
@@ -45,6 +45,21 @@ pub fn routes(state: AppState) -> Router<AppState> {
         // Miner endpoints
         .route("/miners", get(routes::miners::list_miners))
         .route("/miners/:miner_id", get(routes::miners::get_miner))
+        // API key administration
+        .route(
+            "/admin/api-keys",
+            get(routes::api_keys::list_api_keys).post(routes::api_keys::create_api_key),
+        )
+        .route(
+            "/admin/api-keys/:key_id/rotate",
+            post(routes::api_keys::rotate_api_key),
+        )
+        .route(
+            "/admin/api-keys/:key_id/revoke",
+            post(routes::api_keys::revoke_api_key),
+        )
+        // Usage of the calling key
+        .route("/usage", get(routes::usage::get_usage))
         // Health and telemetry
         .route("/health", get(routes::health::health_check))
         .route("/telemetry", get(routes::telemetry::get_telemetry))
@@ -76,6 +91,11 @@ pub fn docs_routes() -> Router<AppState> {
         routes::miners::get_miner,
         routes::health::health_check,
         routes::telemetry::get_telemetry,
+        routes::api_keys::create_api_key,
+        routes::api_keys::list_api_keys,
+        routes::api_keys::rotate_api_key,
+        routes::api_keys::revoke_api_key,
+        routes::usage::get_usage,
     ),
     components(schemas(
         types::RentCapacityRequest,
@@ -88,6 +108,18 @@ pub fn docs_routes() -> Router<AppState> {
         types::MinerDetails,
         types::HealthCheckResponse,
         types::TelemetryResponse,
+        types::ApiKeyTier,
+        types::CreateApiKeyRequest,
+        types::ApiKeyDetails,
+        types::IssuedApiKeyResponse,
+        types::ListApiKeysResponse,
+        types::UsageResponse,
+        types::PeriodUsage,
+        types::UsageLimits,
+        types::AggregationReport,
+        types::ItemDisagreement,
+        types::FieldDisagreement,
+        types::ReportedValue,
         crate::error::ErrorResponse,
     )),
     tags(
@@ -97,6 +129,8 @@ pub fn docs_routes() -> Router<AppState> {
         (name = "validators", description = "Validator information"),
         (name = "miners", description = "Miner information"),
         (name = "health", description = "Health and monitoring"),
+        (name = "admin", description = "API key administration"),
+        (name = "usage", description = "Usage metering and quotas"),
     ),
     info(
         title = "Basilica Public API",
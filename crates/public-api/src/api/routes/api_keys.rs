@@ -0,0 +1,177 @@
+//! API key administration route handlers
+
+use crate::{
+    api::types::{
+        ApiKeyDetails, ApiKeyInfo, CreateApiKeyRequest, IssuedApiKeyResponse, ListApiKeysQuery,
+        ListApiKeysResponse,
+    },
+    api_keys::{IssuedApiKey, NewApiKey, SCOPE_ADMIN},
+    error::{Error, Result},
+    persistence::ApiKeyRecord,
+    server::AppState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// Issue an API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "API key issued", body = IssuedApiKeyResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 403, description = "Admin scope required", body = crate::error::ErrorResponse),
+    ),
+    tag = "admin",
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Arc<ApiKeyInfo>>,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<Json<IssuedApiKeyResponse>> {
+    require_admin(&caller)?;
+    info!(
+        "API key for {} requested by {}",
+        request.owner, caller.key_id
+    );
+
+    let issued = state
+        .api_keys
+        .issue(NewApiKey {
+            owner: request.owner,
+            tier: request.tier,
+            scopes: request.scopes.unwrap_or_default(),
+            rate_limit_override: request.rate_limit_override,
+            expires_in: request
+                .expires_in_days
+                .map(|days| chrono::Duration::days(days.into())),
+        })
+        .await?;
+
+    Ok(Json(issued_response(issued)))
+}
+
+/// List API keys
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys",
+    params(
+        ("owner" = Option<String>, Query, description = "Only list keys of this owner"),
+    ),
+    responses(
+        (status = 200, description = "API keys", body = ListApiKeysResponse),
+        (status = 403, description = "Admin scope required", body = crate::error::ErrorResponse),
+    ),
+    tag = "admin",
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Arc<ApiKeyInfo>>,
+    Query(query): Query<ListApiKeysQuery>,
+) -> Result<Json<ListApiKeysResponse>> {
+    require_admin(&caller)?;
+
+    let keys: Vec<ApiKeyDetails> = state
+        .api_keys
+        .list(query.owner.as_deref())
+        .await?
+        .iter()
+        .map(key_details)
+        .collect();
+    let total_count = keys.len();
+
+    Ok(Json(ListApiKeysResponse { keys, total_count }))
+}
+
+/// Replace the secret of an API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{key_id}/rotate",
+    params(
+        ("key_id" = String, Path, description = "API key ID"),
+    ),
+    responses(
+        (status = 200, description = "API key rotated", body = IssuedApiKeyResponse),
+        (status = 403, description = "Admin scope required", body = crate::error::ErrorResponse),
+        (status = 404, description = "API key not found or revoked", body = crate::error::ErrorResponse),
+    ),
+    tag = "admin",
+)]
+pub async fn rotate_api_key(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Arc<ApiKeyInfo>>,
+    Path(key_id): Path<String>,
+) -> Result<Json<IssuedApiKeyResponse>> {
+    require_admin(&caller)?;
+    info!(
+        "Rotation of API key {} requested by {}",
+        key_id, caller.key_id
+    );
+
+    let issued = state.api_keys.rotate(&key_id).await?;
+    Ok(Json(issued_response(issued)))
+}
+
+/// Revoke an API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{key_id}/revoke",
+    params(
+        ("key_id" = String, Path, description = "API key ID"),
+    ),
+    responses(
+        (status = 200, description = "API key revoked", body = ApiKeyDetails),
+        (status = 403, description = "Admin scope required", body = crate::error::ErrorResponse),
+        (status = 404, description = "API key not found or already revoked", body = crate::error::ErrorResponse),
+    ),
+    tag = "admin",
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Arc<ApiKeyInfo>>,
+    Path(key_id): Path<String>,
+) -> Result<Json<ApiKeyDetails>> {
+    require_admin(&caller)?;
+    info!(
+        "Revocation of API key {} requested by {}",
+        key_id, caller.key_id
+    );
+
+    let record = state.api_keys.revoke(&key_id).await?;
+    Ok(Json(key_details(&record)))
+}
+
+fn require_admin(caller: &ApiKeyInfo) -> Result<()> {
+    if caller.has_scope(SCOPE_ADMIN) {
+        Ok(())
+    } else {
+        Err(Error::Authorization {
+            message: "Managing API keys requires the 'admin' scope".to_string(),
+        })
+    }
+}
+
+fn issued_response(issued: IssuedApiKey) -> IssuedApiKeyResponse {
+    IssuedApiKeyResponse {
+        api_key: issued.api_key,
+        key: key_details(&issued.record),
+    }
+}
+
+fn key_details(record: &ApiKeyRecord) -> ApiKeyDetails {
+    ApiKeyDetails {
+        key_id: record.key_id.clone(),
+        owner: record.owner.clone(),
+        tier: record.tier,
+        scopes: record.scopes.clone(),
+        rate_limit_override: record.rate_limit_override,
+        created_at: record.created_at,
+        expires_at: record.expires_at,
+        revoked_at: record.revoked_at,
+    }
+}
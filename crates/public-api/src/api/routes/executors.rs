@@ -1,7 +1,7 @@
 //! Executor management route handlers
 
 use crate::{
-    aggregator::ResponseAggregator,
+    aggregator::{AggregationPolicy, ResponseAggregator, EXECUTOR_LISTING},
     api::types::{ExecutorDetails, ListExecutorsQuery, ListExecutorsResponse},
     error::{Error, Result},
     server::AppState,
@@ -10,7 +10,6 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use std::collections::HashMap;
 use tracing::{debug, warn};
 
 /// List executors
@@ -22,6 +21,8 @@ use tracing::{debug, warn};
         ("gpu_type" = Option<String>, Query, description = "GPU type filter"),
         ("page" = Option<u32>, Query, description = "Page number"),
         ("page_size" = Option<u32>, Query, description = "Page size"),
+        ("consistency" = Option<String>, Query, description = "Aggregation policy: union, majority, stake_weighted or quorum"),
+        ("quorum" = Option<usize>, Query, description = "Agreeing validators required by the quorum policy"),
     ),
     responses(
         (status = 200, description = "List of executors", body = ListExecutorsResponse),
@@ -36,6 +37,9 @@ pub async fn list_executors(
     let page = query.page.unwrap_or(1);
     let page_size = query.page_size.unwrap_or(20).min(100);
 
+    let policy =
+        AggregationPolicy::resolve(&state.config.aggregation, query.consistency, query.quorum)?;
+
     // Get healthy validators
     let validators = state.discovery.get_healthy_validators();
     if validators.is_empty() {
@@ -63,30 +67,27 @@ pub async fn list_executors(
     // Create aggregator
     let aggregator = ResponseAggregator::new(
         state.http_client.clone(),
+        state.signer.clone(),
         validators.clone(),
         state.config.request_timeout(),
     );
 
-    // Aggregate responses from validators
-    let responses = aggregator.aggregate_get_requests(&full_path, None).await?;
-
-    // Merge executor lists from all validators
-    let mut all_executors: HashMap<String, ExecutorDetails> = HashMap::new();
-    let mut total_count = 0;
+    // Merge the listings of all responding validators
+    let merged = aggregator
+        .aggregate_listings(&full_path, &EXECUTOR_LISTING, policy)
+        .await?;
 
-    for response in responses {
-        if let Ok(data) = serde_json::from_value::<ListExecutorsResponse>(response.data) {
-            total_count = total_count.max(data.total_count);
-
-            for executor in data.executors {
-                // Use executor ID as key to deduplicate
-                all_executors.insert(executor.id.clone(), executor);
+    let mut executors: Vec<ExecutorDetails> = merged
+        .items
+        .into_iter()
+        .filter_map(|item| match serde_json::from_value(item) {
+            Ok(executor) => Some(executor),
+            Err(e) => {
+                warn!("Failed to parse merged executor: {}", e);
+                None
             }
-        }
-    }
-
-    // Convert to vector and apply filters
-    let mut executors: Vec<ExecutorDetails> = all_executors.into_values().collect();
+        })
+        .collect();
 
     // Apply GPU count filter if not already applied by validators
     if let Some(min_gpu) = query.min_gpu_count {
@@ -111,6 +112,7 @@ pub async fn list_executors(
         total_count: executors.len(),
         page,
         page_size,
+        aggregation: Some(merged.report),
     }))
 }
 
@@ -146,13 +148,12 @@ pub async fn get_executor(
     for validator in validators {
         let url = format!("{}{}", validator.endpoint, request_path);
 
-        match state
+        let request = state
             .http_client
             .get(&url)
-            .timeout(state.config.request_timeout())
-            .send()
-            .await
-        {
+            .timeout(state.config.request_timeout());
+
+        match state.signer.send(&state.http_client, request).await {
             Ok(response) => {
                 if response.status().is_success() {
                     match response.json::<ExecutorDetails>().await {
@@ -1,18 +1,24 @@
 //! Log streaming route handlers
 
+use super::rentals::rental_status_request;
 use crate::{
-    api::types::LogQuery,
-    error::{Error, Result},
+    aggregator::RequestDistributor,
+    api::types::{ApiKeyInfo, LogQuery},
+    error::Result,
     server::AppState,
+    signing::RequestSigner,
+    usage::UsageMeter,
 };
 use axum::{
-    extract::{Path, Query, State},
+    extract::{MatchedPath, Path, Query, State},
     response::sse::{Event, KeepAlive, Sse},
+    Extension,
 };
 use futures::{Stream, StreamExt};
 use std::convert::Infallible;
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, error, warn};
+use tracing::{debug, error};
 
 /// Stream rental logs
 #[utoipa::path(
@@ -25,74 +31,83 @@ use tracing::{debug, error, warn};
     ),
     responses(
         (status = 200, description = "Log stream", content_type = "text/event-stream"),
+        (status = 402, description = "Log quota exhausted", body = crate::error::ErrorResponse),
         (status = 404, description = "Rental not found", body = crate::error::ErrorResponse),
     ),
     tag = "logs",
 )]
 pub async fn stream_rental_logs(
     State(state): State<AppState>,
+    Extension(caller): Extension<Arc<ApiKeyInfo>>,
+    matched_path: MatchedPath,
     Path(rental_id): Path<String>,
     Query(query): Query<LogQuery>,
 ) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>> {
     debug!("Starting log stream for rental: {}", rental_id);
 
-    // Get healthy validators
-    let validators = state.discovery.get_healthy_validators();
-    if validators.is_empty() {
-        return Err(Error::NoValidatorsAvailable);
-    }
+    state.usage.check_log_stream(&caller).await?;
 
-    // Find which validator has this rental
-    let mut rental_validator = None;
-    for validator in &validators {
-        let url = format!("{}/api/v1/rentals/{}", validator.endpoint, rental_id);
-
-        match state
-            .http_client
-            .get(&url)
-            .timeout(Duration::from_secs(5))
-            .send()
-            .await
-        {
-            Ok(response) => {
-                if response.status().is_success() {
-                    rental_validator = Some(validator.clone());
-                    break;
-                }
-            }
-            Err(e) => {
-                warn!(
-                    "Failed to check rental on validator {}: {}",
-                    validator.endpoint, e
-                );
-            }
-        }
-    }
-
-    let validator = rental_validator.ok_or_else(|| Error::NotFound {
-        resource: format!("Rental {rental_id}"),
-    })?;
+    // Find the validator owning the rental
+    let distributor = RequestDistributor::new(
+        state.http_client.clone(),
+        state.signer.clone(),
+        state.load_balancer.clone(),
+    );
+    let (validator, _) = state
+        .rental_router
+        .send(&distributor, &rental_id, |validator| {
+            let mut request =
+                rental_status_request(&state.http_client, &validator.endpoint, &rental_id)?;
+            *request.timeout_mut() = Some(Duration::from_secs(5));
+            Ok(request)
+        })
+        .await?;
 
     // Build SSE URL for the validator
-    let sse_url = format!(
-        "{}/api/v1/rentals/{}/logs?follow={}&tail={}",
-        validator.endpoint,
-        rental_id,
-        query.follow.unwrap_or(true),
-        query.tail.unwrap_or(100)
-    );
+    let sse_url = rental_logs_url(&validator.endpoint, &rental_id, &query);
 
     // Create the stream
-    let stream = create_log_stream(state.http_client.clone(), sse_url, rental_id);
+    let metering = LogMetering {
+        meter: state.usage.clone(),
+        caller,
+        route: matched_path.as_str().to_string(),
+    };
+    let stream = create_log_stream(
+        state.http_client.clone(),
+        state.signer.clone(),
+        sse_url,
+        rental_id,
+        metering,
+    );
 
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
+/// URL of the log stream of a rental on the validator at `endpoint`
+pub(crate) fn rental_logs_url(endpoint: &str, rental_id: &str, query: &LogQuery) -> String {
+    format!(
+        "{endpoint}/rentals/{rental_id}/logs?follow={}&tail={}",
+        query.follow.unwrap_or(true),
+        query.tail.unwrap_or(100)
+    )
+}
+
+/// Meters the log bytes a stream forwards to its caller
+struct LogMetering {
+    meter: Arc<UsageMeter>,
+    caller: Arc<ApiKeyInfo>,
+    route: String,
+}
+
 /// Create a log stream from a validator
+///
+/// The stream ends early once the caller's monthly log quota is used up.
 fn create_log_stream(
     client: reqwest::Client,
+    signer: Arc<RequestSigner>,
     url: String,
     rental_id: String,
+    metering: LogMetering,
 ) -> impl Stream<Item = std::result::Result<Event, Infallible>> {
     async_stream::stream! {
         debug!("Connecting to log stream: {}", url);
@@ -103,7 +118,7 @@ fn create_log_stream(
             .data(format!("Connected to log stream for rental {rental_id}")));
 
         // Connect to the validator's SSE endpoint
-        match client.get(&url).send().await {
+        match signer.send(&client, client.get(&url)).await {
             Ok(response) => {
                 if !response.status().is_success() {
                     yield Ok(Event::default()
@@ -119,6 +134,11 @@ fn create_log_stream(
                 while let Some(chunk_result) = stream.next().await {
                     match chunk_result {
                         Ok(chunk) => {
+                            let within_quota = metering
+                                .meter
+                                .record_log_bytes(&metering.caller, &metering.route, chunk.len() as u64)
+                                .await;
+
                             // Convert bytes to string
                             if let Ok(text) = std::str::from_utf8(&chunk) {
                                 buffer.push_str(text);
@@ -139,6 +159,13 @@ fn create_log_stream(
                                     }
                                 }
                             }
+
+                            if !within_quota {
+                                yield Ok(Event::default()
+                                    .event("error")
+                                    .data("Monthly log quota exhausted"));
+                                return;
+                            }
                         }
                         Err(e) => {
                             error!("Error reading log stream: {}", e);
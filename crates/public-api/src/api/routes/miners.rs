@@ -1,7 +1,7 @@
 //! Miner information route handlers
 
 use crate::{
-    aggregator::ResponseAggregator,
+    aggregator::{AggregationPolicy, ResponseAggregator, MINER_LISTING},
     api::types::{ListMinersQuery, ListMinersResponse, MinerDetails},
     error::{Error, Result},
     server::AppState,
@@ -10,7 +10,6 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use std::collections::HashMap;
 use tracing::{debug, warn};
 
 /// List miners
@@ -22,6 +21,8 @@ use tracing::{debug, warn};
         ("min_score" = Option<f64>, Query, description = "Minimum score"),
         ("page" = Option<u32>, Query, description = "Page number"),
         ("page_size" = Option<u32>, Query, description = "Page size"),
+        ("consistency" = Option<String>, Query, description = "Aggregation policy: union, majority, stake_weighted or quorum"),
+        ("quorum" = Option<usize>, Query, description = "Agreeing validators required by the quorum policy"),
     ),
     responses(
         (status = 200, description = "List of miners", body = ListMinersResponse),
@@ -36,6 +37,9 @@ pub async fn list_miners(
     let page = query.page.unwrap_or(1);
     let page_size = query.page_size.unwrap_or(20).min(100);
 
+    let policy =
+        AggregationPolicy::resolve(&state.config.aggregation, query.consistency, query.quorum)?;
+
     // Get healthy validators
     let validators = state.discovery.get_healthy_validators();
     if validators.is_empty() {
@@ -63,30 +67,27 @@ pub async fn list_miners(
     // Create aggregator
     let aggregator = ResponseAggregator::new(
         state.http_client.clone(),
+        state.signer.clone(),
         validators.clone(),
         state.config.request_timeout(),
     );
 
-    // Aggregate responses from validators
-    let responses = aggregator.aggregate_get_requests(&full_path, None).await?;
-
-    // Merge miner lists from all validators
-    let mut all_miners: HashMap<String, MinerDetails> = HashMap::new();
-    let mut total_count = 0;
+    // Merge the listings of all responding validators
+    let merged = aggregator
+        .aggregate_listings(&full_path, &MINER_LISTING, policy)
+        .await?;
 
-    for response in responses {
-        if let Ok(data) = serde_json::from_value::<ListMinersResponse>(response.data) {
-            total_count = total_count.max(data.total_count);
-
-            for miner in data.miners {
-                // Use miner ID (hotkey) as key to deduplicate
-                all_miners.insert(miner.miner_id.clone(), miner);
+    let mut miners: Vec<MinerDetails> = merged
+        .items
+        .into_iter()
+        .filter_map(|item| match serde_json::from_value(item) {
+            Ok(miner) => Some(miner),
+            Err(e) => {
+                warn!("Failed to parse merged miner: {}", e);
+                None
             }
-        }
-    }
-
-    // Convert to vector and apply filters
-    let mut miners: Vec<MinerDetails> = all_miners.into_values().collect();
+        })
+        .collect();
 
     // Apply GPU count filter if not already applied by validators
     if let Some(min_gpu) = query.min_gpu_count {
@@ -115,6 +116,7 @@ pub async fn list_miners(
         total_count: miners.len(),
         page,
         page_size,
+        aggregation: Some(merged.report),
     }))
 }
 
@@ -150,13 +152,12 @@ pub async fn get_miner(
     for validator in validators {
         let url = format!("{}{}", validator.endpoint, request_path);
 
-        match state
+        let request = state
             .http_client
             .get(&url)
-            .timeout(state.config.request_timeout())
-            .send()
-            .await
-        {
+            .timeout(state.config.request_timeout());
+
+        match state.signer.send(&state.http_client, request).await {
             Ok(response) => {
                 if response.status().is_success() {
                     match response.json::<MinerDetails>().await {
@@ -1,9 +1,11 @@
 //! API route handlers
 
+pub mod api_keys;
 pub mod executors;
 pub mod health;
 pub mod logs;
 pub mod miners;
 pub mod rentals;
 pub mod telemetry;
+pub mod usage;
 pub mod validators;
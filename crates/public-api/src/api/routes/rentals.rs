@@ -1,19 +1,20 @@
 //! Rental management route handlers
 
 use crate::{
-    aggregator::{RequestDistributor, ResponseAggregator},
+    aggregator::RequestDistributor,
     api::types::{
-        RentCapacityRequest, RentCapacityResponse, RentalStatusResponse, TerminateRentalRequest,
-        TerminateRentalResponse,
+        ApiKeyInfo, RentCapacityRequest, RentCapacityResponse, RentalStatusResponse,
+        TerminateRentalRequest, TerminateRentalResponse,
     },
     error::{Error, Result},
     server::AppState,
 };
 use axum::{
     extract::{Path, State},
-    Json,
+    Extension, Json,
 };
-use tracing::{debug, info};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
 
 /// Rent GPU capacity
 #[utoipa::path(
@@ -23,12 +24,14 @@ use tracing::{debug, info};
     responses(
         (status = 201, description = "Rental created successfully", body = RentCapacityResponse),
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 402, description = "GPU-hour quota exhausted", body = crate::error::ErrorResponse),
         (status = 503, description = "No validators available", body = crate::error::ErrorResponse),
     ),
     tag = "rentals",
 )]
 pub async fn rent_capacity(
     State(state): State<AppState>,
+    Extension(caller): Extension<Arc<ApiKeyInfo>>,
     Json(request): Json<RentCapacityRequest>,
 ) -> Result<Json<RentCapacityResponse>> {
     info!(
@@ -36,22 +39,26 @@ pub async fn rent_capacity(
         request.gpu_requirements.gpu_count
     );
 
+    let gpu_count = request.gpu_requirements.gpu_count;
+    let duration_hours = request.max_duration_hours;
+    state
+        .usage
+        .check_rental(&caller, gpu_count, duration_hours)
+        .await?;
+
     // Create request distributor
-    let distributor =
-        RequestDistributor::new(state.http_client.clone(), state.load_balancer.clone());
-
-    // Build request to forward to validator
-    let validator_request = state
-        .http_client
-        .post("/api/v1/rentals")
-        .json(&request)
-        .build()
-        .map_err(|e| Error::Internal {
-            message: format!("Failed to build request: {e}"),
-        })?;
+    let distributor = RequestDistributor::new(
+        state.http_client.clone(),
+        state.signer.clone(),
+        state.load_balancer.clone(),
+    );
 
     // Send to a single validator (could be extended to try multiple)
-    let response = distributor.send_to_single(validator_request).await?;
+    let (validator, response) = distributor
+        .send_to_single(|validator| {
+            rent_capacity_request(&state.http_client, &validator.endpoint, &request)
+        })
+        .await?;
 
     // Parse response
     let rental_response: RentCapacityResponse =
@@ -62,7 +69,39 @@ pub async fn rent_capacity(
                 message: format!("Failed to parse validator response: {e}"),
             })?;
 
-    info!("Successfully created rental: {}", rental_response.rental_id);
+    info!(
+        "Successfully created rental {} on validator {}",
+        rental_response.rental_id, validator.uid
+    );
+
+    // Without a route the rental is found again by failing over
+    if let Err(e) = state
+        .rental_router
+        .assign(&rental_response.rental_id, &validator)
+        .await
+    {
+        warn!(
+            "Failed to record route of rental {}: {}",
+            rental_response.rental_id, e
+        );
+    }
+
+    // The rental exists either way, so a metering failure must not fail the request
+    if let Err(e) = state
+        .usage
+        .record_rental_started(
+            &caller,
+            &rental_response.rental_id,
+            gpu_count,
+            duration_hours,
+        )
+        .await
+    {
+        warn!(
+            "Failed to meter rental {}: {}",
+            rental_response.rental_id, e
+        );
+    }
 
     Ok(Json(rental_response))
 }
@@ -88,48 +127,29 @@ pub async fn get_rental_status(
     debug!("Getting status for rental: {}", rental_id);
 
     // Create request distributor
-    let distributor =
-        RequestDistributor::new(state.http_client.clone(), state.load_balancer.clone());
-
-    // Build request to forward to validators
-    let validator_request = state
-        .http_client
-        .get(format!("/api/v1/rentals/{rental_id}"))
-        .build()
-        .map_err(|e| Error::Internal {
-            message: format!("Failed to build request: {e}"),
-        })?;
-
-    // Try multiple validators for better availability
-    match distributor.send_to_multiple(validator_request, 3).await {
-        Ok(responses) => {
-            // Take the first successful response
-            let status = ResponseAggregator::take_first::<RentalStatusResponse>(responses).await?;
-            Ok(Json(status))
-        }
-        Err(_) => {
-            // If multiple requests fail, try a single validator
-            let single_request = state
-                .http_client
-                .get(format!("/api/v1/rentals/{rental_id}"))
-                .build()
-                .map_err(|e| Error::Internal {
-                    message: format!("Failed to build request: {e}"),
-                })?;
-
-            let response = distributor.send_to_single(single_request).await?;
-
-            let status: RentalStatusResponse =
-                response
-                    .json()
-                    .await
-                    .map_err(|e| Error::ValidatorCommunication {
-                        message: format!("Failed to parse validator response: {e}"),
-                    })?;
-
-            Ok(Json(status))
-        }
-    }
+    let distributor = RequestDistributor::new(
+        state.http_client.clone(),
+        state.signer.clone(),
+        state.load_balancer.clone(),
+    );
+
+    // Send to the validator owning the rental
+    let (_, response) = state
+        .rental_router
+        .send(&distributor, &rental_id, |validator| {
+            rental_status_request(&state.http_client, &validator.endpoint, &rental_id)
+        })
+        .await?;
+
+    let status: RentalStatusResponse =
+        response
+            .json()
+            .await
+            .map_err(|e| Error::ValidatorCommunication {
+                message: format!("Failed to parse validator response: {e}"),
+            })?;
+
+    Ok(Json(status))
 }
 
 /// Terminate a rental
@@ -155,21 +175,24 @@ pub async fn terminate_rental(
     info!("Terminating rental: {}", rental_id);
 
     // Create request distributor
-    let distributor =
-        RequestDistributor::new(state.http_client.clone(), state.load_balancer.clone());
-
-    // Build request to forward to validator
-    let validator_request = state
-        .http_client
-        .post(format!("/api/v1/rentals/{rental_id}/terminate"))
-        .json(&request)
-        .build()
-        .map_err(|e| Error::Internal {
-            message: format!("Failed to build request: {e}"),
-        })?;
-
-    // Send to validator
-    let response = distributor.send_to_single(validator_request).await?;
+    let distributor = RequestDistributor::new(
+        state.http_client.clone(),
+        state.signer.clone(),
+        state.load_balancer.clone(),
+    );
+
+    // Send to the validator owning the rental
+    let (_, response) = state
+        .rental_router
+        .send(&distributor, &rental_id, |validator| {
+            terminate_rental_request(
+                &state.http_client,
+                &validator.endpoint,
+                &rental_id,
+                &request,
+            )
+        })
+        .await?;
 
     // Parse response
     let terminate_response: TerminateRentalResponse =
@@ -182,5 +205,185 @@ pub async fn terminate_rental(
 
     info!("Successfully terminated rental: {}", rental_id);
 
+    if terminate_response.success {
+        if let Err(e) = state.usage.record_rental_ended(&rental_id).await {
+            warn!("Failed to stop metering rental {}: {}", rental_id, e);
+        }
+    }
+
     Ok(Json(terminate_response))
 }
+
+/// Request creating a rental on the validator at `endpoint`
+pub(crate) fn rent_capacity_request(
+    client: &reqwest::Client,
+    endpoint: &str,
+    request: &RentCapacityRequest,
+) -> Result<reqwest::Request> {
+    build(client.post(format!("{endpoint}/rentals")).json(request))
+}
+
+/// Request reading the status of a rental from the validator at `endpoint`
+pub(crate) fn rental_status_request(
+    client: &reqwest::Client,
+    endpoint: &str,
+    rental_id: &str,
+) -> Result<reqwest::Request> {
+    build(client.get(format!("{endpoint}/rentals/{rental_id}/status")))
+}
+
+/// Request terminating a rental on the validator at `endpoint`
+pub(crate) fn terminate_rental_request(
+    client: &reqwest::Client,
+    endpoint: &str,
+    rental_id: &str,
+    request: &TerminateRentalRequest,
+) -> Result<reqwest::Request> {
+    build(
+        client
+            .delete(format!("{endpoint}/rentals/{rental_id}"))
+            .json(request),
+    )
+}
+
+fn build(request: reqwest::RequestBuilder) -> Result<reqwest::Request> {
+    request.build().map_err(|e| Error::Internal {
+        message: format!("Failed to build request: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::routes::logs::rental_logs_url;
+    use crate::api::types::{GpuRequirements, LogQuery, RentalStatus};
+    use crate::signing::RequestSigner;
+    use common::crypto::Ed25519PrivateKey;
+    use reqwest::{Client, StatusCode};
+    use serde_json::json;
+    use validator::config::{ApiConfig, RequestAuthConfig};
+    use validator::persistence::entities::{Rental, RentalStatus as ValidatorRentalStatus};
+    use validator::{ApiHandler, SimplePersistence};
+
+    /// Serve the validator API on a local port, accepting requests from `signer`
+    async fn start_validator(signer: &RequestSigner) -> (String, Arc<SimplePersistence>) {
+        let persistence = Arc::new(
+            SimplePersistence::new(":memory:", "test_validator".to_string())
+                .await
+                .unwrap(),
+        );
+        let config = ApiConfig {
+            api_key: None,
+            max_body_size: 1024 * 1024,
+            bind_address: "127.0.0.1:0".to_string(),
+            request_auth: RequestAuthConfig {
+                allowed_ed25519_keys: vec![signer.signer_id().unwrap().to_string()],
+                ..Default::default()
+            },
+        };
+        let router = ApiHandler::new(
+            config,
+            persistence.clone(),
+            common::MemoryStorage::new().await.unwrap(),
+            validator::slashing::SlashingConfig::default(),
+            None,
+            None,
+        )
+        .create_router();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        (endpoint, persistence)
+    }
+
+    async fn send(
+        signer: &RequestSigner,
+        client: &Client,
+        mut request: reqwest::Request,
+    ) -> StatusCode {
+        signer.sign(&mut request).unwrap();
+        client.execute(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_requests_match_validator_routes() {
+        let signer = RequestSigner::ed25519(Ed25519PrivateKey::generate());
+        let client = Client::new();
+        let (endpoint, persistence) = start_validator(&signer).await;
+
+        let mut rental = Rental::new(
+            "exec-a".to_string(),
+            "ssh-ed25519 AAAA".to_string(),
+            "ubuntu:22.04".to_string(),
+            None,
+            json!({}),
+            json!({}),
+            2,
+            1.0,
+        );
+        rental.status = ValidatorRentalStatus::Active;
+        persistence.create_rental(&rental).await.unwrap();
+        let rental_id = rental.id.to_string();
+
+        // Status of a known rental is served and parses
+        let mut request = rental_status_request(&client, &endpoint, &rental_id).unwrap();
+        signer.sign(&mut request).unwrap();
+        let response = client.execute(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let status: RentalStatusResponse = response.json().await.unwrap();
+        assert_eq!(status.rental_id, rental_id);
+        assert!(matches!(status.status, RentalStatus::Active));
+
+        // Only an unknown rental is not found, which the rental router relies on
+        let unknown = uuid::Uuid::new_v4().to_string();
+        let request = rental_status_request(&client, &endpoint, &unknown).unwrap();
+        assert_eq!(send(&signer, &client, request).await, StatusCode::NOT_FOUND);
+
+        // The signature covers the query of the log stream
+        let query = LogQuery {
+            follow: Some(false),
+            tail: Some(10),
+        };
+        let request = client
+            .get(rental_logs_url(&endpoint, &rental_id, &query))
+            .build()
+            .unwrap();
+        assert_eq!(send(&signer, &client, request).await, StatusCode::OK);
+
+        // Rental provisioning is not set up on this validator, so reaching
+        // the handler is all these can show
+        let terminate = TerminateRentalRequest {
+            reason: Some("done".to_string()),
+        };
+        let request = terminate_rental_request(&client, &endpoint, &rental_id, &terminate).unwrap();
+        assert_eq!(
+            send(&signer, &client, request).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        let rent = RentCapacityRequest {
+            gpu_requirements: GpuRequirements {
+                min_memory_gb: 16,
+                gpu_type: None,
+                gpu_count: 1,
+            },
+            ssh_public_key: "ssh-ed25519 AAAA".to_string(),
+            docker_image: "ubuntu:22.04".to_string(),
+            env_vars: None,
+            max_duration_hours: 1,
+        };
+        let request = rent_capacity_request(&client, &endpoint, &rent).unwrap();
+        assert_eq!(
+            send(&signer, &client, request).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        // Unsigned requests are refused on the same routes
+        let request = rental_status_request(&client, &endpoint, &rental_id).unwrap();
+        assert_eq!(
+            send(&RequestSigner::unsigned(), &client, request).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
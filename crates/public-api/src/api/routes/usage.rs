@@ -0,0 +1,26 @@
+//! Usage route handlers
+
+use crate::{
+    api::types::{ApiKeyInfo, UsageResponse},
+    error::Result,
+    server::AppState,
+};
+use axum::{extract::State, Extension, Json};
+use std::sync::Arc;
+
+/// Get the calling API key's usage
+#[utoipa::path(
+    get,
+    path = "/usage",
+    responses(
+        (status = 200, description = "Usage of the current day and month", body = UsageResponse),
+        (status = 401, description = "API key required", body = crate::error::ErrorResponse),
+    ),
+    tag = "usage",
+)]
+pub async fn get_usage(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Arc<ApiKeyInfo>>,
+) -> Result<Json<UsageResponse>> {
+    Ok(Json(state.usage.usage(&caller).await?))
+}
@@ -31,6 +31,7 @@ pub async fn list_validators(
             hotkey: v.hotkey,
             endpoint: v.endpoint,
             score: v.score,
+            stake: v.stake,
             is_healthy: v.is_healthy,
             last_health_check: v.last_health_check,
         })
@@ -67,6 +68,7 @@ pub async fn get_validator(
             hotkey: validator.hotkey,
             endpoint: validator.endpoint,
             score: validator.score,
+            stake: validator.stake,
             is_healthy: validator.is_healthy,
             last_health_check: validator.last_health_check,
         })),
@@ -1,5 +1,6 @@
 //! API types for the Public API Gateway
 
+use crate::config::AggregationMode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use utoipa::ToSchema;
@@ -65,9 +66,15 @@ pub enum RentalStatus {
     /// Rental is pending
     Pending,
 
+    /// Rental container is being started
+    Provisioning,
+
     /// Rental is active
     Active,
 
+    /// Rental container is being torn down
+    Terminating,
+
     /// Rental is terminated
     Terminated,
 
@@ -189,6 +196,12 @@ pub struct ListExecutorsQuery {
 
     /// Page size
     pub page_size: Option<u32>,
+
+    /// Aggregation policy, the configured default if unset
+    pub consistency: Option<AggregationMode>,
+
+    /// Agreeing validators required by the `quorum` policy
+    pub quorum: Option<usize>,
 }
 
 /// List executors response
@@ -205,6 +218,10 @@ pub struct ListExecutorsResponse {
 
     /// Page size
     pub page_size: u32,
+
+    /// How validator responses were merged, set by the gateway
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregation: Option<AggregationReport>,
 }
 
 /// Validator details
@@ -222,6 +239,9 @@ pub struct ValidatorDetails {
     /// Validator score
     pub score: f64,
 
+    /// Total stake in TAO
+    pub stake: f64,
+
     /// Is healthy
     pub is_healthy: bool,
 
@@ -278,6 +298,12 @@ pub struct ListMinersQuery {
 
     /// Page size
     pub page_size: Option<u32>,
+
+    /// Aggregation policy, the configured default if unset
+    pub consistency: Option<AggregationMode>,
+
+    /// Agreeing validators required by the `quorum` policy
+    pub quorum: Option<usize>,
 }
 
 /// List miners response
@@ -294,6 +320,10 @@ pub struct ListMinersResponse {
 
     /// Page size
     pub page_size: u32,
+
+    /// How validator responses were merged, set by the gateway
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregation: Option<AggregationReport>,
 }
 
 /// Health check response
@@ -343,15 +373,29 @@ pub struct ApiKeyInfo {
     /// API key ID
     pub key_id: String,
 
+    /// Account the key belongs to
+    pub owner: String,
+
     /// API key tier
     pub tier: ApiKeyTier,
 
+    /// Operations the key may perform
+    pub scopes: Vec<String>,
+
     /// Rate limit override
     pub rate_limit_override: Option<u32>,
 }
 
+impl ApiKeyInfo {
+    /// Whether the key was granted `scope`
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+}
+
 /// API key tiers
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum ApiKeyTier {
     /// Free tier
     Free,
@@ -362,3 +406,218 @@ pub enum ApiKeyTier {
     /// Enterprise tier
     Enterprise,
 }
+
+impl ApiKeyTier {
+    /// Name used in requests and storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyTier::Free => "free",
+            ApiKeyTier::Premium => "premium",
+            ApiKeyTier::Enterprise => "enterprise",
+        }
+    }
+}
+
+impl std::str::FromStr for ApiKeyTier {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "free" => Ok(ApiKeyTier::Free),
+            "premium" => Ok(ApiKeyTier::Premium),
+            "enterprise" => Ok(ApiKeyTier::Enterprise),
+            other => Err(anyhow::anyhow!("Unknown API key tier: {other}")),
+        }
+    }
+}
+
+/// Request to issue an API key
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Account the key is issued to
+    pub owner: String,
+
+    /// Key tier
+    pub tier: ApiKeyTier,
+
+    /// Granted scopes, defaults to read and write
+    pub scopes: Option<Vec<String>>,
+
+    /// Requests per minute replacing the tier's limit
+    pub rate_limit_override: Option<u32>,
+
+    /// Days until the key expires, never if omitted
+    pub expires_in_days: Option<u32>,
+}
+
+/// API key details, without the secret
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyDetails {
+    /// Key ID
+    pub key_id: String,
+
+    /// Account the key belongs to
+    pub owner: String,
+
+    /// Key tier
+    pub tier: ApiKeyTier,
+
+    /// Granted scopes
+    pub scopes: Vec<String>,
+
+    /// Requests per minute replacing the tier's limit
+    pub rate_limit_override: Option<u32>,
+
+    /// Creation timestamp
+    pub created_at: chrono::DateTime<chrono::Utc>,
+
+    /// Expiry timestamp
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Revocation timestamp
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Newly issued or rotated API key
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IssuedApiKeyResponse {
+    /// The full key, only returned once
+    pub api_key: String,
+
+    /// Key details
+    pub key: ApiKeyDetails,
+}
+
+/// List API keys query parameters
+#[derive(Debug, Deserialize)]
+pub struct ListApiKeysQuery {
+    /// Only list keys of this owner
+    pub owner: Option<String>,
+}
+
+/// List API keys response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListApiKeysResponse {
+    /// API keys
+    pub keys: Vec<ApiKeyDetails>,
+
+    /// Total count
+    pub total_count: usize,
+}
+
+/// Usage of the calling API key
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UsageResponse {
+    /// Key ID
+    pub key_id: String,
+
+    /// Key tier
+    pub tier: ApiKeyTier,
+
+    /// Whether requests are rejected once a quota is exhausted
+    pub quotas_enforced: bool,
+
+    /// Usage of the current UTC day
+    pub daily: PeriodUsage,
+
+    /// Usage of the current UTC month
+    pub monthly: PeriodUsage,
+}
+
+/// Usage within one quota period
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PeriodUsage {
+    /// Start of the period
+    pub period_start: chrono::DateTime<chrono::Utc>,
+
+    /// Total requests
+    pub requests: u64,
+
+    /// Requests per route
+    pub requests_by_route: std::collections::BTreeMap<String, u64>,
+
+    /// GPU-hours of rentals
+    pub gpu_hours: f64,
+
+    /// Bytes of streamed logs
+    pub log_bytes: u64,
+
+    /// Quotas of the period, unlimited if unset
+    pub limits: UsageLimits,
+}
+
+/// Quotas of one period
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct UsageLimits {
+    /// Requests
+    pub requests: Option<u64>,
+
+    /// GPU-hours of rentals
+    pub gpu_hours: Option<f64>,
+
+    /// Bytes of streamed logs
+    pub log_bytes: Option<u64>,
+}
+
+/// How listings from several validators were merged
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AggregationReport {
+    /// Aggregation policy applied
+    #[schema(value_type = String, example = "majority")]
+    pub mode: AggregationMode,
+
+    /// Agreeing validators required, in `quorum` mode
+    pub quorum: Option<usize>,
+
+    /// Validators queried
+    pub validators_queried: usize,
+
+    /// Validators that answered
+    pub validators_responded: usize,
+
+    /// Items left out because the policy's agreement was not reached
+    pub excluded_items: usize,
+
+    /// Items on which the responding validators diverged
+    pub disagreements: Vec<ItemDisagreement>,
+}
+
+/// Divergence between validators on one item
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ItemDisagreement {
+    /// Item ID
+    pub id: String,
+
+    /// Whether the item is part of the response
+    pub included: bool,
+
+    /// UIDs of validators reporting the item
+    pub reported_by: Vec<u16>,
+
+    /// UIDs of responding validators not reporting the item
+    pub missing_from: Vec<u16>,
+
+    /// Fields reported with different values
+    pub fields: Vec<FieldDisagreement>,
+}
+
+/// Values reported for one field of an item
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldDisagreement {
+    /// Field name
+    pub field: String,
+
+    /// Distinct values, the chosen one first
+    pub values: Vec<ReportedValue>,
+}
+
+/// A field value and the validators reporting it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReportedValue {
+    /// Reported value
+    #[schema(value_type = Object)]
+    pub value: serde_json::Value,
+
+    /// UIDs of validators reporting the value
+    pub validators: Vec<u16>,
+}
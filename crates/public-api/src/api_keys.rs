@@ -0,0 +1,355 @@
+//! API key issuance and verification
+//!
+//! Keys have the form `sk_<key_id>_<secret>`. The key ID locates the stored
+//! record and the secret is checked against its hash, so a key can only be
+//! used if it was issued here and has not been revoked or rotated since.
+
+use crate::{
+    api::types::{ApiKeyInfo, ApiKeyTier},
+    config::AuthConfig,
+    error::{Error, Result},
+    persistence::{ApiKeyRecord, GatewayDb},
+};
+use chrono::{Duration, Utc};
+use moka::future::Cache;
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::info;
+
+/// Prefix of every issued API key
+pub const API_KEY_PREFIX: &str = "sk_";
+
+/// Key ID of requests authenticated with a master key
+pub const MASTER_KEY_ID: &str = "master";
+/// Key ID of unauthenticated requests
+pub const ANONYMOUS_KEY_ID: &str = "anonymous";
+
+/// Scope for reading rentals, executors, validators and miners
+pub const SCOPE_READ: &str = "read";
+/// Scope for creating and terminating rentals
+pub const SCOPE_WRITE: &str = "write";
+/// Scope for managing API keys
+pub const SCOPE_ADMIN: &str = "admin";
+
+/// Scopes granted when none are requested
+pub const DEFAULT_SCOPES: &[&str] = &[SCOPE_READ, SCOPE_WRITE];
+
+const KNOWN_SCOPES: &[&str] = &[SCOPE_READ, SCOPE_WRITE, SCOPE_ADMIN];
+const SECRET_LENGTH: usize = 40;
+
+/// Settings of a key to issue
+#[derive(Debug, Clone)]
+pub struct NewApiKey {
+    pub owner: String,
+    pub tier: ApiKeyTier,
+    /// Granted scopes, `DEFAULT_SCOPES` when empty
+    pub scopes: Vec<String>,
+    pub rate_limit_override: Option<u32>,
+    pub expires_in: Option<Duration>,
+}
+
+/// A key together with the secret the client has to present
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+    /// The full key, which is not stored and cannot be recovered
+    pub api_key: String,
+    pub record: ApiKeyRecord,
+}
+
+/// Persistent API key store with a lookup cache in front of it
+///
+/// Records are cached by key ID, including IDs that do not exist, so repeated
+/// requests with the same key do not reach the database. Changes made through
+/// this store take effect immediately; changes made by other gateway instances
+/// take effect once the cached entry expires.
+#[derive(Clone)]
+pub struct ApiKeyStore {
+    db: GatewayDb,
+    cache: Cache<String, Option<Arc<ApiKeyRecord>>>,
+}
+
+impl ApiKeyStore {
+    /// Create a store caching lookups as configured in `config`
+    pub fn new(db: GatewayDb, config: &AuthConfig) -> Self {
+        let cache = Cache::builder()
+            .max_capacity(config.key_cache_capacity)
+            .time_to_live(std::time::Duration::from_secs(config.key_cache_ttl_secs))
+            .build();
+
+        Self { db, cache }
+    }
+
+    /// Issue a new key
+    pub async fn issue(&self, new_key: NewApiKey) -> Result<IssuedApiKey> {
+        let owner = new_key.owner.trim();
+        if owner.is_empty() {
+            return Err(Error::InvalidRequest {
+                message: "API key owner is required".to_string(),
+            });
+        }
+        if new_key.rate_limit_override == Some(0) {
+            return Err(Error::InvalidRequest {
+                message: "Rate limit override must be positive".to_string(),
+            });
+        }
+        let scopes = validate_scopes(new_key.scopes)?;
+
+        let key_id = generate_key_id();
+        let secret = generate_secret();
+        let now = Utc::now();
+        let record = ApiKeyRecord {
+            key_id: key_id.clone(),
+            secret_hash: hash_secret(&secret),
+            owner: owner.to_string(),
+            tier: new_key.tier,
+            scopes,
+            rate_limit_override: new_key.rate_limit_override,
+            created_at: now,
+            expires_at: new_key.expires_in.map(|duration| now + duration),
+            revoked_at: None,
+        };
+        self.db.insert_api_key(&record).await?;
+        self.cache.invalidate(&key_id).await;
+
+        info!(
+            "Issued {} API key {} to {}",
+            record.tier.as_str(),
+            key_id,
+            record.owner
+        );
+        Ok(IssuedApiKey {
+            api_key: format_api_key(&key_id, &secret),
+            record,
+        })
+    }
+
+    /// Replace a key's secret, invalidating the previous one
+    pub async fn rotate(&self, key_id: &str) -> Result<IssuedApiKey> {
+        let secret = generate_secret();
+        if !self
+            .db
+            .update_api_key_secret(key_id, &hash_secret(&secret))
+            .await?
+        {
+            return Err(Error::NotFound {
+                resource: format!("Active API key {key_id}"),
+            });
+        }
+        self.cache.invalidate(key_id).await;
+
+        let record = self.get(key_id).await?;
+        info!("Rotated API key {} of {}", key_id, record.owner);
+        Ok(IssuedApiKey {
+            api_key: format_api_key(key_id, &secret),
+            record,
+        })
+    }
+
+    /// Revoke a key, rejecting it from now on
+    pub async fn revoke(&self, key_id: &str) -> Result<ApiKeyRecord> {
+        if !self.db.revoke_api_key(key_id, Utc::now()).await? {
+            return Err(Error::NotFound {
+                resource: format!("Active API key {key_id}"),
+            });
+        }
+        self.cache.invalidate(key_id).await;
+
+        let record = self.get(key_id).await?;
+        info!("Revoked API key {} of {}", key_id, record.owner);
+        Ok(record)
+    }
+
+    /// Stored record of a key
+    pub async fn get(&self, key_id: &str) -> Result<ApiKeyRecord> {
+        self.db
+            .get_api_key(key_id)
+            .await?
+            .ok_or_else(|| Error::NotFound {
+                resource: format!("API key {key_id}"),
+            })
+    }
+
+    /// Keys of one owner, or of every owner when `None`
+    pub async fn list(&self, owner: Option<&str>) -> Result<Vec<ApiKeyRecord>> {
+        Ok(self.db.list_api_keys(owner).await?)
+    }
+
+    /// Resolve a key presented by a client
+    pub async fn authenticate(&self, api_key: &str) -> Result<ApiKeyInfo> {
+        let invalid = || Error::Authentication {
+            message: "Invalid API key".to_string(),
+        };
+        let (key_id, secret) = parse_api_key(api_key).ok_or_else(invalid)?;
+
+        let db = self.db.clone();
+        let lookup_id = key_id.to_string();
+        let record = self
+            .cache
+            .try_get_with(key_id.to_string(), async move {
+                db.get_api_key(&lookup_id)
+                    .await
+                    .map(|record| record.map(Arc::new))
+            })
+            .await
+            .map_err(|e| Error::Internal {
+                message: format!("Failed to look up API key: {e}"),
+            })?
+            .ok_or_else(invalid)?;
+
+        if !common::crypto::secure_compare(
+            hash_secret(secret).as_bytes(),
+            record.secret_hash.as_bytes(),
+        ) {
+            return Err(invalid());
+        }
+        if record.revoked_at.is_some() {
+            return Err(Error::Authentication {
+                message: "API key has been revoked".to_string(),
+            });
+        }
+        if !record.is_active(Utc::now()) {
+            return Err(Error::Authentication {
+                message: "API key has expired".to_string(),
+            });
+        }
+
+        Ok(ApiKeyInfo {
+            key_id: record.key_id.clone(),
+            owner: record.owner.clone(),
+            tier: record.tier,
+            scopes: record.scopes.clone(),
+            rate_limit_override: record.rate_limit_override,
+        })
+    }
+}
+
+/// Split a presented key into its ID and secret
+fn parse_api_key(api_key: &str) -> Option<(&str, &str)> {
+    let (key_id, secret) = api_key.strip_prefix(API_KEY_PREFIX)?.split_once('_')?;
+    if key_id.is_empty() || secret.len() != SECRET_LENGTH {
+        return None;
+    }
+    Some((key_id, secret))
+}
+
+fn format_api_key(key_id: &str, secret: &str) -> String {
+    format!("{API_KEY_PREFIX}{key_id}_{secret}")
+}
+
+fn generate_key_id() -> String {
+    format!("{:016x}", rand::thread_rng().gen::<u64>())
+}
+
+fn generate_secret() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+/// Deduplicate requested scopes, rejecting unknown ones
+fn validate_scopes(scopes: Vec<String>) -> Result<Vec<String>> {
+    if scopes.is_empty() {
+        return Ok(DEFAULT_SCOPES.iter().map(|s| s.to_string()).collect());
+    }
+
+    let mut validated: Vec<String> = Vec::with_capacity(scopes.len());
+    for scope in scopes {
+        if !KNOWN_SCOPES.contains(&scope.as_str()) {
+            return Err(Error::InvalidRequest {
+                message: format!("Unknown scope: {scope}"),
+            });
+        }
+        if !validated.contains(&scope) {
+            validated.push(scope);
+        }
+    }
+    Ok(validated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::config::DatabaseConfig;
+
+    async fn test_store(dir: &std::path::Path) -> ApiKeyStore {
+        let db = GatewayDb::new(&DatabaseConfig {
+            url: format!("sqlite:{}", dir.join("public-api.db").display()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        ApiKeyStore::new(db, &AuthConfig::default())
+    }
+
+    fn new_key(scopes: &[&str]) -> NewApiKey {
+        NewApiKey {
+            owner: "acme".to_string(),
+            tier: ApiKeyTier::Premium,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            rate_limit_override: None,
+            expires_in: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_issue_rotate_revoke() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = test_store(dir.path()).await;
+
+        let issued = store.issue(new_key(&[])).await.unwrap();
+        assert!(issued.api_key.starts_with(API_KEY_PREFIX));
+        assert_ne!(issued.record.secret_hash, issued.api_key);
+
+        let info = store.authenticate(&issued.api_key).await.unwrap();
+        assert_eq!(info.key_id, issued.record.key_id);
+        assert_eq!(info.tier, ApiKeyTier::Premium);
+        assert!(info.has_scope(SCOPE_WRITE));
+        assert!(!info.has_scope(SCOPE_ADMIN));
+
+        // A well-formed key with the wrong secret is rejected
+        let forged = format_api_key(&issued.record.key_id, &generate_secret());
+        assert!(store.authenticate(&forged).await.is_err());
+        assert!(store
+            .authenticate("sk_enterprise_0123456789abcdef01")
+            .await
+            .is_err());
+
+        let rotated = store.rotate(&issued.record.key_id).await.unwrap();
+        assert!(store.authenticate(&issued.api_key).await.is_err());
+        assert!(store.authenticate(&rotated.api_key).await.is_ok());
+
+        store.revoke(&issued.record.key_id).await.unwrap();
+        assert!(store.authenticate(&rotated.api_key).await.is_err());
+        assert!(store.revoke(&issued.record.key_id).await.is_err());
+        assert!(store.rotate(&issued.record.key_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_expired_keys_and_scope_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = test_store(dir.path()).await;
+
+        let mut expired = new_key(&[SCOPE_READ]);
+        expired.expires_in = Some(Duration::seconds(-1));
+        let issued = store.issue(expired).await.unwrap();
+        assert!(store.authenticate(&issued.api_key).await.is_err());
+
+        let issued = store
+            .issue(new_key(&[SCOPE_ADMIN, SCOPE_READ, SCOPE_ADMIN]))
+            .await
+            .unwrap();
+        assert_eq!(issued.record.scopes, vec![SCOPE_ADMIN, SCOPE_READ]);
+
+        assert!(store.issue(new_key(&["billing"])).await.is_err());
+        let mut unowned = new_key(&[]);
+        unowned.owner = " ".to_string();
+        assert!(store.issue(unowned).await.is_err());
+    }
+}
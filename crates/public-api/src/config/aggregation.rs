@@ -0,0 +1,39 @@
+//! Multi-validator aggregation configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Aggregation of listings queried from several validators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationConfig {
+    /// Policy used when a request does not choose one
+    pub mode: AggregationMode,
+
+    /// Number of agreeing validators required in `quorum` mode
+    pub quorum: usize,
+}
+
+/// How listings from several validators are merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMode {
+    /// Every item reported by any validator; fields by majority vote
+    Union,
+
+    /// Items reported by more than half of the validators; fields by majority vote
+    Majority,
+
+    /// Items backed by more than half of the responding stake; fields by stake vote
+    StakeWeighted,
+
+    /// Items and fields reported identically by at least `quorum` validators
+    Quorum,
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            mode: AggregationMode::Union,
+            quorum: 2,
+        }
+    }
+}
@@ -19,6 +19,12 @@ pub struct AuthConfig {
 
     /// Master API keys for admin access
     pub master_api_keys: Vec<String>,
+
+    /// Seconds a looked-up API key is cached before the store is consulted again
+    pub key_cache_ttl_secs: u64,
+
+    /// Maximum number of API keys held in the lookup cache
+    pub key_cache_capacity: u64,
 }
 
 impl Default for AuthConfig {
@@ -29,6 +35,8 @@ impl Default for AuthConfig {
             jwt_expiration_hours: 24,
             allow_anonymous: true,
             master_api_keys: vec![],
+            key_cache_ttl_secs: 60,
+            key_cache_capacity: 10_000,
         }
     }
 }
@@ -1,22 +1,28 @@
 //! Configuration module for the Public API gateway
 
+mod aggregation;
 mod auth;
 mod cache;
 mod discovery;
 mod load_balancer;
+mod quota;
 mod rate_limit;
 mod server;
+mod signing;
 mod telemetry;
 
+pub use aggregation::{AggregationConfig, AggregationMode};
 pub use auth::AuthConfig;
 pub use cache::{CacheBackend, CacheConfig};
 pub use discovery::DiscoveryConfig;
 pub use load_balancer::{LoadBalancerConfig, LoadBalancerStrategy};
+pub use quota::{QuotaConfig, TierQuota};
 pub use rate_limit::{RateLimitBackend, RateLimitConfig};
 pub use server::ServerConfig;
+pub use signing::SigningConfig;
 pub use telemetry::TelemetryConfig;
 
-use common::config::{BittensorConfig, ConfigLoader};
+use common::config::{BittensorConfig, ConfigLoader, DatabaseConfig};
 use common::ConfigurationError as ConfigError;
 use figment::{
     providers::{Env, Format, Serialized, Toml},
@@ -58,7 +64,7 @@ impl Default for BittensorIntegrationConfig {
 }
 
 /// Main configuration structure for the Public API
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// Server configuration
     pub server: ServerConfig,
@@ -83,6 +89,45 @@ pub struct Config {
 
     /// Validator discovery configuration
     pub discovery: DiscoveryConfig,
+
+    /// Per-tier usage quotas
+    pub quotas: QuotaConfig,
+
+    /// Signing of requests forwarded to validators
+    pub signing: SigningConfig,
+
+    /// Merging of listings from several validators
+    pub aggregation: AggregationConfig,
+
+    /// SQLite database holding issued API keys and metered usage
+    #[serde(default = "default_database_config")]
+    pub database: DatabaseConfig,
+}
+
+fn default_database_config() -> DatabaseConfig {
+    DatabaseConfig {
+        url: "sqlite:./data/public-api.db".to_string(),
+        ..Default::default()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            server: ServerConfig::default(),
+            bittensor: BittensorIntegrationConfig::default(),
+            load_balancer: LoadBalancerConfig::default(),
+            cache: CacheConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+            auth: AuthConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            discovery: DiscoveryConfig::default(),
+            quotas: QuotaConfig::default(),
+            signing: SigningConfig::default(),
+            aggregation: AggregationConfig::default(),
+            database: default_database_config(),
+        }
+    }
 }
 
 impl Config {
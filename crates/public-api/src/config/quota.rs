@@ -0,0 +1,74 @@
+//! Usage quota configuration
+
+use crate::api::types::ApiKeyTier;
+use serde::{Deserialize, Serialize};
+
+/// Usage quota configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Enforce quotas (usage is metered either way)
+    pub enabled: bool,
+
+    /// Seconds between writes of metered usage to the database
+    pub flush_interval_secs: u64,
+
+    /// Free tier quotas
+    pub free: TierQuota,
+
+    /// Premium tier quotas
+    pub premium: TierQuota,
+
+    /// Enterprise tier quotas
+    pub enterprise: TierQuota,
+}
+
+/// Quotas of one tier; unset quotas are unlimited
+///
+/// Days and months are UTC calendar days and months.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TierQuota {
+    /// Requests per day
+    pub daily_requests: Option<u64>,
+
+    /// Requests per month
+    pub monthly_requests: Option<u64>,
+
+    /// GPU-hours of rentals per month
+    pub monthly_gpu_hours: Option<f64>,
+
+    /// Bytes of streamed logs per month
+    pub monthly_log_bytes: Option<u64>,
+}
+
+impl QuotaConfig {
+    /// Quotas applying to keys of `tier`
+    pub fn for_tier(&self, tier: ApiKeyTier) -> &TierQuota {
+        match tier {
+            ApiKeyTier::Free => &self.free,
+            ApiKeyTier::Premium => &self.premium,
+            ApiKeyTier::Enterprise => &self.enterprise,
+        }
+    }
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            flush_interval_secs: 10,
+            free: TierQuota {
+                daily_requests: Some(1_000),
+                monthly_requests: Some(20_000),
+                monthly_gpu_hours: Some(10.0),
+                monthly_log_bytes: Some(1024 * 1024 * 1024), // 1GB
+            },
+            premium: TierQuota {
+                daily_requests: Some(50_000),
+                monthly_requests: Some(1_000_000),
+                monthly_gpu_hours: Some(1_000.0),
+                monthly_log_bytes: Some(50 * 1024 * 1024 * 1024), // 50GB
+            },
+            enterprise: TierQuota::default(),
+        }
+    }
+}
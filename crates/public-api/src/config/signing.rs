@@ -0,0 +1,29 @@
+//! Request signing configuration
+
+use common::crypto::SignatureScheme;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Signing of requests forwarded to validators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningConfig {
+    /// Sign forwarded requests
+    pub enabled: bool,
+
+    /// Key type to sign with: `sr25519` signs with the gateway's hotkey,
+    /// `ed25519` with the service key at `ed25519_key_path`
+    pub scheme: SignatureScheme,
+
+    /// PEM file holding the Ed25519 service key
+    pub ed25519_key_path: Option<PathBuf>,
+}
+
+impl Default for SigningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            scheme: SignatureScheme::Sr25519,
+            ed25519_key_path: None,
+        }
+    }
+}
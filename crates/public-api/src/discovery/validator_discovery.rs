@@ -23,6 +23,9 @@ pub struct ValidatorInfo {
     /// Validator score/weight in the network
     pub score: f64,
 
+    /// Total stake backing the validator, in TAO
+    pub stake: f64,
+
     /// Is the validator currently active
     pub is_active: bool,
 
@@ -167,12 +170,19 @@ impl ValidatorDiscovery {
             return Ok(false);
         };
 
+        let total_stake = metagraph
+            .total_stake
+            .get(uid as usize)
+            .map(|s| s.0)
+            .unwrap_or(0);
+
         // Create or update validator info
         let validator_info = ValidatorInfo {
             uid,
             hotkey,
             endpoint,
             score,
+            stake: bittensor::rao_to_tao(total_stake),
             is_active: true,
             last_health_check: None,
             is_healthy: false, // Will be updated by health monitor
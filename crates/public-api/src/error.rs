@@ -48,6 +48,14 @@ pub enum Error {
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    /// Request quota of the API key exhausted
+    #[error("Quota exceeded: {message}")]
+    QuotaExceeded { message: String },
+
+    /// Paid resource quota of the API key exhausted
+    #[error("Payment required: {message}")]
+    PaymentRequired { message: String },
+
     /// Invalid request
     #[error("Invalid request: {message}")]
     InvalidRequest { message: String },
@@ -56,6 +64,10 @@ pub enum Error {
     #[error("Aggregation error: {message}")]
     Aggregation { message: String },
 
+    /// Too few validators responded or agreed for the requested quorum
+    #[error("Quorum not met: {message}")]
+    QuorumNotMet { message: String },
+
     /// Cache error
     #[error("Cache error: {message}")]
     Cache { message: String },
@@ -103,8 +115,11 @@ impl Error {
             Error::Authentication { .. } => "PUBLIC_API_AUTH_ERROR",
             Error::Authorization { .. } => "PUBLIC_API_AUTHZ_ERROR",
             Error::RateLimitExceeded => "PUBLIC_API_RATE_LIMIT",
+            Error::QuotaExceeded { .. } => "PUBLIC_API_QUOTA_EXCEEDED",
+            Error::PaymentRequired { .. } => "PUBLIC_API_PAYMENT_REQUIRED",
             Error::InvalidRequest { .. } => "PUBLIC_API_INVALID_REQUEST",
             Error::Aggregation { .. } => "PUBLIC_API_AGGREGATION_ERROR",
+            Error::QuorumNotMet { .. } => "PUBLIC_API_QUORUM_NOT_MET",
             Error::Cache { .. } => "PUBLIC_API_CACHE_ERROR",
             Error::Timeout => "PUBLIC_API_TIMEOUT",
             Error::Internal { .. } => "PUBLIC_API_INTERNAL_ERROR",
@@ -124,6 +139,7 @@ impl Error {
                 | Error::Timeout
                 | Error::ServiceUnavailable
                 | Error::NoValidatorsAvailable
+                | Error::QuorumNotMet { .. }
         )
     }
 
@@ -134,6 +150,8 @@ impl Error {
             Error::Authentication { .. }
                 | Error::Authorization { .. }
                 | Error::RateLimitExceeded
+                | Error::QuotaExceeded { .. }
+                | Error::PaymentRequired { .. }
                 | Error::InvalidRequest { .. }
                 | Error::NotFound { .. }
         )
@@ -155,8 +173,11 @@ impl IntoResponse for Error {
                 StatusCode::TOO_MANY_REQUESTS,
                 "Too many requests. Please try again later.".to_string(),
             ),
+            Error::QuotaExceeded { .. } => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
+            Error::PaymentRequired { .. } => (StatusCode::PAYMENT_REQUIRED, self.to_string()),
             Error::InvalidRequest { .. } => (StatusCode::BAD_REQUEST, self.to_string()),
             Error::Aggregation { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+            Error::QuorumNotMet { .. } => (StatusCode::SERVICE_UNAVAILABLE, self.to_string()),
             Error::Cache { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
             Error::Timeout => (StatusCode::REQUEST_TIMEOUT, self.to_string()),
             Error::Internal { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
@@ -222,6 +243,10 @@ mod tests {
     fn test_retryable_errors() {
         assert!(Error::Timeout.is_retryable());
         assert!(Error::ServiceUnavailable.is_retryable());
+        assert!(Error::QuorumNotMet {
+            message: "test".to_string()
+        }
+        .is_retryable());
         assert!(!Error::Authentication {
             message: "test".to_string()
         }
@@ -235,6 +260,10 @@ mod tests {
         }
         .is_client_error());
         assert!(Error::RateLimitExceeded.is_client_error());
+        assert!(Error::PaymentRequired {
+            message: "test".to_string()
+        }
+        .is_client_error());
         assert!(!Error::Timeout.is_client_error());
     }
 }
@@ -6,20 +6,28 @@
 //!
 //! - **Validator Discovery**: Automatic discovery of validators using Bittensor metagraph
 //! - **Load Balancing**: Multiple strategies for distributing requests across validators
+//! - **Sticky Rental Routing**: Rental requests follow the validator owning the rental, with failover
 //! - **Request Aggregation**: Combine responses from multiple validators
-//! - **Authentication**: API key and JWT-based authentication
+//! - **Request Signing**: Requests forwarded to validators are signed with the gateway's key
+//! - **Authentication**: Stored, revocable API keys with tiers and scopes
 //! - **Rate Limiting**: Configurable rate limits with different tiers
+//! - **Usage Metering**: Per-key request, GPU-hour and log quotas persisted across restarts
 //! - **Caching**: Response caching with in-memory or Redis backends
 //! - **OpenAPI Documentation**: Auto-generated API documentation
 //! - **Monitoring**: Prometheus metrics and distributed tracing
 
 pub mod aggregator;
 pub mod api;
+pub mod api_keys;
 pub mod config;
 pub mod discovery;
 pub mod error;
 pub mod load_balancer;
+pub mod persistence;
+pub mod routing;
 pub mod server;
+pub mod signing;
+pub mod usage;
 
 // Re-export commonly used types
 pub use config::Config;
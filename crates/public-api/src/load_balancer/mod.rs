@@ -59,6 +59,11 @@ impl LoadBalancer {
         self.strategy.select_with_key(key).await
     }
 
+    /// Count a request to a validator chosen outside the strategy
+    pub fn track_request(&self, validator_uid: u16) {
+        self.pool.increment_connections(validator_uid);
+    }
+
     /// Report a successful request to a validator
     pub fn report_success(&self, validator_uid: u16) {
         self.pool.decrement_connections(validator_uid);
@@ -0,0 +1,661 @@
+//! Gateway state persisted in SQLite
+//!
+//! Holds the API keys issued to clients, what each key consumed and which
+//! validator owns each rental. Only a hash of each key's secret is stored; the
+//! secret itself is shown once when the key is issued or rotated.
+
+use crate::api::types::ApiKeyTier;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use common::config::DatabaseConfig;
+use common::persistence::SqliteConnection;
+use sqlx::{FromRow, SqlitePool};
+use std::path::Path;
+use tracing::info;
+
+/// API key issued to a client
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKeyRecord {
+    pub key_id: String,
+    /// Hex SHA-256 of the key's secret
+    pub secret_hash: String,
+    /// Account the key was issued to
+    pub owner: String,
+    pub tier: ApiKeyTier,
+    pub scopes: Vec<String>,
+    /// Requests per minute replacing the tier's limit
+    pub rate_limit_override: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKeyRecord {
+    /// Whether the key may still be used at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires| expires > now)
+    }
+}
+
+#[derive(FromRow)]
+struct ApiKeyRow {
+    key_id: String,
+    secret_hash: String,
+    owner: String,
+    tier: String,
+    scopes: String,
+    rate_limit_override: Option<i64>,
+    created_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<ApiKeyRow> for ApiKeyRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ApiKeyRow) -> Result<Self> {
+        Ok(Self {
+            tier: row.tier.parse()?,
+            scopes: serde_json::from_str(&row.scopes).context("Invalid stored API key scopes")?,
+            rate_limit_override: row
+                .rate_limit_override
+                .map(u32::try_from)
+                .transpose()
+                .map_err(|_| anyhow!("Invalid stored rate limit for key {}", row.key_id))?,
+            key_id: row.key_id,
+            secret_hash: row.secret_hash,
+            owner: row.owner,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+            revoked_at: row.revoked_at,
+        })
+    }
+}
+
+/// Requests and streamed log bytes of one key on one route
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteUsage {
+    pub route: String,
+    pub requests: u64,
+    pub log_bytes: u64,
+}
+
+/// Rental whose GPU time is metered against an API key
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct MeteredRental {
+    pub rental_id: String,
+    pub key_id: String,
+    pub gpu_count: u32,
+    pub started_at: DateTime<Utc>,
+    /// End of the requested rental duration
+    pub expires_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl MeteredRental {
+    /// GPU-hours consumed between `from` and `to`
+    ///
+    /// A rental runs from its start until it is terminated or its requested
+    /// duration elapses, whichever comes first.
+    pub fn gpu_hours_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+        let end = self
+            .ended_at
+            .map_or(self.expires_at, |ended| ended.min(self.expires_at))
+            .min(to);
+        let start = self.started_at.max(from);
+        if end <= start {
+            return 0.0;
+        }
+
+        let hours = (end - start).num_milliseconds() as f64 / 3_600_000.0;
+        hours * f64::from(self.gpu_count)
+    }
+}
+
+/// Validator a rental is routed to
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct RentalRoute {
+    pub rental_id: String,
+    pub validator_uid: u16,
+    /// Hotkey of the validator, so a reassigned UID is not mistaken for it
+    pub validator_hotkey: String,
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// Public API database client
+#[derive(Debug, Clone)]
+pub struct GatewayDb {
+    pool: SqlitePool,
+}
+
+impl GatewayDb {
+    /// Open the database, creating it and its tables if needed
+    pub async fn new(config: &DatabaseConfig) -> Result<Self> {
+        ensure_database_directory(&config.url)?;
+
+        let connection = SqliteConnection::new(config)
+            .await
+            .context("Failed to open public API database")?;
+        let db = Self {
+            pool: connection.pool().clone(),
+        };
+
+        if config.run_migrations {
+            db.run_migrations().await?;
+        }
+
+        Ok(db)
+    }
+
+    /// Get the connection pool
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    async fn run_migrations(&self) -> Result<()> {
+        info!("Running public API database migrations");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS api_keys (
+                key_id TEXT PRIMARY KEY,
+                secret_hash TEXT NOT NULL,
+                owner TEXT NOT NULL,
+                tier TEXT NOT NULL,
+                scopes TEXT NOT NULL,
+                rate_limit_override INTEGER,
+                created_at TIMESTAMP NOT NULL,
+                expires_at TIMESTAMP,
+                revoked_at TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create api_keys table")?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_api_keys_owner ON api_keys (owner)")
+            .execute(&self.pool)
+            .await
+            .context("Failed to create api_keys owner index")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS api_key_usage (
+                key_id TEXT NOT NULL,
+                day TEXT NOT NULL,
+                route TEXT NOT NULL,
+                requests INTEGER NOT NULL DEFAULT 0,
+                log_bytes INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (key_id, day, route)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create api_key_usage table")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS metered_rentals (
+                rental_id TEXT PRIMARY KEY,
+                key_id TEXT NOT NULL,
+                gpu_count INTEGER NOT NULL,
+                started_at TIMESTAMP NOT NULL,
+                expires_at TIMESTAMP NOT NULL,
+                ended_at TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create metered_rentals table")?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_metered_rentals_key ON metered_rentals (key_id)",
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create metered_rentals key index")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS rental_routes (
+                rental_id TEXT PRIMARY KEY,
+                validator_uid INTEGER NOT NULL,
+                validator_hotkey TEXT NOT NULL,
+                assigned_at TIMESTAMP NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create rental_routes table")?;
+
+        Ok(())
+    }
+
+    /// Record a newly issued API key
+    pub async fn insert_api_key(&self, record: &ApiKeyRecord) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO api_keys
+                (key_id, secret_hash, owner, tier, scopes, rate_limit_override,
+                 created_at, expires_at, revoked_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&record.key_id)
+        .bind(&record.secret_hash)
+        .bind(&record.owner)
+        .bind(record.tier.as_str())
+        .bind(serde_json::to_string(&record.scopes)?)
+        .bind(record.rate_limit_override.map(i64::from))
+        .bind(record.created_at)
+        .bind(record.expires_at)
+        .bind(record.revoked_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// API key with the given ID, whether or not it is still active
+    pub async fn get_api_key(&self, key_id: &str) -> Result<Option<ApiKeyRecord>> {
+        let row = sqlx::query_as::<_, ApiKeyRow>("SELECT * FROM api_keys WHERE key_id = ?")
+            .bind(key_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(ApiKeyRecord::try_from).transpose()
+    }
+
+    /// API keys of one owner, or of every owner when `None`
+    pub async fn list_api_keys(&self, owner: Option<&str>) -> Result<Vec<ApiKeyRecord>> {
+        let rows = match owner {
+            Some(owner) => {
+                sqlx::query_as::<_, ApiKeyRow>(
+                    "SELECT * FROM api_keys WHERE owner = ? ORDER BY created_at",
+                )
+                .bind(owner)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_as::<_, ApiKeyRow>("SELECT * FROM api_keys ORDER BY created_at")
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+        };
+        rows.into_iter().map(ApiKeyRecord::try_from).collect()
+    }
+
+    /// Replace the secret of a key that has not been revoked
+    ///
+    /// Returns false if there is no such key.
+    pub async fn update_api_key_secret(&self, key_id: &str, secret_hash: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE api_keys SET secret_hash = ? WHERE key_id = ? AND revoked_at IS NULL",
+        )
+        .bind(secret_hash)
+        .bind(key_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark a key as revoked at `revoked_at`
+    ///
+    /// Returns false if there is no such key or it was already revoked.
+    pub async fn revoke_api_key(&self, key_id: &str, revoked_at: DateTime<Utc>) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE api_keys SET revoked_at = ? WHERE key_id = ? AND revoked_at IS NULL",
+        )
+        .bind(revoked_at)
+        .bind(key_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Add requests and log bytes to a key's usage of `route` on `day`
+    pub async fn add_usage(
+        &self,
+        key_id: &str,
+        day: NaiveDate,
+        route: &str,
+        requests: u64,
+        log_bytes: u64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO api_key_usage (key_id, day, route, requests, log_bytes)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (key_id, day, route) DO UPDATE SET
+                requests = requests + excluded.requests,
+                log_bytes = log_bytes + excluded.log_bytes
+            "#,
+        )
+        .bind(key_id)
+        .bind(day)
+        .bind(route)
+        .bind(i64::try_from(requests)?)
+        .bind(i64::try_from(log_bytes)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// A key's usage per route from `from` through `to`, both inclusive
+    pub async fn usage_by_route(
+        &self,
+        key_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<RouteUsage>> {
+        let rows = sqlx::query_as::<_, (String, i64, i64)>(
+            r#"
+            SELECT route, SUM(requests), SUM(log_bytes)
+            FROM api_key_usage
+            WHERE key_id = ? AND day >= ? AND day <= ?
+            GROUP BY route
+            ORDER BY route
+            "#,
+        )
+        .bind(key_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(route, requests, log_bytes)| RouteUsage {
+                route,
+                requests: requests.max(0) as u64,
+                log_bytes: log_bytes.max(0) as u64,
+            })
+            .collect())
+    }
+
+    /// Start metering a rental
+    pub async fn insert_metered_rental(&self, rental: &MeteredRental) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO metered_rentals
+                (rental_id, key_id, gpu_count, started_at, expires_at, ended_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&rental.rental_id)
+        .bind(&rental.key_id)
+        .bind(rental.gpu_count)
+        .bind(rental.started_at)
+        .bind(rental.expires_at)
+        .bind(rental.ended_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stop metering a rental at `ended_at`
+    ///
+    /// Returns false if the rental is not metered or has already ended.
+    pub async fn end_metered_rental(
+        &self,
+        rental_id: &str,
+        ended_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE metered_rentals SET ended_at = ? WHERE rental_id = ? AND ended_at IS NULL",
+        )
+        .bind(ended_at)
+        .bind(rental_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Rentals of a key that were still running at `since`
+    pub async fn metered_rentals_since(
+        &self,
+        key_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<MeteredRental>> {
+        let rentals = sqlx::query_as::<_, MeteredRental>(
+            r#"
+            SELECT * FROM metered_rentals
+            WHERE key_id = ? AND expires_at > ? AND (ended_at IS NULL OR ended_at > ?)
+            ORDER BY started_at
+            "#,
+        )
+        .bind(key_id)
+        .bind(since)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rentals)
+    }
+
+    /// Route a rental to a validator, replacing any previous route
+    pub async fn upsert_rental_route(&self, route: &RentalRoute) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO rental_routes (rental_id, validator_uid, validator_hotkey, assigned_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (rental_id) DO UPDATE SET
+                validator_uid = excluded.validator_uid,
+                validator_hotkey = excluded.validator_hotkey,
+                assigned_at = excluded.assigned_at
+            "#,
+        )
+        .bind(&route.rental_id)
+        .bind(route.validator_uid)
+        .bind(&route.validator_hotkey)
+        .bind(route.assigned_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Validator a rental is routed to
+    pub async fn get_rental_route(&self, rental_id: &str) -> Result<Option<RentalRoute>> {
+        let route =
+            sqlx::query_as::<_, RentalRoute>("SELECT * FROM rental_routes WHERE rental_id = ?")
+                .bind(rental_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(route)
+    }
+}
+
+/// Create the directory holding a `sqlite:` database file
+fn ensure_database_directory(url: &str) -> Result<()> {
+    let Some(path) = url.strip_prefix("sqlite:") else {
+        return Ok(());
+    };
+    let path = path.trim_start_matches("//");
+    let path = path.split('?').next().unwrap_or(path);
+    if path.is_empty() || path.starts_with(":memory:") {
+        return Ok(());
+    }
+
+    if let Some(parent) = Path::new(path)
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    async fn test_db(dir: &Path) -> GatewayDb {
+        GatewayDb::new(&DatabaseConfig {
+            url: format!(
+                "sqlite:{}",
+                dir.join("data").join("public-api.db").display()
+            ),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_api_key_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+        let now = Utc::now();
+
+        let record = ApiKeyRecord {
+            key_id: "0123456789abcdef".to_string(),
+            secret_hash: "hash".to_string(),
+            owner: "acme".to_string(),
+            tier: ApiKeyTier::Premium,
+            scopes: vec!["read".to_string()],
+            rate_limit_override: Some(1200),
+            created_at: now,
+            expires_at: Some(now + Duration::days(30)),
+            revoked_at: None,
+        };
+        db.insert_api_key(&record).await.unwrap();
+        assert!(db.insert_api_key(&record).await.is_err());
+
+        let stored = db.get_api_key(&record.key_id).await.unwrap().unwrap();
+        assert_eq!(stored.tier, ApiKeyTier::Premium);
+        assert_eq!(stored.scopes, record.scopes);
+        assert_eq!(stored.rate_limit_override, Some(1200));
+        assert!(stored.is_active(now));
+        assert!(!stored.is_active(now + Duration::days(31)));
+
+        assert!(db
+            .update_api_key_secret(&record.key_id, "rotated")
+            .await
+            .unwrap());
+        assert!(db.revoke_api_key(&record.key_id, now).await.unwrap());
+        assert!(!db.revoke_api_key(&record.key_id, now).await.unwrap());
+        assert!(!db
+            .update_api_key_secret(&record.key_id, "again")
+            .await
+            .unwrap());
+
+        // Keys survive reopening the database
+        drop(db);
+        let db = test_db(dir.path()).await;
+        let stored = db.get_api_key(&record.key_id).await.unwrap().unwrap();
+        assert_eq!(stored.secret_hash, "rotated");
+        assert!(!stored.is_active(now));
+        assert_eq!(db.list_api_keys(Some("acme")).await.unwrap().len(), 1);
+        assert!(db.list_api_keys(Some("other")).await.unwrap().is_empty());
+        assert!(db.get_api_key("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_usage_accumulates_per_day_and_route() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+        let day = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let next_day = day.succ_opt().unwrap();
+
+        db.add_usage("key", day, "/api/v1/rentals", 2, 0)
+            .await
+            .unwrap();
+        db.add_usage("key", day, "/api/v1/rentals", 1, 0)
+            .await
+            .unwrap();
+        db.add_usage("key", next_day, "/api/v1/rentals/:rental_id/logs", 1, 512)
+            .await
+            .unwrap();
+        db.add_usage("other", day, "/api/v1/rentals", 7, 0)
+            .await
+            .unwrap();
+
+        let usage = db.usage_by_route("key", day, next_day).await.unwrap();
+        assert_eq!(
+            usage,
+            vec![
+                RouteUsage {
+                    route: "/api/v1/rentals".to_string(),
+                    requests: 3,
+                    log_bytes: 0,
+                },
+                RouteUsage {
+                    route: "/api/v1/rentals/:rental_id/logs".to_string(),
+                    requests: 1,
+                    log_bytes: 512,
+                },
+            ]
+        );
+        assert_eq!(db.usage_by_route("key", day, day).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_metered_rentals() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+        let start = Utc::now() - Duration::hours(10);
+
+        let rental = MeteredRental {
+            rental_id: "rental-1".to_string(),
+            key_id: "key".to_string(),
+            gpu_count: 2,
+            started_at: start,
+            expires_at: start + Duration::hours(24),
+            ended_at: None,
+        };
+        db.insert_metered_rental(&rental).await.unwrap();
+
+        let ended_at = start + Duration::hours(3);
+        assert!(db.end_metered_rental("rental-1", ended_at).await.unwrap());
+        assert!(!db.end_metered_rental("rental-1", Utc::now()).await.unwrap());
+
+        let rentals = db.metered_rentals_since("key", start).await.unwrap();
+        assert_eq!(rentals.len(), 1);
+        assert_eq!(rentals[0].ended_at, Some(ended_at));
+        let used = rentals[0].gpu_hours_between(start, Utc::now());
+        assert!((used - 6.0).abs() < 1e-6);
+
+        // Only the part of the rental inside the window is counted
+        let half = rentals[0].gpu_hours_between(start + Duration::hours(2), Utc::now());
+        assert!((half - 2.0).abs() < 1e-6);
+        assert!(db
+            .metered_rentals_since("key", start + Duration::hours(4))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rental_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = test_db(dir.path()).await;
+        assert!(db.get_rental_route("rental-1").await.unwrap().is_none());
+
+        let route = RentalRoute {
+            rental_id: "rental-1".to_string(),
+            validator_uid: 3,
+            validator_hotkey: "validator-3".to_string(),
+            assigned_at: Utc::now(),
+        };
+        db.upsert_rental_route(&route).await.unwrap();
+        assert_eq!(
+            db.get_rental_route("rental-1").await.unwrap(),
+            Some(route.clone())
+        );
+
+        // Failing over moves the rental to another validator
+        let moved = RentalRoute {
+            validator_uid: 7,
+            validator_hotkey: "validator-7".to_string(),
+            ..route
+        };
+        db.upsert_rental_route(&moved).await.unwrap();
+        drop(db);
+        let db = test_db(dir.path()).await;
+        assert_eq!(db.get_rental_route("rental-1").await.unwrap(), Some(moved));
+    }
+}
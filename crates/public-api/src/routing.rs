@@ -0,0 +1,237 @@
+//! Sticky routing of rental requests
+//!
+//! A rental only exists on the validator that created it, so every request
+//! about a rental goes to the validator recorded for it. When that validator
+//! is unhealthy, gone, or not recorded at all, the other validators are asked
+//! in turn, starting with the one the load balancer maps the rental ID to,
+//! and the first one that knows the rental becomes its new owner.
+
+use crate::{
+    aggregator::RequestDistributor,
+    discovery::{ValidatorDiscovery, ValidatorInfo},
+    error::{Error, Result},
+    persistence::{GatewayDb, RentalRoute},
+};
+use chrono::Utc;
+use reqwest::{Request, Response, StatusCode};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Routes rental requests to the validator owning the rental
+pub struct RentalRouter {
+    db: GatewayDb,
+    discovery: Arc<ValidatorDiscovery>,
+}
+
+impl RentalRouter {
+    /// Create a router using the routes persisted in `db`
+    pub fn new(db: GatewayDb, discovery: Arc<ValidatorDiscovery>) -> Self {
+        Self { db, discovery }
+    }
+
+    /// Record `validator` as the owner of a rental
+    pub async fn assign(&self, rental_id: &str, validator: &ValidatorInfo) -> Result<()> {
+        let route = RentalRoute {
+            rental_id: rental_id.to_string(),
+            validator_uid: validator.uid,
+            validator_hotkey: validator.hotkey.clone(),
+            assigned_at: Utc::now(),
+        };
+        self.db.upsert_rental_route(&route).await?;
+        Ok(())
+    }
+
+    /// Send a request about a rental to the validator owning it
+    ///
+    /// `build` creates the request for each validator tried. Returns the
+    /// validator that answered along with its response.
+    pub async fn send<F>(
+        &self,
+        distributor: &RequestDistributor,
+        rental_id: &str,
+        build: F,
+    ) -> Result<(ValidatorInfo, Response)>
+    where
+        F: Fn(&ValidatorInfo) -> Result<Request>,
+    {
+        let route = self.db.get_rental_route(rental_id).await?;
+        let owner = route
+            .as_ref()
+            .and_then(|route| live_owner(route, self.discovery.get_validator(route.validator_uid)));
+
+        let mut tried = HashSet::new();
+        if let Some(owner) = owner {
+            tried.insert(owner.uid);
+            match distributor.send_to_validator(build(&owner)?, &owner).await {
+                Ok(response) if response.status() == StatusCode::NOT_FOUND => {
+                    return Err(rental_not_found(rental_id));
+                }
+                Ok(response) => return Ok((owner, response)),
+                Err(e) => warn!(
+                    "Owner {} of rental {} failed, failing over: {}",
+                    owner.uid, rental_id, e
+                ),
+            }
+        } else if let Some(route) = &route {
+            warn!(
+                "Owner {} of rental {} is unavailable, failing over",
+                route.validator_uid, rental_id
+            );
+        }
+
+        let mut failover = Failover::default();
+
+        if tried.is_empty() {
+            match distributor.send_to_single_with_key(rental_id, &build).await {
+                Ok((validator, response)) => {
+                    tried.insert(validator.uid);
+                    if let Some(found) = failover.record(validator, response) {
+                        return self.rehome(rental_id, found).await;
+                    }
+                }
+                Err(e) => failover.last_error = Some(e),
+            }
+        }
+
+        let mut candidates = self.discovery.get_healthy_validators();
+        candidates.sort_by_key(|validator| validator.uid);
+        for validator in candidates {
+            if !tried.insert(validator.uid) {
+                continue;
+            }
+            match distributor
+                .send_to_validator(build(&validator)?, &validator)
+                .await
+            {
+                Ok(response) => {
+                    if let Some(found) = failover.record(validator, response) {
+                        return self.rehome(rental_id, found).await;
+                    }
+                }
+                Err(e) => failover.last_error = Some(e),
+            }
+        }
+
+        if let Some(answer) = failover.last_response {
+            return Ok(answer);
+        }
+        if failover.not_found {
+            return Err(rental_not_found(rental_id));
+        }
+        Err(failover.last_error.unwrap_or(Error::NoValidatorsAvailable))
+    }
+
+    /// Make the validator that answered the owner of the rental
+    async fn rehome(
+        &self,
+        rental_id: &str,
+        (validator, response): (ValidatorInfo, Response),
+    ) -> Result<(ValidatorInfo, Response)> {
+        info!(
+            "Routing rental {} to validator {}",
+            rental_id, validator.uid
+        );
+        // The answer is valid either way, so only the next request pays for this
+        if let Err(e) = self.assign(rental_id, &validator).await {
+            warn!("Failed to record route of rental {}: {}", rental_id, e);
+        }
+        Ok((validator, response))
+    }
+}
+
+/// Answers collected while failing over
+#[derive(Default)]
+struct Failover {
+    /// Last answer that was neither a success nor a 404
+    last_response: Option<(ValidatorInfo, Response)>,
+    last_error: Option<Error>,
+    /// Whether a validator answered that it does not know the rental
+    not_found: bool,
+}
+
+impl Failover {
+    /// Keep an answer, returning it if the validator knows the rental
+    fn record(
+        &mut self,
+        validator: ValidatorInfo,
+        response: Response,
+    ) -> Option<(ValidatorInfo, Response)> {
+        let status = response.status();
+        if status.is_success() {
+            return Some((validator, response));
+        }
+
+        debug!("Validator {} answered {}", validator.uid, status);
+        if status == StatusCode::NOT_FOUND {
+            self.not_found = true;
+        } else {
+            self.last_response = Some((validator, response));
+        }
+        None
+    }
+}
+
+/// The recorded owner of a rental, if it can still serve it
+///
+/// `current` is what discovery knows about the owner's UID. The owner is gone
+/// if the UID disappeared or now belongs to another hotkey.
+fn live_owner(route: &RentalRoute, current: Option<ValidatorInfo>) -> Option<ValidatorInfo> {
+    current.filter(|validator| {
+        validator.hotkey == route.validator_hotkey && validator.is_active && validator.is_healthy
+    })
+}
+
+fn rental_not_found(rental_id: &str) -> Error {
+    Error::NotFound {
+        resource: format!("Rental {rental_id}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(uid: u16, hotkey: &str, is_healthy: bool) -> ValidatorInfo {
+        ValidatorInfo {
+            uid,
+            hotkey: hotkey.to_string(),
+            endpoint: format!("http://validator-{uid}:8080"),
+            score: 0.75,
+            stake: 0.0,
+            is_active: true,
+            last_health_check: None,
+            is_healthy,
+            failure_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_live_owner() {
+        let route = RentalRoute {
+            rental_id: "rental-1".to_string(),
+            validator_uid: 3,
+            validator_hotkey: "validator-3".to_string(),
+            assigned_at: Utc::now(),
+        };
+
+        let owner = validator(3, "validator-3", true);
+        assert_eq!(
+            live_owner(&route, Some(owner.clone())).map(|v| v.uid),
+            Some(3)
+        );
+
+        // Unhealthy per the health monitor
+        assert!(live_owner(&route, Some(validator(3, "validator-3", false))).is_none());
+
+        // Deregistered, or the UID was given to another hotkey
+        assert!(live_owner(&route, None).is_none());
+        assert!(live_owner(&route, Some(validator(3, "other", true))).is_none());
+
+        let inactive = ValidatorInfo {
+            is_active: false,
+            ..owner
+        };
+        assert!(live_owner(&route, Some(inactive)).is_none());
+    }
+}
@@ -1,11 +1,16 @@
 //! Main server implementation for the Public API Gateway
 
 use crate::{
-    api,
+    api::{self, middleware::RateLimitStorage},
+    api_keys::ApiKeyStore,
     config::Config,
     discovery::ValidatorDiscovery,
     error::{Error, Result},
     load_balancer::LoadBalancer,
+    persistence::GatewayDb,
+    routing::RentalRouter,
+    signing::RequestSigner,
+    usage::UsageMeter,
 };
 use axum::Router;
 use std::sync::Arc;
@@ -22,6 +27,7 @@ use tracing::{info, warn};
 pub struct Server {
     config: Arc<Config>,
     app: Router,
+    usage: Arc<UsageMeter>,
 }
 
 /// Shared application state
@@ -38,6 +44,21 @@ pub struct AppState {
 
     /// HTTP client for validator requests
     pub http_client: reqwest::Client,
+
+    /// Signs requests to validators
+    pub signer: Arc<RequestSigner>,
+
+    /// Issued API keys
+    pub api_keys: Arc<ApiKeyStore>,
+
+    /// Usage metering and quotas
+    pub usage: Arc<UsageMeter>,
+
+    /// Routes rental requests to the validator owning the rental
+    pub rental_router: Arc<RentalRouter>,
+
+    /// Rate limiters, shared by all requests
+    pub rate_limits: Arc<RateLimitStorage>,
 }
 
 impl Server {
@@ -49,14 +70,20 @@ impl Server {
 
         // Initialize Bittensor service for validator discovery
         let bittensor_config = config.to_bittensor_config();
-        let bittensor_service = bittensor::Service::new(bittensor_config).await?;
+        let bittensor_service = Arc::new(bittensor::Service::new(bittensor_config).await?);
 
         // Initialize validator discovery
         let discovery = Arc::new(ValidatorDiscovery::new(
-            Arc::new(bittensor_service),
+            bittensor_service.clone(),
             config.clone(),
         ));
 
+        // Sign requests forwarded to validators
+        let signer = Arc::new(RequestSigner::from_config(
+            &config.signing,
+            bittensor_service,
+        )?);
+
         // Start discovery task
         let discovery_clone = discovery.clone();
         tokio::spawn(async move {
@@ -68,6 +95,21 @@ impl Server {
             config.load_balancer.strategy.clone(),
         )));
 
+        // Keep the load balancer's pool in step with discovery and health checks
+        let discovery_clone = discovery.clone();
+        let load_balancer_clone = load_balancer.clone();
+        let sync_interval = config.health_check_interval();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(sync_interval);
+            loop {
+                interval.tick().await;
+                load_balancer_clone
+                    .write()
+                    .await
+                    .update_validators(discovery_clone.get_all_validators());
+            }
+        });
+
         // Create HTTP client for validator communication
         let http_client = reqwest::Client::builder()
             .timeout(config.request_timeout())
@@ -76,18 +118,42 @@ impl Server {
             .build()
             .map_err(Error::HttpClient)?;
 
+        // Open the API key store, usage meter and rental routes
+        let db = GatewayDb::new(&config.database).await?;
+        let api_keys = Arc::new(ApiKeyStore::new(db.clone(), &config.auth));
+        let usage = Arc::new(UsageMeter::new(db.clone(), config.quotas.clone()));
+        let rental_router = Arc::new(RentalRouter::new(db, discovery.clone()));
+
+        // Start usage flush task
+        let usage_clone = usage.clone();
+        tokio::spawn(async move {
+            usage_clone.start_flush_loop().await;
+        });
+
+        // Rate limits have to outlive single requests to limit anything
+        let rate_limits = Arc::new(RateLimitStorage::new(Arc::new(config.rate_limit.clone())));
+        let rate_limits_clone = rate_limits.clone();
+        tokio::spawn(async move {
+            rate_limits_clone.start_cleanup_loop().await;
+        });
+
         // Create application state
         let state = AppState {
             config: config.clone(),
             discovery,
             load_balancer,
             http_client,
+            signer,
+            api_keys,
+            usage: usage.clone(),
+            rental_router,
+            rate_limits,
         };
 
         // Build the application router
         let app = Self::build_router(state)?;
 
-        Ok(Self { config, app })
+        Ok(Self { config, app, usage })
     }
 
     /// Build the application router with all routes and middleware
@@ -132,6 +198,11 @@ impl Server {
                 message: format!("Server error: {e}"),
             })?;
 
+        // Persist usage counted since the last flush
+        if let Err(e) = self.usage.flush().await {
+            warn!("Failed to flush metered usage on shutdown: {}", e);
+        }
+
         Ok(())
     }
 }
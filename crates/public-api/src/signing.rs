@@ -0,0 +1,217 @@
+//! Signing of requests forwarded to validators
+//!
+//! Validators only serve rental and capacity requests signed by a key on
+//! their allow-list. Every request the gateway sends to a validator therefore
+//! goes through [`RequestSigner`], which adds the signer, signature, timestamp
+//! and nonce headers defined in `common::crypto::request_signing`.
+
+use crate::{
+    config::SigningConfig,
+    error::{Error, Result},
+};
+use common::crypto::request_signing::{
+    canonical_request, generate_request_nonce, NONCE_HEADER, SIGNATURE_HEADER,
+    SIGNATURE_SCHEME_HEADER, SIGNER_HEADER, TIMESTAMP_HEADER,
+};
+use common::crypto::{Ed25519PrivateKey, SignatureScheme};
+use common::identity::Hotkey;
+use reqwest::header::HeaderValue;
+use reqwest::{Client, Request, RequestBuilder, Response};
+use std::sync::Arc;
+use tracing::info;
+
+/// Key requests are signed with
+enum SigningKey {
+    /// The gateway's Bittensor hotkey
+    Hotkey {
+        service: Arc<bittensor::Service>,
+        hotkey: String,
+    },
+    /// A standalone Ed25519 service key
+    Ed25519 {
+        key: Box<Ed25519PrivateKey>,
+        public_key: String,
+    },
+}
+
+/// Signs requests before they are sent to validators
+pub struct RequestSigner {
+    key: Option<SigningKey>,
+}
+
+impl RequestSigner {
+    /// Create the signer configured in `config`
+    pub fn from_config(config: &SigningConfig, bittensor: Arc<bittensor::Service>) -> Result<Self> {
+        if !config.enabled {
+            info!("Requests to validators are not signed");
+            return Ok(Self::unsigned());
+        }
+
+        let signer = match config.scheme {
+            SignatureScheme::Sr25519 => {
+                let hotkey = Hotkey::from_account_id(bittensor.get_account_id()).to_string();
+                Self {
+                    key: Some(SigningKey::Hotkey {
+                        service: bittensor,
+                        hotkey,
+                    }),
+                }
+            }
+            SignatureScheme::Ed25519 => {
+                let path = config
+                    .ed25519_key_path
+                    .as_ref()
+                    .ok_or_else(|| Error::Internal {
+                        message: "signing.ed25519_key_path is required for ed25519 signing"
+                            .to_string(),
+                    })?;
+                let pem = std::fs::read_to_string(path).map_err(|e| Error::Internal {
+                    message: format!("Failed to read signing key {}: {e}", path.display()),
+                })?;
+                let key = Ed25519PrivateKey::from_pem(&pem).map_err(|e| Error::Internal {
+                    message: format!("Invalid signing key {}: {e}", path.display()),
+                })?;
+                Self::ed25519(key)
+            }
+        };
+
+        if let Some(signer_id) = signer.signer_id() {
+            info!(
+                "Signing requests to validators with {} key {}",
+                config.scheme, signer_id
+            );
+        }
+        Ok(signer)
+    }
+
+    /// A signer that sends requests unsigned
+    pub fn unsigned() -> Self {
+        Self { key: None }
+    }
+
+    /// A signer using an Ed25519 service key
+    pub fn ed25519(key: Ed25519PrivateKey) -> Self {
+        let public_key = key.public_key().to_string();
+        Self {
+            key: Some(SigningKey::Ed25519 {
+                key: Box::new(key),
+                public_key,
+            }),
+        }
+    }
+
+    /// Identity validators have to allow, `None` when not signing
+    pub fn signer_id(&self) -> Option<&str> {
+        match &self.key {
+            Some(SigningKey::Hotkey { hotkey, .. }) => Some(hotkey),
+            Some(SigningKey::Ed25519 { public_key, .. }) => Some(public_key),
+            None => None,
+        }
+    }
+
+    /// Add signature headers to a request with its final URL and body
+    pub fn sign(&self, request: &mut Request) -> Result<()> {
+        let Some(key) = &self.key else {
+            return Ok(());
+        };
+
+        let body = match request.body() {
+            Some(body) => body.as_bytes().ok_or_else(|| Error::Internal {
+                message: "Streaming request bodies cannot be signed".to_string(),
+            })?,
+            None => &[],
+        };
+        let url = request.url();
+        let path_and_query = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let timestamp = chrono::Utc::now().timestamp();
+        let nonce = generate_request_nonce();
+        let canonical = canonical_request(
+            request.method().as_str(),
+            &path_and_query,
+            timestamp,
+            &nonce,
+            body,
+        );
+
+        let (scheme, signer, signature) = match key {
+            SigningKey::Hotkey { service, hotkey } => (
+                SignatureScheme::Sr25519,
+                hotkey.as_str(),
+                service.sign_data(canonical.as_bytes())?,
+            ),
+            SigningKey::Ed25519 { key, public_key } => (
+                SignatureScheme::Ed25519,
+                public_key.as_str(),
+                hex::encode(key.sign(canonical.as_bytes())),
+            ),
+        };
+
+        let headers = request.headers_mut();
+        for (name, value) in [
+            (SIGNER_HEADER, signer.to_string()),
+            (SIGNATURE_SCHEME_HEADER, scheme.to_string()),
+            (SIGNATURE_HEADER, signature),
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+            (NONCE_HEADER, nonce),
+        ] {
+            let value = HeaderValue::from_str(&value).map_err(|e| Error::Internal {
+                message: format!("Invalid {name} header: {e}"),
+            })?;
+            headers.insert(name, value);
+        }
+        Ok(())
+    }
+
+    /// Build, sign and send a request to a validator
+    pub async fn send(&self, client: &Client, request: RequestBuilder) -> Result<Response> {
+        let mut request = request.build()?;
+        self.sign(&mut request)?;
+        Ok(client.execute(request).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::crypto::request_signing::verify_request_signature;
+
+    fn header<'a>(request: &'a Request, name: &str) -> &'a str {
+        request.headers()[name].to_str().unwrap()
+    }
+
+    #[test]
+    fn test_signed_request_verifies() {
+        let signer = RequestSigner::ed25519(Ed25519PrivateKey::generate());
+        let client = Client::new();
+        let mut request = client
+            .post("http://validator:8080/rentals?dry_run=true")
+            .body("{\"gpu_count\":1}")
+            .build()
+            .unwrap();
+        signer.sign(&mut request).unwrap();
+
+        assert_eq!(header(&request, SIGNER_HEADER), signer.signer_id().unwrap());
+        assert_eq!(header(&request, SIGNATURE_SCHEME_HEADER), "ed25519");
+        let canonical = canonical_request(
+            "POST",
+            "/rentals?dry_run=true",
+            header(&request, TIMESTAMP_HEADER).parse().unwrap(),
+            header(&request, NONCE_HEADER),
+            b"{\"gpu_count\":1}",
+        );
+        verify_request_signature(
+            SignatureScheme::Ed25519,
+            header(&request, SIGNER_HEADER),
+            &canonical,
+            header(&request, SIGNATURE_HEADER),
+        )
+        .unwrap();
+
+        let mut unsigned = client.get("http://validator:8080/rentals").build().unwrap();
+        RequestSigner::unsigned().sign(&mut unsigned).unwrap();
+        assert!(unsigned.headers().get(SIGNATURE_HEADER).is_none());
+    }
+}
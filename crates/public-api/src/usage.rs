@@ -0,0 +1,583 @@
+//! Usage metering and quota enforcement
+//!
+//! Requests per route, rental GPU-hours and streamed log bytes are metered per
+//! API key. Request and log counters are kept in memory and written to the
+//! database every `flush_interval_secs`; rentals are written as they start and
+//! end. Quotas of each instance are checked against what is stored plus what
+//! that instance has counted since, and anonymous requests are not metered.
+
+use crate::{
+    api::types::{ApiKeyInfo, PeriodUsage, UsageLimits, UsageResponse},
+    api_keys::ANONYMOUS_KEY_ID,
+    config::{QuotaConfig, TierQuota},
+    error::{Error, Result},
+    persistence::{GatewayDb, MeteredRental, RouteUsage},
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Requests and log bytes counted together
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct UsageCounters {
+    requests: u64,
+    log_bytes: u64,
+}
+
+impl UsageCounters {
+    fn add(&mut self, other: UsageCounters) {
+        self.requests += other.requests;
+        self.log_bytes += other.log_bytes;
+    }
+}
+
+/// Counters not yet written to the database
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PendingKey {
+    key_id: String,
+    day: NaiveDate,
+    route: String,
+}
+
+/// Running totals of a key in the current day and month
+#[derive(Debug, Clone, Copy)]
+struct KeyTotals {
+    day: NaiveDate,
+    today: UsageCounters,
+    this_month: UsageCounters,
+}
+
+impl KeyTotals {
+    /// Reset the counters of periods that ended before `today`
+    fn roll_over(&mut self, today: NaiveDate) {
+        if today <= self.day {
+            return;
+        }
+        if month_start(today) != month_start(self.day) {
+            self.this_month = UsageCounters::default();
+        }
+        self.today = UsageCounters::default();
+        self.day = today;
+    }
+}
+
+/// Meters usage per API key and enforces the quotas of its tier
+pub struct UsageMeter {
+    db: GatewayDb,
+    config: QuotaConfig,
+    totals: DashMap<String, KeyTotals>,
+    pending: Mutex<HashMap<PendingKey, UsageCounters>>,
+}
+
+impl UsageMeter {
+    /// Create a meter storing usage in `db`
+    pub fn new(db: GatewayDb, config: QuotaConfig) -> Self {
+        Self {
+            db,
+            config,
+            totals: DashMap::new(),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether usage of a key is metered
+    pub fn is_metered(info: &ApiKeyInfo) -> bool {
+        info.key_id != ANONYMOUS_KEY_ID
+    }
+
+    /// Quotas enforced on a key, `None` if it is not limited
+    fn quota(&self, info: &ApiKeyInfo) -> Option<&TierQuota> {
+        (self.config.enabled && Self::is_metered(info)).then(|| self.config.for_tier(info.tier))
+    }
+
+    /// Reject a request once the key's daily or monthly requests are used up
+    pub async fn check_requests(&self, info: &ApiKeyInfo) -> Result<()> {
+        let Some(quota) = self.quota(info) else {
+            return Ok(());
+        };
+        if quota.daily_requests.is_none() && quota.monthly_requests.is_none() {
+            return Ok(());
+        }
+
+        let totals = self.current_totals(&info.key_id).await?;
+        if let Some(limit) = quota.daily_requests {
+            if totals.today.requests >= limit {
+                return Err(Error::QuotaExceeded {
+                    message: format!("Daily quota of {limit} requests exhausted"),
+                });
+            }
+        }
+        if let Some(limit) = quota.monthly_requests {
+            if totals.this_month.requests >= limit {
+                return Err(Error::QuotaExceeded {
+                    message: format!("Monthly quota of {limit} requests exhausted"),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject a rental whose full duration does not fit in the key's
+    /// remaining monthly GPU-hours
+    pub async fn check_rental(
+        &self,
+        info: &ApiKeyInfo,
+        gpu_count: u32,
+        duration_hours: u32,
+    ) -> Result<()> {
+        let Some(limit) = self.quota(info).and_then(|quota| quota.monthly_gpu_hours) else {
+            return Ok(());
+        };
+
+        let now = Utc::now();
+        let used = self
+            .gpu_hours(
+                &info.key_id,
+                start_of_day(month_start(now.date_naive())),
+                now,
+            )
+            .await?;
+        let requested = f64::from(gpu_count) * f64::from(duration_hours);
+        if used + requested > limit {
+            return Err(Error::PaymentRequired {
+                message: format!(
+                    "Rental of {requested:.1} GPU-hours exceeds the {:.1} GPU-hours left this month",
+                    (limit - used).max(0.0)
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Reject a log stream once the key's monthly log bytes are used up
+    pub async fn check_log_stream(&self, info: &ApiKeyInfo) -> Result<()> {
+        let Some(limit) = self.quota(info).and_then(|quota| quota.monthly_log_bytes) else {
+            return Ok(());
+        };
+
+        let totals = self.current_totals(&info.key_id).await?;
+        if totals.this_month.log_bytes >= limit {
+            return Err(Error::PaymentRequired {
+                message: format!("Monthly quota of {limit} log bytes exhausted"),
+            });
+        }
+        Ok(())
+    }
+
+    /// Count a request to `route`
+    pub async fn record_request(&self, info: &ApiKeyInfo, route: &str) {
+        self.record(
+            info,
+            route,
+            UsageCounters {
+                requests: 1,
+                log_bytes: 0,
+            },
+        )
+        .await;
+    }
+
+    /// Count log bytes streamed through `route`
+    ///
+    /// Returns false once the key's monthly log quota is used up.
+    pub async fn record_log_bytes(&self, info: &ApiKeyInfo, route: &str, bytes: u64) -> bool {
+        let totals = self
+            .record(
+                info,
+                route,
+                UsageCounters {
+                    requests: 0,
+                    log_bytes: bytes,
+                },
+            )
+            .await;
+
+        match (
+            totals,
+            self.quota(info).and_then(|quota| quota.monthly_log_bytes),
+        ) {
+            (Some(totals), Some(limit)) => totals.this_month.log_bytes < limit,
+            _ => true,
+        }
+    }
+
+    /// Start metering a rental created by a key
+    pub async fn record_rental_started(
+        &self,
+        info: &ApiKeyInfo,
+        rental_id: &str,
+        gpu_count: u32,
+        duration_hours: u32,
+    ) -> Result<()> {
+        if !Self::is_metered(info) {
+            return Ok(());
+        }
+
+        let started_at = Utc::now();
+        self.db
+            .insert_metered_rental(&MeteredRental {
+                rental_id: rental_id.to_string(),
+                key_id: info.key_id.clone(),
+                gpu_count,
+                started_at,
+                expires_at: started_at + Duration::hours(duration_hours.into()),
+                ended_at: None,
+            })
+            .await?;
+        debug!("Metering rental {} of key {}", rental_id, info.key_id);
+        Ok(())
+    }
+
+    /// Stop metering a terminated rental
+    pub async fn record_rental_ended(&self, rental_id: &str) -> Result<()> {
+        if self.db.end_metered_rental(rental_id, Utc::now()).await? {
+            debug!("Stopped metering rental {}", rental_id);
+        }
+        Ok(())
+    }
+
+    /// Usage of a key in the current day and month
+    pub async fn usage(&self, info: &ApiKeyInfo) -> Result<UsageResponse> {
+        if !Self::is_metered(info) {
+            return Err(Error::Authentication {
+                message: "Usage is only metered for API keys".to_string(),
+            });
+        }
+        self.flush().await?;
+
+        let now = Utc::now();
+        let today = now.date_naive();
+        let quota = self.config.for_tier(info.tier);
+
+        let daily_routes = self.db.usage_by_route(&info.key_id, today, today).await?;
+        let daily = period_usage(
+            start_of_day(today),
+            &daily_routes,
+            self.gpu_hours(&info.key_id, start_of_day(today), now)
+                .await?,
+            UsageLimits {
+                requests: quota.daily_requests,
+                ..Default::default()
+            },
+        );
+
+        let month = month_start(today);
+        let monthly_routes = self.db.usage_by_route(&info.key_id, month, today).await?;
+        let monthly = period_usage(
+            start_of_day(month),
+            &monthly_routes,
+            self.gpu_hours(&info.key_id, start_of_day(month), now)
+                .await?,
+            UsageLimits {
+                requests: quota.monthly_requests,
+                gpu_hours: quota.monthly_gpu_hours,
+                log_bytes: quota.monthly_log_bytes,
+            },
+        );
+
+        Ok(UsageResponse {
+            key_id: info.key_id.clone(),
+            tier: info.tier,
+            quotas_enforced: self.config.enabled,
+            daily,
+            monthly,
+        })
+    }
+
+    /// Write counted usage to the database
+    ///
+    /// Counters that fail to be written are kept for the next flush.
+    pub async fn flush(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.pending.lock());
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut first_error = None;
+        for (key, counters) in pending {
+            if let Err(e) = self
+                .db
+                .add_usage(
+                    &key.key_id,
+                    key.day,
+                    &key.route,
+                    counters.requests,
+                    counters.log_bytes,
+                )
+                .await
+            {
+                self.pending.lock().entry(key).or_default().add(counters);
+                first_error.get_or_insert(e);
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Flush usage periodically
+    pub async fn start_flush_loop(self: Arc<Self>) {
+        let period = std::time::Duration::from_secs(self.config.flush_interval_secs.max(1));
+        info!("Flushing metered usage every {:?}", period);
+
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.flush().await {
+                warn!("Failed to flush metered usage: {}", e);
+            }
+        }
+    }
+
+    /// Add to a key's counters, returning its updated totals
+    async fn record(
+        &self,
+        info: &ApiKeyInfo,
+        route: &str,
+        counters: UsageCounters,
+    ) -> Option<KeyTotals> {
+        if !Self::is_metered(info) {
+            return None;
+        }
+
+        let today = Utc::now().date_naive();
+        if let Err(e) = self.load_totals(&info.key_id, today).await {
+            // The counters still reach the database with the next flush
+            warn!("Failed to load usage of key {}: {}", info.key_id, e);
+        }
+
+        self.pending
+            .lock()
+            .entry(PendingKey {
+                key_id: info.key_id.clone(),
+                day: today,
+                route: route.to_string(),
+            })
+            .or_default()
+            .add(counters);
+
+        let mut totals = self.totals.get_mut(&info.key_id)?;
+        totals.roll_over(today);
+        totals.today.add(counters);
+        totals.this_month.add(counters);
+        Some(*totals)
+    }
+
+    /// Totals of a key in the current day and month
+    async fn current_totals(&self, key_id: &str) -> Result<KeyTotals> {
+        let today = Utc::now().date_naive();
+        self.load_totals(key_id, today).await?;
+
+        let mut totals = self.totals.get_mut(key_id).ok_or_else(|| Error::Internal {
+            message: format!("Usage of key {key_id} is not loaded"),
+        })?;
+        totals.roll_over(today);
+        Ok(*totals)
+    }
+
+    /// Read a key's stored usage the first time it is seen
+    async fn load_totals(&self, key_id: &str, today: NaiveDate) -> Result<()> {
+        if self.totals.contains_key(key_id) {
+            return Ok(());
+        }
+
+        let daily = self.db.usage_by_route(key_id, today, today).await?;
+        let monthly = self
+            .db
+            .usage_by_route(key_id, month_start(today), today)
+            .await?;
+        self.totals
+            .entry(key_id.to_string())
+            .or_insert_with(|| KeyTotals {
+                day: today,
+                today: sum_routes(&daily),
+                this_month: sum_routes(&monthly),
+            });
+        Ok(())
+    }
+
+    /// GPU-hours of a key's rentals between `from` and `to`
+    async fn gpu_hours(&self, key_id: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<f64> {
+        let rentals = self.db.metered_rentals_since(key_id, from).await?;
+        Ok(rentals
+            .iter()
+            .map(|rental| rental.gpu_hours_between(from, to))
+            .sum())
+    }
+}
+
+fn sum_routes(routes: &[RouteUsage]) -> UsageCounters {
+    let mut total = UsageCounters::default();
+    for route in routes {
+        total.add(UsageCounters {
+            requests: route.requests,
+            log_bytes: route.log_bytes,
+        });
+    }
+    total
+}
+
+fn period_usage(
+    period_start: DateTime<Utc>,
+    routes: &[RouteUsage],
+    gpu_hours: f64,
+    limits: UsageLimits,
+) -> PeriodUsage {
+    let total = sum_routes(routes);
+    let requests_by_route: BTreeMap<String, u64> = routes
+        .iter()
+        .filter(|route| route.requests > 0)
+        .map(|route| (route.route.clone(), route.requests))
+        .collect();
+
+    PeriodUsage {
+        period_start,
+        requests: total.requests,
+        requests_by_route,
+        gpu_hours,
+        log_bytes: total.log_bytes,
+        limits,
+    }
+}
+
+fn month_start(day: NaiveDate) -> NaiveDate {
+    day.with_day(1).unwrap_or(day)
+}
+
+fn start_of_day(day: NaiveDate) -> DateTime<Utc> {
+    day.and_time(NaiveTime::MIN).and_utc()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::ApiKeyTier;
+    use common::config::DatabaseConfig;
+
+    async fn test_meter(dir: &std::path::Path, config: QuotaConfig) -> UsageMeter {
+        let db = GatewayDb::new(&DatabaseConfig {
+            url: format!("sqlite:{}", dir.join("public-api.db").display()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        UsageMeter::new(db, config)
+    }
+
+    fn key(key_id: &str) -> ApiKeyInfo {
+        ApiKeyInfo {
+            key_id: key_id.to_string(),
+            owner: "acme".to_string(),
+            tier: ApiKeyTier::Free,
+            scopes: vec![],
+            rate_limit_override: None,
+        }
+    }
+
+    fn limited_config() -> QuotaConfig {
+        QuotaConfig {
+            free: TierQuota {
+                daily_requests: Some(2),
+                monthly_requests: None,
+                monthly_gpu_hours: Some(8.0),
+                monthly_log_bytes: Some(1000),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_totals_roll_over() {
+        let counters = UsageCounters {
+            requests: 5,
+            log_bytes: 10,
+        };
+        let mut totals = KeyTotals {
+            day: NaiveDate::from_ymd_opt(2026, 1, 30).unwrap(),
+            today: counters,
+            this_month: counters,
+        };
+
+        totals.roll_over(NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+        assert_eq!(totals.today, UsageCounters::default());
+        assert_eq!(totals.this_month, counters);
+
+        totals.roll_over(NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
+        assert_eq!(totals.this_month, UsageCounters::default());
+    }
+
+    #[tokio::test]
+    async fn test_request_quota_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let meter = test_meter(dir.path(), limited_config()).await;
+        let info = key("key");
+
+        for _ in 0..2 {
+            meter.check_requests(&info).await.unwrap();
+            meter.record_request(&info, "/api/v1/executors").await;
+        }
+        assert!(matches!(
+            meter.check_requests(&info).await,
+            Err(Error::QuotaExceeded { .. })
+        ));
+
+        // Other keys and anonymous requests are unaffected
+        meter.check_requests(&key("other")).await.unwrap();
+        meter.check_requests(&key(ANONYMOUS_KEY_ID)).await.unwrap();
+
+        meter.flush().await.unwrap();
+        let meter = test_meter(dir.path(), limited_config()).await;
+        assert!(meter.check_requests(&info).await.is_err());
+
+        let usage = meter.usage(&info).await.unwrap();
+        assert_eq!(usage.daily.requests, 2);
+        assert_eq!(usage.monthly.requests_by_route["/api/v1/executors"], 2);
+        assert_eq!(usage.daily.limits.requests, Some(2));
+
+        let disabled = QuotaConfig {
+            enabled: false,
+            ..limited_config()
+        };
+        let meter = test_meter(dir.path(), disabled).await;
+        meter.check_requests(&info).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rental_and_log_quotas() {
+        let dir = tempfile::tempdir().unwrap();
+        let meter = test_meter(dir.path(), limited_config()).await;
+        let info = key("key");
+
+        meter.check_rental(&info, 2, 4).await.unwrap();
+        assert!(matches!(
+            meter.check_rental(&info, 3, 4).await,
+            Err(Error::PaymentRequired { .. })
+        ));
+
+        meter
+            .record_rental_started(&info, "rental-1", 2, 4)
+            .await
+            .unwrap();
+        meter.record_rental_ended("rental-1").await.unwrap();
+        let usage = meter.usage(&info).await.unwrap();
+        assert!(usage.monthly.gpu_hours < 0.01);
+
+        meter.check_log_stream(&info).await.unwrap();
+        assert!(meter.record_log_bytes(&info, "/logs", 600).await);
+        assert!(!meter.record_log_bytes(&info, "/logs", 600).await);
+        assert!(matches!(
+            meter.check_log_stream(&info).await,
+            Err(Error::PaymentRequired { .. })
+        ));
+
+        let usage = meter.usage(&info).await.unwrap();
+        assert_eq!(usage.monthly.log_bytes, 1200);
+        assert_eq!(usage.monthly.requests, 0);
+        assert!(usage.monthly.requests_by_route.is_empty());
+    }
+}
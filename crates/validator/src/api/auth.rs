@@ -0,0 +1,284 @@
+//! Authentication of API requests
+//!
+//! Rental and capacity requests from the public API gateway must carry a
+//! signature made by an allowed hotkey or Ed25519 service key. Signatures are
+//! only accepted within the configured time window and each nonce is accepted
+//! once per signer.
+//!
+//! Operator actions, such as changing miners, triggering verification or
+//! reviewing slashing cases, require the configured API key instead.
+
+use crate::api::types::ApiError;
+use crate::api::ApiState;
+use crate::config::RequestAuthConfig;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{HeaderMap, Method},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use common::crypto::request_signing::{
+    canonical_request, verify_request_signature, SignatureScheme, NONCE_HEADER, SIGNATURE_HEADER,
+    SIGNATURE_SCHEME_HEADER, SIGNER_HEADER, TIMESTAMP_HEADER,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tracing::warn;
+
+/// Verifies request signatures against the configured allow-lists
+pub struct RequestAuthenticator {
+    enabled: bool,
+    allowed_hotkeys: HashSet<String>,
+    allowed_ed25519_keys: HashSet<String>,
+    max_request_age_secs: i64,
+    max_clock_skew_secs: i64,
+    max_body_size: usize,
+    /// Nonces seen per signer with the timestamp of their request
+    seen_nonces: Mutex<HashMap<(String, String), i64>>,
+}
+
+impl RequestAuthenticator {
+    pub fn new(config: &RequestAuthConfig, max_body_size: usize) -> Self {
+        Self {
+            enabled: config.enabled,
+            allowed_hotkeys: config.allowed_hotkeys.iter().cloned().collect(),
+            allowed_ed25519_keys: config
+                .allowed_ed25519_keys
+                .iter()
+                .map(|key| key.to_ascii_lowercase())
+                .collect(),
+            max_request_age_secs: config.max_request_age_secs as i64,
+            max_clock_skew_secs: config.max_clock_skew_secs as i64,
+            max_body_size,
+            seen_nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Verify the signature headers of a request
+    pub fn verify(
+        &self,
+        method: &Method,
+        path_and_query: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), ApiError> {
+        let signer = header(headers, SIGNER_HEADER)?;
+        let scheme: SignatureScheme = header(headers, SIGNATURE_SCHEME_HEADER)?
+            .parse()
+            .map_err(|_| ApiError::BadRequest("Unknown signature scheme".to_string()))?;
+        let signature = header(headers, SIGNATURE_HEADER)?;
+        let nonce = header(headers, NONCE_HEADER)?;
+        let timestamp: i64 = header(headers, TIMESTAMP_HEADER)?
+            .parse()
+            .map_err(|_| ApiError::BadRequest("Invalid request timestamp".to_string()))?;
+
+        let allowed = match scheme {
+            SignatureScheme::Sr25519 => self.allowed_hotkeys.contains(signer),
+            SignatureScheme::Ed25519 => self
+                .allowed_ed25519_keys
+                .contains(&signer.to_ascii_lowercase()),
+        };
+        if !allowed {
+            warn!(
+                "Rejected request from signer {} not on the allow-list",
+                signer
+            );
+            return Err(ApiError::Unauthorized);
+        }
+
+        if timestamp < now - self.max_request_age_secs || timestamp > now + self.max_clock_skew_secs
+        {
+            warn!(
+                "Rejected request from {} signed at {} (now {})",
+                signer, timestamp, now
+            );
+            return Err(ApiError::Unauthorized);
+        }
+
+        let canonical = canonical_request(method.as_str(), path_and_query, timestamp, nonce, body);
+        if let Err(e) = verify_request_signature(scheme, signer, &canonical, signature) {
+            warn!(
+                "Rejected request with invalid signature from {}: {}",
+                signer, e
+            );
+            return Err(ApiError::Unauthorized);
+        }
+
+        // Only record the nonce once the signature is known to be genuine
+        let mut seen = self.seen_nonces.lock().unwrap();
+        let oldest = now - self.max_request_age_secs;
+        seen.retain(|_, signed_at| *signed_at >= oldest);
+        if seen
+            .insert((signer.to_string(), nonce.to_string()), timestamp)
+            .is_some()
+        {
+            warn!(
+                "Rejected replayed request from {} (nonce {})",
+                signer, nonce
+            );
+            return Err(ApiError::Unauthorized);
+        }
+
+        Ok(())
+    }
+}
+
+/// Header carrying the API key for operator actions
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Check that a request carries the configured operator API key
+///
+/// Always fails when no API key is configured.
+pub fn authorize_operator(state: &ApiState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let provided = headers
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    match (&state.config.api_key, provided) {
+        (Some(expected), Some(provided))
+            if common::crypto::secure_compare(expected.as_bytes(), provided.as_bytes()) =>
+        {
+            Ok(())
+        }
+        _ => Err(ApiError::Unauthorized),
+    }
+}
+
+/// Reject operator requests without the operator API key
+pub async fn require_operator(
+    State(state): State<ApiState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    authorize_operator(&state, req.headers())?;
+    Ok(next.run(req).await)
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, ApiError> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(ApiError::Unauthorized)
+}
+
+/// Reject rental and capacity requests without a valid signature
+pub async fn require_signed_request(
+    State(state): State<ApiState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let auth = &state.request_auth;
+    if !auth.enabled {
+        return Ok(next.run(req).await);
+    }
+
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, auth.max_body_size)
+        .await
+        .map_err(|_| ApiError::BadRequest("Request body too large".to_string()))?;
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map_or(parts.uri.path(), |path| path.as_str());
+
+    auth.verify(
+        &parts.method,
+        path_and_query,
+        &parts.headers,
+        &body,
+        Utc::now().timestamp(),
+    )?;
+
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::crypto::request_signing::generate_request_nonce;
+    use common::crypto::Ed25519PrivateKey;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn signed_headers(key: &Ed25519PrivateKey, path: &str, body: &[u8], at: i64) -> HeaderMap {
+        let nonce = generate_request_nonce();
+        let canonical = canonical_request("POST", path, at, &nonce, body);
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            (SIGNER_HEADER, key.public_key().to_string()),
+            (SIGNATURE_SCHEME_HEADER, "ed25519".to_string()),
+            (
+                SIGNATURE_HEADER,
+                hex::encode(key.sign(canonical.as_bytes())),
+            ),
+            (TIMESTAMP_HEADER, at.to_string()),
+            (NONCE_HEADER, nonce),
+        ] {
+            headers.insert(name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn authenticator(key: &Ed25519PrivateKey) -> RequestAuthenticator {
+        let config = RequestAuthConfig {
+            allowed_ed25519_keys: vec![key.public_key().to_string()],
+            ..Default::default()
+        };
+        RequestAuthenticator::new(&config, 1024)
+    }
+
+    #[test]
+    fn test_accepts_signed_request_once() {
+        let key = Ed25519PrivateKey::generate();
+        let auth = authenticator(&key);
+        let headers = signed_headers(&key, "/rentals", b"{}", NOW);
+
+        assert!(auth
+            .verify(&Method::POST, "/rentals", &headers, b"{}", NOW)
+            .is_ok());
+        assert!(matches!(
+            auth.verify(&Method::POST, "/rentals", &headers, b"{}", NOW + 1),
+            Err(ApiError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_requests() {
+        let key = Ed25519PrivateKey::generate();
+        let auth = authenticator(&key);
+
+        // Tampered body and path
+        let headers = signed_headers(&key, "/rentals", b"{}", NOW);
+        assert!(auth
+            .verify(&Method::POST, "/rentals", &headers, b"{\"a\":1}", NOW)
+            .is_err());
+        assert!(auth
+            .verify(&Method::POST, "/rentals/other", &headers, b"{}", NOW)
+            .is_err());
+
+        // Outside the accepted time window
+        let stale = signed_headers(&key, "/rentals", b"{}", NOW - 301);
+        assert!(auth
+            .verify(&Method::POST, "/rentals", &stale, b"{}", NOW)
+            .is_err());
+        let future = signed_headers(&key, "/rentals", b"{}", NOW + 61);
+        assert!(auth
+            .verify(&Method::POST, "/rentals", &future, b"{}", NOW)
+            .is_err());
+
+        // Signer not on the allow-list
+        let other = Ed25519PrivateKey::generate();
+        let headers = signed_headers(&other, "/rentals", b"{}", NOW);
+        assert!(auth
+            .verify(&Method::POST, "/rentals", &headers, b"{}", NOW)
+            .is_err());
+
+        // Unsigned
+        assert!(auth
+            .verify(&Method::POST, "/rentals", &HeaderMap::new(), b"{}", NOW)
+            .is_err());
+    }
+}
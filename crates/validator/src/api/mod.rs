@@ -3,12 +3,14 @@
 //! Clean, modular HTTP/REST API server for external services to interact with the Validator.
 //! Follows SOLID principles with separation of concerns.
 
+pub mod auth;
 pub mod routes;
 pub mod types;
 
 use crate::config::ApiConfig;
 use anyhow::Result;
 use axum::{
+    middleware,
     routing::{delete, get, post, put},
     Router,
 };
@@ -24,6 +26,13 @@ pub struct ApiState {
     persistence: Arc<crate::persistence::SimplePersistence>,
     #[allow(dead_code)]
     storage: common::MemoryStorage,
+    slashing: crate::slashing::SlashingConfig,
+    /// Rental provisioning, unavailable without a Bittensor connection
+    rentals: Option<Arc<crate::rental::RentalSupervisor>>,
+    /// Weight dry runs, unavailable without a Bittensor connection
+    weights: Option<Arc<crate::bittensor_core::WeightSetter>>,
+    /// Signature checks on rental and capacity requests
+    request_auth: Arc<auth::RequestAuthenticator>,
 }
 
 impl ApiState {
@@ -31,11 +40,22 @@ impl ApiState {
         config: ApiConfig,
         persistence: Arc<crate::persistence::SimplePersistence>,
         storage: common::MemoryStorage,
+        slashing: crate::slashing::SlashingConfig,
+        rentals: Option<Arc<crate::rental::RentalSupervisor>>,
+        weights: Option<Arc<crate::bittensor_core::WeightSetter>>,
     ) -> Self {
+        let request_auth = Arc::new(auth::RequestAuthenticator::new(
+            &config.request_auth,
+            config.max_body_size,
+        ));
         Self {
             config,
             persistence,
             storage,
+            slashing,
+            rentals,
+            weights,
+            request_auth,
         }
     }
 }
@@ -51,9 +71,12 @@ impl ApiHandler {
         config: ApiConfig,
         persistence: Arc<crate::persistence::SimplePersistence>,
         storage: common::MemoryStorage,
+        slashing: crate::slashing::SlashingConfig,
+        rentals: Option<Arc<crate::rental::RentalSupervisor>>,
+        weights: Option<Arc<crate::bittensor_core::WeightSetter>>,
     ) -> Self {
         Self {
-            state: ApiState::new(config, persistence, storage),
+            state: ApiState::new(config, persistence, storage, slashing, rentals, weights),
         }
     }
 
@@ -71,30 +94,156 @@ impl ApiHandler {
 
     /// Create the Axum router with all endpoints
     /// Follows Open/Closed Principle - easy to extend with new routes
-    fn create_router(&self) -> Router {
-        Router::new()
+    pub fn create_router(&self) -> Router {
+        // Routes served on behalf of the public API gateway require signed requests
+        let signed = Router::new()
             .route("/capacity/available", get(routes::list_available_capacity))
             .route("/rentals", post(routes::rent_capacity))
             .route("/rentals/:rental_id", delete(routes::terminate_rental))
             .route("/rentals/:rental_id/status", get(routes::get_rental_status))
             .route("/rentals/:rental_id/logs", get(routes::stream_rental_logs))
-            .route("/miners", get(routes::list_miners))
+            .route("/rentals/:rental_id/usage", get(routes::get_rental_usage))
+            .route_layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                auth::require_signed_request,
+            ));
+
+        // Every other write, and the weight dry run, is reserved to the operator
+        let operator = Router::new()
             .route("/miners/register", post(routes::register_miner))
-            .route("/miners/:miner_id", get(routes::get_miner))
-            .route("/miners/:miner_id", put(routes::update_miner))
-            .route("/miners/:miner_id", delete(routes::remove_miner))
-            .route("/miners/:miner_id/health", get(routes::get_miner_health))
+            .route(
+                "/miners/:miner_id",
+                put(routes::update_miner).delete(routes::remove_miner),
+            )
             .route(
                 "/miners/:miner_id/verify",
                 post(routes::trigger_miner_verification),
             )
+            .route(
+                "/slashing/cases/:case_id/approve",
+                post(routes::approve_slashing_case),
+            )
+            .route(
+                "/slashing/cases/:case_id/reject",
+                post(routes::reject_slashing_case),
+            )
+            .route("/weights/explain", get(routes::explain_weights))
+            .route_layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                auth::require_operator,
+            ));
+
+        Router::new()
+            .merge(signed)
+            .merge(operator)
+            .route("/miners", get(routes::list_miners))
+            .route("/miners/:miner_id", get(routes::get_miner))
+            .route("/miners/:miner_id/health", get(routes::get_miner_health))
             .route(
                 "/miners/:miner_id/executors",
                 get(routes::list_miner_executors),
             )
+            .route("/slashing/cases", get(routes::list_slashing_cases))
+            .route("/slashing/cases/:case_id", get(routes::get_slashing_case))
+            .route(
+                "/slashing/evidence/:file_name",
+                get(routes::get_slashing_evidence),
+            )
             .route("/health", get(routes::health_check))
             .layer(TraceLayer::new_for_http())
             .layer(CorsLayer::permissive())
             .with_state(self.state.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RequestAuthConfig;
+    use axum::{body::Body, http::Method, http::Request, http::StatusCode};
+    use tower::Service;
+
+    async fn router(api_key: Option<&str>) -> Router {
+        let persistence =
+            crate::persistence::SimplePersistence::new(":memory:", "test_validator".to_string())
+                .await
+                .unwrap();
+        let config = ApiConfig {
+            api_key: api_key.map(String::from),
+            max_body_size: 1024 * 1024,
+            bind_address: "127.0.0.1:0".to_string(),
+            request_auth: RequestAuthConfig::default(),
+        };
+        ApiHandler::new(
+            config,
+            Arc::new(persistence),
+            common::MemoryStorage::new().await.unwrap(),
+            crate::slashing::SlashingConfig::default(),
+            None,
+            None,
+        )
+        .create_router()
+    }
+
+    async fn status(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        api_key: Option<&str>,
+    ) -> StatusCode {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(api_key) = api_key {
+            request = request.header(auth::API_KEY_HEADER, api_key);
+        }
+        let request = request.body(Body::from("{}")).unwrap();
+        // A router is always ready to take a request
+        router.clone().call(request).await.unwrap().status()
+    }
+
+    const OPERATOR_ROUTES: &[(Method, &str)] = &[
+        (Method::POST, "/miners/register"),
+        (Method::PUT, "/miners/miner_1"),
+        (Method::DELETE, "/miners/miner_1"),
+        (Method::POST, "/miners/miner_1/verify"),
+        (
+            Method::POST,
+            "/slashing/cases/00000000-0000-0000-0000-000000000000/approve",
+        ),
+        (Method::GET, "/weights/explain"),
+    ];
+
+    #[tokio::test]
+    async fn test_operator_routes_require_api_key() {
+        let router = router(Some("operator-key")).await;
+        for (method, uri) in OPERATOR_ROUTES {
+            for api_key in [None, Some("wrong-key")] {
+                assert_eq!(
+                    status(&router, method.clone(), uri, api_key).await,
+                    StatusCode::UNAUTHORIZED,
+                    "{method} {uri}"
+                );
+            }
+            assert_ne!(
+                status(&router, method.clone(), uri, Some("operator-key")).await,
+                StatusCode::UNAUTHORIZED,
+                "{method} {uri}"
+            );
+        }
+
+        // Reads stay open
+        assert_ne!(
+            status(&router, Method::GET, "/miners/miner_1", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        // Without a configured key nobody is an operator
+        let router = self::router(None).await;
+        assert_eq!(
+            status(&router, Method::DELETE, "/miners/miner_1", Some("")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
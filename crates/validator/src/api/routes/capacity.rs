@@ -2,149 +2,141 @@
 
 use crate::api::types::*;
 use crate::api::ApiState;
+use crate::persistence::entities::Rental;
+use crate::persistence::simple_persistence::{CapacityEntry, CapacityFilter, ListedCapacity};
 use axum::{
     extract::{Query, State},
     Json,
 };
-use serde_json::Value;
+use chrono::{DateTime, Utc};
+use common::persistence::{Cursor, CursorPage};
+use std::collections::HashMap;
 use tracing::{error, info};
 
+/// Default number of executors per page
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// Largest page a client may request
+const MAX_PAGE_SIZE: u32 = 200;
+
+/// Minimum verification success rate for an executor to be listed
+const MIN_SUCCESS_RATE: f64 = 0.8;
+
 /// List available GPU capacity
+///
+/// Filters are applied before pagination, so `total_count` covers every
+/// matching executor and `next_cursor` pages through them in sort order.
+/// A cursor is only accepted with the `sort_by` and `order` it was issued for.
 pub async fn list_available_capacity(
     State(state): State<ApiState>,
     Query(query): Query<ListCapacityQuery>,
 ) -> Result<Json<ListCapacityResponse>, ApiError> {
     info!("Listing available capacity with filters: {:?}", query);
 
-    let min_score = query.max_cost_per_hour.map(|cost| {
-        // Simple scoring: higher cost tolerance = lower min score required
-        if cost > 10.0 {
-            0.5
-        } else if cost > 5.0 {
-            0.7
-        } else {
-            0.9
-        }
+    let sort_by = query.sort_by.unwrap_or_default();
+    let order = query.order.unwrap_or(match sort_by {
+        CapacitySortKey::Price => SortOrder::Asc,
+        _ => SortOrder::Desc,
     });
-    let min_success_rate = Some(0.8); // Require 80% success rate minimum
+    let sort = format!("{}:{}", sort_by.as_str(), order.as_str());
+    let page_size = query
+        .page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let after = query
+        .cursor
+        .as_deref()
+        .map(|token| Cursor::<f64>::decode_for(token, &sort))
+        .transpose()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let open_rentals = state.persistence.get_open_rentals().await.map_err(|e| {
+        error!("Failed to query open rentals: {}", e);
+        ApiError::InternalError("Failed to retrieve capacity data".to_string())
+    })?;
+    let now = Utc::now();
+    let window_end =
+        now + chrono::Duration::hours(query.available_within_hours.unwrap_or(0) as i64);
+    let rented_until: HashMap<String, DateTime<Utc>> = rented_until(&open_rentals)
+        .into_iter()
+        .filter(|(_, until)| *until > now)
+        .collect();
 
-    let limit = 50; // Default page size
-    let offset = 0; // TODO: Add pagination support
+    let filter = CapacityFilter {
+        min_score: query.min_score,
+        min_success_rate: Some(MIN_SUCCESS_RATE),
+        min_gpu_memory_gb: query.min_gpu_memory,
+        gpu_type: query.gpu_type.clone(),
+        min_gpu_count: query.min_gpu_count,
+        max_cost_per_hour: query.max_cost_per_hour,
+        region: query.region.clone(),
+        min_cpu_cores: query.min_cpu_cores,
+        min_ram_gb: query.min_ram_gb,
+        min_disk_gb: query.min_disk_gb,
+        min_bandwidth_mbps: query.min_bandwidth_mbps,
+        // Executors still rented when the requested window ends
+        excluded_executors: rented_until
+            .iter()
+            .filter(|(_, until)| **until > window_end)
+            .map(|(executor_id, _)| executor_id.clone())
+            .collect(),
+    };
 
-    match state
+    // One extra executor tells whether another page follows
+    let (listed, total_count) = state
         .persistence
-        .get_available_capacity(min_score, min_success_rate, limit, offset)
+        .list_capacity(&filter, sort_by, order, after.as_ref(), page_size + 1)
         .await
-    {
-        Ok(capacity_entries) => {
-            let mut available_executors = Vec::new();
-
-            for entry in capacity_entries {
-                // Filter by query parameters
-                if let Some(min_gpu_memory) = query.min_gpu_memory {
-                    if !hardware_meets_memory_requirement(&entry.hardware_info, min_gpu_memory) {
-                        continue;
-                    }
-                }
-
-                if let Some(gpu_type) = &query.gpu_type {
-                    if !hardware_matches_gpu_type(&entry.hardware_info, gpu_type) {
-                        continue;
-                    }
-                }
-
-                if let Some(min_gpu_count) = query.min_gpu_count {
-                    if !hardware_meets_gpu_count(&entry.hardware_info, min_gpu_count) {
-                        continue;
-                    }
-                }
-
-                let cost_per_hour = calculate_cost_per_hour(&entry);
-
-                if let Some(max_cost) = query.max_cost_per_hour {
-                    if cost_per_hour > max_cost {
-                        continue;
-                    }
-                }
-
-                let executor_details = extract_executor_details(&entry)?;
-
-                available_executors.push(AvailableExecutor {
-                    executor: executor_details,
-                    availability: AvailabilityInfo {
-                        available_until: None, // TODO: Calculate based on current rentals
-                        verification_score: entry.verification_score,
-                        uptime_percentage: entry.success_rate * 100.0,
-                    },
-                    cost_per_hour,
-                });
-            }
-
-            Ok(Json(ListCapacityResponse {
-                total_count: available_executors.len(),
-                available_executors,
-            }))
-        }
-        Err(e) => {
+        .map_err(|e| {
             error!("Failed to query available capacity: {}", e);
-            Err(ApiError::InternalError(
-                "Failed to retrieve capacity data".to_string(),
-            ))
-        }
-    }
-}
+            ApiError::InternalError("Failed to retrieve capacity data".to_string())
+        })?;
 
-/// Check if hardware meets memory requirement
-fn hardware_meets_memory_requirement(hardware_info: &Value, min_memory_gb: u32) -> bool {
-    if let Some(gpu_info) = hardware_info.get("gpu") {
-        if let Some(gpus) = gpu_info.as_array() {
-            return gpus.iter().any(|gpu| {
-                gpu.get("memory_gb").and_then(|m| m.as_u64()).unwrap_or(0) >= min_memory_gb as u64
-            });
-        }
-    }
-    false
-}
+    let page =
+        CursorPage::from_fetched(listed, page_size, total_count, |listed: &ListedCapacity| {
+            Cursor::new(listed.sort_value, listed.entry.executor_id.clone()).with_sort(sort.clone())
+        });
 
-/// Check if hardware matches GPU type
-fn hardware_matches_gpu_type(hardware_info: &Value, gpu_type: &str) -> bool {
-    if let Some(gpu_info) = hardware_info.get("gpu") {
-        if let Some(gpus) = gpu_info.as_array() {
-            return gpus.iter().any(|gpu| {
-                gpu.get("name")
-                    .and_then(|n| n.as_str())
-                    .unwrap_or("")
-                    .to_lowercase()
-                    .contains(&gpu_type.to_lowercase())
-            });
-        }
-    }
-    false
-}
+    let available_executors = page
+        .items
+        .into_iter()
+        .map(|listed| {
+            Ok(AvailableExecutor {
+                executor: extract_executor_details(&listed.entry)?,
+                availability: AvailabilityInfo {
+                    available_from: rented_until.get(&listed.entry.executor_id).copied(),
+                    // No rentals are booked ahead, so availability is open-ended
+                    available_until: None,
+                    verification_score: listed.entry.verification_score,
+                    uptime_percentage: listed.entry.success_rate * 100.0,
+                },
+                cost_per_hour: listed.cost_per_hour,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
 
-/// Check if hardware meets GPU count requirement
-fn hardware_meets_gpu_count(hardware_info: &Value, min_count: u32) -> bool {
-    if let Some(gpu_info) = hardware_info.get("gpu") {
-        if let Some(gpus) = gpu_info.as_array() {
-            return gpus.len() >= min_count as usize;
-        }
-    }
-    false
+    Ok(Json(ListCapacityResponse {
+        available_executors,
+        total_count: page.total_count as usize,
+        next_cursor: page.next_cursor,
+    }))
 }
 
-/// Calculate cost per hour based on executor performance and specs
-fn calculate_cost_per_hour(entry: &crate::persistence::simple_persistence::CapacityEntry) -> f64 {
-    let base_cost = 1.0; // Base $1/hour
-    let score_multiplier = entry.verification_score.max(0.1); // Minimum 0.1x multiplier
-    let demand_multiplier = 1.2; // 20% markup for availability
-
-    base_cost * score_multiplier * demand_multiplier
+/// When each executor with an open rental is free again
+fn rented_until(rentals: &[Rental]) -> HashMap<String, DateTime<Utc>> {
+    let mut until: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for rental in rentals {
+        let holds_until = rental.holds_executor_until();
+        until
+            .entry(rental.executor_id.clone())
+            .and_modify(|current| *current = (*current).max(holds_until))
+            .or_insert(holds_until);
+    }
+    until
 }
 
 /// Extract executor details from capacity entry
-fn extract_executor_details(
-    entry: &crate::persistence::simple_persistence::CapacityEntry,
-) -> Result<ExecutorDetails, ApiError> {
+fn extract_executor_details(entry: &CapacityEntry) -> Result<ExecutorDetails, ApiError> {
     let gpu_specs = if let Some(gpu_info) = entry.hardware_info.get("gpu") {
         if let Some(gpus) = gpu_info.as_array() {
             gpus.iter()
@@ -201,3 +193,203 @@ fn extract_executor_details(
             .map(|s| s.to_string()),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ApiConfig, RequestAuthConfig};
+    use crate::persistence::entities::VerificationLog;
+    use crate::persistence::SimplePersistence;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    /// Executors with their score and GPU memory, verified once each
+    async fn state_with(executors: &[(&str, f64, u64)]) -> ApiState {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .unwrap();
+        for (executor_id, score, gpu_memory_gb) in executors {
+            let log = VerificationLog::new(
+                executor_id.to_string(),
+                "test_validator".to_string(),
+                "attestation".to_string(),
+                *score,
+                true,
+                json!({
+                    "gpu": [{ "name": "NVIDIA H100", "memory_gb": gpu_memory_gb }],
+                    "cpu": { "cores": 32, "model": "EPYC", "memory_gb": 256 },
+                    "location": "US-East",
+                }),
+                1000,
+                None,
+            );
+            persistence.create_verification_log(&log).await.unwrap();
+        }
+
+        let config = ApiConfig {
+            api_key: None,
+            max_body_size: 1024 * 1024,
+            bind_address: "127.0.0.1:0".to_string(),
+            request_auth: RequestAuthConfig::default(),
+        };
+        ApiState::new(
+            config,
+            Arc::new(persistence),
+            common::MemoryStorage::new().await.unwrap(),
+            crate::slashing::SlashingConfig::default(),
+            None,
+            None,
+        )
+    }
+
+    async fn list(state: &ApiState, query: ListCapacityQuery) -> ListCapacityResponse {
+        list_available_capacity(State(state.clone()), Query(query))
+            .await
+            .unwrap()
+            .0
+    }
+
+    fn ids(response: &ListCapacityResponse) -> Vec<&str> {
+        response
+            .available_executors
+            .iter()
+            .map(|available| available.executor.id.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_capacity_filters_then_pages() {
+        let state = state_with(&[
+            ("exec-a", 0.9, 80),
+            ("exec-b", 0.5, 80),
+            ("exec-c", 0.9, 24),
+            ("exec-d", 0.7, 40),
+            ("exec-e", 0.7, 80),
+        ])
+        .await;
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let response = list(
+                &state,
+                ListCapacityQuery {
+                    min_gpu_memory: Some(40),
+                    sort_by: Some(CapacitySortKey::Price),
+                    cursor: cursor.take(),
+                    page_size: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await;
+            assert_eq!(response.total_count, 4);
+            assert!(response.available_executors.len() <= 2);
+            seen.extend(ids(&response).into_iter().map(String::from));
+
+            match response.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        // Cheapest first, ties broken by executor ID
+        assert_eq!(seen, vec!["exec-b", "exec-d", "exec-e", "exec-a"]);
+
+        let response = list(
+            &state,
+            ListCapacityQuery {
+                sort_by: Some(CapacitySortKey::GpuMemory),
+                page_size: Some(3),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(ids(&response), vec!["exec-a", "exec-b", "exec-e"]);
+        assert_eq!(response.total_count, 5);
+    }
+
+    #[tokio::test]
+    async fn test_list_capacity_rejects_cursor_of_another_order() {
+        let state = state_with(&[("exec-a", 0.9, 80), ("exec-b", 0.5, 80)]).await;
+
+        let first = list(
+            &state,
+            ListCapacityQuery {
+                sort_by: Some(CapacitySortKey::Price),
+                page_size: Some(1),
+                ..Default::default()
+            },
+        )
+        .await;
+        let cursor = first.next_cursor.unwrap();
+
+        for (sort_by, order) in [
+            (CapacitySortKey::Price, Some(SortOrder::Desc)),
+            (CapacitySortKey::Score, None),
+        ] {
+            let result = list_available_capacity(
+                State(state.clone()),
+                Query(ListCapacityQuery {
+                    sort_by: Some(sort_by),
+                    order,
+                    cursor: Some(cursor.clone()),
+                    ..Default::default()
+                }),
+            )
+            .await;
+            assert!(matches!(result, Err(ApiError::BadRequest(_))));
+        }
+
+        let second = list(
+            &state,
+            ListCapacityQuery {
+                sort_by: Some(CapacitySortKey::Price),
+                order: Some(SortOrder::Asc),
+                cursor: Some(cursor),
+                page_size: Some(1),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(ids(&second), vec!["exec-a"]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_capacity_leaves_out_rented_executors() {
+        let state = state_with(&[("exec-a", 0.9, 80), ("exec-b", 0.8, 80)]).await;
+        let rental = Rental::new(
+            "exec-a".to_string(),
+            "ssh-ed25519 AAAA".to_string(),
+            "ubuntu:22.04".to_string(),
+            None,
+            json!({}),
+            json!({}),
+            2,
+            1.0,
+        );
+        state.persistence.create_rental(&rental).await.unwrap();
+
+        let now_free = list(&state, ListCapacityQuery::default()).await;
+        assert_eq!(ids(&now_free), vec!["exec-b"]);
+        assert_eq!(now_free.total_count, 1);
+
+        let soon_free = list(
+            &state,
+            ListCapacityQuery {
+                available_within_hours: Some(3),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(ids(&soon_free), vec!["exec-a", "exec-b"]);
+        assert!(soon_free.available_executors[0]
+            .availability
+            .available_from
+            .is_some());
+        assert!(soon_free.available_executors[1]
+            .availability
+            .available_from
+            .is_none());
+    }
+}
@@ -5,9 +5,13 @@ pub mod health;
 pub mod logs;
 pub mod miners;
 pub mod rentals;
+pub mod slashing;
+pub mod weights;
 
 pub use capacity::*;
 pub use health::*;
 pub use logs::*;
 pub use miners::*;
 pub use rentals::*;
+pub use slashing::*;
+pub use weights::*;
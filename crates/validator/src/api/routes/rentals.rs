@@ -3,15 +3,20 @@
 use crate::api::types::*;
 use crate::api::ApiState;
 use crate::persistence::entities::{Rental, RentalStatus as EntityRentalStatus};
+use crate::persistence::rental_usage_repository::RentalUsageRepository;
 use axum::{
     extract::{Path, State},
     Json,
 };
 use serde_json::json;
+use std::collections::HashSet;
 use tracing::{error, info};
 use uuid::Uuid;
 
 /// Rent GPU capacity
+///
+/// Starts the requested container on a suitable executor through its miner
+/// and returns the SSH endpoint published for it.
 pub async fn rent_capacity(
     State(state): State<ApiState>,
     Json(request): Json<RentCapacityRequest>,
@@ -31,97 +36,104 @@ pub async fn rent_capacity(
         ));
     }
 
+    let supervisor = state.rentals.clone().ok_or_else(|| {
+        ApiError::ServiceUnavailable(
+            "Rental provisioning is not available on this validator".to_string(),
+        )
+    })?;
+
     // Find suitable executor
     let min_score = Some(0.7); // Require good verification score for rentals
     let min_success_rate = Some(0.8);
 
-    match state
+    let capacity_entries = state
         .persistence
-        .get_available_capacity(min_score, min_success_rate, 10, 0)
+        .get_available_capacity(min_score, min_success_rate, u32::MAX, 0)
         .await
-    {
-        Ok(capacity_entries) => {
-            // Filter executors by requirements
-            let suitable_executor = capacity_entries
-                .into_iter()
-                .find(|entry| executor_meets_requirements(entry, &request.gpu_requirements));
-
-            match suitable_executor {
-                Some(executor) => {
-                    let cost_per_hour = calculate_rental_cost(&executor);
-
-                    // Generate SSH access info (simplified for now)
-                    let ssh_access = SshAccess {
-                        host: format!("{}.executor.basilica.ai", executor.executor_id),
-                        port: 22,
-                        username: "basilica".to_string(),
-                    };
-
-                    // Create GPU requirements JSON
-                    let gpu_requirements_json = json!({
-                        "min_memory_gb": request.gpu_requirements.min_memory_gb,
-                        "gpu_type": request.gpu_requirements.gpu_type,
-                        "gpu_count": request.gpu_requirements.gpu_count
-                    });
-
-                    // Create SSH access JSON
-                    let ssh_access_json = json!({
-                        "host": ssh_access.host,
-                        "port": ssh_access.port,
-                        "username": ssh_access.username
-                    });
-
-                    // Convert env_vars to JSON
-                    let env_vars_json = request.env_vars.map(|vars| json!(vars));
-
-                    // Create rental record
-                    let rental = Rental::new(
-                        executor.executor_id.clone(),
-                        request.ssh_public_key,
-                        request.docker_image,
-                        env_vars_json,
-                        gpu_requirements_json,
-                        ssh_access_json,
-                        request.max_duration_hours,
-                        cost_per_hour,
-                    );
-
-                    // Store rental in database
-                    match state.persistence.create_rental(&rental).await {
-                        Ok(()) => {
-                            let executor_details =
-                                extract_executor_details_from_capacity(&executor)?;
-
-                            Ok(Json(RentCapacityResponse {
-                                rental_id: rental.id.to_string(),
-                                executor: executor_details,
-                                ssh_access,
-                                cost_per_hour,
-                            }))
-                        }
-                        Err(e) => {
-                            error!("Failed to create rental: {}", e);
-                            Err(ApiError::InternalError(
-                                "Failed to create rental".to_string(),
-                            ))
-                        }
-                    }
-                }
-                None => Err(ApiError::NotFound(
-                    "No suitable executor found for requirements".to_string(),
-                )),
-            }
-        }
-        Err(e) => {
+        .map_err(|e| {
             error!("Failed to query available capacity: {}", e);
-            Err(ApiError::InternalError(
-                "Failed to find available capacity".to_string(),
-            ))
-        }
-    }
+            ApiError::InternalError("Failed to find available capacity".to_string())
+        })?;
+
+    // Executors already serving a rental cannot take another one
+    let rented: HashSet<String> = state
+        .persistence
+        .get_open_rentals()
+        .await
+        .map_err(|e| {
+            error!("Failed to query open rentals: {}", e);
+            ApiError::InternalError("Failed to find available capacity".to_string())
+        })?
+        .into_iter()
+        .map(|rental| rental.executor_id)
+        .collect();
+
+    // Filter executors by requirements
+    let executor = capacity_entries
+        .into_iter()
+        .filter(|entry| !rented.contains(&entry.executor_id))
+        .find(|entry| executor_meets_requirements(entry, &request.gpu_requirements))
+        .ok_or_else(|| {
+            ApiError::NotFound("No suitable executor found for requirements".to_string())
+        })?;
+
+    let cost_per_hour = calculate_rental_cost(&executor);
+    let executor_details = extract_executor_details_from_capacity(&executor)?;
+
+    // Create GPU requirements JSON
+    let gpu_requirements_json = json!({
+        "min_memory_gb": request.gpu_requirements.min_memory_gb,
+        "gpu_type": request.gpu_requirements.gpu_type,
+        "gpu_count": request.gpu_requirements.gpu_count
+    });
+
+    // Convert env_vars to JSON
+    let env_vars_json = request.env_vars.map(|vars| json!(vars));
+
+    // Record the rental as pending until its container is running
+    let mut rental = Rental::new(
+        executor.executor_id.clone(),
+        request.ssh_public_key,
+        request.docker_image,
+        env_vars_json,
+        gpu_requirements_json,
+        json!({}),
+        request.max_duration_hours,
+        cost_per_hour,
+    );
+
+    state
+        .persistence
+        .create_rental(&rental)
+        .await
+        .map_err(|e| {
+            error!("Failed to create rental: {}", e);
+            ApiError::InternalError("Failed to create rental".to_string())
+        })?;
+
+    let container = supervisor.start_rental(&mut rental).await.map_err(|e| {
+        error!("Failed to start rental {}: {:#}", rental.id, e);
+        ApiError::ServiceUnavailable(format!("Failed to provision rental container: {e:#}"))
+    })?;
+
+    let ssh_access = SshAccess {
+        host: container.host,
+        port: container.port,
+        username: container.username,
+    };
+
+    Ok(Json(RentCapacityResponse {
+        rental_id: rental.id.to_string(),
+        executor: executor_details,
+        ssh_access,
+        cost_per_hour,
+    }))
 }
 
 /// Terminate a rental
+///
+/// Billing stops immediately. If the rental's container cannot be destroyed
+/// the rental stays terminating and the supervisor retries the teardown.
 pub async fn terminate_rental(
     State(state): State<ApiState>,
     Path(rental_id): Path<String>,
@@ -132,49 +144,58 @@ pub async fn terminate_rental(
     let rental_uuid = Uuid::parse_str(&rental_id)
         .map_err(|_| ApiError::BadRequest("Invalid rental ID format".to_string()))?;
 
-    match state.persistence.get_rental(&rental_uuid).await {
-        Ok(Some(mut rental)) => {
-            if rental.is_terminated() {
-                return Err(ApiError::BadRequest(
-                    "Rental is already terminated".to_string(),
-                ));
-            }
-
-            // Calculate final cost
-            let total_cost = rental.current_cost();
-
-            // Terminate rental
-            rental.terminate(request.reason, total_cost);
-
-            // Update in database
-            match state.persistence.update_rental(&rental).await {
-                Ok(()) => {
-                    info!(
-                        "Rental {} terminated successfully, total cost: ${:.2}",
-                        rental_id, total_cost
-                    );
-
-                    Ok(Json(TerminateRentalResponse {
-                        success: true,
-                        message: format!(
-                            "Rental terminated successfully. Total cost: ${total_cost:.2}"
-                        ),
-                    }))
-                }
-                Err(e) => {
-                    error!("Failed to update rental termination: {}", e);
-                    Err(ApiError::InternalError(
-                        "Failed to terminate rental".to_string(),
-                    ))
-                }
-            }
-        }
-        Ok(None) => Err(ApiError::NotFound(format!("Rental {rental_id} not found"))),
+    let mut rental = match state.persistence.get_rental(&rental_uuid).await {
+        Ok(Some(rental)) => rental,
+        Ok(None) => return Err(ApiError::NotFound(format!("Rental {rental_id} not found"))),
         Err(e) => {
             error!("Failed to query rental: {}", e);
-            Err(ApiError::InternalError("Database error".to_string()))
+            return Err(ApiError::InternalError("Database error".to_string()));
         }
+    };
+
+    if rental.is_terminated() {
+        return Err(ApiError::BadRequest(
+            "Rental is already terminated".to_string(),
+        ));
     }
+
+    if rental.status == EntityRentalStatus::Provisioning {
+        return Err(ApiError::BadRequest(
+            "Rental is still provisioning".to_string(),
+        ));
+    }
+
+    let supervisor = state.rentals.as_ref().ok_or_else(|| {
+        ApiError::ServiceUnavailable(
+            "Rental provisioning is not available on this validator".to_string(),
+        )
+    })?;
+
+    if let Err(e) = supervisor
+        .terminate_rental(&mut rental, request.reason)
+        .await
+    {
+        error!("Failed to terminate rental {}: {:#}", rental_id, e);
+        if rental.status == EntityRentalStatus::Terminating {
+            return Err(ApiError::ServiceUnavailable(format!(
+                "Billing stopped, but the rental container could not be stopped and will be retried: {e:#}"
+            )));
+        }
+        return Err(ApiError::InternalError(
+            "Failed to terminate rental".to_string(),
+        ));
+    }
+
+    let total_cost = rental.total_cost.unwrap_or_default();
+    info!(
+        "Rental {} terminated successfully, total cost: ${:.2}",
+        rental_id, total_cost
+    );
+
+    Ok(Json(TerminateRentalResponse {
+        success: true,
+        message: format!("Rental terminated successfully. Total cost: ${total_cost:.2}"),
+    }))
 }
 
 /// Get rental status
@@ -192,7 +213,9 @@ pub async fn get_rental_status(
             // Convert entity status to API status
             let status = match rental.status {
                 EntityRentalStatus::Pending => RentalStatus::Pending,
+                EntityRentalStatus::Provisioning => RentalStatus::Provisioning,
                 EntityRentalStatus::Active => RentalStatus::Active,
+                EntityRentalStatus::Terminating => RentalStatus::Terminating,
                 EntityRentalStatus::Terminated => RentalStatus::Terminated,
                 EntityRentalStatus::Failed => RentalStatus::Failed,
             };
@@ -239,6 +262,47 @@ pub async fn get_rental_status(
     }
 }
 
+/// Get the usage billed for a rental so far
+pub async fn get_rental_usage(
+    State(state): State<ApiState>,
+    Path(rental_id): Path<String>,
+) -> Result<Json<RentalUsageResponse>, ApiError> {
+    let rental_uuid = Uuid::parse_str(&rental_id)
+        .map_err(|_| ApiError::BadRequest("Invalid rental ID format".to_string()))?;
+
+    match state.persistence.get_rental(&rental_uuid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(ApiError::NotFound(format!("Rental {rental_id} not found"))),
+        Err(e) => {
+            error!("Failed to query rental: {}", e);
+            return Err(ApiError::InternalError("Database error".to_string()));
+        }
+    }
+
+    let records = RentalUsageRepository::new(state.persistence.pool().clone())
+        .get_usage(&rental_uuid)
+        .await
+        .map_err(|e| {
+            error!("Failed to query usage of rental {}: {}", rental_id, e);
+            ApiError::InternalError("Failed to query rental usage".to_string())
+        })?;
+
+    Ok(Json(RentalUsageResponse {
+        rental_id,
+        total_hours: records.iter().map(|r| r.hours).sum(),
+        total_cost: records.iter().map(|r| r.cost).sum(),
+        records: records
+            .into_iter()
+            .map(|r| RentalUsageEntry {
+                period_start: r.period_start,
+                period_end: r.period_end,
+                hours: r.hours,
+                cost: r.cost,
+            })
+            .collect(),
+    }))
+}
+
 /// Check if executor meets GPU requirements
 fn executor_meets_requirements(
     executor: &crate::persistence::simple_persistence::CapacityEntry,
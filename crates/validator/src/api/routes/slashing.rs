@@ -0,0 +1,137 @@
+//! Slashing case review routes
+
+use crate::api::types::*;
+use crate::api::ApiState;
+use crate::persistence::slashing_repository::{
+    SlashingCase, SlashingCaseStatus, SlashingRepository,
+};
+use crate::slashing::evidence::evidence_file_name;
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// List slashing cases, newest first
+pub async fn list_slashing_cases(
+    State(state): State<ApiState>,
+    Query(query): Query<ListSlashingCasesQuery>,
+) -> Result<Json<Vec<SlashingCase>>, ApiError> {
+    let status = query
+        .status
+        .as_deref()
+        .map(str::parse::<SlashingCaseStatus>)
+        .transpose()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let limit = query.limit.unwrap_or(50).min(500);
+
+    repository(&state)
+        .list_cases(status, limit)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            error!("Failed to list slashing cases: {}", e);
+            ApiError::InternalError("Failed to list slashing cases".to_string())
+        })
+}
+
+/// Get a single slashing case
+pub async fn get_slashing_case(
+    State(state): State<ApiState>,
+    Path(case_id): Path<Uuid>,
+) -> Result<Json<SlashingCase>, ApiError> {
+    load_case(&state, &case_id).await.map(Json)
+}
+
+/// Approve a pending case for submission on the next engine pass
+pub async fn approve_slashing_case(
+    State(state): State<ApiState>,
+    Path(case_id): Path<Uuid>,
+) -> Result<Json<SlashingCase>, ApiError> {
+    review_case(&state, case_id, SlashingCaseStatus::Approved).await
+}
+
+/// Reject a pending case
+pub async fn reject_slashing_case(
+    State(state): State<ApiState>,
+    Path(case_id): Path<Uuid>,
+) -> Result<Json<SlashingCase>, ApiError> {
+    review_case(&state, case_id, SlashingCaseStatus::Rejected).await
+}
+
+/// Serve an evidence bundle exactly as written, so its checksum can be verified
+pub async fn get_slashing_evidence(
+    State(state): State<ApiState>,
+    Path(file_name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Only serve files named after a case id, never arbitrary paths
+    let case_id = file_name
+        .strip_suffix(".json")
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(|| ApiError::NotFound("Evidence not found".to_string()))?;
+
+    let path = state
+        .slashing
+        .evidence_dir
+        .join(evidence_file_name(&case_id));
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|_| ApiError::NotFound("Evidence not found".to_string()))?;
+
+    Ok(([(header::CONTENT_TYPE, "application/json")], bytes))
+}
+
+/// Move a pending case to `decision`, the operator having been authorized
+async fn review_case(
+    state: &ApiState,
+    case_id: Uuid,
+    decision: SlashingCaseStatus,
+) -> Result<Json<SlashingCase>, ApiError> {
+    let transitioned = repository(state)
+        .transition(
+            &case_id,
+            SlashingCaseStatus::PendingApproval,
+            decision,
+            None,
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to update slashing case {}: {}", case_id, e);
+            ApiError::InternalError("Failed to update slashing case".to_string())
+        })?;
+
+    let case = load_case(state, &case_id).await?;
+    if !transitioned {
+        return Err(ApiError::BadRequest(format!(
+            "Case is {}, only pending_approval cases can be reviewed",
+            case.status.as_str()
+        )));
+    }
+
+    info!(
+        case_id = %case_id,
+        executor_id = %case.executor_id,
+        "Slashing case {}",
+        decision.as_str()
+    );
+
+    Ok(Json(case))
+}
+
+async fn load_case(state: &ApiState, case_id: &Uuid) -> Result<SlashingCase, ApiError> {
+    repository(state)
+        .get_case(case_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to load slashing case {}: {}", case_id, e);
+            ApiError::InternalError("Failed to load slashing case".to_string())
+        })?
+        .ok_or_else(|| ApiError::NotFound(format!("Slashing case {case_id} not found")))
+}
+
+fn repository(state: &ApiState) -> SlashingRepository {
+    SlashingRepository::new(state.persistence.pool().clone())
+}
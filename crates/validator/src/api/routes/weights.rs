@@ -0,0 +1,27 @@
+//! Weight setting inspection routes
+
+use crate::api::types::ApiError;
+use crate::api::ApiState;
+use crate::bittensor_core::weight_explain::WeightExplanation;
+use axum::{extract::State, Json};
+use tracing::error;
+
+/// Run the weight setting pipeline without submitting and explain the result
+pub async fn explain_weights(
+    State(state): State<ApiState>,
+) -> Result<Json<WeightExplanation>, ApiError> {
+    let weight_setter = state.weights.as_ref().ok_or_else(|| {
+        ApiError::ServiceUnavailable(
+            "Weight setting is not available without a Bittensor connection".to_string(),
+        )
+    })?;
+
+    weight_setter
+        .explain_weights()
+        .await
+        .map(Json)
+        .map_err(|e| {
+            error!("Weight dry run failed: {}", e);
+            ApiError::InternalError(format!("Weight dry run failed: {e}"))
+        })
+}
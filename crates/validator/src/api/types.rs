@@ -84,19 +84,43 @@ pub struct RentalStatusResponse {
     pub cost_incurred: f64,
 }
 
+/// Serialized in lowercase like the public API gateway's rental status
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RentalStatus {
     Pending,
+    Provisioning,
     Active,
+    Terminating,
     Terminated,
     Failed,
 }
 
+/// Usage billed for a rental, one entry per supervisor interval
+#[derive(Debug, Serialize)]
+pub struct RentalUsageResponse {
+    pub rental_id: String,
+    pub records: Vec<RentalUsageEntry>,
+    pub total_hours: f64,
+    pub total_cost: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RentalUsageEntry {
+    pub period_start: chrono::DateTime<chrono::Utc>,
+    pub period_end: chrono::DateTime<chrono::Utc>,
+    pub hours: f64,
+    pub cost: f64,
+}
+
 /// Available capacity listing
 #[derive(Debug, Serialize)]
 pub struct ListCapacityResponse {
     pub available_executors: Vec<AvailableExecutor>,
+    /// Executors matching the filters across all pages
     pub total_count: usize,
+    /// Pass as `cursor` to fetch the next page, absent on the last page
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -106,20 +130,77 @@ pub struct AvailableExecutor {
     pub cost_per_hour: f64,
 }
 
+/// When an executor can be rented
+///
+/// The window starts at `available_from`, or now when that is absent, and
+/// is open-ended unless `available_until` is set.
 #[derive(Debug, Serialize)]
 pub struct AvailabilityInfo {
+    pub available_from: Option<chrono::DateTime<chrono::Utc>>,
     pub available_until: Option<chrono::DateTime<chrono::Utc>>,
     pub verification_score: f64,
     pub uptime_percentage: f64,
 }
 
+/// Sort keys for capacity listing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacitySortKey {
+    Price,
+    #[default]
+    Score,
+    GpuMemory,
+    Uptime,
+}
+
+impl CapacitySortKey {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Price => "price",
+            Self::Score => "score",
+            Self::GpuMemory => "gpu_memory",
+            Self::Uptime => "uptime",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Asc => "asc",
+            Self::Desc => "desc",
+        }
+    }
+}
+
 /// Query parameters for capacity listing
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct ListCapacityQuery {
     pub min_gpu_memory: Option<u32>,
     pub gpu_type: Option<String>,
     pub min_gpu_count: Option<u32>,
     pub max_cost_per_hour: Option<f64>,
+    pub min_score: Option<f64>,
+    /// Case-insensitive match against the executor location
+    pub region: Option<String>,
+    pub min_cpu_cores: Option<u32>,
+    pub min_ram_gb: Option<u32>,
+    pub min_disk_gb: Option<u32>,
+    pub min_bandwidth_mbps: Option<f64>,
+    /// Also list rented executors that become free within this many hours
+    pub available_within_hours: Option<u32>,
+    pub sort_by: Option<CapacitySortKey>,
+    /// Defaults to ascending for price and descending otherwise
+    pub order: Option<SortOrder>,
+    pub cursor: Option<String>,
+    pub page_size: Option<u32>,
 }
 
 /// Log streaming query parameters
@@ -200,6 +281,13 @@ pub struct ListMinersQuery {
     pub page_size: Option<u32>,
 }
 
+/// Query parameters for slashing case listing
+#[derive(Debug, Deserialize)]
+pub struct ListSlashingCasesQuery {
+    pub status: Option<String>,
+    pub limit: Option<u32>,
+}
+
 /// Miner update request
 #[derive(Debug, Deserialize)]
 pub struct UpdateMinerRequest {
@@ -247,6 +335,7 @@ pub enum ApiError {
     BadRequest(String),
     Unauthorized,
     InternalError(String),
+    ServiceUnavailable(String),
 }
 
 impl axum::response::IntoResponse for ApiError {
@@ -259,6 +348,7 @@ impl axum::response::IntoResponse for ApiError {
             ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
             ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            ApiError::ServiceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
         };
 
         let body = Json(serde_json::json!({
@@ -0,0 +1,389 @@
+//! # Collateral Scoring
+//!
+//! Adjusts miner scores by the collateral their executors have posted on the
+//! Collateral contract. Each GPU category can require a minimum amount of TAO
+//! per GPU; executors below it are either excluded (gate) or scaled down
+//! proportionally (scale) before the weight allocation runs.
+
+use crate::config::emission::{CollateralEmissionConfig, CollateralScoringMode};
+use crate::gpu::categorization::GpuCategorizer;
+use crate::persistence::SimplePersistence;
+use anyhow::Result;
+use collateral::CollateralClient;
+use common::identity::MinerUid;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+
+/// Collateral backing a single executor
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutorCollateral {
+    pub executor_id: String,
+    /// Number of GPUs per normalized GPU category
+    pub gpu_counts: HashMap<String, u32>,
+    /// Collateral posted for this executor, in TAO
+    pub collateral_tao: f64,
+}
+
+/// Fraction of an executor's contribution that counts for a category
+fn executor_factor(
+    mode: CollateralScoringMode,
+    min_per_gpu: f64,
+    gpu_count: u32,
+    collateral_tao: f64,
+) -> f64 {
+    let required = min_per_gpu * gpu_count as f64;
+    match mode {
+        CollateralScoringMode::Gate => {
+            if collateral_tao >= required {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        CollateralScoringMode::Scale => (collateral_tao / required).clamp(0.0, 1.0),
+    }
+}
+
+/// Collateral multiplier for a miner in a category
+///
+/// Executors are weighted by their GPU count in the category. A miner without
+/// any known executor in the category gets a multiplier of 0.
+pub fn collateral_factor(
+    config: &CollateralEmissionConfig,
+    category: &str,
+    executors: &[ExecutorCollateral],
+) -> f64 {
+    let Some(min_per_gpu) = config.min_collateral_for(category) else {
+        return 1.0;
+    };
+
+    let mut total_gpus = 0u32;
+    let mut covered_gpus = 0.0;
+    for executor in executors {
+        let gpu_count = executor.gpu_counts.get(category).copied().unwrap_or(0);
+        if gpu_count == 0 {
+            continue;
+        }
+
+        let factor = executor_factor(config.mode, min_per_gpu, gpu_count, executor.collateral_tao);
+        if factor < 1.0 {
+            debug!(
+                "Executor {} has {:.4} TAO collateral for {} {} GPUs, below the {:.4} TAO minimum",
+                executor.executor_id,
+                executor.collateral_tao,
+                gpu_count,
+                category,
+                min_per_gpu * gpu_count as f64
+            );
+        }
+
+        total_gpus += gpu_count;
+        covered_gpus += gpu_count as f64 * factor;
+    }
+
+    if total_gpus == 0 {
+        0.0
+    } else {
+        covered_gpus / total_gpus as f64
+    }
+}
+
+/// Apply the collateral rule to miner scores grouped by GPU category
+///
+/// Miners whose adjusted score drops to zero are removed from their category.
+pub fn apply_collateral_rule(
+    config: &CollateralEmissionConfig,
+    miners_by_category: HashMap<String, Vec<(MinerUid, f64)>>,
+    executors_by_miner: &HashMap<MinerUid, Vec<ExecutorCollateral>>,
+) -> HashMap<String, Vec<(MinerUid, f64)>> {
+    miners_by_category
+        .into_iter()
+        .map(|(category, miners)| {
+            let adjusted = miners
+                .into_iter()
+                .filter_map(|(miner_uid, score)| {
+                    let executors = executors_by_miner
+                        .get(&miner_uid)
+                        .map(Vec::as_slice)
+                        .unwrap_or(&[]);
+                    let factor = collateral_factor(config, &category, executors);
+                    if factor < 1.0 {
+                        debug!(
+                            "Miner {} {} score scaled by collateral factor {:.3}",
+                            miner_uid, category, factor
+                        );
+                    }
+
+                    let adjusted_score = score * factor;
+                    (adjusted_score > 0.0).then_some((miner_uid, adjusted_score))
+                })
+                .collect::<Vec<_>>();
+            (category, adjusted)
+        })
+        .filter(|(_, miners)| !miners.is_empty())
+        .collect()
+}
+
+/// Looks up executor collateral on chain and applies the collateral rule
+pub struct CollateralScorer {
+    config: CollateralEmissionConfig,
+    client: CollateralClient,
+    persistence: Arc<SimplePersistence>,
+    categorizer: GpuCategorizer,
+    cache: RwLock<HashMap<u128, (f64, Instant)>>,
+}
+
+impl CollateralScorer {
+    /// Create a new collateral scorer
+    pub fn new(
+        config: CollateralEmissionConfig,
+        client: CollateralClient,
+        persistence: Arc<SimplePersistence>,
+        categorizer: GpuCategorizer,
+    ) -> Self {
+        Self {
+            config,
+            client,
+            persistence,
+            categorizer,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Adjust miner scores by posted collateral
+    pub async fn apply(
+        &self,
+        miners_by_category: HashMap<String, Vec<(MinerUid, f64)>>,
+    ) -> Result<HashMap<String, Vec<(MinerUid, f64)>>> {
+        if !self.config.enabled {
+            return Ok(miners_by_category);
+        }
+
+        let mut miner_uids: Vec<MinerUid> = miners_by_category
+            .iter()
+            .filter(|(category, _)| self.config.min_collateral_for(category).is_some())
+            .flat_map(|(_, miners)| miners.iter().map(|(uid, _)| *uid))
+            .collect();
+        miner_uids.sort_by_key(|uid| uid.as_u16());
+        miner_uids.dedup();
+
+        let mut executors_by_miner = HashMap::new();
+        for miner_uid in miner_uids {
+            let executors = self.load_miner_executors(miner_uid).await?;
+            executors_by_miner.insert(miner_uid, executors);
+        }
+
+        let adjusted = apply_collateral_rule(&self.config, miners_by_category, &executors_by_miner);
+        info!(
+            "Applied collateral rule ({:?}) to {} miners",
+            self.config.mode,
+            executors_by_miner.len()
+        );
+
+        Ok(adjusted)
+    }
+
+    /// Load a miner's executors with their GPU counts and posted collateral
+    async fn load_miner_executors(&self, miner_uid: MinerUid) -> Result<Vec<ExecutorCollateral>> {
+        let miner_id = format!("miner_{}", miner_uid.as_u16());
+        let prefix = format!("miner{}__", miner_uid.as_u16());
+
+        let mut gpu_counts: HashMap<String, HashMap<String, u32>> = HashMap::new();
+        for (executor_id, gpu_name) in self
+            .persistence
+            .get_miner_gpu_assignments(&miner_id)
+            .await?
+        {
+            let executor_id = executor_id
+                .strip_prefix(&prefix)
+                .unwrap_or(&executor_id)
+                .to_string();
+            let category = self
+                .categorizer
+                .normalize_gpu_model(gpu_name.as_deref().unwrap_or(""));
+            *gpu_counts
+                .entry(executor_id)
+                .or_default()
+                .entry(category)
+                .or_insert(0) += 1;
+        }
+
+        let mut executors = Vec::with_capacity(gpu_counts.len());
+        for (executor_id, gpu_counts) in gpu_counts {
+            let collateral_tao = self.executor_collateral(&executor_id).await;
+            executors.push(ExecutorCollateral {
+                executor_id,
+                gpu_counts,
+                collateral_tao,
+            });
+        }
+
+        Ok(executors)
+    }
+
+    /// Posted collateral for an executor in TAO, served from cache within the TTL
+    ///
+    /// RPC failures fall back to the last known value. An executor that has never
+    /// been looked up counts as having posted nothing, so an unreachable endpoint
+    /// cannot let uncollateralized executors through.
+    async fn executor_collateral(&self, executor_id: &str) -> f64 {
+        let onchain_id = collateral::executor_id_from_str(executor_id);
+        let ttl = Duration::from_secs(self.config.cache_ttl_secs);
+
+        let cached = self.cache.read().await.get(&onchain_id).copied();
+        if let Some((amount, fetched_at)) = cached {
+            if fetched_at.elapsed() < ttl {
+                return amount;
+            }
+        }
+
+        match self.client.collaterals(onchain_id).await {
+            Ok(amount) => {
+                let amount = collateral::wei_to_tao(amount);
+                self.cache
+                    .write()
+                    .await
+                    .insert(onchain_id, (amount, Instant::now()));
+                amount
+            }
+            Err(e) => match cached {
+                Some((amount, _)) => {
+                    warn!(
+                        "Failed to fetch collateral for executor {}, using cached value: {}",
+                        executor_id, e
+                    );
+                    amount
+                }
+                None => {
+                    error!(
+                        "Failed to fetch collateral for executor {}, treating it as 0: {}",
+                        executor_id, e
+                    );
+                    0.0
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mode: CollateralScoringMode) -> CollateralEmissionConfig {
+        CollateralEmissionConfig {
+            enabled: true,
+            mode,
+            min_collateral_per_gpu: HashMap::from([("H100".to_string(), 10.0)]),
+            cache_ttl_secs: 600,
+        }
+    }
+
+    fn executor(id: &str, category: &str, gpus: u32, collateral_tao: f64) -> ExecutorCollateral {
+        ExecutorCollateral {
+            executor_id: id.to_string(),
+            gpu_counts: HashMap::from([(category.to_string(), gpus)]),
+            collateral_tao,
+        }
+    }
+
+    #[test]
+    fn test_gate_mode_excludes_undercollateralized_executors() {
+        let config = config(CollateralScoringMode::Gate);
+        let executors = vec![
+            executor("a", "H100", 2, 20.0),
+            executor("b", "H100", 2, 19.9),
+        ];
+
+        assert_eq!(collateral_factor(&config, "H100", &executors), 0.5);
+        assert_eq!(collateral_factor(&config, "H200", &executors), 1.0);
+        assert_eq!(collateral_factor(&config, "H100", &[]), 0.0);
+    }
+
+    #[test]
+    fn test_scale_mode_is_proportional_and_capped() {
+        let config = config(CollateralScoringMode::Scale);
+        let executors = vec![
+            executor("a", "H100", 1, 50.0),
+            executor("b", "H100", 3, 15.0),
+        ];
+
+        // a: 1 GPU fully covered, b: 3 GPUs at 50%
+        let factor = collateral_factor(&config, "H100", &executors);
+        assert!((factor - (1.0 + 3.0 * 0.5) / 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apply_collateral_rule_drops_zero_scores() {
+        let config = config(CollateralScoringMode::Gate);
+        let miners_by_category = HashMap::from([
+            (
+                "H100".to_string(),
+                vec![(MinerUid::new(1), 100.0), (MinerUid::new(2), 50.0)],
+            ),
+            ("H200".to_string(), vec![(MinerUid::new(2), 80.0)]),
+        ]);
+        let executors_by_miner = HashMap::from([
+            (MinerUid::new(1), vec![executor("a", "H100", 1, 10.0)]),
+            (MinerUid::new(2), vec![executor("b", "H100", 1, 0.0)]),
+        ]);
+
+        let adjusted = apply_collateral_rule(&config, miners_by_category, &executors_by_miner);
+
+        assert_eq!(adjusted["H100"], vec![(MinerUid::new(1), 100.0)]);
+        assert_eq!(adjusted["H200"], vec![(MinerUid::new(2), 80.0)]);
+    }
+
+    async fn unreachable_scorer() -> CollateralScorer {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .unwrap();
+        CollateralScorer::new(
+            config(CollateralScoringMode::Gate),
+            CollateralClient::new(
+                collateral::CollateralConfig::local(Default::default())
+                    .with_rpc_url("http://127.0.0.1:1"),
+            ),
+            Arc::new(persistence),
+            GpuCategorizer::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rpc_error_without_cache_counts_as_no_collateral() {
+        let scorer = unreachable_scorer().await;
+
+        assert_eq!(scorer.executor_collateral("exec-a").await, 0.0);
+        let executors = vec![executor("exec-a", "H100", 1, 0.0)];
+        assert_eq!(collateral_factor(&scorer.config, "H100", &executors), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_error_falls_back_to_cached_value() {
+        let scorer = unreachable_scorer().await;
+        let expired = Instant::now() - Duration::from_secs(scorer.config.cache_ttl_secs + 1);
+        scorer
+            .cache
+            .write()
+            .await
+            .insert(collateral::executor_id_from_str("exec-a"), (25.0, expired));
+
+        assert_eq!(scorer.executor_collateral("exec-a").await, 25.0);
+    }
+
+    #[test]
+    fn test_disabled_categories_leave_scores_untouched() {
+        let config = CollateralEmissionConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let miners_by_category =
+            HashMap::from([("H100".to_string(), vec![(MinerUid::new(1), 10.0)])]);
+
+        let adjusted = apply_collateral_rule(&config, miners_by_category.clone(), &HashMap::new());
+        assert_eq!(adjusted, miners_by_category);
+    }
+}
@@ -4,7 +4,9 @@
 //! Handles weight setting, metagraph operations, and network communication.
 
 pub mod chain_registration;
+pub mod collateral_scoring;
 pub mod weight_allocation;
+pub mod weight_explain;
 pub mod weight_setter;
 
 pub use chain_registration::ChainRegistration;
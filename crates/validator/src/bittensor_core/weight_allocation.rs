@@ -242,7 +242,7 @@ impl WeightAllocationEngine {
     }
 
     /// Distribute weight within a category proportionally by score
-    fn distribute_category_weight(
+    pub fn distribute_category_weight(
         &self,
         category_miners: &[(MinerUid, f64)],
         category_weight_pool: u64,
@@ -381,7 +381,7 @@ pub struct NormalizedWeight {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::emission::EmissionConfig;
+    use crate::config::emission::{EmissionConfig, WeightSubmissionMode};
     use std::collections::HashMap;
 
     fn create_test_config() -> EmissionConfig {
@@ -395,6 +395,9 @@ mod tests {
             gpu_allocations,
             weight_set_interval_blocks: 360,
             weight_version_key: 0,
+            weight_submission: WeightSubmissionMode::Direct,
+            gpu_models: Default::default(),
+            collateral: Default::default(),
         }
     }
 
@@ -638,7 +641,7 @@ mod tests {
         config.gpu_allocations.clear();
         config.gpu_allocations.insert("H100".to_string(), 40.0);
         config.gpu_allocations.insert("H200".to_string(), 30.0);
-        config.gpu_allocations.insert("A100".to_string(), 30.0);
+        config.gpu_allocations.insert("A100_80GB".to_string(), 30.0);
 
         let engine = WeightAllocationEngine::new(config, 0.0);
 
@@ -648,7 +651,7 @@ mod tests {
 
         let distribution = engine.calculate_weight_distribution(miners).unwrap();
 
-        // Should burn H200 and A100 allocations (30% + 30% = 60%) plus base burn (10%)
+        // Should burn H200 and A100_80GB allocations (30% + 30% = 60%) plus base burn (10%)
         let burn = distribution.burn_allocation.unwrap();
         // The exact percentage depends on weight calculations and rounding
         // We expect around 64% (not 70% due to how weights are calculated)
@@ -0,0 +1,354 @@
+//! # Weight Explanations
+//!
+//! Per-miner breakdown of a weight distribution, produced by a dry run of the
+//! weight setting pipeline so operators can see why a miner received its
+//! weight before anything is submitted to the chain.
+
+use crate::bittensor_core::weight_allocation::{
+    BurnAllocation, CategoryAllocation, WeightAllocationEngine, WeightDistribution,
+};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use common::identity::MinerUid;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Result of a weight setting dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightExplanation {
+    pub netuid: u16,
+    pub generated_at: DateTime<Utc>,
+    /// Weight shared out between miners and burn
+    pub total_weight: u64,
+    /// Burn allocation, including the pools of categories without miners
+    pub burn: Option<BurnAllocation>,
+    /// Categories with miners, sorted by name
+    pub categories: Vec<CategoryAllocation>,
+    /// Miners sorted by final weight, highest first
+    pub miners: Vec<MinerWeightExplanation>,
+}
+
+/// How a single miner's weight was arrived at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinerWeightExplanation {
+    pub miner_uid: u16,
+    pub hotkey: Option<String>,
+    pub categories: Vec<MinerCategoryWeight>,
+    /// Weight submitted for the miner, summed over its categories
+    pub final_weight: u16,
+    /// Final weight as a percentage of the total weight
+    pub weight_percentage: f64,
+}
+
+/// A miner's part in one category pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinerCategoryWeight {
+    pub category: String,
+    pub gpu_count: u32,
+    /// Score the miner competes with in this category
+    pub score: f64,
+    /// Fraction of the category pool the miner received
+    pub pool_share: f64,
+    pub weight: u16,
+}
+
+impl WeightExplanation {
+    /// Break a distribution down per miner
+    ///
+    /// `miners_by_category` must be the input the distribution was calculated
+    /// from, so that each category pool splits the same way again.
+    pub fn build(
+        engine: &WeightAllocationEngine,
+        netuid: u16,
+        miners_by_category: &HashMap<String, Vec<(MinerUid, f64)>>,
+        distribution: &WeightDistribution,
+        gpu_counts: &HashMap<u16, HashMap<String, u32>>,
+        hotkeys: &HashMap<u16, String>,
+    ) -> Result<Self> {
+        let mut miners: HashMap<u16, Vec<MinerCategoryWeight>> = HashMap::new();
+
+        for (category, allocation) in &distribution.category_allocations {
+            let Some(category_miners) = miners_by_category.get(category) else {
+                continue;
+            };
+
+            let category_weights =
+                engine.distribute_category_weight(category_miners, allocation.weight_pool)?;
+
+            for (miner_uid, score) in category_miners {
+                let uid = miner_uid.as_u16();
+                let weight = category_weights
+                    .iter()
+                    .find(|w| w.uid == uid)
+                    .map(|w| w.weight)
+                    .unwrap_or(0);
+                let pool_share = if allocation.weight_pool > 0 {
+                    weight as f64 / allocation.weight_pool as f64
+                } else {
+                    0.0
+                };
+                let gpu_count = gpu_counts
+                    .get(&uid)
+                    .and_then(|counts| counts.get(category))
+                    .copied()
+                    .unwrap_or(0);
+
+                miners.entry(uid).or_default().push(MinerCategoryWeight {
+                    category: category.clone(),
+                    gpu_count,
+                    score: *score,
+                    pool_share,
+                    weight,
+                });
+            }
+        }
+
+        let mut miners: Vec<MinerWeightExplanation> = miners
+            .into_iter()
+            .map(|(uid, mut categories)| {
+                categories.sort_by(|a, b| a.category.cmp(&b.category));
+                let final_weight = distribution
+                    .weights
+                    .iter()
+                    .find(|w| w.uid == uid)
+                    .map(|w| w.weight)
+                    .unwrap_or(0);
+
+                MinerWeightExplanation {
+                    miner_uid: uid,
+                    hotkey: hotkeys.get(&uid).cloned(),
+                    categories,
+                    final_weight,
+                    weight_percentage: percentage(final_weight, distribution.total_weight),
+                }
+            })
+            .collect();
+        miners.sort_by(|a, b| {
+            b.final_weight
+                .cmp(&a.final_weight)
+                .then(a.miner_uid.cmp(&b.miner_uid))
+        });
+
+        let mut categories: Vec<CategoryAllocation> = distribution
+            .category_allocations
+            .values()
+            .cloned()
+            .collect();
+        categories.sort_by(|a, b| a.gpu_model.cmp(&b.gpu_model));
+
+        Ok(Self {
+            netuid,
+            generated_at: Utc::now(),
+            total_weight: distribution.total_weight,
+            burn: distribution.burn_allocation.clone(),
+            categories,
+            miners,
+        })
+    }
+
+    /// Render the explanation as plain text tables
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "Weight dry run for netuid {} at {} (total weight {})",
+            self.netuid,
+            self.generated_at.to_rfc3339(),
+            self.total_weight
+        );
+        match &self.burn {
+            Some(burn) => {
+                let _ = writeln!(
+                    out,
+                    "Burn: uid {} receives {} ({:.2}%)",
+                    burn.uid, burn.weight, burn.percentage
+                );
+            }
+            None => {
+                let _ = writeln!(out, "Burn: none");
+            }
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "{:<12} {:>7} {:>14} {:>12} {:>8}",
+            "CATEGORY", "MINERS", "TOTAL SCORE", "POOL", "SHARE"
+        );
+        let _ = writeln!(out, "{}", "-".repeat(57));
+        for category in &self.categories {
+            let _ = writeln!(
+                out,
+                "{:<12} {:>7} {:>14.4} {:>12} {:>7.2}%",
+                category.gpu_model,
+                category.miner_count,
+                category.total_score,
+                category.weight_pool,
+                category.allocation_percentage
+            );
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "{:<6} {:<12} {:<12} {:>5} {:>12} {:>10} {:>8} {:>8} {:>8}",
+            "UID",
+            "HOTKEY",
+            "CATEGORY",
+            "GPUS",
+            "SCORE",
+            "POOL SHARE",
+            "WEIGHT",
+            "FINAL",
+            "FINAL %"
+        );
+        let _ = writeln!(out, "{}", "-".repeat(91));
+        for miner in &self.miners {
+            let hotkey = miner
+                .hotkey
+                .as_deref()
+                .map(|hotkey| hotkey.chars().take(12).collect::<String>())
+                .unwrap_or_else(|| "-".to_string());
+
+            for (index, category) in miner.categories.iter().enumerate() {
+                let (uid, hotkey, final_weight, final_percentage) = if index == 0 {
+                    (
+                        miner.miner_uid.to_string(),
+                        hotkey.as_str(),
+                        miner.final_weight.to_string(),
+                        format!("{:.2}%", miner.weight_percentage),
+                    )
+                } else {
+                    (String::new(), "", String::new(), String::new())
+                };
+
+                let _ = writeln!(
+                    out,
+                    "{:<6} {:<12} {:<12} {:>5} {:>12.4} {:>9.2}% {:>8} {:>8} {:>8}",
+                    uid,
+                    hotkey,
+                    category.category,
+                    category.gpu_count,
+                    category.score,
+                    category.pool_share * 100.0,
+                    category.weight,
+                    final_weight,
+                    final_percentage
+                );
+            }
+        }
+
+        if self.miners.is_empty() {
+            let _ = writeln!(out, "No miners eligible for weights");
+        }
+
+        out
+    }
+}
+
+fn percentage(weight: u16, total_weight: u64) -> f64 {
+    if total_weight == 0 {
+        return 0.0;
+    }
+    weight as f64 / total_weight as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::emission::EmissionConfig;
+
+    fn explain(
+        miners_by_category: HashMap<String, Vec<(MinerUid, f64)>>,
+    ) -> (WeightExplanation, WeightDistribution) {
+        let engine = WeightAllocationEngine::new(EmissionConfig::for_testing(), 0.0);
+        let distribution = engine
+            .calculate_weight_distribution(miners_by_category.clone())
+            .unwrap();
+
+        let gpu_counts = HashMap::from([
+            (1, HashMap::from([("H100".to_string(), 2)])),
+            (
+                2,
+                HashMap::from([("H100".to_string(), 1), ("H200".to_string(), 4)]),
+            ),
+        ]);
+        let hotkeys = HashMap::from([(1, "5FirstMinerHotkey".to_string())]);
+
+        let explanation = WeightExplanation::build(
+            &engine,
+            1,
+            &miners_by_category,
+            &distribution,
+            &gpu_counts,
+            &hotkeys,
+        )
+        .unwrap();
+        (explanation, distribution)
+    }
+
+    #[test]
+    fn test_explanation_matches_distribution() {
+        let miners_by_category = HashMap::from([
+            (
+                "H100".to_string(),
+                vec![(MinerUid::new(1), 2.0), (MinerUid::new(2), 1.0)],
+            ),
+            ("H200".to_string(), vec![(MinerUid::new(2), 4.0)]),
+        ]);
+        let (explanation, distribution) = explain(miners_by_category);
+
+        assert_eq!(explanation.miners.len(), 2);
+        for miner in &explanation.miners {
+            let submitted = distribution
+                .weights
+                .iter()
+                .find(|w| w.uid == miner.miner_uid)
+                .unwrap();
+            assert_eq!(miner.final_weight, submitted.weight);
+
+            let category_total: u64 = miner.categories.iter().map(|c| c.weight as u64).sum();
+            assert_eq!(category_total, miner.final_weight as u64);
+        }
+
+        let miner_two = explanation
+            .miners
+            .iter()
+            .find(|m| m.miner_uid == 2)
+            .unwrap();
+        assert_eq!(miner_two.categories.len(), 2);
+        assert_eq!(miner_two.categories[1].category, "H200");
+        assert_eq!(miner_two.categories[1].gpu_count, 4);
+        assert!((miner_two.categories[1].pool_share - 1.0).abs() < 1e-9);
+        assert!(miner_two.hotkey.is_none());
+
+        let h100_shares: f64 = explanation
+            .miners
+            .iter()
+            .flat_map(|m| m.categories.iter())
+            .filter(|c| c.category == "H100")
+            .map(|c| c.pool_share)
+            .sum();
+        assert!((h100_shares - 1.0).abs() < 1e-3);
+
+        assert!(explanation.miners[0].final_weight >= explanation.miners[1].final_weight);
+        assert_eq!(explanation.categories[0].gpu_model, "H100");
+    }
+
+    #[test]
+    fn test_burn_only_explanation_renders() {
+        let (explanation, _) = explain(HashMap::new());
+
+        assert!(explanation.miners.is_empty());
+        assert!(explanation.categories.is_empty());
+        assert_eq!(
+            explanation.burn.as_ref().map(|b| b.weight as u64),
+            Some(explanation.total_weight)
+        );
+
+        let table = explanation.to_table();
+        assert!(table.contains("No miners eligible for weights"));
+    }
+}
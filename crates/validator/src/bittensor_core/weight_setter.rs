@@ -3,13 +3,16 @@
 //! Manages Bittensor weight setting operations for the Validator.
 //! Sets weights every N blocks based on miner scores from executor validations.
 
-use crate::bittensor_core::weight_allocation::WeightAllocationEngine;
-use crate::config::emission::EmissionConfig;
+use crate::bittensor_core::collateral_scoring::CollateralScorer;
+use crate::bittensor_core::weight_allocation::{WeightAllocationEngine, WeightDistribution};
+use crate::bittensor_core::weight_explain::WeightExplanation;
+use crate::config::emission::{EmissionConfig, WeightSubmissionMode};
 use crate::gpu::categorization;
 use crate::gpu::GpuScoringEngine;
 use crate::metrics::ValidatorMetrics;
 use crate::persistence::entities::VerificationLog;
 use crate::persistence::gpu_profile_repository::GpuProfileRepository;
+use crate::persistence::weight_commit_repository::{WeightCommitRepository, WeightCommitStatus};
 use crate::persistence::SimplePersistence;
 use anyhow::Result;
 use bittensor::{AccountId, Metagraph, NormalizedWeight, Service as BittensorService};
@@ -44,6 +47,13 @@ pub struct ExecutorValidationResult {
     pub gpu_model: String,
 }
 
+/// Output of the allocation pipeline before chain submission
+struct WeightCalculation {
+    metagraph: Metagraph<AccountId>,
+    miners_by_category: HashMap<String, Vec<(MinerUid, f64)>>,
+    distribution: WeightDistribution,
+}
+
 /// Manages weight setting operations for Bittensor network
 #[derive(Clone)]
 pub struct WeightSetter {
@@ -58,7 +68,9 @@ pub struct WeightSetter {
     weight_allocation_engine: Arc<WeightAllocationEngine>,
     emission_config: EmissionConfig,
     gpu_profile_repo: Arc<GpuProfileRepository>,
+    weight_commits: Arc<WeightCommitRepository>,
     metrics: Option<Arc<ValidatorMetrics>>,
+    collateral_scorer: Option<Arc<CollateralScorer>>,
 }
 
 impl WeightSetter {
@@ -107,6 +119,8 @@ impl WeightSetter {
             min_score_threshold,
         ));
 
+        let weight_commits = Arc::new(WeightCommitRepository::new(persistence.pool().clone()));
+
         Ok(Self {
             config,
             bittensor_service,
@@ -119,10 +133,18 @@ impl WeightSetter {
             weight_allocation_engine,
             emission_config,
             gpu_profile_repo,
+            weight_commits,
             metrics,
+            collateral_scorer: None,
         })
     }
 
+    /// Apply on-chain collateral requirements to miner scores before allocation
+    pub fn with_collateral_scorer(mut self, collateral_scorer: Arc<CollateralScorer>) -> Self {
+        self.collateral_scorer = Some(collateral_scorer);
+        self
+    }
+
     /// Start the weight setting loop
     pub async fn start(&self) -> Result<()> {
         // Check every 12 seconds (1 block time on Bittensor)
@@ -154,6 +176,13 @@ impl WeightSetter {
                 }
             };
 
+            // Reveal earlier commits before committing new weights
+            if self.emission_config.weight_submission == WeightSubmissionMode::CommitReveal {
+                if let Err(e) = self.reveal_pending_commits(current_block).await {
+                    error!("Failed to reveal pending weight commits: {}", e);
+                }
+            }
+
             let last_block = *self.last_weight_set_block.lock().await;
 
             // Check if it's time to set weights
@@ -249,54 +278,10 @@ impl WeightSetter {
             self.config.netuid
         );
 
-        // 1. Get current metagraph
-        let metagraph = self.get_metagraph().await?;
-        debug!(
-            "Retrieved metagraph with {} neurons",
-            metagraph.hotkeys.len()
-        );
-
-        // 2. Get last weight set timestamp for epoch filtering
-        let last_weight_timestamp = self.get_last_weight_set_timestamp().await?;
-        info!(
-            "Fetching miners by GPU category from scoring engine, cutoff at {GPU_CATEGORY_CUTOFF_HOURS} hours, epoch: {:?}",
-            last_weight_timestamp
-        );
-
-        // 3. Get miners by GPU category from the scoring engine with axon validation and epoch filtering
-        let miners_by_category = self
-            .gpu_scoring_engine
-            .get_miners_by_gpu_category_since_epoch(
-                last_weight_timestamp,
-                GPU_CATEGORY_CUTOFF_HOURS,
-                &metagraph,
-            )
-            .await?;
-
-        if miners_by_category.is_empty() {
-            warn!("No miners found in any GPU category - proceeding with burn allocation");
-        }
-
-        info!(
-            "Found miners in {} GPU categories: {:?}",
-            miners_by_category.len(),
-            miners_by_category.keys().collect::<Vec<_>>()
-        );
-
-        // 3. Calculate weight distribution using the allocation engine
-        let weight_distribution = self
-            .weight_allocation_engine
-            .calculate_weight_distribution(miners_by_category)?;
-
-        if weight_distribution.miners_served == 0 {
-            warn!("No miners served by weight allocation - proceeding with burn-only weights");
-        }
-
-        info!(
-            "Weight distribution calculated: {} miners served, {} categories",
-            weight_distribution.miners_served,
-            weight_distribution.category_allocations.len()
-        );
+        let WeightCalculation {
+            distribution: weight_distribution,
+            ..
+        } = self.calculate_weights().await?;
 
         // 4. Log category allocations for transparency
         for (category, allocation) in &weight_distribution.category_allocations {
@@ -346,7 +331,7 @@ impl WeightSetter {
         }
 
         // Submit weights to chain with enhanced error handling and retry logic
-        self.submit_weights_to_chain_with_retry(normalized_weights.clone(), version_key)
+        self.submit_weights(normalized_weights.clone(), version_key)
             .await?;
 
         // 8. Store emission metrics to database
@@ -366,6 +351,110 @@ impl WeightSetter {
         Ok(())
     }
 
+    /// Run the allocation pipeline, from GPU profiles to the final weight
+    /// distribution, without submitting anything to the chain
+    async fn calculate_weights(&self) -> Result<WeightCalculation> {
+        // 1. Get current metagraph
+        let metagraph = self.get_metagraph().await?;
+        debug!(
+            "Retrieved metagraph with {} neurons",
+            metagraph.hotkeys.len()
+        );
+
+        // 2. Get last weight set timestamp for epoch filtering
+        let last_weight_timestamp = self.get_last_weight_set_timestamp().await?;
+        info!(
+            "Fetching miners by GPU category from scoring engine, cutoff at {GPU_CATEGORY_CUTOFF_HOURS} hours, epoch: {:?}",
+            last_weight_timestamp
+        );
+
+        // 3. Get miners by GPU category from the scoring engine with axon validation and epoch filtering
+        let miners_by_category = self
+            .gpu_scoring_engine
+            .get_miners_by_gpu_category_since_epoch(
+                last_weight_timestamp,
+                GPU_CATEGORY_CUTOFF_HOURS,
+                &metagraph,
+            )
+            .await?;
+
+        // Apply collateral requirements, if configured
+        let miners_by_category = match &self.collateral_scorer {
+            Some(scorer) => scorer.apply(miners_by_category).await?,
+            None => miners_by_category,
+        };
+
+        if miners_by_category.is_empty() {
+            warn!("No miners found in any GPU category - proceeding with burn allocation");
+        }
+
+        info!(
+            "Found miners in {} GPU categories: {:?}",
+            miners_by_category.len(),
+            miners_by_category.keys().collect::<Vec<_>>()
+        );
+
+        // 3. Calculate weight distribution using the allocation engine
+        let weight_distribution = self
+            .weight_allocation_engine
+            .calculate_weight_distribution(miners_by_category.clone())?;
+
+        if weight_distribution.miners_served == 0 {
+            warn!("No miners served by weight allocation - proceeding with burn-only weights");
+        }
+
+        info!(
+            "Weight distribution calculated: {} miners served, {} categories",
+            weight_distribution.miners_served,
+            weight_distribution.category_allocations.len()
+        );
+
+        Ok(WeightCalculation {
+            metagraph,
+            miners_by_category,
+            distribution: weight_distribution,
+        })
+    }
+
+    /// Dry run of weight setting with a per-miner breakdown of the result
+    pub async fn explain_weights(&self) -> Result<WeightExplanation> {
+        let WeightCalculation {
+            metagraph,
+            miners_by_category,
+            distribution,
+        } = self.calculate_weights().await?;
+
+        let mut gpu_counts = HashMap::new();
+        let mut hotkeys = HashMap::new();
+        for miners in miners_by_category.values() {
+            for (miner_uid, _) in miners {
+                let uid = miner_uid.as_u16();
+                if gpu_counts.contains_key(&uid) {
+                    continue;
+                }
+
+                let counts = match self.gpu_profile_repo.get_gpu_profile(*miner_uid).await? {
+                    Some(profile) => self.gpu_scoring_engine.rewardable_gpu_counts(&profile),
+                    None => HashMap::new(),
+                };
+                gpu_counts.insert(uid, counts);
+
+                if let Some(hotkey) = metagraph.hotkeys.get(uid as usize) {
+                    hotkeys.insert(uid, hotkey.to_string());
+                }
+            }
+        }
+
+        WeightExplanation::build(
+            &self.weight_allocation_engine,
+            self.config.netuid,
+            &miners_by_category,
+            &distribution,
+            &gpu_counts,
+            &hotkeys,
+        )
+    }
+
     /// Build normalized weights from weight distribution
     fn build_normalized_weights(
         &self,
@@ -487,6 +576,135 @@ impl WeightSetter {
         Ok(())
     }
 
+    /// Submit weights using the configured submission mode
+    async fn submit_weights(
+        &self,
+        normalized_weights: Vec<NormalizedWeight>,
+        version_key: u64,
+    ) -> Result<()> {
+        match self.emission_config.weight_submission {
+            WeightSubmissionMode::Direct => {
+                self.submit_weights_to_chain_with_retry(normalized_weights, version_key)
+                    .await
+            }
+            WeightSubmissionMode::CommitReveal => {
+                self.commit_weights(normalized_weights, version_key).await
+            }
+        }
+    }
+
+    /// Commit a hash of the weights and record the commit for its reveal
+    async fn commit_weights(
+        &self,
+        normalized_weights: Vec<NormalizedWeight>,
+        version_key: u64,
+    ) -> Result<()> {
+        self.validate_weights_before_submission(&normalized_weights)?;
+
+        let netuid = self.config.netuid;
+        let params = self
+            .bittensor_service
+            .get_commit_reveal_params(netuid)
+            .await?;
+
+        if !params.enabled {
+            warn!(
+                netuid = netuid,
+                "Commit-reveal is disabled on the subnet, setting weights directly"
+            );
+            return self
+                .submit_weights_to_chain_with_retry(normalized_weights, version_key)
+                .await;
+        }
+
+        let weights = normalized_weights
+            .iter()
+            .map(|w| (w.uid, w.weight))
+            .collect();
+        let commit = self
+            .bittensor_service
+            .commit_weights(netuid, weights, version_key)
+            .await?;
+
+        let (reveal_start_block, reveal_end_block) =
+            params.reveal_window(netuid, commit.commit_block);
+        self.weight_commits
+            .store_commit(&commit, reveal_start_block, reveal_end_block)
+            .await
+            .map_err(|e| {
+                error!(
+                    commit_hash = %commit.commit_hash,
+                    "Weights were committed but the commit could not be stored and will not be revealed: {}",
+                    e
+                );
+                e
+            })?;
+
+        info!(
+            netuid = netuid,
+            version_key = version_key,
+            commit_block = commit.commit_block,
+            reveal_start_block = reveal_start_block,
+            reveal_end_block = reveal_end_block,
+            commit_hash = %commit.commit_hash,
+            "Committed weights, reveal scheduled"
+        );
+
+        Ok(())
+    }
+
+    /// Reveal commits whose reveal window is open and expire those that missed it
+    async fn reveal_pending_commits(&self, current_block: u64) -> Result<()> {
+        for pending in self
+            .weight_commits
+            .get_pending_commits(self.config.netuid)
+            .await?
+        {
+            let commit_hash = &pending.commit.commit_hash;
+
+            if current_block > pending.reveal_end_block {
+                warn!(
+                    commit_hash = %commit_hash,
+                    reveal_end_block = pending.reveal_end_block,
+                    current_block = current_block,
+                    "Weight commit expired before it could be revealed"
+                );
+                self.weight_commits
+                    .update_status(commit_hash, WeightCommitStatus::Expired)
+                    .await?;
+                continue;
+            }
+
+            if current_block < pending.reveal_start_block {
+                continue;
+            }
+
+            match self.bittensor_service.reveal_weights(&pending.commit).await {
+                Ok(()) => {
+                    self.weight_commits
+                        .update_status(commit_hash, WeightCommitStatus::Revealed)
+                        .await?;
+                    info!(
+                        commit_hash = %commit_hash,
+                        current_block = current_block,
+                        "Revealed committed weights"
+                    );
+                }
+                Err(e) => {
+                    // Retried on the next block while the window is open
+                    warn!(
+                        commit_hash = %commit_hash,
+                        reveal_end_block = pending.reveal_end_block,
+                        "Failed to reveal committed weights: {}",
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Submit weights to chain with retry logic
     async fn submit_weights_to_chain_with_retry(
         &self,
@@ -974,11 +1192,15 @@ impl WeightSetter {
     }
 
     /// Extract validation result from verification log
+    ///
+    /// `gpu_memory_gb` is the memory of each of the executor's GPUs, which
+    /// tells variants such as the A100 40GB and 80GB apart.
     async fn extract_validation_result(
         &self,
         miner_id: &str,
         executor_id: ExecutorId,
         log: &VerificationLog,
+        gpu_memory_gb: u64,
     ) -> Result<ExecutorValidationResult> {
         // Parse hardware specs from the verification log details
         // Always try to parse specs, even for failed validations, to track GPU hardware
@@ -1012,9 +1234,6 @@ impl WeightSetter {
                     }
                 };
 
-                // GPU memory is not available in the stored data, default to 0
-                let gpu_memory = 0u64;
-
                 // Extract memory bandwidth from executor_result.memory_bandwidth_gbps
                 let bandwidth = specs["executor_result"]["memory_bandwidth_gbps"]
                     .as_f64()
@@ -1027,13 +1246,13 @@ impl WeightSetter {
                     executor_id, gpu_model, gpu_count, log.success
                 );
 
-                (score, gpu_count, gpu_memory, bandwidth, gpu_model)
+                (score, gpu_count, gpu_memory_gb, bandwidth, gpu_model)
             } else {
                 debug!(
                     "Executor {}: No hardware specs available, validation_success: {}",
                     executor_id, log.success
                 );
-                (0.0, 0, 0, 0.0, "UNKNOWN".to_string())
+                (0.0, 0, gpu_memory_gb, 0.0, "UNKNOWN".to_string())
             };
 
         Ok(ExecutorValidationResult {
@@ -1114,7 +1333,7 @@ impl WeightSetter {
         // Only match executor_ids that belong to this specific miner
         // New format: "miner{uid}__{original_executor_id}" prevents cross-miner matches
         let query = r#"
-            SELECT vl.*, me.miner_id
+            SELECT vl.*, me.miner_id, me.gpu_specs
             FROM verification_logs vl
             JOIN miner_executors me ON vl.executor_id = me.executor_id
             WHERE me.miner_id = ? AND vl.timestamp >= ?
@@ -1171,8 +1390,13 @@ impl WeightSetter {
                     .with_timezone(&Utc),
             };
 
+            let gpu_specs: String = row.get("gpu_specs");
+            let gpu_memory_gb = serde_json::from_str(&gpu_specs)
+                .map(|specs| per_gpu_memory_gb(&specs))
+                .unwrap_or(0);
+
             match self
-                .extract_validation_result(&miner_id, executor_id.clone(), &log)
+                .extract_validation_result(&miner_id, executor_id.clone(), &log, gpu_memory_gb)
                 .await
             {
                 Ok(validation) => {
@@ -1253,12 +1477,50 @@ impl WeightSetter {
     }
 }
 
+/// Memory of a single GPU in GB from an executor's registered GPU specs
+///
+/// Executors report one spec per GPU; the largest is used should they differ.
+fn per_gpu_memory_gb(gpu_specs: &serde_json::Value) -> u64 {
+    gpu_specs
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|gpu| gpu["memory_gb"].as_u64())
+        .max()
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::gpu::GpuCategorizer;
     use crate::persistence::entities::VerificationLog;
     use serde_json::json;
 
+    #[test]
+    fn test_gpu_memory_from_executor_specs() {
+        let specs = json!([
+            { "name": "NVIDIA A100-SXM4", "memory_gb": 80, "compute_capability": "8.0" },
+            { "name": "NVIDIA A100-SXM4", "memory_gb": 80, "compute_capability": "8.0" }
+        ]);
+        let memory_gb = per_gpu_memory_gb(&specs);
+        assert_eq!(memory_gb, 80);
+
+        // Without the memory every A100 would be categorized as the 40GB model
+        let categorizer = GpuCategorizer::default();
+        assert_eq!(
+            categorizer.normalize_gpu_model_with_memory("NVIDIA A100-SXM4", memory_gb),
+            "A100_80GB"
+        );
+        assert_eq!(
+            categorizer.normalize_gpu_model_with_memory("NVIDIA A100-SXM4", 0),
+            "A100_40GB"
+        );
+
+        assert_eq!(per_gpu_memory_gb(&json!([])), 0);
+        assert_eq!(per_gpu_memory_gb(&json!({ "memory_gb": 80 })), 0);
+    }
+
     #[test]
     fn test_extract_validation_result_with_h100() {
         // Create a verification log with H100 GPU
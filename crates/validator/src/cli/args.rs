@@ -1,5 +1,5 @@
 use crate::cli::{
-    handlers::{database, service},
+    handlers::{database, service, weights},
     Command,
 };
 use clap::Parser;
@@ -52,6 +52,7 @@ impl Args {
             }
 
             Command::Database { action } => database::handle_database(action).await,
+            Command::Weights { action } => weights::handle_weights(action, self.config).await,
         }
     }
 }
@@ -112,6 +112,12 @@ pub enum Command {
         #[command(subcommand)]
         action: DatabaseAction,
     },
+
+    /// Inspect weight setting
+    Weights {
+        #[command(subcommand)]
+        action: WeightsAction,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -130,3 +136,22 @@ pub enum DatabaseAction {
         days: u32,
     },
 }
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum WeightsAction {
+    /// Run the weight setting pipeline without submitting and show a per-miner breakdown
+    Explain {
+        /// Output format
+        #[arg(short = 'o', long, value_enum, default_value = "table")]
+        output: OutputFormat,
+    },
+}
+
+/// Output format for inspection commands
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable table format
+    Table,
+    /// JSON format
+    Json,
+}
@@ -4,6 +4,7 @@ use common::config::ConfigValidation;
 
 pub mod database;
 pub mod service;
+pub mod weights;
 
 pub struct HandlerUtils;
 
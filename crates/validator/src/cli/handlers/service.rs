@@ -293,7 +293,7 @@ async fn start_validator_services(
         HandlerUtils::print_info("Running in local test mode - Bittensor services disabled");
     }
 
-    let (_bittensor_service, miner_prover_opt, weight_setter_opt) = if !local_test {
+    let (bittensor_service_opt, miner_prover_opt, weight_setter_opt) = if !local_test {
         let bittensor_service: Arc<BittensorService> =
             Arc::new(BittensorService::new(config.bittensor.common.clone()).await?);
 
@@ -327,28 +327,11 @@ async fn start_validator_services(
             validator_metrics.as_ref().map(|m| Arc::new(m.clone())),
         )?);
 
-        // Initialize weight setter with block-based timing from emission config
-        let blocks_per_weight_set = config.emission.weight_set_interval_blocks;
-
-        // Create GPU scoring engine using the existing gpu_profile_repo
-        let gpu_scoring_engine = if let Some(ref metrics) = validator_metrics {
-            Arc::new(crate::gpu::GpuScoringEngine::with_metrics(
-                gpu_profile_repo.clone(),
-                Arc::new(metrics.clone()),
-            ))
-        } else {
-            Arc::new(crate::gpu::GpuScoringEngine::new(gpu_profile_repo.clone()))
-        };
-
-        let weight_setter = crate::bittensor_core::WeightSetter::with_metrics(
-            config.bittensor.common.clone(),
+        let weight_setter = build_weight_setter(
+            &config,
             bittensor_service.clone(),
             storage.clone(),
             persistence_arc.clone(),
-            config.verification.min_score_threshold,
-            blocks_per_weight_set,
-            gpu_scoring_engine,
-            config.emission.clone(),
             gpu_profile_repo.clone(),
             validator_metrics.as_ref().map(|m| Arc::new(m.clone())),
         )?;
@@ -361,8 +344,38 @@ async fn start_validator_services(
         (None, None, None)
     };
 
-    let api_handler =
-        crate::api::ApiHandler::new(config.api.clone(), persistence_arc.clone(), storage.clone());
+    // Rental containers are provisioned through miners, which requires signing as the validator
+    let rental_supervisor = match &bittensor_service_opt {
+        Some(bittensor_service) => {
+            let provisioner = crate::rental::RentalProvisioner::new(
+                &config.verification,
+                bittensor_service.clone(),
+                persistence_arc.clone(),
+            )?;
+            Some(Arc::new(crate::rental::RentalSupervisor::new(
+                config.rental.clone(),
+                persistence_arc.clone(),
+                Arc::new(provisioner),
+            )))
+        }
+        None => None,
+    };
+
+    let api_handler = crate::api::ApiHandler::new(
+        config.api.clone(),
+        persistence_arc.clone(),
+        storage.clone(),
+        config.slashing.clone(),
+        rental_supervisor.clone(),
+        weight_setter_opt.clone(),
+    );
+
+    // Evidence bundles are signed off with the validator hotkey when it is known
+    let validator_hotkey = bittensor_service_opt
+        .as_ref()
+        .and_then(|service| bittensor::account_id_to_hotkey(service.get_account_id()).ok())
+        .map(|hotkey| hotkey.to_string())
+        .unwrap_or_else(|| config.bittensor.common.hotkey_name.clone());
 
     // Store metrics for cleanup (if needed)
     let _validator_metrics = validator_metrics;
@@ -423,6 +436,35 @@ async fn start_validator_services(
         None
     };
 
+    // Start slashing engine if enabled
+    let slashing_task_handle = if config.slashing.enabled {
+        let slashing_engine = crate::slashing::SlashingEngine::new(
+            config.slashing.clone(),
+            persistence_arc.clone(),
+            config
+                .collateral
+                .clone()
+                .map(collateral::CollateralClient::new),
+            validator_hotkey,
+        );
+
+        Some(tokio::spawn(async move {
+            if let Err(e) = slashing_engine.start().await {
+                error!("Slashing engine task failed: {}", e);
+            }
+        }))
+    } else {
+        info!("Slashing engine is disabled");
+        None
+    };
+
+    // Expire, health-check and bill rentals
+    let rental_supervisor_handle = rental_supervisor.map(|supervisor| {
+        tokio::spawn(async move {
+            supervisor.start().await;
+        })
+    });
+
     HandlerUtils::print_success("Validator started successfully - all services running");
 
     signal::ctrl_c().await?;
@@ -440,6 +482,12 @@ async fn start_validator_services(
     if let Some(handle) = cleanup_task_handle {
         handle.abort();
     }
+    if let Some(handle) = slashing_task_handle {
+        handle.abort();
+    }
+    if let Some(handle) = rental_supervisor_handle {
+        handle.abort();
+    }
     api_handler_handle.abort();
 
     // SQLite connections will be closed automatically when dropped
@@ -448,6 +496,66 @@ async fn start_validator_services(
     Ok(())
 }
 
+/// Build the weight setter, with collateral-aware scoring when configured
+pub(crate) fn build_weight_setter(
+    config: &crate::config::ValidatorConfig,
+    bittensor_service: Arc<BittensorService>,
+    storage: common::MemoryStorage,
+    persistence: Arc<crate::persistence::SimplePersistence>,
+    gpu_profile_repo: Arc<crate::persistence::gpu_profile_repository::GpuProfileRepository>,
+    validator_metrics: Option<Arc<crate::metrics::ValidatorMetrics>>,
+) -> Result<crate::bittensor_core::WeightSetter> {
+    // Categorize with the configured GPU models everywhere scores are computed
+    let categorizer = config.emission.gpu_categorizer();
+
+    // Create GPU scoring engine using the existing gpu_profile_repo
+    let gpu_scoring_engine = match &validator_metrics {
+        Some(metrics) => Arc::new(crate::gpu::GpuScoringEngine::with_metrics(
+            gpu_profile_repo.clone(),
+            categorizer.clone(),
+            metrics.clone(),
+        )),
+        None => Arc::new(crate::gpu::GpuScoringEngine::new(
+            gpu_profile_repo.clone(),
+            categorizer.clone(),
+        )),
+    };
+
+    // Weights are set on block-based timing from the emission config
+    let weight_setter = crate::bittensor_core::WeightSetter::with_metrics(
+        config.bittensor.common.clone(),
+        bittensor_service,
+        storage,
+        persistence.clone(),
+        config.verification.min_score_threshold,
+        config.emission.weight_set_interval_blocks,
+        gpu_scoring_engine,
+        config.emission.clone(),
+        gpu_profile_repo,
+        validator_metrics,
+    )?;
+
+    let weight_setter = match (&config.collateral, config.emission.collateral.enabled) {
+        (Some(collateral_config), true) => {
+            info!(
+                "Collateral-aware scoring enabled against contract {}",
+                collateral_config.contract_address
+            );
+            weight_setter.with_collateral_scorer(Arc::new(
+                crate::bittensor_core::collateral_scoring::CollateralScorer::new(
+                    config.emission.collateral.clone(),
+                    collateral::CollateralClient::new(collateral_config.clone()),
+                    persistence,
+                    categorizer,
+                ),
+            ))
+        }
+        _ => weight_setter,
+    };
+
+    Ok(weight_setter)
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Signal {
     Term,
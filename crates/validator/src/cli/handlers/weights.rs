@@ -0,0 +1,65 @@
+use super::HandlerUtils;
+use crate::cli::commands::{OutputFormat, WeightsAction};
+use anyhow::Result;
+use bittensor::Service as BittensorService;
+use common::config::ConfigValidation;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub async fn handle_weights(action: WeightsAction, config_path: Option<PathBuf>) -> Result<()> {
+    match action {
+        WeightsAction::Explain { output } => explain_weights(config_path, output).await,
+    }
+}
+
+/// Compute weights the way the running validator would, without submitting them
+async fn explain_weights(config_path: Option<PathBuf>, output: OutputFormat) -> Result<()> {
+    let config = HandlerUtils::load_config(config_path.as_ref().and_then(|p| p.to_str()))?;
+    // Warnings are not printed so that JSON output stays parseable
+    config
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Configuration validation failed: {}", e))?;
+
+    // Read the state the validator keeps, so epoch filtering matches its next submission
+    let storage_path =
+        std::path::PathBuf::from(&config.storage.data_dir).join("validator_storage.json");
+    let storage = common::MemoryStorage::with_file(storage_path).await?;
+
+    let db_path = config
+        .database
+        .url
+        .strip_prefix("sqlite:")
+        .unwrap_or(&config.database.url);
+    let persistence = Arc::new(
+        crate::persistence::SimplePersistence::new(
+            db_path,
+            config.bittensor.common.hotkey_name.clone(),
+        )
+        .await?,
+    );
+    let gpu_profile_repo = Arc::new(
+        crate::persistence::gpu_profile_repository::GpuProfileRepository::new(
+            persistence.pool().clone(),
+        ),
+    );
+
+    let bittensor_service = Arc::new(BittensorService::new(config.bittensor.common.clone()).await?);
+
+    let weight_setter = super::service::build_weight_setter(
+        &config,
+        bittensor_service,
+        storage,
+        persistence,
+        gpu_profile_repo,
+        None,
+    )?;
+
+    let explanation = weight_setter.explain_weights().await?;
+
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&explanation)?),
+        OutputFormat::Table => print!("{}", explanation.to_table()),
+    }
+
+    Ok(())
+}
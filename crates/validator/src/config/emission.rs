@@ -1,3 +1,4 @@
+use crate::gpu::{GpuCategorizer, GpuModelRegistry};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -15,14 +16,91 @@ pub struct EmissionConfig {
     pub burn_uid: u16,
 
     /// GPU model allocation percentages (must sum to 100.0)
+    /// Keys must be categories of `gpu_models`
     pub gpu_allocations: HashMap<String, f64>,
 
+    /// Rules mapping reported GPU names to allocation categories
+    #[serde(default)]
+    pub gpu_models: GpuModelRegistry,
+
     /// Blocks between weight setting
     pub weight_set_interval_blocks: u64,
 
     /// Version key for weight setting operations
     /// This prevents replay attacks by incrementing with each weight set
     pub weight_version_key: u64,
+
+    /// Whether weights are set directly or committed and revealed later
+    #[serde(default)]
+    pub weight_submission: WeightSubmissionMode,
+
+    /// Optional collateral requirements applied to miner scores
+    #[serde(default)]
+    pub collateral: CollateralEmissionConfig,
+}
+
+/// How weights are submitted to the chain
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightSubmissionMode {
+    /// Weights are set in a single set_weights extrinsic
+    #[default]
+    Direct,
+
+    /// A hash of the weights is committed and the weights are revealed in
+    /// the subnet's reveal window
+    CommitReveal,
+}
+
+/// How executors below the minimum collateral are treated
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CollateralScoringMode {
+    /// Executors below the minimum contribute nothing
+    #[default]
+    Gate,
+
+    /// Executor contribution is scaled by posted / required collateral, capped at 1.0
+    Scale,
+}
+
+/// Collateral-aware emission rule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CollateralEmissionConfig {
+    /// Whether on-chain collateral affects emissions
+    pub enabled: bool,
+
+    /// How executors below the minimum are treated
+    pub mode: CollateralScoringMode,
+
+    /// Minimum collateral in TAO per GPU, by GPU category
+    /// Categories without an entry are not affected
+    pub min_collateral_per_gpu: HashMap<String, f64>,
+
+    /// How long on-chain collateral lookups are cached, in seconds
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for CollateralEmissionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: CollateralScoringMode::Gate,
+            min_collateral_per_gpu: HashMap::new(),
+            cache_ttl_secs: 600,
+        }
+    }
+}
+
+impl CollateralEmissionConfig {
+    /// Minimum collateral in TAO required per GPU for a category, if any
+    pub fn min_collateral_for(&self, category: &str) -> Option<f64> {
+        self.min_collateral_per_gpu
+            .get(category)
+            .copied()
+            .filter(|min| *min > 0.0)
+    }
 }
 
 impl EmissionConfig {
@@ -57,7 +135,9 @@ impl EmissionConfig {
             ));
         }
 
-        // Validate individual allocations are positive
+        self.gpu_models.validate()?;
+
+        // Validate individual allocations are positive and for known categories
         for (gpu_model, allocation) in &self.gpu_allocations {
             if *allocation < 0.0 {
                 return Err(anyhow!(
@@ -66,6 +146,36 @@ impl EmissionConfig {
                     allocation
                 ));
             }
+
+            if !self.gpu_models.is_known_category(gpu_model) {
+                return Err(anyhow!(
+                    "GPU allocation configured for unknown GPU category: {} (known: {})",
+                    gpu_model,
+                    self.gpu_models
+                        .categories()
+                        .into_iter()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+
+        // Validate collateral requirements
+        for (gpu_model, min_collateral) in &self.collateral.min_collateral_per_gpu {
+            if *min_collateral < 0.0 || !min_collateral.is_finite() {
+                return Err(anyhow!(
+                    "Minimum collateral for {} must be a non-negative number, got: {}",
+                    gpu_model,
+                    min_collateral
+                ));
+            }
+
+            if !self.gpu_allocations.contains_key(gpu_model) {
+                return Err(anyhow!(
+                    "Minimum collateral configured for unknown GPU category: {}",
+                    gpu_model
+                ));
+            }
         }
 
         Ok(())
@@ -112,6 +222,9 @@ impl EmissionConfig {
             gpu_allocations,
             weight_set_interval_blocks: 360,
             weight_version_key: 0,
+            weight_submission: WeightSubmissionMode::Direct,
+            gpu_models: GpuModelRegistry::default(),
+            collateral: CollateralEmissionConfig::default(),
         }
     }
 
@@ -160,6 +273,11 @@ impl EmissionConfig {
         models.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         models
     }
+
+    /// Categorizer using the configured GPU models
+    pub fn gpu_categorizer(&self) -> GpuCategorizer {
+        GpuCategorizer::new(self.gpu_models.clone())
+    }
 }
 
 impl Default for EmissionConfig {
@@ -174,6 +292,9 @@ impl Default for EmissionConfig {
             gpu_allocations,
             weight_set_interval_blocks: 360,
             weight_version_key: 0,
+            weight_submission: WeightSubmissionMode::Direct,
+            gpu_models: GpuModelRegistry::default(),
+            collateral: CollateralEmissionConfig::default(),
         }
     }
 }
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::config::emission::{EmissionConfig, DEFAULT_BURN_UID};
+    use crate::config::emission::{EmissionConfig, WeightSubmissionMode, DEFAULT_BURN_UID};
     use std::collections::HashMap;
     use std::io::Write;
     use std::path::Path;
@@ -151,7 +151,7 @@ weight_version_key = 0
 [gpu_allocations]
 H100 = 25.0
 H200 = 50.0
-A100 = 25.0
+A100_80GB = 25.0
 "#;
 
         let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
@@ -168,7 +168,8 @@ A100 = 25.0
         assert_eq!(config.gpu_allocations.len(), 3);
         assert_eq!(config.gpu_allocations.get("H100"), Some(&25.0));
         assert_eq!(config.gpu_allocations.get("H200"), Some(&50.0));
-        assert_eq!(config.gpu_allocations.get("A100"), Some(&25.0));
+        assert_eq!(config.gpu_allocations.get("A100_80GB"), Some(&25.0));
+        assert_eq!(config.weight_submission, WeightSubmissionMode::Direct);
 
         // Test loading from invalid TOML file (allocations don't sum to 100)
         let invalid_toml = r#"
@@ -203,6 +204,9 @@ H200 = 30.0
             gpu_allocations: HashMap::new(), // Empty - should use default
             weight_set_interval_blocks: 0,   // Invalid - should use default
             weight_version_key: 0,
+            weight_submission: WeightSubmissionMode::Direct,
+            gpu_models: Default::default(),
+            collateral: Default::default(),
         };
 
         let merged = partial_config.merge_with_defaults();
@@ -229,19 +233,19 @@ H200 = 30.0
         };
         assert!(config.validate().is_ok());
 
-        // Test unicode in GPU model names
+        // Test unicode in GPU model names, which are not registry categories
         let mut allocations = HashMap::new();
         allocations.insert("H100-新".to_string(), 50.0);
         allocations.insert("H200-α".to_string(), 50.0);
         config.gpu_allocations = allocations;
-        assert!(config.validate().is_ok());
+        assert!(config.validate().is_err());
 
         // Test very long GPU model names
         let mut allocations = HashMap::new();
         let long_name = "A".repeat(1000);
         allocations.insert(long_name, 100.0);
         config.gpu_allocations = allocations;
-        assert!(config.validate().is_ok());
+        assert!(config.validate().is_err());
 
         // Test very small positive allocations
         let mut allocations = HashMap::new();
@@ -330,7 +334,7 @@ H200 = 30.0
         let mut allocations = HashMap::new();
         allocations.insert("H100".to_string(), 33.333333);
         allocations.insert("H200".to_string(), 33.333333);
-        allocations.insert("A100".to_string(), 33.333334);
+        allocations.insert("A100_80GB".to_string(), 33.333334);
 
         let config = EmissionConfig {
             burn_percentage: 0.0,
@@ -338,6 +342,9 @@ H200 = 30.0
             gpu_allocations: allocations,
             weight_set_interval_blocks: 360,
             weight_version_key: 0,
+            weight_submission: WeightSubmissionMode::Direct,
+            gpu_models: Default::default(),
+            collateral: Default::default(),
         };
 
         // Should be valid because total is very close to 100.0
@@ -347,7 +354,7 @@ H200 = 30.0
         let mut allocations = HashMap::new();
         allocations.insert("H100".to_string(), 33.0);
         allocations.insert("H200".to_string(), 33.0);
-        allocations.insert("A100".to_string(), 33.0);
+        allocations.insert("A100_80GB".to_string(), 33.0);
 
         let config = EmissionConfig {
             burn_percentage: 0.0,
@@ -355,9 +362,133 @@ H200 = 30.0
             gpu_allocations: allocations,
             weight_set_interval_blocks: 360,
             weight_version_key: 0,
+            weight_submission: WeightSubmissionMode::Direct,
+            gpu_models: Default::default(),
+            collateral: Default::default(),
         };
 
         // Should be invalid because total is 99.0 (difference > 0.01)
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_gpu_allocations_require_registry_category() {
+        let toml_content = r#"
+burn_percentage = 0.0
+burn_uid = 0
+weight_set_interval_blocks = 360
+weight_version_key = 0
+
+[gpu_allocations]
+H100 = 50.0
+MI300X = 50.0
+
+[[gpu_models]]
+category = "H100"
+patterns = ["H100"]
+
+[[gpu_models]]
+category = "MI300X"
+patterns = ["MI300X"]
+"#;
+
+        let mut config: EmissionConfig = toml::from_str(toml_content).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config
+                .gpu_categorizer()
+                .normalize_gpu_model("AMD Instinct MI300X"),
+            "MI300X"
+        );
+
+        // H200 is a built-in category but not part of this registry
+        config.gpu_allocations.remove("MI300X");
+        config.gpu_allocations.insert("H200".to_string(), 50.0);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("unknown GPU category: H200"), "{err}");
+
+        // Without a [[gpu_models]] section the built-in registry applies
+        let mut config = EmissionConfig::default();
+        config.gpu_allocations.insert("L40S".to_string(), 0.0);
+        assert!(config.validate().is_ok());
+        config.gpu_allocations.insert("OTHER".to_string(), 0.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_collateral_requirements_validation() {
+        let mut config = EmissionConfig::default();
+        config.collateral.enabled = true;
+        config
+            .collateral
+            .min_collateral_per_gpu
+            .insert("H100".to_string(), 10.0);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.collateral.min_collateral_for("H100"), Some(10.0));
+        assert_eq!(config.collateral.min_collateral_for("H200"), None);
+
+        config
+            .collateral
+            .min_collateral_per_gpu
+            .insert("H100".to_string(), -1.0);
+        assert!(config.validate().is_err());
+
+        config.collateral.min_collateral_per_gpu.clear();
+        config
+            .collateral
+            .min_collateral_per_gpu
+            .insert("A100".to_string(), 5.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_collateral_config_from_toml() {
+        let toml_content = r#"
+burn_percentage = 10.0
+burn_uid = 0
+weight_set_interval_blocks = 360
+weight_version_key = 0
+
+[gpu_allocations]
+H100 = 40.0
+H200 = 60.0
+
+[collateral]
+enabled = true
+mode = "scale"
+
+[collateral.min_collateral_per_gpu]
+H100 = 2.5
+"#;
+
+        let config: EmissionConfig = toml::from_str(toml_content).unwrap();
+        assert!(config.collateral.enabled);
+        assert_eq!(
+            config.collateral.mode,
+            crate::config::emission::CollateralScoringMode::Scale
+        );
+        assert_eq!(config.collateral.min_collateral_for("H100"), Some(2.5));
+        assert_eq!(config.collateral.cache_ttl_secs, 600);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_commit_reveal_submission_from_toml() {
+        let toml_content = r#"
+burn_percentage = 10.0
+burn_uid = 0
+weight_set_interval_blocks = 360
+weight_version_key = 3
+weight_submission = "commit_reveal"
+
+[gpu_allocations]
+H100 = 40.0
+H200 = 60.0
+"#;
+
+        let config: EmissionConfig = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.weight_submission, WeightSubmissionMode::CommitReveal);
+        assert_eq!(config.weight_version_key, 3);
+        assert!(config.validate().is_ok());
+    }
 }
@@ -11,7 +11,9 @@ use common::config::{
     loader, BittensorConfig, ConfigValidation, DatabaseConfig, LoggingConfig, MetricsConfig,
     ServerConfig,
 };
+use common::crypto::request_signing::parse_ed25519_signer;
 use common::error::ConfigurationError;
+use common::identity::Hotkey;
 
 /// Enhanced validator Bittensor configuration with advertised address support
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +76,18 @@ pub struct ValidatorConfig {
     /// Database cleanup configuration
     #[serde(default)]
     pub cleanup: crate::persistence::cleanup_task::CleanupConfig,
+
+    /// Collateral contract connection, required for collateral-aware scoring
+    #[serde(default)]
+    pub collateral: Option<collateral::CollateralConfig>,
+
+    /// Automatic slashing configuration
+    #[serde(default)]
+    pub slashing: crate::slashing::SlashingConfig,
+
+    /// Rental lifecycle configuration
+    #[serde(default)]
+    pub rental: crate::rental::RentalConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +124,9 @@ pub struct VerificationConfig {
     /// Binary validation configuration
     #[serde(default)]
     pub binary_validation: BinaryValidationConfig,
+    /// Signed executor evidence collection
+    #[serde(default)]
+    pub evidence: EvidenceConfig,
 }
 
 fn default_use_dynamic_discovery() -> bool {
@@ -158,6 +175,32 @@ impl Default for BinaryValidationConfig {
     }
 }
 
+/// Configuration for collecting signed system profiles and benchmarks from executors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvidenceConfig {
+    /// Request signed evidence during verification
+    pub enabled: bool,
+    /// Fail verification when evidence cannot be collected. Evidence that
+    /// does not verify fails verification either way
+    pub required: bool,
+    /// Benchmark types to run after the system profile
+    pub benchmarks: Vec<String>,
+    /// Duration of each benchmark in seconds
+    pub benchmark_duration_secs: u64,
+}
+
+impl Default for EvidenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            required: false,
+            benchmarks: vec!["cpu".to_string(), "memory".to_string()],
+            benchmark_duration_secs: 5,
+        }
+    }
+}
+
 /// Configuration for automatic verification during discovery
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AutomaticVerificationConfig {
@@ -258,12 +301,43 @@ pub struct StorageConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiConfig {
-    /// API key for external authentication
+    /// API key for operator actions, which are refused without one
     pub api_key: Option<String>,
     /// Maximum request body size in bytes
     pub max_body_size: usize,
     /// Bind address for the API server
     pub bind_address: String,
+    /// Signature checks on rental and capacity requests
+    #[serde(default)]
+    pub request_auth: RequestAuthConfig,
+}
+
+/// Configuration for authenticating signed requests from the public API gateway
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestAuthConfig {
+    /// Require valid signatures on rental and capacity requests
+    pub enabled: bool,
+    /// SS58 hotkeys allowed to sign requests with sr25519
+    pub allowed_hotkeys: Vec<String>,
+    /// Hex-encoded public keys allowed to sign requests with ed25519
+    pub allowed_ed25519_keys: Vec<String>,
+    /// Maximum age of a signed request in seconds
+    pub max_request_age_secs: u64,
+    /// Tolerated clock skew for requests signed in the future, in seconds
+    pub max_clock_skew_secs: u64,
+}
+
+impl Default for RequestAuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allowed_hotkeys: Vec::new(),
+            allowed_ed25519_keys: Vec::new(),
+            max_request_age_secs: 300,
+            max_clock_skew_secs: 60,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -387,6 +461,7 @@ impl Default for ValidatorConfig {
                 cache_miner_info_ttl: default_cache_miner_info_ttl(),
                 grpc_port_offset: None,
                 binary_validation: BinaryValidationConfig::default(),
+                evidence: EvidenceConfig::default(),
             },
             automatic_verification: AutomaticVerificationConfig::default(),
             storage: StorageConfig {
@@ -396,10 +471,14 @@ impl Default for ValidatorConfig {
                 api_key: None,
                 max_body_size: 1024 * 1024, // 1MB
                 bind_address: "0.0.0.0:8080".to_string(),
+                request_auth: RequestAuthConfig::default(),
             },
             ssh_session: SshSessionConfig::default(),
             emission: super::emission::EmissionConfig::default(),
             cleanup: crate::persistence::cleanup_task::CleanupConfig::default(),
+            collateral: None,
+            slashing: crate::slashing::SlashingConfig::default(),
+            rental: crate::rental::RentalConfig::default(),
         }
     }
 }
@@ -445,6 +524,26 @@ impl ConfigValidation for ValidatorConfig {
             });
         }
 
+        for hotkey in &self.api.request_auth.allowed_hotkeys {
+            if let Err(reason) = Hotkey::new(hotkey.clone()) {
+                return Err(ConfigurationError::InvalidValue {
+                    key: "api.request_auth.allowed_hotkeys".to_string(),
+                    value: hotkey.clone(),
+                    reason,
+                });
+            }
+        }
+
+        for key in &self.api.request_auth.allowed_ed25519_keys {
+            if let Err(e) = parse_ed25519_signer(key) {
+                return Err(ConfigurationError::InvalidValue {
+                    key: "api.request_auth.allowed_ed25519_keys".to_string(),
+                    value: key.clone(),
+                    reason: e.to_string(),
+                });
+            }
+        }
+
         // Validate advertised axon configuration
         if let Err(msg) = self.bittensor.validate_advertised_axon() {
             return Err(ConfigurationError::InvalidValue {
@@ -463,6 +562,33 @@ impl ConfigValidation for ValidatorConfig {
             });
         }
 
+        if self.emission.collateral.enabled && self.collateral.is_none() {
+            return Err(ConfigurationError::InvalidValue {
+                key: "emission.collateral.enabled".to_string(),
+                value: "true".to_string(),
+                reason: "Collateral-aware scoring requires a [collateral] section".to_string(),
+            });
+        }
+
+        if self.slashing.enabled && self.slashing.mode != crate::slashing::SlashingMode::DryRun {
+            if self.slashing.evidence_base_url.is_empty() {
+                return Err(ConfigurationError::InvalidValue {
+                    key: "slashing.evidence_base_url".to_string(),
+                    value: String::new(),
+                    reason: "Evidence must be publicly reachable before slashes are submitted"
+                        .to_string(),
+                });
+            }
+
+            if self.collateral.is_none() {
+                return Err(ConfigurationError::InvalidValue {
+                    key: "slashing.mode".to_string(),
+                    value: format!("{:?}", self.slashing.mode),
+                    reason: "Submitting slashes requires a [collateral] section".to_string(),
+                });
+            }
+        }
+
         Ok(())
     }
 
@@ -471,7 +597,18 @@ impl ConfigValidation for ValidatorConfig {
 
         if self.api.api_key.is_none() {
             warnings
-                .push("No API key configured - external API access will be disabled".to_string());
+                .push("No API key configured - operator API routes will be refused".to_string());
+        }
+
+        let request_auth = &self.api.request_auth;
+        if request_auth.enabled
+            && request_auth.allowed_hotkeys.is_empty()
+            && request_auth.allowed_ed25519_keys.is_empty()
+        {
+            warnings.push(
+                "Request authentication is enabled without allowed signers - rental and capacity requests will be rejected"
+                    .to_string(),
+            );
         }
 
         if self.verification.min_score_threshold < 0.1 {
@@ -1,6 +1,8 @@
+use super::registry::{GpuModelRegistry, OTHER_CATEGORY};
 use chrono::{DateTime, Utc};
 use common::identity::MinerUid;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct MinerGpuProfile {
@@ -13,64 +15,91 @@ pub struct MinerGpuProfile {
     pub last_successful_validation: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum GpuCategory {
-    H100,
-    H200,
-    Other(String),
+/// A GPU category from the model registry, or `OTHER`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GpuCategory(String);
+
+impl GpuCategory {
+    /// Category for GPUs that match no registry rule
+    pub fn other() -> Self {
+        Self(OTHER_CATEGORY.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_other(&self) -> bool {
+        self.0 == OTHER_CATEGORY
+    }
 }
 
-pub struct GpuCategorizer;
+impl std::fmt::Display for GpuCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Maps GPU model names to categories of a model registry
+#[derive(Debug, Clone, Default)]
+pub struct GpuCategorizer {
+    registry: Arc<GpuModelRegistry>,
+}
 
 impl GpuCategorizer {
-    /// Normalize GPU model string to standard category
-    pub fn normalize_gpu_model(gpu_model: &str) -> String {
-        let model = gpu_model.to_uppercase();
-
-        // Remove common prefixes and clean up
-        let cleaned = model
-            .replace("NVIDIA", "")
-            .replace("GEFORCE", "")
-            .replace("TESLA", "")
-            .trim()
-            .to_string();
-
-        // Match against known patterns - only H100 and H200 for now
-        if cleaned.contains("H100") {
-            "H100".to_string()
-        } else if cleaned.contains("H200") {
-            "H200".to_string()
-        } else {
-            "OTHER".to_string()
+    /// Categorize with `registry`, usually the configured `gpu_models`
+    pub fn new(registry: GpuModelRegistry) -> Self {
+        Self {
+            registry: Arc::new(registry),
         }
     }
 
-    /// Convert normalized model to category enum
-    pub fn model_to_category(model: &str) -> GpuCategory {
-        match model.to_uppercase().as_str() {
-            "H100" => GpuCategory::H100,
-            "H200" => GpuCategory::H200,
-            _ => GpuCategory::Other(model.to_string()),
+    /// Registry in use
+    pub fn registry(&self) -> &GpuModelRegistry {
+        &self.registry
+    }
+
+    /// Normalize GPU model string to standard category
+    pub fn normalize_gpu_model(&self, gpu_model: &str) -> String {
+        self.registry.categorize(gpu_model, None)
+    }
+
+    /// Normalize a GPU model using its memory size to tell variants apart
+    pub fn normalize_gpu_model_with_memory(&self, gpu_model: &str, memory_gb: u64) -> String {
+        self.registry.categorize(gpu_model, Some(memory_gb))
+    }
+
+    /// Convert normalized model to category
+    ///
+    /// Names that are not registry categories map to `OTHER`.
+    pub fn model_to_category(&self, model: &str) -> GpuCategory {
+        let upper = model.trim().to_uppercase();
+        if self.registry.is_known_category(&upper) {
+            GpuCategory(upper)
+        } else {
+            GpuCategory::other()
         }
     }
 
     /// Determine primary GPU model from validation results
     /// NOTE: This function is deprecated. Use gpu_counts directly for multi-category scoring.
     pub fn determine_primary_gpu_model(
+        &self,
         executor_validations: &[ExecutorValidationResult],
     ) -> String {
-        let gpu_counts = Self::calculate_gpu_distribution(executor_validations);
+        let gpu_counts = self.calculate_gpu_distribution(executor_validations);
 
         // Return the model with the highest count
         gpu_counts
             .into_iter()
             .max_by_key(|(_, count)| *count)
             .map(|(model, _)| model)
-            .unwrap_or_else(|| "OTHER".to_string())
+            .unwrap_or_else(|| OTHER_CATEGORY.to_string())
     }
 
     /// Calculate GPU model distribution for a miner
     pub fn calculate_gpu_distribution(
+        &self,
         executor_validations: &[ExecutorValidationResult],
     ) -> HashMap<String, u32> {
         let mut gpu_counts = HashMap::new();
@@ -83,7 +112,10 @@ impl GpuCategorizer {
         {
             // Only count each executor once
             if seen_executors.insert(&validation.executor_id) {
-                let normalized = Self::normalize_gpu_model(&validation.gpu_model);
+                let normalized = self.normalize_gpu_model_with_memory(
+                    &validation.gpu_model,
+                    validation.gpu_memory_gb,
+                );
                 *gpu_counts.entry(normalized).or_insert(0) += validation.gpu_count as u32;
             }
         }
@@ -98,9 +130,10 @@ impl MinerGpuProfile {
         miner_uid: MinerUid,
         executor_validations: &[ExecutorValidationResult],
         total_score: f64,
+        categorizer: &GpuCategorizer,
     ) -> Self {
-        let primary_gpu_model = GpuCategorizer::determine_primary_gpu_model(executor_validations);
-        let gpu_counts = GpuCategorizer::calculate_gpu_distribution(executor_validations);
+        let primary_gpu_model = categorizer.determine_primary_gpu_model(executor_validations);
+        let gpu_counts = categorizer.calculate_gpu_distribution(executor_validations);
         let verification_count = executor_validations.len() as u32;
 
         Self {
@@ -119,9 +152,10 @@ impl MinerGpuProfile {
         &mut self,
         executor_validations: &[ExecutorValidationResult],
         new_score: f64,
+        categorizer: &GpuCategorizer,
     ) {
-        self.primary_gpu_model = GpuCategorizer::determine_primary_gpu_model(executor_validations);
-        self.gpu_counts = GpuCategorizer::calculate_gpu_distribution(executor_validations);
+        self.primary_gpu_model = categorizer.determine_primary_gpu_model(executor_validations);
+        self.gpu_counts = categorizer.calculate_gpu_distribution(executor_validations);
         self.total_score = new_score;
         self.verification_count = executor_validations.len() as u32;
         self.last_updated = Utc::now();
@@ -6,98 +6,93 @@ mod tests {
 
     #[test]
     fn test_gpu_model_normalization() {
+        let categorizer = GpuCategorizer::default();
         // Test H100 variants
-        assert_eq!(
-            GpuCategorizer::normalize_gpu_model("NVIDIA H100 PCIe"),
-            "H100"
-        );
-        assert_eq!(GpuCategorizer::normalize_gpu_model("H100 SXM5"), "H100");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("h100"), "H100");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("Tesla H100"), "H100");
-        assert_eq!(
-            GpuCategorizer::normalize_gpu_model("NVIDIA H100-80GB"),
-            "H100"
-        );
+        assert_eq!(categorizer.normalize_gpu_model("NVIDIA H100 PCIe"), "H100");
+        assert_eq!(categorizer.normalize_gpu_model("H100 SXM5"), "H100");
+        assert_eq!(categorizer.normalize_gpu_model("h100"), "H100");
+        assert_eq!(categorizer.normalize_gpu_model("Tesla H100"), "H100");
+        assert_eq!(categorizer.normalize_gpu_model("NVIDIA H100-80GB"), "H100");
 
         // Test H200 variants
-        assert_eq!(GpuCategorizer::normalize_gpu_model("NVIDIA H200"), "H200");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("H200 SXM"), "H200");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("h200"), "H200");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("Tesla H200"), "H200");
-
-        // Test other GPU variants (should all return OTHER)
-        assert_eq!(GpuCategorizer::normalize_gpu_model("A100 80GB"), "OTHER");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("Tesla A100"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("NVIDIA H200"), "H200");
+        assert_eq!(categorizer.normalize_gpu_model("H200 SXM"), "H200");
+        assert_eq!(categorizer.normalize_gpu_model("h200"), "H200");
+        assert_eq!(categorizer.normalize_gpu_model("Tesla H200"), "H200");
+
+        // Test A100 variants, told apart by memory in the name
+        assert_eq!(categorizer.normalize_gpu_model("A100 80GB"), "A100_80GB");
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("NVIDIA A100-SXM4-40GB"),
-            "OTHER"
+            categorizer.normalize_gpu_model("NVIDIA A100-SXM4-40GB"),
+            "A100_40GB"
         );
-        assert_eq!(GpuCategorizer::normalize_gpu_model("a100"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("Tesla A100"), "A100_40GB");
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("GeForce RTX 4090"),
-            "OTHER"
+            categorizer.normalize_gpu_model_with_memory("a100", 80),
+            "A100_80GB"
         );
-        assert_eq!(GpuCategorizer::normalize_gpu_model("RTX 4090"), "OTHER");
+
+        // Test other registry categories
+        assert_eq!(categorizer.normalize_gpu_model("NVIDIA L40S"), "L40S");
+        assert_eq!(categorizer.normalize_gpu_model("NVIDIA B200"), "B200");
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("NVIDIA GeForce RTX 4090"),
-            "OTHER"
+            categorizer.normalize_gpu_model("GeForce RTX 4090"),
+            "RTX4090"
         );
-        assert_eq!(GpuCategorizer::normalize_gpu_model("rtx4090"), "OTHER");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("RTX 3090 Ti"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("RTX 4090"), "RTX4090");
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("GeForce RTX 3090"),
-            "OTHER"
+            categorizer.normalize_gpu_model("NVIDIA GeForce RTX 4090"),
+            "RTX4090"
         );
-        assert_eq!(GpuCategorizer::normalize_gpu_model("RTX 3080"), "OTHER");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("RTX 4080"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("rtx4090"), "RTX4090");
+
+        // Test GPUs outside the registry
+        assert_eq!(categorizer.normalize_gpu_model("RTX 3090 Ti"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("GeForce RTX 3090"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("RTX 3080"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("RTX 4080"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("NVIDIA L40"), "OTHER");
 
         // Test unknown models
-        assert_eq!(GpuCategorizer::normalize_gpu_model("Unknown GPU"), "OTHER");
-        assert_eq!(GpuCategorizer::normalize_gpu_model(""), "OTHER");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("V100"), "OTHER");
-        assert_eq!(GpuCategorizer::normalize_gpu_model("GTX 1080"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("Unknown GPU"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model(""), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("V100"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("GTX 1080"), "OTHER");
 
         // Test edge cases
-        assert_eq!(GpuCategorizer::normalize_gpu_model("   H100   "), "H100");
+        assert_eq!(categorizer.normalize_gpu_model("   H100   "), "H100");
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("NVIDIA NVIDIA H100"),
+            categorizer.normalize_gpu_model("NVIDIA NVIDIA H100"),
             "H100"
         );
     }
 
     #[test]
     fn test_model_to_category_conversion() {
-        // Test all known categories
-        assert_eq!(GpuCategorizer::model_to_category("H100"), GpuCategory::H100);
-        assert_eq!(GpuCategorizer::model_to_category("H200"), GpuCategory::H200);
-        // These should return Other now
-        match GpuCategorizer::model_to_category("A100") {
-            GpuCategory::Other(model) => assert_eq!(model, "A100"),
-            _ => panic!("Expected Other category"),
-        }
-        match GpuCategorizer::model_to_category("RTX4090") {
-            GpuCategory::Other(model) => assert_eq!(model, "RTX4090"),
-            _ => panic!("Expected Other category"),
-        }
+        let categorizer = GpuCategorizer::default();
+        // Test registry categories
+        assert_eq!(categorizer.model_to_category("H100").as_str(), "H100");
+        assert_eq!(categorizer.model_to_category("H200").as_str(), "H200");
+        assert_eq!(
+            categorizer.model_to_category("A100_80GB").as_str(),
+            "A100_80GB"
+        );
+        assert_eq!(categorizer.model_to_category("RTX4090").as_str(), "RTX4090");
 
         // Test case sensitivity
-        assert_eq!(GpuCategorizer::model_to_category("h100"), GpuCategory::H100);
-        assert_eq!(GpuCategorizer::model_to_category("h200"), GpuCategory::H200);
-
-        // Test unknown models
-        match GpuCategorizer::model_to_category("V100") {
-            GpuCategory::Other(model) => assert_eq!(model, "V100"),
-            _ => panic!("Expected Other category"),
-        }
-
-        match GpuCategorizer::model_to_category("GTX1080") {
-            GpuCategory::Other(model) => assert_eq!(model, "GTX1080"),
-            _ => panic!("Expected Other category"),
-        }
+        assert_eq!(categorizer.model_to_category("h100").as_str(), "H100");
+        assert_eq!(categorizer.model_to_category("l40s").as_str(), "L40S");
+
+        // Test names that are not categories
+        assert!(categorizer.model_to_category("A100").is_other());
+        assert!(categorizer.model_to_category("V100").is_other());
+        assert!(categorizer.model_to_category("GTX1080").is_other());
+        assert_eq!(GpuCategory::other().to_string(), "OTHER");
     }
 
     #[test]
     fn test_primary_gpu_determination() {
+        let categorizer = GpuCategorizer::default();
         // Test single GPU type
         let validations = vec![
             ExecutorValidationResult::new_for_testing(
@@ -116,7 +111,7 @@ mod tests {
             ),
         ];
 
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "H100");
 
         // Test multiple GPU types (should pick most common by count)
@@ -144,7 +139,7 @@ mod tests {
             ),
         ];
 
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "H200"); // 3 H200 vs 1 H100
 
         // Test tie scenarios - should return the first one found
@@ -165,13 +160,13 @@ mod tests {
             ),
         ];
 
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         // Should be either H100 or H200 (both have count 2)
         assert!(primary == "H100" || primary == "H200");
 
         // Test empty validation results
         let validations = vec![];
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "OTHER");
 
         // Test all invalid validations
@@ -192,12 +187,13 @@ mod tests {
             ),
         ];
 
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "OTHER");
     }
 
     #[test]
     fn test_gpu_distribution_calculation() {
+        let categorizer = GpuCategorizer::default();
         // Test single GPU model
         let validations = vec![
             ExecutorValidationResult::new_for_testing(
@@ -216,7 +212,7 @@ mod tests {
             ),
         ];
 
-        let distribution = GpuCategorizer::calculate_gpu_distribution(&validations);
+        let distribution = categorizer.calculate_gpu_distribution(&validations);
         assert_eq!(distribution.get("H100"), Some(&3));
         assert_eq!(distribution.len(), 1);
 
@@ -245,7 +241,7 @@ mod tests {
             ),
         ];
 
-        let distribution = GpuCategorizer::calculate_gpu_distribution(&validations);
+        let distribution = categorizer.calculate_gpu_distribution(&validations);
         assert_eq!(distribution.get("H100"), Some(&1));
         assert_eq!(distribution.get("H200"), Some(&2));
         assert_eq!(distribution.get("OTHER"), Some(&1));
@@ -276,7 +272,7 @@ mod tests {
             ),
         ];
 
-        let distribution = GpuCategorizer::calculate_gpu_distribution(&validations);
+        let distribution = categorizer.calculate_gpu_distribution(&validations);
         assert_eq!(distribution.get("H100"), Some(&1));
         assert_eq!(distribution.get("H200"), None);
         assert_eq!(distribution.get("OTHER"), None);
@@ -291,12 +287,13 @@ mod tests {
             true,
         )];
 
-        let distribution = GpuCategorizer::calculate_gpu_distribution(&validations);
+        let distribution = categorizer.calculate_gpu_distribution(&validations);
         assert_eq!(distribution.get("H100"), Some(&0));
     }
 
     #[test]
     fn test_miner_gpu_profile_creation() {
+        let categorizer = GpuCategorizer::default();
         let miner_uid = MinerUid::new(123);
         let validations = vec![
             ExecutorValidationResult::new_for_testing(
@@ -315,7 +312,7 @@ mod tests {
             ),
         ];
 
-        let profile = MinerGpuProfile::new(miner_uid, &validations, 0.85);
+        let profile = MinerGpuProfile::new(miner_uid, &validations, 0.85, &categorizer);
 
         assert_eq!(profile.miner_uid, miner_uid);
         assert_eq!(profile.primary_gpu_model, "H100"); // More GPUs
@@ -338,7 +335,7 @@ mod tests {
             true,
         )];
 
-        profile.update_with_validations(&new_validations, 0.92);
+        profile.update_with_validations(&new_validations, 0.92, &categorizer);
 
         assert_eq!(profile.primary_gpu_model, "H200");
         assert_eq!(profile.total_score, 0.92);
@@ -350,12 +347,13 @@ mod tests {
         // Test timestamp handling
         let old_timestamp = profile.last_updated;
         std::thread::sleep(std::time::Duration::from_millis(10));
-        profile.update_with_validations(&new_validations, 0.95);
+        profile.update_with_validations(&new_validations, 0.95, &categorizer);
         assert!(profile.last_updated > old_timestamp);
     }
 
     #[test]
     fn test_gpu_models_by_count() {
+        let categorizer = GpuCategorizer::default();
         let miner_uid = MinerUid::new(456);
         let validations = vec![
             ExecutorValidationResult::new_for_testing(
@@ -381,17 +379,19 @@ mod tests {
             ),
         ];
 
-        let profile = MinerGpuProfile::new(miner_uid, &validations, 0.8);
+        let profile = MinerGpuProfile::new(miner_uid, &validations, 0.8, &categorizer);
         let models_by_count = profile.gpu_models_by_count();
 
-        // Should be sorted by count descending
-        assert_eq!(models_by_count.len(), 2);
+        // Should be sorted by count descending, the A100 categorized by its reported 80GB
+        assert_eq!(models_by_count.len(), 3);
         assert_eq!(models_by_count[0], ("H100".to_string(), 4));
-        assert_eq!(models_by_count[1], ("OTHER".to_string(), 3)); // A100(1) + RTX4090(2) = OTHER(3)
+        assert_eq!(models_by_count[1], ("RTX4090".to_string(), 2));
+        assert_eq!(models_by_count[2], ("A100_80GB".to_string(), 1));
     }
 
     #[test]
     fn test_edge_cases() {
+        let categorizer = GpuCategorizer::default();
         // Test unicode GPU names
         let validations = vec![ExecutorValidationResult::new_for_testing(
             "exec1".to_string(),
@@ -400,7 +400,7 @@ mod tests {
             true,
             true,
         )];
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "H100");
 
         // Test very long GPU names
@@ -412,7 +412,7 @@ mod tests {
             true,
             true,
         )];
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "H100");
 
         // Test special characters
@@ -423,7 +423,7 @@ mod tests {
             true,
             true,
         )];
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "H100");
 
         // Test null/empty strings
@@ -434,7 +434,7 @@ mod tests {
             true,
             true,
         )];
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "OTHER");
 
         // Test whitespace-only strings
@@ -445,24 +445,21 @@ mod tests {
             true,
             true,
         )];
-        let primary = GpuCategorizer::determine_primary_gpu_model(&validations);
+        let primary = categorizer.determine_primary_gpu_model(&validations);
         assert_eq!(primary, "OTHER");
     }
 
     #[test]
-    fn test_gpu_category_enum() {
-        // Test enum variants
-        let h100 = GpuCategory::H100;
-        let h200 = GpuCategory::H200;
-        let other = GpuCategory::Other("CustomGPU".to_string());
+    fn test_gpu_category_type() {
+        let categorizer = GpuCategorizer::default();
+        let h100 = categorizer.model_to_category("H100");
+        let h200 = categorizer.model_to_category("H200");
+        let other = categorizer.model_to_category("CustomGPU");
 
-        assert_eq!(h100, GpuCategory::H100);
+        assert_eq!(h100, categorizer.model_to_category("h100"));
         assert_ne!(h100, h200);
-
-        match other {
-            GpuCategory::Other(name) => assert_eq!(name, "CustomGPU"),
-            _ => panic!("Expected Other variant"),
-        }
+        assert!(!h100.is_other());
+        assert_eq!(other, GpuCategory::other());
 
         // Test Debug trait
         let debug_str = format!("{h100:?}");
@@ -498,33 +495,35 @@ mod tests {
 
     #[test]
     fn test_complex_gpu_normalization_scenarios() {
+        let categorizer = GpuCategorizer::default();
         // Test multiple NVIDIA prefixes
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("NVIDIA NVIDIA GeForce RTX 4090"),
-            "OTHER"
+            categorizer.normalize_gpu_model("NVIDIA NVIDIA GeForce RTX 4090"),
+            "RTX4090"
         );
 
         // Test mixed case with numbers
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("nvidia h100-80gb-pcie"),
+            categorizer.normalize_gpu_model("nvidia h100-80gb-pcie"),
             "H100"
         );
 
         // Test Tesla prefix variations
-        assert_eq!(GpuCategorizer::normalize_gpu_model("Tesla V100"), "OTHER");
+        assert_eq!(categorizer.normalize_gpu_model("Tesla V100"), "OTHER");
 
         // Test partial matches
-        assert_eq!(GpuCategorizer::normalize_gpu_model("Some H100 GPU"), "H100");
+        assert_eq!(categorizer.normalize_gpu_model("Some H100 GPU"), "H100");
 
         // Test RTX variants with spaces
         assert_eq!(
-            GpuCategorizer::normalize_gpu_model("RTX   4090   Ti"),
-            "OTHER"
+            categorizer.normalize_gpu_model("RTX   4090   Ti"),
+            "RTX4090"
         );
     }
 
     #[test]
     fn test_profile_edge_cases_with_zero_gpus() {
+        let categorizer = GpuCategorizer::default();
         let miner_uid = MinerUid::new(789);
         let validations = vec![ExecutorValidationResult::new_for_testing(
             "exec1".to_string(),
@@ -534,7 +533,7 @@ mod tests {
             true,
         )];
 
-        let profile = MinerGpuProfile::new(miner_uid, &validations, 0.0);
+        let profile = MinerGpuProfile::new(miner_uid, &validations, 0.0, &categorizer);
 
         assert_eq!(profile.total_gpu_count(), 0);
         assert_eq!(profile.primary_gpu_model, "H100");
@@ -544,6 +543,7 @@ mod tests {
 
     #[test]
     fn test_large_gpu_counts() {
+        let categorizer = GpuCategorizer::default();
         let miner_uid = MinerUid::new(999);
         let validations = vec![ExecutorValidationResult::new_for_testing(
             "exec1".to_string(),
@@ -553,7 +553,7 @@ mod tests {
             true,
         )];
 
-        let profile = MinerGpuProfile::new(miner_uid, &validations, 1.0);
+        let profile = MinerGpuProfile::new(miner_uid, &validations, 1.0, &categorizer);
 
         assert_eq!(profile.total_gpu_count(), u32::MAX);
         assert_eq!(profile.get_gpu_count("H100"), u32::MAX);
@@ -5,19 +5,22 @@ use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 use super::categorization::{ExecutorValidationResult, GpuCategorizer, MinerGpuProfile};
+use super::registry::OTHER_CATEGORY;
 use crate::metrics::ValidatorMetrics;
 use crate::persistence::gpu_profile_repository::GpuProfileRepository;
 use common::identity::MinerUid;
 
 pub struct GpuScoringEngine {
     gpu_profile_repo: Arc<GpuProfileRepository>,
+    categorizer: GpuCategorizer,
     metrics: Option<Arc<ValidatorMetrics>>,
 }
 
 impl GpuScoringEngine {
-    pub fn new(gpu_profile_repo: Arc<GpuProfileRepository>) -> Self {
+    pub fn new(gpu_profile_repo: Arc<GpuProfileRepository>, categorizer: GpuCategorizer) -> Self {
         Self {
             gpu_profile_repo,
+            categorizer,
             metrics: None,
         }
     }
@@ -25,10 +28,12 @@ impl GpuScoringEngine {
     /// Create new engine with metrics support
     pub fn with_metrics(
         gpu_profile_repo: Arc<GpuProfileRepository>,
+        categorizer: GpuCategorizer,
         metrics: Arc<ValidatorMetrics>,
     ) -> Self {
         Self {
             gpu_profile_repo,
+            categorizer,
             metrics: Some(metrics),
         }
     }
@@ -43,7 +48,9 @@ impl GpuScoringEngine {
         let new_score = self.calculate_verification_score(&executor_validations);
 
         // Determine primary GPU model
-        let primary_gpu_model = GpuCategorizer::determine_primary_gpu_model(&executor_validations);
+        let primary_gpu_model = self
+            .categorizer
+            .determine_primary_gpu_model(&executor_validations);
 
         // Check if there are any successful validations
         let has_successful_validation = executor_validations
@@ -51,7 +58,12 @@ impl GpuScoringEngine {
             .any(|v| v.is_valid && v.attestation_valid);
 
         // Create or update the profile with the calculated score
-        let mut profile = MinerGpuProfile::new(miner_uid, &executor_validations, new_score);
+        let mut profile = MinerGpuProfile::new(
+            miner_uid,
+            &executor_validations,
+            new_score,
+            &self.categorizer,
+        );
 
         // If there's a successful validation, update the timestamp
         if has_successful_validation {
@@ -188,7 +200,7 @@ impl GpuScoringEngine {
 
     /// Get all miners grouped by GPU category with multi-category support
     /// A single miner can appear in multiple categories if they have multiple GPU types
-    /// Only includes H100 and H200 categories for rewards (OTHER category excluded)
+    /// Only includes registry categories for rewards (OTHER category excluded)
     /// Filters out miners without active axons on the chain
     /// Only includes miners with successful validations since the given timestamp
     pub async fn get_miners_by_gpu_category_since_epoch(
@@ -254,24 +266,7 @@ impl GpuScoringEngine {
                 continue;
             }
 
-            // Only consider H100 and H200 GPUs for rewards
-            let rewardable_gpu_counts: HashMap<String, u32> = profile
-                .gpu_counts
-                .iter()
-                .filter_map(|(gpu_model, &gpu_count)| {
-                    if gpu_count > 0 {
-                        let normalized_model = GpuCategorizer::normalize_gpu_model(gpu_model);
-                        // Only include H100 and H200 for rewards
-                        if normalized_model == "H100" || normalized_model == "H200" {
-                            Some((normalized_model, gpu_count))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+            let rewardable_gpu_counts = self.rewardable_gpu_counts(&profile);
 
             // Skip miners with no rewardable GPUs
             if rewardable_gpu_counts.is_empty() {
@@ -300,7 +295,7 @@ impl GpuScoringEngine {
             total_entries = miners_by_category.values().map(|v| v.len()).sum::<usize>(),
             cutoff_hours = cutoff_hours,
             metagraph_size = metagraph.hotkeys.len(),
-            "Retrieved miners by GPU category (H100/H200 only for rewards, with active axon validation)"
+            "Retrieved miners by GPU category (registry categories only, with active axon validation)"
         );
 
         Ok(miners_by_category)
@@ -308,37 +303,20 @@ impl GpuScoringEngine {
 
     /// Get category statistics with multi-category support
     /// Statistics are calculated per category based on proportional scores
-    /// Only includes H100 and H200 categories for rewards (OTHER category excluded)
+    /// Only includes registry categories for rewards (OTHER category excluded)
     pub async fn get_category_statistics(&self) -> Result<HashMap<String, CategoryStats>> {
         let all_profiles = self.gpu_profile_repo.get_all_gpu_profiles().await?;
         let mut category_stats = HashMap::new();
 
         for profile in all_profiles {
-            // Only consider H100 and H200 GPUs for rewards
-            let rewardable_gpu_counts: HashMap<String, u32> = profile
-                .gpu_counts
-                .iter()
-                .filter_map(|(gpu_model, &gpu_count)| {
-                    if gpu_count > 0 {
-                        let normalized_model = GpuCategorizer::normalize_gpu_model(gpu_model);
-                        // Only include H100 and H200 for rewards
-                        if normalized_model == "H100" || normalized_model == "H200" {
-                            Some((normalized_model, gpu_count))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+            let rewardable_gpu_counts = self.rewardable_gpu_counts(&profile);
 
             // Skip miners with no rewardable GPUs
             if rewardable_gpu_counts.is_empty() {
                 continue;
             }
 
-            // Calculate total rewardable GPUs (only H100 and H200)
+            // Calculate total rewardable GPUs across registry categories
             let total_rewardable_gpus: u32 = rewardable_gpu_counts.values().sum();
 
             // Add stats for each rewardable category the miner has GPUs in
@@ -385,6 +363,25 @@ impl GpuScoringEngine {
 
         Ok(category_stats)
     }
+
+    /// GPU counts of a profile in categories eligible for rewards
+    ///
+    /// Counts are re-categorized so that profiles stored before a registry
+    /// change are scored under the current rules.
+    pub(crate) fn rewardable_gpu_counts(&self, profile: &MinerGpuProfile) -> HashMap<String, u32> {
+        let mut counts = HashMap::new();
+        for (gpu_model, &gpu_count) in &profile.gpu_counts {
+            if gpu_count == 0 {
+                continue;
+            }
+
+            let category = self.categorizer.normalize_gpu_model(gpu_model);
+            if category != OTHER_CATEGORY {
+                *counts.entry(category).or_insert(0) += gpu_count;
+            }
+        }
+        counts
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -417,7 +414,7 @@ mod tests {
     #[tokio::test]
     async fn test_verification_score_calculation() {
         let (repo, _temp_file) = create_test_gpu_profile_repo().await.unwrap();
-        let engine = GpuScoringEngine::new(repo);
+        let engine = GpuScoringEngine::new(repo, GpuCategorizer::default());
 
         // Test with valid attestations
         let validations = vec![
@@ -525,7 +522,7 @@ mod tests {
     #[tokio::test]
     async fn test_gpu_count_weighting() {
         let (repo, _temp_file) = create_test_gpu_profile_repo().await.unwrap();
-        let engine = GpuScoringEngine::new(repo);
+        let engine = GpuScoringEngine::new(repo, GpuCategorizer::default());
 
         // Test different GPU counts
         for gpu_count in 1..=8 {
@@ -565,7 +562,7 @@ mod tests {
     #[tokio::test]
     async fn test_miner_profile_update() {
         let (repo, _temp_file) = create_test_gpu_profile_repo().await.unwrap();
-        let engine = GpuScoringEngine::new(repo);
+        let engine = GpuScoringEngine::new(repo, GpuCategorizer::default());
 
         let miner_uid = MinerUid::new(1);
         let validations = vec![ExecutorValidationResult {
@@ -611,7 +608,7 @@ mod tests {
     #[tokio::test]
     async fn test_category_statistics() {
         let (repo, _temp_file) = create_test_gpu_profile_repo().await.unwrap();
-        let engine = GpuScoringEngine::new(repo.clone());
+        let engine = GpuScoringEngine::new(repo.clone(), GpuCategorizer::default());
 
         // Create test profiles
         let mut h100_counts_1 = HashMap::new();
@@ -679,7 +676,7 @@ mod tests {
     #[tokio::test]
     async fn test_pass_fail_scoring_edge_cases() {
         let (repo, _temp_file) = create_test_gpu_profile_repo().await.unwrap();
-        let engine = GpuScoringEngine::new(repo);
+        let engine = GpuScoringEngine::new(repo, GpuCategorizer::default());
 
         // Test all invalid validations
         let all_invalid = vec![
@@ -745,7 +742,7 @@ mod tests {
     #[tokio::test]
     async fn test_direct_score_update() {
         let (repo, _temp_file) = create_test_gpu_profile_repo().await.unwrap();
-        let engine = GpuScoringEngine::new(repo.clone());
+        let engine = GpuScoringEngine::new(repo.clone(), GpuCategorizer::default());
 
         let miner_uid = MinerUid::new(100);
 
@@ -787,7 +784,7 @@ mod tests {
     #[tokio::test]
     async fn test_scoring_ignores_gpu_memory() {
         let (repo, _temp_file) = create_test_gpu_profile_repo().await.unwrap();
-        let engine = GpuScoringEngine::new(repo);
+        let engine = GpuScoringEngine::new(repo, GpuCategorizer::default());
 
         // Test various memory sizes all get same score
         let memory_sizes = vec![16, 24, 40, 80, 100];
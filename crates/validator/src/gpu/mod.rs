@@ -1,5 +1,6 @@
 pub mod categorization;
 pub mod gpu_scoring;
+pub mod registry;
 
 #[cfg(test)]
 mod categorization_tests;
@@ -12,5 +13,6 @@ mod epoch_filtering_test;
 
 pub use categorization::*;
 pub use gpu_scoring::*;
+pub use registry::{GpuModelRegistry, GpuModelRule, OTHER_CATEGORY};
 
 // Tests temporarily disabled due to metagraph structure changes
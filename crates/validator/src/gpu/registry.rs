@@ -0,0 +1,276 @@
+//! GPU model registry
+//!
+//! Maps the GPU names reported by executors to the categories used as keys
+//! of `emission.gpu_allocations`. Rules are checked in order and the first
+//! match wins, so memory-specific rules must come before general ones.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Category for GPUs that match no rule
+pub const OTHER_CATEGORY: &str = "OTHER";
+
+/// One registry entry mapping GPU names to a category
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuModelRule {
+    /// Category name, as used in `gpu_allocations`
+    pub category: String,
+
+    /// Substrings identifying the model, e.g. "H100" or "RTX 4090".
+    /// Case, spaces and punctuation are ignored when matching
+    pub patterns: Vec<String>,
+
+    /// Only match GPUs with at least this much memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_memory_gb: Option<u64>,
+
+    /// Only match GPUs with at most this much memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_gb: Option<u64>,
+}
+
+impl GpuModelRule {
+    fn new(category: &str, patterns: &[&str]) -> Self {
+        Self {
+            category: category.to_string(),
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            min_memory_gb: None,
+            max_memory_gb: None,
+        }
+    }
+
+    fn with_min_memory(mut self, min_memory_gb: u64) -> Self {
+        self.min_memory_gb = Some(min_memory_gb);
+        self
+    }
+
+    /// Whether a compacted GPU name with the given memory falls under this rule
+    ///
+    /// Memory bounds only match GPUs whose memory is known.
+    fn matches(&self, compact_model: &str, memory_gb: Option<u64>) -> bool {
+        let memory_ok = match (self.min_memory_gb, self.max_memory_gb) {
+            (None, None) => true,
+            (min, max) => memory_gb.is_some_and(|memory| {
+                memory >= min.unwrap_or(0) && memory <= max.unwrap_or(u64::MAX)
+            }),
+        };
+
+        memory_ok
+            && self
+                .patterns
+                .iter()
+                .map(|pattern| compact(pattern))
+                .any(|pattern| !pattern.is_empty() && compact_model.contains(&pattern))
+    }
+}
+
+/// Ordered set of GPU model rules
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GpuModelRegistry {
+    rules: Vec<GpuModelRule>,
+}
+
+impl Default for GpuModelRegistry {
+    fn default() -> Self {
+        Self::new(vec![
+            GpuModelRule::new("H100", &["H100"]),
+            GpuModelRule::new("H200", &["H200"]),
+            GpuModelRule::new("B200", &["B200"]),
+            GpuModelRule::new("A100_80GB", &["A100"]).with_min_memory(60),
+            // A100s that do not report their memory are treated as the smaller model
+            GpuModelRule::new("A100_40GB", &["A100"]),
+            GpuModelRule::new("L40S", &["L40S"]),
+            GpuModelRule::new("RTX4090", &["RTX 4090"]),
+        ])
+    }
+}
+
+impl GpuModelRegistry {
+    /// Create a registry from rules in match order
+    pub fn new(rules: Vec<GpuModelRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn rules(&self) -> &[GpuModelRule] {
+        &self.rules
+    }
+
+    /// Category of a GPU, or `OTHER` if no rule matches
+    ///
+    /// Memory stated in the name (e.g. "A100-SXM4-40GB") takes precedence
+    /// over `memory_gb`, which is ignored when zero.
+    pub fn categorize(&self, gpu_model: &str, memory_gb: Option<u64>) -> String {
+        let compact_model = compact(gpu_model);
+        let memory_gb = memory_from_name(gpu_model).or(memory_gb.filter(|memory| *memory > 0));
+
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(&compact_model, memory_gb))
+            .map(|rule| rule.category.clone())
+            .unwrap_or_else(|| OTHER_CATEGORY.to_string())
+    }
+
+    /// All categories GPUs can be assigned to, excluding `OTHER`
+    pub fn categories(&self) -> BTreeSet<&str> {
+        self.rules
+            .iter()
+            .map(|rule| rule.category.as_str())
+            .collect()
+    }
+
+    pub fn is_known_category(&self, category: &str) -> bool {
+        self.rules.iter().any(|rule| rule.category == category)
+    }
+
+    /// Validate the rules
+    pub fn validate(&self) -> Result<()> {
+        if self.rules.is_empty() {
+            return Err(anyhow!("GPU model registry cannot be empty"));
+        }
+
+        for rule in &self.rules {
+            if rule.category.trim().is_empty() {
+                return Err(anyhow!("GPU model rule has an empty category"));
+            }
+
+            if rule.category == OTHER_CATEGORY {
+                return Err(anyhow!(
+                    "{} is reserved for unmatched GPUs and cannot be a rule category",
+                    OTHER_CATEGORY
+                ));
+            }
+
+            if !rule.patterns.iter().any(|p| !compact(p).is_empty()) {
+                return Err(anyhow!(
+                    "GPU model rule for {} needs at least one non-empty pattern",
+                    rule.category
+                ));
+            }
+
+            if let (Some(min), Some(max)) = (rule.min_memory_gb, rule.max_memory_gb) {
+                if min > max {
+                    return Err(anyhow!(
+                        "GPU model rule for {} has min_memory_gb {} above max_memory_gb {}",
+                        rule.category,
+                        min,
+                        max
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Uppercase alphanumerics only, so "RTX 4090", "rtx-4090" and "RTX4090" compare equal
+fn compact(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Memory size stated in a GPU name, such as the 80 in "H100 80GB HBM3"
+fn memory_from_name(gpu_model: &str) -> Option<u64> {
+    let upper = gpu_model.to_ascii_uppercase();
+    let bytes = upper.as_bytes();
+
+    upper.match_indices("GB").find_map(|(index, _)| {
+        let digits_end = upper[..index].trim_end().len();
+        let digits_start = bytes[..digits_end]
+            .iter()
+            .rposition(|b| !b.is_ascii_digit())
+            .map_or(0, |position| position + 1);
+
+        upper[digits_start..digits_end].parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_registry_categories() {
+        let registry = GpuModelRegistry::default();
+        assert!(registry.validate().is_ok());
+
+        let cases = [
+            ("NVIDIA H100 80GB HBM3", None, "H100"),
+            ("H100 SXM5", None, "H100"),
+            ("NVIDIA H100 PCIe", None, "H100"),
+            ("NVIDIA H200", None, "H200"),
+            ("NVIDIA B200", None, "B200"),
+            ("NVIDIA A100-SXM4-80GB", None, "A100_80GB"),
+            ("NVIDIA A100-PCIE-40GB", None, "A100_40GB"),
+            ("NVIDIA A100 PCIe", Some(80), "A100_80GB"),
+            ("NVIDIA A100 PCIe", Some(0), "A100_40GB"),
+            ("NVIDIA L40S", None, "L40S"),
+            ("NVIDIA L40", None, OTHER_CATEGORY),
+            ("NVIDIA GeForce RTX 4090", None, "RTX4090"),
+            ("rtx4090", None, "RTX4090"),
+            ("GeForce RTX 3090", None, OTHER_CATEGORY),
+            ("", None, OTHER_CATEGORY),
+        ];
+
+        for (model, memory, expected) in cases {
+            assert_eq!(registry.categorize(model, memory), expected, "{model}");
+        }
+    }
+
+    #[test]
+    fn test_categories_are_stable_under_recategorization() {
+        let registry = GpuModelRegistry::default();
+        for category in registry.categories() {
+            assert_eq!(registry.categorize(category, None), category);
+        }
+    }
+
+    #[test]
+    fn test_memory_from_name() {
+        assert_eq!(memory_from_name("NVIDIA A100-SXM4-40GB"), Some(40));
+        assert_eq!(memory_from_name("H100 80 GB"), Some(80));
+        assert_eq!(memory_from_name("A100_80gb"), Some(80));
+        assert_eq!(memory_from_name("NVIDIA H100"), None);
+        assert_eq!(memory_from_name("GB200"), None);
+    }
+
+    #[test]
+    fn test_registry_from_toml_and_validation() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            gpu_models: GpuModelRegistry,
+        }
+
+        let wrapper: Wrapper = toml::from_str(
+            r#"
+            [[gpu_models]]
+            category = "H100_NVL"
+            patterns = ["H100 NVL"]
+
+            [[gpu_models]]
+            category = "H100"
+            patterns = ["H100"]
+            "#,
+        )
+        .unwrap();
+        let registry = wrapper.gpu_models;
+        assert!(registry.validate().is_ok());
+        assert_eq!(registry.categorize("NVIDIA H100 NVL", None), "H100_NVL");
+        assert_eq!(registry.categorize("NVIDIA H100 PCIe", None), "H100");
+
+        let invalid = GpuModelRegistry::new(vec![GpuModelRule {
+            category: "A100".to_string(),
+            patterns: vec!["A100".to_string()],
+            min_memory_gb: Some(80),
+            max_memory_gb: Some(40),
+        }]);
+        assert!(invalid.validate().is_err());
+
+        let reserved = GpuModelRegistry::new(vec![GpuModelRule::new(OTHER_CATEGORY, &["V100"])]);
+        assert!(reserved.validate().is_err());
+    }
+}
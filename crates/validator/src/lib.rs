@@ -12,6 +12,8 @@ pub mod journal;
 pub mod metrics;
 pub mod miner_prover;
 pub mod persistence;
+pub mod rental;
+pub mod slashing;
 pub mod ssh;
 pub mod validation;
 
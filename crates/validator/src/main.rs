@@ -15,6 +15,8 @@ mod journal;
 mod metrics;
 mod miner_prover;
 mod persistence;
+mod rental;
+mod slashing;
 mod ssh;
 mod validation;
 
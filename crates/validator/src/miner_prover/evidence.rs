@@ -0,0 +1,322 @@
+//! # Executor Evidence
+//!
+//! Requests a system profile and benchmarks from an executor through its
+//! miner, each bound to a fresh nonce, and checks that the executor signed
+//! them with the key pinned for it before storing them as evidence.
+//!
+//! An executor that does not sign its answers has no evidence to give, which
+//! the validator may tolerate. Evidence that is presented but does not verify
+//! is an [`InvalidEvidence`] error and is never tolerated.
+
+use super::miner_client::AuthenticatedMinerConnection;
+use crate::config::EvidenceConfig;
+use crate::persistence::executor_evidence_repository::{
+    ExecutorEvidence, ExecutorEvidenceRepository, KeyPinOutcome,
+};
+use anyhow::{anyhow, Result};
+use common::crypto::evidence::{
+    benchmark_message, generate_evidence_nonce, system_profile_message, verify_evidence,
+};
+use common::identity::Hotkey;
+use protocol::executor_control::{
+    BenchmarkRequest, BenchmarkResponse, SystemProfileRequest, SystemProfileResponse,
+};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Evidence an executor presented that failed verification
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct InvalidEvidence(String);
+
+impl InvalidEvidence {
+    /// Whether `error` was caused by evidence that failed verification
+    pub fn is_cause_of(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<Self>().is_some()
+    }
+}
+
+fn invalid(message: String) -> anyhow::Error {
+    InvalidEvidence(message).into()
+}
+
+/// Verified evidence collected from one executor
+#[derive(Debug, Clone)]
+pub struct EvidenceSummary {
+    /// Key that signed every response
+    pub executor_public_key: String,
+    /// Whether the key was pinned during this collection
+    pub newly_pinned: bool,
+    /// Score of each benchmark that was run
+    pub benchmark_scores: HashMap<String, f64>,
+}
+
+/// Collects and verifies signed evidence from executors
+pub struct EvidenceCollector {
+    config: EvidenceConfig,
+    validator_hotkey: Hotkey,
+    repository: ExecutorEvidenceRepository,
+}
+
+impl EvidenceCollector {
+    /// Create a new collector storing evidence in the given pool
+    pub fn new(config: EvidenceConfig, validator_hotkey: Hotkey, pool: SqlitePool) -> Self {
+        Self {
+            config,
+            validator_hotkey,
+            repository: ExecutorEvidenceRepository::new(pool),
+        }
+    }
+
+    /// Request, verify and store a system profile and the configured benchmarks
+    pub async fn collect(
+        &self,
+        connection: &mut AuthenticatedMinerConnection,
+        executor_id: &str,
+    ) -> Result<EvidenceSummary> {
+        let validator_hotkey = self.validator_hotkey.to_string();
+
+        let nonce = generate_evidence_nonce();
+        let profile = connection
+            .execute_executor_system_profile(
+                executor_id,
+                SystemProfileRequest {
+                    validator_hotkey: validator_hotkey.clone(),
+                    session_key: String::new(),
+                    key_mapping: HashMap::new(),
+                    profile_depth: "basic".to_string(),
+                    include_benchmarks: false,
+                    nonce: nonce.clone(),
+                },
+            )
+            .await?;
+        verify_system_profile(&nonce, &validator_hotkey, &profile)?;
+
+        let executor_public_key = profile.executor_public_key.clone();
+        let newly_pinned = match self
+            .repository
+            .check_signing_key(executor_id, &executor_public_key)
+            .await?
+        {
+            KeyPinOutcome::Pinned => {
+                info!(
+                    "Pinned signing key {} for executor {}",
+                    executor_public_key, executor_id
+                );
+                true
+            }
+            KeyPinOutcome::Matched => false,
+            KeyPinOutcome::Mismatch { pinned } => {
+                warn!(
+                    "Executor {} signed with key {} but {} is pinned",
+                    executor_id, executor_public_key, pinned
+                );
+                return Err(invalid(format!(
+                    "Executor {executor_id} signing key changed from pinned key {pinned}"
+                )));
+            }
+        };
+
+        self.repository
+            .store_evidence(&ExecutorEvidence::new(
+                executor_id,
+                "system_profile",
+                &nonce,
+                &executor_public_key,
+                &profile.signature,
+                serde_json::json!({
+                    "profile_hash": profile.profile_hash,
+                    "encrypted_profile": profile.encrypted_profile,
+                    "encryption_nonce": profile.encryption_nonce,
+                }),
+                None,
+            ))
+            .await?;
+
+        let mut benchmark_scores = HashMap::new();
+        for benchmark_type in &self.config.benchmarks {
+            let nonce = generate_evidence_nonce();
+            let response = connection
+                .execute_executor_benchmark(
+                    executor_id,
+                    BenchmarkRequest {
+                        benchmark_type: benchmark_type.clone(),
+                        duration_seconds: self.config.benchmark_duration_secs,
+                        parameters: HashMap::new(),
+                        validator_hotkey: validator_hotkey.clone(),
+                        nonce: nonce.clone(),
+                    },
+                )
+                .await?;
+            verify_benchmark(&nonce, &validator_hotkey, benchmark_type, &response)?;
+
+            if response.executor_public_key != executor_public_key {
+                return Err(invalid(format!(
+                    "Executor {executor_id} signed its {benchmark_type} benchmark with a different key than its profile"
+                )));
+            }
+
+            self.repository
+                .store_evidence(&ExecutorEvidence::new(
+                    executor_id,
+                    format!("benchmark:{benchmark_type}"),
+                    &nonce,
+                    &executor_public_key,
+                    &response.signature,
+                    serde_json::to_value(&response.results)?,
+                    Some(response.score),
+                ))
+                .await?;
+            benchmark_scores.insert(benchmark_type.clone(), response.score);
+        }
+
+        info!(
+            "Stored signed evidence for executor {} ({} benchmarks)",
+            executor_id,
+            benchmark_scores.len()
+        );
+
+        Ok(EvidenceSummary {
+            executor_public_key,
+            newly_pinned,
+            benchmark_scores,
+        })
+    }
+}
+
+/// Check that a system profile answers `nonce` and is signed by its executor
+pub fn verify_system_profile(
+    nonce: &str,
+    validator_hotkey: &str,
+    response: &SystemProfileResponse,
+) -> Result<()> {
+    if is_unsigned(&response.executor_public_key, &response.signature) {
+        return Err(anyhow!("Executor did not sign its system profile"));
+    }
+    if response.nonce != nonce {
+        return Err(invalid(
+            "System profile does not answer the requested nonce".to_string(),
+        ));
+    }
+
+    let message = system_profile_message(
+        nonce,
+        validator_hotkey,
+        &response.profile_hash,
+        &response.encrypted_profile,
+        &response.encryption_nonce,
+    );
+    verify_evidence(&response.executor_public_key, &message, &response.signature)
+        .map_err(|e| invalid(format!("System profile signature is invalid: {e}")))
+}
+
+/// Check that a benchmark result answers `nonce` and is signed by its executor
+pub fn verify_benchmark(
+    nonce: &str,
+    validator_hotkey: &str,
+    benchmark_type: &str,
+    response: &BenchmarkResponse,
+) -> Result<()> {
+    if is_unsigned(&response.executor_public_key, &response.signature) {
+        return Err(anyhow!(
+            "Executor did not sign its {} benchmark",
+            benchmark_type
+        ));
+    }
+    if response.nonce != nonce {
+        return Err(invalid(format!(
+            "{benchmark_type} benchmark does not answer the requested nonce"
+        )));
+    }
+
+    let message = benchmark_message(
+        nonce,
+        validator_hotkey,
+        benchmark_type,
+        response.score,
+        &response.results,
+    );
+    verify_evidence(&response.executor_public_key, &message, &response.signature).map_err(|e| {
+        invalid(format!(
+            "{benchmark_type} benchmark signature is invalid: {e}"
+        ))
+    })
+}
+
+/// Whether a response carries no signature at all, as from executors that
+/// predate signed evidence
+fn is_unsigned(executor_public_key: &str, signature: &str) -> bool {
+    executor_public_key.is_empty() && signature.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::crypto::evidence::sign_evidence;
+    use common::crypto::Ed25519PrivateKey;
+
+    fn signed_benchmark(key: &Ed25519PrivateKey, nonce: &str) -> BenchmarkResponse {
+        let results = HashMap::from([("cpu_threads".to_string(), "8".to_string())]);
+        let message = benchmark_message(nonce, "5Validator", "cpu", 1024.0, &results);
+
+        BenchmarkResponse {
+            results,
+            score: 1024.0,
+            execution_details: String::new(),
+            error: None,
+            nonce: nonce.to_string(),
+            executor_public_key: key.public_key().to_string(),
+            signature: sign_evidence(key, &message),
+        }
+    }
+
+    #[test]
+    fn test_benchmark_verification() {
+        let key = Ed25519PrivateKey::generate();
+        let response = signed_benchmark(&key, "nonce-1");
+
+        assert!(verify_benchmark("nonce-1", "5Validator", "cpu", &response).is_ok());
+        assert!(verify_benchmark("nonce-2", "5Validator", "cpu", &response).is_err());
+        assert!(verify_benchmark("nonce-1", "5Other", "cpu", &response).is_err());
+        assert!(verify_benchmark("nonce-1", "5Validator", "memory", &response).is_err());
+
+        let mut inflated = response.clone();
+        inflated.score = 4096.0;
+        assert!(verify_benchmark("nonce-1", "5Validator", "cpu", &inflated).is_err());
+
+        let mut stripped = response.clone();
+        stripped.signature.clear();
+        let err = verify_benchmark("nonce-1", "5Validator", "cpu", &stripped).unwrap_err();
+        assert!(InvalidEvidence::is_cause_of(&err));
+
+        let mut unsigned = response;
+        unsigned.signature.clear();
+        unsigned.executor_public_key.clear();
+        let err = verify_benchmark("nonce-1", "5Validator", "cpu", &unsigned).unwrap_err();
+        assert!(!InvalidEvidence::is_cause_of(&err));
+    }
+
+    #[test]
+    fn test_system_profile_verification() {
+        let key = Ed25519PrivateKey::generate();
+        let message = system_profile_message("nonce", "5Validator", "hash", "data", "iv");
+        let response = SystemProfileResponse {
+            encrypted_profile: "data".to_string(),
+            encryption_nonce: "iv".to_string(),
+            collected_at: None,
+            profile_hash: "hash".to_string(),
+            error: None,
+            nonce: "nonce".to_string(),
+            executor_public_key: key.public_key().to_string(),
+            signature: sign_evidence(&key, &message),
+        };
+
+        assert!(verify_system_profile("nonce", "5Validator", &response).is_ok());
+
+        let mut tampered = response;
+        tampered.profile_hash = "other".to_string();
+        let err = verify_system_profile("nonce", "5Validator", &tampered).unwrap_err();
+        assert!(InvalidEvidence::is_cause_of(&err));
+    }
+}
@@ -1,7 +1,8 @@
 //! # Miner Client
 //!
 //! gRPC client for communicating with miners' MinerDiscovery service.
-//! Handles authentication, executor discovery, and SSH session initialization.
+//! Handles authentication, executor discovery, SSH session initialization,
+//! container management and signed profile/benchmark requests on executors.
 
 use anyhow::{Context, Result};
 use std::sync::Arc;
@@ -10,10 +11,15 @@ use tonic::transport::Channel;
 use tracing::{debug, info, warn};
 
 use common::identity::Hotkey;
+use protocol::executor_control::{
+    BenchmarkRequest, BenchmarkResponse, ContainerOpRequest, ContainerOpResponse,
+    SystemProfileRequest, SystemProfileResponse,
+};
 use protocol::miner_discovery::{
     miner_discovery_client::MinerDiscoveryClient, CloseSshSessionRequest, CloseSshSessionResponse,
-    ExecutorConnectionDetails, InitiateSshSessionRequest, InitiateSshSessionResponse, LeaseRequest,
-    SessionInitRequest, ValidatorAuthRequest,
+    ExecutorBenchmarkRequest, ExecutorConnectionDetails, ExecutorContainerRequest,
+    ExecutorSystemProfileRequest, InitiateSshSessionRequest, InitiateSshSessionResponse,
+    LeaseRequest, SessionInitRequest, ValidatorAuthRequest,
 };
 
 /// Configuration for the miner client
@@ -225,6 +231,7 @@ impl MinerClient {
         Ok(AuthenticatedMinerConnection {
             client: MinerDiscoveryClient::new(channel),
             session_token,
+            validator_hotkey: self.validator_hotkey.to_string(),
             grpc_endpoint,
         })
     }
@@ -267,6 +274,7 @@ impl MinerClient {
 pub struct AuthenticatedMinerConnection {
     client: MinerDiscoveryClient<Channel>,
     session_token: String,
+    validator_hotkey: String,
     /// The gRPC endpoint used for this connection (useful for debugging/logging)
     #[allow(dead_code)]
     grpc_endpoint: String,
@@ -412,6 +420,104 @@ impl AuthenticatedMinerConnection {
 
         Ok(response)
     }
+
+    /// Run a container operation on one of the miner's executors
+    pub async fn manage_executor_container(
+        &mut self,
+        executor_id: &str,
+        operation: ContainerOpRequest,
+    ) -> Result<ContainerOpResponse> {
+        info!(
+            "Requesting container operation '{}' on executor {}",
+            operation.operation, executor_id
+        );
+
+        let request = ExecutorContainerRequest {
+            validator_hotkey: self.validator_hotkey.clone(),
+            session_token: self.session_token.clone(),
+            executor_id: executor_id.to_string(),
+            operation: Some(operation),
+        };
+
+        let response = self
+            .client
+            .manage_executor_container(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to manage executor container: {}", e))?;
+
+        let response = response.into_inner();
+
+        if !response.success {
+            let error_msg = response
+                .error
+                .map(|e| e.message)
+                .unwrap_or_else(|| "Unknown error".to_string());
+            return Err(anyhow::anyhow!("Container operation failed: {}", error_msg));
+        }
+
+        Ok(response)
+    }
+
+    /// Request a signed system profile from one of the miner's executors
+    pub async fn execute_executor_system_profile(
+        &mut self,
+        executor_id: &str,
+        profile_request: SystemProfileRequest,
+    ) -> Result<SystemProfileResponse> {
+        info!("Requesting system profile from executor {}", executor_id);
+
+        let request = ExecutorSystemProfileRequest {
+            validator_hotkey: self.validator_hotkey.clone(),
+            session_token: self.session_token.clone(),
+            executor_id: executor_id.to_string(),
+            request: Some(profile_request),
+        };
+
+        let response = self
+            .client
+            .execute_executor_system_profile(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to request executor system profile: {}", e))?
+            .into_inner();
+
+        if let Some(error) = response.error {
+            return Err(anyhow::anyhow!("System profile failed: {}", error.message));
+        }
+
+        Ok(response)
+    }
+
+    /// Run a signed benchmark on one of the miner's executors
+    pub async fn execute_executor_benchmark(
+        &mut self,
+        executor_id: &str,
+        benchmark_request: BenchmarkRequest,
+    ) -> Result<BenchmarkResponse> {
+        info!(
+            "Requesting {} benchmark from executor {}",
+            benchmark_request.benchmark_type, executor_id
+        );
+
+        let request = ExecutorBenchmarkRequest {
+            validator_hotkey: self.validator_hotkey.clone(),
+            session_token: self.session_token.clone(),
+            executor_id: executor_id.to_string(),
+            request: Some(benchmark_request),
+        };
+
+        let response = self
+            .client
+            .execute_executor_benchmark(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to request executor benchmark: {}", e))?
+            .into_inner();
+
+        if let Some(error) = response.error {
+            return Err(anyhow::anyhow!("Benchmark failed: {}", error.message));
+        }
+
+        Ok(response)
+    }
 }
 
 /// Information about an SSH session
@@ -4,6 +4,7 @@
 //! This module is organized following SOLID principles with clear separation of concerns.
 
 pub mod discovery;
+pub mod evidence;
 pub mod miner_client;
 pub mod scheduler;
 pub mod types;
@@ -106,6 +106,7 @@ async fn test_dynamic_discovery_config() {
         cache_miner_info_ttl: Duration::from_secs(300),
         grpc_port_offset: Some(42000),
         binary_validation: crate::config::BinaryValidationConfig::default(),
+        evidence: crate::config::EvidenceConfig::default(),
     };
 
     // Verify configuration
@@ -3,6 +3,7 @@
 //! Handles the actual verification of miners and their executors.
 //! Implements Single Responsibility Principle by focusing only on verification logic.
 
+use super::evidence::{EvidenceCollector, InvalidEvidence};
 use super::miner_client::{MinerClient, MinerClientConfig};
 use super::types::{ExecutorInfo, ExecutorStatus, MinerInfo};
 use crate::config::VerificationConfig;
@@ -706,6 +707,18 @@ impl VerificationEngine {
                         executor_id
                     );
 
+                    if existing_miner_id != miner_id {
+                        self.persistence
+                            .record_gpu_uuid_conflict(
+                                &gpu_info.gpu_uuid,
+                                &existing_miner_id,
+                                &existing_executor_id,
+                                &miner_id,
+                                executor_id,
+                            )
+                            .await?;
+                    }
+
                     sqlx::query(
                         "UPDATE gpu_uuid_assignments
                          SET miner_id = ?, executor_id = ?, gpu_index = ?, gpu_name = ?,
@@ -3016,13 +3029,20 @@ impl VerificationEngine {
             binary_score = 0.8; // Default score when disabled
         }
 
+        let evidence_ok = self
+            .collect_executor_evidence(miner_endpoint, &executor_info.id)
+            .await;
+
         // Phase 3: Calculate Combined Score
-        let combined_score = self.calculate_combined_verification_score(
+        let mut combined_score = self.calculate_combined_verification_score(
             validation_details.ssh_score,
             binary_score,
             ssh_connection_successful,
             binary_validation_successful,
         );
+        if !evidence_ok {
+            combined_score = 0.0;
+        }
 
         validation_details.combined_score = combined_score;
         validation_details.binary_score = binary_score;
@@ -3054,12 +3074,69 @@ impl VerificationEngine {
             ssh_connection_successful,
             binary_validation_successful,
             executor_result,
-            error: None,
+            error: (!evidence_ok).then(|| "Signed executor evidence did not verify".to_string()),
             execution_time: total_start.elapsed(),
             validation_details,
             gpu_count,
         })
     }
+
+    /// Collect signed profile and benchmark evidence from an executor
+    ///
+    /// Returns false when the executor presented evidence that did not verify,
+    /// or when evidence is required and could not be collected.
+    async fn collect_executor_evidence(&self, miner_endpoint: &str, executor_id: &str) -> bool {
+        if !self.config.evidence.enabled {
+            return true;
+        }
+
+        info!(
+            executor_id = %executor_id,
+            "[EVAL_FLOW] Collecting signed executor evidence"
+        );
+
+        let collector = EvidenceCollector::new(
+            self.config.evidence.clone(),
+            self.validator_hotkey.clone(),
+            self.persistence.pool().clone(),
+        );
+        let result = async {
+            let client = self.create_authenticated_client()?;
+            let mut connection = client.connect_and_authenticate(miner_endpoint).await?;
+            collector.collect(&mut connection, executor_id).await
+        }
+        .await;
+
+        match result {
+            Ok(summary) => {
+                info!(
+                    executor_id = %executor_id,
+                    public_key = %summary.executor_public_key,
+                    newly_pinned = summary.newly_pinned,
+                    benchmark_scores = ?summary.benchmark_scores,
+                    "[EVAL_FLOW] Signed executor evidence verified"
+                );
+                true
+            }
+            Err(e) if InvalidEvidence::is_cause_of(&e) => {
+                warn!(
+                    executor_id = %executor_id,
+                    error = %e,
+                    "[EVAL_FLOW] Signed executor evidence is invalid"
+                );
+                false
+            }
+            Err(e) => {
+                warn!(
+                    executor_id = %executor_id,
+                    error = %e,
+                    required = self.config.evidence.required,
+                    "[EVAL_FLOW] Signed executor evidence collection failed"
+                );
+                !self.config.evidence.required
+            }
+        }
+    }
 }
 
 /// SSH automation status information
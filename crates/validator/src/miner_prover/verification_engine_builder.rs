@@ -210,6 +210,7 @@ mod tests {
             cache_miner_info_ttl: Duration::from_secs(300),
             grpc_port_offset: Some(1000),
             binary_validation: crate::config::BinaryValidationConfig::default(),
+            evidence: crate::config::EvidenceConfig::default(),
         };
 
         let automatic_verification_config = AutomaticVerificationConfig {
@@ -381,6 +382,7 @@ mod tests {
             cache_miner_info_ttl: Duration::from_secs(300),
             grpc_port_offset: Some(1000),
             binary_validation: crate::config::BinaryValidationConfig::default(),
+            evidence: crate::config::EvidenceConfig::default(),
         };
 
         let miner_client_config = MinerClientConfig::default();
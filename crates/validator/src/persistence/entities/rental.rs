@@ -24,14 +24,74 @@ pub struct Rental {
     pub total_cost: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Rental lifecycle
+///
+/// Pending → Provisioning → Active → Terminating → Terminated, with Failed
+/// reachable from every non-final state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RentalStatus {
+    /// Recorded, no container requested yet
     Pending,
+    /// Container is being created on the executor
+    Provisioning,
+    /// Container is running and billed
     Active,
+    /// Billing has stopped, container teardown is in progress
+    Terminating,
     Terminated,
     Failed,
 }
 
+impl RentalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RentalStatus::Pending => "Pending",
+            RentalStatus::Provisioning => "Provisioning",
+            RentalStatus::Active => "Active",
+            RentalStatus::Terminating => "Terminating",
+            RentalStatus::Terminated => "Terminated",
+            RentalStatus::Failed => "Failed",
+        }
+    }
+
+    /// Whether the lifecycle allows moving from this status to `next`
+    pub fn can_transition_to(&self, next: RentalStatus) -> bool {
+        use RentalStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending, Provisioning)
+                | (Pending, Terminated)
+                | (Provisioning, Active)
+                | (Provisioning, Terminating)
+                | (Active, Terminating)
+                | (Terminating, Terminated)
+                | (Pending | Provisioning | Active | Terminating, Failed)
+        )
+    }
+
+    /// Terminated and Failed rentals never change again
+    pub fn is_final(&self) -> bool {
+        matches!(self, RentalStatus::Terminated | RentalStatus::Failed)
+    }
+}
+
+impl std::str::FromStr for RentalStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "Pending" => Ok(RentalStatus::Pending),
+            "Provisioning" => Ok(RentalStatus::Provisioning),
+            "Active" => Ok(RentalStatus::Active),
+            "Terminating" => Ok(RentalStatus::Terminating),
+            "Terminated" => Ok(RentalStatus::Terminated),
+            "Failed" => Ok(RentalStatus::Failed),
+            _ => Err(anyhow::anyhow!("Invalid rental status: {}", s)),
+        }
+    }
+}
+
 impl Rental {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -65,24 +125,58 @@ impl Rental {
         }
     }
 
-    pub fn activate(&mut self) {
-        self.status = RentalStatus::Active;
-        self.started_at = Some(Utc::now());
+    /// Move to `next`, rejecting transitions the lifecycle does not allow
+    fn transition(&mut self, next: RentalStatus) -> anyhow::Result<()> {
+        if !self.status.can_transition_to(next) {
+            return Err(anyhow::anyhow!(
+                "Rental {} cannot move from {} to {}",
+                self.id,
+                self.status.as_str(),
+                next.as_str()
+            ));
+        }
+
+        self.status = next;
         self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn begin_provisioning(&mut self) -> anyhow::Result<()> {
+        self.transition(RentalStatus::Provisioning)
+    }
+
+    pub fn activate(&mut self) -> anyhow::Result<()> {
+        self.transition(RentalStatus::Active)?;
+        self.started_at = Some(Utc::now());
+        Ok(())
     }
 
-    pub fn terminate(&mut self, reason: Option<String>, total_cost: f64) {
-        self.status = RentalStatus::Terminated;
+    /// Stop billing and start tearing the container down
+    pub fn begin_termination(&mut self, reason: Option<String>) -> anyhow::Result<()> {
+        self.transition(RentalStatus::Terminating)?;
         self.terminated_at = Some(Utc::now());
         self.termination_reason = reason;
+        Ok(())
+    }
+
+    pub fn terminate(&mut self, reason: Option<String>, total_cost: f64) -> anyhow::Result<()> {
+        self.transition(RentalStatus::Terminated)?;
+        self.terminated_at.get_or_insert_with(Utc::now);
+        if reason.is_some() {
+            self.termination_reason = reason;
+        }
         self.total_cost = Some(total_cost);
-        self.updated_at = Utc::now();
+        Ok(())
     }
 
-    pub fn fail(&mut self, reason: String) {
-        self.status = RentalStatus::Failed;
+    pub fn fail(&mut self, reason: String) -> anyhow::Result<()> {
+        self.transition(RentalStatus::Failed)?;
+        if self.started_at.is_some() {
+            self.terminated_at.get_or_insert_with(Utc::now);
+            self.total_cost = Some(self.current_cost());
+        }
         self.termination_reason = Some(reason);
-        self.updated_at = Utc::now();
+        Ok(())
     }
 
     pub fn is_active(&self) -> bool {
@@ -90,7 +184,43 @@ impl Rental {
     }
 
     pub fn is_terminated(&self) -> bool {
-        matches!(self.status, RentalStatus::Terminated)
+        self.status.is_final()
+    }
+
+    /// When the rental reaches `max_duration_hours`, counted from activation
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.started_at
+            .map(|started| started + chrono::Duration::hours(self.max_duration_hours as i64))
+    }
+
+    /// Latest time the rental can keep its executor occupied
+    ///
+    /// Rentals that have not started yet may still run for their full
+    /// duration from now.
+    pub fn holds_executor_until(&self) -> DateTime<Utc> {
+        let now = Utc::now();
+        match self.status {
+            RentalStatus::Pending | RentalStatus::Provisioning => {
+                now + chrono::Duration::hours(self.max_duration_hours as i64)
+            }
+            RentalStatus::Active => self.expires_at().unwrap_or(now),
+            RentalStatus::Terminating | RentalStatus::Terminated | RentalStatus::Failed => now,
+        }
+    }
+
+    /// Executor container backing the rental, once provisioned
+    pub fn container_id(&self) -> Option<&str> {
+        self.ssh_access_info
+            .get("container_id")
+            .and_then(|id| id.as_str())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// End of the billable period so far: termination, expiry or now
+    pub fn billing_end(&self) -> Option<DateTime<Utc>> {
+        let end = self.terminated_at.unwrap_or_else(Utc::now);
+        let expires_at = self.expires_at()?;
+        Some(end.min(expires_at))
     }
 
     pub fn duration_hours(&self) -> Option<f64> {
@@ -105,11 +235,80 @@ impl Rental {
         }
     }
 
+    /// Cost accrued so far, never beyond `max_duration_hours`
     pub fn current_cost(&self) -> f64 {
         if let Some(duration) = self.duration_hours() {
-            duration * self.cost_per_hour
+            duration.min(self.max_duration_hours as f64) * self.cost_per_hour
         } else {
             0.0
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rental() -> Rental {
+        Rental::new(
+            "miner1__exec1".to_string(),
+            "ssh-ed25519 AAAA".to_string(),
+            "ubuntu:22.04".to_string(),
+            None,
+            Value::Null,
+            Value::Null,
+            2,
+            1.5,
+        )
+    }
+
+    #[test]
+    fn test_rental_lifecycle() {
+        let mut rental = rental();
+        assert!(rental.activate().is_err());
+
+        rental.begin_provisioning().unwrap();
+        rental.activate().unwrap();
+        assert!(rental.is_active());
+        assert!(rental.expires_at().is_some());
+
+        rental
+            .begin_termination(Some("user_requested".to_string()))
+            .unwrap();
+        assert_eq!(rental.status, RentalStatus::Terminating);
+        assert!(rental.terminated_at.is_some());
+
+        rental.terminate(None, 0.0).unwrap();
+        assert_eq!(rental.status, RentalStatus::Terminated);
+        assert_eq!(rental.termination_reason.as_deref(), Some("user_requested"));
+
+        // Final states do not change
+        assert!(rental.fail("late failure".to_string()).is_err());
+        assert!(rental.is_terminated());
+    }
+
+    #[test]
+    fn test_cost_is_capped_at_max_duration() {
+        let mut rental = rental();
+        rental.begin_provisioning().unwrap();
+        rental.activate().unwrap();
+        rental.started_at = Some(Utc::now() - chrono::Duration::hours(5));
+
+        assert!((rental.current_cost() - 3.0).abs() < 1e-9);
+        assert_eq!(rental.billing_end(), rental.expires_at());
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            RentalStatus::Pending,
+            RentalStatus::Provisioning,
+            RentalStatus::Active,
+            RentalStatus::Terminating,
+            RentalStatus::Terminated,
+            RentalStatus::Failed,
+        ] {
+            assert_eq!(status.as_str().parse::<RentalStatus>().unwrap(), status);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Executor evidence repository
+//!
+//! Stores signed system profiles and benchmark results collected from
+//! executors, and pins each executor's signing key the first time it is seen
+//! so a later response signed by a different key is detected
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use uuid::Uuid;
+
+/// A signed response from an executor, as verified by the validator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutorEvidence {
+    pub id: String,
+    pub executor_id: String,
+    /// `system_profile` or `benchmark:<type>`
+    pub evidence_type: String,
+    /// Nonce the validator sent with the request
+    pub nonce: String,
+    /// Hex-encoded executor public key that signed the response
+    pub public_key: String,
+    /// Hex-encoded signature
+    pub signature: String,
+    /// Signed response fields as JSON
+    pub payload: serde_json::Value,
+    /// Benchmark score, if the evidence is a benchmark
+    pub score: Option<f64>,
+    pub collected_at: DateTime<Utc>,
+}
+
+impl ExecutorEvidence {
+    /// Create evidence collected now
+    pub fn new(
+        executor_id: &str,
+        evidence_type: impl Into<String>,
+        nonce: &str,
+        public_key: &str,
+        signature: &str,
+        payload: serde_json::Value,
+        score: Option<f64>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            executor_id: executor_id.to_string(),
+            evidence_type: evidence_type.into(),
+            nonce: nonce.to_string(),
+            public_key: public_key.to_string(),
+            signature: signature.to_string(),
+            payload,
+            score,
+            collected_at: Utc::now(),
+        }
+    }
+}
+
+/// Result of checking an executor's key against the pinned one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyPinOutcome {
+    /// First key seen for this executor, now pinned
+    Pinned,
+    /// Key matches the pinned key
+    Matched,
+    /// Key differs from the pinned key
+    Mismatch { pinned: String },
+}
+
+/// Repository for executor evidence and pinned signing keys
+pub struct ExecutorEvidenceRepository {
+    pool: SqlitePool,
+}
+
+impl ExecutorEvidenceRepository {
+    /// Create a new repository instance
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Compare a key with the executor's pinned key, pinning it if there is none
+    pub async fn check_signing_key(
+        &self,
+        executor_id: &str,
+        public_key: &str,
+    ) -> Result<KeyPinOutcome> {
+        let now = Utc::now().to_rfc3339();
+
+        let pinned: Option<String> = sqlx::query_scalar(
+            "SELECT public_key FROM executor_signing_keys WHERE executor_id = ?",
+        )
+        .bind(executor_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match pinned {
+            None => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO executor_signing_keys (executor_id, public_key, first_seen_at, last_seen_at)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(executor_id)
+                .bind(public_key)
+                .bind(&now)
+                .bind(&now)
+                .execute(&self.pool)
+                .await?;
+                Ok(KeyPinOutcome::Pinned)
+            }
+            Some(pinned) if pinned == public_key => {
+                sqlx::query(
+                    "UPDATE executor_signing_keys SET last_seen_at = ? WHERE executor_id = ?",
+                )
+                .bind(&now)
+                .bind(executor_id)
+                .execute(&self.pool)
+                .await?;
+                Ok(KeyPinOutcome::Matched)
+            }
+            Some(pinned) => Ok(KeyPinOutcome::Mismatch { pinned }),
+        }
+    }
+
+    /// Store verified evidence
+    pub async fn store_evidence(&self, evidence: &ExecutorEvidence) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO executor_evidence (
+                id, executor_id, evidence_type, nonce, public_key, signature,
+                payload, score, collected_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&evidence.id)
+        .bind(&evidence.executor_id)
+        .bind(&evidence.evidence_type)
+        .bind(&evidence.nonce)
+        .bind(&evidence.public_key)
+        .bind(&evidence.signature)
+        .bind(evidence.payload.to_string())
+        .bind(evidence.score)
+        .bind(evidence.collected_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Most recent evidence of an executor, newest first
+    pub async fn get_recent_evidence(
+        &self,
+        executor_id: &str,
+        limit: u32,
+    ) -> Result<Vec<ExecutorEvidence>> {
+        let rows = sqlx::query(
+            "SELECT * FROM executor_evidence WHERE executor_id = ? ORDER BY collected_at DESC LIMIT ?",
+        )
+        .bind(executor_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_evidence).collect()
+    }
+
+    fn row_to_evidence(row: sqlx::sqlite::SqliteRow) -> Result<ExecutorEvidence> {
+        let payload: String = row.get("payload");
+        let collected_at: String = row.get("collected_at");
+
+        Ok(ExecutorEvidence {
+            id: row.get("id"),
+            executor_id: row.get("executor_id"),
+            evidence_type: row.get("evidence_type"),
+            nonce: row.get("nonce"),
+            public_key: row.get("public_key"),
+            signature: row.get("signature"),
+            payload: serde_json::from_str(&payload)?,
+            score: row.get("score"),
+            collected_at: DateTime::parse_from_rfc3339(&collected_at)?.with_timezone(&Utc),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::SimplePersistence;
+
+    #[tokio::test]
+    async fn test_signing_key_pinning_and_evidence() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .unwrap();
+        let repo = ExecutorEvidenceRepository::new(persistence.pool().clone());
+
+        assert_eq!(
+            repo.check_signing_key("exec-1", "aa").await.unwrap(),
+            KeyPinOutcome::Pinned
+        );
+        assert_eq!(
+            repo.check_signing_key("exec-1", "aa").await.unwrap(),
+            KeyPinOutcome::Matched
+        );
+        assert_eq!(
+            repo.check_signing_key("exec-1", "bb").await.unwrap(),
+            KeyPinOutcome::Mismatch {
+                pinned: "aa".to_string()
+            }
+        );
+
+        let evidence = ExecutorEvidence::new(
+            "exec-1",
+            "benchmark:cpu",
+            "nonce",
+            "aa",
+            "sig",
+            serde_json::json!({ "cpu_threads": "8" }),
+            Some(4096.0),
+        );
+        repo.store_evidence(&evidence).await.unwrap();
+
+        let stored = repo.get_recent_evidence("exec-1", 10).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].evidence_type, "benchmark:cpu");
+        assert_eq!(stored[0].score, Some(4096.0));
+        assert_eq!(stored[0].payload["cpu_threads"], "8");
+        assert!(repo
+            .get_recent_evidence("exec-2", 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,7 +1,11 @@
 pub mod cleanup_task;
 pub mod entities;
+pub mod executor_evidence_repository;
 pub mod gpu_profile_repository;
+pub mod rental_usage_repository;
 pub mod simple_persistence;
+pub mod slashing_repository;
+pub mod weight_commit_repository;
 
 pub use simple_persistence::*;
 
@@ -0,0 +1,171 @@
+//! Rental usage ledger
+//!
+//! Billed usage of each rental, recorded per supervisor interval so the
+//! ledger sums to the rental's total cost once it ends
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use uuid::Uuid;
+
+/// Usage billed for one period of a rental
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RentalUsageRecord {
+    pub id: Uuid,
+    pub rental_id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub hours: f64,
+    pub cost: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl RentalUsageRecord {
+    /// Usage between two points in time at an hourly rate
+    pub fn new(
+        rental_id: Uuid,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        cost_per_hour: f64,
+    ) -> Self {
+        let hours = period_end
+            .signed_duration_since(period_start)
+            .num_milliseconds() as f64
+            / 3_600_000.0;
+
+        Self {
+            id: Uuid::new_v4(),
+            rental_id,
+            period_start,
+            period_end,
+            hours,
+            cost: hours * cost_per_hour,
+            recorded_at: Utc::now(),
+        }
+    }
+}
+
+/// Repository for the rental usage ledger
+pub struct RentalUsageRepository {
+    pool: SqlitePool,
+}
+
+impl RentalUsageRepository {
+    /// Create a new repository instance
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Append a usage record
+    pub async fn record_usage(&self, record: &RentalUsageRecord) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO rental_usage (
+                id, rental_id, period_start, period_end, hours, cost, recorded_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(record.id.to_string())
+        .bind(record.rental_id.to_string())
+        .bind(record.period_start.to_rfc3339())
+        .bind(record.period_end.to_rfc3339())
+        .bind(record.hours)
+        .bind(record.cost)
+        .bind(record.recorded_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// End of the last recorded period of a rental
+    pub async fn last_period_end(&self, rental_id: &Uuid) -> Result<Option<DateTime<Utc>>> {
+        let period_end: Option<String> =
+            sqlx::query_scalar("SELECT MAX(period_end) FROM rental_usage WHERE rental_id = ?")
+                .bind(rental_id.to_string())
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(period_end
+            .map(|ts| DateTime::parse_from_rfc3339(&ts))
+            .transpose()?
+            .map(|ts| ts.with_timezone(&Utc)))
+    }
+
+    /// All usage records of a rental in billing order
+    pub async fn get_usage(&self, rental_id: &Uuid) -> Result<Vec<RentalUsageRecord>> {
+        let rows =
+            sqlx::query("SELECT * FROM rental_usage WHERE rental_id = ? ORDER BY period_start ASC")
+                .bind(rental_id.to_string())
+                .fetch_all(&self.pool)
+                .await?;
+
+        rows.into_iter().map(Self::row_to_record).collect()
+    }
+
+    fn row_to_record(row: sqlx::sqlite::SqliteRow) -> Result<RentalUsageRecord> {
+        let id: String = row.get("id");
+        let rental_id: String = row.get("rental_id");
+        let period_start: String = row.get("period_start");
+        let period_end: String = row.get("period_end");
+        let recorded_at: String = row.get("recorded_at");
+
+        Ok(RentalUsageRecord {
+            id: Uuid::parse_str(&id)?,
+            rental_id: Uuid::parse_str(&rental_id)?,
+            period_start: DateTime::parse_from_rfc3339(&period_start)?.with_timezone(&Utc),
+            period_end: DateTime::parse_from_rfc3339(&period_end)?.with_timezone(&Utc),
+            hours: row.get("hours"),
+            cost: row.get("cost"),
+            recorded_at: DateTime::parse_from_rfc3339(&recorded_at)?.with_timezone(&Utc),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::entities::Rental;
+    use crate::persistence::SimplePersistence;
+
+    #[tokio::test]
+    async fn test_usage_ledger() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .unwrap();
+        let rental = Rental::new(
+            "miner1__exec1".to_string(),
+            "ssh-ed25519 AAAA".to_string(),
+            "ubuntu:22.04".to_string(),
+            None,
+            serde_json::json!({}),
+            serde_json::json!({}),
+            4,
+            2.0,
+        );
+        persistence.create_rental(&rental).await.unwrap();
+
+        let repo = RentalUsageRepository::new(persistence.pool().clone());
+        assert!(repo.last_period_end(&rental.id).await.unwrap().is_none());
+
+        let start = Utc::now() - chrono::Duration::hours(2);
+        let middle = start + chrono::Duration::minutes(90);
+        let end = start + chrono::Duration::hours(2);
+        repo.record_usage(&RentalUsageRecord::new(rental.id, middle, end, 2.0))
+            .await
+            .unwrap();
+        repo.record_usage(&RentalUsageRecord::new(rental.id, start, middle, 2.0))
+            .await
+            .unwrap();
+
+        let usage = repo.get_usage(&rental.id).await.unwrap();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].period_start.timestamp(), start.timestamp());
+        assert!((usage[0].hours - 1.5).abs() < 1e-6);
+        assert!((usage.iter().map(|r| r.cost).sum::<f64>() - 4.0).abs() < 1e-6);
+
+        let last = repo.last_period_end(&rental.id).await.unwrap().unwrap();
+        assert_eq!(last.timestamp(), end.timestamp());
+    }
+}
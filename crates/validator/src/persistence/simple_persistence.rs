@@ -1,9 +1,11 @@
 use chrono::{DateTime, Utc};
+use common::persistence::Cursor;
 use serde_json::Value;
-use sqlx::{Row, SqlitePool};
+use sqlx::{sqlite::SqliteRow, QueryBuilder, Row, Sqlite, SqlitePool};
 use tracing::info;
 use uuid::Uuid;
 
+use crate::api::types::{CapacitySortKey, SortOrder};
 use crate::persistence::entities::{Rental, RentalStatus, VerificationLog};
 
 /// Simplified persistence implementation for quick testing
@@ -327,6 +329,87 @@ impl SimplePersistence {
         .execute(&self.pool)
         .await?;
 
+        // GPU UUIDs claimed by a different miner than the current owner
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS gpu_uuid_conflicts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                gpu_uuid TEXT NOT NULL,
+                previous_miner_id TEXT NOT NULL,
+                previous_executor_id TEXT NOT NULL,
+                miner_id TEXT NOT NULL,
+                executor_id TEXT NOT NULL,
+                detected_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS slashing_cases (
+                id TEXT PRIMARY KEY,
+                executor_id TEXT NOT NULL,
+                miner_uid INTEGER NOT NULL,
+                offense TEXT NOT NULL,
+                status TEXT NOT NULL,
+                evidence_path TEXT NOT NULL,
+                evidence_url TEXT NOT NULL,
+                evidence_checksum TEXT NOT NULL,
+                error_message TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS rental_usage (
+                id TEXT PRIMARY KEY,
+                rental_id TEXT NOT NULL,
+                period_start TEXT NOT NULL,
+                period_end TEXT NOT NULL,
+                hours REAL NOT NULL,
+                cost REAL NOT NULL,
+                recorded_at TEXT NOT NULL,
+                FOREIGN KEY (rental_id) REFERENCES rentals (id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS weight_commits (
+                commit_hash TEXT PRIMARY KEY,
+                netuid INTEGER NOT NULL,
+                commit_data TEXT NOT NULL,
+                commit_block INTEGER NOT NULL,
+                reveal_start_block INTEGER NOT NULL,
+                reveal_end_block INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS executor_signing_keys (
+                executor_id TEXT PRIMARY KEY,
+                public_key TEXT NOT NULL,
+                first_seen_at TEXT NOT NULL,
+                last_seen_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS executor_evidence (
+                id TEXT PRIMARY KEY,
+                executor_id TEXT NOT NULL,
+                evidence_type TEXT NOT NULL,
+                nonce TEXT NOT NULL,
+                public_key TEXT NOT NULL,
+                signature TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                score REAL,
+                collected_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_gpu_conflicts_uuid ON gpu_uuid_conflicts(gpu_uuid);
+            CREATE INDEX IF NOT EXISTS idx_gpu_conflicts_detected ON gpu_uuid_conflicts(detected_at);
+            CREATE INDEX IF NOT EXISTS idx_slashing_cases_executor ON slashing_cases(executor_id, offense);
+            CREATE INDEX IF NOT EXISTS idx_slashing_cases_status ON slashing_cases(status);
+            CREATE INDEX IF NOT EXISTS idx_rental_usage_rental ON rental_usage(rental_id, period_end);
+            CREATE INDEX IF NOT EXISTS idx_weight_commits_status ON weight_commits(netuid, status);
+            CREATE INDEX IF NOT EXISTS idx_executor_evidence_executor ON executor_evidence(executor_id, collected_at);
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         Ok(())
     }
 
@@ -496,36 +579,103 @@ impl SimplePersistence {
         .fetch_all(&self.pool)
         .await?;
 
-        let mut entries = Vec::new();
-        for row in rows {
-            let executor_id: String = row.get("executor_id");
-            let total_verifications: i64 = row.get("total_verifications");
-            let successful_verifications: i64 = row.get("successful_verifications");
-            let avg_score: f64 = row.get("avg_score");
-            let last_verification: String = row.get("last_verification");
-            let latest_details: String = row.get("latest_details");
-
-            let success_rate = if total_verifications > 0 {
-                successful_verifications as f64 / total_verifications as f64
-            } else {
-                0.0
-            };
+        rows.into_iter().map(capacity_entry_from_row).collect()
+    }
+
+    /// List executors matching `filter`, ordered by `sort_by` and then by executor ID
+    ///
+    /// Returns up to `limit` executors following `after`, along with the
+    /// number of executors matching the filter across all pages.
+    pub async fn list_capacity(
+        &self,
+        filter: &CapacityFilter,
+        sort_by: CapacitySortKey,
+        order: SortOrder,
+        after: Option<&Cursor<f64>>,
+        limit: u32,
+    ) -> Result<(Vec<ListedCapacity>, u64), anyhow::Error> {
+        let mut count = capacity_query(filter, sort_by);
+        count.push(" SELECT COUNT(*) FROM listed");
+        let total_count: i64 = count.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let (past, direction) = match order {
+            SortOrder::Asc => (">", "ASC"),
+            SortOrder::Desc => ("<", "DESC"),
+        };
+        let mut page = capacity_query(filter, sort_by);
+        page.push(" SELECT * FROM listed");
+        if let Some(after) = after {
+            // Executor IDs break ties in ascending order either way
+            page.push(format_args!(" WHERE sort_value {past} "))
+                .push_bind(after.key)
+                .push(" OR (sort_value = ")
+                .push_bind(after.key)
+                .push(" AND executor_id > ")
+                .push_bind(after.id.clone())
+                .push(")");
+        }
+        page.push(format_args!(
+            " ORDER BY sort_value {direction}, executor_id ASC LIMIT "
+        ))
+        .push_bind(limit as i64);
+
+        let rows = page.build().fetch_all(&self.pool).await?;
+        let listed = rows
+            .into_iter()
+            .map(|row| {
+                let cost_per_hour: f64 = row.get("cost_per_hour");
+                let sort_value: f64 = row.get("sort_value");
+                Ok(ListedCapacity {
+                    entry: capacity_entry_from_row(row)?,
+                    cost_per_hour,
+                    sort_value,
+                })
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+        Ok((listed, total_count as u64))
+    }
 
-            let details: Value = serde_json::from_str(&latest_details).unwrap_or(Value::Null);
+    /// Get all verification logs recorded since a point in time, newest first per executor
+    pub async fn get_verification_logs_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<VerificationLog>, anyhow::Error> {
+        let rows = sqlx::query(
+            "SELECT id, executor_id, validator_hotkey, verification_type, timestamp,
+             score, success, details, duration_ms, error_message, created_at, updated_at
+             FROM verification_logs
+             WHERE timestamp >= ?
+             ORDER BY executor_id, timestamp DESC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
 
-            entries.push(CapacityEntry {
-                executor_id,
-                verification_score: avg_score,
-                success_rate,
-                last_verification: DateTime::parse_from_rfc3339(&last_verification)
-                    .unwrap()
-                    .with_timezone(&Utc),
-                hardware_info: details,
-                total_verifications: total_verifications as u64,
-            });
+        let mut logs = Vec::new();
+        for row in rows {
+            logs.push(self.row_to_verification_log(row)?);
         }
 
-        Ok(entries)
+        Ok(logs)
+    }
+
+    /// Time of the most recent successful verification of an executor
+    pub async fn get_last_successful_verification(
+        &self,
+        executor_id: &str,
+    ) -> Result<Option<DateTime<Utc>>, anyhow::Error> {
+        let timestamp: Option<String> = sqlx::query_scalar(
+            "SELECT MAX(timestamp) FROM verification_logs WHERE executor_id = ? AND success = 1",
+        )
+        .bind(executor_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(timestamp
+            .map(|ts| DateTime::parse_from_rfc3339(&ts))
+            .transpose()?
+            .map(|ts| ts.with_timezone(&Utc)))
     }
 
     /// Helper function to convert database row to VerificationLog
@@ -566,12 +716,7 @@ impl SimplePersistence {
             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
-        let status_str = match rental.status {
-            RentalStatus::Pending => "Pending",
-            RentalStatus::Active => "Active",
-            RentalStatus::Terminated => "Terminated",
-            RentalStatus::Failed => "Failed",
-        };
+        let status_str = rental.status.as_str();
 
         sqlx::query(query)
             .bind(rental.id.to_string())
@@ -624,22 +769,18 @@ impl SimplePersistence {
 
     /// Update rental record
     pub async fn update_rental(&self, rental: &Rental) -> Result<(), anyhow::Error> {
-        let status_str = match rental.status {
-            RentalStatus::Pending => "Pending",
-            RentalStatus::Active => "Active",
-            RentalStatus::Terminated => "Terminated",
-            RentalStatus::Failed => "Failed",
-        };
+        let status_str = rental.status.as_str();
 
         let query = r#"
             UPDATE rentals SET
-                status = ?, updated_at = ?, started_at = ?,
+                status = ?, ssh_access_info = ?, updated_at = ?, started_at = ?,
                 terminated_at = ?, termination_reason = ?, total_cost = ?
             WHERE id = ?
         "#;
 
         sqlx::query(query)
             .bind(status_str)
+            .bind(serde_json::to_string(&rental.ssh_access_info)?)
             .bind(rental.updated_at.to_rfc3339())
             .bind(rental.started_at.map(|dt| dt.to_rfc3339()))
             .bind(rental.terminated_at.map(|dt| dt.to_rfc3339()))
@@ -658,6 +799,35 @@ impl SimplePersistence {
         Ok(())
     }
 
+    /// Get all rentals that have not reached a final status
+    pub async fn get_open_rentals(&self) -> Result<Vec<Rental>, anyhow::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM rentals WHERE status NOT IN (?, ?) ORDER BY created_at")
+                .bind(RentalStatus::Terminated.as_str())
+                .bind(RentalStatus::Failed.as_str())
+                .fetch_all(&self.pool)
+                .await?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_rental(row))
+            .collect()
+    }
+
+    /// Get all rentals in a lifecycle status, oldest first
+    pub async fn get_rentals_by_status(
+        &self,
+        status: RentalStatus,
+    ) -> Result<Vec<Rental>, anyhow::Error> {
+        let rows = sqlx::query("SELECT * FROM rentals WHERE status = ? ORDER BY created_at")
+            .bind(status.as_str())
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_rental(row))
+            .collect()
+    }
+
     /// Helper function to convert database row to Rental
     fn row_to_rental(&self, row: sqlx::sqlite::SqliteRow) -> Result<Rental, anyhow::Error> {
         let id_str: String = row.get("id");
@@ -670,13 +840,7 @@ impl SimplePersistence {
         let started_at_str: Option<String> = row.get("started_at");
         let terminated_at_str: Option<String> = row.get("terminated_at");
 
-        let status = match status_str.as_str() {
-            "Pending" => RentalStatus::Pending,
-            "Active" => RentalStatus::Active,
-            "Terminated" => RentalStatus::Terminated,
-            "Failed" => RentalStatus::Failed,
-            _ => return Err(anyhow::anyhow!("Invalid rental status: {}", status_str)),
-        };
+        let status: RentalStatus = status_str.parse()?;
 
         Ok(Rental {
             id: Uuid::parse_str(&id_str)?,
@@ -867,6 +1031,19 @@ impl SimplePersistence {
         }
     }
 
+    /// Get the axon endpoint of a miner
+    pub async fn get_miner_endpoint(
+        &self,
+        miner_id: &str,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let row = sqlx::query("SELECT endpoint FROM miners WHERE id = ?")
+            .bind(miner_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|row| row.get("endpoint")))
+    }
+
     /// Update miner information
     pub async fn update_miner(
         &self,
@@ -1128,6 +1305,83 @@ impl SimplePersistence {
         Ok(results)
     }
 
+    /// Get (executor_id, gpu_name) for every GPU assigned to a miner
+    pub async fn get_miner_gpu_assignments(
+        &self,
+        miner_id: &str,
+    ) -> Result<Vec<(String, Option<String>)>, anyhow::Error> {
+        let rows = sqlx::query(
+            "SELECT executor_id, gpu_name FROM gpu_uuid_assignments
+             WHERE miner_id = ?
+             ORDER BY executor_id, gpu_index",
+        )
+        .bind(miner_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("executor_id"), row.get("gpu_name")))
+            .collect())
+    }
+
+    /// Record a GPU UUID being claimed by a different miner than its current owner
+    pub async fn record_gpu_uuid_conflict(
+        &self,
+        gpu_uuid: &str,
+        previous_miner_id: &str,
+        previous_executor_id: &str,
+        miner_id: &str,
+        executor_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "INSERT INTO gpu_uuid_conflicts
+             (gpu_uuid, previous_miner_id, previous_executor_id, miner_id, executor_id, detected_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(gpu_uuid)
+        .bind(previous_miner_id)
+        .bind(previous_executor_id)
+        .bind(miner_id)
+        .bind(executor_id)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get GPU UUID conflicts detected since a point in time, oldest first
+    pub async fn get_gpu_uuid_conflicts_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<GpuUuidConflict>, anyhow::Error> {
+        let rows = sqlx::query(
+            "SELECT gpu_uuid, previous_miner_id, previous_executor_id, miner_id, executor_id, detected_at
+             FROM gpu_uuid_conflicts
+             WHERE detected_at >= ?
+             ORDER BY gpu_uuid, detected_at",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut conflicts = Vec::new();
+        for row in rows {
+            let detected_at: String = row.get("detected_at");
+            conflicts.push(GpuUuidConflict {
+                gpu_uuid: row.get("gpu_uuid"),
+                previous_miner_id: row.get("previous_miner_id"),
+                previous_executor_id: row.get("previous_executor_id"),
+                miner_id: row.get("miner_id"),
+                executor_id: row.get("executor_id"),
+                detected_at: DateTime::parse_from_rfc3339(&detected_at)?.with_timezone(&Utc),
+            });
+        }
+
+        Ok(conflicts)
+    }
+
     /// Get total GPU count for a miner from gpu_uuid_assignments
     pub async fn get_miner_total_gpu_count_from_assignments(
         &self,
@@ -1178,6 +1432,178 @@ pub struct CapacityEntry {
     pub total_verifications: u64,
 }
 
+/// Filters for [`SimplePersistence::list_capacity`]
+#[derive(Debug, Clone, Default)]
+pub struct CapacityFilter {
+    pub min_score: Option<f64>,
+    pub min_success_rate: Option<f64>,
+    /// Memory of at least one GPU
+    pub min_gpu_memory_gb: Option<u32>,
+    /// Case-insensitive match against the GPU names
+    pub gpu_type: Option<String>,
+    pub min_gpu_count: Option<u32>,
+    pub max_cost_per_hour: Option<f64>,
+    /// Case-insensitive match against the executor location
+    pub region: Option<String>,
+    pub min_cpu_cores: Option<u32>,
+    pub min_ram_gb: Option<u32>,
+    pub min_disk_gb: Option<u32>,
+    pub min_bandwidth_mbps: Option<f64>,
+    /// Executors left out of the listing
+    pub excluded_executors: Vec<String>,
+}
+
+/// Executor returned by [`SimplePersistence::list_capacity`]
+#[derive(Debug, Clone)]
+pub struct ListedCapacity {
+    pub entry: CapacityEntry,
+    pub cost_per_hour: f64,
+    /// Value the listing is ordered by
+    pub sort_value: f64,
+}
+
+/// Start a query defining the executors matching `filter` as `listed`
+///
+/// Hardware is read from the latest verification details, which the
+/// listing also returns as `hardware_info`.
+fn capacity_query(
+    filter: &CapacityFilter,
+    sort_by: CapacitySortKey,
+) -> QueryBuilder<'static, Sqlite> {
+    let mut query = QueryBuilder::new(
+        "WITH stats AS (
+            SELECT
+                executor_id,
+                COUNT(*) AS total_verifications,
+                SUM(CASE WHEN success = 1 THEN 1 ELSE 0 END) AS successful_verifications,
+                AVG(score) AS avg_score,
+                MAX(timestamp) AS last_verification,
+                MAX(details) AS latest_details
+            FROM verification_logs
+            GROUP BY executor_id
+        ),
+        executors AS (
+            SELECT
+                *,
+                CAST(successful_verifications AS REAL) / total_verifications AS success_rate,
+                -- $1/hour scaled by score, at least 0.1x, with a 20% availability markup
+                MAX(avg_score, 0.1) * 1.2 AS cost_per_hour,
+                CASE WHEN json_valid(latest_details) THEN latest_details ELSE 'null' END AS hardware
+            FROM stats
+        ),
+        with_gpus AS (
+            SELECT
+                *,
+                CASE WHEN json_type(hardware, '$.gpu') = 'array'
+                    THEN json_extract(hardware, '$.gpu') ELSE '[]' END AS gpus
+            FROM executors
+        ),
+        listed AS (
+            SELECT *, CAST(",
+    );
+    query.push(match sort_by {
+        CapacitySortKey::Price => "cost_per_hour",
+        CapacitySortKey::Score => "avg_score",
+        CapacitySortKey::GpuMemory => {
+            "(SELECT TOTAL(json_extract(value, '$.memory_gb')) FROM json_each(gpus))"
+        }
+        CapacitySortKey::Uptime => "success_rate",
+    });
+    query
+        .push(" AS REAL) AS sort_value FROM with_gpus WHERE avg_score >= ")
+        .push_bind(filter.min_score.unwrap_or(0.0))
+        .push(" AND success_rate >= ")
+        .push_bind(filter.min_success_rate.unwrap_or(0.0));
+
+    if let Some(min_memory) = filter.min_gpu_memory_gb {
+        query
+            .push(
+                " AND EXISTS (SELECT 1 FROM json_each(gpus)
+                    WHERE json_extract(value, '$.memory_gb') >= ",
+            )
+            .push_bind(min_memory as i64)
+            .push(")");
+    }
+    if let Some(gpu_type) = &filter.gpu_type {
+        query
+            .push(
+                " AND EXISTS (SELECT 1 FROM json_each(gpus)
+                    WHERE instr(lower(json_extract(value, '$.name')), lower(",
+            )
+            .push_bind(gpu_type.clone())
+            .push(")) > 0)");
+    }
+    if let Some(min_count) = filter.min_gpu_count {
+        query
+            .push(" AND json_array_length(gpus) >= ")
+            .push_bind(min_count as i64);
+    }
+    if let Some(max_cost) = filter.max_cost_per_hour {
+        query.push(" AND cost_per_hour <= ").push_bind(max_cost);
+    }
+    if let Some(region) = &filter.region {
+        query
+            .push(" AND instr(lower(COALESCE(json_extract(hardware, '$.location'), '')), lower(")
+            .push_bind(region.clone())
+            .push(")) > 0");
+    }
+    if let Some(min_cores) = filter.min_cpu_cores {
+        query
+            .push(" AND COALESCE(json_extract(hardware, '$.cpu.cores'), 0) >= ")
+            .push_bind(min_cores as i64);
+    }
+    if let Some(min_ram) = filter.min_ram_gb {
+        // Reported either under `memory` or with the CPU
+        query
+            .push(
+                " AND COALESCE(json_extract(hardware, '$.memory.total_mb') / 1024.0,
+                    json_extract(hardware, '$.cpu.memory_gb'), 0) >= ",
+            )
+            .push_bind(min_ram as f64);
+    }
+    if let Some(min_disk) = filter.min_disk_gb {
+        query
+            .push(" AND COALESCE(json_extract(hardware, '$.disk.total_gb'), 0) >= ")
+            .push_bind(min_disk as f64);
+    }
+    if let Some(min_bandwidth) = filter.min_bandwidth_mbps {
+        query
+            .push(" AND COALESCE(json_extract(hardware, '$.network.bandwidth_mbps'), 0) >= ")
+            .push_bind(min_bandwidth);
+    }
+    if !filter.excluded_executors.is_empty() {
+        query
+            .push(" AND executor_id NOT IN (SELECT value FROM json_each(")
+            .push_bind(Value::from(filter.excluded_executors.clone()).to_string())
+            .push("))");
+    }
+
+    query.push(")");
+    query
+}
+
+fn capacity_entry_from_row(row: SqliteRow) -> Result<CapacityEntry, anyhow::Error> {
+    let total_verifications: i64 = row.get("total_verifications");
+    let successful_verifications: i64 = row.get("successful_verifications");
+    let last_verification: String = row.get("last_verification");
+    let latest_details: String = row.get("latest_details");
+
+    let success_rate = if total_verifications > 0 {
+        successful_verifications as f64 / total_verifications as f64
+    } else {
+        0.0
+    };
+
+    Ok(CapacityEntry {
+        executor_id: row.get("executor_id"),
+        verification_score: row.get("avg_score"),
+        success_rate,
+        last_verification: DateTime::parse_from_rfc3339(&last_verification)?.with_timezone(&Utc),
+        hardware_info: serde_json::from_str(&latest_details).unwrap_or(Value::Null),
+        total_verifications: total_verifications as u64,
+    })
+}
+
 /// Miner data for listings
 #[derive(Debug, Clone)]
 pub struct MinerData {
@@ -1206,6 +1632,17 @@ pub struct ExecutorHealthData {
     pub last_seen: DateTime<Utc>,
 }
 
+/// A GPU UUID claimed by a miner while assigned to another
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GpuUuidConflict {
+    pub gpu_uuid: String,
+    pub previous_miner_id: String,
+    pub previous_executor_id: String,
+    pub miner_id: String,
+    pub executor_id: String,
+    pub detected_at: DateTime<Utc>,
+}
+
 /// Executor details for miner listings
 #[derive(Debug, Clone)]
 pub struct ExecutorData {
@@ -1463,4 +1900,54 @@ mod tests {
                 .unwrap();
         assert_eq!(gpu_count, 1);
     }
+
+    #[tokio::test]
+    async fn test_rentals_by_status_track_ssh_access_updates() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+
+        let mut active = Rental::new(
+            "miner1__exec1".to_string(),
+            "ssh-ed25519 AAAA".to_string(),
+            "ubuntu:22.04".to_string(),
+            None,
+            serde_json::json!({}),
+            serde_json::json!({}),
+            2,
+            1.0,
+        );
+        let mut pending = Rental::new(
+            "miner1__exec2".to_string(),
+            "ssh-ed25519 AAAA".to_string(),
+            "ubuntu:22.04".to_string(),
+            None,
+            serde_json::json!({}),
+            serde_json::json!({}),
+            2,
+            1.0,
+        );
+        persistence.create_rental(&active).await.unwrap();
+        persistence.create_rental(&pending).await.unwrap();
+
+        active.ssh_access_info = serde_json::json!({ "container_id": "abc123" });
+        active.begin_provisioning().unwrap();
+        active.activate().unwrap();
+        persistence.update_rental(&active).await.unwrap();
+
+        let rentals = persistence
+            .get_rentals_by_status(RentalStatus::Active)
+            .await
+            .unwrap();
+        assert_eq!(rentals.len(), 1);
+        assert_eq!(rentals[0].id, active.id);
+        assert_eq!(rentals[0].ssh_access_info["container_id"], "abc123");
+
+        pending.terminate(None, 0.0).unwrap();
+        persistence.update_rental(&pending).await.unwrap();
+
+        let open = persistence.get_open_rentals().await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id, active.id);
+    }
 }
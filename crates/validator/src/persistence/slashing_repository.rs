@@ -0,0 +1,291 @@
+//! Slashing case repository
+//!
+//! Stores slashing cases raised by the slashing engine together with their
+//! evidence reference and review status
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use uuid::Uuid;
+
+/// Review and submission status of a slashing case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingCaseStatus {
+    /// Recorded in dry-run mode, never submitted
+    DryRun,
+    /// Waiting for an operator to approve or reject
+    PendingApproval,
+    /// Approved, will be submitted on the next engine pass
+    Approved,
+    /// Slash transaction submitted on chain
+    Submitted,
+    /// Rejected by an operator
+    Rejected,
+    /// Submission failed, see error message
+    Failed,
+}
+
+impl SlashingCaseStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SlashingCaseStatus::DryRun => "dry_run",
+            SlashingCaseStatus::PendingApproval => "pending_approval",
+            SlashingCaseStatus::Approved => "approved",
+            SlashingCaseStatus::Submitted => "submitted",
+            SlashingCaseStatus::Rejected => "rejected",
+            SlashingCaseStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for SlashingCaseStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dry_run" => Ok(SlashingCaseStatus::DryRun),
+            "pending_approval" => Ok(SlashingCaseStatus::PendingApproval),
+            "approved" => Ok(SlashingCaseStatus::Approved),
+            "submitted" => Ok(SlashingCaseStatus::Submitted),
+            "rejected" => Ok(SlashingCaseStatus::Rejected),
+            "failed" => Ok(SlashingCaseStatus::Failed),
+            _ => Err(anyhow!("Unknown slashing case status: {}", s)),
+        }
+    }
+}
+
+/// A slashable offense with its evidence bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashingCase {
+    pub id: Uuid,
+    /// Unique executor id (`miner{uid}__{executor_id}`)
+    pub executor_id: String,
+    pub miner_uid: u16,
+    pub offense: String,
+    pub status: SlashingCaseStatus,
+    pub evidence_path: String,
+    pub evidence_url: String,
+    /// Hex-encoded MD5 checksum of the evidence bundle
+    pub evidence_checksum: String,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Repository for slashing cases
+pub struct SlashingRepository {
+    pool: SqlitePool,
+}
+
+impl SlashingRepository {
+    /// Create a new repository instance
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Store a new slashing case
+    pub async fn create_case(&self, case: &SlashingCase) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO slashing_cases (
+                id, executor_id, miner_uid, offense, status, evidence_path,
+                evidence_url, evidence_checksum, error_message, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(case.id.to_string())
+        .bind(&case.executor_id)
+        .bind(case.miner_uid as i64)
+        .bind(&case.offense)
+        .bind(case.status.as_str())
+        .bind(&case.evidence_path)
+        .bind(&case.evidence_url)
+        .bind(&case.evidence_checksum)
+        .bind(&case.error_message)
+        .bind(case.created_at.to_rfc3339())
+        .bind(case.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get a case by id
+    pub async fn get_case(&self, id: &Uuid) -> Result<Option<SlashingCase>> {
+        let row = sqlx::query("SELECT * FROM slashing_cases WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(Self::row_to_case).transpose()
+    }
+
+    /// List cases, newest first, optionally filtered by status
+    pub async fn list_cases(
+        &self,
+        status: Option<SlashingCaseStatus>,
+        limit: u32,
+    ) -> Result<Vec<SlashingCase>> {
+        let rows = match status {
+            Some(status) => sqlx::query(
+                "SELECT * FROM slashing_cases WHERE status = ? ORDER BY created_at DESC LIMIT ?",
+            )
+            .bind(status.as_str())
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?,
+            None => {
+                sqlx::query("SELECT * FROM slashing_cases ORDER BY created_at DESC LIMIT ?")
+                    .bind(limit as i64)
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+        };
+
+        rows.into_iter().map(Self::row_to_case).collect()
+    }
+
+    /// Whether a non-rejected case for this executor and offense was raised since `since`
+    pub async fn has_recent_case(
+        &self,
+        executor_id: &str,
+        offense: &str,
+        since: DateTime<Utc>,
+    ) -> Result<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM slashing_cases
+             WHERE executor_id = ? AND offense = ? AND status != 'rejected' AND created_at >= ?",
+        )
+        .bind(executor_id)
+        .bind(offense)
+        .bind(since.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    /// Move a case from one status to another
+    ///
+    /// Returns false if the case was not in the expected status.
+    pub async fn transition(
+        &self,
+        id: &Uuid,
+        from: SlashingCaseStatus,
+        to: SlashingCaseStatus,
+        error_message: Option<&str>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE slashing_cases SET status = ?, error_message = ?, updated_at = ?
+             WHERE id = ? AND status = ?",
+        )
+        .bind(to.as_str())
+        .bind(error_message)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id.to_string())
+        .bind(from.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn row_to_case(row: sqlx::sqlite::SqliteRow) -> Result<SlashingCase> {
+        let id: String = row.get("id");
+        let status: String = row.get("status");
+        let miner_uid: i64 = row.get("miner_uid");
+        let created_at: String = row.get("created_at");
+        let updated_at: String = row.get("updated_at");
+
+        Ok(SlashingCase {
+            id: Uuid::parse_str(&id)?,
+            executor_id: row.get("executor_id"),
+            miner_uid: miner_uid as u16,
+            offense: row.get("offense"),
+            status: status.parse()?,
+            evidence_path: row.get("evidence_path"),
+            evidence_url: row.get("evidence_url"),
+            evidence_checksum: row.get("evidence_checksum"),
+            error_message: row.get("error_message"),
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::SimplePersistence;
+
+    fn case(status: SlashingCaseStatus) -> SlashingCase {
+        let now = Utc::now();
+        SlashingCase {
+            id: Uuid::new_v4(),
+            executor_id: "miner1__exec1".to_string(),
+            miner_uid: 1,
+            offense: "attestation_failure".to_string(),
+            status,
+            evidence_path: "/tmp/evidence.json".to_string(),
+            evidence_url: "http://localhost/evidence.json".to_string(),
+            evidence_checksum: "00".repeat(16),
+            error_message: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_case_lifecycle() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .unwrap();
+        let repo = SlashingRepository::new(persistence.pool().clone());
+
+        let case = case(SlashingCaseStatus::PendingApproval);
+        repo.create_case(&case).await.unwrap();
+
+        let since = Utc::now() - chrono::Duration::hours(1);
+        assert!(repo
+            .has_recent_case("miner1__exec1", "attestation_failure", since)
+            .await
+            .unwrap());
+        assert!(!repo
+            .has_recent_case("miner1__exec1", "gpu_uuid_reuse", since)
+            .await
+            .unwrap());
+
+        // Only pending cases can be approved
+        assert!(!repo
+            .transition(
+                &case.id,
+                SlashingCaseStatus::Approved,
+                SlashingCaseStatus::Submitted,
+                None
+            )
+            .await
+            .unwrap());
+        assert!(repo
+            .transition(
+                &case.id,
+                SlashingCaseStatus::PendingApproval,
+                SlashingCaseStatus::Approved,
+                None
+            )
+            .await
+            .unwrap());
+
+        let approved = repo
+            .list_cases(Some(SlashingCaseStatus::Approved), 10)
+            .await
+            .unwrap();
+        assert_eq!(approved.len(), 1);
+        assert_eq!(approved[0].id, case.id);
+
+        let loaded = repo.get_case(&case.id).await.unwrap().unwrap();
+        assert_eq!(loaded.status, SlashingCaseStatus::Approved);
+        assert_eq!(loaded.miner_uid, 1);
+    }
+}
@@ -0,0 +1,191 @@
+//! Weight commit repository
+//!
+//! Keeps commit-reveal weight commits, including their salts, until they are
+//! revealed so a restart between commit and reveal does not forfeit them
+
+use anyhow::{anyhow, Result};
+use bittensor::WeightCommit;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+
+/// Lifecycle of a weight commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightCommitStatus {
+    /// Committed on chain, waiting for its reveal window
+    Pending,
+    /// Weights revealed on chain
+    Revealed,
+    /// Reveal window passed without a successful reveal
+    Expired,
+}
+
+impl WeightCommitStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeightCommitStatus::Pending => "pending",
+            WeightCommitStatus::Revealed => "revealed",
+            WeightCommitStatus::Expired => "expired",
+        }
+    }
+}
+
+impl std::str::FromStr for WeightCommitStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(WeightCommitStatus::Pending),
+            "revealed" => Ok(WeightCommitStatus::Revealed),
+            "expired" => Ok(WeightCommitStatus::Expired),
+            _ => Err(anyhow!("Unknown weight commit status: {}", s)),
+        }
+    }
+}
+
+/// A weight commit with its reveal window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredWeightCommit {
+    pub commit: WeightCommit,
+    /// First block at which the commit can be revealed
+    pub reveal_start_block: u64,
+    /// Last block at which the commit can be revealed
+    pub reveal_end_block: u64,
+    pub status: WeightCommitStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Repository for weight commits
+pub struct WeightCommitRepository {
+    pool: SqlitePool,
+}
+
+impl WeightCommitRepository {
+    /// Create a new repository instance
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a new pending commit
+    pub async fn store_commit(
+        &self,
+        commit: &WeightCommit,
+        reveal_start_block: u64,
+        reveal_end_block: u64,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO weight_commits (
+                commit_hash, netuid, commit_data, commit_block, reveal_start_block,
+                reveal_end_block, status, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&commit.commit_hash)
+        .bind(commit.netuid as i64)
+        .bind(serde_json::to_string(commit)?)
+        .bind(commit.commit_block as i64)
+        .bind(reveal_start_block as i64)
+        .bind(reveal_end_block as i64)
+        .bind(WeightCommitStatus::Pending.as_str())
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Pending commits of a subnet, oldest first
+    pub async fn get_pending_commits(&self, netuid: u16) -> Result<Vec<StoredWeightCommit>> {
+        let rows = sqlx::query(
+            "SELECT * FROM weight_commits WHERE netuid = ? AND status = ? ORDER BY commit_block ASC",
+        )
+        .bind(netuid as i64)
+        .bind(WeightCommitStatus::Pending.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_commit).collect()
+    }
+
+    /// Move a commit to a new status
+    pub async fn update_status(&self, commit_hash: &str, status: WeightCommitStatus) -> Result<()> {
+        sqlx::query("UPDATE weight_commits SET status = ?, updated_at = ? WHERE commit_hash = ?")
+            .bind(status.as_str())
+            .bind(Utc::now().to_rfc3339())
+            .bind(commit_hash)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    fn row_to_commit(row: sqlx::sqlite::SqliteRow) -> Result<StoredWeightCommit> {
+        let commit_data: String = row.get("commit_data");
+        let status: String = row.get("status");
+        let created_at: String = row.get("created_at");
+        let updated_at: String = row.get("updated_at");
+
+        Ok(StoredWeightCommit {
+            commit: serde_json::from_str(&commit_data)?,
+            reveal_start_block: row.get::<i64, _>("reveal_start_block") as u64,
+            reveal_end_block: row.get::<i64, _>("reveal_end_block") as u64,
+            status: status.parse()?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::SimplePersistence;
+
+    fn commit(commit_hash: &str, commit_block: u64) -> WeightCommit {
+        WeightCommit {
+            netuid: 39,
+            uids: vec![1, 2],
+            values: vec![100, 200],
+            salt: vec![7; 8],
+            version_key: 1,
+            commit_hash: commit_hash.to_string(),
+            commit_block,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pending_commits_survive_until_resolved() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .unwrap();
+        let repo = WeightCommitRepository::new(persistence.pool().clone());
+
+        repo.store_commit(&commit("bb", 2000), 2200, 2560)
+            .await
+            .unwrap();
+        repo.store_commit(&commit("aa", 1000), 1100, 1460)
+            .await
+            .unwrap();
+
+        let pending = repo.get_pending_commits(39).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].commit, commit("aa", 1000));
+        assert_eq!(pending[0].reveal_start_block, 1100);
+        assert_eq!(pending[0].reveal_end_block, 1460);
+        assert!(repo.get_pending_commits(1).await.unwrap().is_empty());
+
+        repo.update_status("aa", WeightCommitStatus::Revealed)
+            .await
+            .unwrap();
+        repo.update_status("bb", WeightCommitStatus::Expired)
+            .await
+            .unwrap();
+        assert!(repo.get_pending_commits(39).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+//! # Rentals
+//!
+//! Provisioning and lifecycle of rented capacity. Rental containers are
+//! created on the executor through the miner's `ManageExecutorContainer`
+//! RPC, which forwards to the executor's `ManageContainer`. The supervisor
+//! moves rentals through `Pending → Provisioning → Active → Terminating →
+//! Terminated`, failing them when the executor or container goes away, and
+//! records billed usage to the rental usage ledger.
+
+pub mod provisioner;
+pub mod supervisor;
+
+pub use provisioner::{RentalContainer, RentalContainers, RentalProvisioner};
+pub use supervisor::RentalSupervisor;
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the rental supervisor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RentalConfig {
+    /// Seconds between supervisor passes, which is also the usage ledger granularity
+    pub supervisor_interval_secs: u64,
+
+    /// Minutes without a successful verification before an executor's rentals fail
+    pub executor_health_window_minutes: i64,
+
+    /// Minutes a rental may spend provisioning before it is failed
+    pub provisioning_timeout_minutes: i64,
+
+    /// Whether active rental containers are checked on their executor each pass
+    pub check_container_health: bool,
+}
+
+impl Default for RentalConfig {
+    fn default() -> Self {
+        Self {
+            supervisor_interval_secs: 60,
+            executor_health_window_minutes: 60,
+            provisioning_timeout_minutes: 30,
+            check_container_health: true,
+        }
+    }
+}
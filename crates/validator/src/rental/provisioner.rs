@@ -0,0 +1,332 @@
+//! Rental container provisioning through miners
+
+use crate::config::VerificationConfig;
+use crate::miner_prover::miner_client::{
+    AuthenticatedMinerConnection, BittensorServiceSigner, MinerClient, MinerClientConfig,
+};
+use crate::persistence::entities::Rental;
+use crate::persistence::SimplePersistence;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use common::identity::Hotkey;
+use protocol::common::ContainerSpec;
+use protocol::executor_control::ContainerOpRequest;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Container port renters connect to over SSH
+const SSH_CONTAINER_PORT: &str = "22/tcp";
+
+/// User reported when the executor could not determine the container user
+const DEFAULT_SSH_USER: &str = "root";
+
+/// Container creation includes the image pull on the executor, so calls get
+/// considerably more time than discovery does
+const CONTAINER_OPERATION_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// A running rental container and how to reach it
+#[derive(Debug, Clone)]
+pub struct RentalContainer {
+    pub container_id: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+}
+
+/// Container operations the rental supervisor relies on
+#[async_trait]
+pub trait RentalContainers: Send + Sync {
+    /// Start the rental's container on its executor
+    async fn provision(&self, rental: &Rental) -> Result<RentalContainer>;
+
+    /// Destroy the rental's container, if one was provisioned
+    async fn teardown(&self, rental: &Rental) -> Result<()>;
+
+    /// Current state of the rental's container as reported by the executor
+    ///
+    /// Returns `None` if no container was provisioned for the rental.
+    async fn container_status(&self, rental: &Rental) -> Result<Option<String>>;
+}
+
+/// Starts and stops rental containers on executors via their miners
+pub struct RentalProvisioner {
+    persistence: Arc<SimplePersistence>,
+    miner_client_config: MinerClientConfig,
+    validator_hotkey: Hotkey,
+    bittensor_service: Arc<bittensor::Service>,
+}
+
+impl RentalProvisioner {
+    /// Create a new rental provisioner
+    pub fn new(
+        verification: &VerificationConfig,
+        bittensor_service: Arc<bittensor::Service>,
+        persistence: Arc<SimplePersistence>,
+    ) -> Result<Self> {
+        let validator_hotkey = bittensor::account_id_to_hotkey(bittensor_service.get_account_id())
+            .map_err(|e| anyhow!("Failed to convert account ID to hotkey: {}", e))?;
+
+        Ok(Self {
+            persistence,
+            miner_client_config: MinerClientConfig {
+                timeout: CONTAINER_OPERATION_TIMEOUT,
+                grpc_port_offset: verification.grpc_port_offset,
+                ..Default::default()
+            },
+            validator_hotkey,
+            bittensor_service,
+        })
+    }
+}
+
+#[async_trait]
+impl RentalContainers for RentalProvisioner {
+    async fn provision(&self, rental: &Rental) -> Result<RentalContainer> {
+        let (mut connection, executor_id) = self.connect(&rental.executor_id).await?;
+
+        let lease_duration = Duration::from_secs(rental.max_duration_hours as u64 * 3600);
+        let executor = connection
+            .request_executors(None, lease_duration)
+            .await?
+            .into_iter()
+            .find(|details| details.executor_id == executor_id)
+            .ok_or_else(|| anyhow!("Executor {} is not available on its miner", executor_id))?;
+        let host = executor_host(&executor.grpc_endpoint)?;
+
+        let spec = ContainerSpec {
+            image: rental.docker_image.clone(),
+            environment: rental_environment(rental),
+            port_mappings: HashMap::from([(SSH_CONTAINER_PORT.to_string(), String::new())]),
+            // Rentals take the whole executor
+            gpu_requirements: vec!["all".to_string()],
+            ..Default::default()
+        };
+
+        let response = connection
+            .manage_executor_container(
+                &executor_id,
+                ContainerOpRequest {
+                    operation: "create".to_string(),
+                    container_spec: Some(spec),
+                    ssh_public_key: rental.customer_public_key.clone(),
+                    validator_hotkey: self.validator_hotkey.to_string(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let container_id = response.container_id;
+        let (port, username) = match parse_create_details(&response.details) {
+            Ok(access) => access,
+            Err(e) => {
+                // Do not leave an unreachable container running on the executor
+                if let Err(destroy_err) = self
+                    .destroy_container(&mut connection, &executor_id, &container_id)
+                    .await
+                {
+                    warn!(
+                        "Failed to clean up container {} for rental {}: {}",
+                        container_id, rental.id, destroy_err
+                    );
+                }
+                return Err(e);
+            }
+        };
+
+        info!(
+            "Provisioned container {} for rental {} on executor {} ({}:{})",
+            container_id, rental.id, rental.executor_id, host, port
+        );
+
+        Ok(RentalContainer {
+            container_id,
+            host,
+            port,
+            username: username.unwrap_or_else(|| DEFAULT_SSH_USER.to_string()),
+        })
+    }
+
+    async fn teardown(&self, rental: &Rental) -> Result<()> {
+        let Some(container_id) = rental.container_id() else {
+            return Ok(());
+        };
+
+        let (mut connection, executor_id) = self.connect(&rental.executor_id).await?;
+        self.destroy_container(&mut connection, &executor_id, container_id)
+            .await?;
+
+        info!(
+            "Destroyed container {} for rental {} on executor {}",
+            container_id, rental.id, rental.executor_id
+        );
+        Ok(())
+    }
+
+    async fn container_status(&self, rental: &Rental) -> Result<Option<String>> {
+        let Some(container_id) = rental.container_id() else {
+            return Ok(None);
+        };
+
+        let (mut connection, executor_id) = self.connect(&rental.executor_id).await?;
+        let response = connection
+            .manage_executor_container(
+                &executor_id,
+                ContainerOpRequest {
+                    operation: "get_status".to_string(),
+                    container_id: container_id.to_string(),
+                    validator_hotkey: self.validator_hotkey.to_string(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        Ok(Some(
+            response
+                .status
+                .map(|status| status.status)
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+impl RentalProvisioner {
+    /// Authenticate with the miner owning a unique executor ID
+    async fn connect(
+        &self,
+        unique_executor_id: &str,
+    ) -> Result<(AuthenticatedMinerConnection, String)> {
+        let (miner_uid, executor_id) =
+            crate::slashing::parse_unique_executor_id(unique_executor_id)
+                .ok_or_else(|| anyhow!("Invalid unique executor ID: {}", unique_executor_id))?;
+
+        let miner_id = format!("miner_{miner_uid}");
+        let endpoint = self
+            .persistence
+            .get_miner_endpoint(&miner_id)
+            .await?
+            .ok_or_else(|| anyhow!("No endpoint known for {}", miner_id))?;
+
+        let client = MinerClient::with_signer(
+            self.miner_client_config.clone(),
+            self.validator_hotkey.clone(),
+            Box::new(BittensorServiceSigner::new(self.bittensor_service.clone())),
+        );
+        let connection = client
+            .connect_and_authenticate(&endpoint)
+            .await
+            .with_context(|| format!("Failed to connect to {miner_id} at {endpoint}"))?;
+
+        Ok((connection, executor_id.to_string()))
+    }
+
+    async fn destroy_container(
+        &self,
+        connection: &mut AuthenticatedMinerConnection,
+        executor_id: &str,
+        container_id: &str,
+    ) -> Result<()> {
+        connection
+            .manage_executor_container(
+                executor_id,
+                ContainerOpRequest {
+                    operation: "destroy".to_string(),
+                    container_id: container_id.to_string(),
+                    parameters: HashMap::from([("force".to_string(), "true".to_string())]),
+                    validator_hotkey: self.validator_hotkey.to_string(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Environment variables requested for the rental
+fn rental_environment(rental: &Rental) -> HashMap<String, String> {
+    rental
+        .env_vars
+        .as_ref()
+        .and_then(|vars| vars.as_object())
+        .map(|vars| {
+            vars.iter()
+                .map(|(key, value)| {
+                    let value = value
+                        .as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| value.to_string());
+                    (key.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Host renters reach an executor on, taken from its gRPC endpoint
+fn executor_host(grpc_endpoint: &str) -> Result<String> {
+    let endpoint = if grpc_endpoint.contains("://") {
+        grpc_endpoint.to_string()
+    } else {
+        format!("http://{grpc_endpoint}")
+    };
+
+    let url = url::Url::parse(&endpoint)
+        .with_context(|| format!("Invalid executor endpoint: {grpc_endpoint}"))?;
+    url.host_str()
+        .map(|host| host.trim_matches(|c| c == '[' || c == ']').to_string())
+        .ok_or_else(|| anyhow!("No host in executor endpoint: {}", grpc_endpoint))
+}
+
+/// Published SSH port and container user from the executor's create details
+fn parse_create_details(details: &str) -> Result<(u16, Option<String>)> {
+    let details: serde_json::Value = serde_json::from_str(details)
+        .with_context(|| format!("Unexpected container create details: {details}"))?;
+
+    let port = details
+        .get("ports")
+        .and_then(|ports| ports.get(SSH_CONTAINER_PORT))
+        .and_then(|port| port.as_u64())
+        .and_then(|port| u16::try_from(port).ok())
+        .ok_or_else(|| anyhow!("Executor did not publish an SSH port for the container"))?;
+
+    let username = details
+        .get("ssh_user")
+        .and_then(|user| user.as_str())
+        .map(String::from);
+
+    Ok((port, username))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_executor_host() {
+        assert_eq!(
+            executor_host("http://185.26.8.109:50051").unwrap(),
+            "185.26.8.109"
+        );
+        assert_eq!(
+            executor_host("executor.example.com:50051").unwrap(),
+            "executor.example.com"
+        );
+        assert_eq!(executor_host("http://[::1]:50051").unwrap(), "::1");
+    }
+
+    #[test]
+    fn test_parse_create_details() {
+        let (port, user) =
+            parse_create_details(r#"{"ports":{"22/tcp":32768},"ssh_user":"ubuntu"}"#).unwrap();
+        assert_eq!(port, 32768);
+        assert_eq!(user.as_deref(), Some("ubuntu"));
+
+        let (_, user) =
+            parse_create_details(r#"{"ports":{"22/tcp":2222},"ssh_user":null}"#).unwrap();
+        assert_eq!(user, None);
+
+        assert!(parse_create_details(r#"{"ports":{}}"#).is_err());
+        assert!(parse_create_details("Container created successfully").is_err());
+    }
+}
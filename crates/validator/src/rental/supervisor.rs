@@ -0,0 +1,444 @@
+//! Rental lifecycle supervision
+//!
+//! Drives rentals through their lifecycle, terminates them at expiry, fails
+//! them when their executor or container stops responding, and records the
+//! usage billed for each supervisor interval.
+
+use super::{RentalConfig, RentalContainer, RentalContainers};
+use crate::persistence::entities::{Rental, RentalStatus};
+use crate::persistence::rental_usage_repository::{RentalUsageRecord, RentalUsageRepository};
+use crate::persistence::SimplePersistence;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+/// Container state reported by the executor for a healthy rental
+const CONTAINER_RUNNING: &str = "running";
+
+/// Owns every state change of a rental after it has been created
+pub struct RentalSupervisor {
+    config: RentalConfig,
+    persistence: Arc<SimplePersistence>,
+    provisioner: Arc<dyn RentalContainers>,
+    usage: RentalUsageRepository,
+}
+
+impl RentalSupervisor {
+    /// Create a new rental supervisor
+    pub fn new(
+        config: RentalConfig,
+        persistence: Arc<SimplePersistence>,
+        provisioner: Arc<dyn RentalContainers>,
+    ) -> Self {
+        let usage = RentalUsageRepository::new(persistence.pool().clone());
+        Self {
+            config,
+            persistence,
+            provisioner,
+            usage,
+        }
+    }
+
+    /// Provision a pending rental and activate it
+    ///
+    /// The rental is persisted at every step, so a failure leaves it failed
+    /// rather than stuck in provisioning. A container that finishes starting
+    /// after the supervisor timed the rental out is removed again.
+    pub async fn start_rental(&self, rental: &mut Rental) -> Result<RentalContainer> {
+        rental.begin_provisioning()?;
+        self.persistence.update_rental(rental).await?;
+
+        let container = match self.provisioner.provision(rental).await {
+            Ok(container) => container,
+            Err(e) => {
+                rental.fail(format!("provisioning_failed: {e}"))?;
+                self.persistence.update_rental(rental).await?;
+                return Err(e);
+            }
+        };
+
+        rental.ssh_access_info = serde_json::json!({
+            "host": container.host,
+            "port": container.port,
+            "username": container.username,
+            "container_id": container.container_id,
+        });
+
+        let timed_out = self
+            .persistence
+            .get_rental(&rental.id)
+            .await?
+            .is_some_and(|current| current.status != RentalStatus::Provisioning);
+        if timed_out {
+            if let Err(e) = self.provisioner.teardown(rental).await {
+                warn!(
+                    "Failed to tear down container for timed out rental {}: {}",
+                    rental.id, e
+                );
+            }
+            return Err(anyhow!("Rental {} timed out while provisioning", rental.id));
+        }
+        rental.activate()?;
+
+        if let Err(e) = self.persistence.update_rental(rental).await {
+            // An unrecorded container would run unbilled, so remove it
+            if let Err(teardown_err) = self.provisioner.teardown(rental).await {
+                warn!(
+                    "Failed to tear down container for unrecorded rental {}: {}",
+                    rental.id, teardown_err
+                );
+            }
+            return Err(e);
+        }
+
+        info!("Rental {} is active", rental.id);
+        Ok(container)
+    }
+
+    /// Stop billing a rental and tear its container down
+    ///
+    /// If the teardown fails the rental stays `Terminating` and the
+    /// supervisor retries it on its next pass.
+    pub async fn terminate_rental(
+        &self,
+        rental: &mut Rental,
+        reason: Option<String>,
+    ) -> Result<()> {
+        if rental.status == RentalStatus::Pending {
+            rental.terminate(reason, 0.0)?;
+            return self.persistence.update_rental(rental).await;
+        }
+
+        if rental.status != RentalStatus::Terminating {
+            rental.begin_termination(reason)?;
+            self.persistence.update_rental(rental).await?;
+        }
+
+        self.finish_termination(rental).await
+    }
+
+    /// Run the supervisor until the task is aborted
+    pub async fn start(&self) {
+        info!(
+            "Starting rental supervisor - checking every {} seconds",
+            self.config.supervisor_interval_secs
+        );
+
+        let mut interval = interval(Duration::from_secs(
+            self.config.supervisor_interval_secs.max(1),
+        ));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.run_once().await {
+                error!("Rental supervisor pass failed: {}", e);
+            }
+        }
+    }
+
+    /// One supervisor pass over all non-final rentals
+    pub async fn run_once(&self) -> Result<()> {
+        let now = Utc::now();
+
+        for mut rental in self
+            .persistence
+            .get_rentals_by_status(RentalStatus::Terminating)
+            .await?
+        {
+            if let Err(e) = self.finish_termination(&mut rental).await {
+                warn!("Failed to terminate rental {}: {}", rental.id, e);
+            }
+        }
+
+        for mut rental in self
+            .persistence
+            .get_rentals_by_status(RentalStatus::Provisioning)
+            .await?
+        {
+            let deadline = rental.updated_at
+                + chrono::Duration::minutes(self.config.provisioning_timeout_minutes);
+            if deadline <= now {
+                warn!("Rental {} timed out while provisioning", rental.id);
+                // A container may have been created before the timeout
+                if let Err(e) = self.provisioner.teardown(&rental).await {
+                    warn!(
+                        "Failed to tear down container of timed out rental {}: {}",
+                        rental.id, e
+                    );
+                }
+                rental.fail("provisioning_timeout".to_string())?;
+                self.persistence.update_rental(&rental).await?;
+            }
+        }
+
+        for mut rental in self
+            .persistence
+            .get_rentals_by_status(RentalStatus::Active)
+            .await?
+        {
+            if let Err(e) = self.supervise_active(&mut rental, now).await {
+                warn!("Failed to supervise rental {}: {}", rental.id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn supervise_active(&self, rental: &mut Rental, now: DateTime<Utc>) -> Result<()> {
+        if matches!(rental.expires_at(), Some(expires_at) if expires_at <= now) {
+            info!(
+                "Rental {} reached its maximum duration of {} hours",
+                rental.id, rental.max_duration_hours
+            );
+            return self
+                .terminate_rental(rental, Some("max_duration_exceeded".to_string()))
+                .await;
+        }
+
+        if !self.executor_healthy(rental, now).await? {
+            warn!(
+                "Executor {} of rental {} has not verified within {} minutes",
+                rental.executor_id, rental.id, self.config.executor_health_window_minutes
+            );
+            return self.fail_active(rental, "executor_unhealthy").await;
+        }
+
+        if self.config.check_container_health {
+            match self.provisioner.container_status(rental).await {
+                Ok(Some(status)) if !status.eq_ignore_ascii_case(CONTAINER_RUNNING) => {
+                    warn!(
+                        "Container of rental {} is no longer running: {}",
+                        rental.id, status
+                    );
+                    return self
+                        .fail_active(rental, &format!("container_{}", status.to_lowercase()))
+                        .await;
+                }
+                Ok(_) => {}
+                // An unreachable miner is covered by the executor health window
+                Err(e) => warn!("Failed to check container of rental {}: {}", rental.id, e),
+            }
+        }
+
+        self.record_usage(rental).await
+    }
+
+    /// Whether the rental's executor verified successfully within the health window
+    ///
+    /// Rentals younger than the window are given the benefit of the doubt.
+    async fn executor_healthy(&self, rental: &Rental, now: DateTime<Utc>) -> Result<bool> {
+        let window = chrono::Duration::minutes(self.config.executor_health_window_minutes);
+        if rental
+            .started_at
+            .is_some_and(|started| now - started < window)
+        {
+            return Ok(true);
+        }
+
+        let last_success = self
+            .persistence
+            .get_last_successful_verification(&rental.executor_id)
+            .await?;
+        Ok(last_success.is_some_and(|verified| now - verified < window))
+    }
+
+    /// Fail an active rental, billing it up to now and removing what is left of its container
+    async fn fail_active(&self, rental: &mut Rental, reason: &str) -> Result<()> {
+        self.record_usage(rental).await?;
+        rental.fail(reason.to_string())?;
+        self.persistence.update_rental(rental).await?;
+
+        if let Err(e) = self.provisioner.teardown(rental).await {
+            warn!(
+                "Failed to tear down container of failed rental {}: {}",
+                rental.id, e
+            );
+        }
+        Ok(())
+    }
+
+    async fn finish_termination(&self, rental: &mut Rental) -> Result<()> {
+        self.provisioner.teardown(rental).await?;
+        self.record_usage(rental).await?;
+
+        let total_cost = rental.current_cost();
+        rental.terminate(None, total_cost)?;
+        self.persistence.update_rental(rental).await?;
+
+        info!(
+            "Rental {} terminated, total cost: ${:.2}",
+            rental.id, total_cost
+        );
+        Ok(())
+    }
+
+    /// Record usage from the end of the last recorded period to the billing end
+    async fn record_usage(&self, rental: &Rental) -> Result<()> {
+        let Some(period_end) = rental.billing_end() else {
+            return Ok(());
+        };
+
+        let period_start = match self.usage.last_period_end(&rental.id).await? {
+            Some(last) => last,
+            None => match rental.started_at {
+                Some(started) => started,
+                None => return Ok(()),
+            },
+        };
+
+        if period_end <= period_start {
+            return Ok(());
+        }
+
+        self.usage
+            .record_usage(&RentalUsageRecord::new(
+                rental.id,
+                period_start,
+                period_end,
+                rental.cost_per_hour,
+            ))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Records teardowns and can fail them or time the rental out mid-provisioning
+    #[derive(Default)]
+    struct FakeContainers {
+        persistence: Option<Arc<SimplePersistence>>,
+        fail_teardown: bool,
+        torn_down: Mutex<Vec<(Uuid, Option<String>)>>,
+    }
+
+    #[async_trait]
+    impl RentalContainers for FakeContainers {
+        async fn provision(&self, rental: &Rental) -> Result<RentalContainer> {
+            if let Some(persistence) = &self.persistence {
+                let mut current = persistence.get_rental(&rental.id).await?.unwrap();
+                current.fail("provisioning_timeout".to_string())?;
+                persistence.update_rental(&current).await?;
+            }
+            Ok(RentalContainer {
+                container_id: "container-1".to_string(),
+                host: "203.0.113.10".to_string(),
+                port: 32768,
+                username: "root".to_string(),
+            })
+        }
+
+        async fn teardown(&self, rental: &Rental) -> Result<()> {
+            self.torn_down
+                .lock()
+                .unwrap()
+                .push((rental.id, rental.container_id().map(String::from)));
+            if self.fail_teardown {
+                return Err(anyhow!("executor unreachable"));
+            }
+            Ok(())
+        }
+
+        async fn container_status(&self, _rental: &Rental) -> Result<Option<String>> {
+            Ok(Some(CONTAINER_RUNNING.to_string()))
+        }
+    }
+
+    async fn persistence() -> Arc<SimplePersistence> {
+        Arc::new(
+            SimplePersistence::new(":memory:", "test_validator".to_string())
+                .await
+                .unwrap(),
+        )
+    }
+
+    fn rental(ssh_access_info: serde_json::Value) -> Rental {
+        Rental::new(
+            "miner1__exec1".to_string(),
+            "ssh-ed25519 AAAA".to_string(),
+            "ubuntu:22.04".to_string(),
+            None,
+            serde_json::json!({}),
+            ssh_access_info,
+            2,
+            1.5,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_provisioning_timeout_tears_down_container() {
+        let persistence = persistence().await;
+        let mut stale = rental(serde_json::json!({ "container_id": "container-1" }));
+        stale.begin_provisioning().unwrap();
+        stale.updated_at = Utc::now() - chrono::Duration::hours(2);
+        persistence.create_rental(&stale).await.unwrap();
+
+        let recent = {
+            let mut rental = rental(serde_json::json!({}));
+            rental.begin_provisioning().unwrap();
+            persistence.create_rental(&rental).await.unwrap();
+            rental
+        };
+
+        // Teardown failures are logged and do not keep the rental provisioning
+        let containers = Arc::new(FakeContainers {
+            fail_teardown: true,
+            ..Default::default()
+        });
+        let supervisor = RentalSupervisor::new(
+            RentalConfig::default(),
+            persistence.clone(),
+            containers.clone(),
+        );
+        supervisor.run_once().await.unwrap();
+
+        assert_eq!(
+            *containers.torn_down.lock().unwrap(),
+            vec![(stale.id, Some("container-1".to_string()))]
+        );
+
+        let stale = persistence.get_rental(&stale.id).await.unwrap().unwrap();
+        assert_eq!(stale.status, RentalStatus::Failed);
+        assert_eq!(
+            stale.termination_reason.as_deref(),
+            Some("provisioning_timeout")
+        );
+
+        let recent = persistence.get_rental(&recent.id).await.unwrap().unwrap();
+        assert_eq!(recent.status, RentalStatus::Provisioning);
+    }
+
+    #[tokio::test]
+    async fn test_container_started_after_timeout_is_torn_down() {
+        let persistence = persistence().await;
+        let mut pending = rental(serde_json::json!({}));
+        persistence.create_rental(&pending).await.unwrap();
+
+        let containers = Arc::new(FakeContainers {
+            persistence: Some(persistence.clone()),
+            ..Default::default()
+        });
+        let supervisor = RentalSupervisor::new(
+            RentalConfig::default(),
+            persistence.clone(),
+            containers.clone(),
+        );
+
+        assert!(supervisor.start_rental(&mut pending).await.is_err());
+        assert_eq!(
+            *containers.torn_down.lock().unwrap(),
+            vec![(pending.id, Some("container-1".to_string()))]
+        );
+
+        let stored = persistence.get_rental(&pending.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, RentalStatus::Failed);
+    }
+}
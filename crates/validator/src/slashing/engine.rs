@@ -0,0 +1,313 @@
+//! Slashing engine task
+//!
+//! Periodically scans verification history for offenses, opens a case with an
+//! evidence bundle for each new one and submits approved cases on chain.
+
+use super::evidence::EvidenceBundle;
+use super::offenses::{detect_attestation_failures, detect_gpu_uuid_reuse, DetectedOffense};
+use super::{parse_unique_executor_id, SlashingConfig, SlashingMode};
+use crate::persistence::slashing_repository::{
+    SlashingCase, SlashingCaseStatus, SlashingRepository,
+};
+use crate::persistence::SimplePersistence;
+use anyhow::{anyhow, Result};
+use chrono::{Duration as ChronoDuration, Utc};
+use collateral::CollateralClient;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Number of approved cases submitted per pass
+const SUBMISSION_BATCH_SIZE: u32 = 50;
+
+/// Detects slashable offenses and drives cases through review and submission
+pub struct SlashingEngine {
+    config: SlashingConfig,
+    persistence: Arc<SimplePersistence>,
+    repository: SlashingRepository,
+    client: Option<CollateralClient>,
+    validator_hotkey: String,
+}
+
+impl SlashingEngine {
+    /// Create a new slashing engine
+    ///
+    /// Without a collateral client cases are still detected and recorded, but
+    /// approved cases stay approved until a client is configured.
+    pub fn new(
+        config: SlashingConfig,
+        persistence: Arc<SimplePersistence>,
+        client: Option<CollateralClient>,
+        validator_hotkey: String,
+    ) -> Self {
+        let repository = SlashingRepository::new(persistence.pool().clone());
+        Self {
+            config,
+            persistence,
+            repository,
+            client,
+            validator_hotkey,
+        }
+    }
+
+    /// Start the slashing loop
+    pub async fn start(&self) -> Result<()> {
+        if !self.config.enabled {
+            info!("Slashing engine is disabled");
+            return Ok(());
+        }
+
+        info!(
+            "Starting slashing engine in {:?} mode - scanning every {} seconds",
+            self.config.mode, self.config.scan_interval_secs
+        );
+
+        let mut interval = interval(Duration::from_secs(self.config.scan_interval_secs.max(1)));
+
+        loop {
+            interval.tick().await;
+
+            match self.run_once().await {
+                Ok(0) => {}
+                Ok(opened) => info!("Opened {} slashing cases", opened),
+                Err(e) => error!("Slashing scan failed: {}", e),
+            }
+
+            if self.config.mode != SlashingMode::DryRun {
+                if let Err(e) = self.submit_approved().await {
+                    error!("Failed to submit approved slashing cases: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Scan for offenses and open a case for each new one, returning the number opened
+    pub async fn run_once(&self) -> Result<usize> {
+        let since = Utc::now() - ChronoDuration::hours(self.config.lookback_hours);
+
+        let logs = self.persistence.get_verification_logs_since(since).await?;
+        let conflicts = self.persistence.get_gpu_uuid_conflicts_since(since).await?;
+
+        let mut offenses = detect_attestation_failures(&self.config, &logs);
+        offenses.extend(detect_gpu_uuid_reuse(&self.config, &conflicts));
+
+        let cooldown_start = Utc::now() - ChronoDuration::hours(self.config.cooldown_hours);
+        let mut opened = 0;
+        for offense in offenses {
+            if self
+                .repository
+                .has_recent_case(&offense.executor_id, offense.kind.as_str(), cooldown_start)
+                .await?
+            {
+                continue;
+            }
+
+            self.open_case(offense).await?;
+            opened += 1;
+        }
+
+        Ok(opened)
+    }
+
+    /// Write the evidence bundle and record the case
+    async fn open_case(&self, offense: DetectedOffense) -> Result<SlashingCase> {
+        let case_id = Uuid::new_v4();
+        let executor_id = offense.executor_id.clone();
+        let miner_uid = offense.miner_uid;
+        let kind = offense.kind;
+        let summary = offense.summary.clone();
+
+        let evidence = EvidenceBundle::new(case_id, self.validator_hotkey.clone(), offense)
+            .store(&self.config.evidence_dir, &self.config.evidence_base_url)
+            .await?;
+
+        let status = match self.config.mode {
+            SlashingMode::DryRun => SlashingCaseStatus::DryRun,
+            SlashingMode::Approval => SlashingCaseStatus::PendingApproval,
+            SlashingMode::Automatic => SlashingCaseStatus::Approved,
+        };
+
+        let now = Utc::now();
+        let case = SlashingCase {
+            id: case_id,
+            executor_id,
+            miner_uid,
+            offense: kind.as_str().to_string(),
+            status,
+            evidence_path: evidence.path.display().to_string(),
+            evidence_url: evidence.url.clone(),
+            evidence_checksum: evidence.checksum_hex(),
+            error_message: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.repository.create_case(&case).await?;
+
+        warn!(
+            case_id = %case.id,
+            executor_id = %case.executor_id,
+            miner_uid = case.miner_uid,
+            offense = %case.offense,
+            status = case.status.as_str(),
+            "Slashable offense detected: {}",
+            summary
+        );
+
+        Ok(case)
+    }
+
+    /// Submit slash transactions for approved cases
+    pub async fn submit_approved(&self) -> Result<()> {
+        let cases = self
+            .repository
+            .list_cases(Some(SlashingCaseStatus::Approved), SUBMISSION_BATCH_SIZE)
+            .await?;
+        if cases.is_empty() {
+            return Ok(());
+        }
+
+        let Some(client) = &self.client else {
+            warn!(
+                "{} approved slashing cases waiting, but no collateral contract is configured",
+                cases.len()
+            );
+            return Ok(());
+        };
+
+        for case in cases {
+            let (status, error_message) = match self.submit_case(client, &case).await {
+                Ok(tx_hash) => {
+                    info!(
+                        case_id = %case.id,
+                        executor_id = %case.executor_id,
+                        tx_hash = %tx_hash,
+                        "Slashed collateral for executor"
+                    );
+                    (SlashingCaseStatus::Submitted, None)
+                }
+                Err(e) => {
+                    error!(
+                        case_id = %case.id,
+                        executor_id = %case.executor_id,
+                        "Failed to slash collateral: {}",
+                        e
+                    );
+                    (SlashingCaseStatus::Failed, Some(e.to_string()))
+                }
+            };
+
+            self.repository
+                .transition(
+                    &case.id,
+                    SlashingCaseStatus::Approved,
+                    status,
+                    error_message.as_deref(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn submit_case(&self, client: &CollateralClient, case: &SlashingCase) -> Result<String> {
+        let (_, executor_id) = parse_unique_executor_id(&case.executor_id)
+            .ok_or_else(|| anyhow!("Invalid executor id: {}", case.executor_id))?;
+        let onchain_id = collateral::executor_id_from_str(executor_id);
+
+        if client.collaterals(onchain_id).await?.is_zero() {
+            return Err(anyhow!("Executor has no collateral posted"));
+        }
+
+        let checksum = u128::from_str_radix(&case.evidence_checksum, 16)?;
+        let tx_hash = client
+            .slash_collateral(onchain_id, &case.evidence_url, checksum)
+            .await?;
+        Ok(tx_hash.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::entities::VerificationLog;
+    use tempfile::TempDir;
+
+    async fn engine(mode: SlashingMode, dir: &TempDir) -> SlashingEngine {
+        let persistence = Arc::new(
+            SimplePersistence::new(":memory:", "test_validator".to_string())
+                .await
+                .unwrap(),
+        );
+        let config = SlashingConfig {
+            enabled: true,
+            mode,
+            attestation_failure_threshold: 2,
+            evidence_dir: dir.path().to_path_buf(),
+            evidence_base_url: "https://validator.example.com/slashing/evidence".to_string(),
+            ..Default::default()
+        };
+        SlashingEngine::new(config, persistence, None, "validator".to_string())
+    }
+
+    async fn record_attestation_failure(engine: &SlashingEngine, executor_id: &str) {
+        let log = VerificationLog::new(
+            executor_id.to_string(),
+            "validator".to_string(),
+            "ssh_automation".to_string(),
+            0.0,
+            false,
+            serde_json::json!({
+                "ssh_connection_successful": true,
+                "binary_validation_successful": false,
+            }),
+            10,
+            Some("Binary validation failed".to_string()),
+        );
+        engine
+            .persistence
+            .create_verification_log(&log)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cases_are_opened_once_per_cooldown() {
+        let dir = TempDir::new().unwrap();
+        let engine = engine(SlashingMode::Approval, &dir).await;
+
+        record_attestation_failure(&engine, "miner7__exec").await;
+        assert_eq!(engine.run_once().await.unwrap(), 0);
+
+        record_attestation_failure(&engine, "miner7__exec").await;
+        assert_eq!(engine.run_once().await.unwrap(), 1);
+        assert_eq!(engine.run_once().await.unwrap(), 0);
+
+        let cases = engine
+            .repository
+            .list_cases(Some(SlashingCaseStatus::PendingApproval), 10)
+            .await
+            .unwrap();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].miner_uid, 7);
+        assert!(std::path::Path::new(&cases[0].evidence_path).exists());
+        assert!(cases[0]
+            .evidence_url
+            .ends_with(&format!("{}.json", cases[0].id)));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_cases_are_never_submitted() {
+        let dir = TempDir::new().unwrap();
+        let engine = engine(SlashingMode::DryRun, &dir).await;
+
+        record_attestation_failure(&engine, "miner3__exec").await;
+        record_attestation_failure(&engine, "miner3__exec").await;
+        assert_eq!(engine.run_once().await.unwrap(), 1);
+        engine.submit_approved().await.unwrap();
+
+        let cases = engine.repository.list_cases(None, 10).await.unwrap();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].status, SlashingCaseStatus::DryRun);
+    }
+}
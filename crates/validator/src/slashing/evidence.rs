@@ -0,0 +1,132 @@
+//! Evidence bundles backing slashing cases
+//!
+//! The bundle is written once as JSON and served verbatim, so the MD5 checksum
+//! recorded on chain always matches what is fetched from the evidence URL.
+
+use super::offenses::DetectedOffense;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use md5::{Digest, Md5};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Everything a reviewer needs to check a slashing case
+#[derive(Debug, Clone, Serialize)]
+pub struct EvidenceBundle {
+    pub case_id: Uuid,
+    pub validator_hotkey: String,
+    pub created_at: DateTime<Utc>,
+    pub offense: DetectedOffense,
+}
+
+/// An evidence bundle persisted to disk
+#[derive(Debug, Clone)]
+pub struct StoredEvidence {
+    pub path: PathBuf,
+    pub url: String,
+    pub checksum: u128,
+}
+
+impl StoredEvidence {
+    /// Hex-encoded checksum as stored in the database
+    pub fn checksum_hex(&self) -> String {
+        format!("{:032x}", self.checksum)
+    }
+}
+
+/// File name of the evidence bundle for a case
+pub fn evidence_file_name(case_id: &Uuid) -> String {
+    format!("{case_id}.json")
+}
+
+/// MD5 checksum of an evidence bundle as the contract's bytes16
+pub fn evidence_checksum(bytes: &[u8]) -> u128 {
+    let digest = Md5::digest(bytes);
+    u128::from_be_bytes(
+        digest
+            .as_slice()
+            .try_into()
+            .expect("MD5 digest is 16 bytes"),
+    )
+}
+
+impl EvidenceBundle {
+    pub fn new(case_id: Uuid, validator_hotkey: String, offense: DetectedOffense) -> Self {
+        Self {
+            case_id,
+            validator_hotkey,
+            created_at: Utc::now(),
+            offense,
+        }
+    }
+
+    /// Write the bundle to `dir` and compute its public URL and checksum
+    pub async fn store(&self, dir: &Path, base_url: &str) -> Result<StoredEvidence> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Failed to create evidence directory {}", dir.display()))?;
+
+        let file_name = evidence_file_name(&self.case_id);
+        let path = dir.join(&file_name);
+        tokio::fs::write(&path, &bytes)
+            .await
+            .with_context(|| format!("Failed to write evidence bundle {}", path.display()))?;
+
+        Ok(StoredEvidence {
+            path,
+            url: format!("{}/{}", base_url.trim_end_matches('/'), file_name),
+            checksum: evidence_checksum(&bytes),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slashing::offenses::OffenseKind;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_store_evidence_bundle() {
+        let dir = TempDir::new().unwrap();
+        let case_id = Uuid::new_v4();
+        let bundle = EvidenceBundle::new(
+            case_id,
+            "validator".to_string(),
+            DetectedOffense {
+                kind: OffenseKind::AttestationFailure,
+                executor_id: "miner1__exec".to_string(),
+                miner_uid: 1,
+                summary: "test".to_string(),
+                records: serde_json::json!([]),
+            },
+        );
+
+        let stored = bundle
+            .store(
+                dir.path(),
+                "https://validator.example.com/slashing/evidence/",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stored.url,
+            format!("https://validator.example.com/slashing/evidence/{case_id}.json")
+        );
+        let bytes = std::fs::read(&stored.path).unwrap();
+        assert_eq!(stored.checksum, evidence_checksum(&bytes));
+        assert_eq!(stored.checksum_hex().len(), 32);
+    }
+
+    #[test]
+    fn test_evidence_checksum_is_md5() {
+        assert_eq!(
+            format!("{:032x}", evidence_checksum(b"")),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! # Slashing
+//!
+//! Policy engine that turns verification history into collateral slashes.
+//! Offenses are detected from `verification_logs` and `gpu_uuid_conflicts`,
+//! each one is written to an evidence bundle whose URL and MD5 checksum are
+//! recorded on chain, and cases go through dry-run or operator approval
+//! before the slash transaction is submitted.
+
+pub mod engine;
+pub mod evidence;
+pub mod offenses;
+
+pub use engine::SlashingEngine;
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// How detected offenses are handled
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingMode {
+    /// Record cases and evidence, never submit
+    #[default]
+    DryRun,
+
+    /// Submit only cases approved by an operator through the API
+    Approval,
+
+    /// Submit every case as soon as it is detected
+    Automatic,
+}
+
+/// Configuration for the slashing engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlashingConfig {
+    /// Whether the slashing engine runs
+    pub enabled: bool,
+
+    /// How detected offenses are handled
+    pub mode: SlashingMode,
+
+    /// Seconds between offense scans
+    pub scan_interval_secs: u64,
+
+    /// How far back verification history is considered, in hours
+    pub lookback_hours: i64,
+
+    /// Consecutive failed hardware attestations on a reachable executor before it is slashable
+    pub attestation_failure_threshold: u32,
+
+    /// Times a GPU UUID must move between miners before its claimants are slashable
+    pub gpu_reuse_threshold: u32,
+
+    /// Hours before the same offense can be raised again for an executor
+    pub cooldown_hours: i64,
+
+    /// Directory where evidence bundles are written
+    pub evidence_dir: PathBuf,
+
+    /// Public base URL under which evidence bundles are served
+    /// e.g. "https://validator.example.com/slashing/evidence"
+    pub evidence_base_url: String,
+}
+
+impl Default for SlashingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: SlashingMode::DryRun,
+            scan_interval_secs: 600,
+            lookback_hours: 24,
+            attestation_failure_threshold: 5,
+            gpu_reuse_threshold: 2,
+            cooldown_hours: 72,
+            evidence_dir: PathBuf::from("./slashing_evidence"),
+            evidence_base_url: String::new(),
+        }
+    }
+}
+
+/// Split a unique executor id (`miner{uid}__{executor_id}`) into miner UID and executor id
+pub fn parse_unique_executor_id(unique_executor_id: &str) -> Option<(u16, &str)> {
+    let (miner, executor_id) = unique_executor_id.split_once("__")?;
+    let miner_uid = miner.strip_prefix("miner")?.parse().ok()?;
+    Some((miner_uid, executor_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unique_executor_id() {
+        assert_eq!(
+            parse_unique_executor_id("miner12__exec-1"),
+            Some((12, "exec-1"))
+        );
+        assert_eq!(parse_unique_executor_id("exec-1"), None);
+        assert_eq!(parse_unique_executor_id("minerX__exec-1"), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! Detection of slashable offenses from verification history
+
+use super::{parse_unique_executor_id, SlashingConfig};
+use crate::persistence::entities::VerificationLog;
+use crate::persistence::GpuUuidConflict;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Kind of slashable offense
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OffenseKind {
+    /// Executor was reachable but repeatedly failed hardware attestation
+    AttestationFailure,
+
+    /// Executor claimed a GPU that keeps moving between miners
+    GpuUuidReuse,
+}
+
+impl OffenseKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OffenseKind::AttestationFailure => "attestation_failure",
+            OffenseKind::GpuUuidReuse => "gpu_uuid_reuse",
+        }
+    }
+}
+
+/// A slashable offense together with the records that prove it
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedOffense {
+    pub kind: OffenseKind,
+    /// Unique executor id (`miner{uid}__{executor_id}`)
+    pub executor_id: String,
+    pub miner_uid: u16,
+    pub summary: String,
+    pub records: serde_json::Value,
+}
+
+/// Whether a verification log is a failed attestation on a reachable executor
+///
+/// Connection failures are not slashable, so only logs where SSH succeeded
+/// but the attestation binary did not are counted.
+fn is_attestation_failure(log: &VerificationLog) -> bool {
+    !log.success
+        && log
+            .details
+            .get("ssh_connection_successful")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        && !log
+            .details
+            .get("binary_validation_successful")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true)
+}
+
+/// Find executors whose most recent verifications are all attestation failures
+///
+/// `logs` must be ordered by executor and newest first, as returned by
+/// `SimplePersistence::get_verification_logs_since`.
+pub fn detect_attestation_failures(
+    config: &SlashingConfig,
+    logs: &[VerificationLog],
+) -> Vec<DetectedOffense> {
+    let threshold = config.attestation_failure_threshold.max(1) as usize;
+
+    let mut by_executor: BTreeMap<&str, Vec<&VerificationLog>> = BTreeMap::new();
+    for log in logs {
+        by_executor.entry(&log.executor_id).or_default().push(log);
+    }
+
+    by_executor
+        .into_iter()
+        .filter_map(|(executor_id, logs)| {
+            let (miner_uid, _) = parse_unique_executor_id(executor_id)?;
+            let failures: Vec<&VerificationLog> = logs
+                .into_iter()
+                .take_while(|log| is_attestation_failure(log))
+                .collect();
+            if failures.len() < threshold {
+                return None;
+            }
+
+            Some(DetectedOffense {
+                kind: OffenseKind::AttestationFailure,
+                executor_id: executor_id.to_string(),
+                miner_uid,
+                summary: format!(
+                    "{} consecutive failed hardware attestations",
+                    failures.len()
+                ),
+                records: serde_json::to_value(&failures).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Find executors that claimed a GPU UUID which moved between miners too often
+pub fn detect_gpu_uuid_reuse(
+    config: &SlashingConfig,
+    conflicts: &[GpuUuidConflict],
+) -> Vec<DetectedOffense> {
+    let threshold = config.gpu_reuse_threshold.max(1) as usize;
+
+    let mut by_gpu: BTreeMap<&str, Vec<&GpuUuidConflict>> = BTreeMap::new();
+    for conflict in conflicts {
+        by_gpu.entry(&conflict.gpu_uuid).or_default().push(conflict);
+    }
+
+    // An executor may claim several reused GPUs; report it once with all records
+    let mut by_executor: BTreeMap<&str, Vec<&GpuUuidConflict>> = BTreeMap::new();
+    for conflicts in by_gpu.values().filter(|c| c.len() >= threshold) {
+        let claimants: BTreeSet<&str> = conflicts.iter().map(|c| c.executor_id.as_str()).collect();
+        for claimant in claimants {
+            by_executor
+                .entry(claimant)
+                .or_default()
+                .extend(conflicts.iter().copied());
+        }
+    }
+
+    by_executor
+        .into_iter()
+        .filter_map(|(executor_id, records)| {
+            let (miner_uid, _) = parse_unique_executor_id(executor_id)?;
+            let gpus: BTreeSet<&str> = records.iter().map(|c| c.gpu_uuid.as_str()).collect();
+
+            Some(DetectedOffense {
+                kind: OffenseKind::GpuUuidReuse,
+                executor_id: executor_id.to_string(),
+                miner_uid,
+                summary: format!(
+                    "{} GPU UUID(s) also claimed by other miners: {}",
+                    gpus.len(),
+                    gpus.into_iter().collect::<Vec<_>>().join(", ")
+                ),
+                records: serde_json::to_value(&records).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn log(executor_id: &str, success: bool, ssh_ok: bool) -> VerificationLog {
+        VerificationLog::new(
+            executor_id.to_string(),
+            "validator".to_string(),
+            "ssh_automation".to_string(),
+            0.0,
+            success,
+            serde_json::json!({
+                "ssh_connection_successful": ssh_ok,
+                "binary_validation_successful": success,
+            }),
+            10,
+            None,
+        )
+    }
+
+    fn conflict(gpu_uuid: &str, from: u16, to: u16) -> GpuUuidConflict {
+        GpuUuidConflict {
+            gpu_uuid: gpu_uuid.to_string(),
+            previous_miner_id: format!("miner_{from}"),
+            previous_executor_id: format!("miner{from}__exec"),
+            miner_id: format!("miner_{to}"),
+            executor_id: format!("miner{to}__exec"),
+            detected_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_attestation_failures_must_be_consecutive_and_reachable() {
+        let config = SlashingConfig {
+            attestation_failure_threshold: 2,
+            ..Default::default()
+        };
+        let logs = vec![
+            // Newest first: two attestation failures
+            log("miner1__a", false, true),
+            log("miner1__a", false, true),
+            log("miner1__a", true, true),
+            // Failure, success, failure: not consecutive
+            log("miner2__b", false, true),
+            log("miner2__b", true, true),
+            log("miner2__b", false, true),
+            // Unreachable executor is not slashable
+            log("miner3__c", false, false),
+            log("miner3__c", false, false),
+        ];
+
+        let offenses = detect_attestation_failures(&config, &logs);
+        assert_eq!(offenses.len(), 1);
+        assert_eq!(offenses[0].executor_id, "miner1__a");
+        assert_eq!(offenses[0].miner_uid, 1);
+        assert_eq!(offenses[0].kind, OffenseKind::AttestationFailure);
+        assert_eq!(offenses[0].records.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_gpu_uuid_reuse_flags_all_claimants() {
+        let config = SlashingConfig::default();
+        let conflicts = vec![
+            conflict("GPU-1", 1, 2),
+            conflict("GPU-1", 2, 1),
+            // Moved once, below threshold
+            conflict("GPU-2", 3, 4),
+        ];
+
+        let offenses = detect_gpu_uuid_reuse(&config, &conflicts);
+        let executors: Vec<&str> = offenses.iter().map(|o| o.executor_id.as_str()).collect();
+        assert_eq!(executors, vec!["miner1__exec", "miner2__exec"]);
+        assert!(offenses.iter().all(|o| o.summary.contains("GPU-1")));
+    }
+}